@@ -0,0 +1,16 @@
+//! Compiles the gRPC service definitions of the `grpc` feature.
+
+fn main() {
+    println!("cargo:rerun-if-changed=proto");
+
+    #[cfg(feature = "grpc")]
+    {
+        // No system protoc needed
+        let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc");
+        std::env::set_var("PROTOC", protoc);
+        tonic_build::configure()
+            .build_client(false)
+            .compile_protos(&["proto/raydium_alerts.proto"], &["proto"])
+            .expect("failed to compile proto/raydium_alerts.proto");
+    }
+}
@@ -0,0 +1,382 @@
+//! Rolling per-pool volume aggregation and periodic summaries.
+//!
+//! This module provides:
+//! - [`AggregationConfig`] - Summary interval, pool limit, and volume threshold
+//! - [`VolumeAggregator`] - Rolling 1m/5m/1h volume, trade count, and unique makers per pool
+//!
+//! The aggregator is fed by the processors with every emitted swap. Swaps are
+//! kept in 10-second buckets per pool, so the windows advance in 10-second
+//! steps. Every interval, [`VolumeAggregator::spawn_summaries`] emits a
+//! `VolumeSummary` event for each of the busiest pools traded since the
+//! previous summary; the events are logged and queued for the webhooks like
+//! any other alert.
+
+use {
+    crate::{
+        enrichment::PriceEnricher,
+        output::{
+            EventType, OutputFormat, Protocol, SwapEvent, VolumeSummary, WebhookNotifier,
+            WindowVolume,
+        },
+        server::unix_now,
+    },
+    std::{
+        collections::{HashMap, HashSet},
+        env,
+        sync::{Arc, Mutex, PoisonError},
+        time::Duration,
+    },
+};
+
+/// Seconds covered by one bucket.
+const BUCKET_SECS: i64 = 10;
+
+/// Number of buckets per pool (1h window).
+const BUCKETS: usize = 360;
+
+/// Rolling windows with their length in seconds, shortest first.
+const WINDOWS: [(&str, i64); 3] = [("1m", 60), ("5m", 300), ("1h", 3600)];
+
+/// Default number of pools summarized per interval.
+const DEFAULT_TOP_POOLS: usize = 10;
+
+/// Configuration for periodic volume summaries.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AggregationConfig {
+    /// Time between summaries
+    pub interval: Duration,
+    /// Most pools summarized per interval, busiest (5m volume) first
+    pub top_pools: usize,
+    /// Minimum 5m USD volume for a pool to be summarized
+    pub min_volume_usd: f64,
+}
+
+impl AggregationConfig {
+    /// Creates aggregation configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `AGGREGATION_INTERVAL_SECS` - Seconds between volume summaries (e.g., `60`)
+    /// - `AGGREGATION_TOP_POOLS` - Optional: Most pools summarized per interval (default: 10)
+    /// - `AGGREGATION_MIN_USD` - Optional: Minimum 5m USD volume of a summarized pool (default: 0)
+    ///
+    /// # Returns
+    ///
+    /// `Some(AggregationConfig)` if `AGGREGATION_INTERVAL_SECS` is set and positive, `None` otherwise.
+    pub fn from_env() -> Option<Self> {
+        let value = env::var("AGGREGATION_INTERVAL_SECS").ok()?;
+        let interval_secs = match value.trim().parse::<u64>() {
+            Ok(secs) => secs,
+            Err(_) => {
+                log::warn!(
+                    "Invalid AGGREGATION_INTERVAL_SECS '{value}', volume summaries disabled"
+                );
+                return None;
+            }
+        };
+        let top_pools = env::var("AGGREGATION_TOP_POOLS")
+            .ok()
+            .and_then(|n| n.trim().parse().ok())
+            .unwrap_or(DEFAULT_TOP_POOLS);
+        let min_volume_usd = env::var("AGGREGATION_MIN_USD")
+            .ok()
+            .and_then(|usd| usd.trim().parse().ok())
+            .unwrap_or(0.0);
+        (interval_secs > 0).then(|| Self {
+            interval: Duration::from_secs(interval_secs),
+            top_pools,
+            min_volume_usd,
+        })
+    }
+}
+
+/// Swaps of one pool within one bucket.
+#[derive(Debug, Clone, Default)]
+struct Bucket {
+    /// Buckets since the Unix epoch
+    index: i64,
+    usd: f64,
+    trades: u64,
+    makers: HashSet<String>,
+}
+
+/// Rolling volume of one pool.
+#[derive(Debug)]
+struct PoolEntry {
+    protocol: Protocol,
+    buckets: Vec<Bucket>,
+    /// Timestamp of the latest swap
+    last_seen: i64,
+    /// Slot of the latest swap
+    last_slot: u64,
+    /// Whether the pool was traded since the previous summary
+    traded: bool,
+}
+
+impl PoolEntry {
+    fn new(protocol: Protocol) -> Self {
+        Self {
+            protocol,
+            buckets: vec![Bucket::default(); BUCKETS],
+            last_seen: 0,
+            last_slot: 0,
+            traded: false,
+        }
+    }
+
+    fn add(&mut self, timestamp: i64, usd: Option<f64>, maker: Option<&str>) {
+        let index = timestamp.div_euclid(BUCKET_SECS);
+        let bucket = &mut self.buckets[index.rem_euclid(BUCKETS as i64) as usize];
+        if bucket.index != index {
+            *bucket = Bucket {
+                index,
+                ..Default::default()
+            };
+        }
+        bucket.usd += usd.unwrap_or_default();
+        bucket.trades += 1;
+        if let Some(maker) = maker {
+            bucket.makers.insert(maker.to_string());
+        }
+    }
+
+    /// Returns the volume of the window of `secs` seconds ending at `now`.
+    fn window(&self, name: &str, secs: i64, now: i64) -> WindowVolume {
+        let current = now.div_euclid(BUCKET_SECS);
+        let span = secs / BUCKET_SECS;
+        let mut makers = HashSet::new();
+        let mut volume = WindowVolume {
+            window: name.to_string(),
+            volume_usd: 0.0,
+            trades: 0,
+            unique_makers: 0,
+        };
+        for bucket in self
+            .buckets
+            .iter()
+            .filter(|b| b.trades > 0 && (0..span).contains(&(current - b.index)))
+        {
+            volume.volume_usd += bucket.usd;
+            volume.trades += bucket.trades;
+            makers.extend(bucket.makers.iter());
+        }
+        volume.unique_makers = makers.len() as u64;
+        volume
+    }
+
+    fn summary(&self, now: i64) -> VolumeSummary {
+        VolumeSummary {
+            windows: WINDOWS
+                .iter()
+                .map(|(name, secs)| self.window(name, *secs, now))
+                .collect(),
+        }
+    }
+}
+
+/// Rolling per-pool volume of emitted swaps.
+#[derive(Default)]
+pub struct VolumeAggregator {
+    pools: Mutex<HashMap<String, PoolEntry>>,
+    /// Values swaps without a USD value at trade time
+    prices: Option<Arc<PriceEnricher>>,
+}
+
+impl VolumeAggregator {
+    /// Creates an empty aggregator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Values swaps without a USD value at the price service's current
+    /// prices, so pools quoted in SOL and in stablecoins rank alike.
+    pub fn with_prices(mut self, prices: Arc<PriceEnricher>) -> Self {
+        self.prices = Some(prices);
+        self
+    }
+
+    /// Records an emitted event; only swaps are aggregated.
+    pub fn record(&self, event: &SwapEvent) {
+        if event.event_type != EventType::Swap {
+            return;
+        }
+        let timestamp = event.timestamp.unwrap_or_else(unix_now);
+        let mut pools = self.pools.lock().unwrap_or_else(PoisonError::into_inner);
+        let entry = pools
+            .entry(event.pool.clone())
+            .or_insert_with(|| PoolEntry::new(event.protocol));
+        let usd = match self.prices {
+            Some(ref prices) => prices.swap_value_usd(event),
+            None => event.usd_value(),
+        };
+        entry.add(timestamp, usd, event.maker.as_deref());
+        entry.last_seen = entry.last_seen.max(timestamp);
+        entry.last_slot = entry.last_slot.max(event.slot);
+        entry.traded = true;
+    }
+
+    /// Builds the summaries of pools traded since the previous call, busiest
+    /// (5m volume) first.
+    ///
+    /// Pools below `config.min_volume_usd` are skipped, and pools without swaps
+    /// in the last hour are forgotten.
+    pub fn summarize(&self, config: &AggregationConfig, now: i64) -> Vec<SwapEvent> {
+        let mut pools = self.pools.lock().unwrap_or_else(PoisonError::into_inner);
+        let (_, longest) = WINDOWS[WINDOWS.len() - 1];
+        pools.retain(|_, entry| now - entry.last_seen < longest);
+
+        let mut summaries: Vec<(f64, SwapEvent)> = pools
+            .iter_mut()
+            .filter(|(_, entry)| std::mem::take(&mut entry.traded))
+            .filter_map(|(pool, entry)| {
+                let summary = entry.summary(now);
+                let ranking = summary.window("5m").map_or(0.0, |w| w.volume_usd);
+                (ranking >= config.min_volume_usd).then(|| {
+                    let event = SwapEvent::from_volume_summary(
+                        entry.protocol,
+                        pool.clone(),
+                        entry.last_slot,
+                        now,
+                        summary,
+                    );
+                    (ranking, event)
+                })
+            })
+            .collect();
+        summaries.sort_by(|(a, a_event), (b, b_event)| {
+            b.total_cmp(a).then_with(|| a_event.pool.cmp(&b_event.pool))
+        });
+        summaries
+            .into_iter()
+            .take(config.top_pools)
+            .map(|(_, event)| event)
+            .collect()
+    }
+
+    /// Spawns the task emitting summaries every `config.interval`.
+    ///
+    /// Summaries are logged in `output_format` and queued for the webhooks.
+    pub fn spawn_summaries(
+        self: Arc<Self>,
+        config: AggregationConfig,
+        output_format: OutputFormat,
+        webhook_notifier: Option<Arc<WebhookNotifier>>,
+    ) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(config.interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            // The first tick completes immediately
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                for event in self.summarize(&config, unix_now()) {
+                    log::info!("{}", event.format(output_format));
+                    if let Some(ref notifier) = webhook_notifier {
+                        if let Err(e) = notifier.try_send(event) {
+                            log::warn!("Failed to queue volume summary: {e}");
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::output::TokenInfo};
+
+    fn swap(pool: &str, maker: &str, usd: f64, timestamp: i64) -> SwapEvent {
+        SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature(format!("sig-{timestamp}"))
+            .pool(pool)
+            .input_token(TokenInfo::new("mint-a", 1).with_usd_value(usd))
+            .output_token(TokenInfo::new("mint-b", 1).with_usd_value(usd))
+            .maker(maker)
+            .slot(timestamp as u64)
+            .timestamp(timestamp)
+            .build()
+    }
+
+    fn config(top_pools: usize, min_volume_usd: f64) -> AggregationConfig {
+        AggregationConfig {
+            interval: Duration::from_secs(60),
+            top_pools,
+            min_volume_usd,
+        }
+    }
+
+    #[test]
+    fn test_rolling_windows() {
+        let aggregator = VolumeAggregator::new();
+        let now = 1_700_000_000;
+        aggregator.record(&swap("pool", "alice", 100.0, now - 30));
+        aggregator.record(&swap("pool", "bob", 50.0, now - 10));
+        aggregator.record(&swap("pool", "alice", 20.0, now - 200));
+        aggregator.record(&swap("pool", "carol", 5.0, now - 1800));
+        aggregator.record(&swap("pool", "dave", 1.0, now - 7200));
+
+        let summaries = aggregator.summarize(&config(10, 0.0), now);
+        assert_eq!(summaries.len(), 1);
+        let volume = summaries[0].volume.as_ref().unwrap();
+        let windows: Vec<_> = volume
+            .windows
+            .iter()
+            .map(|w| (w.window.as_str(), w.volume_usd, w.trades, w.unique_makers))
+            .collect();
+        assert_eq!(
+            windows,
+            [
+                ("1m", 150.0, 2, 2),
+                ("5m", 170.0, 3, 2),
+                ("1h", 175.0, 4, 3)
+            ]
+        );
+        assert_eq!(summaries[0].slot, (now - 10) as u64);
+    }
+
+    #[test]
+    fn test_summaries_rank_and_filter_pools() {
+        let aggregator = VolumeAggregator::new();
+        let now = 1_700_000_000;
+        let pools = |summaries: Vec<SwapEvent>| -> Vec<String> {
+            summaries.into_iter().map(|event| event.pool).collect()
+        };
+        aggregator.record(&swap("stale", "dave", 5000.0, now - 120));
+        assert_eq!(
+            pools(aggregator.summarize(&config(10, 0.0), now - 60)),
+            ["stale"]
+        );
+
+        // `stale` was not traded since the previous summary
+        let trade = |pool: &str, usd: f64| aggregator.record(&swap(pool, "alice", usd, now - 5));
+        for (pool, usd) in [("quiet", 10.0), ("busy", 900.0), ("medium", 300.0)] {
+            trade(pool, usd);
+        }
+        assert_eq!(
+            pools(aggregator.summarize(&config(2, 0.0), now)),
+            ["busy", "medium"]
+        );
+        for (pool, usd) in [("quiet", 10.0), ("busy", 900.0), ("medium", 300.0)] {
+            trade(pool, usd);
+        }
+        assert_eq!(
+            pools(aggregator.summarize(&config(10, 500.0), now)),
+            ["busy", "medium"]
+        );
+    }
+
+    #[test]
+    fn test_forgets_idle_pools_and_ignores_other_events() {
+        let aggregator = VolumeAggregator::new();
+        let now = 1_700_000_000;
+        aggregator.record(&swap("old", "alice", 10.0, now - 4000));
+        let mut deposit = swap("lp", "bob", 10.0, now);
+        deposit.event_type = EventType::AddLiquidity;
+        aggregator.record(&deposit);
+
+        assert!(aggregator.summarize(&config(10, 0.0), now).is_empty());
+        assert!(aggregator.pools.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,566 @@
+//! Alert system wired from environment variables.
+//!
+//! [`run`] is the whole binary: it installs the process-wide settings (chain
+//! profile, clock, number format, templates), runs a subcommand if one was
+//! given, and otherwise builds every configured output, runs the pipeline of
+//! [`PipelineBuilder`] on the block subscription (or a soak recording), and
+//! drains the outputs on shutdown.
+
+use {
+    crate::{
+        aggregation::{AggregationConfig, VolumeAggregator},
+        candles::{CandleBuilder, CandleConfig, CandleService},
+        chain::{self, ChainProfile},
+        cli,
+        clock::ClockConfig,
+        config::{block_filter, parse_market_filter, ConcurrencyConfig, MarketType},
+        drain::{DrainConfig, DrainDetector},
+        enrichment::{
+            AuthorityEnricher, Enricher, EnrichmentCache, EnrichmentConfig, EnrichmentPipeline,
+            EnrichmentPipelineConfig, HolderEnricher, ImpactEnricher, MetadataEnricher,
+            PriceEnricher,
+        },
+        listings::{ListingConfig, ListingTracker},
+        lp_burn::{LpBurnConfig, LpBurnTracker},
+        metrics::{StatsdConfig, StatsdMetrics, SummaryConfig},
+        mev::{MevConfig, MevDetector},
+        output::{
+            parse_output_format, DedupConfig, EventLog, NumberFormat, PushConfig, PushNotifier,
+            SequenceConfig, Sequencer, SlotBatchConfig, SlotBatcher, TextTemplates, WebhookConfig,
+            WebhookNotifier,
+        },
+        pipeline::PipelineBuilder,
+        pool_stats::{PoolStatsConfig, PoolStatsTracker},
+        reload::{ConfigWatcher, LiveConfig, SharedConfig},
+        rules,
+        server::{self, ResponseCache, ServerConfig, StreamHub},
+        service::{self, WatchdogConfig},
+        slo::{SloConfig, SloMonitor},
+        soak::Soak,
+        stats::StatsStore,
+        storage::{self, MigrationMode},
+        subscriptions::{
+            SubscriptionBus, SubscriptionRegistry, SubscriptionsConfig, TelegramBot, TelegramClient,
+        },
+        supervisor::{SupervisedDatasource, SupervisorConfig},
+        telemetry::{Telemetry, TelemetryConfig},
+        throttle::{AlertThrottle, ThrottleConfig},
+    },
+    arc_swap::ArcSwap,
+    carbon_core::{
+        error::CarbonResult,
+        metrics::{Metrics, MetricsCollection},
+    },
+    carbon_rpc_block_subscribe_datasource::{Filters, RpcBlockSubscribe},
+    solana_client::rpc_config::RpcBlockSubscribeConfig,
+    std::{env, path::PathBuf, sync::Arc, time::Duration},
+    tokio::signal,
+};
+
+/// Runs the alert system configured from environment variables: a
+/// subcommand, or the pipeline until SIGINT/SIGTERM, then drains its outputs.
+pub async fn run() -> CarbonResult<()> {
+    // Load the config file; it is reloaded later for filters and rules
+    let config_file = match env::var("CONFIG_FILE") {
+        Ok(path) if !path.trim().is_empty() => {
+            let path = PathBuf::from(path.trim());
+            dotenv::from_path(&path).ok();
+            Some(path)
+        }
+        _ => dotenv::dotenv().ok(),
+    };
+    let telemetry = TelemetryConfig::from_env()
+        .and_then(|config| Telemetry::init(&config))
+        .map_err(carbon_core::error::Error::Custom)?;
+
+    // Select the chain before anything reads its endpoints or program IDs
+    ChainProfile::from_env()
+        .and_then(ChainProfile::install)
+        .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+
+    // Install the clock of the timing features before anything reads the time
+    ClockConfig::from_env().install();
+
+    // Install number formatting and text alert templates before rules reference them
+    NumberFormat::from_env().install();
+    if let Some(templates) =
+        TextTemplates::from_env().map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?
+    {
+        log::info!("Text alert templates: {}", templates.count());
+        templates
+            .install()
+            .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+    }
+
+    // Subcommands (e.g., `archive`) run instead of the pipeline
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let migrations = if cli::take_migrate_flag(&mut args) {
+        MigrationMode::Apply
+    } else {
+        MigrationMode::Check
+    };
+    if let Some(result) = cli::run(&args).await {
+        return result.map_err(carbon_core::error::Error::Custom);
+    }
+
+    // `soak` runs the pipeline set up below on recorded blocks instead of the subscription
+    let mut soak = cli::soak(&args)
+        .map(|config| config.and_then(Soak::load))
+        .transpose()
+        .map_err(carbon_core::error::Error::Custom)?;
+
+    // Report start-up and stop requests to the Windows service control manager
+    service::start();
+
+    let rpc_ws_url = chain::profile().rpc_ws_url.clone();
+
+    // Parse filters from environment variables; markets not deployed on the chain are skipped
+    let filter_markets = chain::profile().deployed(parse_market_filter("FILTER_MARKETS"));
+
+    // Create filter for block subscription; narrowed to the program of a single
+    // selected market, otherwise unselected markets are skipped by not
+    // registering their decoders
+    let subscribe_filter = block_filter(&filter_markets);
+    let output_format = parse_output_format("OUTPUT_FORMAT");
+
+    // Push pipeline metrics to an optional StatsD server or Datadog agent
+    let statsd = match StatsdConfig::from_env() {
+        Some(config) => {
+            Some(Arc::new(StatsdMetrics::new(config).map_err(|e| {
+                carbon_core::error::Error::Custom(format!("StatsD: {e}"))
+            })?))
+        }
+        None => None,
+    };
+
+    // Evaluate SLOs on the pipeline and webhook metrics, alerting on breaches
+    let slo = match SloConfig::from_env()
+        .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?
+    {
+        Some(config) => {
+            log::info!("SLO monitoring enabled: {} objective(s)", config.slos.len());
+            let monitor = Arc::new(SloMonitor::new(config.slos));
+            monitor.clone().spawn(config.webhook_url);
+            Some(monitor)
+        }
+        None => None,
+    };
+
+    // Webhook deliveries are reported next to the pipeline metrics
+    let mut delivery_metrics: Vec<Arc<dyn Metrics>> = Vec::new();
+    if let Some(ref statsd) = statsd {
+        delivery_metrics.push(statsd.clone());
+    }
+    if let Some(ref slo) = slo {
+        delivery_metrics.push(slo.clone());
+    }
+
+    // Shadow rule matches are counted next to the webhook deliveries
+    if !delivery_metrics.is_empty() {
+        rules::install_metrics(Arc::new(MetricsCollection::new(delivery_metrics.clone())));
+    }
+
+    // Initialize optional webhook notifier
+    let webhooks = WebhookConfig::all_from_env()
+        .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+    for webhook in &webhooks {
+        log::info!(
+            "Webhook notifications enabled: {} ({})",
+            webhook.name,
+            webhook.url
+        );
+    }
+
+    // Number webhook deliveries per stream so consumers can detect and backfill gaps
+    let sequencer = match SequenceConfig::from_env() {
+        Some(config) => {
+            log::info!(
+                "Webhook stream sequencing enabled: {}",
+                config.path.display()
+            );
+            Some(Arc::new(Sequencer::open(config).map_err(|e| {
+                carbon_core::error::Error::Custom(e.to_string())
+            })?))
+        }
+        None => None,
+    };
+    let webhook_notifier = (!webhooks.is_empty()).then(|| {
+        let mut notifier = WebhookNotifier::fan_out(webhooks);
+        if let Some(ref sequencer) = sequencer {
+            notifier = notifier.with_sequencer(sequencer.clone());
+        }
+        if !delivery_metrics.is_empty() {
+            notifier = notifier.with_metrics(Arc::new(MetricsCollection::new(delivery_metrics)));
+        }
+        Arc::new(notifier)
+    });
+
+    // Load filters, optional alert rules, and labels; reloaded on SIGHUP or file change
+    let live_config: SharedConfig = Arc::new(ArcSwap::from_pointee(
+        LiveConfig::from_env().map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?,
+    ));
+
+    // Initialize optional storage; stored swaps are aggregated into candles, which
+    // are kept in memory when no database is configured
+    let candle_config = CandleConfig::from_env();
+    let candle_builder = Arc::new(CandleBuilder::new(candle_config.intervals));
+    let (storage_sink, candle_service, event_store) = match storage::from_env(
+        Some(candle_builder.clone()),
+        candle_config.memory_limit,
+        migrations,
+    )
+    .await
+    .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?
+    {
+        Some(storage) => {
+            let rollup_after = storage.rollup_after;
+            let candle_service = storage.candles.map(|store| {
+                let mut service = CandleService::new(candle_builder, store);
+                if let Some(after) = rollup_after {
+                    service = service.with_rollup(after);
+                }
+                Arc::new(service)
+            });
+            (Some(storage.sink), candle_service, storage.events)
+        }
+        None => (None, None, None),
+    };
+
+    // The price service is shared by the enrichment pipeline and the volume
+    // statistics, which value unpriced swaps at trade time
+    let enrichment_cache = EnrichmentConfig::from_env().map(|c| Arc::new(EnrichmentCache::new(c)));
+    let mut price = PriceEnricher::from_env();
+    if let Some(ref cache) = enrichment_cache {
+        price = price.with_cache(cache.clone());
+    }
+    let prices = Arc::new(price);
+
+    // Per-token stats answer Telegram inline lookups and back the Grafana
+    // datasource of the streaming server
+    let server_config = ServerConfig::from_env();
+    let subscriptions_config = SubscriptionsConfig::from_env();
+    let stats = (server_config.is_some() || subscriptions_config.is_some())
+        .then(|| Arc::new(StatsStore::default().with_prices(prices.clone())));
+
+    // Start the optional streaming server (WebSocket/SSE topics, swaps, candles, and Grafana API)
+    let stream_hub = match (server_config, &stats) {
+        (Some(config), Some(stats)) => {
+            let hub = Arc::new(
+                StreamHub::new(config.channel_capacity, config.recent_capacity)
+                    .with_cache(ResponseCache::new(config.cache)),
+            );
+            server::spawn(
+                config,
+                hub.clone(),
+                candle_service,
+                stats.clone(),
+                sequencer,
+                event_store,
+            )
+            .await
+            .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?;
+            Some(hub)
+        }
+        _ => None,
+    };
+
+    // Watch for reloads; new labels re-annotate the swaps buffered for the query API and
+    // drop its cached responses
+    let mut watcher = ConfigWatcher::new(live_config.clone(), config_file);
+    if let Some(ref hub) = stream_hub {
+        let hub = hub.clone();
+        watcher = watcher.with_relabel(move |labels| {
+            hub.recent().relabel(labels);
+            hub.cache().clear();
+        });
+    }
+    watcher.spawn();
+
+    // Start the optional Telegram bot managing per-chat subscriptions and
+    // answering inline token lookups from the per-token stats
+    let subscriptions = match subscriptions_config {
+        Some(config) => {
+            let registry = Arc::new(
+                SubscriptionRegistry::load(&config.file)
+                    .map_err(|e| carbon_core::error::Error::Custom(e.to_string()))?
+                    .with_max_per_chat(config.max_per_chat),
+            );
+            let client = Arc::new(TelegramClient::new(&config.api_url, &config.bot_token));
+            let mut bot = TelegramBot::new(client.clone(), registry.clone());
+            if let Some(ref stats) = stats {
+                bot = bot.with_stats(stats.clone());
+            }
+            bot.spawn();
+            let bus =
+                SubscriptionBus::new(registry, client).with_alert_quota(config.max_alerts_per_hour);
+            Some(Arc::new(bus))
+        }
+        None => None,
+    };
+
+    // Summarize rolling per-pool volume periodically to surface flow spikes
+    let aggregator = AggregationConfig::from_env().map(|config| {
+        let aggregator = Arc::new(VolumeAggregator::new().with_prices(prices.clone()));
+        aggregator
+            .clone()
+            .spawn_summaries(config, output_format, webhook_notifier.clone());
+        aggregator
+    });
+
+    // Withhold swap alerts of tokens and pools over their limits, digested per window
+    let throttle = ThrottleConfig::from_env().map(|config| {
+        let throttle = Arc::new(AlertThrottle::new(config));
+        throttle
+            .clone()
+            .spawn_digests(output_format, webhook_notifier.clone());
+        throttle
+    });
+
+    // Build the enrichment pipeline; the metadata stage needs an RPC-backed
+    // cache, which is pre-resolved for the configured filters. The cache also
+    // resolves CLMM pool states for legacy swaps.
+    let mut enrichers: Vec<Arc<dyn Enricher>> = vec![prices.clone()];
+    if let Some(ref cache) = enrichment_cache {
+        let live = live_config.load();
+        cache.warm_up(&live.filter_tokens, &live.filter_amms).await;
+        cache.spawn_reserve_refresh();
+        enrichers.push(Arc::new(MetadataEnricher::new(cache.clone())));
+        enrichers.push(Arc::new(ImpactEnricher::new(cache.clone())));
+        enrichers.push(Arc::new(HolderEnricher::new(cache.clone())));
+        enrichers.push(Arc::new(AuthorityEnricher::new(cache.clone())));
+    }
+    let enrichment = EnrichmentPipeline::from_config(
+        &EnrichmentPipelineConfig::from_env(&["metadata", "price", "impact"]),
+        enrichers,
+    )
+    .with_updates(storage_sink.clone(), stream_hub.clone());
+    let enrichment = (!enrichment.is_empty()).then(|| Arc::new(enrichment));
+
+    // Detect liquidity drains against the reserves and supplies of the enrichment cache
+    let drain = match (DrainConfig::from_env(), &enrichment_cache) {
+        (Some(config), Some(cache)) => Some(Arc::new(DrainDetector::new(config, cache.clone()))),
+        (Some(_), None) => {
+            log::warn!("Liquidity drain detection requires RPC_HTTP_URL, disabled");
+            None
+        }
+        (None, _) => None,
+    };
+
+    // Poll the LP supply of new pools for burns, badging the swaps of burned ones
+    let lp_burn = match (LpBurnConfig::from_env(), &enrichment_cache) {
+        (Some(config), Some(cache)) => {
+            log::info!(
+                "LP burn detection: every {}s, burned at {}% of the LP supply",
+                config.interval.as_secs(),
+                config.min_burned_pct
+            );
+            let lp_burn = Arc::new(LpBurnTracker::new(config, cache.rpc().clone()));
+            lp_burn
+                .clone()
+                .spawn_checks(output_format, webhook_notifier.clone());
+            Some(lp_burn)
+        }
+        (Some(_), None) => {
+            log::warn!("LP burn detection requires RPC_HTTP_URL, disabled");
+            None
+        }
+        (None, _) => None,
+    };
+
+    let mut builder = PipelineBuilder::new(filter_markets, live_config, output_format)
+        .with_event_log(Arc::new(EventLog::from_env(output_format)))
+        .with_summary(SummaryConfig::from_env());
+    for market in MarketType::ALL {
+        builder = builder.with_concurrency(market, ConcurrencyConfig::from_env(market));
+    }
+    if let Some(notifier) = webhook_notifier {
+        builder = builder.with_notifier(notifier);
+    }
+    if let Some(bus) = subscriptions {
+        log::info!(
+            "Telegram subscriptions: {} subscription(s), alerts/hour per chat: {}",
+            bus.len(),
+            bus.alert_limit()
+                .map_or("unlimited".to_string(), |limit| limit.to_string())
+        );
+        builder = builder.with_notifier(bus);
+    }
+    for config in PushConfig::all_from_env() {
+        log::info!(
+            "Push notifications enabled: {}{}",
+            config.backend.name(),
+            config
+                .min_usd
+                .map_or(String::new(), |min| format!(" (swaps from ${min})"))
+        );
+        builder = builder.with_notifier(Arc::new(PushNotifier::new(config)));
+    }
+    if let Some(hub) = stream_hub {
+        builder = builder.with_stream_hub(hub);
+    }
+    if let Some(stats) = stats {
+        builder = builder.with_stats(stats);
+    }
+    if let Some(aggregator) = aggregator {
+        builder = builder.with_aggregator(aggregator);
+    }
+    if let Some(config) = MevConfig::from_env() {
+        builder = builder.with_mev(Arc::new(MevDetector::new(config)));
+    }
+    if let Some(drain) = drain {
+        builder = builder.with_drain(drain);
+    }
+    if let Some(lp_burn) = lp_burn {
+        builder = builder.with_lp_burn(lp_burn);
+    }
+    if let Some(config) = PoolStatsConfig::from_env() {
+        log::info!("Pool stats: up to {} pools", config.max_pools);
+        builder = builder.with_pool_stats(Arc::new(PoolStatsTracker::new(config)));
+    }
+    if let Some(config) = ListingConfig::from_env() {
+        log::info!(
+            "New listing filter: pools and tokens created within {} min",
+            config.max_age_secs / 60
+        );
+        builder = builder.with_listings(Arc::new(ListingTracker::new(config)));
+    }
+    if let Some(throttle) = throttle {
+        builder = builder.with_throttle(throttle);
+    }
+    if let Some(sink) = storage_sink {
+        builder = builder.with_storage(sink);
+    }
+    if let Some(enrichment) = enrichment {
+        builder = builder.with_enrichment(enrichment);
+    }
+    if let Some(cache) = enrichment_cache {
+        builder = builder.with_enrichment_cache(cache);
+    }
+    if let Some(config) = DedupConfig::from_env() {
+        builder = builder.with_dedup(config);
+    }
+    if let Some(config) = SlotBatchConfig::from_env() {
+        builder = builder.with_slot_batcher(SlotBatcher::spawn(config, output_format));
+    }
+    if let Some(statsd) = statsd {
+        builder = builder.with_metrics(statsd);
+    }
+    if let Some(slo) = slo {
+        builder = builder.with_metrics(slo);
+    }
+    if let Some(ref soak) = soak {
+        builder = builder.with_metrics(soak.counters());
+    }
+
+    builder.log_startup_info(&rpc_ws_url);
+
+    // Each (re)connection subscribes anew; stalls and slot gaps are detected by the supervisor
+    let block_subscribe = SupervisedDatasource::new(SupervisorConfig::from_env(), move || {
+        RpcBlockSubscribe::new(
+            rpc_ws_url.clone(),
+            Filters::new(
+                subscribe_filter.clone(),
+                Some(RpcBlockSubscribeConfig {
+                    max_supported_transaction_version: Some(0),
+                    ..RpcBlockSubscribeConfig::default()
+                }),
+            ),
+        )
+    });
+
+    // Build pipeline with selected market processors
+    let mut pipeline = match soak {
+        Some(ref soak) => builder.build(soak.datasource())?,
+        None => builder.build(block_subscribe)?,
+    };
+
+    // Tell the service supervisor we are up, and keep its watchdog fed while blocks arrive
+    service::ready();
+    if let Some(config) = WatchdogConfig::from_env() {
+        service::spawn_watchdog(config, builder.heartbeat().clone());
+    }
+
+    // Run pipeline until SIGINT/SIGTERM or the end of a soak test, then flush
+    // held slots and drain webhook queues
+    if let Some(ref mut soak) = soak {
+        soak.begin();
+    }
+    let result = tokio::select! {
+        result = pipeline.run() => result,
+        Some(()) = async {
+            match soak {
+                Some(ref soak) => Some(soak.run().await),
+                None => None,
+            }
+        } => {
+            log::info!("Soak test complete, shutting down...");
+            Ok(())
+        }
+        signal = shutdown_signal() => {
+            log::info!("Received {signal}, shutting down...");
+            Ok(())
+        }
+    };
+
+    let timeout = shutdown_timeout();
+    service::stopping(timeout);
+    if let Some(batcher) = builder.slot_batcher() {
+        batcher.shutdown().await;
+    }
+    if let Some(notifiers) = builder.notifiers() {
+        notifiers.shutdown(timeout).await;
+    }
+
+    // Check the soak test once the pipeline's tasks had the chance to end
+    drop(pipeline);
+    let result = match soak {
+        Some(soak) => result.and(
+            soak.finish()
+                .await
+                .map_err(carbon_core::error::Error::Custom),
+        ),
+        None => result,
+    };
+    service::stopped(result.is_ok());
+    telemetry.shutdown();
+
+    result
+}
+
+/// Returns the time allowed for queued alerts to be delivered on shutdown.
+///
+/// Read from `SHUTDOWN_TIMEOUT_SECS` (default: 10).
+fn shutdown_timeout() -> Duration {
+    let secs = env::var("SHUTDOWN_TIMEOUT_SECS")
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(10);
+    Duration::from_secs(secs)
+}
+
+/// Waits for SIGINT (Ctrl+C), SIGTERM on Unix, or a Windows service stop
+/// request and returns the signal's name.
+async fn shutdown_signal() -> &'static str {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{self, SignalKind};
+        match unix::signal(SignalKind::terminate()) {
+            Ok(mut terminate) => tokio::select! {
+                _ = signal::ctrl_c() => "Ctrl+C",
+                _ = terminate.recv() => "SIGTERM",
+            },
+            Err(e) => {
+                log::warn!("Failed to register SIGTERM handler: {e}");
+                let _ = signal::ctrl_c().await;
+                "Ctrl+C"
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        tokio::select! {
+            _ = signal::ctrl_c() => "Ctrl+C",
+            _ = service::stop_requested() => "service stop request",
+        }
+    }
+}
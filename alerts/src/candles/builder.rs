@@ -0,0 +1,144 @@
+//! In-memory aggregation of price ticks into candles.
+
+use {
+    super::{Candle, Interval},
+    crate::server::PriceTick,
+    std::{
+        collections::HashMap,
+        sync::{Mutex, PoisonError},
+    },
+};
+
+/// Seconds a candle stays open after its interval ends, so swaps whose block
+/// time falls just before the boundary still land in it.
+const CLOSE_GRACE_SECS: i64 = 10;
+
+/// Aggregates price ticks into one open candle per pool and interval.
+///
+/// A candle is closed when a tick for a later interval arrives or when
+/// [`close_expired`](Self::close_expired) runs after its interval ended. A tick
+/// older than the open candle is returned as its own single-tick candle, to be
+/// merged into the stored one.
+pub struct CandleBuilder {
+    intervals: Vec<Interval>,
+    open: Mutex<HashMap<(String, Interval), Candle>>,
+}
+
+impl CandleBuilder {
+    /// Creates a builder for the given intervals.
+    pub fn new(intervals: Vec<Interval>) -> Self {
+        Self {
+            intervals,
+            open: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns the intervals being built.
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    /// Applies a tick for a pool and returns the candles it closed.
+    pub fn update(&self, pool: &str, tick: &PriceTick) -> Vec<Candle> {
+        let mut open = self.open.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut closed = Vec::new();
+
+        for &interval in &self.intervals {
+            let tick_candle = Candle::from_tick(pool, interval, tick);
+            let open_time = tick_candle.open_time;
+
+            match open.get_mut(&(pool.to_string(), interval)) {
+                Some(candle) if candle.open_time == open_time => candle.merge(&tick_candle),
+                Some(candle) if candle.open_time < open_time => {
+                    closed.push(std::mem::replace(candle, tick_candle));
+                }
+                Some(_) => closed.push(tick_candle),
+                None => {
+                    open.insert((pool.to_string(), interval), tick_candle);
+                }
+            }
+        }
+
+        closed
+    }
+
+    /// Closes and returns candles whose interval ended before `now` (Unix seconds).
+    pub fn close_expired(&self, now: i64) -> Vec<Candle> {
+        let mut open = self.open.lock().unwrap_or_else(PoisonError::into_inner);
+        let expired: Vec<_> = open
+            .iter()
+            .filter(|(_, c)| c.open_time + c.interval.seconds() + CLOSE_GRACE_SECS <= now)
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        expired
+            .into_iter()
+            .filter_map(|key| open.remove(&key))
+            .collect()
+    }
+
+    /// Returns a copy of the open candle for a pool and interval.
+    pub fn open_candle(&self, pool: &str, interval: Interval) -> Option<Candle> {
+        self.open
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&(pool.to_string(), interval))
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(price: f64, ts: i64) -> PriceTick {
+        PriceTick {
+            price,
+            price_usd: None,
+            volume: 1.0,
+            volume_usd: Some(10.0),
+            base_mint: "base".to_string(),
+            ts,
+            slot: 0,
+        }
+    }
+
+    #[test]
+    fn test_builder_aggregates_and_rolls_over() {
+        let builder = CandleBuilder::new(vec![Interval::M1, Interval::M5]);
+
+        assert!(builder.update("pool", &tick(2.0, 60)).is_empty());
+        assert!(builder.update("pool", &tick(3.0, 90)).is_empty());
+        assert!(builder.update("pool", &tick(1.0, 119)).is_empty());
+
+        let open = builder.open_candle("pool", Interval::M1).unwrap();
+        assert_eq!(
+            (open.open, open.high, open.low, open.close),
+            (2.0, 3.0, 1.0, 1.0)
+        );
+        assert_eq!(open.trades, 3);
+        assert_eq!(open.volume_usd, Some(30.0));
+
+        // Next minute closes the 1m candle but not the 5m one
+        let closed = builder.update("pool", &tick(4.0, 120));
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].interval, Interval::M1);
+        assert_eq!(closed[0].open_time, 60);
+        assert_eq!(builder.open_candle("pool", Interval::M5).unwrap().trades, 4);
+    }
+
+    #[test]
+    fn test_builder_returns_late_ticks_and_closes_expired() {
+        let builder = CandleBuilder::new(vec![Interval::M1]);
+        builder.update("pool", &tick(2.0, 120));
+
+        let late = builder.update("pool", &tick(5.0, 70));
+        assert_eq!(late.len(), 1);
+        assert_eq!((late[0].open_time, late[0].trades), (60, 1));
+
+        assert!(builder.close_expired(180).is_empty());
+        let closed = builder.close_expired(180 + CLOSE_GRACE_SECS);
+        assert_eq!(closed.len(), 1);
+        assert!(builder.open_candle("pool", Interval::M1).is_none());
+    }
+}
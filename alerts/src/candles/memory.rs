@@ -0,0 +1,153 @@
+//! In-memory candle store for deployments without a database.
+
+use {
+    super::{Candle, CandleStore, Interval},
+    crate::{
+        output::SwapEvent,
+        storage::{BatchWriter, StorageError},
+    },
+    async_trait::async_trait,
+    std::{
+        collections::{HashMap, VecDeque},
+        sync::{Arc, Mutex, PoisonError},
+    },
+};
+
+/// Keeps the most recent closed candles per pool and interval in memory.
+///
+/// Used as the [`BatchWriter`] of a storage sink, it builds candles from the
+/// swap stream without storing the events themselves. Candles are lost on
+/// restart.
+#[derive(Clone)]
+pub struct MemoryCandles {
+    /// Closed candles kept per pool and interval
+    limit: usize,
+    candles: Arc<Mutex<HashMap<(String, Interval), VecDeque<Candle>>>>,
+}
+
+impl MemoryCandles {
+    /// Creates a store keeping at most `limit` closed candles per pool and interval.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit: limit.max(1),
+            candles: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Inserts a closed candle in open time order, merging it into a kept
+    /// candle for the same interval.
+    fn insert(&self, candle: &Candle) {
+        let mut candles = self.candles.lock().unwrap_or_else(PoisonError::into_inner);
+        let series = candles
+            .entry((candle.pool.clone(), candle.interval))
+            .or_default();
+        let position = series.partition_point(|kept| kept.open_time < candle.open_time);
+        match series.get_mut(position) {
+            Some(kept) if kept.open_time == candle.open_time => kept.merge(candle),
+            _ => series.insert(position, candle.clone()),
+        }
+        while series.len() > self.limit {
+            series.pop_front();
+        }
+    }
+}
+
+#[async_trait]
+impl BatchWriter for MemoryCandles {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+
+    /// Events are not kept; the sink only feeds them to the candle builder.
+    async fn write_batch(&mut self, _events: &[SwapEvent]) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    async fn write_candles(&mut self, candles: &[Candle]) -> Result<(), StorageError> {
+        for candle in candles {
+            self.insert(candle);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CandleStore for MemoryCandles {
+    async fn candles(
+        &self,
+        pool: &str,
+        interval: Interval,
+        from: i64,
+        to: i64,
+        limit: usize,
+    ) -> Result<Vec<Candle>, StorageError> {
+        let candles = self.candles.lock().unwrap_or_else(PoisonError::into_inner);
+        Ok(candles
+            .get(&(pool.to_string(), interval))
+            .map(|series| {
+                series
+                    .iter()
+                    .filter(|candle| (from..=to).contains(&candle.open_time))
+                    .take(limit)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candle(open_time: i64, close: f64) -> Candle {
+        Candle {
+            pool: "pool".to_string(),
+            interval: Interval::M1,
+            open_time,
+            open: close,
+            high: close,
+            low: close,
+            close,
+            volume: 1.0,
+            volume_usd: None,
+            trades: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_keeps_latest_candles_in_order() {
+        let mut store = MemoryCandles::new(2);
+        store
+            .write_candles(&[candle(120, 2.0), candle(60, 1.0)])
+            .await
+            .unwrap();
+        // A late candle for a kept interval is merged
+        store.write_candles(&[candle(120, 2.5)]).await.unwrap();
+        store.write_candles(&[candle(180, 3.0)]).await.unwrap();
+
+        let candles = store
+            .candles("pool", Interval::M1, 0, 1_000, 10)
+            .await
+            .unwrap();
+        let bars: Vec<_> = candles
+            .iter()
+            .map(|c| (c.open_time, c.close, c.trades))
+            .collect();
+        assert_eq!(bars, [(120, 2.5, 2), (180, 3.0, 1)]);
+
+        assert_eq!(
+            store
+                .candles("pool", Interval::M1, 150, 1_000, 10)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(store
+            .candles("pool", Interval::M5, 0, 1_000, 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
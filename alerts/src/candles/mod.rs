@@ -0,0 +1,406 @@
+//! OHLCV candles built from stored swaps.
+//!
+//! This module provides:
+//! - [`Interval`] - Supported candle resolutions (`1m` to `1d`)
+//! - [`Candle`] - One OHLCV bar for a pool and interval
+//! - [`CandleBuilder`] - Aggregates price ticks into open candles and closes them
+//! - [`CandleStore`] - Read access to persisted candles
+//! - [`CandleService`] - Serves persisted candles merged with the open ones
+//! - [`MemoryCandles`] - Keeps recent closed candles in memory when no database is set
+//! - [`Rollup`] - Aggregates raw swaps into hourly and daily candles before they are deleted
+//!
+//! Candles are built by the storage sink from the events it persists, so they
+//! cover exactly the swaps in the `swaps` table. Closed candles are upserted
+//! into a `candles` table; a candle written more than once (late events, or a
+//! restart in the middle of an interval) is merged rather than overwritten.
+//! Without a database, a `memory` sink builds candles from the swap stream
+//! and keeps the latest `CANDLE_MEMORY_LIMIT` of them per pool and interval.
+//!
+//! With `ROLLUP_AFTER_DAYS`, the database keeps swaps and sub-hour candles for
+//! that many days only; older swaps are rolled up into hourly and daily
+//! candles first. [`CandleService`] answers sub-hour queries reaching past the
+//! rolled-up boundary with hourly candles for the older part.
+
+mod builder;
+mod memory;
+mod rollup;
+
+pub use {
+    builder::CandleBuilder,
+    memory::MemoryCandles,
+    rollup::{rollup_cutoff, Rollup, ROLLUP_INTERVAL},
+};
+
+use {
+    crate::{
+        server::{unix_now, PriceTick},
+        storage::StorageError,
+    },
+    async_trait::async_trait,
+    serde::Serialize,
+    std::{env, fmt, sync::Arc, time::Duration},
+};
+
+/// Candle resolution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Interval {
+    /// One minute
+    M1,
+    /// Five minutes
+    M5,
+    /// Fifteen minutes
+    M15,
+    /// One hour
+    H1,
+    /// Four hours
+    H4,
+    /// One day
+    D1,
+}
+
+impl Interval {
+    /// All supported intervals, shortest first.
+    pub const ALL: [Interval; 6] = [Self::M1, Self::M5, Self::M15, Self::H1, Self::H4, Self::D1];
+
+    /// Parses an interval name such as `1m`, `4h`, or `1d` (case-insensitive).
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "1m" => Some(Self::M1),
+            "5m" => Some(Self::M5),
+            "15m" => Some(Self::M15),
+            "1h" => Some(Self::H1),
+            "4h" => Some(Self::H4),
+            "1d" => Some(Self::D1),
+            _ => None,
+        }
+    }
+
+    /// Returns the interval length in seconds.
+    pub fn seconds(self) -> i64 {
+        match self {
+            Self::M1 => 60,
+            Self::M5 => 300,
+            Self::M15 => 900,
+            Self::H1 => 3_600,
+            Self::H4 => 14_400,
+            Self::D1 => 86_400,
+        }
+    }
+
+    /// Returns the open time of the interval containing `ts` (Unix seconds).
+    pub fn bucket(self, ts: i64) -> i64 {
+        ts.div_euclid(self.seconds()) * self.seconds()
+    }
+
+    /// Returns `true` for intervals shorter than an hour, whose candles are
+    /// deleted with the raw swaps once those are rolled up.
+    pub fn is_sub_hour(self) -> bool {
+        self < Self::H1
+    }
+}
+
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::M1 => "1m",
+            Self::M5 => "5m",
+            Self::M15 => "15m",
+            Self::H1 => "1h",
+            Self::H4 => "4h",
+            Self::D1 => "1d",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// One OHLCV bar.
+///
+/// Prices are the pool's non-base token priced in the base token, as in the
+/// `price:<pool>` stream topic. Serialized without `pool` and `interval`,
+/// which the API response carries once.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Candle {
+    /// Pool address
+    #[serde(skip)]
+    pub pool: String,
+    /// Candle resolution
+    #[serde(skip)]
+    pub interval: Interval,
+    /// Interval open time (Unix seconds)
+    #[serde(rename = "time")]
+    pub open_time: i64,
+    /// First price in the interval
+    pub open: f64,
+    /// Highest price in the interval
+    pub high: f64,
+    /// Lowest price in the interval
+    pub low: f64,
+    /// Last price in the interval
+    pub close: f64,
+    /// Volume in base token units
+    pub volume: f64,
+    /// Volume in USD, if known for any swap in the interval
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume_usd: Option<f64>,
+    /// Number of swaps in the interval
+    pub trades: u64,
+}
+
+impl Candle {
+    /// Creates a single-swap candle of the interval containing the tick.
+    pub fn from_tick(pool: &str, interval: Interval, tick: &PriceTick) -> Self {
+        Self {
+            pool: pool.to_string(),
+            interval,
+            open_time: interval.bucket(tick.ts),
+            open: tick.price,
+            high: tick.price,
+            low: tick.price,
+            close: tick.price,
+            volume: tick.volume,
+            volume_usd: tick.volume_usd,
+            trades: 1,
+        }
+    }
+
+    /// Merges a later partial candle for the same interval into this one.
+    pub fn merge(&mut self, later: &Candle) {
+        self.high = self.high.max(later.high);
+        self.low = self.low.min(later.low);
+        self.close = later.close;
+        self.volume += later.volume;
+        self.volume_usd = match (self.volume_usd, later.volume_usd) {
+            (None, None) => None,
+            (a, b) => Some(a.unwrap_or_default() + b.unwrap_or_default()),
+        };
+        self.trades += later.trades;
+    }
+}
+
+/// Configuration for candle building.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CandleConfig {
+    /// Intervals to build and persist
+    pub intervals: Vec<Interval>,
+    /// Closed candles kept in memory per pool and interval when no database is configured
+    pub memory_limit: Option<usize>,
+}
+
+impl Default for CandleConfig {
+    fn default() -> Self {
+        Self {
+            intervals: Interval::ALL.to_vec(),
+            memory_limit: None,
+        }
+    }
+}
+
+impl CandleConfig {
+    /// Creates candle configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `CANDLE_INTERVALS` - Optional: Comma-separated intervals to build
+    ///   (default: `1m,5m,15m,1h,4h,1d`). Unknown names are logged and ignored.
+    /// - `CANDLE_MEMORY_LIMIT` - Optional: Closed candles kept in memory per pool
+    ///   and interval when no database is configured (default: disabled)
+    pub fn from_env() -> Self {
+        let memory_limit = env::var("CANDLE_MEMORY_LIMIT")
+            .ok()
+            .and_then(|n| n.trim().parse().ok())
+            .filter(|limit| *limit > 0);
+        let value = match env::var("CANDLE_INTERVALS") {
+            Ok(value) if !value.trim().is_empty() => value,
+            _ => {
+                return Self {
+                    memory_limit,
+                    ..Self::default()
+                }
+            }
+        };
+
+        let mut intervals = Vec::new();
+        for name in value.split(',').filter(|name| !name.trim().is_empty()) {
+            match Interval::parse(name) {
+                Some(interval) if !intervals.contains(&interval) => intervals.push(interval),
+                Some(_) => {}
+                None => log::warn!("Unknown candle interval '{}', skipping", name.trim()),
+            }
+        }
+        intervals.sort();
+        Self {
+            intervals,
+            memory_limit,
+        }
+    }
+}
+
+/// Read access to persisted candles.
+#[async_trait]
+pub trait CandleStore: Send + Sync {
+    /// Returns closed candles with `from <= open_time <= to`, oldest first,
+    /// at most `limit` of them.
+    async fn candles(
+        &self,
+        pool: &str,
+        interval: Interval,
+        from: i64,
+        to: i64,
+        limit: usize,
+    ) -> Result<Vec<Candle>, StorageError>;
+}
+
+/// Serves candles from the store, including the still-open candle.
+pub struct CandleService {
+    builder: Arc<CandleBuilder>,
+    store: Arc<dyn CandleStore>,
+    /// Age after which the store keeps only rolled-up candles
+    rollup_after: Option<Duration>,
+}
+
+impl CandleService {
+    /// Creates a service reading persisted candles from `store` and open
+    /// candles from `builder`.
+    pub fn new(builder: Arc<CandleBuilder>, store: Arc<dyn CandleStore>) -> Self {
+        Self {
+            builder,
+            store,
+            rollup_after: None,
+        }
+    }
+
+    /// Serves hourly candles for sub-hour queries before the store's rollup
+    /// boundary, where only rolled-up candles are kept.
+    pub fn with_rollup(mut self, after: Duration) -> Self {
+        self.rollup_after = Some(after);
+        self
+    }
+
+    /// Returns the open time before which candles of `interval` are served
+    /// from the hourly rollup, if the store rolls them up.
+    pub fn rollup_before(&self, interval: Interval, now: i64) -> Option<i64> {
+        let after = self.rollup_after?;
+        interval.is_sub_hour().then(|| rollup_cutoff(after, now))
+    }
+
+    /// Returns `true` if candles are built for this interval.
+    pub fn supports(&self, interval: Interval) -> bool {
+        self.builder.intervals().contains(&interval)
+    }
+
+    /// Returns the intervals candles are built for.
+    pub fn intervals(&self) -> &[Interval] {
+        self.builder.intervals()
+    }
+
+    /// Returns candles with `from <= open_time <= to`, oldest first.
+    ///
+    /// Sub-hour queries starting before the rollup boundary get hourly
+    /// candles up to the boundary, followed by candles of the requested
+    /// interval. The open candle is appended (or merged into a stored candle
+    /// for the same interval) when it falls in range.
+    pub async fn query(
+        &self,
+        pool: &str,
+        interval: Interval,
+        from: i64,
+        to: i64,
+        limit: usize,
+    ) -> Result<Vec<Candle>, StorageError> {
+        let mut candles = match self
+            .rollup_before(interval, unix_now())
+            .filter(|&cutoff| from < cutoff)
+        {
+            Some(cutoff) => {
+                let mut candles = self
+                    .store
+                    .candles(
+                        pool,
+                        ROLLUP_INTERVAL,
+                        ROLLUP_INTERVAL.bucket(from),
+                        to.min(cutoff - 1),
+                        limit,
+                    )
+                    .await?;
+                let remaining = limit.saturating_sub(candles.len());
+                if remaining > 0 && to >= cutoff {
+                    let raw = self
+                        .store
+                        .candles(pool, interval, cutoff, to, remaining)
+                        .await?;
+                    candles.extend(raw);
+                }
+                candles
+            }
+            None => self.store.candles(pool, interval, from, to, limit).await?,
+        };
+
+        if let Some(open) = self
+            .builder
+            .open_candle(pool, interval)
+            .filter(|c| (from..=to).contains(&c.open_time))
+        {
+            let full = candles.len() >= limit;
+            match candles.last_mut() {
+                Some(last) if last.open_time == open.open_time => last.merge(&open),
+                Some(last) if last.open_time > open.open_time => {}
+                _ if !full => candles.push(open),
+                _ => {}
+            }
+        }
+
+        Ok(candles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval_parse_and_bucket() {
+        assert_eq!(Interval::parse("1M"), Some(Interval::M1));
+        assert_eq!(Interval::parse(" 4h "), Some(Interval::H4));
+        assert_eq!(Interval::parse("2m"), None);
+        assert_eq!(Interval::H1.to_string(), "1h");
+
+        assert_eq!(Interval::M1.bucket(125), 120);
+        assert_eq!(Interval::M5.bucket(299), 0);
+        assert_eq!(Interval::M1.bucket(-1), -60);
+    }
+
+    #[test]
+    fn test_candle_merge() {
+        let mut stored = Candle {
+            pool: "pool".to_string(),
+            interval: Interval::M1,
+            open_time: 60,
+            open: 1.0,
+            high: 2.0,
+            low: 0.5,
+            close: 1.5,
+            volume: 10.0,
+            volume_usd: None,
+            trades: 2,
+        };
+        let later = Candle {
+            open: 1.4,
+            high: 3.0,
+            low: 0.8,
+            close: 2.5,
+            volume: 5.0,
+            volume_usd: Some(100.0),
+            trades: 1,
+            ..stored.clone()
+        };
+
+        stored.merge(&later);
+        assert_eq!(stored.open, 1.0);
+        assert_eq!(stored.high, 3.0);
+        assert_eq!(stored.low, 0.5);
+        assert_eq!(stored.close, 2.5);
+        assert_eq!(stored.volume, 15.0);
+        assert_eq!(stored.volume_usd, Some(100.0));
+        assert_eq!(stored.trades, 3);
+    }
+}
@@ -0,0 +1,126 @@
+//! Rollup of raw swaps into hourly and daily candles.
+
+use {
+    super::{Candle, Interval},
+    crate::{output::SwapEvent, server::PriceTick},
+    std::{collections::HashMap, time::Duration},
+};
+
+/// Intervals raw swaps are rolled up into before they are deleted.
+const ROLLUP_INTERVALS: [Interval; 2] = [Interval::H1, Interval::D1];
+
+/// Interval served for sub-hour queries before the rollup boundary.
+pub const ROLLUP_INTERVAL: Interval = Interval::H1;
+
+/// Returns the rollup boundary for swaps kept `after` at full resolution.
+///
+/// The boundary is aligned down to a day, so the hourly and daily buckets of
+/// rolled-up swaps are complete.
+pub fn rollup_cutoff(after: Duration, now: i64) -> i64 {
+    Interval::D1.bucket(now - after.as_secs() as i64)
+}
+
+/// Aggregates swaps into hourly and daily candles, one per pool, interval,
+/// and open time.
+///
+/// Swaps are expected in roughly time order; a candle closes at the price of
+/// the last swap added to it.
+#[derive(Debug, Default)]
+pub struct Rollup {
+    candles: HashMap<(String, Interval, i64), Candle>,
+}
+
+impl Rollup {
+    /// Adds a swap at time `ts` (Unix seconds). Events without a price are ignored.
+    pub fn add(&mut self, event: &SwapEvent, ts: i64) {
+        let Some(mut tick) = PriceTick::from_event(event) else {
+            return;
+        };
+        tick.ts = ts;
+        for interval in ROLLUP_INTERVALS {
+            let candle = Candle::from_tick(&event.pool, interval, &tick);
+            match self
+                .candles
+                .get_mut(&(event.pool.clone(), interval, candle.open_time))
+            {
+                Some(rolled) => rolled.merge(&candle),
+                None => {
+                    self.candles
+                        .insert((event.pool.clone(), interval, candle.open_time), candle);
+                }
+            }
+        }
+    }
+
+    /// Returns the rolled-up candles ordered by pool, interval, and open time.
+    pub fn into_candles(self) -> Vec<Candle> {
+        let mut candles: Vec<Candle> = self.candles.into_values().collect();
+        candles.sort_by(|a, b| {
+            (&a.pool, a.interval, a.open_time).cmp(&(&b.pool, b.interval, b.open_time))
+        });
+        candles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{swap_event::WSOL_MINT, Protocol, TokenInfo},
+    };
+
+    fn swap(sol: u64, tokens: u64) -> SwapEvent {
+        SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .input_token(TokenInfo::new(WSOL_MINT, sol))
+            .output_token(TokenInfo::new("mint", tokens).with_decimals(0))
+            .build()
+    }
+
+    #[test]
+    fn test_rollup_cutoff_is_day_aligned() {
+        let day = 86_400;
+        let now = 10 * day + 3_600;
+        assert_eq!(
+            rollup_cutoff(Duration::from_secs(7 * day as u64), now),
+            3 * day
+        );
+    }
+
+    #[test]
+    fn test_rolls_up_hours_and_days() {
+        let mut rollup = Rollup::default();
+        rollup.add(&swap(1_000_000_000, 2), 60);
+        rollup.add(&swap(3_000_000_000, 2), 120);
+        rollup.add(&swap(1_000_000_000, 4), 3_700);
+        // Without decimals the swap has no price
+        rollup.add(
+            &SwapEvent::builder()
+                .protocol(Protocol::Cpmm)
+                .signature("sig")
+                .pool("pool")
+                .input_token(TokenInfo::new("a", 1))
+                .output_token(TokenInfo::new("b", 1))
+                .build(),
+            60,
+        );
+
+        let candles = rollup.into_candles();
+        let bars: Vec<_> = candles
+            .iter()
+            .map(|c| (c.interval, c.open_time, c.open, c.close, c.trades))
+            .collect();
+        assert_eq!(
+            bars,
+            [
+                (Interval::H1, 0, 0.5, 1.5, 2),
+                (Interval::H1, 3_600, 0.25, 0.25, 1),
+                (Interval::D1, 0, 0.5, 0.25, 3),
+            ]
+        );
+        assert_eq!(candles[2].volume, 5.0);
+        assert_eq!(candles[2].high, 1.5);
+    }
+}
@@ -0,0 +1,535 @@
+//! Chain profiles: the cluster the monitor runs against.
+//!
+//! A [`ChainProfile`] holds what differs between Raydium deployments on
+//! Solana and on SVM side networks or L2s: RPC endpoints, program IDs,
+//! explorer links, and the native token. Solana mainnet is built in; another
+//! deployment is monitored by pointing `CHAIN_PROFILE` at a TOML profile:
+//!
+//! ```toml
+//! name = "eclipse"
+//! rpc_ws_url = "wss://mainnetbeta-rpc.eclipse.xyz"
+//! rpc_http_url = "https://mainnetbeta-rpc.eclipse.xyz"
+//! explorer_tx_url = "https://eclipsescan.xyz/tx/{signature}"
+//! native_symbol = "ETH"
+//! native_decimals = 9
+//!
+//! [programs]
+//! cpmm = "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C"
+//! clmm = "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK"
+//! ```
+//!
+//! Markets without a program ID in the profile are not deployed on that
+//! chain and are skipped. `<MARKET>_PROGRAM_ID` (e.g., `CPMM_PROGRAM_ID`)
+//! overrides a market's program ID on any chain, such as for a devnet
+//! deployment.
+//!
+//! A market's decoder can also handle additional programs reusing its
+//! instruction layout (e.g., a forked AMM), each registered as a
+//! [`Deployment`] with its own label, which replaces the protocol name in its
+//! events. They are listed in the profile:
+//!
+//! ```toml
+//! [[deployments]]
+//! label = "forkswap"
+//! market = "cpmm"
+//! program = "Fork1111111111111111111111111111111111111111"
+//! ```
+//!
+//! or in `PROGRAM_DEPLOYMENTS` as `label=market:program` pairs. Decoders
+//! recognize their mainnet program IDs, so instructions of a deployment at
+//! another ID are decoded through [`Deployed`].
+//!
+//! The profile is installed once at start-up; [`profile`] returns it (or the
+//! Solana mainnet profile before installation).
+
+use {
+    crate::config::MarketType,
+    carbon_core::instruction::{DecodedInstruction, InstructionDecoder},
+    serde::Deserialize,
+    solana_instruction::Instruction,
+    solana_pubkey::Pubkey,
+    std::{
+        collections::{HashMap, HashSet},
+        env, fmt, fs,
+        path::Path,
+        str::FromStr,
+        sync::OnceLock,
+    },
+};
+
+/// Name of the built-in Solana mainnet profile.
+pub const SOLANA: &str = "solana";
+
+/// Placeholder replaced with the transaction signature in explorer links.
+const SIGNATURE_PLACEHOLDER: &str = "{signature}";
+
+static PROFILE: OnceLock<ChainProfile> = OnceLock::new();
+
+/// Errors that can occur while loading a chain profile.
+#[derive(Debug)]
+pub enum ChainError {
+    /// The profile file could not be read
+    Read(String),
+    /// The profile file is not valid TOML or has an unknown shape
+    Parse(String),
+    /// A field is invalid (e.g., unknown market, malformed program ID)
+    Invalid(String),
+}
+
+impl fmt::Display for ChainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(e) => write!(f, "failed to read chain profile: {e}"),
+            Self::Parse(e) => write!(f, "failed to parse chain profile: {e}"),
+            Self::Invalid(e) => write!(f, "invalid chain profile: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ChainError {}
+
+/// Contents of a chain profile file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProfileFile {
+    name: String,
+    rpc_ws_url: String,
+    #[serde(default)]
+    rpc_http_url: Option<String>,
+    #[serde(default)]
+    explorer_tx_url: Option<String>,
+    #[serde(default)]
+    native_symbol: Option<String>,
+    #[serde(default)]
+    native_decimals: Option<u8>,
+    #[serde(default)]
+    programs: HashMap<String, String>,
+    #[serde(default)]
+    deployments: Vec<DeploymentFile>,
+}
+
+/// Additional deployment in a chain profile file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DeploymentFile {
+    label: String,
+    market: String,
+    program: String,
+}
+
+/// A program decoded by a market's decoder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deployment {
+    /// Market whose decoder and processor handle the program
+    pub market: MarketType,
+    /// Program ID
+    pub program_id: Pubkey,
+    /// Label shown instead of the protocol name (additional deployments only)
+    pub label: Option<String>,
+}
+
+/// Cluster-specific settings of a Raydium deployment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainProfile {
+    /// Profile name shown at start-up
+    pub name: String,
+    /// WebSocket RPC endpoint the blocks are subscribed from
+    pub rpc_ws_url: String,
+    /// HTTP RPC endpoint for enrichment, if any
+    pub rpc_http_url: Option<String>,
+    /// Transaction explorer link with a `{signature}` placeholder
+    pub explorer_tx_url: String,
+    /// Symbol of the native token (wrapped at the native mint)
+    pub native_symbol: String,
+    /// Decimals of the native token
+    pub native_decimals: u8,
+    /// Program ID of each market deployed on the chain
+    pub programs: HashMap<MarketType, Pubkey>,
+    /// Additional labeled programs decoded by a market's decoder
+    pub deployments: Vec<Deployment>,
+}
+
+impl ChainProfile {
+    /// Returns the Solana mainnet profile.
+    pub fn solana() -> Self {
+        Self {
+            name: SOLANA.to_string(),
+            rpc_ws_url: "wss://api.mainnet-beta.solana.com/".to_string(),
+            rpc_http_url: None,
+            explorer_tx_url: format!("https://solscan.io/tx/{SIGNATURE_PLACEHOLDER}"),
+            native_symbol: "SOL".to_string(),
+            native_decimals: 9,
+            programs: MarketType::ALL
+                .into_iter()
+                .map(|market| (market, market.program_id()))
+                .collect(),
+            deployments: Vec::new(),
+        }
+    }
+
+    /// Creates the chain profile from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `CHAIN_PROFILE` - Optional: `solana` or a TOML profile file (default: solana)
+    /// - `RPC_WS_URL` - Optional: WebSocket RPC endpoint overriding the profile's
+    /// - `RPC_HTTP_URL` - Optional: HTTP RPC endpoint overriding the profile's
+    /// - `<MARKET>_PROGRAM_ID` - Optional: program ID of a market (e.g.,
+    ///   `CPMM_PROGRAM_ID`) overriding the profile's
+    /// - `PROGRAM_DEPLOYMENTS` - Optional: comma-separated `label=market:program`
+    ///   additional deployments (e.g., `forkswap=cpmm:Fork111...`)
+    pub fn from_env() -> Result<Self, ChainError> {
+        let mut profile = match env::var("CHAIN_PROFILE") {
+            Ok(value) if !value.trim().is_empty() && value.trim() != SOLANA => {
+                Self::load(value.trim())?
+            }
+            _ => Self::solana(),
+        };
+        if let Some(url) = env::var("RPC_WS_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+        {
+            profile.rpc_ws_url = url.trim().to_string();
+        }
+        if let Some(url) = env::var("RPC_HTTP_URL")
+            .ok()
+            .filter(|url| !url.trim().is_empty())
+        {
+            profile.rpc_http_url = Some(url.trim().to_string());
+        }
+        for market in MarketType::ALL {
+            let name = format!("{}_PROGRAM_ID", market.env_prefix());
+            if let Some(program) = env::var(&name).ok().filter(|id| !id.trim().is_empty()) {
+                let program = Pubkey::from_str(program.trim())
+                    .map_err(|e| ChainError::Invalid(format!("{name}: {e}")))?;
+                profile.programs.insert(market, program);
+            }
+        }
+        if let Ok(value) = env::var("PROGRAM_DEPLOYMENTS") {
+            for entry in value.split(',').filter(|entry| !entry.trim().is_empty()) {
+                let parsed = entry
+                    .split_once('=')
+                    .and_then(|(label, target)| Some((label, target.split_once(':')?)));
+                let Some((label, (market, program))) = parsed else {
+                    return Err(ChainError::Invalid(format!(
+                        "PROGRAM_DEPLOYMENTS entry '{}', expected label=market:program",
+                        entry.trim()
+                    )));
+                };
+                profile.add_deployment(label.trim(), market.trim(), program.trim())?;
+            }
+        }
+        profile.check_programs()?;
+        Ok(profile)
+    }
+
+    /// Loads a profile from a TOML file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ChainError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|e| ChainError::Read(format!("{}: {e}", path.display())))?;
+        Self::parse(&content)
+    }
+
+    /// Parses a profile from TOML content.
+    ///
+    /// Unset explorer and native token fields default to Solana's.
+    pub fn parse(content: &str) -> Result<Self, ChainError> {
+        let file: ProfileFile =
+            toml::from_str(content).map_err(|e| ChainError::Parse(e.to_string()))?;
+        let solana = Self::solana();
+
+        let explorer_tx_url = file.explorer_tx_url.unwrap_or(solana.explorer_tx_url);
+        if !explorer_tx_url.contains(SIGNATURE_PLACEHOLDER) {
+            return Err(ChainError::Invalid(format!(
+                "explorer_tx_url must contain {SIGNATURE_PLACEHOLDER}"
+            )));
+        }
+        let programs = file
+            .programs
+            .iter()
+            .map(|(market, program)| {
+                let market = MarketType::from_str(market).map_err(ChainError::Invalid)?;
+                let program = Pubkey::from_str(program)
+                    .map_err(|e| ChainError::Invalid(format!("{market:?} program: {e}")))?;
+                Ok((market, program))
+            })
+            .collect::<Result<_, ChainError>>()?;
+
+        let mut profile = Self {
+            name: file.name,
+            rpc_ws_url: file.rpc_ws_url,
+            rpc_http_url: file.rpc_http_url.filter(|url| !url.trim().is_empty()),
+            explorer_tx_url,
+            native_symbol: file.native_symbol.unwrap_or(solana.native_symbol),
+            native_decimals: file.native_decimals.unwrap_or(solana.native_decimals),
+            programs,
+            deployments: Vec::new(),
+        };
+        for deployment in &file.deployments {
+            profile.add_deployment(&deployment.label, &deployment.market, &deployment.program)?;
+        }
+        profile.check_programs()?;
+        Ok(profile)
+    }
+
+    /// Adds a labeled deployment of a market's program layout.
+    fn add_deployment(
+        &mut self,
+        label: &str,
+        market: &str,
+        program: &str,
+    ) -> Result<(), ChainError> {
+        let market = MarketType::from_str(market).map_err(ChainError::Invalid)?;
+        let program_id = Pubkey::from_str(program)
+            .map_err(|e| ChainError::Invalid(format!("deployment {label} program: {e}")))?;
+        if label.is_empty() {
+            return Err(ChainError::Invalid(format!(
+                "deployment of {program_id} has an empty label"
+            )));
+        }
+        if self
+            .deployments
+            .iter()
+            .any(|d| d.label.as_deref() == Some(label))
+        {
+            return Err(ChainError::Invalid(format!(
+                "duplicate deployment label {label}"
+            )));
+        }
+        self.deployments.push(Deployment {
+            market,
+            program_id,
+            label: Some(label.to_string()),
+        });
+        Ok(())
+    }
+
+    /// Rejects a program ID registered for several deployments, whose
+    /// instructions would be decoded twice.
+    fn check_programs(&self) -> Result<(), ChainError> {
+        let mut seen = HashSet::new();
+        for program_id in self
+            .programs
+            .values()
+            .chain(self.deployments.iter().map(|d| &d.program_id))
+        {
+            if !seen.insert(program_id) {
+                return Err(ChainError::Invalid(format!(
+                    "program {program_id} is registered more than once"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the program ID of a market, or `None` if it is not deployed.
+    pub fn program_id(&self, market: MarketType) -> Option<Pubkey> {
+        self.programs.get(&market).copied()
+    }
+
+    /// Returns the programs decoded by a market's decoder: its own program
+    /// first, then the additional deployments.
+    pub fn deployments(&self, market: MarketType) -> Vec<Deployment> {
+        let own = self.program_id(market).map(|program_id| Deployment {
+            market,
+            program_id,
+            label: None,
+        });
+        own.into_iter()
+            .chain(
+                self.deployments
+                    .iter()
+                    .filter(|d| d.market == market)
+                    .cloned(),
+            )
+            .collect()
+    }
+
+    /// Returns the selected markets deployed on the chain, warning about the others.
+    pub fn deployed(&self, mut markets: HashSet<MarketType>) -> HashSet<MarketType> {
+        markets.retain(|market| {
+            let deployed = !self.deployments(*market).is_empty();
+            if !deployed {
+                log::warn!(
+                    "Market {} is not deployed on chain {}, skipped",
+                    market.name(),
+                    self.name
+                );
+            }
+            deployed
+        });
+        markets
+    }
+
+    /// Returns the explorer link of a transaction.
+    pub fn explorer_tx(&self, signature: &str) -> String {
+        self.explorer_tx_url
+            .replace(SIGNATURE_PLACEHOLDER, signature)
+    }
+
+    /// Installs the profile returned by [`profile`]. Fails if a profile was
+    /// already installed or read.
+    pub fn install(self) -> Result<(), ChainError> {
+        PROFILE
+            .set(self)
+            .map_err(|_| ChainError::Invalid("a chain profile is already installed".to_string()))
+    }
+}
+
+/// Returns the installed chain profile, or Solana mainnet's.
+pub fn profile() -> &'static ChainProfile {
+    PROFILE.get_or_init(ChainProfile::solana)
+}
+
+/// Decodes the instructions of a deployment at another program ID than the
+/// decoder's.
+///
+/// Instructions are handed to the decoder under its own program ID, and the
+/// decoded instruction carries the deployment's again.
+pub struct Deployed<D> {
+    decoder: D,
+    program_id: Pubkey,
+    decoder_program_id: Pubkey,
+}
+
+impl<D> Deployed<D> {
+    /// Wraps the decoder of a deployment's market for the deployment's program ID.
+    pub fn new(decoder: D, deployment: &Deployment) -> Self {
+        Self {
+            decoder,
+            program_id: deployment.program_id,
+            decoder_program_id: deployment.market.program_id(),
+        }
+    }
+}
+
+impl<'a, D, T> InstructionDecoder<'a> for Deployed<D>
+where
+    D: for<'b> InstructionDecoder<'b, InstructionType = T>,
+{
+    type InstructionType = T;
+
+    fn decode_instruction(&self, instruction: &'a Instruction) -> Option<DecodedInstruction<T>> {
+        if instruction.program_id != self.program_id {
+            return None;
+        }
+        if self.program_id == self.decoder_program_id {
+            return self.decoder.decode_instruction(instruction);
+        }
+        let renamed = Instruction {
+            program_id: self.decoder_program_id,
+            accounts: instruction.accounts.clone(),
+            data: instruction.data.clone(),
+        };
+        let mut decoded = self.decoder.decode_instruction(&renamed)?;
+        decoded.program_id = self.program_id;
+        Some(decoded)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_profile() {
+        let profile = ChainProfile::parse(
+            r#"
+            name = "side"
+            rpc_ws_url = "wss://rpc.side.example"
+            explorer_tx_url = "https://explorer.side.example/tx/{signature}?cluster=side"
+            native_symbol = "ETH"
+
+            [programs]
+            cpmm = "11111111111111111111111111111111"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(profile.name, "side");
+        assert_eq!(profile.rpc_http_url, None);
+        assert_eq!(
+            profile.explorer_tx("sig"),
+            "https://explorer.side.example/tx/sig?cluster=side"
+        );
+        assert_eq!(
+            (profile.native_symbol.as_str(), profile.native_decimals),
+            ("ETH", 9)
+        );
+        assert_eq!(
+            profile.program_id(MarketType::Cpmm),
+            Some(Pubkey::default())
+        );
+        assert_eq!(profile.program_id(MarketType::Clmm), None);
+        assert_eq!(
+            profile.deployed(MarketType::ALL.into_iter().collect()),
+            HashSet::from([MarketType::Cpmm])
+        );
+
+        assert!(matches!(
+            ChainProfile::parse("name = \"x\"\nrpc_ws_url = \"wss://x\"\n[programs]\nfoo = \"1\""),
+            Err(ChainError::Invalid(_))
+        ));
+        assert!(matches!(
+            ChainProfile::parse(
+                "name = \"x\"\nrpc_ws_url = \"wss://x\"\nexplorer_tx_url = \"https://x\""
+            ),
+            Err(ChainError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_deployments() {
+        let profile = ChainProfile::parse(
+            r#"
+            name = "forks"
+            rpc_ws_url = "wss://x"
+
+            [programs]
+            clmm = "11111111111111111111111111111111"
+
+            [[deployments]]
+            label = "forkswap"
+            market = "cpmm"
+            program = "SysvarC1ock11111111111111111111111111111111"
+            "#,
+        )
+        .unwrap();
+
+        let cpmm = profile.deployments(MarketType::Cpmm);
+        assert_eq!(cpmm.len(), 1);
+        assert_eq!(cpmm[0].label.as_deref(), Some("forkswap"));
+        assert_eq!(profile.deployments(MarketType::Clmm)[0].label, None);
+        // A market with only an additional deployment is deployed
+        assert_eq!(
+            profile.deployed(MarketType::ALL.into_iter().collect()),
+            HashSet::from([MarketType::Cpmm, MarketType::Clmm])
+        );
+
+        // The same program cannot be registered twice
+        let twice = r#"
+            name = "x"
+            rpc_ws_url = "wss://x"
+            [programs]
+            clmm = "11111111111111111111111111111111"
+            [[deployments]]
+            label = "fork"
+            market = "cpmm"
+            program = "11111111111111111111111111111111"
+        "#;
+        assert!(matches!(
+            ChainProfile::parse(twice),
+            Err(ChainError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_solana_profile_deploys_every_market() {
+        let solana = ChainProfile::solana();
+        for market in MarketType::ALL {
+            assert_eq!(solana.program_id(market), Some(market.program_id()));
+        }
+        assert_eq!(solana.explorer_tx("abc"), "https://solscan.io/tx/abc");
+    }
+}
@@ -0,0 +1,618 @@
+//! Command-line subcommands that run instead of the alert pipeline.
+//!
+//! ```bash
+//! raydium-alerts archive <DIR> [--from-slot N] [--to-slot N] [--pool ADDR] [--limit N] [--index]
+//! raydium-alerts replay <DIR> [--from-slot N] [--to-slot N] [--pool ADDR] [--limit N] [--speed X]
+//! raydium-alerts rules test [FILE]
+//! raydium-alerts rpc-bench <WS_URL> <WS_URL>... [--duration SECS]
+//! raydium-alerts soak <RECORDING> [--duration SECS] [--interval SECS] [--warmup SECS]
+//!     [--max-memory-growth PCT] [--max-throughput-drop PCT] [--report FILE]
+//! raydium-alerts --replay <SIGNATURE>
+//! raydium-alerts [--migrate]
+//! ```
+//!
+//! `--migrate` (with or without a subcommand) applies pending schema
+//! migrations of the `POSTGRES_URL` or `SQLITE_PATH` database before the
+//! pipeline starts; without it, startup fails if the database needs them
+//! (see [`crate::storage::MigrationMode`]).
+//!
+//! `archive` prints archived events matching the filters as JSON lines, or
+//! the block index with `--index`. `replay` runs archived events through the
+//! candle, volume summary, and MEV subsystems on block time and prints their
+//! outputs as JSON lines, as fast as possible or at `--speed` times real time
+//! (see [`crate::replay`]). `rules test` runs the example-event
+//! fixtures of a rules file (default: `RULES_FILE`) and fails if any rule
+//! does not behave as expected. `rpc-bench` subscribes to blocks on each
+//! WebSocket endpoint for the test window (default: 60s) and prints their
+//! delivery lag and missing blocks (see [`crate::rpc_bench`]). `--replay`
+//! fetches one transaction and prints the events the processors would have
+//! emitted for it, without delivering them (see [`crate::dry_run`]). `soak` is not
+//! run here: it runs the full pipeline on recorded blocks (see [`crate::soak`]),
+//! so its arguments are only parsed here and the pipeline is set up as usual.
+
+use {
+    crate::{
+        aggregation::AggregationConfig,
+        candles::CandleConfig,
+        dry_run::DryRun,
+        mev::MevConfig,
+        replay::{Pacer, Replay, ReplayOutput},
+        rpc_bench,
+        rules::RulesFile,
+        soak::SoakConfig,
+        storage::{ArchiveQuery, ArchiveReader},
+    },
+    std::{
+        env,
+        io::{self, Write},
+        path::PathBuf,
+        time::{Duration, Instant},
+    },
+};
+
+/// Usage text for the `archive` subcommand.
+const ARCHIVE_USAGE: &str = "usage: raydium-alerts archive <DIR> [--from-slot N] [--to-slot N] \
+                             [--pool ADDR] [--limit N] [--index]";
+
+/// Usage text for the `replay` subcommand.
+const REPLAY_USAGE: &str = "usage: raydium-alerts replay <DIR> [--from-slot N] [--to-slot N] \
+                            [--pool ADDR] [--limit N] [--speed X]";
+
+/// Usage text for the `rules` subcommand.
+const RULES_USAGE: &str = "usage: raydium-alerts rules test [FILE]";
+
+/// Usage text for the `rpc-bench` subcommand.
+const RPC_BENCH_USAGE: &str =
+    "usage: raydium-alerts rpc-bench <WS_URL> <WS_URL>... [--duration SECS]";
+
+/// Usage text for the `soak` subcommand.
+const SOAK_USAGE: &str = "usage: raydium-alerts soak <RECORDING> [--duration SECS] \
+                          [--interval SECS] [--warmup SECS] [--max-memory-growth PCT] \
+                          [--max-throughput-drop PCT] [--report FILE]";
+
+/// Usage text for the `--replay` dry run.
+const DRY_RUN_USAGE: &str = "usage: raydium-alerts --replay <SIGNATURE>";
+
+/// Default `rpc-bench` test window.
+const DEFAULT_BENCH_SECS: u64 = 60;
+
+/// Default `soak` test duration (4 hours).
+const DEFAULT_SOAK_SECS: u64 = 4 * 60 * 60;
+
+/// Default time between `soak` samples.
+const DEFAULT_SOAK_INTERVAL_SECS: u64 = 60;
+
+/// Default `soak` warm-up before samples are compared.
+const DEFAULT_SOAK_WARMUP_SECS: u64 = 10 * 60;
+
+/// Default maximum growth of resident memory and drop of throughput in `soak`, in percent.
+const DEFAULT_SOAK_TOLERANCE_PCT: f64 = 10.0;
+
+/// Parsed `archive` subcommand arguments.
+#[derive(Debug, PartialEq, Eq)]
+struct ArchiveArgs {
+    /// Archive directory
+    dir: String,
+    /// Event filter
+    query: ArchiveQuery,
+    /// Print the block index instead of events
+    index: bool,
+}
+
+impl ArchiveArgs {
+    /// Parses the arguments following `archive`.
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut dir = None;
+        let mut query = ArchiveQuery::default();
+        let mut index = false;
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = |name: &str| {
+                args.next()
+                    .cloned()
+                    .ok_or_else(|| format!("{name} requires a value"))
+            };
+            let number = |name: &str, value: String| {
+                value
+                    .parse::<u64>()
+                    .map_err(|_| format!("{name}: invalid number '{value}'"))
+            };
+
+            match arg.as_str() {
+                "--from-slot" => query.from_slot = Some(number(arg, value(arg)?)?),
+                "--to-slot" => query.to_slot = Some(number(arg, value(arg)?)?),
+                "--pool" => query.pool = Some(value(arg)?),
+                "--limit" => query.limit = Some(number(arg, value(arg)?)? as usize),
+                "--index" => index = true,
+                flag if flag.starts_with("--") => return Err(format!("unknown option {flag}")),
+                _ if dir.is_none() => dir = Some(arg.clone()),
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        Ok(Self {
+            dir: dir.ok_or("missing archive directory")?,
+            query,
+            index,
+        })
+    }
+}
+
+/// Parsed `replay` subcommand arguments.
+#[derive(Debug, PartialEq)]
+struct ReplayArgs {
+    /// Archive directory and event filter
+    archive: ArchiveArgs,
+    /// Seconds of block time replayed per second, or `None` for no pacing
+    speed: Option<f64>,
+}
+
+impl ReplayArgs {
+    /// Parses the arguments following `replay`: the `archive` filters and `--speed`.
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut speed = None;
+        let mut rest = Vec::new();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--speed" => {
+                    let value = args.next().ok_or("--speed requires a value")?;
+                    speed = match value.as_str() {
+                        "max" => None,
+                        _ => Some(
+                            value
+                                .parse::<f64>()
+                                .ok()
+                                .filter(|speed| speed.is_finite() && *speed > 0.0)
+                                .ok_or_else(|| format!("--speed: invalid speed '{value}'"))?,
+                        ),
+                    };
+                }
+                "--index" => return Err("unknown option --index".to_string()),
+                _ => rest.push(arg.clone()),
+            }
+        }
+
+        Ok(Self {
+            archive: ArchiveArgs::parse(&rest)?,
+            speed,
+        })
+    }
+}
+
+/// Parsed `rpc-bench` subcommand arguments.
+#[derive(Debug, PartialEq, Eq)]
+struct RpcBenchArgs {
+    /// WebSocket endpoints to compare
+    urls: Vec<String>,
+    /// Test window
+    duration: Duration,
+}
+
+impl RpcBenchArgs {
+    /// Parses the arguments following `rpc-bench`.
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut urls = Vec::new();
+        let mut secs = DEFAULT_BENCH_SECS;
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--duration" => {
+                    let value = args.next().ok_or("--duration requires a value")?;
+                    secs = value
+                        .parse::<u64>()
+                        .ok()
+                        .filter(|secs| *secs > 0)
+                        .ok_or_else(|| format!("--duration: invalid number '{value}'"))?;
+                }
+                flag if flag.starts_with("--") => return Err(format!("unknown option {flag}")),
+                _ => urls.push(arg.clone()),
+            }
+        }
+
+        if urls.len() < 2 {
+            return Err("at least two WebSocket endpoints are required".to_string());
+        }
+        Ok(Self {
+            urls,
+            duration: Duration::from_secs(secs),
+        })
+    }
+}
+
+/// Parses the arguments following `soak`.
+fn parse_soak(args: &[String]) -> Result<SoakConfig, String> {
+    let mut recording = None;
+    let mut duration = DEFAULT_SOAK_SECS;
+    let mut interval = DEFAULT_SOAK_INTERVAL_SECS;
+    let mut warmup = DEFAULT_SOAK_WARMUP_SECS;
+    let mut max_memory_growth_pct = DEFAULT_SOAK_TOLERANCE_PCT;
+    let mut max_throughput_drop_pct = DEFAULT_SOAK_TOLERANCE_PCT;
+    let mut report = None;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .cloned()
+                .ok_or_else(|| format!("{name} requires a value"))
+        };
+        let secs = |name: &str, value: String| {
+            value
+                .parse::<u64>()
+                .map_err(|_| format!("{name}: invalid number '{value}'"))
+        };
+        let pct = |name: &str, value: String| {
+            value
+                .parse::<f64>()
+                .ok()
+                .filter(|pct| pct.is_finite() && *pct >= 0.0)
+                .ok_or_else(|| format!("{name}: invalid percentage '{value}'"))
+        };
+
+        match arg.as_str() {
+            "--duration" => duration = secs(arg, value(arg)?)?,
+            "--interval" => interval = secs(arg, value(arg)?)?,
+            "--warmup" => warmup = secs(arg, value(arg)?)?,
+            "--max-memory-growth" => max_memory_growth_pct = pct(arg, value(arg)?)?,
+            "--max-throughput-drop" => max_throughput_drop_pct = pct(arg, value(arg)?)?,
+            "--report" => report = Some(PathBuf::from(value(arg)?)),
+            flag if flag.starts_with("--") => return Err(format!("unknown option {flag}")),
+            _ if recording.is_none() => recording = Some(PathBuf::from(arg)),
+            _ => return Err(format!("unexpected argument '{arg}'")),
+        }
+    }
+
+    if interval == 0 {
+        return Err("--interval must be at least 1 second".to_string());
+    }
+    if duration < warmup + 2 * interval {
+        return Err(format!(
+            "--duration must cover the warm-up and two intervals ({}s)",
+            warmup + 2 * interval
+        ));
+    }
+    Ok(SoakConfig {
+        recording: recording.ok_or("missing recording file or directory")?,
+        duration: Duration::from_secs(duration),
+        interval: Duration::from_secs(interval),
+        warmup: Duration::from_secs(warmup),
+        max_memory_growth_pct,
+        max_throughput_drop_pct,
+        report,
+    })
+}
+
+/// Removes the `--migrate` flag from `args`.
+///
+/// # Returns
+///
+/// `true` if the flag was given.
+pub fn take_migrate_flag(args: &mut Vec<String>) -> bool {
+    let before = args.len();
+    args.retain(|arg| arg != "--migrate");
+    args.len() != before
+}
+
+/// Returns the soak test configuration if `args` (without the program name)
+/// name the `soak` subcommand.
+pub fn soak(args: &[String]) -> Option<Result<SoakConfig, String>> {
+    match args.first().map(String::as_str) {
+        Some("soak") => Some(parse_soak(&args[1..]).map_err(|e| format!("{e}\n{SOAK_USAGE}"))),
+        _ => None,
+    }
+}
+
+/// Runs a subcommand if `args` (without the program name) names one.
+///
+/// # Returns
+///
+/// `None` if no subcommand was given, or `soak`, and the pipeline should start.
+pub async fn run(args: &[String]) -> Option<Result<(), String>> {
+    match args.first().map(String::as_str) {
+        Some("archive") => Some(archive(&args[1..])),
+        Some("replay") => Some(replay(&args[1..])),
+        Some("rules") => Some(rules(&args[1..])),
+        Some("rpc-bench") => Some(rpc_bench(&args[1..]).await),
+        Some("--replay") => Some(dry_run(&args[1..]).await),
+        _ => None,
+    }
+}
+
+/// Compares block delivery of WebSocket endpoints and prints one line per endpoint.
+async fn rpc_bench(args: &[String]) -> Result<(), String> {
+    let args = RpcBenchArgs::parse(args).map_err(|e| format!("{e}\n{RPC_BENCH_USAGE}"))?;
+    eprintln!(
+        "Comparing {} endpoint(s) for {}s...",
+        args.urls.len(),
+        args.duration.as_secs()
+    );
+    let reports = rpc_bench::run(args.urls, args.duration).await?;
+
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout, "{}", rpc_bench::ProviderReport::header()).map_err(|e| e.to_string())?;
+    for report in &reports {
+        writeln!(stdout, "{report}").map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Runs one transaction through the processors and prints the events they
+/// would have emitted to stdout, and the decoded instructions to stderr.
+async fn dry_run(args: &[String]) -> Result<(), String> {
+    let [signature] = args else {
+        return Err(format!("expected a transaction signature\n{DRY_RUN_USAGE}"));
+    };
+    let report = DryRun::from_env()?.run(signature).await?;
+
+    for decoded in &report.instructions {
+        eprintln!(
+            "{} instruction {}: {}",
+            decoded.market.name(),
+            decoded.location,
+            decoded.instruction
+        );
+    }
+    let mut stdout = io::stdout().lock();
+    for emitted in &report.events {
+        let line = serde_json::to_string(&emitted.event).map_err(|e| e.to_string())?;
+        writeln!(stdout, "{line}").map_err(|e| e.to_string())?;
+        if !emitted.rules.is_empty() {
+            eprintln!("Matches rule(s): {}", emitted.rules.join(", "));
+        }
+    }
+    eprintln!("{}", report.verdict());
+    Ok(())
+}
+
+/// Prints archived events (or the block index) to stdout.
+fn archive(args: &[String]) -> Result<(), String> {
+    let args = ArchiveArgs::parse(args).map_err(|e| format!("{e}\n{ARCHIVE_USAGE}"))?;
+    let reader = ArchiveReader::new(&args.dir);
+    let mut stdout = io::stdout().lock();
+
+    if args.index {
+        for block in reader.blocks().map_err(|e| e.to_string())? {
+            let line = serde_json::to_string(&block).map_err(|e| e.to_string())?;
+            writeln!(stdout, "{line}").map_err(|e| e.to_string())?;
+        }
+        return Ok(());
+    }
+
+    let mut write_error = None;
+    let count = reader
+        .for_each(&args.query, |event| {
+            if write_error.is_some() {
+                return;
+            }
+            let result = serde_json::to_string(event)
+                .map_err(|e| e.to_string())
+                .and_then(|line| writeln!(stdout, "{line}").map_err(|e| e.to_string()));
+            write_error = result.err();
+        })
+        .map_err(|e| e.to_string())?;
+
+    match write_error {
+        Some(e) => Err(e),
+        None => {
+            eprintln!("{count} event(s)");
+            Ok(())
+        }
+    }
+}
+
+/// Replays archived events through the analytics subsystems and prints their outputs.
+///
+/// Candle intervals, volume summaries, and MEV detection are configured like
+/// the live pipeline (`CANDLE_INTERVALS`, `AGGREGATION_*`, `MEV_*`).
+fn replay(args: &[String]) -> Result<(), String> {
+    let args = ReplayArgs::parse(args).map_err(|e| format!("{e}\n{REPLAY_USAGE}"))?;
+    let mut replay = Replay::new(CandleConfig::from_env().intervals);
+    if let Some(config) = AggregationConfig::from_env() {
+        replay = replay.with_aggregation(config);
+    }
+    if let Some(config) = MevConfig::from_env() {
+        replay = replay.with_mev(config);
+    }
+    let mut pacer = args.speed.map(Pacer::new);
+    let reader = ArchiveReader::new(&args.archive.dir);
+    let mut stdout = io::stdout().lock();
+
+    let mut write = |outputs: Vec<ReplayOutput>| -> Result<usize, String> {
+        for output in &outputs {
+            let line = serde_json::to_string(output).map_err(|e| e.to_string())?;
+            writeln!(stdout, "{line}").map_err(|e| e.to_string())?;
+        }
+        Ok(outputs.len())
+    };
+
+    let mut written = 0;
+    let mut write_error = None;
+    let count = reader
+        .for_each(&args.archive.query, |event| {
+            if write_error.is_some() {
+                return;
+            }
+            let outputs = replay.process(event);
+            if let (Some(pacer), Some(now)) = (pacer.as_mut(), replay.clock().now()) {
+                std::thread::sleep(pacer.delay(now, Instant::now()));
+            }
+            match write(outputs) {
+                Ok(n) => written += n,
+                Err(e) => write_error = Some(e),
+            }
+        })
+        .map_err(|e| e.to_string())?;
+    if let Some(e) = write_error {
+        return Err(e);
+    }
+    written += write(replay.finish())?;
+
+    eprintln!("{count} event(s) replayed, {written} output(s)");
+    Ok(())
+}
+
+/// Resolves the rules file of `rules test` from its arguments or `RULES_FILE`.
+fn rules_test_path(args: &[String]) -> Result<String, String> {
+    match args {
+        [command] if command == "test" => env::var("RULES_FILE")
+            .ok()
+            .filter(|path| !path.trim().is_empty())
+            .ok_or_else(|| "no rules file given and RULES_FILE is not set".to_string()),
+        [command, path] if command == "test" => Ok(path.clone()),
+        _ => Err("expected 'test' and an optional rules file".to_string()),
+    }
+}
+
+/// Runs the fixtures of a rules file, printing one line per fixture.
+fn rules(args: &[String]) -> Result<(), String> {
+    let path = rules_test_path(args).map_err(|e| format!("{e}\n{RULES_USAGE}"))?;
+    let file = RulesFile::load(&path).map_err(|e| e.to_string())?;
+    let outcomes = file.run_fixtures();
+
+    let mut stdout = io::stdout().lock();
+    let mut failed = 0;
+    for outcome in &outcomes {
+        let expectation = |fired| if fired { "match" } else { "no match" };
+        let line = if outcome.passed() {
+            format!("ok   {} :: {}", outcome.rule, outcome.fixture)
+        } else {
+            failed += 1;
+            format!(
+                "FAIL {} :: {} (expected {}, got {})",
+                outcome.rule,
+                outcome.fixture,
+                expectation(outcome.expected),
+                expectation(outcome.actual)
+            )
+        };
+        writeln!(stdout, "{line}").map_err(|e| e.to_string())?;
+    }
+
+    eprintln!(
+        "{} fixture(s): {} passed, {failed} failed",
+        outcomes.len(),
+        outcomes.len() - failed
+    );
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{failed} rule fixture(s) failed")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_parse_archive_args() {
+        let parsed = ArchiveArgs::parse(&args(&[
+            "/data/archive",
+            "--from-slot",
+            "100",
+            "--pool",
+            "pool",
+            "--limit",
+            "5",
+        ]))
+        .unwrap();
+
+        assert_eq!(parsed.dir, "/data/archive");
+        assert_eq!(parsed.query.from_slot, Some(100));
+        assert_eq!(parsed.query.to_slot, None);
+        assert_eq!(parsed.query.pool.as_deref(), Some("pool"));
+        assert_eq!(parsed.query.limit, Some(5));
+        assert!(!parsed.index);
+
+        assert!(ArchiveArgs::parse(&args(&["--index"])).is_err());
+        assert!(ArchiveArgs::parse(&args(&["dir", "--to-slot", "x"])).is_err());
+        assert!(ArchiveArgs::parse(&args(&["dir", "--bogus"])).is_err());
+        assert!(run(&args(&["serve"])).await.is_none());
+    }
+
+    #[test]
+    fn test_parse_replay_args() {
+        let parsed =
+            ReplayArgs::parse(&args(&["/data/archive", "--speed", "60", "--pool", "pool"]))
+                .unwrap();
+        assert_eq!(parsed.archive.dir, "/data/archive");
+        assert_eq!(parsed.archive.query.pool.as_deref(), Some("pool"));
+        assert_eq!(parsed.speed, Some(60.0));
+
+        let parsed = ReplayArgs::parse(&args(&["dir", "--speed", "max"])).unwrap();
+        assert_eq!(parsed.speed, None);
+
+        assert!(ReplayArgs::parse(&args(&["dir", "--speed", "0"])).is_err());
+        assert!(ReplayArgs::parse(&args(&["dir", "--speed"])).is_err());
+        assert!(ReplayArgs::parse(&args(&["dir", "--index"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_rpc_bench_args() {
+        let parsed =
+            RpcBenchArgs::parse(&args(&["wss://a", "--duration", "30", "wss://b"])).unwrap();
+        assert_eq!(parsed.urls, ["wss://a", "wss://b"]);
+        assert_eq!(parsed.duration, Duration::from_secs(30));
+
+        let parsed = RpcBenchArgs::parse(&args(&["wss://a", "wss://b", "wss://c"])).unwrap();
+        assert_eq!(parsed.duration, Duration::from_secs(DEFAULT_BENCH_SECS));
+
+        assert!(RpcBenchArgs::parse(&args(&["wss://a"])).is_err());
+        assert!(RpcBenchArgs::parse(&args(&["wss://a", "wss://b", "--duration", "0"])).is_err());
+        assert!(RpcBenchArgs::parse(&args(&["wss://a", "wss://b", "--bogus"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_soak_args() {
+        let parsed = soak(&args(&[
+            "soak",
+            "recording.jsonl",
+            "--duration",
+            "3600",
+            "--max-memory-growth",
+            "5",
+            "--report",
+            "soak.json",
+        ]))
+        .unwrap()
+        .unwrap();
+        assert_eq!(parsed.recording, PathBuf::from("recording.jsonl"));
+        assert_eq!(parsed.duration, Duration::from_secs(3600));
+        assert_eq!(
+            parsed.interval,
+            Duration::from_secs(DEFAULT_SOAK_INTERVAL_SECS)
+        );
+        assert_eq!(parsed.max_memory_growth_pct, 5.0);
+        assert_eq!(parsed.max_throughput_drop_pct, DEFAULT_SOAK_TOLERANCE_PCT);
+        assert_eq!(parsed.report, Some(PathBuf::from("soak.json")));
+
+        assert!(soak(&args(&["archive", "dir"])).is_none());
+
+        let mut migrate = args(&["--migrate", "soak", "recording.jsonl"]);
+        assert!(take_migrate_flag(&mut migrate));
+        assert_eq!(migrate, args(&["soak", "recording.jsonl"]));
+        assert!(!take_migrate_flag(&mut migrate));
+        assert!(parse_soak(&args(&[])).is_err());
+        assert!(parse_soak(&args(&["rec", "--duration", "600"])).is_err());
+        assert!(parse_soak(&args(&["rec", "--interval", "0"])).is_err());
+        assert!(parse_soak(&args(&["rec", "--max-throughput-drop", "-1"])).is_err());
+    }
+
+    #[test]
+    fn test_rules_test_path() {
+        assert_eq!(
+            rules_test_path(&args(&["test", "rules.toml"])),
+            Ok("rules.toml".to_string())
+        );
+        assert!(rules_test_path(&args(&["check", "rules.toml"])).is_err());
+        assert!(rules_test_path(&args(&["test", "a", "b"])).is_err());
+        assert!(rules_test_path(&args(&[])).is_err());
+    }
+}
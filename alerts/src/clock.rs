@@ -0,0 +1,326 @@
+//! Clock of the timing features and host clock drift detection.
+//!
+//! Cooldowns, staleness guards, quota and digest windows, slot batches, and
+//! schedules read the time from the installed [`Clock`] instead of the host
+//! clock: [`now`] for monotonic deadlines and [`unix_now`] for wall clock
+//! windows (e.g., 24h token stats, candle rollups, retention, SLO windows).
+//!
+//! A skewed host clock silently breaks the wall clock features: block times
+//! look stale or from the future, and digest windows close early or late. The
+//! supervisor therefore compares the time of live blocks with the host clock
+//! ([`DriftMonitor`]). The offset is the median of the last [`DRIFT_SAMPLES`]
+//! blocks and includes the few seconds a block takes to be confirmed; a
+//! warning is logged when it exceeds `CLOCK_DRIFT_WARN_SECS`. With
+//! `CLOCK_SOURCE=block`, [`unix_now`] follows the block times instead (the host
+//! clock corrected by that offset), for hosts whose clock cannot be fixed.
+
+use std::{
+    collections::VecDeque,
+    env, fmt,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, AtomicI64, Ordering},
+        Mutex, OnceLock, PoisonError,
+    },
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// Blocks the offset to the host clock is the median of.
+pub const DRIFT_SAMPLES: usize = 31;
+
+/// Blocks observed before drift is reported.
+const MIN_DRIFT_SAMPLES: usize = 5;
+
+/// Default offset to block times above which drift is reported.
+const DEFAULT_DRIFT_WARN_SECS: u64 = 30;
+
+/// Offset value before the first block.
+const NO_OFFSET: i64 = i64::MIN;
+
+static CLOCK: OnceLock<Box<dyn Clock>> = OnceLock::new();
+static DRIFT: OnceLock<DriftMonitor> = OnceLock::new();
+
+/// Source of the time read by the timing features.
+pub trait Clock: Send + Sync {
+    /// Returns the monotonic time of deadlines and cooldowns.
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    /// Returns the wall clock time in Unix seconds.
+    fn unix_now(&self) -> i64;
+}
+
+/// The host clock.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn unix_now(&self) -> i64 {
+        host_unix_now()
+    }
+}
+
+/// The host clock corrected by its offset to block times.
+///
+/// Until the first block is observed, this is the host clock.
+pub struct BlockClock;
+
+impl Clock for BlockClock {
+    fn unix_now(&self) -> i64 {
+        host_unix_now() - drift().offset().unwrap_or(0)
+    }
+}
+
+/// Returns the host clock in Unix seconds.
+fn host_unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default()
+}
+
+/// Clock the timing features read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClockSource {
+    /// The host clock (default)
+    #[default]
+    System,
+    /// The host clock corrected by its offset to block times
+    Block,
+}
+
+impl FromStr for ClockSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "system" => Ok(Self::System),
+            "block" => Ok(Self::Block),
+            other => Err(format!(
+                "unknown clock source '{other}' (expected system or block)"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ClockSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::System => write!(f, "system"),
+            Self::Block => write!(f, "block"),
+        }
+    }
+}
+
+/// Configuration for the clock and drift detection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockConfig {
+    /// Clock the timing features read
+    pub source: ClockSource,
+    /// Offset to block times above which drift is reported (`None` never reports)
+    pub drift_warn_secs: Option<u64>,
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        Self {
+            source: ClockSource::default(),
+            drift_warn_secs: Some(DEFAULT_DRIFT_WARN_SECS),
+        }
+    }
+}
+
+impl ClockConfig {
+    /// Creates clock configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `CLOCK_SOURCE` - Optional: `system` (host clock) or `block` (host clock
+    ///   corrected by its offset to block times) (default: system)
+    /// - `CLOCK_DRIFT_WARN_SECS` - Optional: Offset of the host clock to block times
+    ///   above which a warning is logged, 0 disables (default: 30)
+    pub fn from_env() -> Self {
+        let source = match env::var("CLOCK_SOURCE") {
+            Ok(source) if !source.trim().is_empty() => source.parse().unwrap_or_else(|e| {
+                log::warn!("Invalid CLOCK_SOURCE: {e}, using the system clock");
+                ClockSource::System
+            }),
+            _ => ClockSource::System,
+        };
+        let drift_warn_secs = env::var("CLOCK_DRIFT_WARN_SECS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_DRIFT_WARN_SECS);
+
+        Self {
+            source,
+            drift_warn_secs: (drift_warn_secs > 0).then_some(drift_warn_secs),
+        }
+    }
+
+    /// Installs the clock returned by [`clock`] (see [`install`]) and the drift
+    /// threshold of [`drift`].
+    ///
+    /// Must be called before the time is read; later calls are ignored.
+    pub fn install(self) {
+        let drift = DriftMonitor::new(self.drift_warn_secs);
+        let clock: Box<dyn Clock> = match self.source {
+            ClockSource::System => Box::new(SystemClock),
+            ClockSource::Block => Box::new(BlockClock),
+        };
+        if DRIFT.set(drift).is_err() {
+            log::warn!("Clock drift threshold already installed, ignoring");
+        }
+        if install(clock).is_err() {
+            log::warn!("Clock already installed, ignoring");
+        }
+    }
+}
+
+/// Installs the clock the timing features read, e.g. a fake clock in tests
+/// or an embedder's own time source.
+///
+/// Must be called before the time is read; returns the clock back if one is
+/// already installed.
+pub fn install(clock: Box<dyn Clock>) -> Result<(), Box<dyn Clock>> {
+    CLOCK.set(clock)
+}
+
+/// Returns the installed clock, or the host clock.
+pub fn clock() -> &'static dyn Clock {
+    CLOCK.get_or_init(|| Box::new(SystemClock)).as_ref()
+}
+
+/// Returns the monotonic time of the installed clock.
+pub fn now() -> Instant {
+    clock().now()
+}
+
+/// Returns the wall clock time of the installed clock in Unix seconds.
+pub fn unix_now() -> i64 {
+    clock().unix_now()
+}
+
+/// Returns the drift monitor fed by the supervisor.
+pub fn drift() -> &'static DriftMonitor {
+    DRIFT.get_or_init(|| DriftMonitor::new(Some(DEFAULT_DRIFT_WARN_SECS)))
+}
+
+/// Offset of the host clock to block times.
+pub struct DriftMonitor {
+    /// Offset above which drift is reported
+    warn_secs: Option<i64>,
+    /// Offsets of the last blocks, oldest first
+    samples: Mutex<VecDeque<i64>>,
+    /// Median of `samples`, [`NO_OFFSET`] before the first block
+    offset: AtomicI64,
+    /// Whether drift is currently reported
+    drifting: AtomicBool,
+}
+
+impl DriftMonitor {
+    fn new(warn_secs: Option<u64>) -> Self {
+        Self {
+            warn_secs: warn_secs.map(|secs| secs as i64),
+            samples: Mutex::new(VecDeque::with_capacity(DRIFT_SAMPLES)),
+            offset: AtomicI64::new(NO_OFFSET),
+            drifting: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns how many seconds the host clock is ahead of block times
+    /// (negative if behind), once a block was observed.
+    pub fn offset(&self) -> Option<i64> {
+        let offset = self.offset.load(Ordering::Relaxed);
+        (offset != NO_OFFSET).then_some(offset)
+    }
+
+    /// Records the time of a live block and returns the updated offset.
+    ///
+    /// Logs a warning when the offset exceeds the threshold, and once more
+    /// when it is back within it.
+    pub fn observe(&self, block_time: i64) -> i64 {
+        self.observe_at(block_time, host_unix_now())
+    }
+
+    fn observe_at(&self, block_time: i64, host_now: i64) -> i64 {
+        let (offset, observed) = {
+            let mut samples = self.samples.lock().unwrap_or_else(PoisonError::into_inner);
+            if samples.len() == DRIFT_SAMPLES {
+                samples.pop_front();
+            }
+            samples.push_back(host_now - block_time);
+            let mut sorted: Vec<i64> = samples.iter().copied().collect();
+            sorted.sort_unstable();
+            (sorted[sorted.len() / 2], sorted.len())
+        };
+        self.offset.store(offset, Ordering::Relaxed);
+
+        let Some(warn_secs) = self.warn_secs else {
+            return offset;
+        };
+        if observed < MIN_DRIFT_SAMPLES {
+            return offset;
+        }
+        let drifting = offset.abs() > warn_secs;
+        if drifting != self.drifting.swap(drifting, Ordering::Relaxed) {
+            if drifting {
+                log::warn!(
+                    "Host clock is {}s {} block times (more than CLOCK_DRIFT_WARN_SECS={warn_secs}); \
+                     staleness guards and time windows are off by as much. Sync the host clock \
+                     (NTP) or set CLOCK_SOURCE=block",
+                    offset.abs(),
+                    if offset > 0 { "ahead of" } else { "behind" }
+                );
+            } else {
+                log::info!("Host clock back within {warn_secs}s of block times ({offset}s)");
+            }
+        }
+        offset
+    }
+
+    /// Returns `true` while the offset exceeds the threshold.
+    pub fn is_drifting(&self) -> bool {
+        self.drifting.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drift_is_median_of_block_offsets() {
+        let monitor = DriftMonitor::new(Some(30));
+        assert_eq!(monitor.offset(), None);
+
+        // Blocks confirmed 2s after their time, one outlier
+        for (block_time, host_now) in [(100, 102), (101, 103), (102, 500), (103, 105)] {
+            monitor.observe_at(block_time, host_now);
+        }
+        assert_eq!(monitor.offset(), Some(2));
+        assert!(!monitor.is_drifting());
+
+        // The host clock jumps 60s ahead
+        for block_time in 104..104 + DRIFT_SAMPLES as i64 {
+            monitor.observe_at(block_time, block_time + 62);
+        }
+        assert_eq!(monitor.offset(), Some(62));
+        assert!(monitor.is_drifting());
+
+        // Behind block times, and back in sync
+        assert!(monitor.observe_at(1_000, 900) > 0);
+        for block_time in 2_000..2_000 + DRIFT_SAMPLES as i64 {
+            monitor.observe_at(block_time, block_time - 45);
+        }
+        assert_eq!(monitor.offset(), Some(-45));
+        assert!(monitor.is_drifting());
+        for block_time in 3_000..3_000 + DRIFT_SAMPLES as i64 {
+            monitor.observe_at(block_time, block_time + 1);
+        }
+        assert!(!monitor.is_drifting());
+
+        assert_eq!("Block".parse::<ClockSource>(), Ok(ClockSource::Block));
+        assert!("ntp".parse::<ClockSource>().is_err());
+    }
+}
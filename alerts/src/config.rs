@@ -3,9 +3,12 @@
 //! This module provides utilities for loading pubkey-based filters from environment
 //! variables, commonly used for filtering by token mints or AMM pool addresses.
 
-use {solana_pubkey::Pubkey, std::collections::HashSet, std::env, std::str::FromStr};
+use {
+    crate::output::TradeSide, solana_client::rpc_config::RpcBlockSubscribeFilter,
+    solana_pubkey::Pubkey, std::collections::HashSet, std::env, std::str::FromStr,
+};
 
-/// Supported Raydium market types for filtering.
+/// Supported market types for filtering.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MarketType {
     /// Constant Product Market Maker
@@ -14,6 +17,63 @@ pub enum MarketType {
     Clmm,
     /// AMM V4 (legacy with Serum integration)
     AmmV4,
+    /// Launchpad (LaunchLab) bonding curves
+    Launchpad,
+    /// Pump.fun bonding curves
+    PumpFun,
+    /// PumpSwap AMM
+    PumpSwap,
+}
+
+impl MarketType {
+    /// All market types, in pipeline registration order.
+    pub const ALL: [MarketType; 6] = [
+        Self::Cpmm,
+        Self::Clmm,
+        Self::AmmV4,
+        Self::Launchpad,
+        Self::PumpFun,
+        Self::PumpSwap,
+    ];
+
+    /// Returns the market's `FILTER_MARKETS` name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Cpmm => "cpmm",
+            Self::Clmm => "clmm",
+            Self::AmmV4 => "amm_v4",
+            Self::Launchpad => "launchpad",
+            Self::PumpFun => "pumpfun",
+            Self::PumpSwap => "pumpswap",
+        }
+    }
+
+    /// Returns the Solana mainnet ID of the program decoded for this market.
+    ///
+    /// The decoders recognize this ID; deployments on other chains are looked
+    /// up in the [`ChainProfile`](crate::chain::ChainProfile).
+    pub fn program_id(&self) -> Pubkey {
+        match self {
+            Self::Cpmm => carbon_raydium_cpmm_decoder::PROGRAM_ID,
+            Self::Clmm => carbon_raydium_clmm_decoder::PROGRAM_ID,
+            Self::AmmV4 => carbon_raydium_amm_v4_decoder::PROGRAM_ID,
+            Self::Launchpad => carbon_raydium_launchpad_decoder::PROGRAM_ID,
+            Self::PumpFun => carbon_pumpfun_decoder::PROGRAM_ID,
+            Self::PumpSwap => carbon_pump_swap_decoder::PROGRAM_ID,
+        }
+    }
+
+    /// Returns the environment variable prefix for per-market settings.
+    pub fn env_prefix(&self) -> &'static str {
+        match self {
+            Self::Cpmm => "CPMM",
+            Self::Clmm => "CLMM",
+            Self::AmmV4 => "AMM_V4",
+            Self::Launchpad => "LAUNCHPAD",
+            Self::PumpFun => "PUMPFUN",
+            Self::PumpSwap => "PUMPSWAP",
+        }
+    }
 }
 
 impl FromStr for MarketType {
@@ -25,8 +85,11 @@ impl FromStr for MarketType {
             "cpmm" => Ok(Self::Cpmm),
             "clmm" => Ok(Self::Clmm),
             "amm_v4" | "ammv4" | "amm-v4" | "v4" => Ok(Self::AmmV4),
+            "launchpad" | "launchlab" => Ok(Self::Launchpad),
+            "pumpfun" | "pump_fun" | "pump-fun" | "pump" => Ok(Self::PumpFun),
+            "pumpswap" | "pump_swap" | "pump-swap" | "pump_amm" => Ok(Self::PumpSwap),
             _ => Err(format!(
-                "Unknown market type: '{s}'. Valid options: cpmm, clmm, amm_v4"
+                "Unknown market type: '{s}'. Valid options: cpmm, clmm, amm_v4, launchpad, pumpfun, pumpswap"
             )),
         }
     }
@@ -54,7 +117,7 @@ impl FromStr for MarketType {
 ///
 /// // Empty or unset = all markets
 /// let all_markets = parse_market_filter("UNSET_VAR");
-/// assert_eq!(all_markets.len(), 3); // cpmm, clmm, amm_v4
+/// assert_eq!(all_markets.len(), 6); // cpmm, clmm, amm_v4, launchpad, pumpfun, pumpswap
 /// ```
 pub fn parse_market_filter(env_var: &str) -> HashSet<MarketType> {
     env::var(env_var)
@@ -90,13 +153,31 @@ pub fn parse_market_filter(env_var: &str) -> HashSet<MarketType> {
             }
         })
         // Default: all market types enabled
-        .unwrap_or_else(|| {
-            let mut all = HashSet::new();
-            all.insert(MarketType::Cpmm);
-            all.insert(MarketType::Clmm);
-            all.insert(MarketType::AmmV4);
-            all
-        })
+        .unwrap_or_else(|| MarketType::ALL.into_iter().collect())
+}
+
+/// Returns the block subscription filter for the selected markets.
+///
+/// `blockSubscribe` accepts a single program, so blocks are narrowed to
+/// transactions mentioning the program of a lone selected market with a single
+/// deployment; otherwise, all transactions are received and the decoders of
+/// unselected markets are not registered.
+pub fn block_filter(markets: &HashSet<MarketType>) -> RpcBlockSubscribeFilter {
+    let program_id = match markets.iter().next() {
+        Some(&market) if markets.len() == 1 => {
+            match crate::chain::profile().deployments(market).as_slice() {
+                [deployment] => Some(deployment.program_id),
+                _ => None,
+            }
+        }
+        _ => None,
+    };
+    match program_id {
+        Some(program_id) => {
+            RpcBlockSubscribeFilter::MentionsAccountOrProgram(program_id.to_string())
+        }
+        None => RpcBlockSubscribeFilter::All,
+    }
 }
 
 /// Parses a comma-separated list of pubkey addresses from an environment variable.
@@ -119,26 +200,113 @@ pub fn parse_market_filter(env_var: &str) -> HashSet<MarketType> {
 pub fn parse_pubkey_filter(env_var: &str) -> HashSet<Pubkey> {
     env::var(env_var)
         .ok()
-        .map(|val| {
-            val.split(',')
-                .filter_map(|s| {
-                    let trimmed = s.trim();
-                    if trimmed.is_empty() {
-                        return None;
-                    }
-                    match Pubkey::from_str(trimmed) {
-                        Ok(pk) => Some(pk),
-                        Err(e) => {
-                            log::warn!("Invalid pubkey '{}' in {}: {}", trimmed, env_var, e);
-                            None
-                        }
-                    }
-                })
-                .collect()
-        })
+        .map(|val| parse_pubkey_list(env_var, &val))
         .unwrap_or_default()
 }
 
+/// Parses a comma-separated list of pubkey addresses, skipping invalid entries.
+///
+/// # Arguments
+///
+/// * `name` - Setting name used in warnings (e.g., `FILTER_TOKENS`)
+/// * `value` - Comma-separated addresses
+pub fn parse_pubkey_list(name: &str, value: &str) -> HashSet<Pubkey> {
+    value
+        .split(',')
+        .filter_map(|s| {
+            let trimmed = s.trim();
+            if trimmed.is_empty() {
+                return None;
+            }
+            match Pubkey::from_str(trimmed) {
+                Ok(pk) => Some(pk),
+                Err(e) => {
+                    log::warn!("Invalid pubkey '{}' in {}: {}", trimmed, name, e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Parses a trade side filter (`buy`, `sell`, or `both`).
+///
+/// # Arguments
+///
+/// * `name` - Setting name used in warnings (e.g., `FILTER_SIDE`)
+/// * `value` - Side to keep
+///
+/// # Returns
+///
+/// The side to keep, or `None` to keep both. Unknown values are logged and keep both.
+pub fn parse_side_filter(name: &str, value: &str) -> Option<TradeSide> {
+    match value.trim().to_lowercase().as_str() {
+        "buy" | "buys" => Some(TradeSide::Buy),
+        "sell" | "sells" => Some(TradeSide::Sell),
+        "" | "both" | "all" => None,
+        other => {
+            log::warn!("Unknown {name} '{other}'. Valid options: buy, sell, both");
+            None
+        }
+    }
+}
+
+/// Per-decoder processing concurrency settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConcurrencyConfig {
+    /// Number of worker tasks processing instructions in parallel.
+    pub workers: usize,
+    /// Channel capacity per worker before backpressure is applied.
+    pub channel_capacity: usize,
+}
+
+impl Default for ConcurrencyConfig {
+    fn default() -> Self {
+        Self {
+            workers: 1,
+            channel_capacity: 1000,
+        }
+    }
+}
+
+impl ConcurrencyConfig {
+    /// Loads concurrency settings for a market from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `{PREFIX}_WORKERS` - Worker count (default: 1)
+    /// - `{PREFIX}_CHANNEL_CAPACITY` - Per-worker channel capacity (default: 1000)
+    ///
+    /// Where `{PREFIX}` is `CPMM`, `CLMM`, `AMM_V4`, `LAUNCHPAD`, `PUMPFUN`, or `PUMPSWAP`.
+    /// Zero or invalid values fall back to the defaults.
+    pub fn from_env(market: MarketType) -> Self {
+        let defaults = Self::default();
+        let prefix = market.env_prefix();
+
+        Self {
+            workers: parse_positive_usize(&format!("{prefix}_WORKERS")).unwrap_or(defaults.workers),
+            channel_capacity: parse_positive_usize(&format!("{prefix}_CHANNEL_CAPACITY"))
+                .unwrap_or(defaults.channel_capacity),
+        }
+    }
+}
+
+/// Parses a positive integer from an environment variable.
+///
+/// Returns `None` if the variable is unset, empty, zero, or not a number.
+fn parse_positive_usize(env_var: &str) -> Option<usize> {
+    let val = env::var(env_var).ok()?;
+    match val.trim().parse::<usize>() {
+        Ok(n) if n > 0 => Some(n),
+        _ => {
+            if !val.trim().is_empty() {
+                log::warn!("Invalid value '{}' for {}, using default", val, env_var);
+            }
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -169,6 +337,19 @@ mod tests {
         assert_eq!(MarketType::from_str("amm_v4").unwrap(), MarketType::AmmV4);
         assert_eq!(MarketType::from_str("ammv4").unwrap(), MarketType::AmmV4);
         assert_eq!(MarketType::from_str("v4").unwrap(), MarketType::AmmV4);
+        assert_eq!(
+            MarketType::from_str("launchpad").unwrap(),
+            MarketType::Launchpad
+        );
+        assert_eq!(
+            MarketType::from_str("LaunchLab").unwrap(),
+            MarketType::Launchpad
+        );
+        assert_eq!(MarketType::from_str("pump").unwrap(), MarketType::PumpFun);
+        assert_eq!(
+            MarketType::from_str("pump_swap").unwrap(),
+            MarketType::PumpSwap
+        );
         assert!(MarketType::from_str("invalid").is_err());
     }
 
@@ -176,10 +357,13 @@ mod tests {
     fn test_parse_market_filter_default() {
         // Non-existent env var should return all markets
         let result = parse_market_filter("NON_EXISTENT_MARKET_VAR_12345");
-        assert_eq!(result.len(), 3);
+        assert_eq!(result.len(), 6);
         assert!(result.contains(&MarketType::Cpmm));
         assert!(result.contains(&MarketType::Clmm));
         assert!(result.contains(&MarketType::AmmV4));
+        assert!(result.contains(&MarketType::Launchpad));
+        assert!(result.contains(&MarketType::PumpFun));
+        assert!(result.contains(&MarketType::PumpSwap));
     }
 
     #[test]
@@ -202,12 +386,68 @@ mod tests {
         env::remove_var("TEST_SINGLE_MARKET");
     }
 
+    #[test]
+    fn test_block_filter() {
+        let all: HashSet<MarketType> = MarketType::ALL.into_iter().collect();
+        assert!(matches!(block_filter(&all), RpcBlockSubscribeFilter::All));
+
+        env::set_var("TEST_FILTER_MARKET", "pumpswap");
+        let pumpswap = parse_market_filter("TEST_FILTER_MARKET");
+        match block_filter(&pumpswap) {
+            RpcBlockSubscribeFilter::MentionsAccountOrProgram(program) => {
+                assert_eq!(program, carbon_pump_swap_decoder::PROGRAM_ID.to_string())
+            }
+            other => panic!("unexpected filter: {other:?}"),
+        }
+        env::remove_var("TEST_FILTER_MARKET");
+    }
+
+    #[test]
+    fn test_market_names_round_trip() {
+        for market in MarketType::ALL {
+            assert_eq!(MarketType::from_str(market.name()).unwrap(), market);
+        }
+    }
+
     #[test]
     fn test_parse_market_filter_empty_string() {
         env::set_var("TEST_EMPTY_MARKET", "");
         let result = parse_market_filter("TEST_EMPTY_MARKET");
         // Empty string should return all markets (default)
-        assert_eq!(result.len(), 3);
+        assert_eq!(result.len(), 6);
         env::remove_var("TEST_EMPTY_MARKET");
     }
+
+    #[test]
+    fn test_parse_side_filter() {
+        assert_eq!(
+            parse_side_filter("FILTER_SIDE", " Sell "),
+            Some(TradeSide::Sell)
+        );
+        assert_eq!(
+            parse_side_filter("FILTER_SIDE", "buy"),
+            Some(TradeSide::Buy)
+        );
+        assert_eq!(parse_side_filter("FILTER_SIDE", "both"), None);
+        assert_eq!(parse_side_filter("FILTER_SIDE", ""), None);
+        assert_eq!(parse_side_filter("FILTER_SIDE", "long"), None);
+    }
+
+    #[test]
+    fn test_concurrency_config_default() {
+        let config = ConcurrencyConfig::from_env(MarketType::Cpmm);
+        assert_eq!(config, ConcurrencyConfig::default());
+    }
+
+    #[test]
+    fn test_concurrency_config_from_env() {
+        env::set_var("AMM_V4_WORKERS", "4");
+        env::set_var("AMM_V4_CHANNEL_CAPACITY", "0");
+        let config = ConcurrencyConfig::from_env(MarketType::AmmV4);
+        assert_eq!(config.workers, 4);
+        // Zero is invalid and falls back to the default
+        assert_eq!(config.channel_capacity, 1000);
+        env::remove_var("AMM_V4_WORKERS");
+        env::remove_var("AMM_V4_CHANNEL_CAPACITY");
+    }
 }
@@ -0,0 +1,345 @@
+//! Liquidity drain (rug-pull) detection.
+//!
+//! This module provides:
+//! - [`DrainConfig`] - Reserve and supply share thresholds
+//! - [`DrainDetector`] - Checks withdrawals against tracked pool reserves and
+//!   creator sells against token supply
+//!
+//! Processors feed every delivered event to the detector. A `RemoveLiquidity`
+//! event removing at least `DRAIN_LIQUIDITY_PCT` of either reserve tracked in
+//! the enrichment cache raises a `LiquidityDrain` event, and moves the tracked
+//! reserves by its amounts. Pool creators are learned from `CreatePool`
+//! events; a single sell by a pool's creator of at least
+//! `DRAIN_CREATOR_SUPPLY_PCT` of the token's supply raises one as well. Pools
+//! created before startup have no known creator.
+
+use {
+    crate::{
+        enrichment::EnrichmentCache,
+        output::{DrainKind, EventType, LiquidityDrainEvent, SwapEvent, TradeSide},
+    },
+    solana_pubkey::Pubkey,
+    std::{
+        collections::{HashMap, VecDeque},
+        env,
+        str::FromStr,
+        sync::{Arc, Mutex, PoisonError},
+    },
+};
+
+/// Pools whose creator or last withdrawal is remembered.
+const MAX_POOLS: usize = 10_000;
+
+/// Configuration for liquidity drain detection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DrainConfig {
+    /// Minimum share of a tracked reserve removed by one withdrawal, in percent
+    pub liquidity_pct: Option<f64>,
+    /// Minimum share of a token's supply sold by the pool creator in one swap, in percent
+    pub creator_supply_pct: Option<f64>,
+}
+
+impl DrainConfig {
+    /// Creates drain detection configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `DRAIN_LIQUIDITY_PCT` - Optional: Share of a pool's reserve removed by a
+    ///   single withdrawal that raises an alert, in percent
+    /// - `DRAIN_CREATOR_SUPPLY_PCT` - Optional: Share of a token's supply sold by
+    ///   the pool creator in a single swap that raises an alert, in percent
+    ///
+    /// # Returns
+    ///
+    /// `Some(DrainConfig)` if either variable is set to a valid percentage,
+    /// `None` otherwise.
+    pub fn from_env() -> Option<Self> {
+        let config = Self {
+            liquidity_pct: parse_pct("DRAIN_LIQUIDITY_PCT"),
+            creator_supply_pct: parse_pct("DRAIN_CREATOR_SUPPLY_PCT"),
+        };
+        (config.liquidity_pct.is_some() || config.creator_supply_pct.is_some()).then_some(config)
+    }
+}
+
+/// Parses a percentage in `(0, 100]` from an environment variable.
+fn parse_pct(name: &str) -> Option<f64> {
+    let value = env::var(name).ok()?;
+    match value.trim().parse::<f64>() {
+        Ok(pct) if pct > 0.0 && pct <= 100.0 => Some(pct),
+        _ => {
+            log::warn!("Invalid {name} '{value}', expected a percentage in (0, 100]");
+            None
+        }
+    }
+}
+
+/// Returns `amount` as a percentage of `total`, or `None` if `total` is zero.
+fn share_pct(amount: u64, total: u64) -> Option<f64> {
+    (total > 0).then(|| (amount as f64 * 100.0 / total as f64).min(100.0))
+}
+
+/// Creators and last withdrawals of recently seen pools.
+#[derive(Debug, Default)]
+struct PoolState {
+    creators: HashMap<String, String>,
+    /// Pools in the order their creator was learned, oldest first
+    creation_order: VecDeque<String>,
+    /// Signature of each pool's last withdrawal
+    last_withdraw: HashMap<String, String>,
+}
+
+/// Detects withdrawals and creator sells draining a pool.
+pub struct DrainDetector {
+    config: DrainConfig,
+    cache: Arc<EnrichmentCache>,
+    state: Mutex<PoolState>,
+}
+
+impl DrainDetector {
+    /// Creates a detector reading pool reserves and token supplies from the given cache.
+    pub fn new(config: DrainConfig, cache: Arc<EnrichmentCache>) -> Self {
+        Self {
+            config,
+            cache,
+            state: Mutex::new(PoolState::default()),
+        }
+    }
+
+    /// Records an event and returns a `LiquidityDrain` event if it drains a pool.
+    pub fn observe(&self, event: &SwapEvent) -> Option<SwapEvent> {
+        let drain = match event.event_type {
+            EventType::CreatePool => {
+                self.record_creator(event);
+                None
+            }
+            EventType::RemoveLiquidity => self.check_withdraw(event),
+            EventType::Swap => self.check_creator_sell(event),
+            _ => None,
+        }?;
+        Some(SwapEvent::from_liquidity_drain(event, drain))
+    }
+
+    /// Remembers the creator of a new pool, forgetting the oldest pool when full.
+    fn record_creator(&self, event: &SwapEvent) {
+        if self.config.creator_supply_pct.is_none() {
+            return;
+        }
+        let Some(ref created) = event.pool_created else {
+            return;
+        };
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if state.creators.len() >= MAX_POOLS && !state.creators.contains_key(&event.pool) {
+            if let Some(oldest) = state.creation_order.pop_front() {
+                state.creators.remove(&oldest);
+            }
+        }
+        let previous = state
+            .creators
+            .insert(event.pool.clone(), created.creator.clone());
+        if previous.is_none() {
+            state.creation_order.push_back(event.pool.clone());
+        }
+    }
+
+    /// Checks a withdrawal against the pool's tracked reserves.
+    ///
+    /// Legs are matched to the pool's tokens by mint, or by position when the
+    /// event does not carry the mints (CPMM LP change logs). A withdrawal seen
+    /// both as an instruction and as an event log is counted once.
+    fn check_withdraw(&self, event: &SwapEvent) -> Option<LiquidityDrainEvent> {
+        let threshold = self.config.liquidity_pct?;
+        let pool = Pubkey::from_str(&event.pool).ok()?;
+        let info = self.cache.pool(&pool)?;
+        {
+            let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+            if state.last_withdraw.get(&event.pool) == Some(&event.signature) {
+                return None;
+            }
+            if state.last_withdraw.len() >= MAX_POOLS {
+                state.last_withdraw.clear();
+            }
+            state
+                .last_withdraw
+                .insert(event.pool.clone(), event.signature.clone());
+        }
+
+        let mut amounts = [0u64; 2];
+        let legs = [event.input_token.as_ref(), event.output_token.as_ref()];
+        for (position, token) in legs.into_iter().enumerate() {
+            let Some(token) = token else {
+                continue;
+            };
+            let index = match Pubkey::from_str(&token.mint) {
+                Ok(mint) if mint == info.token_mint0 => 0,
+                Ok(mint) if mint == info.token_mint1 => 1,
+                _ => position,
+            };
+            amounts[index] = token.amount_raw;
+        }
+        let (reserve0, reserve1) = self.cache.apply_withdraw(&pool, amounts[0], amounts[1])?;
+
+        let (mint, share) = [
+            (info.token_mint0, share_pct(amounts[0], reserve0)),
+            (info.token_mint1, share_pct(amounts[1], reserve1)),
+        ]
+        .into_iter()
+        .filter_map(|(mint, share)| Some((mint, share?)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))?;
+        (share >= threshold).then(|| LiquidityDrainEvent {
+            kind: DrainKind::LiquidityRemoved,
+            mint: mint.to_string(),
+            share_pct: share,
+        })
+    }
+
+    /// Checks a sell by the pool's creator against the sold token's supply.
+    fn check_creator_sell(&self, event: &SwapEvent) -> Option<LiquidityDrainEvent> {
+        let threshold = self.config.creator_supply_pct?;
+        if event.side != Some(TradeSide::Sell) {
+            return None;
+        }
+        let maker = event.maker.as_deref()?;
+        {
+            let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+            if state.creators.get(&event.pool).map(String::as_str) != Some(maker) {
+                return None;
+            }
+        }
+
+        let token = event.input_token.as_ref()?;
+        let supply = self
+            .cache
+            .token(&Pubkey::from_str(&token.mint).ok()?)?
+            .supply?;
+        let share = share_pct(token.amount_raw, supply)?;
+        (share >= threshold).then(|| LiquidityDrainEvent {
+            kind: DrainKind::CreatorDump,
+            mint: token.mint.clone(),
+            share_pct: share,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            enrichment::{EnrichmentConfig, PoolInfo, TokenMetadata},
+            output::{swap_event::WSOL_MINT, PoolCreatedEvent, Protocol, TokenInfo},
+        },
+    };
+
+    fn detector() -> (DrainDetector, Arc<EnrichmentCache>) {
+        let cache = Arc::new(EnrichmentCache::new(EnrichmentConfig {
+            rpc_http_url: "http://127.0.0.1:1".to_string(),
+            batch_size: 100,
+            concurrency: 1,
+            token_account_cache_size: 16,
+            reserve_refresh: None,
+        }));
+        let config = DrainConfig {
+            liquidity_pct: Some(50.0),
+            creator_supply_pct: Some(10.0),
+        };
+        (DrainDetector::new(config, cache.clone()), cache)
+    }
+
+    #[test]
+    fn test_share_pct() {
+        assert_eq!(share_pct(250, 1_000), Some(25.0));
+        assert_eq!(share_pct(2_000, 1_000), Some(100.0));
+        assert_eq!(share_pct(1, 0), None);
+    }
+
+    #[test]
+    fn test_detects_large_withdrawal() {
+        let (detector, cache) = detector();
+        let pool = Pubkey::new_unique();
+        let token = Pubkey::new_unique();
+        cache.insert_pool(
+            pool,
+            PoolInfo {
+                protocol: Protocol::Cpmm,
+                token_mint0: Pubkey::from_str(WSOL_MINT).unwrap(),
+                token_mint1: token,
+                vault0: Pubkey::new_unique(),
+                vault1: Pubkey::new_unique(),
+                reserve0: Some(1_000),
+                reserve1: Some(4_000),
+            },
+        );
+        let withdraw = |sig: &str, sol: u64, tokens: u64| {
+            SwapEvent::builder()
+                .event_type(EventType::RemoveLiquidity)
+                .protocol(Protocol::Cpmm)
+                .signature(sig)
+                .pool(pool.to_string())
+                .input_token(TokenInfo::new(WSOL_MINT, sol))
+                .output_token(TokenInfo::from_pubkey(&token, tokens))
+                .maker("lp")
+                .build()
+        };
+
+        let alert = detector.observe(&withdraw("pull", 750, 3_000)).unwrap();
+        assert_eq!(alert.event_type, EventType::LiquidityDrain);
+        assert_eq!(alert.signature, "pull");
+        assert_eq!(alert.maker.as_deref(), Some("lp"));
+        let drain = alert.drain.unwrap();
+        assert_eq!(drain.kind, DrainKind::LiquidityRemoved);
+        assert_eq!(drain.share_pct, 75.0);
+        assert_eq!(cache.pool(&pool).unwrap().reserve0, Some(250));
+
+        // The same withdrawal seen again is not counted twice
+        assert!(detector.observe(&withdraw("pull", 750, 3_000)).is_none());
+        assert_eq!(cache.pool(&pool).unwrap().reserve0, Some(250));
+
+        // A small withdrawal of the remaining reserves
+        assert!(detector.observe(&withdraw("trim", 25, 100)).is_none());
+        assert_eq!(cache.pool(&pool).unwrap().reserve1, Some(900));
+    }
+
+    #[test]
+    fn test_detects_creator_dump() {
+        let (detector, cache) = detector();
+        let token = Pubkey::new_unique();
+        cache.insert_token(
+            token,
+            TokenMetadata {
+                symbol: None,
+                decimals: 6,
+                supply: Some(1_000_000),
+            },
+        );
+        let sol = Pubkey::from_str(WSOL_MINT).unwrap();
+        let created = SwapEvent::builder()
+            .event_type(EventType::CreatePool)
+            .protocol(Protocol::Cpmm)
+            .signature("create")
+            .pool("pool")
+            .pool_created(PoolCreatedEvent::new(&sol, &token, &Pubkey::new_unique()))
+            .build();
+        let creator = created.pool_created.as_ref().unwrap().creator.clone();
+        assert!(detector.observe(&created).is_none());
+
+        let sell = |maker: &str, tokens: u64| {
+            SwapEvent::builder()
+                .protocol(Protocol::Cpmm)
+                .signature("sell")
+                .pool("pool")
+                .input_token(TokenInfo::from_pubkey(&token, tokens))
+                .output_token(TokenInfo::new(WSOL_MINT, 1_000))
+                .maker(maker)
+                .build()
+        };
+        let alert = detector.observe(&sell(&creator, 250_000)).unwrap();
+        let drain = alert.drain.unwrap();
+        assert_eq!(drain.kind, DrainKind::CreatorDump);
+        assert_eq!(drain.mint, token.to_string());
+        assert_eq!(drain.share_pct, 25.0);
+
+        assert!(detector.observe(&sell(&creator, 50_000)).is_none());
+        assert!(detector.observe(&sell("someone", 250_000)).is_none());
+    }
+}
@@ -0,0 +1,373 @@
+//! Dry run of a single transaction through the processors.
+//!
+//! `raydium-alerts --replay <SIGNATURE>` answers "why didn't I get an alert
+//! for this transaction": it fetches the transaction from `RPC_HTTP_URL`,
+//! decodes its top-level and inner instructions with the decoders of the
+//! selected markets (`FILTER_MARKETS`), runs them through the processors with
+//! the live filters, and reports the decoded instructions, the events that
+//! would have been emitted, and the rules each event matches.
+//!
+//! Nothing is delivered: the events are captured instead of being sent to
+//! webhooks, storage, streams, or rule destinations. Pool caches resolve the
+//! mints of CLMM, AMM V4, Launchpad, and PumpSwap swaps as in the pipeline.
+
+use {
+    crate::{
+        chain::{self, Deployed, Deployment},
+        config::{parse_market_filter, MarketType},
+        enrichment::{EnrichmentCache, EnrichmentConfig},
+        harness::Capture,
+        output::{EventLog, Notifiers, OutputFormat, Provenance, SwapEvent},
+        processors::{
+            EventEmitter, FilterSet, InstructionInput, PumpFunInstructionProcessor,
+            PumpSwapInstructionProcessor, RaydiumAmmV4InstructionProcessor,
+            RaydiumClmmInstructionProcessor, RaydiumCpmmInstructionProcessor,
+            RaydiumLaunchpadInstructionProcessor,
+        },
+        reload::{LiveConfig, SharedConfig},
+        rules::RuleEngine,
+        soak::transaction_update,
+    },
+    arc_swap::ArcSwap,
+    carbon_core::{
+        instruction::{InstructionDecoder, NestedInstructions},
+        metrics::MetricsCollection,
+        processor::Processor,
+        transaction::TransactionMetadata,
+        transformers::extract_instructions_with_metadata,
+    },
+    carbon_pump_swap_decoder::PumpSwapDecoder,
+    carbon_pumpfun_decoder::PumpfunDecoder,
+    carbon_raydium_amm_v4_decoder::RaydiumAmmV4Decoder,
+    carbon_raydium_clmm_decoder::RaydiumClmmDecoder,
+    carbon_raydium_cpmm_decoder::RaydiumCpmmDecoder,
+    carbon_raydium_launchpad_decoder::RaydiumLaunchpadDecoder,
+    solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcTransactionConfig},
+    solana_signature::Signature,
+    solana_transaction_status_client_types::UiTransactionEncoding,
+    std::{collections::HashSet, fmt, str::FromStr, sync::Arc, time::Duration},
+};
+
+/// Time given to the capturing notifier to receive the emitted events.
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// An instruction of a selected market found in the transaction.
+#[derive(Debug, Clone)]
+pub struct DecodedSummary {
+    /// Market whose decoder decoded the instruction
+    pub market: MarketType,
+    /// Instruction location and invoking programs (e.g., "#2.0 (JUP6Lk → CPMMoo)")
+    pub location: String,
+    /// Decoded instruction
+    pub instruction: String,
+}
+
+/// An event the pipeline would have emitted.
+#[derive(Debug, Clone)]
+pub struct WouldEmit {
+    /// The event, as it would have been delivered
+    pub event: SwapEvent,
+    /// Rules whose condition matches the event
+    pub rules: Vec<String>,
+}
+
+/// Outcome of a dry run.
+#[derive(Debug, Default)]
+pub struct DryRunReport {
+    /// Slot of the transaction
+    pub slot: u64,
+    /// Instructions of the selected markets, in processing order
+    pub instructions: Vec<DecodedSummary>,
+    /// Events that would have been emitted, in emit order
+    pub events: Vec<WouldEmit>,
+}
+
+impl DryRunReport {
+    /// Returns why the transaction did or did not alert.
+    pub fn verdict(&self) -> String {
+        match (self.instructions.len(), self.events.len()) {
+            (0, _) => format!(
+                "No instruction of the selected markets in the transaction (slot {})",
+                self.slot
+            ),
+            (decoded, 0) => format!(
+                "{decoded} instruction(s) decoded, no event emitted: filtered out by \
+                 FILTER_TOKENS, FILTER_AMMS, and WATCH_WALLETS, or not an alerting instruction"
+            ),
+            (decoded, events) => {
+                format!(
+                    "{decoded} instruction(s) decoded, {events} event(s) would have been emitted"
+                )
+            }
+        }
+    }
+}
+
+/// Runs single transactions through the processors of the selected markets.
+pub struct DryRun {
+    rpc_http_url: String,
+    markets: HashSet<MarketType>,
+    /// Live filters and labels, without rules so nothing is delivered
+    config: SharedConfig,
+    rules: Option<Arc<RuleEngine>>,
+    pool_cache: Option<Arc<EnrichmentCache>>,
+    event_log: Arc<EventLog>,
+}
+
+impl DryRun {
+    /// Sets up the processors like the pipeline, from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `RPC_HTTP_URL` - Required: HTTP RPC endpoint the transaction is fetched from
+    /// - `FILTER_MARKETS`, `FILTER_TOKENS`, `FILTER_AMMS`, `WATCH_WALLETS`,
+    ///   `RULES_FILE`, `LABELS_FILE` - As for the pipeline
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `RPC_HTTP_URL` is not set or the live configuration
+    /// is invalid.
+    pub fn from_env() -> Result<Self, String> {
+        let rpc_http_url = chain::profile()
+            .rpc_http_url
+            .clone()
+            .ok_or("--replay requires RPC_HTTP_URL")?;
+        let mut config = LiveConfig::from_env().map_err(|e| e.to_string())?;
+        let rules = config.rules.take();
+        Ok(Self {
+            rpc_http_url,
+            markets: chain::profile().deployed(parse_market_filter("FILTER_MARKETS")),
+            config: Arc::new(ArcSwap::from_pointee(config)),
+            rules,
+            pool_cache: EnrichmentConfig::from_env().map(|c| Arc::new(EnrichmentCache::new(c))),
+            event_log: Arc::new(EventLog::new(OutputFormat::Json, 0)),
+        })
+    }
+
+    /// Fetches a transaction and runs it through the processors.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signature is invalid, the transaction can't be
+    /// fetched or decoded, or a processor fails.
+    pub async fn run(&self, signature: &str) -> Result<DryRunReport, String> {
+        let signature = Signature::from_str(signature.trim())
+            .map_err(|e| format!("invalid signature {signature}: {e}"))?;
+        let confirmed = RpcClient::new(self.rpc_http_url.clone())
+            .get_transaction_with_config(
+                &signature,
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Base64),
+                    max_supported_transaction_version: Some(0),
+                    ..RpcTransactionConfig::default()
+                },
+            )
+            .await
+            .map_err(|e| format!("failed to fetch transaction {signature}: {e}"))?;
+        let update =
+            transaction_update(confirmed.transaction, confirmed.slot, confirmed.block_time)
+                .ok_or_else(|| {
+                    format!("transaction {signature} has no status or could not be decoded")
+                })?;
+        let transaction =
+            Arc::new(TransactionMetadata::try_from(update.clone()).map_err(|e| e.to_string())?);
+        let instructions: NestedInstructions =
+            extract_instructions_with_metadata(&transaction, &update)
+                .map_err(|e| e.to_string())?
+                .into();
+
+        let capture = Arc::new(Capture::default());
+        let notifiers = Arc::new(Notifiers::spawn(vec![capture.clone()]));
+        let mut report = DryRunReport {
+            slot: update.slot,
+            ..DryRunReport::default()
+        };
+        let mut result = Ok(());
+        for market in MarketType::ALL {
+            if !self.markets.contains(&market) || result.is_err() {
+                continue;
+            }
+            for deployment in chain::profile().deployments(market) {
+                result = self
+                    .process_deployment(&deployment, &notifiers, &instructions, &mut report)
+                    .await;
+                if result.is_err() {
+                    break;
+                }
+            }
+        }
+        notifiers.shutdown(DRAIN_TIMEOUT).await;
+        result?;
+
+        report.events = capture
+            .take()
+            .into_iter()
+            .map(|event| WouldEmit {
+                rules: self.rules.as_ref().map_or_else(Vec::new, |rules| {
+                    rules
+                        .matching_names(&event)
+                        .into_iter()
+                        .map(String::from)
+                        .collect()
+                }),
+                event,
+            })
+            .collect();
+        Ok(report)
+    }
+
+    /// Runs the instructions of one program deployment through its market's processor.
+    async fn process_deployment(
+        &self,
+        deployment: &Deployment,
+        notifiers: &Arc<Notifiers>,
+        instructions: &NestedInstructions,
+        report: &mut DryRunReport,
+    ) -> Result<(), String> {
+        let market = deployment.market;
+        let filters = FilterSet::new(self.config.clone(), market);
+        let mut emitter = EventEmitter::new(
+            market,
+            self.config.clone(),
+            self.event_log.clone(),
+            notifiers.clone(),
+        );
+        if let Some(ref label) = deployment.label {
+            emitter = emitter.with_deployment(label.clone());
+        }
+        let decoded = &mut report.instructions;
+
+        match market {
+            MarketType::Cpmm => {
+                let processor = RaydiumCpmmInstructionProcessor::new(filters, emitter);
+                let decoder = Deployed::new(RaydiumCpmmDecoder, deployment);
+                process(market, decoder, processor, instructions, decoded).await
+            }
+            MarketType::Clmm => {
+                let mut processor = RaydiumClmmInstructionProcessor::new(filters, emitter);
+                if let Some(ref cache) = self.pool_cache {
+                    processor = processor.with_pool_cache(cache.clone());
+                }
+                let decoder = Deployed::new(RaydiumClmmDecoder, deployment);
+                process(market, decoder, processor, instructions, decoded).await
+            }
+            MarketType::AmmV4 => {
+                let mut processor = RaydiumAmmV4InstructionProcessor::new(filters, emitter);
+                if let Some(ref cache) = self.pool_cache {
+                    processor = processor.with_account_cache(cache.clone());
+                }
+                let decoder = Deployed::new(RaydiumAmmV4Decoder, deployment);
+                process(market, decoder, processor, instructions, decoded).await
+            }
+            MarketType::Launchpad => {
+                let mut processor = RaydiumLaunchpadInstructionProcessor::new(filters, emitter);
+                if let Some(ref cache) = self.pool_cache {
+                    processor = processor.with_pool_cache(cache.clone());
+                }
+                let decoder = Deployed::new(RaydiumLaunchpadDecoder, deployment);
+                process(market, decoder, processor, instructions, decoded).await
+            }
+            MarketType::PumpFun => {
+                let processor = PumpFunInstructionProcessor::new(filters, emitter);
+                let decoder = Deployed::new(PumpfunDecoder, deployment);
+                process(market, decoder, processor, instructions, decoded).await
+            }
+            MarketType::PumpSwap => {
+                let mut processor = PumpSwapInstructionProcessor::new(filters, emitter);
+                if let Some(ref cache) = self.pool_cache {
+                    processor = processor.with_pool_cache(cache.clone());
+                }
+                let decoder = Deployed::new(PumpSwapDecoder, deployment);
+                process(market, decoder, processor, instructions, decoded).await
+            }
+        }
+    }
+}
+
+/// Feeds every instruction `decoder` decodes, top-level or inner, to
+/// `processor` and records it in `decoded`.
+async fn process<D, T, P>(
+    market: MarketType,
+    decoder: D,
+    mut processor: P,
+    instructions: &NestedInstructions,
+    decoded: &mut Vec<DecodedSummary>,
+) -> Result<(), String>
+where
+    D: for<'a> InstructionDecoder<'a, InstructionType = T>,
+    T: fmt::Debug,
+    P: Processor<InputType = InstructionInput<T>>,
+{
+    let mut inputs = Vec::new();
+    collect(&decoder, instructions, &mut inputs);
+
+    let metrics = Arc::new(MetricsCollection::new(vec![]));
+    for input in inputs {
+        decoded.push(DecodedSummary {
+            market,
+            location: Provenance::from_metadata(&input.0).to_string(),
+            instruction: format!("{:?}", input.1.data),
+        });
+        processor
+            .process(input, metrics.clone())
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Adds the processor input of every instruction `decoder` decodes to
+/// `inputs`, each before the instructions it invoked.
+fn collect<D, T>(
+    decoder: &D,
+    instructions: &NestedInstructions,
+    inputs: &mut Vec<InstructionInput<T>>,
+) where
+    D: for<'a> InstructionDecoder<'a, InstructionType = T>,
+{
+    for nested in instructions.iter() {
+        if let Some(decoded) = decoder.decode_instruction(&nested.instruction) {
+            inputs.push((
+                nested.metadata.clone(),
+                decoded,
+                nested.inner_instructions.clone(),
+                nested.instruction.clone(),
+            ));
+        }
+        collect(decoder, &nested.inner_instructions, inputs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::output::Protocol};
+
+    #[test]
+    fn test_verdict() {
+        let mut report = DryRunReport {
+            slot: 42,
+            ..DryRunReport::default()
+        };
+        assert!(report.verdict().starts_with("No instruction"));
+
+        report.instructions.push(DecodedSummary {
+            market: MarketType::Cpmm,
+            location: "#0".to_string(),
+            instruction: "SwapBaseInput".to_string(),
+        });
+        assert!(report.verdict().contains("no event emitted"));
+
+        report.events.push(WouldEmit {
+            event: SwapEvent::builder()
+                .protocol(Protocol::Cpmm)
+                .signature("sig")
+                .pool("pool")
+                .build(),
+            rules: Vec::new(),
+        });
+        assert_eq!(
+            report.verdict(),
+            "1 instruction(s) decoded, 1 event(s) would have been emitted"
+        );
+    }
+}
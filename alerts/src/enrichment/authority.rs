@@ -0,0 +1,87 @@
+//! Mint and freeze authority enricher.
+//!
+//! Pool creations get the mint and freeze authorities of the new token
+//! ([`TokenAuthorities`]), read from its mint account. A token whose mint
+//! authority is active can be inflated, and one whose freeze authority is
+//! active can have holders' accounts frozen; both are standard safety screens.
+//! With `SUPPRESS_FREEZABLE_TOKENS`, pool creations of freezable tokens are not
+//! alerted on (see [`LiveConfig::passes_authorities`](crate::reload::LiveConfig::passes_authorities)).
+
+use {
+    super::{
+        parse_freeze_authority, parse_mint_authority,
+        pipeline::{EnrichError, Enricher},
+        token_account::is_token_program,
+        EnrichmentCache,
+    },
+    crate::output::{EventType, SwapEvent, TokenAuthorities},
+    async_trait::async_trait,
+    solana_pubkey::Pubkey,
+    std::{str::FromStr, sync::Arc},
+};
+
+/// Sets `pool_created.authorities` on pool creations.
+pub struct AuthorityEnricher {
+    cache: Arc<EnrichmentCache>,
+}
+
+impl AuthorityEnricher {
+    /// Creates an authority enricher using the cache's RPC client.
+    pub fn new(cache: Arc<EnrichmentCache>) -> Self {
+        Self { cache }
+    }
+
+    /// Fetches the mint account of `mint` and reads its authorities.
+    async fn authorities(&self, mint: &str) -> Result<TokenAuthorities, EnrichError> {
+        let address =
+            Pubkey::from_str(mint).map_err(|e| EnrichError(format!("invalid mint {mint}: {e}")))?;
+        let account = self
+            .cache
+            .rpc()
+            .get_account(&address)
+            .await
+            .map_err(|e| EnrichError(format!("failed to fetch mint {mint}: {e}")))?;
+        if !is_token_program(&account.owner) {
+            return Err(EnrichError(format!("{mint} is not a token mint")));
+        }
+        match (
+            parse_mint_authority(&account.data),
+            parse_freeze_authority(&account.data),
+        ) {
+            (Some(mint_authority), Some(freeze_authority)) => Ok(TokenAuthorities {
+                mint: mint.to_string(),
+                mint_authority: mint_authority.map(|authority| authority.to_string()),
+                freeze_authority: freeze_authority.map(|authority| authority.to_string()),
+            }),
+            _ => Err(EnrichError(format!("invalid mint account {mint}"))),
+        }
+    }
+}
+
+#[async_trait]
+impl Enricher for AuthorityEnricher {
+    fn name(&self) -> &'static str {
+        "authorities"
+    }
+
+    async fn enrich(&self, event: &mut SwapEvent) -> Result<(), EnrichError> {
+        if event.event_type != EventType::CreatePool {
+            return Ok(());
+        }
+        let Some(mint) = event
+            .pool_created
+            .as_ref()
+            .filter(|created| created.authorities.is_none())
+            .and_then(|created| created.listed_mint())
+            .map(str::to_string)
+        else {
+            return Ok(());
+        };
+
+        let authorities = self.authorities(&mint).await?;
+        if let Some(ref mut created) = event.pool_created {
+            created.authorities = Some(authorities);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+//! Holder concentration enricher.
+//!
+//! Pool creations get the supply share of the new token's largest holders
+//! ([`HolderConcentration`]), flagging a single holder of more than half the
+//! supply, a common rug heuristic. The token's supply and largest token
+//! accounts are fetched (`getTokenSupply`, `getTokenLargestAccounts`), then the
+//! accounts' owners (`getMultipleAccounts`), so a wallet holding through
+//! several accounts counts once. The pool's vaults and the accounts owned by
+//! the pool (bonding curves) hold its liquidity rather than a holder's tokens
+//! and are skipped.
+//!
+//! The lookups take several RPC round trips, longer than the default enricher
+//! timeout: raise `ENRICHER_HOLDERS_TIMEOUT_MS`, or complete the events in
+//! follow-ups (`ENRICHMENT_FOLLOW_UP`).
+
+use {
+    super::{
+        parse_token_account_owner,
+        pipeline::{EnrichError, Enricher},
+        token_account::is_token_program,
+        EnrichmentCache,
+    },
+    crate::output::{EventType, HolderConcentration, SwapEvent},
+    async_trait::async_trait,
+    solana_pubkey::Pubkey,
+    std::{collections::HashMap, str::FromStr, sync::Arc},
+};
+
+/// Sets `pool_created.holder_concentration` on pool creations.
+pub struct HolderEnricher {
+    cache: Arc<EnrichmentCache>,
+}
+
+impl HolderEnricher {
+    /// Creates a holder enricher using the cache's RPC client and pool lookups.
+    pub fn new(cache: Arc<EnrichmentCache>) -> Self {
+        Self { cache }
+    }
+
+    /// Fetches the supply and largest holders of `mint`, leaving out the liquidity of `pool`.
+    async fn concentration(
+        &self,
+        pool: &str,
+        mint: &str,
+    ) -> Result<Option<HolderConcentration>, EnrichError> {
+        let mint_address =
+            Pubkey::from_str(mint).map_err(|e| EnrichError(format!("invalid mint {mint}: {e}")))?;
+        let rpc = self.cache.rpc();
+        let supply = rpc
+            .get_token_supply(&mint_address)
+            .await
+            .map_err(|e| EnrichError(format!("failed to fetch the supply of {mint}: {e}")))?;
+        let supply: u64 = supply
+            .amount
+            .parse()
+            .map_err(|e| EnrichError(format!("invalid supply of {mint}: {e}")))?;
+        let largest = rpc
+            .get_token_largest_accounts(&mint_address)
+            .await
+            .map_err(|e| EnrichError(format!("failed to fetch the holders of {mint}: {e}")))?;
+
+        let balances: Vec<(Pubkey, u64)> = largest
+            .iter()
+            .filter_map(|balance| {
+                Some((
+                    Pubkey::from_str(&balance.address).ok()?,
+                    balance.amount.amount.parse().ok()?,
+                ))
+            })
+            .collect();
+        let addresses: Vec<Pubkey> = balances.iter().map(|(address, _)| *address).collect();
+        let accounts = rpc
+            .get_multiple_accounts(&addresses)
+            .await
+            .map_err(|e| EnrichError(format!("failed to fetch the holders of {mint}: {e}")))?;
+        let owners = accounts.into_iter().map(|account| {
+            account
+                .filter(|account| is_token_program(&account.owner))
+                .and_then(|account| parse_token_account_owner(&account.data))
+        });
+
+        let pool = Pubkey::from_str(pool).ok();
+        let vaults = match pool {
+            Some(ref pool) => self
+                .cache
+                .pool_or_resolve(pool)
+                .await
+                .map_or_else(Vec::new, |info| vec![info.vault0, info.vault1]),
+            None => Vec::new(),
+        };
+        let holdings = holdings(balances.into_iter().zip(owners), pool, &vaults);
+        Ok(HolderConcentration::from_holdings(mint, supply, holdings))
+    }
+}
+
+/// Sums the balances of token accounts per owner, skipping the pool's vaults
+/// and the accounts owned by the pool. Accounts whose owner is unknown count
+/// as their own holder.
+fn holdings(
+    balances: impl IntoIterator<Item = ((Pubkey, u64), Option<Pubkey>)>,
+    pool: Option<Pubkey>,
+    vaults: &[Pubkey],
+) -> Vec<(String, u64)> {
+    let mut holdings: HashMap<Pubkey, u64> = HashMap::new();
+    for ((address, amount), owner) in balances {
+        let owner = owner.unwrap_or(address);
+        if vaults.contains(&address) || Some(owner) == pool {
+            continue;
+        }
+        *holdings.entry(owner).or_default() += amount;
+    }
+    holdings
+        .into_iter()
+        .map(|(owner, amount)| (owner.to_string(), amount))
+        .collect()
+}
+
+#[async_trait]
+impl Enricher for HolderEnricher {
+    fn name(&self) -> &'static str {
+        "holders"
+    }
+
+    async fn enrich(&self, event: &mut SwapEvent) -> Result<(), EnrichError> {
+        if event.event_type != EventType::CreatePool {
+            return Ok(());
+        }
+        let Some(mint) = event
+            .pool_created
+            .as_ref()
+            .filter(|created| created.holder_concentration.is_none())
+            .and_then(|created| created.listed_mint())
+            .map(str::to_string)
+        else {
+            return Ok(());
+        };
+
+        let concentration = self.concentration(&event.pool, &mint).await?;
+        if let Some(ref mut created) = event.pool_created {
+            created.holder_concentration = concentration;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_holdings_skip_pool_liquidity() {
+        let (pool, vault, whale, unknown) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let balances = vec![
+            ((vault, 5_000), Some(Pubkey::new_unique())),
+            ((Pubkey::new_unique(), 4_000), Some(pool)),
+            ((Pubkey::new_unique(), 300), Some(whale)),
+            ((Pubkey::new_unique(), 200), Some(whale)),
+            ((unknown, 100), None),
+        ];
+        let mut holdings = holdings(balances, Some(pool), &[vault]);
+        holdings.sort();
+
+        let mut expected = vec![(whale.to_string(), 500), (unknown.to_string(), 100)];
+        expected.sort();
+        assert_eq!(holdings, expected);
+    }
+}
@@ -0,0 +1,190 @@
+//! Execution price and price impact enricher.
+//!
+//! Every swap with known amounts gets its execution price. Swaps on
+//! constant-product pools (CPMM, AMM V4, PumpSwap) also get the estimated price
+//! impact, `amount_in / (reserve_in + amount_in)`, from the pool's reserve of
+//! the input token before the swap. Reserves are read from the pool vaults when
+//! a pool is resolved, refreshed every `RESERVE_REFRESH_SECS`, and moved by the
+//! amounts of each enriched swap in between.
+
+use {
+    super::{
+        pipeline::{EnrichError, Enricher},
+        EnrichmentCache,
+    },
+    crate::output::{EventType, Protocol, SwapEvent},
+    async_trait::async_trait,
+    solana_pubkey::Pubkey,
+    std::{str::FromStr, sync::Arc},
+};
+
+/// Sets `effective_price` and `price_impact_pct` on swaps.
+pub struct ImpactEnricher {
+    cache: Arc<EnrichmentCache>,
+}
+
+impl ImpactEnricher {
+    /// Creates an impact enricher reading pool reserves from the given cache.
+    pub fn new(cache: Arc<EnrichmentCache>) -> Self {
+        Self { cache }
+    }
+}
+
+/// Returns the price impact in percent of swapping `amount_in` into a
+/// constant-product pool holding `reserve_in` of the input token.
+fn price_impact_pct(amount_in: u64, reserve_in: u64) -> Option<f64> {
+    if reserve_in == 0 {
+        return None;
+    }
+    let amount_in = amount_in as f64;
+    Some(amount_in / (reserve_in as f64 + amount_in) * 100.0)
+}
+
+#[async_trait]
+impl Enricher for ImpactEnricher {
+    fn name(&self) -> &'static str {
+        "impact"
+    }
+
+    async fn enrich(&self, event: &mut SwapEvent) -> Result<(), EnrichError> {
+        if event.event_type != EventType::Swap {
+            return Ok(());
+        }
+        if event.effective_price.is_none() {
+            event.effective_price = event.execution_price();
+        }
+
+        // Skip pool lookups for protocols without constant-product pools
+        if !matches!(
+            event.protocol,
+            Protocol::Cpmm | Protocol::AmmV4 | Protocol::PumpSwap
+        ) {
+            return Ok(());
+        }
+        let (Some(input), Some(output)) = (&event.input_token, &event.output_token) else {
+            return Ok(());
+        };
+        let (Ok(pool), Ok(input_mint)) =
+            (Pubkey::from_str(&event.pool), Pubkey::from_str(&input.mint))
+        else {
+            return Ok(());
+        };
+        let Some(info) = self.cache.pool_or_resolve(&pool).await else {
+            return Ok(());
+        };
+        if !info.is_constant_product() {
+            return Ok(());
+        }
+
+        if let Some(reserve_in) =
+            self.cache
+                .apply_swap(&pool, &input_mint, input.amount_raw, output.amount_raw)
+        {
+            event.price_impact_pct = price_impact_pct(input.amount_raw, reserve_in);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            enrichment::{EnrichmentConfig, PoolInfo},
+            output::{swap_event::WSOL_MINT, TokenInfo},
+        },
+    };
+
+    fn cache() -> Arc<EnrichmentCache> {
+        Arc::new(EnrichmentCache::new(EnrichmentConfig {
+            rpc_http_url: "http://127.0.0.1:1".to_string(),
+            batch_size: 100,
+            concurrency: 1,
+            token_account_cache_size: 16,
+            reserve_refresh: None,
+        }))
+    }
+
+    #[test]
+    fn test_price_impact_pct() {
+        assert_eq!(price_impact_pct(1_000, 99_000), Some(1.0));
+        assert_eq!(price_impact_pct(1_000, 0), None);
+    }
+
+    #[tokio::test]
+    async fn test_impact_from_tracked_reserves() {
+        let cache = cache();
+        let pool = Pubkey::new_unique();
+        let token = Pubkey::new_unique();
+        let sol = Pubkey::from_str(WSOL_MINT).unwrap();
+        cache.pools.write().unwrap().insert(
+            pool,
+            PoolInfo {
+                protocol: Protocol::Cpmm,
+                token_mint0: sol,
+                token_mint1: token,
+                vault0: Pubkey::new_unique(),
+                vault1: Pubkey::new_unique(),
+                reserve0: Some(99_000_000_000),
+                reserve1: Some(50_000_000),
+            },
+        );
+
+        let swap = || {
+            SwapEvent::builder()
+                .protocol(Protocol::Cpmm)
+                .signature("sig")
+                .pool(pool.to_string())
+                .input_token(TokenInfo::new(WSOL_MINT, 1_000_000_000).with_decimals(9))
+                .output_token(TokenInfo::from_pubkey(&token, 500_000).with_decimals(6))
+                .build()
+        };
+        let enricher = ImpactEnricher::new(cache.clone());
+        let mut event = swap();
+        enricher.enrich(&mut event).await.unwrap();
+        assert_eq!(event.effective_price, Some(2.0));
+        assert_eq!(event.price_impact_pct, Some(1.0));
+
+        // The first swap moved the reserves the second one trades against
+        let info = cache.pool(&pool).unwrap();
+        assert_eq!(info.reserve0, Some(100_000_000_000));
+        assert_eq!(info.reserve1, Some(49_500_000));
+        let mut event = swap();
+        enricher.enrich(&mut event).await.unwrap();
+        assert!(event.price_impact_pct.unwrap() < 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_clmm_swaps_get_price_only() {
+        let cache = cache();
+        let pool = Pubkey::new_unique();
+        let token = Pubkey::new_unique();
+        cache.pools.write().unwrap().insert(
+            pool,
+            PoolInfo {
+                protocol: Protocol::Clmm,
+                token_mint0: Pubkey::from_str(WSOL_MINT).unwrap(),
+                token_mint1: token,
+                vault0: Pubkey::new_unique(),
+                vault1: Pubkey::new_unique(),
+                reserve0: Some(1_000_000_000),
+                reserve1: Some(1_000_000),
+            },
+        );
+        let mut event = SwapEvent::builder()
+            .protocol(Protocol::Clmm)
+            .signature("sig")
+            .pool(pool.to_string())
+            .input_token(TokenInfo::new(WSOL_MINT, 1_000_000_000).with_decimals(9))
+            .output_token(TokenInfo::from_pubkey(&token, 500_000).with_decimals(6))
+            .build();
+        ImpactEnricher::new(cache.clone())
+            .enrich(&mut event)
+            .await
+            .unwrap();
+        assert_eq!(event.effective_price, Some(2.0));
+        assert!(event.price_impact_pct.is_none());
+        assert_eq!(cache.pool(&pool).unwrap().reserve0, Some(1_000_000_000));
+    }
+}
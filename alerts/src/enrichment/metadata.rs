@@ -0,0 +1,48 @@
+//! Token metadata enricher backed by [`EnrichmentCache`].
+
+use {
+    super::{
+        pipeline::{EnrichError, Enricher},
+        EnrichmentCache,
+    },
+    crate::output::SwapEvent,
+    async_trait::async_trait,
+    std::sync::Arc,
+};
+
+/// Sets token symbols and decimals from the enrichment cache.
+///
+/// Uncached mints are resolved via RPC. The enricher waits for the lookup
+/// within its pipeline timeout; if it times out, resolution continues in the
+/// background so later events for the same tokens are enriched.
+pub struct MetadataEnricher {
+    cache: Arc<EnrichmentCache>,
+}
+
+impl MetadataEnricher {
+    /// Creates a metadata enricher reading from the given cache.
+    pub fn new(cache: Arc<EnrichmentCache>) -> Self {
+        Self { cache }
+    }
+}
+
+#[async_trait]
+impl Enricher for MetadataEnricher {
+    fn name(&self) -> &'static str {
+        "metadata"
+    }
+
+    async fn enrich(&self, event: &mut SwapEvent) -> Result<(), EnrichError> {
+        let missing = self.cache.apply_cached(event);
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        // Mints already being resolved for another event are not awaited
+        if let Some(handle) = self.cache.resolve_tokens_in_background(missing) {
+            handle.await.map_err(|e| EnrichError(e.to_string()))?;
+            self.cache.apply_cached(event);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,591 @@
+//! Token and pool enrichment backed by on-chain lookups.
+//!
+//! This module provides:
+//! - [`EnrichmentPipeline`] - Ordered [`Enricher`] stages with per-stage timeouts
+//! - [`MetadataEnricher`] - Token symbols and decimals from the cache
+//! - [`AuthorityEnricher`] - Mint and freeze authorities of new pools' tokens
+//! - [`HolderEnricher`] - Supply share of the largest holders of new pools' tokens
+//! - [`ImpactEnricher`] - Execution price and price impact from tracked pool reserves
+//! - [`PriceEnricher`] - USD values and market caps from stablecoin, SOL, and derived mint prices
+//! - [`EnrichmentCache`] - Cached token metadata and pool info with RPC resolution
+//! - [`TokenMetadata`] - Symbol, decimals, and supply for a mint
+//! - [`PoolInfo`] - Pool mints, vaults, and reserves
+//! - [`TokenAccountResolver`] - Token account to mint lookups with an LRU cache
+//!
+//! Processors run every event through the pipeline before emitting it. The
+//! metadata stage reads the cache and resolves misses via RPC. On startup, [`EnrichmentCache::warm_up`]
+//! bulk-resolves everything referenced by the configured filters so the first
+//! alerts after a deploy are fully enriched.
+
+mod authority;
+mod holders;
+mod impact;
+mod metadata;
+mod pipeline;
+mod pool;
+mod price;
+mod token;
+mod token_account;
+
+// `EnrichError` and the builder are extension points for custom enrichers
+#[allow(unused_imports)]
+pub use {
+    authority::AuthorityEnricher,
+    holders::HolderEnricher,
+    impact::ImpactEnricher,
+    metadata::MetadataEnricher,
+    pipeline::{
+        EnrichError, Enricher, EnrichmentPipeline, EnrichmentPipelineBuilder,
+        EnrichmentPipelineConfig,
+    },
+    pool::{decode_pool_account, decode_pool_lp_supply, parse_token_account_amount, PoolInfo},
+    price::PriceEnricher,
+    token::{
+        metadata_address, parse_freeze_authority, parse_metadata_symbol, parse_mint_authority,
+        parse_mint_decimals, parse_mint_supply, well_known_token, TokenMetadata,
+    },
+    token_account::{parse_token_account_mint, parse_token_account_owner, TokenAccountResolver},
+};
+
+use {
+    crate::{
+        clock,
+        output::{SwapEvent, TokenInfo},
+    },
+    solana_client::nonblocking::rpc_client::RpcClient,
+    solana_pubkey::Pubkey,
+    std::{
+        collections::{HashMap, HashSet},
+        env,
+        str::FromStr,
+        sync::{Arc, RwLock},
+        time::{Duration, Instant},
+    },
+    tokio::task::{JoinHandle, JoinSet},
+};
+
+/// Maximum accounts per `getMultipleAccounts` request.
+const MAX_ACCOUNTS_PER_REQUEST: usize = 100;
+
+/// Maximum time an on-demand pool lookup may block event processing.
+const POOL_LOOKUP_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Time before a pool that failed to resolve is looked up again.
+const POOL_RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Default number of token account to mint mappings kept in memory.
+const DEFAULT_TOKEN_ACCOUNT_CACHE_SIZE: usize = 50_000;
+
+/// Default interval between reserve refreshes of cached pools, in seconds.
+const DEFAULT_RESERVE_REFRESH_SECS: u64 = 30;
+
+/// Configuration for RPC-backed enrichment.
+#[derive(Debug, Clone)]
+pub struct EnrichmentConfig {
+    /// HTTP RPC endpoint used for account lookups
+    pub rpc_http_url: String,
+    /// Accounts per `getMultipleAccounts` request (max 100)
+    pub batch_size: usize,
+    /// Maximum concurrent RPC requests during warm-up
+    pub concurrency: usize,
+    /// Token account to mint mappings kept in the LRU cache
+    pub token_account_cache_size: usize,
+    /// Interval between vault balance refreshes of cached pools (`None` disables)
+    pub reserve_refresh: Option<Duration>,
+}
+
+impl EnrichmentConfig {
+    /// Creates enrichment configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `RPC_HTTP_URL` - Required: HTTP RPC endpoint enabling enrichment, unless the
+    ///   chain profile sets `rpc_http_url`
+    /// - `ENRICHMENT_BATCH_SIZE` - Optional: Accounts per request (default: 100, max: 100)
+    /// - `ENRICHMENT_CONCURRENCY` - Optional: Parallel requests during warm-up (default: 4)
+    /// - `TOKEN_ACCOUNT_CACHE_SIZE` - Optional: Cached token account mints (default: 50000)
+    /// - `RESERVE_REFRESH_SECS` - Optional: Seconds between vault balance refreshes of
+    ///   cached pools (default: 30, 0 disables)
+    ///
+    /// # Returns
+    ///
+    /// `Some(EnrichmentConfig)` if an HTTP RPC endpoint is set, `None` otherwise.
+    pub fn from_env() -> Option<Self> {
+        let rpc_http_url = crate::chain::profile().rpc_http_url.clone()?;
+
+        let batch_size: usize = env::var("ENRICHMENT_BATCH_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(MAX_ACCOUNTS_PER_REQUEST)
+            .min(MAX_ACCOUNTS_PER_REQUEST);
+
+        let concurrency: usize = env::var("ENRICHMENT_CONCURRENCY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(4);
+
+        let token_account_cache_size: usize = env::var("TOKEN_ACCOUNT_CACHE_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(DEFAULT_TOKEN_ACCOUNT_CACHE_SIZE);
+
+        let reserve_refresh_secs: u64 = env::var("RESERVE_REFRESH_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_RESERVE_REFRESH_SECS);
+
+        Some(Self {
+            rpc_http_url,
+            batch_size,
+            concurrency,
+            token_account_cache_size,
+            reserve_refresh: (reserve_refresh_secs > 0)
+                .then(|| Duration::from_secs(reserve_refresh_secs)),
+        })
+    }
+}
+
+/// Summary of a warm-up run.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct WarmUpStats {
+    /// Pools resolved
+    pub pools: usize,
+    /// Tokens resolved
+    pub tokens: usize,
+}
+
+/// Cached token metadata and pool info, resolved via RPC.
+pub struct EnrichmentCache {
+    rpc: Arc<RpcClient>,
+    config: EnrichmentConfig,
+    tokens: RwLock<HashMap<Pubkey, TokenMetadata>>,
+    pools: RwLock<HashMap<Pubkey, PoolInfo>>,
+    /// Pools that failed to resolve, with the time of the last attempt
+    failed_pools: RwLock<HashMap<Pubkey, Instant>>,
+    /// Mints currently being resolved in the background
+    pending_tokens: RwLock<HashSet<Pubkey>>,
+    /// Token account to mint lookups (AMM V4 swaps)
+    token_accounts: TokenAccountResolver,
+}
+
+impl EnrichmentCache {
+    /// Creates an empty cache using the given configuration.
+    pub fn new(config: EnrichmentConfig) -> Self {
+        let rpc = Arc::new(RpcClient::new(config.rpc_http_url.clone()));
+        Self {
+            token_accounts: TokenAccountResolver::new(rpc.clone(), config.token_account_cache_size),
+            rpc,
+            config,
+            tokens: RwLock::new(HashMap::new()),
+            pools: RwLock::new(HashMap::new()),
+            failed_pools: RwLock::new(HashMap::new()),
+            pending_tokens: RwLock::new(HashSet::new()),
+        }
+    }
+
+    /// Returns cached metadata for a mint.
+    pub fn token(&self, mint: &Pubkey) -> Option<TokenMetadata> {
+        well_known_token(mint).or_else(|| self.tokens.read().ok()?.get(mint).cloned())
+    }
+
+    /// Returns cached info for a pool.
+    pub fn pool(&self, pool: &Pubkey) -> Option<PoolInfo> {
+        self.pools.read().ok()?.get(pool).cloned()
+    }
+
+    /// Returns the RPC client of the cache's lookups.
+    pub fn rpc(&self) -> &Arc<RpcClient> {
+        &self.rpc
+    }
+
+    /// Returns the token account to mint resolver sharing this cache's RPC client.
+    pub fn token_accounts(&self) -> &TokenAccountResolver {
+        &self.token_accounts
+    }
+
+    /// Returns info for a pool, fetching and caching its state account on a miss.
+    ///
+    /// Lookups are bounded by a short timeout. A pool that fails to resolve is
+    /// not looked up again for a minute, so unknown or unreachable pools don't
+    /// cost an RPC call per event.
+    pub async fn pool_or_resolve(&self, pool: &Pubkey) -> Option<PoolInfo> {
+        if let Some(info) = self.pool(pool) {
+            return Some(info);
+        }
+        let recently_failed =
+            self.failed_pools.read().ok()?.get(pool).is_some_and(|&at| {
+                clock::now().saturating_duration_since(at) < POOL_RETRY_INTERVAL
+            });
+        if recently_failed {
+            return None;
+        }
+
+        let resolved = tokio::time::timeout(POOL_LOOKUP_TIMEOUT, self.resolve_pools(&[*pool]))
+            .await
+            .ok()
+            .and_then(|infos| infos.into_iter().next());
+        if let Ok(mut failed) = self.failed_pools.write() {
+            match resolved {
+                Some(_) => failed.remove(pool),
+                None => failed.insert(*pool, clock::now()),
+            };
+        }
+        if resolved.is_none() {
+            log::debug!("Failed to resolve pool {pool}, retrying in {POOL_RETRY_INTERVAL:?}");
+        }
+        resolved
+    }
+
+    /// Moves the cached reserves of a pool by the amounts of a swap.
+    ///
+    /// Returns the reserve of the input token before the swap, or `None` if
+    /// the pool's reserves are not cached or `input_mint` is not one of its
+    /// mints.
+    pub fn apply_swap(
+        &self,
+        pool: &Pubkey,
+        input_mint: &Pubkey,
+        amount_in: u64,
+        amount_out: u64,
+    ) -> Option<u64> {
+        let mut pools = self.pools.write().ok()?;
+        let info = pools.get_mut(pool)?;
+        let (reserve_in, reserve_out) = if *input_mint == info.token_mint0 {
+            (&mut info.reserve0, &mut info.reserve1)
+        } else if *input_mint == info.token_mint1 {
+            (&mut info.reserve1, &mut info.reserve0)
+        } else {
+            return None;
+        };
+        let (Some(before), Some(out)) = (*reserve_in, *reserve_out) else {
+            return None;
+        };
+        *reserve_in = Some(before.saturating_add(amount_in));
+        *reserve_out = Some(out.saturating_sub(amount_out));
+        Some(before)
+    }
+
+    /// Sets the cached reserves of a pool, e.g. to the exact reserves before a
+    /// swap reported by the program's log.
+    ///
+    /// Returns `false` if the pool is not cached.
+    pub fn set_reserves(&self, pool: &Pubkey, reserve0: u64, reserve1: u64) -> bool {
+        let Ok(mut pools) = self.pools.write() else {
+            return false;
+        };
+        let Some(info) = pools.get_mut(pool) else {
+            return false;
+        };
+        info.reserve0 = Some(reserve0);
+        info.reserve1 = Some(reserve1);
+        true
+    }
+
+    /// Moves the cached reserves of a pool by the amounts of a withdrawal.
+    ///
+    /// Returns the reserves of token 0 and token 1 before the withdrawal, or
+    /// `None` if the pool's reserves are not cached.
+    pub fn apply_withdraw(&self, pool: &Pubkey, amount0: u64, amount1: u64) -> Option<(u64, u64)> {
+        let mut pools = self.pools.write().ok()?;
+        let info = pools.get_mut(pool)?;
+        let (Some(reserve0), Some(reserve1)) = (info.reserve0, info.reserve1) else {
+            return None;
+        };
+        info.reserve0 = Some(reserve0.saturating_sub(amount0));
+        info.reserve1 = Some(reserve1.saturating_sub(amount1));
+        Some((reserve0, reserve1))
+    }
+
+    /// Applies cached token metadata to an event's tokens.
+    ///
+    /// Returns the mints that are not cached yet.
+    pub fn apply_cached(&self, event: &mut SwapEvent) -> Vec<Pubkey> {
+        [event.input_token.as_mut(), event.output_token.as_mut()]
+            .into_iter()
+            .flatten()
+            .filter_map(|token| self.enrich_token(token))
+            .collect()
+    }
+
+    /// Applies cached metadata to a token, returning its mint if not cached.
+    fn enrich_token(&self, token: &mut TokenInfo) -> Option<Pubkey> {
+        let mint = Pubkey::from_str(&token.mint).ok()?;
+        match self.token(&mint) {
+            Some(meta) => {
+                if token.symbol.is_none() {
+                    token.symbol = meta.symbol;
+                }
+                if token.decimals.is_none() {
+                    *token = std::mem::take(token).with_decimals(meta.decimals);
+                }
+                None
+            }
+            None => Some(mint),
+        }
+    }
+
+    /// Spawns a task resolving mints that are not already being resolved.
+    ///
+    /// Returns the task handle, or `None` if every mint is already pending.
+    /// The task keeps running if the handle is dropped.
+    pub fn resolve_tokens_in_background(
+        self: &Arc<Self>,
+        mints: Vec<Pubkey>,
+    ) -> Option<JoinHandle<()>> {
+        let mints: Vec<Pubkey> = match self.pending_tokens.write() {
+            Ok(mut pending) => mints.into_iter().filter(|m| pending.insert(*m)).collect(),
+            Err(_) => return None,
+        };
+        if mints.is_empty() {
+            return None;
+        }
+
+        let cache = self.clone();
+        Some(tokio::spawn(async move {
+            cache.resolve_tokens(&mints).await;
+            if let Ok(mut pending) = cache.pending_tokens.write() {
+                for mint in &mints {
+                    pending.remove(mint);
+                }
+            }
+        }))
+    }
+
+    /// Bulk-resolves the configured filter tokens and pools before processing starts.
+    ///
+    /// Pools are resolved first so their mints are included in the token batch.
+    pub async fn warm_up(
+        self: &Arc<Self>,
+        filter_tokens: &HashSet<Pubkey>,
+        filter_pools: &HashSet<Pubkey>,
+    ) -> WarmUpStats {
+        let start = clock::now();
+        let pools: Vec<Pubkey> = filter_pools.iter().copied().collect();
+        let resolved_pools = self.resolve_pools(&pools).await;
+
+        let mut mints: HashSet<Pubkey> = filter_tokens.clone();
+        for info in &resolved_pools {
+            mints.insert(info.token_mint0);
+            mints.insert(info.token_mint1);
+        }
+        let mints: Vec<Pubkey> = mints.into_iter().collect();
+        let resolved_tokens = self.resolve_tokens(&mints).await;
+
+        let stats = WarmUpStats {
+            pools: resolved_pools.len(),
+            tokens: resolved_tokens,
+        };
+        log::info!(
+            "Enrichment warm-up: {}/{} pools, {}/{} tokens in {:?}",
+            stats.pools,
+            pools.len(),
+            stats.tokens,
+            mints.len(),
+            start.elapsed()
+        );
+        stats
+    }
+
+    /// Resolves token metadata for uncached mints and stores it.
+    ///
+    /// Returns the number of mints resolved.
+    pub async fn resolve_tokens(&self, mints: &[Pubkey]) -> usize {
+        let uncached: Vec<Pubkey> = mints
+            .iter()
+            .filter(|m| self.token(m).is_none())
+            .copied()
+            .collect();
+        if uncached.is_empty() {
+            return 0;
+        }
+
+        // Fetch mint and metadata accounts together: [mint0, meta0, mint1, meta1, ...]
+        let addresses: Vec<Pubkey> = uncached
+            .iter()
+            .flat_map(|mint| [*mint, metadata_address(mint)])
+            .collect();
+        let accounts = self.fetch_accounts(&addresses).await;
+
+        let mut resolved = 0;
+        if let Ok(mut tokens) = self.tokens.write() {
+            for (i, mint) in uncached.iter().enumerate() {
+                let Some(mint_data) = accounts.get(&addresses[i * 2]) else {
+                    continue;
+                };
+                let Some(decimals) = parse_mint_decimals(mint_data) else {
+                    continue;
+                };
+                let symbol = accounts
+                    .get(&addresses[i * 2 + 1])
+                    .and_then(|data| parse_metadata_symbol(data));
+                tokens.insert(
+                    *mint,
+                    TokenMetadata {
+                        symbol,
+                        decimals,
+                        supply: parse_mint_supply(mint_data),
+                    },
+                );
+                resolved += 1;
+            }
+        }
+        resolved
+    }
+
+    /// Resolves pool info (including vault reserves) and stores it.
+    ///
+    /// Returns the pools that were resolved.
+    pub async fn resolve_pools(&self, pools: &[Pubkey]) -> Vec<PoolInfo> {
+        if pools.is_empty() {
+            return Vec::new();
+        }
+
+        let pool_accounts = self.fetch_raw_accounts(pools).await;
+        let mut infos: Vec<(Pubkey, PoolInfo)> = pool_accounts
+            .iter()
+            .filter_map(|(address, account)| Some((*address, decode_pool_account(account)?)))
+            .collect();
+        self.fetch_reserves(&mut infos).await;
+
+        if let Ok(mut cache) = self.pools.write() {
+            for (address, info) in &infos {
+                cache.insert(*address, info.clone());
+            }
+        }
+        infos.into_iter().map(|(_, info)| info).collect()
+    }
+
+    /// Re-reads the vault balances of cached pools.
+    ///
+    /// Only pools whose reserves give a price impact estimate are refreshed.
+    /// Returns the number of pools refreshed.
+    pub async fn refresh_reserves(&self) -> usize {
+        let mut infos: Vec<(Pubkey, PoolInfo)> = match self.pools.read() {
+            Ok(pools) => pools
+                .iter()
+                .filter(|(_, info)| info.is_constant_product())
+                .map(|(address, info)| (*address, info.clone()))
+                .collect(),
+            Err(_) => return 0,
+        };
+        self.fetch_reserves(&mut infos).await;
+
+        let mut refreshed = 0;
+        if let Ok(mut cache) = self.pools.write() {
+            for (address, info) in infos {
+                if let Some(cached) = cache.get_mut(&address) {
+                    if info.reserve0.is_some() && info.reserve1.is_some() {
+                        cached.reserve0 = info.reserve0;
+                        cached.reserve1 = info.reserve1;
+                        refreshed += 1;
+                    }
+                }
+            }
+        }
+        refreshed
+    }
+
+    /// Spawns a task refreshing cached pool reserves at the configured interval.
+    ///
+    /// Returns `None` if reserve refreshes are disabled.
+    pub fn spawn_reserve_refresh(self: &Arc<Self>) -> Option<JoinHandle<()>> {
+        let interval = self.config.reserve_refresh?;
+        let cache = self.clone();
+        Some(tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let refreshed = cache.refresh_reserves().await;
+                log::debug!("Refreshed reserves of {refreshed} pools");
+            }
+        }))
+    }
+
+    /// Reads the vault balances of pools into their reserves.
+    async fn fetch_reserves(&self, infos: &mut [(Pubkey, PoolInfo)]) {
+        if infos.is_empty() {
+            return;
+        }
+        let vaults: Vec<Pubkey> = infos
+            .iter()
+            .flat_map(|(_, info)| [info.vault0, info.vault1])
+            .collect();
+        let vault_data = self.fetch_accounts(&vaults).await;
+        for (_, info) in infos.iter_mut() {
+            info.reserve0 = vault_data
+                .get(&info.vault0)
+                .and_then(|d| parse_token_account_amount(d));
+            info.reserve1 = vault_data
+                .get(&info.vault1)
+                .and_then(|d| parse_token_account_amount(d));
+        }
+    }
+
+    /// Fetches account data for the given addresses, keyed by address.
+    async fn fetch_accounts(&self, addresses: &[Pubkey]) -> HashMap<Pubkey, Vec<u8>> {
+        self.fetch_raw_accounts(addresses)
+            .await
+            .into_iter()
+            .map(|(address, account)| (address, account.data))
+            .collect()
+    }
+
+    /// Fetches accounts in parallel batches, bounded by the configured concurrency.
+    ///
+    /// Failed batches are logged and skipped; missing accounts are omitted.
+    async fn fetch_raw_accounts(
+        &self,
+        addresses: &[Pubkey],
+    ) -> Vec<(Pubkey, solana_account::Account)> {
+        let mut results = Vec::with_capacity(addresses.len());
+        let mut batches = addresses.chunks(self.config.batch_size);
+        let mut tasks = JoinSet::new();
+
+        loop {
+            while tasks.len() < self.config.concurrency {
+                let Some(batch) = batches.next() else { break };
+                let rpc = self.rpc.clone();
+                let batch = batch.to_vec();
+                tasks.spawn(async move {
+                    let accounts = rpc.get_multiple_accounts(&batch).await;
+                    (batch, accounts)
+                });
+            }
+
+            let Some(joined) = tasks.join_next().await else {
+                break;
+            };
+            match joined {
+                Ok((batch, Ok(accounts))) => results.extend(
+                    batch
+                        .into_iter()
+                        .zip(accounts)
+                        .filter_map(|(address, account)| Some((address, account?))),
+                ),
+                Ok((batch, Err(e))) => {
+                    log::warn!("Failed to fetch {} accounts: {e}", batch.len());
+                }
+                Err(e) => log::warn!("Account fetch task failed: {e}"),
+            }
+        }
+
+        results
+    }
+}
+
+#[cfg(test)]
+impl EnrichmentCache {
+    /// Caches pool info without an RPC lookup.
+    pub(crate) fn insert_pool(&self, pool: Pubkey, info: PoolInfo) {
+        self.pools.write().unwrap().insert(pool, info);
+    }
+
+    /// Caches token metadata without an RPC lookup.
+    pub(crate) fn insert_token(&self, mint: Pubkey, meta: TokenMetadata) {
+        self.tokens.write().unwrap().insert(mint, meta);
+    }
+}
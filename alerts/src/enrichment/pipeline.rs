@@ -0,0 +1,499 @@
+//! Ordered, pluggable enrichment pipeline.
+//!
+//! Each [`Enricher`] adds one kind of information to a [`SwapEvent`] (token
+//! metadata, USD prices, ...). Enrichers run in the configured order, so later
+//! stages can build on earlier ones (the price stage uses the decimals set by
+//! the metadata stage). Every stage has its own timeout: a stage that does not
+//! finish in time is skipped for that event, trading completeness for latency.
+//!
+//! An optional per-event budget bounds the time spent in all stages together:
+//! once it is used up, the event is emitted with the enrichment completed so
+//! far. With follow-ups enabled, the stages cut by the budget or timed out
+//! then run again in the background with a longer timeout. The completed
+//! event is queued as an update for storage sinks that can replace stored
+//! events, and its newly resolved fields are published to stream clients as
+//! an [`EventUpdate`] (see [`EnrichmentPipeline::spawn_follow_up`]).
+//!
+//! Custom enrichers can be added through [`EnrichmentPipelineBuilder::stage`].
+
+use {
+    crate::{
+        output::{EventUpdate, SwapEvent},
+        server::StreamHub,
+        storage::StorageSink,
+    },
+    async_trait::async_trait,
+    carbon_core::metrics::MetricsCollection,
+    std::{env, fmt, sync::Arc, time::Duration, time::Instant},
+};
+
+/// Default per-enricher timeout.
+const DEFAULT_TIMEOUT_MS: u64 = 50;
+
+/// Minimum per-enricher timeout of follow-ups, which do not delay any event.
+const FOLLOW_UP_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Error returned by an enricher that could not enrich an event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnrichError(pub String);
+
+impl fmt::Display for EnrichError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for EnrichError {}
+
+/// A single enrichment stage.
+#[async_trait]
+pub trait Enricher: Send + Sync {
+    /// Stable name used in `ENRICHERS`, timeouts, and metric names (e.g., "price").
+    fn name(&self) -> &'static str;
+
+    /// Adds information to the event in place.
+    async fn enrich(&self, event: &mut SwapEvent) -> Result<(), EnrichError>;
+}
+
+/// Enricher order, enable flags, and timeouts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnrichmentPipelineConfig {
+    /// Enabled enricher names, in execution order
+    pub enrichers: Vec<String>,
+    /// Timeout applied to enrichers without a specific override
+    pub default_timeout: Duration,
+    /// Time an event may spend in all enrichers together
+    pub budget: Option<Duration>,
+    /// Complete enrichment cut by the budget or timed out in a follow-up update
+    pub follow_up: bool,
+}
+
+impl EnrichmentPipelineConfig {
+    /// Creates pipeline configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `ENRICHERS` - Optional: Enabled enrichers in order (default: `defaults`)
+    /// - `ENRICHER_TIMEOUT_MS` - Optional: Default per-enricher timeout (default: 50)
+    /// - `ENRICHER_<NAME>_TIMEOUT_MS` - Optional: Timeout for one enricher (e.g.,
+    ///   `ENRICHER_METADATA_TIMEOUT_MS`)
+    /// - `ENRICHMENT_BUDGET_MS` - Optional: Time an event may spend in all enrichers
+    ///   together (default: unlimited)
+    /// - `ENRICHMENT_FOLLOW_UP` - Optional: `true` to update stored and streamed events
+    ///   once the enrichment cut by the budget or timed out completes (default: false)
+    pub fn from_env(defaults: &[&str]) -> Self {
+        let enrichers = match env::var("ENRICHERS") {
+            Ok(value) if !value.trim().is_empty() => value
+                .split(',')
+                .map(|name| name.trim().to_lowercase())
+                .filter(|name| !name.is_empty())
+                .collect(),
+            _ => defaults.iter().map(|name| name.to_string()).collect(),
+        };
+
+        Self {
+            enrichers,
+            default_timeout: timeout_from_env("ENRICHER_TIMEOUT_MS")
+                .unwrap_or(Duration::from_millis(DEFAULT_TIMEOUT_MS)),
+            budget: timeout_from_env("ENRICHMENT_BUDGET_MS").filter(|budget| !budget.is_zero()),
+            follow_up: env::var("ENRICHMENT_FOLLOW_UP")
+                .is_ok_and(|v| matches!(v.trim().to_lowercase().as_str(), "true" | "1")),
+        }
+    }
+
+    /// Returns the timeout for an enricher, honoring `ENRICHER_<NAME>_TIMEOUT_MS`.
+    pub fn timeout_for(&self, name: &str) -> Duration {
+        timeout_from_env(&format!("ENRICHER_{}_TIMEOUT_MS", name.to_uppercase()))
+            .unwrap_or(self.default_timeout)
+    }
+}
+
+/// Reads a millisecond duration from an environment variable.
+fn timeout_from_env(var: &str) -> Option<Duration> {
+    env::var(var)
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(Duration::from_millis)
+}
+
+/// An enricher with its timeout.
+struct Stage {
+    enricher: Arc<dyn Enricher>,
+    timeout: Duration,
+}
+
+/// Runs enrichers in order with per-stage timeouts and an optional per-event budget.
+///
+/// Reports per-enricher metrics:
+/// - `enricher_{name}_time_us` (histogram) - time spent in the stage
+/// - `enricher_{name}_timeouts` (counter) - events for which the stage timed out
+/// - `enricher_{name}_errors` (counter) - events for which the stage failed
+/// - `enrichment_budget_exceeded` (counter) - events emitted with stages cut by the budget
+pub struct EnrichmentPipeline {
+    stages: Vec<Stage>,
+    budget: Option<Duration>,
+    follow_up: bool,
+    /// Storage updated by follow-ups
+    storage: Option<Arc<dyn StorageSink>>,
+    /// Stream hub follow-ups publish event updates to
+    hub: Option<Arc<StreamHub>>,
+}
+
+impl EnrichmentPipeline {
+    /// Creates an empty pipeline builder.
+    pub fn builder() -> EnrichmentPipelineBuilder {
+        EnrichmentPipelineBuilder::default()
+    }
+
+    /// Builds a pipeline from the enrichers enabled in `config`, in its order.
+    ///
+    /// Unknown names are logged and ignored.
+    pub fn from_config(
+        config: &EnrichmentPipelineConfig,
+        available: Vec<Arc<dyn Enricher>>,
+    ) -> Self {
+        let mut builder = Self::builder();
+        if let Some(budget) = config.budget {
+            builder = builder.budget(budget);
+        }
+        builder = builder.follow_up(config.follow_up);
+        for name in &config.enrichers {
+            match available.iter().find(|e| e.name() == name) {
+                Some(enricher) => {
+                    builder = builder.stage(enricher.clone(), config.timeout_for(name));
+                }
+                None => log::warn!("Unknown or unavailable enricher '{name}', skipping"),
+            }
+        }
+        builder.build()
+    }
+
+    /// Sets where follow-ups deliver completed events: stored events are
+    /// replaced in `storage`, and the newly resolved fields are published on
+    /// `hub`'s `updates` topic.
+    pub fn with_updates(
+        mut self,
+        storage: Option<Arc<dyn StorageSink>>,
+        hub: Option<Arc<StreamHub>>,
+    ) -> Self {
+        self.storage = storage;
+        self.hub = hub;
+        self
+    }
+
+    /// Returns the names of the enabled enrichers, in order.
+    pub fn names(&self) -> Vec<&'static str> {
+        self.stages.iter().map(|s| s.enricher.name()).collect()
+    }
+
+    /// Returns `true` if no enricher is enabled.
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Runs every stage on the event.
+    ///
+    /// Failures and timeouts are recorded and the event continues through the
+    /// remaining stages. Stages that do not fit in the remaining budget are
+    /// cut; with follow-ups enabled, their names and those of timed out
+    /// stages are left in `event.pending_enrichment`.
+    pub async fn run(&self, event: &mut SwapEvent, metrics: &MetricsCollection) {
+        let deadline = self.budget.map(|budget| Instant::now() + budget);
+        let mut cut = Vec::new();
+        let mut pending = Vec::new();
+
+        for stage in &self.stages {
+            let name = stage.enricher.name();
+            let remaining =
+                deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+            if remaining.is_some_and(|remaining| remaining.is_zero()) {
+                cut.push(name);
+                pending.push(name);
+                continue;
+            }
+            let timeout = remaining.map_or(stage.timeout, |remaining| remaining.min(stage.timeout));
+
+            let start = Instant::now();
+            let result = tokio::time::timeout(timeout, stage.enricher.enrich(event)).await;
+            let elapsed_us = start.elapsed().as_micros() as f64;
+
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    log::debug!("Enricher '{name}' failed for {}: {e}", event.signature);
+                    let _ = metrics
+                        .increment_counter(&format!("enricher_{name}_errors"), 1)
+                        .await;
+                }
+                Err(_) if timeout < stage.timeout => {
+                    cut.push(name);
+                    pending.push(name);
+                }
+                Err(_) => {
+                    log::debug!("Enricher '{name}' timed out for {}", event.signature);
+                    let _ = metrics
+                        .increment_counter(&format!("enricher_{name}_timeouts"), 1)
+                        .await;
+                    pending.push(name);
+                }
+            }
+            let _ = metrics
+                .record_histogram(&format!("enricher_{name}_time_us"), elapsed_us)
+                .await;
+        }
+
+        if !cut.is_empty() {
+            log::debug!(
+                "Enrichment budget exceeded for {}, cut: {}",
+                event.signature,
+                cut.join(",")
+            );
+            let _ = metrics
+                .increment_counter("enrichment_budget_exceeded", 1)
+                .await;
+        }
+        if self.follow_up {
+            event.pending_enrichment = pending;
+        }
+    }
+
+    /// Runs the stages left in `event.pending_enrichment` on the event, with
+    /// at least the follow-up timeout, and clears the list.
+    pub async fn complete(&self, event: &mut SwapEvent) {
+        let pending = std::mem::take(&mut event.pending_enrichment);
+        for stage in &self.stages {
+            let name = stage.enricher.name();
+            if !pending.contains(&name) {
+                continue;
+            }
+            let timeout = stage.timeout.max(FOLLOW_UP_TIMEOUT);
+            match tokio::time::timeout(timeout, stage.enricher.enrich(event)).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    log::debug!(
+                        "Follow-up enricher '{name}' failed for {}: {e}",
+                        event.signature
+                    )
+                }
+                Err(_) => log::debug!(
+                    "Follow-up enricher '{name}' timed out for {}",
+                    event.signature
+                ),
+            }
+        }
+    }
+
+    /// Spawns the follow-up of an event published with enrichment pending:
+    /// the pending stages are completed and, if they resolved new fields, the
+    /// event is queued as an update on the storage sink and an
+    /// [`EventUpdate`] is published on the stream hub. Does nothing if no
+    /// enrichment is pending or no update target is set.
+    pub fn spawn_follow_up(self: &Arc<Self>, event: &SwapEvent) {
+        if event.pending_enrichment.is_empty() || (self.storage.is_none() && self.hub.is_none()) {
+            return;
+        }
+        let (pipeline, mut event) = (self.clone(), event.clone());
+        tokio::spawn(async move {
+            let emitted = event.clone();
+            pipeline.complete(&mut event).await;
+            let Some(update) = EventUpdate::diff(&emitted, &event) else {
+                return;
+            };
+            if let Some(ref hub) = pipeline.hub {
+                hub.publish_update(&update);
+            }
+            if let Some(ref sink) = pipeline.storage {
+                if let Err(e) = sink.try_update(event) {
+                    log::warn!(
+                        "Failed to queue enrichment update for {} storage: {e}",
+                        sink.name()
+                    );
+                }
+            }
+        });
+    }
+}
+
+/// Builder for [`EnrichmentPipeline`].
+#[derive(Default)]
+pub struct EnrichmentPipelineBuilder {
+    stages: Vec<Stage>,
+    budget: Option<Duration>,
+    follow_up: bool,
+}
+
+impl EnrichmentPipelineBuilder {
+    /// Appends an enricher with its timeout.
+    pub fn stage(mut self, enricher: Arc<dyn Enricher>, timeout: Duration) -> Self {
+        self.stages.push(Stage { enricher, timeout });
+        self
+    }
+
+    /// Limits the time an event may spend in all stages together.
+    pub fn budget(mut self, budget: Duration) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Leaves the stages cut by the budget or timed out in
+    /// `event.pending_enrichment` for a follow-up update.
+    pub fn follow_up(mut self, enabled: bool) -> Self {
+        self.follow_up = enabled;
+        self
+    }
+
+    /// Builds the pipeline.
+    pub fn build(self) -> EnrichmentPipeline {
+        EnrichmentPipeline {
+            stages: self.stages,
+            budget: self.budget,
+            follow_up: self.follow_up,
+            storage: None,
+            hub: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{Protocol, TokenInfo},
+    };
+
+    struct SymbolEnricher(&'static str);
+
+    #[async_trait]
+    impl Enricher for SymbolEnricher {
+        fn name(&self) -> &'static str {
+            self.0
+        }
+
+        async fn enrich(&self, event: &mut SwapEvent) -> Result<(), EnrichError> {
+            if let Some(token) = event.input_token.as_mut() {
+                let symbol = token.symbol.take().unwrap_or_default();
+                token.symbol = Some(format!("{symbol}{}", self.0));
+            }
+            Ok(())
+        }
+    }
+
+    struct SlowEnricher;
+
+    #[async_trait]
+    impl Enricher for SlowEnricher {
+        fn name(&self) -> &'static str {
+            "slow"
+        }
+
+        async fn enrich(&self, event: &mut SwapEvent) -> Result<(), EnrichError> {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            event.fee = Some(1);
+            Ok(())
+        }
+    }
+
+    struct DelayedEnricher;
+
+    #[async_trait]
+    impl Enricher for DelayedEnricher {
+        fn name(&self) -> &'static str {
+            "delayed"
+        }
+
+        async fn enrich(&self, event: &mut SwapEvent) -> Result<(), EnrichError> {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            event.fee = Some(2);
+            Ok(())
+        }
+    }
+
+    fn test_event() -> SwapEvent {
+        SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .input_token(TokenInfo::new("mint", 1))
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_runs_configured_order() {
+        let config = EnrichmentPipelineConfig {
+            enrichers: vec!["b".to_string(), "a".to_string(), "missing".to_string()],
+            default_timeout: Duration::from_millis(50),
+            budget: None,
+            follow_up: false,
+        };
+        let available: Vec<Arc<dyn Enricher>> = vec![
+            Arc::new(SymbolEnricher("a")),
+            Arc::new(SymbolEnricher("b")),
+            Arc::new(SymbolEnricher("c")),
+        ];
+        let pipeline = EnrichmentPipeline::from_config(&config, available);
+        assert_eq!(pipeline.names(), vec!["b", "a"]);
+
+        let mut event = test_event();
+        pipeline
+            .run(&mut event, &MetricsCollection::new(vec![]))
+            .await;
+        assert_eq!(event.input_token.unwrap().symbol.as_deref(), Some("ba"));
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_skips_stage_on_timeout() {
+        let pipeline = EnrichmentPipeline::builder()
+            .stage(Arc::new(SlowEnricher), Duration::from_millis(10))
+            .stage(Arc::new(SymbolEnricher("a")), Duration::from_millis(50))
+            .build();
+
+        let mut event = test_event();
+        pipeline
+            .run(&mut event, &MetricsCollection::new(vec![]))
+            .await;
+        assert_eq!(event.fee, None);
+        assert_eq!(event.input_token.unwrap().symbol.as_deref(), Some("a"));
+    }
+
+    #[tokio::test]
+    async fn test_budget_cuts_stages_for_follow_up() {
+        let pipeline = EnrichmentPipeline::builder()
+            .stage(Arc::new(SymbolEnricher("a")), Duration::from_millis(50))
+            .stage(Arc::new(DelayedEnricher), Duration::from_secs(1))
+            .stage(Arc::new(SymbolEnricher("b")), Duration::from_millis(50))
+            .budget(Duration::from_millis(20))
+            .follow_up(true)
+            .build();
+
+        let mut event = test_event();
+        pipeline
+            .run(&mut event, &MetricsCollection::new(vec![]))
+            .await;
+        assert_eq!(event.fee, None);
+        assert_eq!(
+            event.input_token.as_ref().unwrap().symbol.as_deref(),
+            Some("a")
+        );
+        assert_eq!(event.pending_enrichment, ["delayed", "b"]);
+
+        pipeline.complete(&mut event).await;
+        assert_eq!(event.fee, Some(2));
+        assert_eq!(event.input_token.unwrap().symbol.as_deref(), Some("ab"));
+        assert!(event.pending_enrichment.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_stage_pending_for_follow_up() {
+        let pipeline = EnrichmentPipeline::builder()
+            .stage(Arc::new(SlowEnricher), Duration::from_millis(10))
+            .stage(Arc::new(SymbolEnricher("a")), Duration::from_millis(50))
+            .follow_up(true)
+            .build();
+
+        let mut event = test_event();
+        pipeline
+            .run(&mut event, &MetricsCollection::new(vec![]))
+            .await;
+        assert_eq!(event.pending_enrichment, ["slow"]);
+    }
+}
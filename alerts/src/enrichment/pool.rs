@@ -0,0 +1,222 @@
+//! Pool account resolution for CPMM, CLMM, AMM V4, Launchpad, and PumpSwap.
+//!
+//! Decodes pool state accounts with the workspace decoders to find the pool's
+//! token mints and vaults. Reserves are read from the vault token accounts.
+
+use {
+    crate::output::Protocol,
+    carbon_core::account::AccountDecoder,
+    carbon_pump_swap_decoder::{accounts::PumpSwapAccount, PumpSwapDecoder},
+    carbon_raydium_amm_v4_decoder::{accounts::RaydiumAmmV4Account, RaydiumAmmV4Decoder},
+    carbon_raydium_clmm_decoder::{accounts::RaydiumClmmAccount, RaydiumClmmDecoder},
+    carbon_raydium_cpmm_decoder::{accounts::RaydiumCpmmAccount, RaydiumCpmmDecoder},
+    carbon_raydium_launchpad_decoder::{
+        accounts::RaydiumLaunchpadAccount, RaydiumLaunchpadDecoder,
+    },
+    solana_account::Account,
+    solana_pubkey::Pubkey,
+};
+
+/// Offset of the `amount` field in an SPL token account.
+const TOKEN_ACCOUNT_AMOUNT_OFFSET: usize = 64;
+
+/// Resolved pool information.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolInfo {
+    /// Protocol that owns the pool
+    pub protocol: Protocol,
+    /// Mint of token 0 (coin for AMM V4, base for Launchpad and PumpSwap)
+    pub token_mint0: Pubkey,
+    /// Mint of token 1 (pc for AMM V4, quote for Launchpad and PumpSwap)
+    pub token_mint1: Pubkey,
+    /// Vault holding token 0
+    pub vault0: Pubkey,
+    /// Vault holding token 1
+    pub vault1: Pubkey,
+    /// Vault 0 balance in raw units, as of the last refresh plus observed swaps
+    pub reserve0: Option<u64>,
+    /// Vault 1 balance in raw units, as of the last refresh plus observed swaps
+    pub reserve1: Option<u64>,
+}
+
+impl PoolInfo {
+    /// Returns the pool's mint for a given vault, if it is one of the pool vaults.
+    pub fn mint_for_vault(&self, vault: &Pubkey) -> Option<Pubkey> {
+        if *vault == self.vault0 {
+            Some(self.token_mint0)
+        } else if *vault == self.vault1 {
+            Some(self.token_mint1)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the pool prices swaps from its vault balances.
+    ///
+    /// CLMM vaults hold liquidity across all ranges, and Launchpad curves use
+    /// virtual reserves, so their vault balances don't give the pool price.
+    pub fn is_constant_product(&self) -> bool {
+        matches!(
+            self.protocol,
+            Protocol::Cpmm | Protocol::AmmV4 | Protocol::PumpSwap
+        )
+    }
+
+    /// Returns the pool's other mint, if `mint` is one of the pool's mints.
+    pub fn other_mint(&self, mint: &Pubkey) -> Option<Pubkey> {
+        if *mint == self.token_mint0 {
+            Some(self.token_mint1)
+        } else if *mint == self.token_mint1 {
+            Some(self.token_mint0)
+        } else {
+            None
+        }
+    }
+}
+
+/// Decodes a Raydium pool account into [`PoolInfo`] (without reserves).
+///
+/// Returns `None` if the account is not a CPMM, CLMM, AMM V4, Launchpad, or PumpSwap pool.
+pub fn decode_pool_account(account: &Account) -> Option<PoolInfo> {
+    if let Some(decoded) = RaydiumCpmmDecoder.decode_account(account) {
+        if let RaydiumCpmmAccount::PoolState(pool) = decoded.data {
+            return Some(PoolInfo {
+                protocol: Protocol::Cpmm,
+                token_mint0: pool.token_0_mint,
+                token_mint1: pool.token_1_mint,
+                vault0: pool.token_0_vault,
+                vault1: pool.token_1_vault,
+                reserve0: None,
+                reserve1: None,
+            });
+        }
+    }
+
+    if let Some(decoded) = RaydiumClmmDecoder.decode_account(account) {
+        if let RaydiumClmmAccount::PoolState(pool) = decoded.data {
+            return Some(PoolInfo {
+                protocol: Protocol::Clmm,
+                token_mint0: pool.token_mint0,
+                token_mint1: pool.token_mint1,
+                vault0: pool.token_vault0,
+                vault1: pool.token_vault1,
+                reserve0: None,
+                reserve1: None,
+            });
+        }
+    }
+
+    if let Some(decoded) = RaydiumAmmV4Decoder.decode_account(account) {
+        if let RaydiumAmmV4Account::AmmInfo(amm) = decoded.data {
+            return Some(PoolInfo {
+                protocol: Protocol::AmmV4,
+                token_mint0: amm.coin_mint,
+                token_mint1: amm.pc_mint,
+                vault0: amm.token_coin,
+                vault1: amm.token_pc,
+                reserve0: None,
+                reserve1: None,
+            });
+        }
+    }
+
+    if let Some(decoded) = RaydiumLaunchpadDecoder.decode_account(account) {
+        if let RaydiumLaunchpadAccount::PoolState(pool) = decoded.data {
+            return Some(PoolInfo {
+                protocol: Protocol::Launchpad,
+                token_mint0: pool.base_mint,
+                token_mint1: pool.quote_mint,
+                vault0: pool.base_vault,
+                vault1: pool.quote_vault,
+                reserve0: None,
+                reserve1: None,
+            });
+        }
+    }
+
+    if let Some(decoded) = PumpSwapDecoder.decode_account(account) {
+        if let PumpSwapAccount::Pool(pool) = decoded.data {
+            return Some(PoolInfo {
+                protocol: Protocol::PumpSwap,
+                token_mint0: pool.base_mint,
+                token_mint1: pool.quote_mint,
+                vault0: pool.pool_base_token_account,
+                vault1: pool.pool_quote_token_account,
+                reserve0: None,
+                reserve1: None,
+            });
+        }
+    }
+
+    None
+}
+
+/// Decodes the LP supply a CPMM or AMM V4 pool accounts for.
+///
+/// Withdrawals reduce it along with the LP mint's supply, while LP tokens
+/// burned directly only reduce the mint's supply.
+///
+/// Returns `None` if the account is not a CPMM or AMM V4 pool.
+pub fn decode_pool_lp_supply(account: &Account) -> Option<u64> {
+    if let Some(decoded) = RaydiumCpmmDecoder.decode_account(account) {
+        if let RaydiumCpmmAccount::PoolState(pool) = decoded.data {
+            return Some(pool.lp_supply);
+        }
+    }
+    if let Some(decoded) = RaydiumAmmV4Decoder.decode_account(account) {
+        if let RaydiumAmmV4Account::AmmInfo(amm) = decoded.data {
+            return Some(amm.lp_amount);
+        }
+    }
+    None
+}
+
+/// Parses the balance from an SPL token account's data.
+pub fn parse_token_account_amount(data: &[u8]) -> Option<u64> {
+    let bytes = data.get(TOKEN_ACCOUNT_AMOUNT_OFFSET..TOKEN_ACCOUNT_AMOUNT_OFFSET + 8)?;
+    Some(u64::from_le_bytes(bytes.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_token_account_amount() {
+        let mut data = vec![0u8; 165];
+        data[64..72].copy_from_slice(&1_234_567u64.to_le_bytes());
+        assert_eq!(parse_token_account_amount(&data), Some(1_234_567));
+        assert_eq!(parse_token_account_amount(&data[..70]), None);
+    }
+
+    #[test]
+    fn test_mint_for_vault() {
+        let info = PoolInfo {
+            protocol: Protocol::Clmm,
+            token_mint0: Pubkey::new_unique(),
+            token_mint1: Pubkey::new_unique(),
+            vault0: Pubkey::new_unique(),
+            vault1: Pubkey::new_unique(),
+            reserve0: None,
+            reserve1: None,
+        };
+        assert_eq!(info.mint_for_vault(&info.vault0), Some(info.token_mint0));
+        assert_eq!(info.mint_for_vault(&info.vault1), Some(info.token_mint1));
+        assert_eq!(info.mint_for_vault(&Pubkey::new_unique()), None);
+        assert_eq!(info.other_mint(&info.token_mint0), Some(info.token_mint1));
+        assert_eq!(info.other_mint(&info.vault0), None);
+    }
+
+    #[test]
+    fn test_decode_pool_account_rejects_unknown_owner() {
+        let account = Account {
+            lamports: 1,
+            data: vec![0u8; 1024],
+            owner: Pubkey::new_unique(),
+            executable: false,
+            rent_epoch: 0,
+        };
+        assert!(decode_pool_account(&account).is_none());
+        assert!(decode_pool_lp_supply(&account).is_none());
+    }
+}
@@ -0,0 +1,453 @@
+//! USD price enricher.
+//!
+//! Stablecoins (USDC, USDT) are valued at $1. The SOL price is learned from
+//! observed SOL/stablecoin swaps (smoothed with an exponential moving average)
+//! and can be seeded with `SOL_PRICE_USD`. For swaps, a leg without a known
+//! price takes the USD value of the other leg.
+//!
+//! Mints trading against SOL get a derived USD price: the last observed
+//! mint/SOL price times the SOL price. Each leg is timestamped and the derived
+//! price is only used while both are fresher than `PRICE_MAX_AGE_SECS`, so
+//! events of long-tail tokens are valued (e.g., liquidity changes, or swaps
+//! against another long-tail token) without an external token API. A seeded
+//! SOL price stays fresh until the first observation replaces it.
+//!
+//! With an [`EnrichmentCache`], the market cap of a non-quote token is set from
+//! its resolved supply.
+
+use {
+    super::{
+        pipeline::{EnrichError, Enricher},
+        EnrichmentCache,
+    },
+    crate::{
+        clock,
+        output::{
+            swap_event::{USDC_MINT, USDT_MINT, WSOL_MINT},
+            EventType, SwapEvent, TokenInfo,
+        },
+    },
+    async_trait::async_trait,
+    solana_pubkey::Pubkey,
+    std::{
+        collections::HashMap,
+        env,
+        str::FromStr,
+        sync::{Arc, RwLock},
+        time::{Duration, Instant},
+    },
+};
+
+/// Minimum SOL amount for a swap to update the learned SOL price.
+const MIN_SOL_FOR_PRICE: f64 = 0.1;
+
+/// Weight of a new observation in the SOL price moving average.
+const SOL_PRICE_ALPHA: f64 = 0.2;
+
+/// Minimum SOL amount for a swap to update the SOL price of a mint.
+const MIN_SOL_FOR_MINT_PRICE: f64 = 0.01;
+
+/// Default maximum age of a price leg used for derived prices.
+const DEFAULT_PRICE_MAX_AGE: Duration = Duration::from_secs(300);
+
+/// Mint SOL prices kept before stale ones are dropped.
+const MAX_MINT_PRICES: usize = 10_000;
+
+/// A price and when it was observed (`None` for a configured seed).
+#[derive(Debug, Clone, Copy)]
+struct Observed {
+    price: f64,
+    at: Option<Instant>,
+}
+
+impl Observed {
+    fn is_fresh(&self, max_age: Duration) -> bool {
+        self.at
+            .is_none_or(|at| clock::now().saturating_duration_since(at) <= max_age)
+    }
+}
+
+/// Sets `amount_usd` on event legs from stablecoin, SOL, and derived mint
+/// prices, and `market_cap_usd` from resolved supplies.
+pub struct PriceEnricher {
+    sol_price_usd: RwLock<Option<Observed>>,
+    /// Last observed price in SOL per mint
+    mint_prices: RwLock<HashMap<String, Observed>>,
+    max_age: Duration,
+    cache: Option<Arc<EnrichmentCache>>,
+}
+
+impl PriceEnricher {
+    /// Creates a price enricher from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `SOL_PRICE_USD` - Optional: Initial SOL price, until one is observed
+    /// - `PRICE_MAX_AGE_SECS` - Optional: Maximum age of the mint/SOL and SOL/USD
+    ///   prices a derived price is computed from (default: 300)
+    pub fn from_env() -> Self {
+        let seed = env::var("SOL_PRICE_USD")
+            .ok()
+            .and_then(|v| v.trim().parse::<f64>().ok())
+            .filter(|p| *p > 0.0);
+        let max_age = env::var("PRICE_MAX_AGE_SECS")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_PRICE_MAX_AGE);
+        Self::new(seed, max_age)
+    }
+
+    /// Creates a price enricher with an optional initial SOL price and the
+    /// maximum age of the prices behind derived token prices.
+    pub fn new(seed: Option<f64>, max_age: Duration) -> Self {
+        Self {
+            sol_price_usd: RwLock::new(seed.map(|price| Observed { price, at: None })),
+            mint_prices: RwLock::new(HashMap::new()),
+            max_age,
+            cache: None,
+        }
+    }
+
+    /// Sets the cache whose resolved supplies give market caps.
+    pub fn with_cache(mut self, cache: Arc<EnrichmentCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Returns the current SOL price estimate in USD.
+    pub fn sol_price_usd(&self) -> Option<f64> {
+        self.sol_price_usd.read().ok()?.map(|p| p.price)
+    }
+
+    /// Returns the derived USD price of a mint if both legs are fresh.
+    pub fn mint_price_usd(&self, mint: &str) -> Option<f64> {
+        let sol = (*self.sol_price_usd.read().ok()?).filter(|p| p.is_fresh(self.max_age))?;
+        let mint = self
+            .mint_prices
+            .read()
+            .ok()?
+            .get(mint)
+            .copied()
+            .filter(|p| p.is_fresh(self.max_age))?;
+        Some(mint.price * sol.price)
+    }
+
+    /// Returns the USD value of a swap: its priced leg's `amount_usd`, or a leg
+    /// valued at the current prices.
+    ///
+    /// Volume statistics use this to value swaps in USD at trade time even
+    /// when the price stage did not run or could not price them yet.
+    pub fn swap_value_usd(&self, event: &SwapEvent) -> Option<f64> {
+        event.usd_value().or_else(|| {
+            [event.input_token.as_ref(), event.output_token.as_ref()]
+                .into_iter()
+                .flatten()
+                .find_map(|token| Some(self.unit_price(token)? * ui_amount(token)?))
+        })
+    }
+
+    /// Returns the USD price of one whole token, if known.
+    fn unit_price(&self, token: &TokenInfo) -> Option<f64> {
+        match token.mint.as_str() {
+            USDC_MINT | USDT_MINT => Some(1.0),
+            WSOL_MINT => self.sol_price_usd(),
+            mint => self.mint_price_usd(mint),
+        }
+    }
+
+    /// Updates the SOL price from a SOL/stablecoin swap, or the SOL price of
+    /// a mint from a mint/SOL swap.
+    fn observe(&self, a: &TokenInfo, b: &TokenInfo) {
+        let (sol, other) = match (a.mint.as_str(), b.mint.as_str()) {
+            (WSOL_MINT, _) => (a, b),
+            (_, WSOL_MINT) => (b, a),
+            _ => return,
+        };
+        let (Some(sol_amount), Some(other_amount)) = (ui_amount(sol), ui_amount(other)) else {
+            return;
+        };
+        if other_amount <= 0.0 {
+            return;
+        }
+
+        if is_quote(&other.mint) {
+            if sol_amount < MIN_SOL_FOR_PRICE || !is_stable(&other.mint) {
+                return;
+            }
+            let observed = other_amount / sol_amount;
+            if let Ok(mut price) = self.sol_price_usd.write() {
+                let smoothed = match *price {
+                    Some(current) => current.price + SOL_PRICE_ALPHA * (observed - current.price),
+                    None => observed,
+                };
+                *price = Some(Observed {
+                    price: smoothed,
+                    at: Some(clock::now()),
+                });
+            }
+        } else if sol_amount >= MIN_SOL_FOR_MINT_PRICE {
+            if let Ok(mut prices) = self.mint_prices.write() {
+                if prices.len() >= MAX_MINT_PRICES && !prices.contains_key(&other.mint) {
+                    prices.retain(|_, p| p.is_fresh(self.max_age));
+                }
+                prices.insert(
+                    other.mint.clone(),
+                    Observed {
+                        price: sol_amount / other_amount,
+                        at: Some(clock::now()),
+                    },
+                );
+            }
+        }
+    }
+
+    /// Returns the market cap of the event's non-quote token, if its supply
+    /// and price are known.
+    fn market_cap(&self, event: &SwapEvent) -> Option<f64> {
+        let cache = self.cache.as_ref()?;
+        let mut legs = [event.input_token.as_ref(), event.output_token.as_ref()]
+            .into_iter()
+            .flatten()
+            .filter(|token| !is_quote(&token.mint));
+        let token = legs.next()?;
+        if legs.next().is_some() {
+            // Neither leg is a quote token, so the market is ambiguous
+            return None;
+        }
+
+        let meta = cache.token(&Pubkey::from_str(&token.mint).ok()?)?;
+        let supply = meta.supply? as f64 / 10_f64.powi(meta.decimals as i32);
+        let price = match (token.amount_usd, token.amount) {
+            (Some(usd), Some(amount)) if amount > 0.0 => usd / amount,
+            _ => self.unit_price(token)?,
+        };
+        Some(price * supply)
+    }
+}
+
+/// Returns `true` for SOL and stablecoin mints, which price other tokens.
+fn is_quote(mint: &str) -> bool {
+    mint == WSOL_MINT || is_stable(mint)
+}
+
+fn is_stable(mint: &str) -> bool {
+    matches!(mint, USDC_MINT | USDT_MINT)
+}
+
+/// Returns the human-readable amount, falling back to well-known decimals.
+fn ui_amount(token: &TokenInfo) -> Option<f64> {
+    token.amount.or_else(|| {
+        let decimals = match token.mint.as_str() {
+            WSOL_MINT => i32::from(crate::chain::profile().native_decimals),
+            USDC_MINT | USDT_MINT => 6,
+            _ => return None,
+        };
+        Some(token.amount_raw as f64 / 10_f64.powi(decimals))
+    })
+}
+
+#[async_trait]
+impl Enricher for PriceEnricher {
+    fn name(&self) -> &'static str {
+        "price"
+    }
+
+    async fn enrich(&self, event: &mut SwapEvent) -> Result<(), EnrichError> {
+        let is_swap = event.event_type == EventType::Swap;
+        if is_swap {
+            if let (Some(input), Some(output)) = (&event.input_token, &event.output_token) {
+                self.observe(input, output);
+            }
+        }
+
+        for token in [event.input_token.as_mut(), event.output_token.as_mut()]
+            .into_iter()
+            .flatten()
+        {
+            if token.amount_usd.is_none() {
+                if let (Some(price), Some(amount)) = (self.unit_price(token), ui_amount(token)) {
+                    token.amount_usd = Some(amount * price);
+                }
+            }
+        }
+
+        // Both legs of a swap have the same value
+        if is_swap {
+            if let (Some(input), Some(output)) =
+                (event.input_token.as_mut(), event.output_token.as_mut())
+            {
+                match (input.amount_usd, output.amount_usd) {
+                    (Some(usd), None) => output.amount_usd = Some(usd),
+                    (None, Some(usd)) => input.amount_usd = Some(usd),
+                    _ => {}
+                }
+            }
+        }
+
+        if event.market_cap_usd.is_none() {
+            event.market_cap_usd = self.market_cap(event);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::output::Protocol};
+
+    fn swap(input: TokenInfo, output: TokenInfo) -> SwapEvent {
+        SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .input_token(input)
+            .output_token(output)
+            .build()
+    }
+
+    fn enricher(seed: Option<f64>) -> PriceEnricher {
+        PriceEnricher::new(seed, DEFAULT_PRICE_MAX_AGE)
+    }
+
+    #[tokio::test]
+    async fn test_stablecoin_leg_values_other_leg() {
+        let enricher = enricher(None);
+        let mut event = swap(
+            TokenInfo::new(USDC_MINT, 250_000_000),
+            TokenInfo::new("SomeMint", 1_000),
+        );
+        enricher.enrich(&mut event).await.unwrap();
+
+        assert_eq!(event.input_token.unwrap().amount_usd, Some(250.0));
+        assert_eq!(event.output_token.unwrap().amount_usd, Some(250.0));
+    }
+
+    #[tokio::test]
+    async fn test_learns_sol_price_from_stable_swaps() {
+        let enricher = enricher(None);
+        let mut event = swap(
+            TokenInfo::new(WSOL_MINT, 2_000_000_000),
+            TokenInfo::new(USDT_MINT, 300_000_000),
+        );
+        enricher.enrich(&mut event).await.unwrap();
+        assert_eq!(enricher.sol_price_usd(), Some(150.0));
+
+        let mut event = swap(
+            TokenInfo::new("SomeMint", 1_000),
+            TokenInfo::new(WSOL_MINT, 1_000_000_000),
+        );
+        enricher.enrich(&mut event).await.unwrap();
+        assert_eq!(event.input_token.unwrap().amount_usd, Some(150.0));
+    }
+
+    #[tokio::test]
+    async fn test_sol_price_is_smoothed() {
+        let enricher = enricher(Some(100.0));
+        let mut event = swap(
+            TokenInfo::new(WSOL_MINT, 1_000_000_000),
+            TokenInfo::new(USDC_MINT, 200_000_000),
+        );
+        enricher.enrich(&mut event).await.unwrap();
+        assert_eq!(enricher.sol_price_usd(), Some(120.0));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_tokens_are_left_unpriced() {
+        let enricher = enricher(None);
+        let mut event = swap(TokenInfo::new("A", 1), TokenInfo::new("B", 2));
+        enricher.enrich(&mut event).await.unwrap();
+        assert!(event.input_token.unwrap().amount_usd.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_derives_mint_price_from_sol_pair() {
+        let enricher = enricher(Some(150.0));
+        // 1 SOL buys 2 tokens: 0.5 SOL each
+        let mut event = swap(
+            TokenInfo::new(WSOL_MINT, 1_000_000_000),
+            TokenInfo::new("Mint", 2_000).with_decimals(3),
+        );
+        enricher.enrich(&mut event).await.unwrap();
+        assert_eq!(enricher.mint_price_usd("Mint"), Some(75.0));
+
+        // A liquidity change has no SOL leg to copy from
+        let mut event = swap(
+            TokenInfo::new("Mint", 10_000).with_decimals(3),
+            TokenInfo::new("Other", 1),
+        );
+        event.event_type = EventType::AddLiquidity;
+        enricher.enrich(&mut event).await.unwrap();
+        assert_eq!(event.input_token.unwrap().amount_usd, Some(750.0));
+        assert!(event.output_token.unwrap().amount_usd.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stale_legs_are_not_used() {
+        let enricher = PriceEnricher::new(None, Duration::from_secs(60));
+        enricher.observe(
+            &TokenInfo::new(WSOL_MINT, 1_000_000_000),
+            &TokenInfo::new(USDC_MINT, 100_000_000),
+        );
+        enricher.observe(
+            &TokenInfo::new(WSOL_MINT, 1_000_000_000),
+            &TokenInfo::new("Mint", 4).with_decimals(0),
+        );
+        assert_eq!(enricher.mint_price_usd("Mint"), Some(25.0));
+
+        let Some(old) = Instant::now().checked_sub(Duration::from_secs(120)) else {
+            return;
+        };
+        enricher
+            .mint_prices
+            .write()
+            .unwrap()
+            .get_mut("Mint")
+            .unwrap()
+            .at = Some(old);
+        assert_eq!(enricher.mint_price_usd("Mint"), None);
+
+        enricher
+            .mint_prices
+            .write()
+            .unwrap()
+            .get_mut("Mint")
+            .unwrap()
+            .at = Some(Instant::now());
+        enricher.sol_price_usd.write().unwrap().as_mut().unwrap().at = Some(old);
+        assert_eq!(enricher.mint_price_usd("Mint"), None);
+        // Direct SOL valuation does not expire
+        assert_eq!(enricher.sol_price_usd(), Some(100.0));
+    }
+
+    #[tokio::test]
+    async fn test_small_sol_trades_do_not_price_mints() {
+        let enricher = enricher(Some(150.0));
+        enricher.observe(
+            &TokenInfo::new("Mint", 1).with_decimals(0),
+            &TokenInfo::new(WSOL_MINT, 1_000_000),
+        );
+        assert_eq!(enricher.mint_price_usd("Mint"), None);
+    }
+
+    #[test]
+    fn test_swap_value_usd_at_current_prices() {
+        let enricher = enricher(Some(150.0));
+        let event = swap(
+            TokenInfo::new("SomeMint", 1_000),
+            TokenInfo::new(WSOL_MINT, 2_000_000_000),
+        );
+        assert_eq!(enricher.swap_value_usd(&event), Some(300.0));
+
+        let priced = swap(
+            TokenInfo::new("SomeMint", 1_000).with_usd_value(280.0),
+            TokenInfo::new(WSOL_MINT, 2_000_000_000),
+        );
+        assert_eq!(enricher.swap_value_usd(&priced), Some(280.0));
+        assert_eq!(
+            enricher.swap_value_usd(&swap(TokenInfo::new("A", 1), TokenInfo::new("B", 2))),
+            None
+        );
+    }
+}
@@ -0,0 +1,200 @@
+//! Token metadata resolution from mint and Metaplex metadata accounts.
+//!
+//! Decimals, supply, and the mint and freeze authorities come from the SPL
+//! mint account (same base layout for Token-2022).
+//! Symbols come from the Metaplex Token Metadata account derived from the mint.
+
+use {
+    crate::{
+        chain,
+        output::swap_event::{USDC_MINT, USDT_MINT, WSOL_MINT},
+    },
+    solana_pubkey::Pubkey,
+    std::str::FromStr,
+};
+
+/// Metaplex Token Metadata program ID.
+pub const METAPLEX_METADATA_PROGRAM_ID: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+
+/// Offset of the `mint_authority` field (`COption<Pubkey>`) in an SPL mint account.
+const MINT_AUTHORITY_OFFSET: usize = 0;
+
+/// Offset of the `freeze_authority` field (`COption<Pubkey>`) in an SPL mint account.
+const MINT_FREEZE_AUTHORITY_OFFSET: usize = 46;
+
+/// Offset of the `supply` field in an SPL mint account.
+const MINT_SUPPLY_OFFSET: usize = 36;
+
+/// Offset of the `decimals` field in an SPL mint account.
+const MINT_DECIMALS_OFFSET: usize = 44;
+
+/// Offset of the `name` field in a Metaplex metadata account
+/// (key: 1 byte, update_authority: 32 bytes, mint: 32 bytes).
+const METADATA_NAME_OFFSET: usize = 65;
+
+/// Resolved token metadata.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenMetadata {
+    /// Token symbol (e.g., "SOL"), if published
+    pub symbol: Option<String>,
+    /// Token decimals
+    pub decimals: u8,
+    /// Total supply in base units when resolved (unset for well-known tokens)
+    pub supply: Option<u64>,
+}
+
+/// Returns metadata for well-known base tokens without an RPC round-trip.
+///
+/// The native mint carries the chain's native token symbol and decimals.
+pub fn well_known_token(mint: &Pubkey) -> Option<TokenMetadata> {
+    let chain = chain::profile();
+    let (symbol, decimals) = match mint.to_string().as_str() {
+        WSOL_MINT => (chain.native_symbol.as_str(), chain.native_decimals),
+        USDC_MINT => ("USDC", 6),
+        USDT_MINT => ("USDT", 6),
+        _ => return None,
+    };
+    Some(TokenMetadata {
+        symbol: Some(symbol.to_string()),
+        decimals,
+        supply: None,
+    })
+}
+
+/// Derives the Metaplex metadata account address for a mint.
+pub fn metadata_address(mint: &Pubkey) -> Pubkey {
+    let program_id = Pubkey::from_str(METAPLEX_METADATA_PROGRAM_ID)
+        .expect("Invalid Metaplex metadata program ID constant");
+    Pubkey::find_program_address(
+        &[b"metadata", program_id.as_ref(), mint.as_ref()],
+        &program_id,
+    )
+    .0
+}
+
+/// Parses the decimals from an SPL mint account's data.
+pub fn parse_mint_decimals(data: &[u8]) -> Option<u8> {
+    data.get(MINT_DECIMALS_OFFSET).copied()
+}
+
+/// Parses the total supply from an SPL mint account's data.
+pub fn parse_mint_supply(data: &[u8]) -> Option<u64> {
+    let bytes = data.get(MINT_SUPPLY_OFFSET..MINT_SUPPLY_OFFSET + 8)?;
+    Some(u64::from_le_bytes(bytes.try_into().ok()?))
+}
+
+/// Parses the mint authority from an SPL mint account's data.
+///
+/// Returns `Some(None)` if the authority is revoked, `None` if the data is too short.
+pub fn parse_mint_authority(data: &[u8]) -> Option<Option<Pubkey>> {
+    read_coption_pubkey(data, MINT_AUTHORITY_OFFSET)
+}
+
+/// Parses the freeze authority from an SPL mint account's data.
+///
+/// Returns `Some(None)` if the authority is revoked, `None` if the data is too short.
+pub fn parse_freeze_authority(data: &[u8]) -> Option<Option<Pubkey>> {
+    read_coption_pubkey(data, MINT_FREEZE_AUTHORITY_OFFSET)
+}
+
+/// Reads a `COption<Pubkey>` (u32 tag, then the key) at `offset`.
+fn read_coption_pubkey(data: &[u8], offset: usize) -> Option<Option<Pubkey>> {
+    let tag = u32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?);
+    let key: [u8; 32] = data.get(offset + 4..offset + 36)?.try_into().ok()?;
+    Some((tag != 0).then(|| Pubkey::new_from_array(key)))
+}
+
+/// Parses the symbol from a Metaplex metadata account's data.
+///
+/// Layout after the fixed header: `name: String`, `symbol: String` (borsh,
+/// u32 length prefix). Values are right-padded with NUL bytes on-chain.
+pub fn parse_metadata_symbol(data: &[u8]) -> Option<String> {
+    let (_, rest) = read_borsh_string(data.get(METADATA_NAME_OFFSET..)?)?;
+    let (symbol, _) = read_borsh_string(rest)?;
+    let symbol = symbol.trim_matches(char::from(0)).trim();
+    if symbol.is_empty() {
+        None
+    } else {
+        Some(symbol.to_string())
+    }
+}
+
+/// Reads a borsh-encoded string, returning it and the remaining bytes.
+fn read_borsh_string(data: &[u8]) -> Option<(String, &[u8])> {
+    let len = u32::from_le_bytes(data.get(..4)?.try_into().ok()?) as usize;
+    let bytes = data.get(4..4 + len)?;
+    Some((
+        String::from_utf8_lossy(bytes).into_owned(),
+        &data[4 + len..],
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn borsh_string(s: &str, padded_len: usize) -> Vec<u8> {
+        let mut bytes = s.as_bytes().to_vec();
+        bytes.resize(padded_len, 0);
+        let mut out = (bytes.len() as u32).to_le_bytes().to_vec();
+        out.extend(bytes);
+        out
+    }
+
+    #[test]
+    fn test_parse_metadata_symbol() {
+        let mut data = vec![4u8; METADATA_NAME_OFFSET];
+        data.extend(borsh_string("Macaron Token", 32));
+        data.extend(borsh_string("MACARON", 10));
+        data.extend(borsh_string("https://example.com", 200));
+
+        assert_eq!(parse_metadata_symbol(&data), Some("MACARON".to_string()));
+    }
+
+    #[test]
+    fn test_parse_metadata_symbol_truncated() {
+        let data = vec![4u8; METADATA_NAME_OFFSET + 2];
+        assert_eq!(parse_metadata_symbol(&data), None);
+    }
+
+    #[test]
+    fn test_parse_mint_decimals() {
+        let mut data = vec![0u8; 82];
+        data[MINT_DECIMALS_OFFSET] = 6;
+        assert_eq!(parse_mint_decimals(&data), Some(6));
+        assert_eq!(parse_mint_decimals(&data[..10]), None);
+    }
+
+    #[test]
+    fn test_parse_mint_supply() {
+        let mut data = vec![0u8; 82];
+        data[MINT_SUPPLY_OFFSET..MINT_SUPPLY_OFFSET + 8]
+            .copy_from_slice(&1_000_000_000_000_000u64.to_le_bytes());
+        assert_eq!(parse_mint_supply(&data), Some(1_000_000_000_000_000));
+        assert_eq!(parse_mint_supply(&data[..40]), None);
+    }
+
+    #[test]
+    fn test_parse_authorities() {
+        let authority = Pubkey::new_unique();
+        let mut data = vec![0u8; 82];
+        data[..4].copy_from_slice(&1u32.to_le_bytes());
+        data[4..36].copy_from_slice(authority.as_ref());
+        assert_eq!(parse_mint_authority(&data), Some(Some(authority)));
+        assert_eq!(parse_freeze_authority(&data), Some(None));
+
+        data[MINT_FREEZE_AUTHORITY_OFFSET..MINT_FREEZE_AUTHORITY_OFFSET + 4]
+            .copy_from_slice(&1u32.to_le_bytes());
+        assert!(parse_freeze_authority(&data).unwrap().is_some());
+        assert_eq!(parse_freeze_authority(&data[..60]), None);
+    }
+
+    #[test]
+    fn test_well_known_token() {
+        let sol = Pubkey::from_str(WSOL_MINT).unwrap();
+        let meta = well_known_token(&sol).unwrap();
+        assert_eq!(meta.symbol.as_deref(), Some("SOL"));
+        assert_eq!(meta.decimals, 9);
+        assert!(well_known_token(&Pubkey::new_unique()).is_none());
+    }
+}
@@ -0,0 +1,167 @@
+//! Token account to mint resolution.
+//!
+//! AMM V4 swap instructions reference the user's token accounts instead of
+//! mints. [`TokenAccountResolver`] looks them up with `getMultipleAccounts` and
+//! keeps the results in an LRU cache. A token account's mint never changes,
+//! so cached entries never go stale; the cache only bounds memory.
+
+use {
+    crate::output::token_transfer::{SPL_TOKEN_2022_PROGRAM_ID, SPL_TOKEN_PROGRAM_ID},
+    lru::LruCache,
+    solana_client::nonblocking::rpc_client::RpcClient,
+    solana_pubkey::Pubkey,
+    std::{
+        collections::HashMap,
+        num::NonZeroUsize,
+        sync::{Arc, Mutex, PoisonError},
+        time::Duration,
+    },
+};
+
+/// Size of an SPL token account without extensions.
+const TOKEN_ACCOUNT_LEN: usize = 165;
+
+/// Maximum time a lookup may block event processing.
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Parses the mint from an SPL token (or Token-2022) account's data.
+pub fn parse_token_account_mint(data: &[u8]) -> Option<Pubkey> {
+    if data.len() < TOKEN_ACCOUNT_LEN {
+        return None;
+    }
+    Some(Pubkey::new_from_array(data[..32].try_into().ok()?))
+}
+
+/// Parses the owner from an SPL token (or Token-2022) account's data.
+pub fn parse_token_account_owner(data: &[u8]) -> Option<Pubkey> {
+    if data.len() < TOKEN_ACCOUNT_LEN {
+        return None;
+    }
+    Some(Pubkey::new_from_array(data[32..64].try_into().ok()?))
+}
+
+/// Returns `true` if `owner` is the SPL Token or Token-2022 program.
+pub(crate) fn is_token_program(owner: &Pubkey) -> bool {
+    let owner = owner.to_string();
+    owner == SPL_TOKEN_PROGRAM_ID || owner == SPL_TOKEN_2022_PROGRAM_ID
+}
+
+/// Resolves token accounts to their mints, caching the results.
+pub struct TokenAccountResolver {
+    rpc: Arc<RpcClient>,
+    mints: Mutex<LruCache<Pubkey, Pubkey>>,
+}
+
+impl TokenAccountResolver {
+    /// Creates a resolver caching up to `capacity` token accounts.
+    pub fn new(rpc: Arc<RpcClient>, capacity: usize) -> Self {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            rpc,
+            mints: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    /// Returns the cached mint of a token account.
+    pub fn cached(&self, account: &Pubkey) -> Option<Pubkey> {
+        self.mints
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(account)
+            .copied()
+    }
+
+    /// Returns the mints of the given token accounts, fetching cache misses in
+    /// a single `getMultipleAccounts` request.
+    ///
+    /// Accounts that don't exist (e.g., closed in the same transaction), are
+    /// not token accounts, or can't be fetched within the timeout are omitted.
+    pub async fn resolve(&self, accounts: &[Pubkey]) -> HashMap<Pubkey, Pubkey> {
+        let mut resolved = HashMap::new();
+        let mut misses = Vec::new();
+        for account in accounts {
+            match self.cached(account) {
+                Some(mint) => {
+                    resolved.insert(*account, mint);
+                }
+                None if !misses.contains(account) => misses.push(*account),
+                None => {}
+            }
+        }
+        if misses.is_empty() {
+            return resolved;
+        }
+
+        let fetched =
+            match tokio::time::timeout(LOOKUP_TIMEOUT, self.rpc.get_multiple_accounts(&misses))
+                .await
+            {
+                Ok(Ok(fetched)) => fetched,
+                Ok(Err(e)) => {
+                    log::debug!("Failed to fetch {} token accounts: {e}", misses.len());
+                    return resolved;
+                }
+                Err(_) => {
+                    log::debug!("Timed out fetching {} token accounts", misses.len());
+                    return resolved;
+                }
+            };
+
+        let mut cache = self.mints.lock().unwrap_or_else(PoisonError::into_inner);
+        for (address, account) in misses.into_iter().zip(fetched) {
+            let Some(mint) = account
+                .filter(|account| is_token_program(&account.owner))
+                .and_then(|account| parse_token_account_mint(&account.data))
+            else {
+                continue;
+            };
+            cache.put(address, mint);
+            resolved.insert(address, mint);
+        }
+        resolved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::str::FromStr};
+
+    #[test]
+    fn test_parse_token_account_mint() {
+        let (mint, owner) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut data = vec![0u8; TOKEN_ACCOUNT_LEN];
+        data[..32].copy_from_slice(mint.as_ref());
+        data[32..64].copy_from_slice(owner.as_ref());
+
+        assert_eq!(parse_token_account_mint(&data), Some(mint));
+        assert_eq!(parse_token_account_owner(&data), Some(owner));
+        assert_eq!(parse_token_account_mint(&data[..64]), None);
+        assert!(is_token_program(
+            &Pubkey::from_str(SPL_TOKEN_2022_PROGRAM_ID).unwrap()
+        ));
+        assert!(!is_token_program(&Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let resolver = TokenAccountResolver::new(
+            Arc::new(RpcClient::new("http://127.0.0.1:0".to_string())),
+            2,
+        );
+        let (a, b, c) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        {
+            let mut cache = resolver.mints.lock().unwrap();
+            cache.put(a, Pubkey::new_unique());
+            cache.put(b, Pubkey::new_unique());
+        }
+        assert!(resolver.cached(&a).is_some());
+        resolver.mints.lock().unwrap().put(c, Pubkey::new_unique());
+
+        assert!(resolver.cached(&a).is_some());
+        assert!(resolver.cached(&b).is_none());
+    }
+}
@@ -0,0 +1,505 @@
+//! Fixture harness running recorded instructions through the processors.
+//!
+//! A fixture is a JSON file describing one transaction: its top-level
+//! instructions with their nested (CPI) instructions, and the events the
+//! processors are expected to emit for it. [`Fixture::check`] feeds the
+//! instructions to the processor of the fixture's market the way Carbon does,
+//! outer instruction first, then each inner one with the instructions below
+//! it, and compares the emitted events with the expected ones.
+//!
+//! ```json
+//! {
+//!   "description": "CPMM swap with the amounts of its token transfers",
+//!   "market": "cpmm",
+//!   "slot": 300000000,
+//!   "filters": { "tokens": [], "amms": [], "wallets": [] },
+//!   "instructions": [
+//!     {
+//!       "accounts": ["<payer>", "<authority>", "..."],
+//!       "instruction": { "SwapBaseInput": { "amount_in": 1000, "minimum_amount_out": 1 } },
+//!       "inner_instructions": [
+//!         { "program_id": "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+//!           "accounts": ["<source>", "<destination>", "<owner>"],
+//!           "data": "03e803000000000000" }
+//!       ]
+//!     }
+//!   ],
+//!   "expected": [{ "event_type": "swap", "input_token": { "amount_raw": 1000 } }]
+//! }
+//! ```
+//!
+//! - `instruction` is the decoded instruction of the market's decoder in its
+//!   serde form; instructions without one (token transfers, routers) are only
+//!   passed along as raw `data` (hex) in the nested instructions
+//! - `program_id` defaults to the market's program for decoded instructions
+//! - each `expected` event only lists the fields to check; the emitted events
+//!   must match them in order, and no other event may be emitted
+//!
+//! Events go through the processors' emit path without enrichment, dedup,
+//! slot batching, or throttling. Pool caches are not seeded, so markets that
+//! resolve mints from pool state emit events without them.
+
+use {
+    crate::{
+        config::MarketType,
+        output::{EventLog, Notifier, Notifiers, OutputFormat, SwapEvent},
+        processors::{
+            EventEmitter, FilterSet, InstructionInput, PumpFunInstructionProcessor,
+            PumpSwapInstructionProcessor, RaydiumAmmV4InstructionProcessor,
+            RaydiumClmmInstructionProcessor, RaydiumCpmmInstructionProcessor,
+            RaydiumLaunchpadInstructionProcessor,
+        },
+        reload::{LiveConfig, SharedConfig},
+    },
+    arc_swap::ArcSwap,
+    async_trait::async_trait,
+    carbon_core::{
+        instruction::{
+            DecodedInstruction, InstructionMetadata, NestedInstruction, NestedInstructions,
+        },
+        metrics::MetricsCollection,
+        processor::Processor,
+        transaction::TransactionMetadata,
+    },
+    serde::{de::DeserializeOwned, Deserialize},
+    serde_json::Value,
+    solana_instruction::{AccountMeta, Instruction},
+    solana_pubkey::Pubkey,
+    solana_signature::Signature,
+    std::{
+        collections::HashSet,
+        fs,
+        path::{Path, PathBuf},
+        str::FromStr,
+        sync::{Arc, Mutex},
+        time::Duration,
+    },
+};
+
+/// Time given to the capturing notifier to receive the emitted events.
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// One recorded transaction and the events it should produce.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Fixture {
+    /// What the fixture covers, shown when it fails
+    #[serde(default)]
+    pub description: String,
+    /// Market whose processor runs the instructions (e.g., "cpmm")
+    pub market: String,
+    /// Transaction signature (default: all zeros)
+    #[serde(default)]
+    pub signature: Option<String>,
+    /// Slot of the transaction
+    #[serde(default)]
+    pub slot: u64,
+    /// Block time of the transaction (Unix seconds)
+    #[serde(default)]
+    pub block_time: Option<i64>,
+    /// Live filters the processor checks (default: track everything)
+    #[serde(default)]
+    pub filters: FixtureFilters,
+    /// Top-level instructions in transaction order
+    pub instructions: Vec<FixtureInstruction>,
+    /// Fields of the expected events, in emit order
+    #[serde(default)]
+    pub expected: Vec<Value>,
+}
+
+/// Token, pool, and wallet filters of a fixture.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FixtureFilters {
+    /// Token mints to track
+    #[serde(default)]
+    pub tokens: Vec<String>,
+    /// Pool addresses to track
+    #[serde(default)]
+    pub amms: Vec<String>,
+    /// Wallets whose swaps always alert
+    #[serde(default)]
+    pub wallets: Vec<String>,
+}
+
+/// An instruction of a fixture and the instructions it invoked.
+#[derive(Debug, Clone, Deserialize)]
+pub struct FixtureInstruction {
+    /// Program invoked (default: the market's program)
+    #[serde(default)]
+    pub program_id: Option<String>,
+    /// Account addresses in instruction order
+    #[serde(default)]
+    pub accounts: Vec<String>,
+    /// Decoded instruction of the market's decoder, if it is one of the market's
+    #[serde(default)]
+    pub instruction: Option<Value>,
+    /// Raw instruction data (hex)
+    #[serde(default)]
+    pub data: String,
+    /// Instructions invoked by this one (CPIs)
+    #[serde(default)]
+    pub inner_instructions: Vec<FixtureInstruction>,
+}
+
+impl Fixture {
+    /// Reads a fixture file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be read or is not a fixture.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let json = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        serde_json::from_str(&json).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    /// Runs the fixture's instructions through its market's processor and
+    /// returns the emitted events in emit order.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the fixture names an unknown market, holds an
+    /// invalid address or hex string, or an instruction the market's decoder
+    /// does not know.
+    pub async fn run(&self) -> Result<Vec<SwapEvent>, String> {
+        let market: MarketType = self.market.parse()?;
+        let config: SharedConfig = Arc::new(ArcSwap::from_pointee(self.filters.live_config()?));
+        let capture = Arc::new(Capture::default());
+        let notifiers = Arc::new(Notifiers::spawn(vec![capture.clone()]));
+
+        let filters = FilterSet::new(config.clone(), market);
+        let emitter = EventEmitter::new(
+            market,
+            config,
+            Arc::new(EventLog::new(OutputFormat::Json, 0)),
+            notifiers.clone(),
+        );
+        let result = match market {
+            MarketType::Cpmm => {
+                let processor = RaydiumCpmmInstructionProcessor::new(filters, emitter);
+                self.process(market, processor).await
+            }
+            MarketType::Clmm => {
+                let processor = RaydiumClmmInstructionProcessor::new(filters, emitter);
+                self.process(market, processor).await
+            }
+            MarketType::AmmV4 => {
+                let processor = RaydiumAmmV4InstructionProcessor::new(filters, emitter);
+                self.process(market, processor).await
+            }
+            MarketType::Launchpad => {
+                let processor = RaydiumLaunchpadInstructionProcessor::new(filters, emitter);
+                self.process(market, processor).await
+            }
+            MarketType::PumpFun => {
+                let processor = PumpFunInstructionProcessor::new(filters, emitter);
+                self.process(market, processor).await
+            }
+            MarketType::PumpSwap => {
+                let processor = PumpSwapInstructionProcessor::new(filters, emitter);
+                self.process(market, processor).await
+            }
+        };
+        notifiers.shutdown(DRAIN_TIMEOUT).await;
+        result?;
+
+        Ok(capture.take())
+    }
+
+    /// Runs the fixture and compares the emitted events with the expected ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error describing the first mismatch, or why the fixture
+    /// could not be run.
+    pub async fn check(&self) -> Result<(), String> {
+        let events = self.run().await?;
+        let emitted: Vec<Value> = events
+            .iter()
+            .map(|event| serde_json::to_value(event).map_err(|e| e.to_string()))
+            .collect::<Result<_, _>>()?;
+
+        for (i, expected) in self.expected.iter().enumerate() {
+            let Some(event) = emitted.get(i) else {
+                return Err(format!(
+                    "{}: expected {} event(s), {} emitted",
+                    self.description,
+                    self.expected.len(),
+                    emitted.len()
+                ));
+            };
+            if !contains(event, expected) {
+                return Err(format!(
+                    "{}: event {i} does not match\nexpected: {expected}\nemitted:  {event}",
+                    self.description
+                ));
+            }
+        }
+        if emitted.len() > self.expected.len() {
+            return Err(format!(
+                "{}: expected {} event(s), {} emitted, the first unexpected: {}",
+                self.description,
+                self.expected.len(),
+                emitted.len(),
+                emitted[self.expected.len()]
+            ));
+        }
+        Ok(())
+    }
+
+    /// Feeds every decoded instruction, top-level or inner, to `processor`.
+    async fn process<P, T>(&self, market: MarketType, mut processor: P) -> Result<(), String>
+    where
+        P: Processor<InputType = InstructionInput<T>>,
+        T: DeserializeOwned,
+    {
+        let signature = match self.signature {
+            Some(ref signature) => Signature::from_str(signature)
+                .map_err(|e| format!("invalid signature {signature}: {e}"))?,
+            None => Signature::default(),
+        };
+        let transaction = Arc::new(TransactionMetadata {
+            slot: self.slot,
+            signature,
+            block_time: self.block_time,
+            ..Default::default()
+        });
+
+        let mut inputs = Vec::new();
+        for (index, instruction) in self.instructions.iter().enumerate() {
+            let path = [index as u8];
+            collect(market, &transaction, instruction, &path, &mut inputs)?;
+        }
+
+        let metrics = Arc::new(MetricsCollection::new(vec![]));
+        for input in inputs {
+            processor
+                .process(input, metrics.clone())
+                .await
+                .map_err(|e| format!("{}: {e}", self.description))?;
+        }
+        Ok(())
+    }
+}
+
+impl FixtureFilters {
+    /// Returns the live configuration holding the filters.
+    fn live_config(&self) -> Result<LiveConfig, String> {
+        let parse = |addresses: &[String]| -> Result<HashSet<Pubkey>, String> {
+            addresses.iter().map(|address| pubkey(address)).collect()
+        };
+        Ok(LiveConfig {
+            filter_tokens: parse(&self.tokens)?,
+            filter_amms: parse(&self.amms)?,
+            watch_wallets: parse(&self.wallets)?,
+            ..LiveConfig::default()
+        })
+    }
+}
+
+/// Reads the `.json` fixtures of a directory in name order.
+///
+/// # Errors
+///
+/// Returns an error if the directory or a fixture can't be read.
+pub fn load_fixtures(dir: &Path) -> Result<Vec<(PathBuf, Fixture)>, String> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map_err(|e| format!("{}: {e}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|file| file.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    files.sort();
+    files
+        .into_iter()
+        .map(|file| Fixture::load(&file).map(|fixture| (file, fixture)))
+        .collect()
+}
+
+/// Notifier keeping the events it was sent.
+#[derive(Default)]
+pub(crate) struct Capture {
+    events: Mutex<Vec<SwapEvent>>,
+}
+
+impl Capture {
+    /// Returns the events sent so far, in the order they were sent.
+    pub(crate) fn take(&self) -> Vec<SwapEvent> {
+        std::mem::take(&mut *self.events.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+}
+
+#[async_trait]
+impl Notifier for Capture {
+    fn name(&self) -> &str {
+        "capture"
+    }
+
+    async fn notify(&self, event: &SwapEvent) {
+        self.events
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(event.clone());
+    }
+}
+
+/// Adds the processor input of `instruction` and of the decoded instructions
+/// below it to `inputs`, in execution order.
+///
+/// `path` is the instruction's absolute path: its top-level index followed by
+/// its position at each inner level.
+fn collect<T: DeserializeOwned>(
+    market: MarketType,
+    transaction: &Arc<TransactionMetadata>,
+    instruction: &FixtureInstruction,
+    path: &[u8],
+    inputs: &mut Vec<InstructionInput<T>>,
+) -> Result<(), String> {
+    if let Some(ref decoded) = instruction.instruction {
+        let data: T = serde_json::from_value(decoded.clone())
+            .map_err(|e| format!("{} instruction {decoded}: {e}", market.name()))?;
+        let raw = raw_instruction(market, instruction)?;
+        inputs.push((
+            metadata(transaction, path),
+            DecodedInstruction {
+                program_id: raw.program_id,
+                data,
+                accounts: raw.accounts.clone(),
+            },
+            nested(market, transaction, &instruction.inner_instructions, path)?,
+            raw,
+        ));
+    }
+    for (position, inner) in instruction.inner_instructions.iter().enumerate() {
+        let path = [path, &[position as u8]].concat();
+        collect(market, transaction, inner, &path, inputs)?;
+    }
+    Ok(())
+}
+
+/// Returns the nested instructions below the instruction at `parent`.
+fn nested(
+    market: MarketType,
+    transaction: &Arc<TransactionMetadata>,
+    instructions: &[FixtureInstruction],
+    parent: &[u8],
+) -> Result<NestedInstructions, String> {
+    let mut nested = Vec::with_capacity(instructions.len());
+    for (position, instruction) in instructions.iter().enumerate() {
+        let path = [parent, &[position as u8]].concat();
+        nested.push(NestedInstruction {
+            metadata: metadata(transaction, &path),
+            instruction: raw_instruction(market, instruction)?,
+            inner_instructions: nested(
+                market,
+                transaction,
+                &instruction.inner_instructions,
+                &path,
+            )?,
+        });
+    }
+    Ok(NestedInstructions(nested))
+}
+
+/// Returns the metadata of the instruction at `path`.
+fn metadata(transaction: &Arc<TransactionMetadata>, path: &[u8]) -> InstructionMetadata {
+    InstructionMetadata {
+        transaction_metadata: transaction.clone(),
+        stack_height: path.len() as u32,
+        index: path[0] as u32,
+        absolute_path: path.to_vec(),
+    }
+}
+
+/// Returns the instruction as invoked on chain.
+fn raw_instruction(
+    market: MarketType,
+    instruction: &FixtureInstruction,
+) -> Result<Instruction, String> {
+    let program_id = match instruction.program_id {
+        Some(ref program_id) => pubkey(program_id)?,
+        None if instruction.instruction.is_some() => market.program_id(),
+        None => return Err("program_id is required for undecoded instructions".to_string()),
+    };
+    let accounts = instruction
+        .accounts
+        .iter()
+        .map(|account| pubkey(account).map(|pubkey| AccountMeta::new(pubkey, false)))
+        .collect::<Result<_, _>>()?;
+    Ok(Instruction {
+        program_id,
+        accounts,
+        data: hex(&instruction.data)?,
+    })
+}
+
+fn pubkey(address: &str) -> Result<Pubkey, String> {
+    Pubkey::from_str(address).map_err(|e| format!("invalid address {address}: {e}"))
+}
+
+fn hex(data: &str) -> Result<Vec<u8>, String> {
+    let data = data.trim();
+    if data.len() % 2 != 0 {
+        return Err(format!("invalid hex data {data}: odd length"));
+    }
+    (0..data.len())
+        .step_by(2)
+        .map(|i| {
+            data.get(i..i + 2)
+                .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                .ok_or_else(|| format!("invalid hex data {data}"))
+        })
+        .collect()
+}
+
+/// Returns `true` if `actual` holds every field of `expected` with the same
+/// value; objects may have more fields, arrays must have the same length.
+fn contains(actual: &Value, expected: &Value) -> bool {
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => expected.iter().all(|(key, value)| {
+            actual
+                .get(key)
+                .is_some_and(|actual| contains(actual, value))
+        }),
+        (Value::Array(actual), Value::Array(expected)) => {
+            actual.len() == expected.len()
+                && actual.iter().zip(expected).all(|(a, e)| contains(a, e))
+        }
+        _ => actual == expected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, serde_json::json};
+
+    #[test]
+    fn test_contains_only_checks_expected_fields() {
+        let event = json!({
+            "event_type": "swap",
+            "pool": "pool",
+            "input_token": { "mint": "a", "amount_raw": 1000 },
+            "suppressed": [{ "source": "event_log" }],
+        });
+        assert!(contains(&event, &json!({})));
+        assert!(contains(
+            &event,
+            &json!({ "pool": "pool", "input_token": { "amount_raw": 1000 } })
+        ));
+        assert!(!contains(
+            &event,
+            &json!({ "input_token": { "amount_raw": 999 } })
+        ));
+        assert!(!contains(&event, &json!({ "output_token": null })));
+        assert!(!contains(&event, &json!({ "suppressed": [] })));
+    }
+
+    #[tokio::test]
+    async fn test_fixtures() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+        let fixtures = load_fixtures(&dir).unwrap();
+        assert!(!fixtures.is_empty());
+        for (path, fixture) in fixtures {
+            if let Err(e) = fixture.check().await {
+                panic!("{}: {e}", path.display());
+            }
+        }
+    }
+}
@@ -0,0 +1,192 @@
+//! Human-readable labels for addresses.
+//!
+//! A labels file maps wallet (or any) addresses to names shown in alerts
+//! instead of truncated pubkeys:
+//!
+//! ```toml
+//! [labels]
+//! 7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU = "whale-3"
+//! 5Q544fKrFoe6tsEbD7S8EmxGTJYAKtTVhAW5Q5pge4j1 = "raydium-authority"
+//! ```
+//!
+//! The file is set with `LABELS_FILE` and reloaded with the rest of the live
+//! configuration. A remote list in the same format can be set with
+//! `LABELS_URL`; it is fetched periodically and labels addresses the file
+//! does not.
+
+use {
+    serde::Deserialize,
+    solana_pubkey::Pubkey,
+    std::{collections::HashMap, fmt, fs, path::Path, str::FromStr, sync::Arc},
+};
+
+/// Errors that can occur while loading a labels file.
+#[derive(Debug)]
+pub enum LabelsError {
+    /// The labels file could not be read
+    Read(String),
+    /// The remote labels list could not be fetched
+    Fetch(String),
+    /// The labels file is not valid TOML or has an unknown shape
+    Parse(String),
+    /// An entry is invalid (e.g., not a pubkey, empty label)
+    Invalid(String),
+}
+
+impl fmt::Display for LabelsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(e) => write!(f, "failed to read labels file: {e}"),
+            Self::Fetch(e) => write!(f, "failed to fetch labels list: {e}"),
+            Self::Parse(e) => write!(f, "failed to parse labels file: {e}"),
+            Self::Invalid(e) => write!(f, "invalid label: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for LabelsError {}
+
+/// Contents of a labels file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LabelsFile {
+    #[serde(default)]
+    labels: HashMap<String, String>,
+}
+
+/// Address to label map.
+///
+/// Labels from the remote list are kept as a second layer, consulted for
+/// addresses the labels file does not label, so each source can be reloaded
+/// on its own.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AddressLabels {
+    labels: HashMap<String, String>,
+    remote: Arc<HashMap<String, String>>,
+}
+
+impl AddressLabels {
+    /// Parses and validates labels from TOML.
+    pub fn parse(toml: &str) -> Result<Self, LabelsError> {
+        let file: LabelsFile =
+            toml::from_str(toml).map_err(|e| LabelsError::Parse(e.to_string()))?;
+
+        let mut labels = HashMap::with_capacity(file.labels.len());
+        for (address, label) in file.labels {
+            Pubkey::from_str(&address)
+                .map_err(|_| LabelsError::Invalid(format!("'{address}' is not a valid pubkey")))?;
+            let label = label.trim();
+            if label.is_empty() {
+                return Err(LabelsError::Invalid(format!("empty label for {address}")));
+            }
+            labels.insert(address, label.to_string());
+        }
+        Ok(Self {
+            labels,
+            remote: Arc::default(),
+        })
+    }
+
+    /// Reads, parses, and validates a labels file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LabelsError> {
+        let path = path.as_ref();
+        let toml = fs::read_to_string(path)
+            .map_err(|e| LabelsError::Read(format!("{}: {e}", path.display())))?;
+        Self::parse(&toml)
+    }
+
+    /// Fetches, parses, and validates a remote labels list.
+    pub async fn fetch(client: &reqwest::Client, url: &str) -> Result<Self, LabelsError> {
+        let fetch_err = |e: reqwest::Error| LabelsError::Fetch(format!("{url}: {e}"));
+        let toml = client
+            .get(url)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(fetch_err)?
+            .text()
+            .await
+            .map_err(fetch_err)?;
+        Self::parse(&toml)
+    }
+
+    /// Replaces the remote layer with the labels of a fetched list.
+    pub fn with_remote(mut self, remote: &AddressLabels) -> Self {
+        self.remote = Arc::new(remote.labels.clone());
+        self
+    }
+
+    /// Keeps the remote layer of `previous`, e.g. across a labels file reload.
+    pub fn with_remote_of(mut self, previous: &AddressLabels) -> Self {
+        self.remote = previous.remote.clone();
+        self
+    }
+
+    /// Returns the label of an address, if any. File labels take precedence
+    /// over remote ones.
+    pub fn get(&self, address: &str) -> Option<&str> {
+        self.labels
+            .get(address)
+            .or_else(|| self.remote.get(address))
+            .map(String::as_str)
+    }
+
+    /// Returns the number of labeled addresses.
+    pub fn len(&self) -> usize {
+        let remote_only = self
+            .remote
+            .keys()
+            .filter(|address| !self.labels.contains_key(*address))
+            .count();
+        self.labels.len() + remote_only
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHALE: &str = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+
+    #[test]
+    fn test_parse_labels() {
+        let labels = AddressLabels::parse(&format!("[labels]\n{WHALE} = \" whale-3 \"\n")).unwrap();
+        assert_eq!(labels.get(WHALE), Some("whale-3"));
+        assert_eq!(labels.get("other"), None);
+        assert_eq!(labels.len(), 1);
+
+        assert_eq!(AddressLabels::parse("").unwrap().len(), 0);
+        assert!(matches!(
+            AddressLabels::parse("[labels]\nnot_a_pubkey = \"x\""),
+            Err(LabelsError::Invalid(_))
+        ));
+        assert!(matches!(
+            AddressLabels::parse(&format!("[labels]\n{WHALE} = \"\"")),
+            Err(LabelsError::Invalid(_))
+        ));
+        assert!(matches!(
+            AddressLabels::parse("[wallets]"),
+            Err(LabelsError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_remote_labels_fill_in_for_file() {
+        const AUTHORITY: &str = "5Q544fKrFoe6tsEbD7S8EmxGTJYAKtTVhAW5Q5pge4j1";
+        let file = AddressLabels::parse(&format!("[labels]\n{WHALE} = \"whale-3\"")).unwrap();
+        let remote = AddressLabels::parse(&format!(
+            "[labels]\n{WHALE} = \"remote-whale\"\n{AUTHORITY} = \"raydium-authority\""
+        ))
+        .unwrap();
+
+        let labels = file.with_remote(&remote);
+        assert_eq!(labels.get(WHALE), Some("whale-3"));
+        assert_eq!(labels.get(AUTHORITY), Some("raydium-authority"));
+        assert_eq!(labels.len(), 2);
+
+        // A reloaded file keeps the fetched remote labels
+        let reloaded = AddressLabels::default().with_remote_of(&labels);
+        assert_eq!(reloaded.get(WHALE), Some("remote-whale"));
+        assert_eq!(reloaded.len(), 2);
+    }
+}
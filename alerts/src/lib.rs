@@ -0,0 +1,63 @@
+//! Raydium DEX alert pipeline as a library.
+//!
+//! The `raydium-alerts` binary is a thin wrapper around [`app::run`], which
+//! configures everything from environment variables. Other projects can embed
+//! the monitoring pipeline instead and choose its parts themselves:
+//!
+//! - [`PipelineBuilder`] - Wires the market processors to the given outputs and
+//!   builds a Carbon pipeline over any datasource
+//! - [`processors`] - Instruction processors of each Raydium and Pump.fun program
+//! - [`SwapEvent`] - The normalized event every processor emits
+//! - [`StorageSink`] and [`BatchWriter`] - Traits of custom event sinks; a
+//!   `BatchWriter` gets batching, retention, and candles from
+//!   [`BatchSink`](storage::BatchSink)
+//! - [`Notifier`] - Trait of alert channels, such as [`WebhookNotifier`] (webhook,
+//!   Discord, and Telegram delivery)
+//! - [`RuleEngine`] - Rules-based alert routing
+//!
+//! The chain profile ([`chain::ChainProfile::install`]), clock
+//! ([`clock::ClockConfig::install`]), and number format
+//! ([`output::NumberFormat::install`]) are process-wide and should be
+//! installed before the pipeline is built.
+
+pub mod aggregation;
+pub mod app;
+pub mod candles;
+pub mod chain;
+pub mod cli;
+pub mod clock;
+pub mod config;
+pub mod drain;
+pub mod dry_run;
+pub mod enrichment;
+pub mod harness;
+pub mod labels;
+pub mod listings;
+pub mod lp_burn;
+pub mod metrics;
+pub mod mev;
+pub mod output;
+pub mod pipeline;
+pub mod pool_stats;
+pub mod processors;
+pub mod reload;
+pub mod replay;
+pub mod rpc_bench;
+pub mod rules;
+pub mod server;
+pub mod service;
+pub mod slo;
+pub mod soak;
+pub mod stats;
+pub mod storage;
+pub mod subscriptions;
+pub mod supervisor;
+pub mod telemetry;
+pub mod throttle;
+
+pub use {
+    output::{Notifier, SwapEvent, WebhookNotifier},
+    pipeline::PipelineBuilder,
+    rules::RuleEngine,
+    storage::{BatchWriter, StorageSink},
+};
@@ -0,0 +1,205 @@
+//! New listing filter.
+//!
+//! This module provides:
+//! - [`ListingConfig`] - Maximum age of the pools and tokens alerted on
+//! - [`ListingTracker`] - Remembers when pools and tokens were created and
+//!   keeps only the swaps on recent ones
+//!
+//! Creation times are learned from `CreatePool` events (including Launchpad
+//! and Pump.fun launches), at their block time. A swap passes when its pool,
+//! or a token it trades other than SOL, USDC, and USDT, was created within
+//! `FILTER_MAX_POOL_AGE_MINS`. Pools and tokens created before startup are
+//! unknown, so their swaps are dropped: after a restart only the listings
+//! seen since alert. Other events (pool creations, liquidity changes) always
+//! pass.
+
+use {
+    crate::{
+        clock,
+        output::{
+            swap_event::{USDC_MINT, USDT_MINT, WSOL_MINT},
+            EventType, SwapEvent,
+        },
+    },
+    std::{
+        collections::{HashMap, VecDeque},
+        env,
+        sync::{Mutex, PoisonError},
+    },
+};
+
+/// Pools and tokens whose creation time is remembered.
+const MAX_LISTINGS: usize = 20_000;
+
+/// Configuration of the new listing filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListingConfig {
+    /// Maximum age of a pool or token whose swaps alert, in seconds
+    pub max_age_secs: i64,
+}
+
+impl ListingConfig {
+    /// Creates the new listing filter configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `FILTER_MAX_POOL_AGE_MINS` - Optional: Only alert on swaps on pools or
+    ///   tokens created within this many minutes (e.g., `120` for two hours)
+    ///
+    /// # Returns
+    ///
+    /// `Some(ListingConfig)` if the variable is set to a positive number of
+    /// minutes, `None` otherwise.
+    pub fn from_env() -> Option<Self> {
+        let value = env::var("FILTER_MAX_POOL_AGE_MINS").ok()?;
+        match value.trim().parse::<i64>() {
+            Ok(mins) if mins > 0 => Some(Self {
+                max_age_secs: mins.saturating_mul(60),
+            }),
+            _ => {
+                log::warn!(
+                    "Invalid FILTER_MAX_POOL_AGE_MINS '{value}', expected a positive number of minutes"
+                );
+                None
+            }
+        }
+    }
+}
+
+/// Returns `true` for the quote tokens every listing is paired with.
+fn is_base_mint(mint: &str) -> bool {
+    matches!(mint, WSOL_MINT | USDC_MINT | USDT_MINT)
+}
+
+/// Creation times of recently created pools and tokens.
+#[derive(Debug, Default)]
+struct Listings {
+    /// Creation time of each pool and token (Unix seconds)
+    created: HashMap<String, i64>,
+    /// Pools and tokens in the order they were created, oldest first
+    order: VecDeque<(i64, String)>,
+}
+
+impl Listings {
+    /// Remembers the first creation time of a pool or token.
+    fn record(&mut self, key: &str, time: i64) {
+        if self.created.contains_key(key) {
+            return;
+        }
+        if self.created.len() >= MAX_LISTINGS {
+            if let Some((_, oldest)) = self.order.pop_front() {
+                self.created.remove(&oldest);
+            }
+        }
+        self.created.insert(key.to_string(), time);
+        self.order.push_back((time, key.to_string()));
+    }
+
+    /// Forgets the pools and tokens created before `cutoff`.
+    fn expire(&mut self, cutoff: i64) {
+        while self.order.front().is_some_and(|(time, _)| *time < cutoff) {
+            if let Some((_, key)) = self.order.pop_front() {
+                self.created.remove(&key);
+            }
+        }
+    }
+
+    /// Returns `true` if the pool or token was created at or after `cutoff`.
+    fn is_recent(&self, key: &str, cutoff: i64) -> bool {
+        self.created.get(key).is_some_and(|time| *time >= cutoff)
+    }
+}
+
+/// Keeps only the swaps on recently created pools and tokens.
+pub struct ListingTracker {
+    config: ListingConfig,
+    listings: Mutex<Listings>,
+}
+
+impl ListingTracker {
+    /// Creates a tracker with no known listings.
+    pub fn new(config: ListingConfig) -> Self {
+        Self {
+            config,
+            listings: Mutex::new(Listings::default()),
+        }
+    }
+
+    /// Records a pool creation, and returns `false` for a swap on a pool and
+    /// tokens all older than the maximum age (or created before startup).
+    ///
+    /// Ages are measured at the event's block time, or now if it has none.
+    pub fn observe(&self, event: &SwapEvent) -> bool {
+        let now = event.timestamp.unwrap_or_else(clock::unix_now);
+        let cutoff = now.saturating_sub(self.config.max_age_secs);
+        let mut listings = self.listings.lock().unwrap_or_else(PoisonError::into_inner);
+        listings.expire(cutoff);
+
+        match event.event_type {
+            EventType::CreatePool => {
+                listings.record(&event.pool, now);
+                if let Some(ref created) = event.pool_created {
+                    for mint in [&created.token_mint_a, &created.token_mint_b] {
+                        if !is_base_mint(mint) {
+                            listings.record(mint, now);
+                        }
+                    }
+                }
+                true
+            }
+            EventType::Swap => {
+                listings.is_recent(&event.pool, cutoff)
+                    || [&event.input_token, &event.output_token]
+                        .into_iter()
+                        .flatten()
+                        .filter(|token| !token.is_base_token())
+                        .any(|token| listings.is_recent(&token.mint, cutoff))
+            }
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{PoolCreatedEvent, Protocol, TokenInfo},
+        solana_pubkey::Pubkey,
+        std::str::FromStr,
+    };
+
+    #[test]
+    fn test_keeps_swaps_on_recent_listings() {
+        let tracker = ListingTracker::new(ListingConfig { max_age_secs: 600 });
+        let sol = Pubkey::from_str(WSOL_MINT).unwrap();
+        let token = Pubkey::new_unique();
+        let created = SwapEvent::builder()
+            .event_type(EventType::CreatePool)
+            .protocol(Protocol::Cpmm)
+            .signature("create")
+            .pool("new_pool")
+            .timestamp(1_000)
+            .pool_created(PoolCreatedEvent::new(&sol, &token, &Pubkey::new_unique()))
+            .build();
+        assert!(tracker.observe(&created));
+
+        let swap = |pool: &str, mint: &str, timestamp: i64| {
+            SwapEvent::builder()
+                .protocol(Protocol::Cpmm)
+                .signature("swap")
+                .pool(pool)
+                .timestamp(timestamp)
+                .input_token(TokenInfo::new(WSOL_MINT, 1_000))
+                .output_token(TokenInfo::new(mint, 5_000))
+                .build()
+        };
+        let token = token.to_string();
+        assert!(tracker.observe(&swap("new_pool", "other", 1_300)));
+        // The token's first pool makes its other pools new as well
+        assert!(tracker.observe(&swap("old_pool", &token, 1_300)));
+        // A pool and token not seen created, or created too long ago
+        assert!(!tracker.observe(&swap("old_pool", "other", 1_300)));
+        assert!(!tracker.observe(&swap("new_pool", &token, 1_601)));
+    }
+}
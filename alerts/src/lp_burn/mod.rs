@@ -0,0 +1,376 @@
+//! LP burn detection for new pools.
+//!
+//! This module provides:
+//! - [`LpBurnConfig`] - Check interval and burned share threshold
+//! - [`LpBurnTracker`] - Tracks the LP mints of new CPMM and AMM V4 pools and
+//!   finds burns of their LP supply
+//!
+//! LP tokens that are burned, or sent to the incinerator, can never be
+//! redeemed, so the liquidity they represent stays in the pool for good.
+//! Burns are plain token program instructions the decoders don't see, so the
+//! LP supply of the pools created in the last 24 hours is polled instead:
+//! every `LP_BURN_CHECK_SECS`, the pool states, LP mints, and the
+//! incinerator's LP token accounts are fetched in batches. The burned share is
+//! the LP supply the pool accounts for but the mint no longer has (withdrawals
+//! reduce both), plus the incinerator's balance. A pool with at least
+//! `LP_BURN_MIN_PCT` burned raises one `LpBurn` event, and the events of the
+//! pool emitted after it are badged `LP burned ✅`. Pools created before
+//! startup are not tracked.
+
+use {
+    crate::{
+        clock,
+        enrichment::{decode_pool_lp_supply, parse_mint_supply, parse_token_account_amount},
+        output::{
+            token_transfer::SPL_TOKEN_PROGRAM_ID, EventType, LpBurnEvent, OutputFormat, Protocol,
+            SwapEvent, WebhookNotifier,
+        },
+    },
+    solana_client::nonblocking::rpc_client::RpcClient,
+    solana_pubkey::Pubkey,
+    std::{
+        collections::{HashMap, HashSet, VecDeque},
+        env,
+        str::FromStr,
+        sync::{Arc, Mutex, PoisonError},
+        time::Duration,
+    },
+};
+
+/// Owner of the burn address LP tokens are sent to instead of being burned.
+pub const INCINERATOR: &str = "1nc1nerator11111111111111111111111111111111";
+
+/// Associated Token Account program ID.
+const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+
+/// Time a new pool is checked for LP burns, in seconds.
+const TRACK_SECS: i64 = 24 * 3600;
+
+/// Pools tracked or remembered as burned.
+const MAX_POOLS: usize = 10_000;
+
+/// Pools per `getMultipleAccounts` request (three accounts each, 100 per request).
+const POOLS_PER_REQUEST: usize = 33;
+
+/// Default share of the LP supply burned for a pool to count as burned, in percent.
+const DEFAULT_MIN_BURNED_PCT: f64 = 90.0;
+
+/// Configuration for LP burn detection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LpBurnConfig {
+    /// Time between checks of the tracked pools
+    pub interval: Duration,
+    /// Minimum share of the LP supply burned, in percent
+    pub min_burned_pct: f64,
+}
+
+impl LpBurnConfig {
+    /// Creates LP burn detection configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `LP_BURN_CHECK_SECS` - Seconds between LP supply checks of new pools (e.g., `60`)
+    /// - `LP_BURN_MIN_PCT` - Optional: Share of the LP supply burned for a pool to
+    ///   count as burned, in percent (default: 90)
+    ///
+    /// # Returns
+    ///
+    /// `Some(LpBurnConfig)` if `LP_BURN_CHECK_SECS` is set and positive, `None` otherwise.
+    pub fn from_env() -> Option<Self> {
+        let value = env::var("LP_BURN_CHECK_SECS").ok()?;
+        let interval_secs = match value.trim().parse::<u64>() {
+            Ok(secs) if secs > 0 => secs,
+            _ => {
+                log::warn!("Invalid LP_BURN_CHECK_SECS '{value}', LP burn detection disabled");
+                return None;
+            }
+        };
+        let min_burned_pct = match env::var("LP_BURN_MIN_PCT") {
+            Ok(value) => match value.trim().parse::<f64>() {
+                Ok(pct) if pct > 0.0 && pct <= 100.0 => pct,
+                _ => {
+                    log::warn!(
+                        "Invalid LP_BURN_MIN_PCT '{value}', expected a percentage in (0, 100]"
+                    );
+                    DEFAULT_MIN_BURNED_PCT
+                }
+            },
+            Err(_) => DEFAULT_MIN_BURNED_PCT,
+        };
+        Some(Self {
+            interval: Duration::from_secs(interval_secs),
+            min_burned_pct,
+        })
+    }
+}
+
+/// Returns the incinerator's associated token account for an LP mint.
+fn incinerator_account(lp_mint: &Pubkey) -> Pubkey {
+    let owner = Pubkey::from_str(INCINERATOR).expect("Invalid incinerator address constant");
+    let token_program =
+        Pubkey::from_str(SPL_TOKEN_PROGRAM_ID).expect("Invalid token program ID constant");
+    let program_id = Pubkey::from_str(ASSOCIATED_TOKEN_PROGRAM_ID)
+        .expect("Invalid associated token program ID constant");
+    Pubkey::find_program_address(
+        &[owner.as_ref(), token_program.as_ref(), lp_mint.as_ref()],
+        &program_id,
+    )
+    .0
+}
+
+/// Returns the share of the pool's LP supply that was burned or incinerated,
+/// in percent, or `None` if the pool accounts for no LP supply.
+fn burned_pct(pool_lp_supply: u64, mint_supply: u64, incinerated: u64) -> Option<f64> {
+    if pool_lp_supply == 0 {
+        return None;
+    }
+    let burned = pool_lp_supply
+        .saturating_sub(mint_supply)
+        .saturating_add(incinerated);
+    Some((burned as f64 * 100.0 / pool_lp_supply as f64).min(100.0))
+}
+
+/// A new pool whose LP supply is checked.
+#[derive(Debug, Clone)]
+struct TrackedPool {
+    protocol: Protocol,
+    pool: Pubkey,
+    lp_mint: Pubkey,
+    /// Creation time (Unix seconds)
+    created_at: i64,
+}
+
+/// Pools being checked and pools found burned.
+#[derive(Debug, Default)]
+struct BurnState {
+    tracked: HashMap<String, TrackedPool>,
+    burned: HashSet<String>,
+    /// Burned pools in the order they were found, oldest first
+    burned_order: VecDeque<String>,
+}
+
+/// Tracks the LP supply of new pools and reports burns.
+pub struct LpBurnTracker {
+    config: LpBurnConfig,
+    rpc: Arc<RpcClient>,
+    state: Mutex<BurnState>,
+}
+
+impl LpBurnTracker {
+    /// Creates a tracker fetching pool and mint accounts with the given client.
+    pub fn new(config: LpBurnConfig, rpc: Arc<RpcClient>) -> Self {
+        Self {
+            config,
+            rpc,
+            state: Mutex::new(BurnState::default()),
+        }
+    }
+
+    /// Starts tracking the LP mint of a new CPMM or AMM V4 pool, and sets
+    /// `lp_burned` on the events of tracked and burned pools.
+    pub fn observe(&self, event: &mut SwapEvent) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        if event.event_type == EventType::CreatePool
+            && matches!(event.protocol, Protocol::Cpmm | Protocol::AmmV4)
+            && state.tracked.len() < MAX_POOLS
+        {
+            let lp_mint = event
+                .pool_created
+                .as_ref()
+                .and_then(|created| created.lp_mint.as_deref())
+                .and_then(|mint| Pubkey::from_str(mint).ok());
+            if let (Some(lp_mint), Ok(pool)) = (lp_mint, Pubkey::from_str(&event.pool)) {
+                state.tracked.insert(
+                    event.pool.clone(),
+                    TrackedPool {
+                        protocol: event.protocol,
+                        pool,
+                        lp_mint,
+                        created_at: event.timestamp.unwrap_or_else(clock::unix_now),
+                    },
+                );
+            }
+        }
+
+        if state.burned.contains(&event.pool) {
+            event.lp_burned = Some(true);
+        } else if state.tracked.contains_key(&event.pool) {
+            event.lp_burned = Some(false);
+        }
+    }
+
+    /// Checks the tracked pools' LP supply and returns an `LpBurn` event for
+    /// each pool found burned, which is no longer checked.
+    ///
+    /// Pools created more than 24 hours before `now` are dropped unchecked.
+    pub async fn check(&self, now: i64) -> Vec<SwapEvent> {
+        let pools: Vec<TrackedPool> = {
+            let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+            state
+                .tracked
+                .retain(|_, tracked| tracked.created_at >= now - TRACK_SECS);
+            state.tracked.values().cloned().collect()
+        };
+
+        let mut burns = Vec::new();
+        for chunk in pools.chunks(POOLS_PER_REQUEST) {
+            let addresses: Vec<Pubkey> = chunk
+                .iter()
+                .flat_map(|tracked| {
+                    [
+                        tracked.pool,
+                        tracked.lp_mint,
+                        incinerator_account(&tracked.lp_mint),
+                    ]
+                })
+                .collect();
+            let accounts = match self.rpc.get_multiple_accounts(&addresses).await {
+                Ok(accounts) => accounts,
+                Err(e) => {
+                    log::debug!(
+                        "Failed to fetch the LP supply of {} pools: {e}",
+                        chunk.len()
+                    );
+                    continue;
+                }
+            };
+            for (tracked, accounts) in chunk.iter().zip(accounts.chunks(3)) {
+                let [pool, mint, incinerated] = accounts else {
+                    continue;
+                };
+                let (Some(pool_lp_supply), Some(mint_supply)) = (
+                    pool.as_ref().and_then(decode_pool_lp_supply),
+                    mint.as_ref().and_then(|mint| parse_mint_supply(&mint.data)),
+                ) else {
+                    continue;
+                };
+                let incinerated = incinerated
+                    .as_ref()
+                    .and_then(|account| parse_token_account_amount(&account.data))
+                    .unwrap_or(0);
+                match burned_pct(pool_lp_supply, mint_supply, incinerated) {
+                    Some(pct) if pct >= self.config.min_burned_pct => {
+                        burns.push((tracked.clone(), pct))
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        burns
+            .into_iter()
+            .map(|(tracked, burned_pct)| {
+                let pool = tracked.pool.to_string();
+                state.tracked.remove(&pool);
+                if state.burned.len() >= MAX_POOLS {
+                    if let Some(oldest) = state.burned_order.pop_front() {
+                        state.burned.remove(&oldest);
+                    }
+                }
+                if state.burned.insert(pool.clone()) {
+                    state.burned_order.push_back(pool.clone());
+                }
+                SwapEvent::from_lp_burn(
+                    tracked.protocol,
+                    pool,
+                    now,
+                    LpBurnEvent {
+                        lp_mint: tracked.lp_mint.to_string(),
+                        burned_pct,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Spawns the periodic checks, logging the `LpBurn` events in
+    /// `output_format` and queueing them for the webhook.
+    pub fn spawn_checks(
+        self: Arc<Self>,
+        output_format: OutputFormat,
+        webhook_notifier: Option<Arc<WebhookNotifier>>,
+    ) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(self.config.interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            // The first tick completes immediately
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                for event in self.check(clock::unix_now()).await {
+                    log::info!("{}", event.format(output_format));
+                    if let Some(ref notifier) = webhook_notifier {
+                        if let Err(e) = notifier.try_send(event) {
+                            log::warn!("Failed to queue LP burn alert: {e}");
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::output::PoolCreatedEvent};
+
+    #[test]
+    fn test_burned_pct() {
+        // Withdrawals reduce the pool's and the mint's supply alike
+        assert_eq!(burned_pct(1_000, 1_000, 0), Some(0.0));
+        assert_eq!(burned_pct(1_000, 50, 0), Some(95.0));
+        assert_eq!(burned_pct(1_000, 1_000, 600), Some(60.0));
+        assert_eq!(burned_pct(0, 0, 0), None);
+    }
+
+    #[test]
+    fn test_observe_tracks_new_pools() {
+        let tracker = LpBurnTracker::new(
+            LpBurnConfig {
+                interval: Duration::from_secs(60),
+                min_burned_pct: DEFAULT_MIN_BURNED_PCT,
+            },
+            Arc::new(RpcClient::new("http://127.0.0.1:0".to_string())),
+        );
+        let pool = Pubkey::new_unique().to_string();
+        let lp_mint = Pubkey::new_unique();
+        let mut created = SwapEvent::builder()
+            .event_type(EventType::CreatePool)
+            .protocol(Protocol::Cpmm)
+            .signature("create")
+            .pool(pool.clone())
+            .pool_created(
+                PoolCreatedEvent::new(
+                    &Pubkey::new_unique(),
+                    &Pubkey::new_unique(),
+                    &Pubkey::new_unique(),
+                )
+                .with_lp_mint(&lp_mint),
+            )
+            .build();
+        tracker.observe(&mut created);
+        assert_eq!(created.lp_burned, Some(false));
+
+        let mut swap = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("swap")
+            .pool(pool.clone())
+            .build();
+        {
+            let mut state = tracker.state.lock().unwrap();
+            state.tracked.remove(&pool);
+            state.burned.insert(pool);
+        }
+        tracker.observe(&mut swap);
+        assert_eq!(swap.lp_burned, Some(true));
+
+        let mut other = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("other")
+            .pool("other")
+            .build();
+        tracker.observe(&mut other);
+        assert_eq!(other.lp_burned, None);
+    }
+}
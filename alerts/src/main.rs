@@ -1,17 +1,165 @@
 //! Raydium DEX Alert System
 //!
 //! This application monitors Raydium swap events on the Solana blockchain in real-time.
-//! It supports CPMM, CLMM, and AMM V4 programs with configurable filtering.
+//! It supports CPMM, CLMM, AMM V4, and Launchpad programs, plus the Pump.fun bonding
+//! curve and PumpSwap AMM programs that most new Raydium pools originate from, with
+//! configurable filtering.
 //!
 //! # Configuration
 //!
 //! Environment variables:
-//! - `RPC_WS_URL` - WebSocket RPC endpoint (default: wss://api.mainnet-beta.solana.com/)
-//! - `FILTER_MARKETS` - Comma-separated list of markets to listen: cpmm, clmm, amm_v4 (default: all)
+//! - `CHAIN_PROFILE` - `solana` or a TOML chain profile with the RPC endpoints, program IDs,
+//!   explorer, and native token of another SVM deployment (default: solana)
+//! - `RPC_WS_URL` - WebSocket RPC endpoint (default: the chain profile's,
+//!   wss://api.mainnet-beta.solana.com/ for solana)
+//! - `CPMM_PROGRAM_ID`, `CLMM_PROGRAM_ID`, `AMM_V4_PROGRAM_ID`, `LAUNCHPAD_PROGRAM_ID`,
+//!   `PUMPFUN_PROGRAM_ID`, `PUMPSWAP_PROGRAM_ID` - Optional program ID overrides (e.g., devnet)
+//! - `PROGRAM_DEPLOYMENTS` - Optional `label=market:program` pairs decoding additional programs
+//!   with a market's decoder (e.g., a forked AMM), labeled in their events
+//! - `SUBSCRIBE_STALL_SECS`, `SUBSCRIBE_BACKOFF_MAX_SECS` - Seconds without blocks before the block
+//!   subscription is replaced, and the maximum reconnection backoff (default: 60, 60)
+//! - `GAP_BACKFILL_MAX_SLOTS` - Optional: largest slot gap after a reconnect fetched block by block
+//!   (requires `RPC_HTTP_URL`)
+//! - `CLOCK_SOURCE` - Clock of the timing features: `system`, or `block` to correct the host clock by
+//!   its offset to block times (default: system)
+//! - `CLOCK_DRIFT_WARN_SECS` - Host clock offset to block times that logs a warning, 0 disables
+//!   (default: 30)
+//! - `FILTER_MARKETS` - Comma-separated list of markets to listen: cpmm, clmm, amm_v4,
+//!   launchpad, pumpfun, pumpswap (default: all)
 //! - `FILTER_TOKENS` - Comma-separated list of token mints to filter (optional)
 //! - `FILTER_AMMS` - Comma-separated list of AMM/pool addresses to filter (optional)
+//! - `FILTER_SIDE` - Only alert on `buy` or `sell` swaps (default: both)
+//! - `FILTER_MAX_POOL_AGE_MINS` - Only alert on swaps on pools or tokens created within this many
+//!   minutes (optional)
+//! - `SUPPRESS_FREEZABLE_TOKENS` - `true` to drop pool creations of tokens whose freeze authority
+//!   is active (optional, needs the `authorities` enricher)
+//! - `WATCH_WALLETS` - Comma-separated list of wallets whose swaps always alert (optional)
+//! - `LABELS_FILE` - Optional TOML file mapping addresses to labels shown in alerts
+//! - `NUMBER_LOCALE`, `NUMBER_THOUSANDS_SEPARATOR` - Optional decimal and thousands separators of
+//!   text alerts (`en`, `de`, `fr`, `ch`; default: `.` and no grouping)
+//! - `NUMBER_AMOUNT_DECIMALS`, `NUMBER_TOKEN_DECIMALS` - Decimals of token amounts (default: 4), and
+//!   optional `token=decimals` overrides by mint or symbol
+//! - `NUMBER_SCIENTIFIC_BELOW` - Optional: amounts and prices below this in scientific notation
+//! - `TEMPLATES_FILE` - Optional TOML file of text alert layouts (`console`, `subscriptions`,
+//!   `push`, `default`, or named by Discord/Telegram/email/X rule destinations)
+//! - `LABELS_URL`, `LABELS_REFRESH_SECS` - Optional remote labels list in the same format,
+//!   fetched on `SIGHUP` and at this interval (default: 3600)
 //! - `OUTPUT_FORMAT` - Output format: text, json, json_pretty (default: text)
+//! - `LOG_SERIALIZE_WORKERS`, `WEBHOOK_SERIALIZE_WORKERS` - Threads formatting logged events and
+//!   rendering each webhook's request bodies off the async runtime, 0 formats on the processor or
+//!   delivery task (default: 1, 1)
+//! - `EVENT_FILE_DIR` - Optional directory of NDJSON event files, taking events off the log stream
+//!   in JSON output; `EVENT_FILE_ROTATION` (`hourly` or `daily`), `EVENT_FILE_COMPRESS` (zstd)
 //! - `WEBHOOK_URL` - Optional webhook URL for notifications
+//! - `WEBHOOKS_FILE` - Optional TOML file of additional webhooks with headers, filter
+//!   expressions, and payload templates
+//! - `WEBHOOK_BATCH_SIZE`, `WEBHOOK_BATCH_MS` - Events per webhook request as a JSON array
+//!   (default: 1) and max wait for a batch to fill (default: 1000)
+//! - `WEBHOOK_SPOOL_DIR` - Optional directory spooling undeliverable webhook events for replay
+//! - `WEBHOOK_SCHEMA_VERSION` - JSON layout of webhook events, `1` (frozen original swap fields)
+//!   or `2` (every field; default)
+//! - `RULES_FILE` - Optional TOML file of alert rules with per-rule destinations and bridges
+//! - `SMTP_HOST`, `SMTP_PORT`, `SMTP_TLS`, `SMTP_USERNAME`, `SMTP_PASSWORD`, `SMTP_FROM` - SMTP
+//!   server of email rule destinations
+//! - `EMAIL_BATCH_SECS`, `EMAIL_MAX_EVENTS` - Digest window after an alert email (default: 300)
+//!   and alerts listed per digest (default: 50)
+//! - `X_API_KEY`, `X_API_SECRET`, `X_ACCESS_TOKEN`, `X_ACCESS_SECRET` - OAuth 1.0a credentials of
+//!   X rule destinations
+//! - `X_API_URL`, `X_MAX_POSTS_PER_DAY` - X API server and posts in any 24 hours (default:
+//!   https://api.x.com, 17)
+//! - `CONFIG_FILE` - Env-format config file reloaded on SIGHUP or change (default: .env)
+//! - `CONFIG_WATCH_INTERVAL_MS` - Config file change check interval, 0 disables (default: 2000)
+//! - `SERVER_ADDR` - Optional listen address for the WebSocket/SSE streaming server, recent
+//!   swaps API, and Grafana datasource (`HTTP_PORT` listens on all interfaces instead)
+//! - `SERVER_RECENT_SWAPS` - Swaps kept in memory for the recent swaps API (default: 10000)
+//! - `GRPC_ADDR` - Optional listen address of the `SubscribeSwaps` gRPC API, next to `SERVER_ADDR`
+//!   (requires the `grpc` feature)
+//! - `SERVER_CACHE_TTL_MS`, `SERVER_CACHE_CANDLES_TTL_MS`, `SERVER_CACHE_MAX_ENTRIES` - Query API
+//!   response cache TTLs of swaps and of candles/Grafana, and its size (default: 1000, 10000, 1024)
+//! - `TELEGRAM_BOT_TOKEN` - Optional Telegram bot token enabling self-service deep-link subscriptions
+//! - `TELEGRAM_API_URL`, `SUBSCRIPTIONS_FILE` - Bot API endpoint and subscription registry file
+//!   (default: https://api.telegram.org, subscriptions.json)
+//! - `SUBSCRIPTION_MAX_PER_CHAT`, `SUBSCRIPTION_MAX_ALERTS_PER_HOUR` - Per-chat subscription and
+//!   hourly alert limits, 0 is unlimited; alerts over the limit are digested (default: 20, 60)
+//! - `NTFY_TOPIC`, `NTFY_URL`, `NTFY_TOKEN` - Optional ntfy topic, server, and token of push
+//!   notifications (default server: https://ntfy.sh)
+//! - `PUSHOVER_TOKEN`, `PUSHOVER_USER` - Optional Pushover application token and user key
+//! - `PUSH_MIN_USD` - Optional smallest swap USD value pushed to phones (default: all)
+//! - `DEDUP_WINDOW_MS` - Window for collapsing duplicate swap alerts, 0 disables (default: 300)
+//! - `SLOT_BATCH_MS` - Optional delay after a slot's first event before its events are emitted
+//!   together, sorted and behind a slot header (default: disabled)
+//! - `SEQUENCE_FILE` - Optional state file numbering each webhook's events with a persistent `seq`,
+//!   backfilled via `/streams/{stream}/events?from_seq=` (`SEQUENCE_BACKFILL` events retained,
+//!   default: 10000)
+//! - `RPC_HTTP_URL` - Optional HTTP RPC endpoint enabling token/pool enrichment,
+//!   AMM V4 token account to mint resolution, and Launchpad/PumpSwap trade event mints
+//! - `ENRICHERS` - Enabled enrichers in order (default: metadata,price,impact; `holders` and
+//!   `authorities` are opt-in)
+//! - `ENRICHER_TIMEOUT_MS`, `ENRICHER_<NAME>_TIMEOUT_MS` - Per-enricher timeouts (default: 50ms)
+//! - `ENRICHMENT_BUDGET_MS` - Optional total enrichment time per event; `ENRICHMENT_FOLLOW_UP=true`
+//!   updates stored and streamed events once the enrichment cut by the budget or timed out completes
+//! - `RESERVE_REFRESH_SECS` - Seconds between vault balance refreshes of cached pools for price
+//!   impact estimates (default: 30, 0 disables)
+//! - `SOL_PRICE_USD` - Optional initial SOL price for USD values
+//! - `PRICE_MAX_AGE_SECS` - Maximum age of the mint/SOL and SOL/USD prices a derived token price
+//!   is computed from (default: 300)
+//! - `POSTGRES_URL` - Optional PostgreSQL URL for persisting events; pending schema migrations of an
+//!   existing database are only applied with `--migrate`
+//! - `CANDLE_INTERVALS` - OHLCV candle intervals persisted with storage (default: 1m,5m,15m,1h,4h,1d)
+//! - `CANDLE_MEMORY_LIMIT` - Optional closed candles kept in memory per pool and interval when no
+//!   database is configured, for `/candles` without storage
+//! - `ARCHIVE_DIR` - Optional directory for the zstd-compressed event archive
+//! - `EXPORT_DIR`, `EXPORT_FORMAT`, `EXPORT_ROTATION` - Optional directory of `csv` or `parquet`
+//!   export files rotated `hourly` or `daily` (default: csv, hourly)
+//! - `NATS_URL`, `NATS_SUBJECT_PREFIX`, `NATS_JETSTREAM_STREAM` - Optional NATS server events are
+//!   published to, subject prefix, and JetStream stream persisting them (default: raydium, core NATS)
+//! - `AMQP_URL`, `AMQP_EXCHANGE` - Optional AMQP broker events are published to with the routing
+//!   key `<protocol>.<event_type>`, and its topic exchange (default: raydium)
+//! - `MQTT_HOST`, `MQTT_PORT`, `MQTT_TOPIC_PREFIX`, `MQTT_QOS` - Optional MQTT broker events are
+//!   published to per pool and token, compact unless `MQTT_PAYLOAD=full` (default: 1883, raydium, 1)
+//! - `INFLUX_URL`, `INFLUX_BUCKET`, `INFLUX_ORG`, `INFLUX_TOKEN` - Optional InfluxDB server swaps are
+//!   written to as `raydium_swap` points (`INFLUX_MEASUREMENT`)
+//! - `STORAGE_BATCH_SIZE`, `STORAGE_FLUSH_INTERVAL_MS` - Storage batching (default: 100, 1000ms)
+//! - `STORAGE_MAX_AGE_HOURS`, `STORAGE_MAX_SIZE_MB`, `ARCHIVE_MAX_AGE_HOURS`, `ARCHIVE_MAX_SIZE_MB`,
+//!   `CANDLE_MAX_AGE_HOURS` - Optional retention limits (default: keep forever)
+//! - `ROLLUP_AFTER_DAYS` - Optional: days of full-resolution swaps and sub-hour candles kept in
+//!   the database before swaps are rolled up into hourly and daily candles
+//! - `CPMM_WORKERS`, `CLMM_WORKERS`, `AMM_V4_WORKERS`, `LAUNCHPAD_WORKERS`, `PUMPFUN_WORKERS`,
+//!   `PUMPSWAP_WORKERS` - Worker count per decoder (default: 1)
+//! - `CPMM_CHANNEL_CAPACITY`, `CLMM_CHANNEL_CAPACITY`, `AMM_V4_CHANNEL_CAPACITY`,
+//!   `LAUNCHPAD_CHANNEL_CAPACITY`, `PUMPFUN_CHANNEL_CAPACITY`, `PUMPSWAP_CHANNEL_CAPACITY` -
+//!   Per-worker queue capacity (default: 1000)
+//! - `STATSD_ADDR` - Optional StatsD/DogStatsD address the pipeline metrics are pushed to
+//! - `STATSD_PREFIX`, `STATSD_FLAVOR`, `STATSD_TAGS` - Metric prefix, `statsd` or `datadog`
+//!   protocol, and DogStatsD tags (default: raydium_alerts, statsd, none)
+//! - `SLO_FILE` - Optional TOML file of SLOs (e.g., minimum `events_emitted` rate during market
+//!   hours, maximum `webhook_delivery_ms` p95) evaluated continuously; breaches are logged
+//! - `SLO_WEBHOOK_URL` - Optional URL SLO breach and recovery alerts are posted to as JSON
+//! - `METRICS_TOP_POOLS` - Most active pools listed in the periodic activity summary (default: 5)
+//! - `AGGREGATION_INTERVAL_SECS` - Optional interval of rolling 1m/5m/1h per-pool volume summaries
+//! - `AGGREGATION_TOP_POOLS`, `AGGREGATION_MIN_USD` - Pools per summary, busiest first, and their
+//!   minimum 5m USD volume (default: 10, 0)
+//! - `POOL_STATS` - Optional: `true` attaches the pool's buys, sells, and volume since first seen
+//!   to every event (`POOL_STATS_MAX_POOLS` pools, default: 50000)
+//! - `MEV_MIN_VICTIM_USD` - Optional: enables sandwich detection, alerting when the victim swaps
+//!   total at least this USD value
+//! - `MEV_SIZE_TOLERANCE_PCT` - Maximum token amount difference between a sandwich's front-run and
+//!   back-run (default: 10)
+//! - `DRAIN_LIQUIDITY_PCT` - Optional: alerts when one withdrawal removes at least this share of
+//!   a pool's tracked reserve (requires `RPC_HTTP_URL`)
+//! - `DRAIN_CREATOR_SUPPLY_PCT` - Optional: alerts when a pool's creator sells at least this share
+//!   of the token's supply in one swap (requires `RPC_HTTP_URL`)
+//! - `LP_BURN_CHECK_SECS` - Optional: checks new pools' LP supply at this interval, alerting once
+//!   `LP_BURN_MIN_PCT` of it is burned (default: 90, requires `RPC_HTTP_URL`)
+//! - `THROTTLE_TOKEN_PER_MIN`, `THROTTLE_POOL_PER_MIN` - Optional: most swap alerts per token and
+//!   per pool each minute; swaps over a limit are still stored and summarized in a digest
+//! - `SHUTDOWN_TIMEOUT_SECS` - Time allowed on SIGINT/SIGTERM for queued webhook events to be
+//!   delivered before they are dropped (default: 10)
+//! - `LOG_FORMAT` - `text` or `json` log lines, carrying slot and signature spans (default: text)
+//! - `OTEL_EXPORTER_OTLP_ENDPOINT`, `OTEL_SERVICE_NAME` - Optional OTLP/gRPC collector spans are
+//!   exported to, and their service name (default: raydium-alerts; requires the `otlp` feature)
+//! - `WATCHDOG_STALL_SECS` - Seconds without pipeline progress after which systemd watchdog pings
+//!   stop, so the service is restarted (default: 120; requires `WatchdogSec=`)
 //!
 //! # Example
 //!
@@ -21,224 +169,21 @@
 //! export FILTER_TOKENS="So11111111111111111111111111111111111111112"
 //! export OUTPUT_FORMAT="json"
 //! cargo run
+//!
+//! # Query the event archive instead of running the pipeline
+//! cargo run -- archive ./archive --from-slot 301234567 --pool <POOL>
+//!
+//! # Apply pending storage schema migrations, then run the pipeline
+//! cargo run --release -- --migrate
+//!
+//! # Print the events a transaction would have alerted with, without delivering them
+//! cargo run -- --replay <SIGNATURE>
+//!
+//! # Soak test the pipeline on recorded blocks for 8 hours
+//! cargo run --release -- soak ./recording --duration 28800 --report soak.json
 //! ```
 
-mod config;
-mod output;
-mod processors;
-
-use {
-    carbon_core::{error::CarbonResult, pipeline::Pipeline},
-    carbon_log_metrics::LogMetrics,
-    carbon_raydium_amm_v4_decoder::{RaydiumAmmV4Decoder, PROGRAM_ID as RAYDIUM_AMM_V4_PROGRAM_ID},
-    carbon_raydium_clmm_decoder::{RaydiumClmmDecoder, PROGRAM_ID as RAYDIUM_CLMM_PROGRAM_ID},
-    carbon_raydium_cpmm_decoder::{RaydiumCpmmDecoder, PROGRAM_ID as RAYDIUM_CPMM_PROGRAM_ID},
-    carbon_rpc_block_subscribe_datasource::{Filters, RpcBlockSubscribe},
-    config::{parse_market_filter, parse_pubkey_filter, MarketType},
-    output::{parse_output_format, OutputFormat, WebhookConfig, WebhookNotifier},
-    processors::{
-        RaydiumAmmV4InstructionProcessor, RaydiumClmmInstructionProcessor,
-        RaydiumCpmmInstructionProcessor,
-    },
-    solana_client::rpc_config::{RpcBlockSubscribeConfig, RpcBlockSubscribeFilter},
-    solana_pubkey::Pubkey,
-    std::{collections::HashSet, env, sync::Arc},
-    tokio::signal,
-};
-
 #[tokio::main]
-pub async fn main() -> CarbonResult<()> {
-    dotenv::dotenv().ok();
-    env_logger::init();
-
-    // Create filter for block subscription
-    // Note: RpcBlockSubscribeFilter only supports single program, so we use "All" and filter in processor
-    let filters = Filters::new(
-        RpcBlockSubscribeFilter::All,
-        Some(RpcBlockSubscribeConfig {
-            max_supported_transaction_version: Some(0),
-            ..RpcBlockSubscribeConfig::default()
-        }),
-    );
-
-    let rpc_ws_url =
-        env::var("RPC_WS_URL").unwrap_or_else(|_| "wss://api.mainnet-beta.solana.com/".to_string());
-
-    // Parse filters from environment variables
-    let filter_markets = parse_market_filter("FILTER_MARKETS");
-    let filter_tokens = parse_pubkey_filter("FILTER_TOKENS");
-    let filter_amms = parse_pubkey_filter("FILTER_AMMS");
-    let output_format = parse_output_format("OUTPUT_FORMAT");
-
-    // Initialize optional webhook notifier
-    let webhook_notifier = WebhookConfig::from_env().map(|config| {
-        log::info!("Webhook notifications enabled: {}", config.url);
-        Arc::new(WebhookNotifier::new(config))
-    });
-
-    log_startup_info(
-        &rpc_ws_url,
-        &filter_markets,
-        &filter_tokens,
-        &filter_amms,
-        output_format,
-        webhook_notifier.is_some(),
-    );
-
-    let block_subscribe = RpcBlockSubscribe::new(rpc_ws_url, filters);
-
-    // Build pipeline with selected market processors
-    let mut pipeline = build_pipeline(
-        block_subscribe,
-        &filter_markets,
-        &filter_tokens,
-        &filter_amms,
-        output_format,
-        webhook_notifier,
-    )?;
-
-    // Run pipeline with graceful shutdown on Ctrl+C
-    tokio::select! {
-        result = pipeline.run() => {
-            result?;
-        }
-        _ = signal::ctrl_c() => {
-            log::info!("Received Ctrl+C, shutting down...");
-        }
-    }
-
-    Ok(())
-}
-
-/// Builds the pipeline with only the selected market processors.
-///
-/// This dynamically adds decoders based on `FILTER_MARKETS` configuration.
-fn build_pipeline(
-    datasource: RpcBlockSubscribe,
-    filter_markets: &HashSet<MarketType>,
-    filter_tokens: &HashSet<Pubkey>,
-    filter_amms: &HashSet<Pubkey>,
-    output_format: OutputFormat,
-    webhook_notifier: Option<Arc<WebhookNotifier>>,
-) -> CarbonResult<Pipeline> {
-    let mut builder = Pipeline::builder()
-        .datasource(datasource)
-        .metrics(Arc::new(LogMetrics::new()))
-        .metrics_flush_interval(3);
-
-    // Add CPMM decoder if enabled
-    if filter_markets.contains(&MarketType::Cpmm) {
-        let processor = RaydiumCpmmInstructionProcessor::new(
-            filter_tokens.clone(),
-            filter_amms.clone(),
-            output_format,
-            webhook_notifier.clone(),
-        );
-        builder = builder.instruction(RaydiumCpmmDecoder, processor);
-        log::info!("CPMM processor: enabled");
-    } else {
-        log::info!("CPMM processor: disabled");
-    }
-
-    // Add CLMM decoder if enabled
-    if filter_markets.contains(&MarketType::Clmm) {
-        let processor = RaydiumClmmInstructionProcessor::new(
-            filter_tokens.clone(),
-            filter_amms.clone(),
-            output_format,
-            webhook_notifier.clone(),
-        );
-        builder = builder.instruction(RaydiumClmmDecoder, processor);
-        log::info!("CLMM processor: enabled");
-    } else {
-        log::info!("CLMM processor: disabled");
-    }
-
-    // Add AMM V4 decoder if enabled
-    if filter_markets.contains(&MarketType::AmmV4) {
-        let processor = RaydiumAmmV4InstructionProcessor::new(
-            filter_amms.clone(),
-            output_format,
-            webhook_notifier,
-        );
-        builder = builder.instruction(RaydiumAmmV4Decoder, processor);
-        log::info!("AMM V4 processor: enabled");
-    } else {
-        log::info!("AMM V4 processor: disabled");
-    }
-
-    builder
-        .shutdown_strategy(carbon_core::pipeline::ShutdownStrategy::Immediate)
-        .build()
-}
-
-/// Logs startup configuration information.
-///
-/// Displays program IDs and filter status for debugging and verification.
-fn log_startup_info(
-    rpc_ws_url: &str,
-    filter_markets: &HashSet<MarketType>,
-    filter_tokens: &HashSet<Pubkey>,
-    filter_amms: &HashSet<Pubkey>,
-    output_format: OutputFormat,
-    webhook_enabled: bool,
-) {
-    log::info!("=== Raydium Alert System ===");
-    log::info!("Raydium CPMM Program ID: {}", RAYDIUM_CPMM_PROGRAM_ID);
-    log::info!("Raydium CLMM Program ID: {}", RAYDIUM_CLMM_PROGRAM_ID);
-    log::info!("Raydium AMM V4 Program ID: {}", RAYDIUM_AMM_V4_PROGRAM_ID);
-
-    // Log market filter status
-    let market_names: Vec<&str> = filter_markets
-        .iter()
-        .map(|m| match m {
-            MarketType::Cpmm => "cpmm",
-            MarketType::Clmm => "clmm",
-            MarketType::AmmV4 => "amm_v4",
-        })
-        .collect();
-    log::info!("Markets filter: {:?}", market_names);
-
-    // Log token filter status
-    if filter_tokens.is_empty() {
-        log::info!("Token filter: disabled (tracking all tokens)");
-    } else {
-        log::info!(
-            "Token filter: {} token(s) - {:?}",
-            filter_tokens.len(),
-            filter_tokens
-        );
-    }
-
-    // Log AMM/pool filter status
-    if filter_amms.is_empty() {
-        log::info!("AMM/Pool filter: disabled (tracking all AMMs/pools)");
-    } else {
-        log::info!(
-            "AMM/Pool filter: {} address(es) - {:?}",
-            filter_amms.len(),
-            filter_amms
-        );
-    }
-
-    // Log output settings
-    log::info!(
-        "Output format: {:?}",
-        match output_format {
-            OutputFormat::Text => "text",
-            OutputFormat::Json => "json",
-            OutputFormat::JsonPretty => "json_pretty",
-        }
-    );
-    log::info!(
-        "Webhook notifications: {}",
-        if webhook_enabled {
-            "enabled"
-        } else {
-            "disabled"
-        }
-    );
-
-    log::info!("RPC WebSocket: {rpc_ws_url}");
-    log::info!("============================");
+async fn main() -> carbon_core::error::CarbonResult<()> {
+    raydium_alerts::app::run().await
 }
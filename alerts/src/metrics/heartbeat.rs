@@ -0,0 +1,100 @@
+//! Pipeline liveness for the service watchdog.
+//!
+//! The pipeline updates its metrics for every block it receives, so a
+//! [`Heartbeat`] registered as a metrics backend beats as long as blocks keep
+//! arriving. A hung subscription or a stuck pipeline stops the beats, and the
+//! watchdog withholds its pings so the supervisor restarts the process.
+
+use {
+    async_trait::async_trait,
+    carbon_core::{error::CarbonResult, metrics::Metrics},
+    std::{
+        sync::atomic::{AtomicU64, Ordering},
+        time::{Duration, Instant},
+    },
+};
+
+/// Time of the pipeline's last metric update.
+#[derive(Debug)]
+pub struct Heartbeat {
+    start: Instant,
+    /// Milliseconds from `start` to the last beat
+    last: AtomicU64,
+}
+
+impl Default for Heartbeat {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Heartbeat {
+    /// Creates a heartbeat whose first beat is now.
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            last: AtomicU64::new(0),
+        }
+    }
+
+    /// Records a beat.
+    pub fn beat(&self) {
+        self.last
+            .store(self.start.elapsed().as_millis() as u64, Ordering::Relaxed);
+    }
+
+    /// Returns the time since the last beat.
+    pub fn age(&self) -> Duration {
+        let last = Duration::from_millis(self.last.load(Ordering::Relaxed));
+        self.start.elapsed().saturating_sub(last)
+    }
+}
+
+#[async_trait]
+impl Metrics for Heartbeat {
+    async fn initialize(&self) -> CarbonResult<()> {
+        self.beat();
+        Ok(())
+    }
+
+    async fn flush(&self) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn update_gauge(&self, _name: &str, _value: f64) -> CarbonResult<()> {
+        self.beat();
+        Ok(())
+    }
+
+    async fn increment_counter(&self, _name: &str, _value: u64) -> CarbonResult<()> {
+        self.beat();
+        Ok(())
+    }
+
+    async fn record_histogram(&self, _name: &str, _value: f64) -> CarbonResult<()> {
+        self.beat();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_metric_updates_beat() {
+        let heartbeat = Heartbeat::new();
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(heartbeat.age() >= Duration::from_millis(20));
+
+        heartbeat
+            .increment_counter("updates_received", 1)
+            .await
+            .unwrap();
+        assert!(heartbeat.age() < Duration::from_millis(20));
+    }
+}
@@ -0,0 +1,15 @@
+//! Pipeline metrics backends.
+//!
+//! This module provides:
+//! - [`StatsdMetrics`] - Pushes the pipeline metrics to a StatsD server or the Datadog agent
+//! - [`SummaryMetrics`] - Logs top pools, instruction kinds, and filter hit-rates on each flush
+//! - [`Activity`] - Per-window processing activity recorded by the processors
+//! - [`Heartbeat`] - Time of the last metric update, watched by the service watchdog
+
+mod heartbeat;
+mod statsd;
+mod summary;
+
+pub use heartbeat::Heartbeat;
+pub use statsd::{StatsdConfig, StatsdMetrics};
+pub use summary::{Activity, SummaryConfig, SummaryMetrics};
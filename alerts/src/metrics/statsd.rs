@@ -0,0 +1,344 @@
+//! Push-based StatsD/DogStatsD metrics.
+//!
+//! [`StatsdMetrics`] implements Carbon's [`Metrics`] trait, so it receives the
+//! same counters, gauges, and histograms as the log metrics (pipeline
+//! throughput, per-decoder queue depth and processing time, enricher errors)
+//! and pushes them over UDP to a StatsD server or the Datadog agent.
+//!
+//! Updates are aggregated between flushes: counters are summed and gauges keep
+//! their latest value. Histogram samples are sent individually (as timers for
+//! plain StatsD). Lines are packed into datagrams of at most
+//! [`MAX_DATAGRAM_BYTES`].
+
+use {
+    async_trait::async_trait,
+    carbon_core::{
+        error::{CarbonResult, Error},
+        metrics::Metrics,
+    },
+    std::{
+        collections::HashMap,
+        env, fmt,
+        net::UdpSocket,
+        sync::{Mutex, PoisonError},
+    },
+};
+
+/// Largest datagram sent, safe for the usual 1500-byte MTU.
+pub const MAX_DATAGRAM_BYTES: usize = 1432;
+
+/// Default prefix of metric names.
+const DEFAULT_PREFIX: &str = "raydium_alerts";
+
+/// Line protocol spoken by the server.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StatsdFlavor {
+    /// Plain StatsD: histograms are sent as timers, tags are not supported
+    #[default]
+    Statsd,
+    /// DogStatsD: native histograms and `|#tag` suffixes
+    Datadog,
+}
+
+impl StatsdFlavor {
+    /// Parses a flavor name (`statsd` or `datadog`/`dogstatsd`, case-insensitive).
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "statsd" => Some(Self::Statsd),
+            "datadog" | "dogstatsd" => Some(Self::Datadog),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for StatsdFlavor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Statsd => write!(f, "statsd"),
+            Self::Datadog => write!(f, "datadog"),
+        }
+    }
+}
+
+/// Configuration for the StatsD emitter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatsdConfig {
+    /// Server address (`host:port`)
+    pub addr: String,
+    /// Prefix joined to every metric name with a `.`; empty for none
+    pub prefix: String,
+    /// Line protocol
+    pub flavor: StatsdFlavor,
+    /// DogStatsD tags (`key:value`) added to every metric
+    pub tags: Vec<String>,
+}
+
+impl StatsdConfig {
+    /// Creates StatsD configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `STATSD_ADDR` - Server address (e.g., `127.0.0.1:8125`)
+    /// - `STATSD_PREFIX` - Optional: Metric name prefix (default: `raydium_alerts`)
+    /// - `STATSD_FLAVOR` - Optional: `statsd` or `datadog` (default: `statsd`)
+    /// - `STATSD_TAGS` - Optional: Comma-separated DogStatsD tags (e.g., `env:prod`);
+    ///   ignored by the `statsd` flavor
+    ///
+    /// # Returns
+    ///
+    /// `Some(StatsdConfig)` if `STATSD_ADDR` is set, `None` otherwise.
+    pub fn from_env() -> Option<Self> {
+        let addr = env::var("STATSD_ADDR")
+            .ok()
+            .filter(|addr| !addr.trim().is_empty())?;
+
+        let prefix = env::var("STATSD_PREFIX")
+            .map(|prefix| prefix.trim().trim_end_matches('.').to_string())
+            .unwrap_or_else(|_| DEFAULT_PREFIX.to_string());
+
+        let flavor = match env::var("STATSD_FLAVOR") {
+            Ok(name) if !name.trim().is_empty() => {
+                StatsdFlavor::parse(&name).unwrap_or_else(|| {
+                    log::warn!("Unknown STATSD_FLAVOR '{}', using statsd", name.trim());
+                    StatsdFlavor::Statsd
+                })
+            }
+            _ => StatsdFlavor::Statsd,
+        };
+
+        let tags: Vec<String> = env::var("STATSD_TAGS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_string)
+            .collect();
+        if !tags.is_empty() && flavor == StatsdFlavor::Statsd {
+            log::warn!("STATSD_TAGS requires STATSD_FLAVOR=datadog, tags ignored");
+        }
+
+        Some(Self {
+            addr: addr.trim().to_string(),
+            prefix,
+            flavor,
+            tags,
+        })
+    }
+}
+
+/// Updates received since the last flush.
+#[derive(Debug, Default)]
+struct Pending {
+    counters: HashMap<String, u64>,
+    gauges: HashMap<String, f64>,
+    histograms: Vec<(String, f64)>,
+}
+
+/// Carbon metrics backend pushing to a StatsD server.
+pub struct StatsdMetrics {
+    config: StatsdConfig,
+    socket: UdpSocket,
+    pending: Mutex<Pending>,
+}
+
+impl StatsdMetrics {
+    /// Creates an emitter sending to `config.addr`.
+    ///
+    /// The address is resolved once, so a bad host fails at startup.
+    pub fn new(config: StatsdConfig) -> std::io::Result<Self> {
+        let socket = UdpSocket::bind("0.0.0.0:0")?;
+        socket.connect(&config.addr)?;
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            config,
+            socket,
+            pending: Mutex::new(Pending::default()),
+        })
+    }
+
+    fn pending(&self) -> std::sync::MutexGuard<'_, Pending> {
+        self.pending.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Formats one metric line, e.g. `raydium_alerts.cpmm_queue_depth:3|g`.
+    fn line(&self, name: &str, value: impl fmt::Display, kind: &str) -> String {
+        let mut line = if self.config.prefix.is_empty() {
+            format!("{name}:{value}|{kind}")
+        } else {
+            format!("{}.{name}:{value}|{kind}", self.config.prefix)
+        };
+        if self.config.flavor == StatsdFlavor::Datadog && !self.config.tags.is_empty() {
+            line.push_str("|#");
+            line.push_str(&self.config.tags.join(","));
+        }
+        line
+    }
+
+    /// Drains the pending updates into metric lines.
+    fn drain(&self) -> Vec<String> {
+        let pending = std::mem::take(&mut *self.pending());
+        let histogram = match self.config.flavor {
+            StatsdFlavor::Statsd => "ms",
+            StatsdFlavor::Datadog => "h",
+        };
+
+        let counters = pending
+            .counters
+            .iter()
+            .map(|(name, value)| self.line(name, value, "c"));
+        let gauges = pending
+            .gauges
+            .iter()
+            .map(|(name, value)| self.line(name, value, "g"));
+        let histograms = pending
+            .histograms
+            .iter()
+            .map(|(name, value)| self.line(name, value, histogram));
+        counters.chain(gauges).chain(histograms).collect()
+    }
+}
+
+/// Packs lines into newline-separated datagrams of at most `max_bytes`.
+///
+/// A line longer than `max_bytes` is sent alone.
+fn pack(lines: &[String], max_bytes: usize) -> Vec<String> {
+    let mut datagrams = Vec::new();
+    let mut current = String::new();
+    for line in lines {
+        if !current.is_empty() && current.len() + 1 + line.len() > max_bytes {
+            datagrams.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(line);
+    }
+    if !current.is_empty() {
+        datagrams.push(current);
+    }
+    datagrams
+}
+
+#[async_trait]
+impl Metrics for StatsdMetrics {
+    async fn initialize(&self) -> CarbonResult<()> {
+        log::info!(
+            "StatsD metrics enabled: {} ({})",
+            self.config.addr,
+            self.config.flavor
+        );
+        Ok(())
+    }
+
+    async fn flush(&self) -> CarbonResult<()> {
+        let lines = self.drain();
+        for datagram in pack(&lines, MAX_DATAGRAM_BYTES) {
+            // UDP is fire-and-forget; a missing agent must not stall the pipeline
+            if let Err(e) = self.socket.send(datagram.as_bytes()) {
+                log::debug!("Failed to send StatsD metrics: {e}");
+            }
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> CarbonResult<()> {
+        self.flush().await
+    }
+
+    async fn update_gauge(&self, name: &str, value: f64) -> CarbonResult<()> {
+        if !value.is_finite() {
+            return Err(Error::Custom(format!("gauge {name} is not finite")));
+        }
+        self.pending().gauges.insert(name.to_string(), value);
+        Ok(())
+    }
+
+    async fn increment_counter(&self, name: &str, value: u64) -> CarbonResult<()> {
+        *self.pending().counters.entry(name.to_string()).or_default() += value;
+        Ok(())
+    }
+
+    async fn record_histogram(&self, name: &str, value: f64) -> CarbonResult<()> {
+        if !value.is_finite() {
+            return Err(Error::Custom(format!("histogram {name} is not finite")));
+        }
+        self.pending().histograms.push((name.to_string(), value));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn metrics(flavor: StatsdFlavor, tags: &[&str]) -> StatsdMetrics {
+        StatsdMetrics::new(StatsdConfig {
+            addr: "127.0.0.1:8125".to_string(),
+            prefix: DEFAULT_PREFIX.to_string(),
+            flavor,
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_aggregates_between_flushes() {
+        let statsd = metrics(StatsdFlavor::Statsd, &["env:prod"]);
+        statsd
+            .increment_counter("cpmm_instructions_processed", 2)
+            .await
+            .unwrap();
+        statsd
+            .increment_counter("cpmm_instructions_processed", 3)
+            .await
+            .unwrap();
+        statsd.update_gauge("cpmm_queue_depth", 7.0).await.unwrap();
+        statsd.update_gauge("cpmm_queue_depth", 4.0).await.unwrap();
+        statsd
+            .record_histogram("cpmm_process_time_us", 12.5)
+            .await
+            .unwrap();
+        assert!(statsd.update_gauge("bad", f64::NAN).await.is_err());
+
+        assert_eq!(
+            statsd.drain(),
+            [
+                "raydium_alerts.cpmm_instructions_processed:5|c",
+                "raydium_alerts.cpmm_queue_depth:4|g",
+                "raydium_alerts.cpmm_process_time_us:12.5|ms",
+            ]
+        );
+        assert!(statsd.drain().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_datadog_histograms_and_tags() {
+        let statsd = metrics(StatsdFlavor::Datadog, &["env:prod", "service:alerts"]);
+        statsd
+            .record_histogram("enricher_price_time_us", 3.0)
+            .await
+            .unwrap();
+        assert_eq!(
+            statsd.drain(),
+            ["raydium_alerts.enricher_price_time_us:3|h|#env:prod,service:alerts"]
+        );
+    }
+
+    #[test]
+    fn test_pack_datagrams() {
+        let lines: Vec<String> = ["a:1|c", "b:2|c", "c:3|c"].map(String::from).to_vec();
+        assert_eq!(pack(&lines, 11), ["a:1|c\nb:2|c", "c:3|c"]);
+        assert_eq!(pack(&lines, 3), ["a:1|c", "b:2|c", "c:3|c"]);
+        assert!(pack(&[], 10).is_empty());
+    }
+
+    #[test]
+    fn test_flavor_parse() {
+        assert_eq!(
+            StatsdFlavor::parse("DogStatsD"),
+            Some(StatsdFlavor::Datadog)
+        );
+        assert_eq!(StatsdFlavor::parse(" statsd "), Some(StatsdFlavor::Statsd));
+        assert_eq!(StatsdFlavor::parse("graphite"), None);
+    }
+}
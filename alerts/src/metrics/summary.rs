@@ -0,0 +1,353 @@
+//! Periodic processing summary for tuning filters.
+//!
+//! Carbon's metrics only carry names and values, so the log metrics show
+//! throughput but not *what* was processed. [`Activity`] is shared with the
+//! processors, which record the kind of every decoded instruction and the
+//! outcome of every filter check along with its pool. On each metrics flush
+//! [`SummaryMetrics`] logs the window and starts a new one:
+//!
+//! ```text
+//! Activity: 1520 instructions, filter matched 48/1210 (4.0%)
+//!   clmm: 900 instructions (Swap 610, SwapV2 250, IncreaseLiquidityV2 40), filter matched 30/800 (3.8%)
+//!   cpmm: 620 instructions (SwapBaseInput 590, SwapBaseOutput 30), filter matched 18/410 (4.4%)
+//!   #1 3ucNos4NbumPLZNWztqGHNFFgkHeRMBQAVemeeomsUxv [clmm] 220 checked, 0 matched
+//! ```
+//!
+//! Pools are ranked by filter checks, i.e. by how often instructions touched
+//! them, so busy pools that never match show up as well.
+
+use {
+    async_trait::async_trait,
+    carbon_core::{error::CarbonResult, metrics::Metrics},
+    solana_pubkey::Pubkey,
+    std::{
+        collections::{BTreeMap, HashMap},
+        env,
+        fmt::{self, Write},
+        sync::{Arc, Mutex, PoisonError},
+    },
+};
+
+/// Default number of pools listed in the summary.
+const DEFAULT_TOP_POOLS: usize = 5;
+
+/// Configuration for the activity summary.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SummaryConfig {
+    /// Most active pools listed per flush (`0` lists none)
+    pub top_pools: usize,
+}
+
+impl Default for SummaryConfig {
+    fn default() -> Self {
+        Self {
+            top_pools: DEFAULT_TOP_POOLS,
+        }
+    }
+}
+
+impl SummaryConfig {
+    /// Creates summary configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `METRICS_TOP_POOLS` - Optional: Most active pools listed per flush (default: 5)
+    pub fn from_env() -> Self {
+        let top_pools = env::var("METRICS_TOP_POOLS")
+            .ok()
+            .and_then(|n| n.trim().parse().ok())
+            .unwrap_or(DEFAULT_TOP_POOLS);
+        Self { top_pools }
+    }
+}
+
+/// Filter checks and how many of them matched.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct FilterCounts {
+    checked: u64,
+    matched: u64,
+}
+
+impl FilterCounts {
+    fn record(&mut self, matched: bool) {
+        self.checked += 1;
+        self.matched += u64::from(matched);
+    }
+
+    fn add(&mut self, other: FilterCounts) {
+        self.checked += other.checked;
+        self.matched += other.matched;
+    }
+}
+
+impl fmt::Display for FilterCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "filter matched {}/{}", self.matched, self.checked)?;
+        if self.checked > 0 {
+            let rate = self.matched as f64 * 100.0 / self.checked as f64;
+            write!(f, " ({rate:.1}%)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Activity of one protocol within a window.
+#[derive(Debug, Default)]
+struct ProtocolActivity {
+    instructions: u64,
+    kinds: HashMap<String, u64>,
+    filter: FilterCounts,
+}
+
+/// Activity of one pool within a window.
+#[derive(Debug)]
+struct PoolActivity {
+    label: &'static str,
+    filter: FilterCounts,
+}
+
+/// Activity recorded since the last flush.
+#[derive(Debug, Default)]
+struct Window {
+    protocols: BTreeMap<&'static str, ProtocolActivity>,
+    pools: HashMap<Pubkey, PoolActivity>,
+}
+
+impl Window {
+    fn is_empty(&self) -> bool {
+        self.protocols.is_empty()
+    }
+
+    /// Renders the summary lines, listing at most `top_pools` pools.
+    fn summarize(&self, top_pools: usize) -> Vec<String> {
+        let mut filter = FilterCounts::default();
+        let mut instructions = 0;
+        for activity in self.protocols.values() {
+            filter.add(activity.filter);
+            instructions += activity.instructions;
+        }
+
+        let mut lines = vec![format!("Activity: {instructions} instructions, {filter}")];
+        for (label, activity) in &self.protocols {
+            let mut kinds: Vec<_> = activity.kinds.iter().collect();
+            kinds.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+            let mut line = format!("  {label}: {} instructions", activity.instructions);
+            if !kinds.is_empty() {
+                let kinds: Vec<String> = kinds
+                    .iter()
+                    .map(|(kind, count)| format!("{kind} {count}"))
+                    .collect();
+                let _ = write!(line, " ({})", kinds.join(", "));
+            }
+            if activity.filter.checked > 0 {
+                let _ = write!(line, ", {}", activity.filter);
+            }
+            lines.push(line);
+        }
+
+        let mut pools: Vec<_> = self.pools.iter().collect();
+        pools.sort_by(|a, b| {
+            b.1.filter
+                .checked
+                .cmp(&a.1.filter.checked)
+                .then_with(|| a.0.cmp(b.0))
+        });
+        for (rank, (pool, activity)) in pools.into_iter().take(top_pools).enumerate() {
+            lines.push(format!(
+                "  #{} {pool} [{}] {} checked, {} matched",
+                rank + 1,
+                activity.label,
+                activity.filter.checked,
+                activity.filter.matched
+            ));
+        }
+        lines
+    }
+}
+
+/// Processing activity shared between the processors and [`SummaryMetrics`].
+#[derive(Debug, Default)]
+pub struct Activity {
+    window: Mutex<Window>,
+}
+
+impl Activity {
+    /// Creates an empty activity window.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn window(&self) -> std::sync::MutexGuard<'_, Window> {
+        self.window.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Records a decoded instruction under its variant name (e.g., `SwapV2`).
+    pub fn record_instruction(&self, label: &'static str, instruction: &impl fmt::Debug) {
+        let kind = variant_name(instruction);
+        let mut window = self.window();
+        let activity = window.protocols.entry(label).or_default();
+        activity.instructions += 1;
+        *activity.kinds.entry(kind).or_default() += 1;
+    }
+
+    /// Records whether an instruction touching `pool` passed the filters.
+    pub fn record_filter(&self, label: &'static str, pool: &Pubkey, matched: bool) {
+        let mut window = self.window();
+        window
+            .protocols
+            .entry(label)
+            .or_default()
+            .filter
+            .record(matched);
+        window
+            .pools
+            .entry(*pool)
+            .or_insert(PoolActivity {
+                label,
+                filter: FilterCounts::default(),
+            })
+            .filter
+            .record(matched);
+    }
+
+    /// Takes the current window, starting a new one.
+    fn take(&self) -> Window {
+        std::mem::take(&mut *self.window())
+    }
+}
+
+/// Returns the variant name of a decoded instruction from its `Debug` output.
+///
+/// Formatting stops at the first delimiter, so the instruction's fields are
+/// never rendered.
+fn variant_name(instruction: &impl fmt::Debug) -> String {
+    struct Name(String);
+
+    impl Write for Name {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            match s.find(['(', ' ', '{']) {
+                Some(end) => {
+                    self.0.push_str(&s[..end]);
+                    Err(fmt::Error)
+                }
+                None => {
+                    self.0.push_str(s);
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    let mut name = Name(String::new());
+    let _ = write!(name, "{instruction:?}");
+    name.0
+}
+
+/// Carbon metrics backend logging the [`Activity`] window on every flush.
+///
+/// Counters, gauges, and histograms are left to the other backends.
+pub struct SummaryMetrics {
+    config: SummaryConfig,
+    activity: Arc<Activity>,
+}
+
+impl SummaryMetrics {
+    /// Creates a backend summarizing `activity`.
+    pub fn new(config: SummaryConfig, activity: Arc<Activity>) -> Self {
+        Self { config, activity }
+    }
+}
+
+#[async_trait]
+impl Metrics for SummaryMetrics {
+    async fn initialize(&self) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn flush(&self) -> CarbonResult<()> {
+        let window = self.activity.take();
+        if window.is_empty() {
+            return Ok(());
+        }
+        for line in window.summarize(self.config.top_pools) {
+            log::info!("{line}");
+        }
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> CarbonResult<()> {
+        self.flush().await
+    }
+
+    async fn update_gauge(&self, _name: &str, _value: f64) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn increment_counter(&self, _name: &str, _value: u64) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn record_histogram(&self, _name: &str, _value: f64) -> CarbonResult<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(dead_code)]
+    #[derive(Debug)]
+    enum Instruction {
+        Swap(u64),
+        SwapV2 { amount: u64 },
+        Close,
+    }
+
+    #[test]
+    fn test_variant_name() {
+        assert_eq!(variant_name(&Instruction::Swap(7)), "Swap");
+        assert_eq!(variant_name(&Instruction::SwapV2 { amount: 7 }), "SwapV2");
+        assert_eq!(variant_name(&Instruction::Close), "Close");
+    }
+
+    #[test]
+    fn test_summarize_window() {
+        let activity = Activity::new();
+        let busy = Pubkey::new_from_array([1; 32]);
+        let watched = Pubkey::new_from_array([2; 32]);
+        for _ in 0..3 {
+            activity.record_instruction("clmm", &Instruction::Swap(1));
+            activity.record_filter("clmm", &busy, false);
+        }
+        activity.record_instruction("clmm", &Instruction::Close);
+        activity.record_instruction("cpmm", &Instruction::SwapV2 { amount: 1 });
+        activity.record_filter("cpmm", &watched, true);
+
+        let window = activity.take();
+        assert_eq!(
+            window.summarize(1),
+            [
+                "Activity: 5 instructions, filter matched 1/4 (25.0%)".to_string(),
+                "  clmm: 4 instructions (Swap 3, Close 1), filter matched 0/3 (0.0%)".to_string(),
+                "  cpmm: 1 instructions (SwapV2 1), filter matched 1/1 (100.0%)".to_string(),
+                format!("  #1 {busy} [clmm] 3 checked, 0 matched"),
+            ]
+        );
+        assert_eq!(window.summarize(0).len(), 3);
+        assert!(activity.take().is_empty());
+    }
+
+    #[test]
+    fn test_summary_without_filter_checks() {
+        let activity = Activity::new();
+        activity.record_instruction("pumpfun", &Instruction::Close);
+        assert_eq!(
+            activity.take().summarize(5),
+            [
+                "Activity: 1 instructions, filter matched 0/0",
+                "  pumpfun: 1 instructions (Close 1)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,319 @@
+//! Sandwich attack detection.
+//!
+//! This module provides:
+//! - [`MevConfig`] - Victim size threshold and size tolerance of the attacker's legs
+//! - [`MevDetector`] - Correlates the swaps of a pool within one block
+//!
+//! Processors feed every delivered swap to the detector before slot batching,
+//! so swaps of a pool arrive in block order (with one worker per decoder;
+//! more workers may reorder transactions). A sandwich is a swap by a maker,
+//! followed by swaps of other makers in the same direction, followed by a swap
+//! of the first maker in the opposite direction for about the same token
+//! amount. When the back-run arrives, the detector returns a `MevAlert` event,
+//! which the processor delivers like any other event.
+
+use {
+    crate::output::{
+        EventType, MevAlertEvent, MevKind, MevVictim, SwapEvent, TokenInfo, TradeSide,
+    },
+    std::{
+        collections::HashMap,
+        env,
+        sync::{Mutex, PoisonError},
+    },
+};
+
+/// Default tolerance between the token amounts of the front-run and back-run, in percent.
+const DEFAULT_SIZE_TOLERANCE_PCT: f64 = 10.0;
+
+/// Pools tracked before those of older slots are dropped.
+const MAX_POOLS: usize = 4_096;
+
+/// Slots behind the latest one whose pools are kept when pruning.
+const PRUNE_SLOTS: u64 = 32;
+
+/// Configuration for sandwich detection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MevConfig {
+    /// Minimum total USD value of the victim swaps (unpriced victims count as 0)
+    pub min_victim_usd: f64,
+    /// Maximum difference between the token amounts of the front-run and
+    /// back-run, in percent of the front-run amount
+    pub size_tolerance_pct: f64,
+}
+
+impl MevConfig {
+    /// Creates MEV detection configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `MEV_MIN_VICTIM_USD` - Enables detection; minimum USD value of the victim
+    ///   swaps of an alert (`0` alerts on every sandwich)
+    /// - `MEV_SIZE_TOLERANCE_PCT` - Optional: Maximum difference between the token
+    ///   amounts of the front-run and back-run, in percent (default: 10)
+    ///
+    /// # Returns
+    ///
+    /// `Some(MevConfig)` if `MEV_MIN_VICTIM_USD` is set, `None` otherwise.
+    pub fn from_env() -> Option<Self> {
+        let value = env::var("MEV_MIN_VICTIM_USD").ok()?;
+        let min_victim_usd = match value.trim().parse::<f64>() {
+            Ok(usd) if usd >= 0.0 => usd,
+            _ => {
+                log::warn!("Invalid MEV_MIN_VICTIM_USD '{value}', MEV detection disabled");
+                return None;
+            }
+        };
+        let size_tolerance_pct = env::var("MEV_SIZE_TOLERANCE_PCT")
+            .ok()
+            .and_then(|v| v.trim().parse::<f64>().ok())
+            .filter(|pct| *pct >= 0.0)
+            .unwrap_or(DEFAULT_SIZE_TOLERANCE_PCT);
+        Some(Self {
+            min_victim_usd,
+            size_tolerance_pct,
+        })
+    }
+}
+
+/// A swap of a pool in the current block.
+#[derive(Debug, Clone)]
+struct Trade {
+    signature: String,
+    maker: String,
+    side: TradeSide,
+    /// Raw amount of the non-base token
+    token_amount: u64,
+    amount_usd: Option<f64>,
+}
+
+impl Trade {
+    /// Returns the trade of a swap between a base token and another token.
+    fn from_event(event: &SwapEvent) -> Option<Self> {
+        if event.event_type != EventType::Swap {
+            return None;
+        }
+        let side = event.side?;
+        let (input, output) = (event.input_token.as_ref()?, event.output_token.as_ref()?);
+        let token: &TokenInfo = match side {
+            TradeSide::Buy => output,
+            TradeSide::Sell => input,
+        };
+        Some(Self {
+            signature: event.signature.clone(),
+            maker: event.maker.clone()?,
+            side,
+            token_amount: token.amount_raw,
+            amount_usd: event.usd_value(),
+        })
+    }
+}
+
+/// Swaps of one pool in its latest block, in arrival order.
+#[derive(Debug, Default)]
+struct Block {
+    slot: u64,
+    trades: Vec<Trade>,
+}
+
+/// Detects sandwich attacks among the swaps of each pool's latest block.
+pub struct MevDetector {
+    config: MevConfig,
+    pools: Mutex<HashMap<String, Block>>,
+}
+
+impl MevDetector {
+    /// Creates a detector.
+    pub fn new(config: MevConfig) -> Self {
+        Self {
+            config,
+            pools: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Records a swap and returns a `MevAlert` event if it completes a sandwich.
+    ///
+    /// Events other than swaps between a base token and another token, and
+    /// swaps of a block older than the pool's latest one, are ignored.
+    pub fn observe(&self, event: &SwapEvent) -> Option<SwapEvent> {
+        let trade = Trade::from_event(event)?;
+        let mut pools = self.pools.lock().unwrap_or_else(PoisonError::into_inner);
+        if pools.len() >= MAX_POOLS && !pools.contains_key(&event.pool) {
+            let oldest_kept = event.slot.saturating_sub(PRUNE_SLOTS);
+            pools.retain(|_, block| block.slot >= oldest_kept);
+        }
+
+        let block = pools.entry(event.pool.clone()).or_default();
+        if event.slot < block.slot {
+            return None;
+        }
+        if event.slot > block.slot {
+            block.slot = event.slot;
+            block.trades.clear();
+        }
+
+        let alert = self.find_sandwich(&block.trades, &trade);
+        block.trades.push(trade);
+        alert.map(|mev| SwapEvent::from_mev_alert(event, mev))
+    }
+
+    /// Finds a sandwich completed by `back_run` among the earlier trades.
+    ///
+    /// The latest earlier trade of the same maker in the opposite direction is
+    /// the front-run; the trades of other makers in its direction since then
+    /// are the victims.
+    fn find_sandwich(&self, trades: &[Trade], back_run: &Trade) -> Option<MevAlertEvent> {
+        let front_index = trades
+            .iter()
+            .rposition(|t| t.maker == back_run.maker && t.side != back_run.side)?;
+        let front_run = &trades[front_index];
+        if !self.sizes_match(front_run.token_amount, back_run.token_amount) {
+            return None;
+        }
+
+        let victims: Vec<MevVictim> = trades[front_index + 1..]
+            .iter()
+            .filter(|t| t.maker != back_run.maker && t.side == front_run.side)
+            .map(|t| MevVictim {
+                signature: t.signature.clone(),
+                maker: t.maker.clone(),
+                amount_usd: t.amount_usd,
+            })
+            .collect();
+        if victims.is_empty() {
+            return None;
+        }
+        let victim_usd: f64 = victims.iter().filter_map(|v| v.amount_usd).sum();
+        if victim_usd < self.config.min_victim_usd {
+            return None;
+        }
+
+        let (buy, sell) = match front_run.side {
+            TradeSide::Buy => (front_run, back_run),
+            TradeSide::Sell => (back_run, front_run),
+        };
+        Some(MevAlertEvent {
+            kind: MevKind::Sandwich,
+            attacker: back_run.maker.clone(),
+            side: front_run.side,
+            front_run: front_run.signature.clone(),
+            back_run: back_run.signature.clone(),
+            victims,
+            profit_usd: sell.amount_usd.zip(buy.amount_usd).map(|(s, b)| s - b),
+        })
+    }
+
+    /// Returns `true` if the back-run trades about the front-run's token amount.
+    fn sizes_match(&self, front: u64, back: u64) -> bool {
+        if front == 0 {
+            return false;
+        }
+        let diff = (front as f64 - back as f64).abs() / front as f64 * 100.0;
+        diff <= self.config.size_tolerance_pct
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{swap_event::WSOL_MINT, Protocol},
+    };
+
+    const TOKEN: &str = "TokenMint";
+
+    fn config() -> MevConfig {
+        MevConfig {
+            min_victim_usd: 0.0,
+            size_tolerance_pct: DEFAULT_SIZE_TOLERANCE_PCT,
+        }
+    }
+
+    /// A swap of `sol` lamports for `tokens`, or back when `side` is a sell.
+    fn swap(sig: &str, maker: &str, side: TradeSide, sol: u64, tokens: u64, usd: f64) -> SwapEvent {
+        let sol = TokenInfo::new(WSOL_MINT, sol).with_usd_value(usd);
+        let token = TokenInfo::new(TOKEN, tokens);
+        let (input, output) = match side {
+            TradeSide::Buy => (sol, token),
+            TradeSide::Sell => (token, sol),
+        };
+        SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature(sig)
+            .pool("pool")
+            .input_token(input)
+            .output_token(output)
+            .maker(maker)
+            .slot(100)
+            .build()
+    }
+
+    #[test]
+    fn test_detects_buy_sandwich() {
+        let detector = MevDetector::new(config());
+        assert!(detector
+            .observe(&swap("front", "bot", TradeSide::Buy, 10, 1_000, 1_000.0))
+            .is_none());
+        assert!(detector
+            .observe(&swap("victim", "alice", TradeSide::Buy, 5, 400, 500.0))
+            .is_none());
+        // An unrelated sell between the legs is not a victim
+        assert!(detector
+            .observe(&swap("other", "carol", TradeSide::Sell, 1, 100, 100.0))
+            .is_none());
+
+        let alert = detector
+            .observe(&swap("back", "bot", TradeSide::Sell, 11, 1_000, 1_040.0))
+            .unwrap();
+        assert_eq!(alert.event_type, EventType::MevAlert);
+        assert_eq!(alert.signature, "back");
+        assert_eq!(alert.maker.as_deref(), Some("bot"));
+        let mev = alert.mev.unwrap();
+        assert_eq!(mev.side, TradeSide::Buy);
+        assert_eq!(mev.front_run, "front");
+        assert_eq!(mev.victims.len(), 1);
+        assert_eq!(mev.victims[0].maker, "alice");
+        assert_eq!(mev.profit_usd, Some(40.0));
+    }
+
+    #[test]
+    fn test_requires_victim_and_matching_size() {
+        let detector = MevDetector::new(config());
+        detector.observe(&swap("a", "bot", TradeSide::Buy, 10, 1_000, 1_000.0));
+        // Round trip without a victim
+        assert!(detector
+            .observe(&swap("b", "bot", TradeSide::Sell, 10, 1_000, 1_000.0))
+            .is_none());
+
+        detector.observe(&swap("c", "bot", TradeSide::Sell, 10, 1_000, 1_000.0));
+        detector.observe(&swap("d", "alice", TradeSide::Sell, 5, 400, 500.0));
+        // Buys back far more than it sold
+        assert!(detector
+            .observe(&swap("e", "bot", TradeSide::Buy, 20, 2_000, 2_000.0))
+            .is_none());
+    }
+
+    #[test]
+    fn test_new_block_resets_pool() {
+        let detector = MevDetector::new(config());
+        detector.observe(&swap("front", "bot", TradeSide::Buy, 10, 1_000, 1_000.0));
+        detector.observe(&swap("victim", "alice", TradeSide::Buy, 5, 400, 500.0));
+
+        let mut back = swap("back", "bot", TradeSide::Sell, 11, 1_000, 1_040.0);
+        back.slot = 101;
+        assert!(detector.observe(&back).is_none());
+    }
+
+    #[test]
+    fn test_min_victim_usd() {
+        let detector = MevDetector::new(MevConfig {
+            min_victim_usd: 1_000.0,
+            ..config()
+        });
+        detector.observe(&swap("front", "bot", TradeSide::Buy, 10, 1_000, 1_000.0));
+        detector.observe(&swap("victim", "alice", TradeSide::Buy, 5, 400, 500.0));
+        assert!(detector
+            .observe(&swap("back", "bot", TradeSide::Sell, 11, 1_000, 1_040.0))
+            .is_none());
+    }
+}
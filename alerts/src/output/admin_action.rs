@@ -0,0 +1,170 @@
+//! Administrative actions on pools and fee configs.
+//!
+//! Attached to [`EventType::AdminAction`](super::EventType::AdminAction) events
+//! so holders of a pool learn about fee changes, paused swaps, and authority
+//! transfers, which are easy to miss among the trades.
+
+use {
+    serde::{Deserialize, Serialize},
+    solana_pubkey::Pubkey,
+    std::fmt,
+};
+
+/// Kind of administrative action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdminAction {
+    /// A fee rate of a pool or fee config changed
+    FeeChange,
+    /// Operations of a pool were enabled or disabled
+    StatusChange,
+    /// An owner or authority was handed to another account
+    AuthorityTransfer,
+}
+
+impl fmt::Display for AdminAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FeeChange => write!(f, "fee change"),
+            Self::StatusChange => write!(f, "status change"),
+            Self::AuthorityTransfer => write!(f, "authority transfer"),
+        }
+    }
+}
+
+/// Details of an administrative action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdminActionEvent {
+    /// Kind of action
+    pub action: AdminAction,
+
+    /// Changed parameter (e.g., `trade_fee_rate`, `status`, `owner`)
+    pub param: String,
+
+    /// New value of the parameter (fee rate or status bits)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<u64>,
+
+    /// Account receiving the authority (authority transfers only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_authority: Option<String>,
+
+    /// Whether swaps are disabled after a status change
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub swaps_disabled: Option<bool>,
+
+    /// Signer of the action
+    pub authority: String,
+}
+
+impl AdminActionEvent {
+    /// Creates the details of a fee rate change.
+    pub fn fee_change(param: impl Into<String>, value: u64, authority: &Pubkey) -> Self {
+        Self::new(AdminAction::FeeChange, param, authority).with_value(value)
+    }
+
+    /// Creates the details of a status change.
+    ///
+    /// # Arguments
+    ///
+    /// * `status` - New status value, as stored by the program
+    /// * `swaps_disabled` - Whether the new status disables swaps
+    /// * `authority` - Signer of the change
+    pub fn status_change(status: u64, swaps_disabled: bool, authority: &Pubkey) -> Self {
+        Self {
+            swaps_disabled: Some(swaps_disabled),
+            ..Self::new(AdminAction::StatusChange, "status", authority).with_value(status)
+        }
+    }
+
+    /// Creates the details of an authority transfer.
+    ///
+    /// `new_authority` is `None` when the instruction does not name it.
+    pub fn authority_transfer(
+        param: impl Into<String>,
+        new_authority: Option<&Pubkey>,
+        authority: &Pubkey,
+    ) -> Self {
+        Self {
+            new_authority: new_authority.map(Pubkey::to_string),
+            ..Self::new(AdminAction::AuthorityTransfer, param, authority)
+        }
+    }
+
+    fn new(action: AdminAction, param: impl Into<String>, authority: &Pubkey) -> Self {
+        Self {
+            action,
+            param: param.into(),
+            value: None,
+            new_authority: None,
+            swaps_disabled: None,
+            authority: authority.to_string(),
+        }
+    }
+
+    fn with_value(mut self, value: u64) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    /// Returns the text output lines describing the action.
+    pub fn format_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("🛠️ {}: {}", self.action, self.param)];
+        if let Some(value) = self.value {
+            lines.push(format!("🔢 New value: {value}"));
+        }
+        match self.swaps_disabled {
+            Some(true) => lines.push("⛔ Swaps disabled".to_string()),
+            Some(false) => lines.push("✅ Swaps enabled".to_string()),
+            None => {}
+        }
+        if let Some(ref new_authority) = self.new_authority {
+            lines.push(format!("🔑 New authority: {new_authority}"));
+        }
+        lines.push(format!("👤 Signer: {}", self.authority));
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_lines() {
+        let signer = Pubkey::new_from_array([1; 32]);
+        let paused = AdminActionEvent::status_change(4, true, &signer);
+        assert_eq!(
+            paused.format_lines(),
+            [
+                "🛠️ status change: status".to_string(),
+                "🔢 New value: 4".to_string(),
+                "⛔ Swaps disabled".to_string(),
+                format!("👤 Signer: {signer}"),
+            ]
+        );
+
+        let owner = Pubkey::new_from_array([2; 32]);
+        let transfer = AdminActionEvent::authority_transfer("owner", Some(&owner), &signer);
+        assert_eq!(transfer.value, None);
+        assert_eq!(
+            transfer.format_lines()[1],
+            format!("🔑 New authority: {owner}")
+        );
+    }
+
+    #[test]
+    fn test_json_omits_unset_fields() {
+        let signer = Pubkey::new_from_array([1; 32]);
+        let json = serde_json::to_value(AdminActionEvent::fee_change(
+            "trade_fee_rate",
+            2500,
+            &signer,
+        ))
+        .unwrap();
+        assert_eq!(json["action"], "fee_change");
+        assert_eq!(json["value"], 2500);
+        assert!(json.get("new_authority").is_none());
+        assert!(json.get("swaps_disabled").is_none());
+    }
+}
@@ -0,0 +1,40 @@
+//! User-defined events derived by rules.
+//!
+//! Attached to [`EventType::Custom`](super::EventType::Custom) events emitted by
+//! a rule's `emit` action, so notifiers receive a named alert (e.g.,
+//! "WhaleExit") with its rendered description alongside the fields of the
+//! event it was derived from.
+
+use {
+    super::EventType,
+    serde::{Deserialize, Serialize},
+};
+
+/// A named event emitted by a rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomEvent {
+    /// Event name (e.g., "WhaleExit")
+    pub name: String,
+
+    /// Rule that emitted the event
+    pub rule: String,
+
+    /// Description rendered from the rule's template
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+
+    /// Type of the event the custom event was derived from
+    pub source_type: EventType,
+}
+
+impl CustomEvent {
+    /// Returns the header line shown instead of the event type.
+    pub fn format_header(&self, protocol: impl std::fmt::Display) -> String {
+        format!("⚡ {} [{protocol}]", self.name)
+    }
+
+    /// Returns the description line, if any.
+    pub fn format_description(&self) -> Option<String> {
+        (!self.description.is_empty()).then(|| format!("📝 {}", self.description))
+    }
+}
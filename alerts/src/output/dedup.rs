@@ -0,0 +1,505 @@
+//! Alert deduplication across event sources.
+//!
+//! A single CPMM or CLMM swap can reach a processor more than once: as the swap
+//! instruction (with slippage bounds as amounts when its token transfers cannot
+//! be read), possibly nested under an aggregator route, and as the program's
+//! self-CPI `SwapEvent` carrying the actual executed amounts. Emitting each
+//! variant double-counts volume.
+//!
+//! [`Deduplicator`] holds swap variants for a short window, grouped by the swap
+//! they describe, then emits a single event chosen by [`EventSource::precedence`]
+//! (event log > instruction > aggregator CPI). The other variants are attached to
+//! the emitted event as [`SuppressedVariant`] provenance.
+//!
+//! An event log only knows the executed amounts exactly: CLMM logs name the
+//! user's token accounts rather than the mints when the pool state is not
+//! resolved, and its tokens miss the instruction's enrichment. A collapsed
+//! event log therefore takes the mints, side, and enrichment of the
+//! instruction variant and keeps only its own amounts.
+
+use {
+    super::swap_event::{
+        EventSource, EventType, SuppressedVariant, SwapDirection, SwapEvent, TokenInfo,
+    },
+    std::{
+        collections::HashMap,
+        env,
+        sync::{Arc, Mutex},
+        time::Duration,
+    },
+    tokio::sync::mpsc,
+};
+
+/// Default time to wait for further variants of a swap.
+const DEFAULT_WINDOW_MS: u64 = 300;
+
+/// Configuration for alert deduplication.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DedupConfig {
+    /// How long to hold a swap while waiting for other variants
+    pub window: Duration,
+}
+
+impl DedupConfig {
+    /// Creates deduplication configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `DEDUP_WINDOW_MS` - Optional: Grouping window in milliseconds (default: 300).
+    ///   Set to `0` to disable deduplication.
+    ///
+    /// # Returns
+    ///
+    /// `Some(DedupConfig)` unless deduplication is disabled.
+    pub fn from_env() -> Option<Self> {
+        let window_ms = match env::var("DEDUP_WINDOW_MS") {
+            Ok(value) if !value.trim().is_empty() => match value.trim().parse::<u64>() {
+                Ok(ms) => ms,
+                Err(_) => {
+                    log::warn!(
+                        "Invalid DEDUP_WINDOW_MS '{value}', using default {DEFAULT_WINDOW_MS}"
+                    );
+                    DEFAULT_WINDOW_MS
+                }
+            },
+            _ => DEFAULT_WINDOW_MS,
+        };
+
+        if window_ms == 0 {
+            return None;
+        }
+        Some(Self {
+            window: Duration::from_millis(window_ms),
+        })
+    }
+}
+
+/// Identifies the swap a variant describes.
+///
+/// Event logs are emitted as a CPI of the swap instruction, so an event's
+/// parent path is the path of the instruction it belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct DedupKey {
+    signature: String,
+    instruction_index: u32,
+    swap_path: String,
+    pool: String,
+}
+
+impl DedupKey {
+    fn of(event: &SwapEvent) -> Self {
+        let swap_path = match event.source {
+            EventSource::EventLog => parent_path(&event.provenance.inner_path),
+            _ => event.provenance.inner_path.as_str(),
+        };
+        Self {
+            signature: event.signature.clone(),
+            instruction_index: event.provenance.instruction_index,
+            swap_path: swap_path.to_string(),
+            pool: event.pool.clone(),
+        }
+    }
+}
+
+/// Returns the parent of an inner instruction path ("1.0" -> "1", "0" -> "").
+fn parent_path(path: &str) -> &str {
+    path.rsplit_once('.').map_or("", |(parent, _)| parent)
+}
+
+/// Returns the index of the highest-precedence variant, the first one on ties.
+fn best_variant<'a>(variants: impl Iterator<Item = (usize, &'a SwapEvent)>) -> Option<usize> {
+    variants
+        .max_by_key(|(i, event)| (event.source.precedence(), std::cmp::Reverse(*i)))
+        .map(|(i, _)| i)
+}
+
+/// Collapses variants of one swap into the highest-precedence event.
+///
+/// Ties keep the variant seen first. An event-log winner takes its mints,
+/// side, and enrichment from the best instruction variant (see
+/// [`adopt_instruction`]). The remaining variants are recorded in the returned
+/// event's `suppressed` list, and fields the winner lacks (maker, fee,
+/// direction, router) are filled in from them.
+pub fn collapse(variants: Vec<SwapEvent>) -> Option<SwapEvent> {
+    let winner_index = best_variant(variants.iter().enumerate())?;
+
+    let mut variants = variants;
+    let mut winner = variants.remove(winner_index);
+    if winner.source == EventSource::EventLog {
+        let instruction = best_variant(
+            variants
+                .iter()
+                .enumerate()
+                .filter(|(_, event)| event.source != EventSource::EventLog),
+        );
+        if let Some(index) = instruction {
+            adopt_instruction(&mut winner, &variants[index]);
+        }
+    }
+    for variant in &variants {
+        if winner.maker.is_none() {
+            winner.maker = variant.maker.clone();
+        }
+        if winner.fee.is_none() {
+            winner.fee = variant.fee;
+        }
+        if winner.direction == SwapDirection::Unknown {
+            winner.direction = variant.direction;
+        }
+        if winner.router.is_none() {
+            winner.router = variant.router.clone();
+        }
+        if winner.top_level_program.is_none() {
+            winner.top_level_program = variant.top_level_program.clone();
+        }
+    }
+    winner
+        .suppressed
+        .extend(variants.iter().map(SuppressedVariant::from));
+    Some(winner)
+}
+
+/// Gives an event-log variant the mints, side, and enrichment of the
+/// instruction variant of the same swap.
+///
+/// The log's amount of each leg is kept, unless the log names the mint of the
+/// instruction's other leg there, i.e. the two disagree on the swap's direction.
+fn adopt_instruction(event_log: &mut SwapEvent, instruction: &SwapEvent) {
+    let other_mints = [
+        instruction.output_token.as_ref().map(|t| t.mint.as_str()),
+        instruction.input_token.as_ref().map(|t| t.mint.as_str()),
+    ];
+    let legs = [
+        (&mut event_log.input_token, &instruction.input_token),
+        (&mut event_log.output_token, &instruction.output_token),
+    ];
+    for ((leg, instruction_leg), other_mint) in legs.into_iter().zip(other_mints) {
+        let Some(instruction_leg) = instruction_leg else {
+            continue;
+        };
+        let exact = leg
+            .take()
+            .filter(|exact| other_mint != Some(exact.mint.as_str()))
+            .map(|exact| exact.amount_raw);
+        *leg = Some(match exact {
+            Some(amount_raw) => with_amount(instruction_leg, amount_raw),
+            None => instruction_leg.clone(),
+        });
+    }
+
+    event_log.side = instruction.side;
+    event_log.effective_price = event_log.execution_price().or(instruction.effective_price);
+    event_log.price_impact_pct = event_log.price_impact_pct.or(instruction.price_impact_pct);
+    event_log.market_cap_usd = event_log.market_cap_usd.or(instruction.market_cap_usd);
+    event_log.signed_by_owner = event_log.signed_by_owner.or(instruction.signed_by_owner);
+    event_log.lp_burned = event_log.lp_burned.or(instruction.lp_burned);
+    if event_log.pending_enrichment.is_empty() {
+        event_log.pending_enrichment = instruction.pending_enrichment.clone();
+    }
+}
+
+/// Returns an enriched token with another raw amount, rescaling its
+/// human-readable amount and USD value.
+fn with_amount(token: &TokenInfo, amount_raw: u64) -> TokenInfo {
+    let mut token = token.clone();
+    if token.amount_raw == amount_raw {
+        return token;
+    }
+    token.amount_usd = match token.amount_usd {
+        Some(usd) if token.amount_raw > 0 => {
+            Some(usd * amount_raw as f64 / token.amount_raw as f64)
+        }
+        _ => None,
+    };
+    token.amount_raw = amount_raw;
+    token.amount = token
+        .decimals
+        .map(|decimals| amount_raw as f64 / 10_f64.powi(decimals as i32));
+    token
+}
+
+/// Groups swap variants for a short window and emits one event per swap.
+///
+/// Non-swap events are passed through immediately. Deduplicated events are
+/// delivered on the receiver returned by [`Deduplicator::new`].
+pub struct Deduplicator {
+    window: Duration,
+    pending: Arc<Mutex<HashMap<DedupKey, Vec<SwapEvent>>>>,
+    output: mpsc::UnboundedSender<SwapEvent>,
+}
+
+impl Deduplicator {
+    /// Creates a deduplicator and the receiver for its collapsed events.
+    pub fn new(config: DedupConfig) -> (Self, mpsc::UnboundedReceiver<SwapEvent>) {
+        let (output, rx) = mpsc::unbounded_channel();
+        let dedup = Self {
+            window: config.window,
+            pending: Arc::new(Mutex::new(HashMap::new())),
+            output,
+        };
+        (dedup, rx)
+    }
+
+    /// Submits an event; it is emitted once its grouping window closes.
+    pub fn submit(&self, event: SwapEvent) {
+        if event.event_type != EventType::Swap {
+            let _ = self.output.send(event);
+            return;
+        }
+
+        let key = DedupKey::of(&event);
+        let Ok(mut pending) = self.pending.lock() else {
+            let _ = self.output.send(event);
+            return;
+        };
+
+        if let Some(variants) = pending.get_mut(&key) {
+            variants.push(event);
+            return;
+        }
+        pending.insert(key.clone(), vec![event]);
+
+        let pending = self.pending.clone();
+        let output = self.output.clone();
+        let window = self.window;
+        tokio::spawn(async move {
+            tokio::time::sleep(window).await;
+            let variants = pending
+                .lock()
+                .ok()
+                .and_then(|mut pending| pending.remove(&key));
+            if let Some(event) = variants.and_then(collapse) {
+                if !event.suppressed.is_empty() {
+                    log::debug!(
+                        "Collapsed {} duplicate variant(s) of swap in {}",
+                        event.suppressed.len(),
+                        event.signature
+                    );
+                }
+                let _ = output.send(event);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{Protocol, TokenInfo},
+    };
+
+    fn variant(source: EventSource, inner_path: &str, amount_out: u64) -> SwapEvent {
+        let mut event = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .input_token(TokenInfo::new("mint_in", 100))
+            .output_token(TokenInfo::new("mint_out", amount_out))
+            .source(source)
+            .build();
+        event.provenance.inner_path = inner_path.to_string();
+        event
+    }
+
+    #[test]
+    fn test_parent_path() {
+        assert_eq!(parent_path("1.0"), "1");
+        assert_eq!(parent_path("0"), "");
+        assert_eq!(parent_path(""), "");
+    }
+
+    #[test]
+    fn test_event_log_keyed_to_its_instruction() {
+        let instruction = variant(EventSource::Aggregator, "2", 90);
+        let event_log = variant(EventSource::EventLog, "2.1", 95);
+        let other_swap = variant(EventSource::Aggregator, "3", 90);
+
+        assert_eq!(DedupKey::of(&instruction), DedupKey::of(&event_log));
+        assert_ne!(DedupKey::of(&instruction), DedupKey::of(&other_swap));
+    }
+
+    #[test]
+    fn test_collapse_prefers_event_log() {
+        let collapsed = collapse(vec![
+            variant(EventSource::Instruction, "", 90),
+            variant(EventSource::EventLog, "0", 95),
+        ])
+        .unwrap();
+
+        assert_eq!(collapsed.source, EventSource::EventLog);
+        assert_eq!(collapsed.output_token.as_ref().unwrap().amount_raw, 95);
+        assert_eq!(
+            collapsed.suppressed,
+            vec![SuppressedVariant {
+                source: EventSource::Instruction,
+                inner_path: String::new(),
+                input_amount: Some(100),
+                output_amount: Some(90),
+                direction: SwapDirection::Unknown,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_collapse_fills_missing_fields_from_variants() {
+        let mut instruction = variant(EventSource::Instruction, "", 90);
+        instruction.maker = Some("maker".to_string());
+        instruction.direction = SwapDirection::ExactInput;
+
+        let collapsed =
+            collapse(vec![instruction, variant(EventSource::EventLog, "0", 95)]).unwrap();
+        assert_eq!(collapsed.maker.as_deref(), Some("maker"));
+        assert_eq!(collapsed.direction, SwapDirection::ExactInput);
+        assert_eq!(collapsed.output_token.as_ref().unwrap().amount_raw, 95);
+    }
+
+    #[test]
+    fn test_cpmm_swap_base_input_and_event_log_yield_one_alert() {
+        use crate::output::swap_event::{TradeSide, WSOL_MINT};
+
+        // SwapBaseInput falls back to the minimum output when its transfers are missing
+        let instruction = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .input_token(TokenInfo::new(WSOL_MINT, 1_000_000_000))
+            .output_token(TokenInfo::new("MemeMint", 4_750))
+            .direction(SwapDirection::ExactInput)
+            .maker("payer")
+            .build();
+        let mut event_log = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .source(EventSource::EventLog)
+            .input_token(TokenInfo::new(WSOL_MINT, 1_000_000_000))
+            .output_token(TokenInfo::new("MemeMint", 5_000))
+            .fee(2_500_000)
+            .build();
+        event_log.provenance.inner_path = "0".to_string();
+        assert_eq!(DedupKey::of(&instruction), DedupKey::of(&event_log));
+
+        let collapsed = collapse(vec![instruction, event_log]).unwrap();
+        assert_eq!(collapsed.source, EventSource::EventLog);
+        assert_eq!(collapsed.output_token.as_ref().unwrap().amount_raw, 5_000);
+        assert_eq!(collapsed.fee, Some(2_500_000));
+        assert_eq!(collapsed.maker.as_deref(), Some("payer"));
+        assert_eq!(collapsed.direction, SwapDirection::ExactInput);
+        assert_eq!(collapsed.side, Some(TradeSide::Buy));
+        assert_eq!(collapsed.suppressed[0].output_amount, Some(4_750));
+    }
+
+    #[test]
+    fn test_clmm_one_for_zero_event_log_keeps_instruction_mints() {
+        use crate::output::swap_event::{TradeSide, WSOL_MINT};
+
+        // Pool token 0 is the meme token, token 1 is SOL: a buy swaps one for zero
+        let instruction = SwapEvent::builder()
+            .protocol(Protocol::Clmm)
+            .signature("sig")
+            .pool("pool")
+            .input_token(
+                TokenInfo::new(WSOL_MINT, 1_000_000_000)
+                    .with_decimals(9)
+                    .with_usd_value(150.0),
+            )
+            .output_token(
+                TokenInfo::new("MemeMint", 4_750_000)
+                    .with_symbol("MEME")
+                    .with_decimals(6)
+                    .with_usd_value(142.5),
+            )
+            .direction(SwapDirection::ExactInput)
+            .maker("payer")
+            .build();
+        assert_eq!(instruction.side, Some(TradeSide::Buy));
+
+        // Unresolved event log: the user's token accounts stand in for the mints
+        let mut event_log = SwapEvent::builder()
+            .protocol(Protocol::Clmm)
+            .signature("sig")
+            .pool("pool")
+            .source(EventSource::EventLog)
+            .input_token(TokenInfo::new("token_account1", 1_000_000_000))
+            .output_token(TokenInfo::new("token_account0", 5_000_000))
+            .build();
+        event_log.provenance.inner_path = "0".to_string();
+
+        let collapsed = collapse(vec![instruction.clone(), event_log]).unwrap();
+        assert_eq!(collapsed.source, EventSource::EventLog);
+        let input = collapsed.input_token.as_ref().unwrap();
+        let output = collapsed.output_token.as_ref().unwrap();
+        assert_eq!(
+            (input.mint.as_str(), input.amount_raw),
+            (WSOL_MINT, 1_000_000_000)
+        );
+        assert_eq!(input.amount_usd, Some(150.0));
+        assert_eq!(
+            (output.mint.as_str(), output.amount_raw),
+            ("MemeMint", 5_000_000)
+        );
+        assert_eq!(output.symbol.as_deref(), Some("MEME"));
+        assert_eq!(output.amount, Some(5.0));
+        assert_eq!(output.amount_usd, Some(150.0));
+        assert_eq!(collapsed.side, Some(TradeSide::Buy));
+        assert_eq!(collapsed.maker.as_deref(), Some("payer"));
+
+        // A log naming the legs the other way round keeps the instruction's amounts
+        let mut reversed = SwapEvent::builder()
+            .protocol(Protocol::Clmm)
+            .signature("sig")
+            .pool("pool")
+            .source(EventSource::EventLog)
+            .input_token(TokenInfo::new("MemeMint", 5_000_000))
+            .output_token(TokenInfo::new(WSOL_MINT, 1_000_000_000))
+            .build();
+        reversed.provenance.inner_path = "0".to_string();
+        let collapsed = collapse(vec![instruction, reversed]).unwrap();
+        assert_eq!(collapsed.input_token.as_ref().unwrap().mint, WSOL_MINT);
+        assert_eq!(
+            collapsed.output_token.as_ref().unwrap().amount_raw,
+            4_750_000
+        );
+        assert_eq!(collapsed.side, Some(TradeSide::Buy));
+    }
+
+    #[test]
+    fn test_collapse_prefers_instruction_over_aggregator() {
+        let collapsed = collapse(vec![
+            variant(EventSource::Aggregator, "1", 80),
+            variant(EventSource::Instruction, "", 90),
+        ])
+        .unwrap();
+        assert_eq!(collapsed.source, EventSource::Instruction);
+        assert_eq!(collapsed.suppressed.len(), 1);
+    }
+
+    #[test]
+    fn test_collapse_single_variant_has_no_provenance() {
+        let collapsed = collapse(vec![variant(EventSource::Instruction, "", 90)]).unwrap();
+        assert!(collapsed.suppressed.is_empty());
+        assert!(collapse(Vec::new()).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_deduplicator_emits_one_event_per_swap() {
+        let (dedup, mut rx) = Deduplicator::new(DedupConfig {
+            window: Duration::from_millis(20),
+        });
+
+        dedup.submit(variant(EventSource::Instruction, "", 90));
+        dedup.submit(variant(EventSource::EventLog, "0", 95));
+        let mut other_pool = variant(EventSource::Instruction, "", 90);
+        other_pool.pool = "other".to_string();
+        dedup.submit(other_pool);
+
+        let mut events = [rx.recv().await.unwrap(), rx.recv().await.unwrap()];
+        events.sort_by(|a, b| a.pool.cmp(&b.pool));
+        assert_eq!(events[0].pool, "other");
+        assert!(events[0].suppressed.is_empty());
+        assert_eq!(events[1].source, EventSource::EventLog);
+        assert_eq!(events[1].suppressed.len(), 1);
+        assert!(rx.try_recv().is_err());
+    }
+}
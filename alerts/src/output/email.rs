@@ -0,0 +1,416 @@
+//! Email (SMTP) delivery of rule alerts.
+//!
+//! Rules send rare, high-severity alerts (liquidity drains, watched-wallet
+//! trades) to inboxes with an `email` destination:
+//!
+//! ```toml
+//! destinations = [{ type = "email", to = ["oncall@example.com"], subject = "{emoji} {name} on {pool}" }]
+//! ```
+//!
+//! The SMTP server is shared by every email destination and configured with
+//! `SMTP_*` variables. The subject is rendered with the rule `emit`
+//! placeholders, and the body with the destination's text `template` (see
+//! [`template`](super::template)).
+//!
+//! To keep a burst of alerts from flooding an inbox, the first alert is sent
+//! at once, and the alerts arriving within `EMAIL_BATCH_SECS` after an email
+//! are collected into one digest email sent when the window ends. A digest
+//! holds at most `EMAIL_MAX_EVENTS` alerts; the rest are counted in it.
+
+use {
+    super::{template::DEFAULT_TEMPLATE, SwapEvent},
+    crate::{clock, rules::render_template},
+    lettre::{
+        message::{header::ContentType, Mailbox},
+        transport::smtp::authentication::Credentials,
+        AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+    },
+    std::{
+        env, fmt,
+        time::{Duration, Instant},
+    },
+    tokio::{sync::mpsc, time},
+};
+
+/// Alerts queued per email destination before new ones are dropped.
+const QUEUE_CAPACITY: usize = 1000;
+
+/// Subject of destinations without a `subject`.
+pub const DEFAULT_SUBJECT: &str = "{emoji} {name} [{protocol}] {pool}";
+
+/// Timeout of an SMTP session.
+const SEND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default time alerts are collected after an email.
+const DEFAULT_BATCH_SECS: u64 = 300;
+
+/// Default most alerts of a digest email.
+const DEFAULT_MAX_EVENTS: usize = 50;
+
+/// Errors that can occur while setting up email delivery.
+#[derive(Debug)]
+pub enum EmailError {
+    /// An address is not a valid mailbox
+    Address(String),
+    /// The SMTP transport could not be built
+    Transport(String),
+}
+
+impl fmt::Display for EmailError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Address(e) => write!(f, "invalid email address: {e}"),
+            Self::Transport(e) => write!(f, "failed to set up SMTP transport: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for EmailError {}
+
+/// How the SMTP connection is encrypted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SmtpTls {
+    /// Upgrade a plain connection with STARTTLS (default, port 587)
+    #[default]
+    StartTls,
+    /// TLS from the start (port 465)
+    Tls,
+    /// No encryption (local relays only)
+    None,
+}
+
+impl SmtpTls {
+    /// Parses `starttls`, `tls`, or `none` (case-insensitive).
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "starttls" => Some(Self::StartTls),
+            "tls" => Some(Self::Tls),
+            "none" => Some(Self::None),
+            _ => None,
+        }
+    }
+
+    /// Returns the usual port of the mode.
+    fn default_port(self) -> u16 {
+        match self {
+            Self::StartTls => 587,
+            Self::Tls => 465,
+            Self::None => 25,
+        }
+    }
+}
+
+/// SMTP server shared by email destinations.
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    /// Server host name
+    pub host: String,
+    /// Server port
+    pub port: u16,
+    /// Connection encryption
+    pub tls: SmtpTls,
+    /// Login, if the server requires one
+    pub credentials: Option<(String, String)>,
+    /// Sender of the emails
+    pub from: Mailbox,
+    /// Time alerts are collected into a digest after an email
+    pub batch_window: Duration,
+    /// Most alerts listed in a digest
+    pub max_events: usize,
+}
+
+impl SmtpConfig {
+    /// Creates SMTP configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `SMTP_HOST` - Optional: SMTP server enabling email destinations
+    /// - `SMTP_PORT` - Optional: Server port (default: 587, 465 with `tls`, 25 with `none`)
+    /// - `SMTP_TLS` - Optional: `starttls`, `tls`, or `none` (default: starttls)
+    /// - `SMTP_USERNAME`, `SMTP_PASSWORD` - Optional: Login
+    /// - `SMTP_FROM` - Sender address (e.g., `Raydium Alerts <alerts@example.com>`)
+    /// - `EMAIL_BATCH_SECS` - Optional: Digest window after an email (default: 300)
+    /// - `EMAIL_MAX_EVENTS` - Optional: Most alerts listed in a digest (default: 50)
+    ///
+    /// # Returns
+    ///
+    /// `Ok(None)` if `SMTP_HOST` is not set, an error if `SMTP_FROM` is missing
+    /// or invalid.
+    pub fn from_env() -> Result<Option<Self>, EmailError> {
+        let Some(host) = non_empty("SMTP_HOST") else {
+            return Ok(None);
+        };
+
+        let tls = match non_empty("SMTP_TLS") {
+            Some(value) => SmtpTls::parse(&value).unwrap_or_else(|| {
+                log::warn!("Invalid SMTP_TLS '{value}', expected starttls, tls, or none");
+                SmtpTls::default()
+            }),
+            None => SmtpTls::default(),
+        };
+        let port = non_empty("SMTP_PORT")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_else(|| tls.default_port());
+
+        let from = non_empty("SMTP_FROM")
+            .ok_or_else(|| EmailError::Address("SMTP_FROM is not set".to_string()))?;
+        let from = parse_mailbox(&from)?;
+
+        let credentials = non_empty("SMTP_USERNAME")
+            .map(|username| (username, env::var("SMTP_PASSWORD").unwrap_or_default()));
+
+        let batch_secs: u64 = non_empty("EMAIL_BATCH_SECS")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_BATCH_SECS);
+        let max_events: usize = non_empty("EMAIL_MAX_EVENTS")
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(DEFAULT_MAX_EVENTS);
+
+        Ok(Some(Self {
+            host,
+            port,
+            tls,
+            credentials,
+            from,
+            batch_window: Duration::from_secs(batch_secs),
+            max_events,
+        }))
+    }
+}
+
+/// Returns a trimmed, non-empty environment variable.
+fn non_empty(name: &str) -> Option<String> {
+    env::var(name)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Parses an address, optionally with a display name.
+pub fn parse_mailbox(address: &str) -> Result<Mailbox, EmailError> {
+    address
+        .trim()
+        .parse()
+        .map_err(|e| EmailError::Address(format!("'{address}': {e}")))
+}
+
+/// One email destination.
+#[derive(Debug, Clone)]
+pub struct EmailConfig {
+    /// SMTP server and batching
+    pub smtp: SmtpConfig,
+    /// Recipients
+    pub to: Vec<Mailbox>,
+    /// Subject template
+    pub subject: String,
+    /// Text template of the body (default: `default`)
+    pub template: Option<String>,
+}
+
+impl EmailConfig {
+    /// Renders the subject of an email of `events`, prefixed with the alert
+    /// count of digests.
+    pub fn render_subject(&self, events: &[SwapEvent]) -> String {
+        let Some(first) = events.first() else {
+            return String::new();
+        };
+        let (name, rule) = match first.custom {
+            Some(ref custom) => (custom.name.clone(), custom.rule.clone()),
+            None => (first.event_type.to_string(), String::new()),
+        };
+        // Header values are a single line
+        let subject =
+            render_template(&self.subject, first, &name, &rule).replace(['\r', '\n'], " ");
+        if events.len() > 1 {
+            format!("[{} alerts] {subject}", events.len())
+        } else {
+            subject
+        }
+    }
+
+    /// Renders the body of an email of `events`, noting `dropped` alerts left
+    /// out of a full digest.
+    pub fn render_body(&self, events: &[SwapEvent], dropped: usize) -> String {
+        let template = self.template.as_deref().unwrap_or(DEFAULT_TEMPLATE);
+        let mut body = events
+            .iter()
+            .map(|event| event.format_text_with(template))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        if dropped > 0 {
+            body.push_str(&format!(
+                "\n\n… and {dropped} more alert(s) not listed (EMAIL_MAX_EVENTS)"
+            ));
+        }
+        body
+    }
+
+    /// Builds the message of an email of `events`.
+    fn message(&self, events: &[SwapEvent], dropped: usize) -> Result<Message, String> {
+        let mut builder = Message::builder()
+            .from(self.smtp.from.clone())
+            .subject(self.render_subject(events))
+            .header(ContentType::TEXT_PLAIN);
+        for to in &self.to {
+            builder = builder.to(to.clone());
+        }
+        builder
+            .body(self.render_body(events, dropped))
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Sends rule alerts by email, batching bursts into digests.
+pub struct EmailNotifier {
+    tx: mpsc::Sender<SwapEvent>,
+}
+
+impl EmailNotifier {
+    /// Creates the notifier and spawns its delivery task.
+    ///
+    /// Must be called within a Tokio runtime.
+    pub fn new(config: EmailConfig) -> Result<Self, EmailError> {
+        let smtp = &config.smtp;
+        let builder = match smtp.tls {
+            SmtpTls::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&smtp.host)
+                .map_err(|e| EmailError::Transport(e.to_string()))?,
+            SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&smtp.host)
+                .map_err(|e| EmailError::Transport(e.to_string()))?,
+            SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&smtp.host),
+        };
+        let mut builder = builder.port(smtp.port).timeout(Some(SEND_TIMEOUT));
+        if let Some((ref username, ref password)) = smtp.credentials {
+            builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+        }
+        let transport = builder.build();
+
+        let (tx, rx) = mpsc::channel(QUEUE_CAPACITY);
+        tokio::spawn(Self::delivery_task(rx, transport, config));
+        Ok(Self { tx })
+    }
+
+    /// Tries to queue an alert without blocking.
+    #[allow(clippy::result_large_err)]
+    pub fn try_send(&self, event: SwapEvent) -> Result<(), mpsc::error::TrySendError<SwapEvent>> {
+        self.tx.try_send(event)
+    }
+
+    /// Sends the first alert at once, and the alerts of the following window
+    /// as one digest when it ends. Windows follow the installed [`clock`]. The
+    /// pending digest is sent when the notifier is dropped.
+    async fn delivery_task(
+        mut rx: mpsc::Receiver<SwapEvent>,
+        transport: AsyncSmtpTransport<Tokio1Executor>,
+        config: EmailConfig,
+    ) {
+        let window = config.smtp.batch_window;
+        let mut last_sent: Option<Instant> = None;
+        let mut pending: Vec<SwapEvent> = Vec::new();
+        let mut dropped = 0;
+
+        loop {
+            let flush_at = last_sent.map(|sent| sent + window);
+            tokio::select! {
+                received = rx.recv() => {
+                    let Some(event) = received else {
+                        break;
+                    };
+                    let open = flush_at.is_some_and(|at| clock::now() < at);
+                    if !open && pending.is_empty() {
+                        Self::send(&transport, &config, &[event], 0).await;
+                        last_sent = Some(clock::now());
+                    } else if pending.len() < config.smtp.max_events {
+                        pending.push(event);
+                    } else {
+                        dropped += 1;
+                    }
+                }
+                _ = time::sleep_until(flush_at.unwrap_or_else(clock::now).into()), if !pending.is_empty() => {
+                    Self::send(&transport, &config, &pending, dropped).await;
+                    pending.clear();
+                    dropped = 0;
+                    last_sent = Some(clock::now());
+                }
+            }
+        }
+
+        if !pending.is_empty() {
+            Self::send(&transport, &config, &pending, dropped).await;
+        }
+    }
+
+    /// Sends one email, logging failures.
+    async fn send(
+        transport: &AsyncSmtpTransport<Tokio1Executor>,
+        config: &EmailConfig,
+        events: &[SwapEvent],
+        dropped: usize,
+    ) {
+        let message = match config.message(events, dropped) {
+            Ok(message) => message,
+            Err(e) => {
+                log::warn!("Failed to build alert email: {e}");
+                return;
+            }
+        };
+        match transport.send(message).await {
+            Ok(_) => log::debug!("Sent alert email with {} alert(s)", events.len()),
+            Err(e) => log::warn!(
+                "Failed to send alert email with {} alert(s) via {}: {e}",
+                events.len(),
+                config.smtp.host
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{swap_event::WSOL_MINT, Protocol, TokenInfo},
+    };
+
+    fn config() -> EmailConfig {
+        EmailConfig {
+            smtp: SmtpConfig {
+                host: "smtp.example.com".to_string(),
+                port: 587,
+                tls: SmtpTls::StartTls,
+                credentials: None,
+                from: parse_mailbox("Alerts <alerts@example.com>").unwrap(),
+                batch_window: Duration::from_secs(300),
+                max_events: 50,
+            },
+            to: vec![parse_mailbox("oncall@example.com").unwrap()],
+            subject: "{name} on {pool}".to_string(),
+            template: None,
+        }
+    }
+
+    #[test]
+    fn test_render_subject_and_digest_body() {
+        let event = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .input_token(TokenInfo::new(WSOL_MINT, 1_000_000_000))
+            .output_token(TokenInfo::new("mint", 5))
+            .build();
+        let config = config();
+
+        assert_eq!(
+            config.render_subject(std::slice::from_ref(&event)),
+            "SWAP on pool"
+        );
+        let digest = [event.clone(), event];
+        assert_eq!(config.render_subject(&digest), "[2 alerts] SWAP on pool");
+        assert!(config
+            .render_body(&digest, 3)
+            .ends_with("… and 3 more alert(s) not listed (EMAIL_MAX_EVENTS)"));
+
+        assert!(parse_mailbox("not an address").is_err());
+        assert_eq!(SmtpTls::parse("TLS"), Some(SmtpTls::Tls));
+    }
+}
@@ -0,0 +1,209 @@
+//! Rotating NDJSON file of emitted events.
+//!
+//! With `EVENT_FILE_DIR`, the console sink ([`EventLog`](super::EventLog))
+//! writes every emitted event as one compact JSON line to a file of its own,
+//! so parsers no longer pick events out of the log stream:
+//!
+//! ```text
+//! <EVENT_FILE_DIR>/
+//! ├── events-2026-10-15T12.ndjson.zst   # Closed and compressed
+//! ├── events-2026-10-15T13.ndjson.zst
+//! └── events-2026-10-15T14.ndjson       # Current file
+//! ```
+//!
+//! Files rotate every hour or day of the wall clock. With
+//! `EVENT_FILE_COMPRESS`, a file is compressed with zstd once it is closed by
+//! rotation, on a thread of its own, and the plain file removed. A restart
+//! within a period appends to its file; the file of the period running at
+//! shutdown stays uncompressed.
+
+use {
+    crate::storage::Rotation,
+    std::{
+        env,
+        fs::{self, File, OpenOptions},
+        io::{self, Write},
+        path::{Path, PathBuf},
+        thread,
+    },
+};
+
+/// Prefix of event file names.
+const FILE_PREFIX: &str = "events-";
+
+/// Extension of event files.
+const EXTENSION: &str = "ndjson";
+
+/// zstd level of compressed files.
+const COMPRESSION_LEVEL: i32 = 3;
+
+/// Configuration for the event file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventFileConfig {
+    /// Directory the files are written to
+    pub dir: PathBuf,
+    /// Time-based rotation
+    pub rotation: Rotation,
+    /// Whether closed files are compressed with zstd
+    pub compress: bool,
+}
+
+impl EventFileConfig {
+    /// Creates event file configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `EVENT_FILE_DIR` - Optional: Directory enabling the NDJSON event file
+    /// - `EVENT_FILE_ROTATION` - Optional: `hourly` or `daily` (default: hourly)
+    /// - `EVENT_FILE_COMPRESS` - Optional: `true` to compress closed files with zstd
+    ///
+    /// # Returns
+    ///
+    /// `None` if `EVENT_FILE_DIR` is not set.
+    pub fn from_env() -> Option<Self> {
+        let dir = env::var("EVENT_FILE_DIR")
+            .ok()
+            .filter(|v| !v.trim().is_empty())?;
+
+        let rotation = match env::var("EVENT_FILE_ROTATION") {
+            Ok(value) if !value.trim().is_empty() => Rotation::parse(&value).unwrap_or_else(|| {
+                log::warn!("Invalid EVENT_FILE_ROTATION '{value}', expected hourly or daily");
+                Rotation::default()
+            }),
+            _ => Rotation::default(),
+        };
+
+        let compress = env::var("EVENT_FILE_COMPRESS")
+            .is_ok_and(|v| matches!(v.trim().to_lowercase().as_str(), "true" | "1"));
+
+        Some(Self {
+            dir: PathBuf::from(dir.trim()),
+            rotation,
+            compress,
+        })
+    }
+}
+
+/// The file of the current period.
+struct CurrentFile {
+    period: String,
+    path: PathBuf,
+    file: File,
+}
+
+/// NDJSON event file rotated by period.
+pub struct EventFile {
+    config: EventFileConfig,
+    current: Option<CurrentFile>,
+}
+
+impl EventFile {
+    /// Creates the event file directory if needed.
+    pub fn open(config: EventFileConfig) -> io::Result<Self> {
+        fs::create_dir_all(&config.dir)?;
+        Ok(Self {
+            config,
+            current: None,
+        })
+    }
+
+    /// Returns the directory the files are written to.
+    pub fn dir(&self) -> &Path {
+        &self.config.dir
+    }
+
+    /// Appends a line to the file of the period `now` falls in, closing the
+    /// previous period's file first.
+    pub fn write(&mut self, line: &str, now: i64) -> io::Result<()> {
+        let period = self.config.rotation.period(now);
+        if self
+            .current
+            .as_ref()
+            .is_none_or(|current| current.period != period)
+        {
+            if let Some(previous) = self.current.take() {
+                self.close(previous);
+            }
+            let path = self
+                .config
+                .dir
+                .join(format!("{FILE_PREFIX}{period}.{EXTENSION}"));
+            let file = OpenOptions::new().create(true).append(true).open(&path)?;
+            self.current = Some(CurrentFile { period, path, file });
+        }
+
+        let mut record = String::with_capacity(line.len() + 1);
+        record.push_str(line);
+        record.push('\n');
+        let current = self.current.as_mut().expect("a file is open");
+        current.file.write_all(record.as_bytes())
+    }
+
+    /// Closes a file, compressing it in the background if enabled.
+    fn close(&self, previous: CurrentFile) {
+        let CurrentFile { path, file, .. } = previous;
+        drop(file);
+        if !self.config.compress {
+            return;
+        }
+        let spawned = thread::Builder::new()
+            .name("event-file-compress".to_string())
+            .spawn(move || {
+                if let Err(e) = compress(&path) {
+                    log::warn!("Failed to compress {}: {e}", path.display());
+                }
+            });
+        if let Err(e) = spawned {
+            log::warn!("Failed to spawn event file compression: {e}");
+        }
+    }
+}
+
+/// Compresses a file to `<path>.zst` and removes it.
+fn compress(path: &Path) -> io::Result<PathBuf> {
+    let mut target = path.as_os_str().to_owned();
+    target.push(".zst");
+    let target = PathBuf::from(target);
+    zstd::stream::copy_encode(File::open(path)?, File::create(&target)?, COMPRESSION_LEVEL)?;
+    fs::remove_file(path)?;
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotates_by_period_and_compresses() {
+        let dir = env::temp_dir().join(format!("raydium-alerts-event-file-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let mut file = EventFile::open(EventFileConfig {
+            dir: dir.clone(),
+            rotation: Rotation::Hourly,
+            compress: false,
+        })
+        .unwrap();
+
+        // 2026-10-15T13:00:00Z and one hour later
+        let hour = 1_792_069_200;
+        file.write(r#"{"seq":1}"#, hour).unwrap();
+        file.write(r#"{"seq":2}"#, hour + 60).unwrap();
+        file.write(r#"{"seq":3}"#, hour + 3_600).unwrap();
+
+        let first = dir.join("events-2026-10-15T13.ndjson");
+        assert_eq!(
+            fs::read_to_string(&first).unwrap(),
+            "{\"seq\":1}\n{\"seq\":2}\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("events-2026-10-15T14.ndjson")).unwrap(),
+            "{\"seq\":3}\n"
+        );
+
+        let compressed = compress(&first).unwrap();
+        assert!(!first.exists());
+        let decoded = zstd::decode_all(File::open(compressed).unwrap()).unwrap();
+        assert_eq!(decoded, b"{\"seq\":1}\n{\"seq\":2}\n");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
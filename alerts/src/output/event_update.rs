@@ -0,0 +1,209 @@
+//! Patches for events completed after they were emitted.
+//!
+//! Events are emitted without waiting for slow enrichment: stages cut by the
+//! enrichment budget or timed out (e.g., a token's metadata or a pool's state
+//! still being fetched) complete in the background. An [`EventUpdate`] then
+//! carries only the newly resolved fields, referencing the event by its
+//! `event_id`, so databases and dashboards converge to the complete data
+//! without delaying real-time alerts.
+
+use {
+    super::{HolderConcentration, SwapEvent, TokenAuthorities, TokenInfo},
+    serde::{Deserialize, Serialize},
+};
+
+/// Newly resolved fields of an event's token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenUpdate {
+    /// Token mint address
+    pub mint: String,
+
+    /// Resolved token symbol
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+
+    /// Resolved token decimals
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<u8>,
+
+    /// Human-readable amount computed from the resolved decimals
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<f64>,
+
+    /// Resolved USD value of the amount
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_usd: Option<f64>,
+}
+
+impl TokenUpdate {
+    /// Returns the fields of `after` that are set and differ from `before`,
+    /// or `None` if there are none.
+    fn diff(before: Option<&TokenInfo>, after: Option<&TokenInfo>) -> Option<Self> {
+        let after = after?;
+        let update = Self {
+            mint: after.mint.clone(),
+            symbol: resolved(
+                before.and_then(|t| t.symbol.as_ref()),
+                after.symbol.as_ref(),
+            ),
+            decimals: resolved(
+                before.and_then(|t| t.decimals.as_ref()),
+                after.decimals.as_ref(),
+            ),
+            amount: resolved(
+                before.and_then(|t| t.amount.as_ref()),
+                after.amount.as_ref(),
+            ),
+            amount_usd: resolved(
+                before.and_then(|t| t.amount_usd.as_ref()),
+                after.amount_usd.as_ref(),
+            ),
+        };
+        let empty = update.symbol.is_none()
+            && update.decimals.is_none()
+            && update.amount.is_none()
+            && update.amount_usd.is_none();
+        (!empty).then_some(update)
+    }
+}
+
+/// Fields of an emitted event resolved after the fact.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventUpdate {
+    /// `event_id` of the updated event
+    pub event_id: String,
+
+    /// Transaction signature of the updated event
+    pub signature: String,
+
+    /// Block slot of the updated event
+    pub slot: u64,
+
+    /// Newly resolved input token fields
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_token: Option<TokenUpdate>,
+
+    /// Newly resolved output token fields
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_token: Option<TokenUpdate>,
+
+    /// Resolved market cap of the non-base token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub market_cap_usd: Option<f64>,
+
+    /// Resolved execution price
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_price: Option<f64>,
+
+    /// Resolved price impact, in percent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_impact_pct: Option<f64>,
+
+    /// Resolved holder concentration of a new pool's token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holder_concentration: Option<HolderConcentration>,
+
+    /// Resolved mint and freeze authorities of a new pool's token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorities: Option<TokenAuthorities>,
+}
+
+impl EventUpdate {
+    /// Returns the fields set in `after` that were missing or different in
+    /// `before` (the same event as emitted), or `None` if nothing changed.
+    pub fn diff(before: &SwapEvent, after: &SwapEvent) -> Option<Self> {
+        let update = Self {
+            event_id: after.event_id.clone(),
+            signature: after.signature.clone(),
+            slot: after.slot,
+            input_token: TokenUpdate::diff(before.input_token.as_ref(), after.input_token.as_ref()),
+            output_token: TokenUpdate::diff(
+                before.output_token.as_ref(),
+                after.output_token.as_ref(),
+            ),
+            market_cap_usd: resolved(
+                before.market_cap_usd.as_ref(),
+                after.market_cap_usd.as_ref(),
+            ),
+            effective_price: resolved(
+                before.effective_price.as_ref(),
+                after.effective_price.as_ref(),
+            ),
+            price_impact_pct: resolved(
+                before.price_impact_pct.as_ref(),
+                after.price_impact_pct.as_ref(),
+            ),
+            holder_concentration: resolved(
+                before
+                    .pool_created
+                    .as_ref()
+                    .and_then(|created| created.holder_concentration.as_ref()),
+                after
+                    .pool_created
+                    .as_ref()
+                    .and_then(|created| created.holder_concentration.as_ref()),
+            ),
+            authorities: resolved(
+                before
+                    .pool_created
+                    .as_ref()
+                    .and_then(|created| created.authorities.as_ref()),
+                after
+                    .pool_created
+                    .as_ref()
+                    .and_then(|created| created.authorities.as_ref()),
+            ),
+        };
+        let empty = update.input_token.is_none()
+            && update.output_token.is_none()
+            && update.market_cap_usd.is_none()
+            && update.effective_price.is_none()
+            && update.price_impact_pct.is_none()
+            && update.holder_concentration.is_none()
+            && update.authorities.is_none();
+        (!empty).then_some(update)
+    }
+}
+
+/// Returns `after` if it is set and differs from `before`.
+fn resolved<T: PartialEq + Clone>(before: Option<&T>, after: Option<&T>) -> Option<T> {
+    after.filter(|value| before != Some(*value)).cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::output::Protocol};
+
+    #[test]
+    fn test_diff_carries_resolved_fields_only() {
+        let before = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .input_token(TokenInfo::new("mint_in", 1_000).with_usd_value(5.0))
+            .output_token(TokenInfo::new("mint_out", 2_000))
+            .slot(7)
+            .build();
+        assert_eq!(EventUpdate::diff(&before, &before), None);
+
+        let mut after = before.clone();
+        let output = after.output_token.as_mut().unwrap();
+        output.symbol = Some("BONK".to_string());
+        output.amount_usd = Some(5.0);
+        after.market_cap_usd = Some(1e6);
+
+        let update = EventUpdate::diff(&before, &after).unwrap();
+        assert_eq!(update.event_id, "sig#0");
+        assert_eq!(update.input_token, None);
+        let output = update.output_token.as_ref().unwrap();
+        assert_eq!(
+            (output.symbol.as_deref(), output.decimals, output.amount_usd),
+            (Some("BONK"), None, Some(5.0))
+        );
+        assert_eq!(update.market_cap_usd, Some(1e6));
+
+        let json = serde_json::to_value(&update).unwrap();
+        assert_eq!(json["output_token"]["mint"], "mint_out");
+        assert!(json.get("effective_price").is_none());
+    }
+}
@@ -0,0 +1,103 @@
+//! Fees and rewards collected from concentrated-liquidity pools.
+//!
+//! Attached to [`EventType::CollectFees`](super::EventType::CollectFees)
+//! events, which the CLMM processor emits when a position owner harvests its
+//! trading fees, when the pool's protocol or fund fees are collected, and when
+//! a reward funder withdraws the rewards left after a farm. The fees of the
+//! pool's two tokens are the event's input and output tokens; the kind of
+//! collection, the position, and the rewards paid out are kept here.
+
+use {
+    super::TokenInfo,
+    serde::{Deserialize, Serialize},
+    std::fmt,
+};
+
+/// Kind of fee collection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeCollectionKind {
+    /// A position owner collected its trading fees and farm rewards
+    Position,
+    /// The protocol fee owner collected the pool's protocol fees
+    Protocol,
+    /// The fund fee owner collected the pool's fund fees
+    Fund,
+    /// The reward funder withdrew the rewards left in a reward vault
+    RemainingRewards,
+}
+
+impl fmt::Display for FeeCollectionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Position => write!(f, "position fees"),
+            Self::Protocol => write!(f, "protocol fees"),
+            Self::Fund => write!(f, "fund fees"),
+            Self::RemainingRewards => write!(f, "remaining rewards"),
+        }
+    }
+}
+
+/// Details of a fee and reward collection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeCollectionEvent {
+    /// Kind of collection
+    pub kind: FeeCollectionKind,
+
+    /// Personal position account the fees were collected from (position
+    /// collections only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<String>,
+
+    /// Farm rewards paid out, by reward mint (or reward vault, when the
+    /// transfer does not name the mint)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rewards: Vec<TokenInfo>,
+}
+
+impl FeeCollectionEvent {
+    /// Returns the text output lines describing the collection and its rewards.
+    pub fn format_lines(&self) -> Vec<String> {
+        let mut lines = vec![format!("🧾 Collected: {}", self.kind)];
+        if let Some(ref position) = self.position {
+            lines.push(format!("🎫 Position: {position}"));
+        }
+        for reward in &self.rewards {
+            lines.push(format!("🎁 Reward: {}", reward.format_display(false)));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_lines() {
+        let collection = FeeCollectionEvent {
+            kind: FeeCollectionKind::Position,
+            position: Some("position".to_string()),
+            rewards: vec![TokenInfo::new(
+                "4k3Dyjzvzp8eMZWUXbBCjEvwSkkk59S5iCNLY3QrkX6R",
+                1500,
+            )],
+        };
+        assert_eq!(
+            collection.format_lines(),
+            vec![
+                "🧾 Collected: position fees",
+                "🎫 Position: position",
+                "🎁 Reward: 🪙 4k3Dyjzv 1500"
+            ]
+        );
+
+        let json = serde_json::to_string(&FeeCollectionEvent {
+            kind: FeeCollectionKind::Protocol,
+            position: None,
+            rewards: Vec::new(),
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"kind":"protocol"}"#);
+    }
+}
@@ -0,0 +1,99 @@
+//! Detected liquidity drains.
+//!
+//! Attached to [`EventType::LiquidityDrain`](super::EventType::LiquidityDrain)
+//! events, which the drain detector emits when a single withdrawal removes a
+//! large share of a pool's reserves or the pool creator sells a large share of
+//! a token's supply.
+
+use {
+    serde::{Deserialize, Serialize},
+    std::fmt,
+};
+
+/// Kind of liquidity drain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DrainKind {
+    /// A withdrawal removed a share of the pool's tracked reserve
+    LiquidityRemoved,
+    /// The pool creator sold a share of the token's supply
+    CreatorDump,
+}
+
+impl fmt::Display for DrainKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LiquidityRemoved => write!(f, "liquidity removed"),
+            Self::CreatorDump => write!(f, "creator dump"),
+        }
+    }
+}
+
+/// Details of a detected liquidity drain.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiquidityDrainEvent {
+    /// Kind of drain
+    pub kind: DrainKind,
+
+    /// Mint of the drained reserve or of the sold token
+    pub mint: String,
+
+    /// Share of the pool's reserve removed, or of the token's supply sold, in percent
+    pub share_pct: f64,
+}
+
+impl LiquidityDrainEvent {
+    /// Returns the text output line describing the drain.
+    pub fn format_lines(&self) -> Vec<String> {
+        let of = match self.kind {
+            DrainKind::LiquidityRemoved => "reserve",
+            DrainKind::CreatorDump => "supply",
+        };
+        vec![format!(
+            "🚨 {}: {:.2}% of {} {of}",
+            capitalize(&self.kind.to_string()),
+            self.share_pct,
+            short(&self.mint)
+        )]
+    }
+}
+
+/// Uppercases the first character of a label.
+fn capitalize(label: &str) -> String {
+    let mut chars = label.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Shortens an address to its first 6 and last 4 characters.
+fn short(value: &str) -> String {
+    if value.len() > 12 {
+        format!("{}...{}", &value[..6], &value[value.len() - 4..])
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_lines() {
+        let drain = LiquidityDrainEvent {
+            kind: DrainKind::LiquidityRemoved,
+            mint: "So11111111111111111111111111111111111111112".to_string(),
+            share_pct: 92.5,
+        };
+        assert_eq!(
+            drain.format_lines(),
+            ["🚨 Liquidity removed: 92.50% of So1111...1112 reserve"]
+        );
+
+        let json = serde_json::to_value(&drain).unwrap();
+        assert_eq!(json["kind"], "liquidity_removed");
+        assert_eq!(json["share_pct"], 92.5);
+    }
+}
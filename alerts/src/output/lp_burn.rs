@@ -0,0 +1,42 @@
+//! Detected LP burns.
+//!
+//! Attached to [`EventType::LpBurn`](super::EventType::LpBurn) events, which
+//! the LP burn tracker emits when most of a new pool's LP tokens are burned or
+//! sent to the incinerator, locking the liquidity in the pool for good. Swaps
+//! on the pool are badged `LP burned ✅` from then on.
+
+use serde::{Deserialize, Serialize};
+
+/// Details of a detected LP burn.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LpBurnEvent {
+    /// LP token mint of the pool
+    pub lp_mint: String,
+
+    /// Share of the pool's LP supply burned or held by the incinerator, in percent
+    pub burned_pct: f64,
+}
+
+impl LpBurnEvent {
+    /// Returns the text output line describing the burn.
+    pub fn format_lines(&self) -> Vec<String> {
+        vec![format!(
+            "🔥 LP burned ✅: {:.2}% of {}",
+            self.burned_pct, self.lp_mint
+        )]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_lines() {
+        let burn = LpBurnEvent {
+            lp_mint: "lp".to_string(),
+            burned_pct: 99.5,
+        };
+        assert_eq!(burn.format_lines(), vec!["🔥 LP burned ✅: 99.50% of lp"]);
+    }
+}
@@ -0,0 +1,144 @@
+//! Detected MEV patterns.
+//!
+//! Attached to [`EventType::MevAlert`](super::EventType::MevAlert) events,
+//! which the MEV detector emits when swaps of one block match a known attack
+//! pattern.
+
+use {
+    super::swap_event::{format_number, TradeSide},
+    serde::{Deserialize, Serialize},
+    std::fmt,
+};
+
+/// Kind of MEV pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MevKind {
+    /// Front-run, victim swaps in the same direction, back-run by the same maker
+    Sandwich,
+}
+
+impl fmt::Display for MevKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sandwich => write!(f, "sandwich"),
+        }
+    }
+}
+
+/// A swap caught between the front-run and the back-run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MevVictim {
+    /// Transaction signature of the victim swap
+    pub signature: String,
+    /// Maker of the victim swap
+    pub maker: String,
+    /// USD value of the victim swap (if priced)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_usd: Option<f64>,
+}
+
+/// Details of a detected MEV pattern.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MevAlertEvent {
+    /// Kind of pattern
+    pub kind: MevKind,
+
+    /// Maker of the front-run and back-run
+    pub attacker: String,
+
+    /// Side of the front-run, shared by the victims
+    pub side: TradeSide,
+
+    /// Transaction signature of the front-run
+    pub front_run: String,
+
+    /// Transaction signature of the back-run
+    pub back_run: String,
+
+    /// Swaps between the front-run and the back-run
+    pub victims: Vec<MevVictim>,
+
+    /// USD received by the attacker's sell minus USD spent on its buy (if priced)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub profit_usd: Option<f64>,
+}
+
+impl MevAlertEvent {
+    /// Returns the text output lines describing the pattern.
+    ///
+    /// The attacker is the event's maker, so it is not repeated here.
+    pub fn format_lines(&self) -> Vec<String> {
+        let noun = if self.victims.len() == 1 {
+            "swap"
+        } else {
+            "swaps"
+        };
+        let mut lines = vec![
+            format!("🥪 {} of {} {noun}", self.kind, self.victims.len()),
+            format!("⏩ Front-run: {} ({})", short(&self.front_run), self.side),
+        ];
+        for victim in &self.victims {
+            match victim.amount_usd {
+                Some(usd) => lines.push(format!(
+                    "🎯 Victim: {} ${}",
+                    short(&victim.maker),
+                    format_number(usd)
+                )),
+                None => lines.push(format!("🎯 Victim: {}", short(&victim.maker))),
+            }
+        }
+        lines.push(format!("⏪ Back-run: {}", short(&self.back_run)));
+        if let Some(profit) = self.profit_usd {
+            let sign = if profit < 0.0 { "-" } else { "" };
+            lines.push(format!("💸 Profit: {sign}${}", format_number(profit.abs())));
+        }
+        lines
+    }
+}
+
+/// Shortens an address or signature to its first 6 and last 4 characters.
+fn short(value: &str) -> String {
+    if value.len() > 12 {
+        format!("{}...{}", &value[..6], &value[value.len() - 4..])
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_lines() {
+        let alert = MevAlertEvent {
+            kind: MevKind::Sandwich,
+            attacker: "Bot".to_string(),
+            side: TradeSide::Buy,
+            front_run: "5frontRunSignature".to_string(),
+            back_run: "5backRunSignature".to_string(),
+            victims: vec![MevVictim {
+                signature: "victim".to_string(),
+                maker: "Victim".to_string(),
+                amount_usd: Some(2_500.0),
+            }],
+            profit_usd: Some(-3.5),
+        };
+        assert_eq!(
+            alert.format_lines(),
+            [
+                "🥪 sandwich of 1 swap",
+                "⏩ Front-run: 5front...ture (buy)",
+                "🎯 Victim: Victim $2.50K",
+                "⏪ Back-run: 5backR...ture",
+                "💸 Profit: -$3.50",
+            ]
+        );
+
+        let json = serde_json::to_value(&alert).unwrap();
+        assert_eq!(json["kind"], "sandwich");
+        assert_eq!(json["side"], "buy");
+        assert_eq!(json["victims"][0]["amount_usd"], 2_500.0);
+    }
+}
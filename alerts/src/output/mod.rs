@@ -4,15 +4,96 @@
 //! - [`SwapEvent`] - A normalized swap event structure that abstracts protocol differences
 //! - [`TokenInfo`] - Token information with optional metadata (symbol, decimals, USD value)
 //! - [`OutputFormat`] - Configurable output formatting (text, JSON)
+//! - [`CustomEvent`] - Name and rendered description of rule-emitted events
+//! - [`PoolCreatedEvent`] - Token pair, initial liquidity, creator, open time, and LP mint of new pools,
+//!   with the [`HolderConcentration`] and [`TokenAuthorities`] of the new token
+//! - [`AdminActionEvent`] - Fee, status, and authority changes of pools and fee configs
+//! - [`MevAlertEvent`] - Attacker, front-run, victims, and back-run of detected MEV patterns
+//! - [`LiquidityDrainEvent`] - Reserve or supply share of detected liquidity drains
+//! - [`LpBurnEvent`] - Burned share of the LP supply of new pools
+//! - [`FeeCollectionEvent`] - Kind, position, and rewards of CLMM fee collections
+//! - [`PoolStats`] - Buys, sells, and USD volume of a pool since it was first seen
+//! - [`VolumeSummary`] - Rolling volume, trades, and unique makers of a pool per window
+//! - [`ThrottleDigest`] - Swaps of a token or pool withheld by the alert throttle
+//! - [`EventUpdate`] - Fields of an emitted event resolved after the fact, by `event_id`
+//! - [`Provenance`] - Instruction index, inner path, and program stack that produced an event
+//! - [`Deduplicator`] - Collapses variants of the same swap seen through several sources
+//! - [`Sequencer`] - Per-webhook stream sequence numbers, persisted across restarts, with backfill
+//! - [`SlotBatcher`] - Emits events slot by slot, sorted and behind a slot header
+//! - [`NumberFormat`] - Decimals, separators, locale, and scientific notation of text numbers
+//! - [`TextTemplates`] - Named text alert layouts replacing the built-in one per notifier
+//! - [`SchemaVersion`] - Versioned JSON layouts of webhook events, with the frozen
+//!   [`SwapEventV1`](schema::SwapEventV1) layout and decoding of every version
+//! - [`SerializePool`] - Worker threads rendering the events of a sink off the async runtime,
+//!   with [`EventLog`] formatting the console lines of emitted events
+//! - [`EventFile`] - Rotating, optionally compressed NDJSON file of emitted events
 //! - [`token_transfer`] - Utilities for parsing actual transfer amounts from nested instructions
-//! - Webhook notification support for alerting systems
+//! - [`RaySwapLog`] - Exact amounts and reserves of AMM V4 swaps from their `ray_log` records
+//! - Webhook notification support for alerting systems (JSON, Discord, Telegram), spooling
+//!   undeliverable events to disk for replay
+//! - [`EmailNotifier`] - SMTP delivery of rule alerts, batching bursts into digest emails
+//! - [`PushNotifier`] - Mobile push notifications of alerts through ntfy or Pushover
+//! - [`XNotifier`] - Rate-limited posting of rule alerts to X, truncated to its length limit
+//! - [`Notifier`] - Trait of alert channels, dispatched to by processors through [`Notifiers`]
 
+mod admin_action;
+mod custom_event;
+mod dedup;
+pub mod email;
+mod event_file;
+mod event_update;
+mod fee_collection;
+mod liquidity_drain;
+mod lp_burn;
+mod mev_alert;
+mod notifier;
+pub mod number_format;
+mod pool_created;
+mod pool_stats;
+mod provenance;
+mod push;
+mod ray_log;
+pub mod schema;
+mod sequence;
+mod serialize;
+mod slot_batch;
+mod spool;
 pub mod swap_event;
+pub mod template;
+mod throttle_digest;
 pub mod token_transfer;
+mod volume_summary;
 mod webhook;
+pub mod x_post;
 
+pub use admin_action::{AdminAction, AdminActionEvent};
+pub use custom_event::CustomEvent;
+pub use dedup::{DedupConfig, Deduplicator};
+pub use email::{EmailConfig, EmailError, EmailNotifier, SmtpConfig, SmtpTls};
+pub use event_file::{EventFile, EventFileConfig};
+pub use event_update::{EventUpdate, TokenUpdate};
+pub use fee_collection::{FeeCollectionEvent, FeeCollectionKind};
+pub use liquidity_drain::{DrainKind, LiquidityDrainEvent};
+pub use lp_burn::LpBurnEvent;
+pub use mev_alert::{MevAlertEvent, MevKind, MevVictim};
+pub use notifier::{Notifier, Notifiers};
+pub use number_format::NumberFormat;
+pub use pool_created::{HolderConcentration, PoolCreatedEvent, TokenAuthorities};
+pub use pool_stats::PoolStats;
+pub use provenance::Provenance;
+pub use push::{PushBackend, PushConfig, PushMessage, PushNotifier};
+pub use ray_log::RaySwapLog;
+pub use schema::SchemaVersion;
+pub use sequence::{SequenceConfig, Sequencer};
+pub use serialize::{EventLog, SerializePool};
+pub use slot_batch::{SlotBatchConfig, SlotBatchSender, SlotBatcher};
 pub use swap_event::{
-    parse_output_format, EventType, OutputFormat, Protocol, SwapDirection, SwapEvent, TokenInfo,
+    parse_output_format, EventSource, EventType, OutputFormat, Protocol, SwapDirection, SwapEvent,
+    TokenInfo, TradeSide,
 };
+pub use template::TextTemplates;
+pub use throttle_digest::{ThrottleDigest, ThrottleScope};
 pub use token_transfer::extract_swap_amounts;
-pub use webhook::{WebhookConfig, WebhookNotifier};
+pub use volume_summary::{VolumeSummary, WindowVolume};
+pub use webhook::{WebhookBatch, WebhookConfig, WebhookFormat, WebhookNotifier};
+pub use x_post::{XConfig, XCredentials, XNotifier};
@@ -0,0 +1,186 @@
+//! Alert channels of the processors.
+//!
+//! Every channel alerts are sent to (webhooks, Telegram subscriptions, or a
+//! channel added by an embedding project) implements [`Notifier`]. Processors
+//! hand each alert to one [`Notifiers`] set built from the configured
+//! channels, so a new channel is registered once instead of in every
+//! processor.
+//!
+//! The set gives each notifier its own queue and task, so a slow notifier
+//! never blocks the processors or the other notifiers; its alerts queue up,
+//! and are dropped once [`QUEUE_CAPACITY`] are waiting. Each queue delivers
+//! alerts in the order they were dispatched.
+
+use {
+    super::SwapEvent,
+    async_trait::async_trait,
+    std::{
+        sync::{Arc, Mutex, RwLock},
+        time::Duration,
+    },
+    tokio::{sync::mpsc, task::JoinHandle, time::Instant},
+    tracing::{Instrument, Span},
+};
+
+/// Alerts queued per notifier before new ones are dropped.
+pub const QUEUE_CAPACITY: usize = 10_000;
+
+/// A channel alerts are sent to.
+#[async_trait]
+pub trait Notifier: Send + Sync + 'static {
+    /// Returns a short name for logging (e.g., "webhook").
+    fn name(&self) -> &str;
+
+    /// Sends an alert.
+    ///
+    /// Runs on the notifier's own task, in the span of the processor that
+    /// dispatched the alert.
+    async fn notify(&self, event: &SwapEvent);
+
+    /// Delivers queued alerts within `timeout` on shutdown.
+    async fn shutdown(&self, _timeout: Duration) {}
+}
+
+/// Queue of one notifier.
+struct Queue {
+    name: String,
+    tx: mpsc::Sender<(SwapEvent, Span)>,
+}
+
+/// Notifiers alerts are dispatched to, each on its own task.
+pub struct Notifiers {
+    notifiers: Vec<Arc<dyn Notifier>>,
+    /// Queues in `notifiers` order; empty once shut down
+    queues: RwLock<Vec<Queue>>,
+    tasks: Mutex<Vec<JoinHandle<()>>>,
+}
+
+impl Notifiers {
+    /// Spawns a task per notifier. Must be called within a Tokio runtime.
+    pub fn spawn(notifiers: Vec<Arc<dyn Notifier>>) -> Self {
+        let mut queues = Vec::with_capacity(notifiers.len());
+        let mut tasks = Vec::with_capacity(notifiers.len());
+        for notifier in &notifiers {
+            let (tx, mut rx) = mpsc::channel::<(SwapEvent, Span)>(QUEUE_CAPACITY);
+            let notifier = notifier.clone();
+            queues.push(Queue {
+                name: notifier.name().to_string(),
+                tx,
+            });
+            tasks.push(tokio::spawn(async move {
+                while let Some((event, span)) = rx.recv().await {
+                    notifier.notify(&event).instrument(span).await;
+                }
+            }));
+        }
+        Self {
+            notifiers,
+            queues: RwLock::new(queues),
+            tasks: Mutex::new(tasks),
+        }
+    }
+
+    /// Returns the names of the notifiers.
+    pub fn names(&self) -> Vec<&str> {
+        self.notifiers
+            .iter()
+            .map(|notifier| notifier.name())
+            .collect()
+    }
+
+    /// Returns `true` if there are no notifiers.
+    pub fn is_empty(&self) -> bool {
+        self.notifiers.is_empty()
+    }
+
+    /// Queues an alert for every notifier without blocking.
+    ///
+    /// A notifier whose queue is full drops the alert; the others still get it.
+    pub fn dispatch(&self, event: &SwapEvent) {
+        let queues = self.queues.read().unwrap_or_else(|e| e.into_inner());
+        for queue in queues.iter() {
+            if let Err(e) = queue.tx.try_send((event.clone(), Span::current())) {
+                log::warn!("Failed to queue alert for {}: {e}", queue.name);
+            }
+        }
+    }
+
+    /// Stops accepting alerts, waits up to `timeout` for the queued ones to be
+    /// handed to their notifiers, then shuts the notifiers down with the time left.
+    pub async fn shutdown(&self, timeout: Duration) {
+        let deadline = Instant::now() + timeout;
+        // Dropping the senders ends each task once its queue is empty
+        std::mem::take(&mut *self.queues.write().unwrap_or_else(|e| e.into_inner()));
+        let tasks = std::mem::take(&mut *self.tasks.lock().unwrap_or_else(|e| e.into_inner()));
+        for task in tasks {
+            let abort = task.abort_handle();
+            if tokio::time::timeout_at(deadline, task).await.is_err() {
+                log::warn!("Notifier queue drain timed out after {timeout:?}");
+                abort.abort();
+            }
+        }
+        for notifier in &self.notifiers {
+            notifier
+                .shutdown(deadline.saturating_duration_since(Instant::now()))
+                .await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::output::Protocol};
+
+    /// Notifier recording the signatures it was sent.
+    #[derive(Default)]
+    struct Recording {
+        sent: Mutex<Vec<String>>,
+        closed: Mutex<bool>,
+    }
+
+    #[async_trait]
+    impl Notifier for Recording {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn notify(&self, event: &SwapEvent) {
+            tokio::task::yield_now().await;
+            self.sent.lock().unwrap().push(event.signature.clone());
+        }
+
+        async fn shutdown(&self, _timeout: Duration) {
+            *self.closed.lock().unwrap() = true;
+        }
+    }
+
+    fn event(signature: &str) -> SwapEvent {
+        SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature(signature)
+            .pool("pool")
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_in_order_and_drain_on_shutdown() {
+        let first = Arc::new(Recording::default());
+        let second = Arc::new(Recording::default());
+        let notifiers = Notifiers::spawn(vec![first.clone(), second.clone()]);
+        assert_eq!(notifiers.names(), vec!["recording", "recording"]);
+
+        for signature in ["a", "b", "c"] {
+            notifiers.dispatch(&event(signature));
+        }
+        notifiers.shutdown(Duration::from_secs(5)).await;
+
+        for recording in [&first, &second] {
+            assert_eq!(*recording.sent.lock().unwrap(), vec!["a", "b", "c"]);
+            assert!(*recording.closed.lock().unwrap());
+        }
+
+        // Alerts dispatched after shutdown are not queued
+        notifiers.dispatch(&event("d"));
+        assert_eq!(first.sent.lock().unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,305 @@
+//! Number and amount formatting of text alerts.
+//!
+//! Amounts, prices, and USD values of text alerts, digests, and template
+//! placeholders are formatted by the installed [`NumberFormat`]:
+//!
+//! - Token amounts have 4 decimals, or as many as a token is configured
+//!   with. Amounts below 1 keep at least four significant digits, so a
+//!   fraction of a six-decimal meme token shows as `0.00001250`, not `0.0000`.
+//! - USD values and market caps are compacted with `K`/`M`/`B` suffixes.
+//! - The locale sets the decimal and thousands separators (`en`: `1,234.56`,
+//!   `de`: `1.234,56`, `fr`: `1 234,56`, `ch`: `1'234.56`); by default
+//!   numbers are not grouped.
+//! - Non-zero amounts and prices below the scientific threshold are written
+//!   in scientific notation (`1.250e-9`).
+
+use {
+    super::TokenInfo,
+    std::{collections::HashMap, env, sync::OnceLock},
+};
+
+/// Decimals of token amounts without a configured number of decimals.
+const DEFAULT_AMOUNT_DECIMALS: usize = 4;
+
+/// Significant digits kept for amounts and prices below 1.
+const SIGNIFICANT_DIGITS: usize = 4;
+
+static NUMBER_FORMAT: OnceLock<NumberFormat> = OnceLock::new();
+
+/// Configuration for number formatting.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberFormat {
+    /// Decimals of token amounts
+    pub amount_decimals: usize,
+    /// Decimals of specific tokens, by mint or (uppercase) symbol
+    pub token_decimals: HashMap<String, usize>,
+    /// Separator of the fractional part
+    pub decimal_separator: char,
+    /// Separator of thousands groups (default: not grouped)
+    pub thousands_separator: Option<char>,
+    /// Non-zero amounts and prices below this are written in scientific notation
+    pub scientific_below: Option<f64>,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self {
+            amount_decimals: DEFAULT_AMOUNT_DECIMALS,
+            token_decimals: HashMap::new(),
+            decimal_separator: '.',
+            thousands_separator: None,
+            scientific_below: None,
+        }
+    }
+}
+
+impl NumberFormat {
+    /// Creates number formatting configuration from environment variables.
+    ///
+    /// Invalid values are logged and ignored.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `NUMBER_LOCALE` - Optional: `en`, `de`, `fr`, or `ch` decimal and thousands separators
+    /// - `NUMBER_THOUSANDS_SEPARATOR` - Optional: Thousands separator overriding the locale's
+    ///   (a single character, or `none`)
+    /// - `NUMBER_AMOUNT_DECIMALS` - Optional: Decimals of token amounts (default: 4)
+    /// - `NUMBER_TOKEN_DECIMALS` - Optional: Comma-separated `token=decimals` pairs, the token
+    ///   being a mint or a symbol (e.g., `BONK=0,USDC=2`)
+    /// - `NUMBER_SCIENTIFIC_BELOW` - Optional: Non-zero amounts and prices below this are
+    ///   written in scientific notation (e.g., `0.000001`)
+    pub fn from_env() -> Self {
+        let mut format = Self::default();
+
+        if let Ok(locale) = env::var("NUMBER_LOCALE") {
+            match locale_separators(&locale) {
+                Some((decimal, thousands)) => {
+                    format.decimal_separator = decimal;
+                    format.thousands_separator = Some(thousands);
+                }
+                None => {
+                    log::warn!("Invalid NUMBER_LOCALE '{locale}', expected one of: en, de, fr, ch")
+                }
+            }
+        }
+
+        if let Ok(separator) = env::var("NUMBER_THOUSANDS_SEPARATOR") {
+            let mut chars = separator.chars();
+            match (chars.next(), chars.next()) {
+                _ if separator.eq_ignore_ascii_case("none") => format.thousands_separator = None,
+                (Some(c), None) if c != format.decimal_separator && !c.is_ascii_digit() => {
+                    format.thousands_separator = Some(c)
+                }
+                _ => log::warn!(
+                    "Invalid NUMBER_THOUSANDS_SEPARATOR '{separator}', expected one character \
+                     other than the decimal separator, or 'none'"
+                ),
+            }
+        }
+
+        if let Ok(value) = env::var("NUMBER_AMOUNT_DECIMALS") {
+            match value.trim().parse() {
+                Ok(decimals) => format.amount_decimals = decimals,
+                Err(_) => log::warn!("Invalid NUMBER_AMOUNT_DECIMALS '{value}'"),
+            }
+        }
+
+        if let Ok(value) = env::var("NUMBER_TOKEN_DECIMALS") {
+            for pair in value.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+                let parsed = pair
+                    .split_once('=')
+                    .and_then(|(token, decimals)| {
+                        Some((token.trim(), decimals.trim().parse().ok()?))
+                    })
+                    .filter(|(token, _)| !token.is_empty());
+                match parsed {
+                    Some((token, decimals)) => {
+                        format.token_decimals.insert(token_key(token), decimals);
+                    }
+                    None => log::warn!(
+                        "Invalid NUMBER_TOKEN_DECIMALS entry '{pair}', expected token=decimals"
+                    ),
+                }
+            }
+        }
+
+        if let Ok(value) = env::var("NUMBER_SCIENTIFIC_BELOW") {
+            match value.trim().parse::<f64>() {
+                Ok(threshold) if threshold > 0.0 => format.scientific_below = Some(threshold),
+                _ => log::warn!(
+                    "Invalid NUMBER_SCIENTIFIC_BELOW '{value}', expected a positive number"
+                ),
+            }
+        }
+
+        format
+    }
+
+    /// Installs the format used by text output.
+    ///
+    /// Must be called before events are formatted; later calls are ignored.
+    pub fn install(self) {
+        if NUMBER_FORMAT.set(self).is_err() {
+            log::warn!("Number format already installed, ignoring");
+        }
+    }
+
+    /// Formats a value with `K`/`M`/`B` suffixes and two decimals (e.g., `45.20K`).
+    pub fn compact(&self, n: f64) -> String {
+        if n >= 1_000_000_000.0 {
+            format!("{}B", self.fixed(n / 1_000_000_000.0, 2))
+        } else if n >= 1_000_000.0 {
+            format!("{}M", self.fixed(n / 1_000_000.0, 2))
+        } else if n >= 1_000.0 {
+            format!("{}K", self.fixed(n / 1_000.0, 2))
+        } else {
+            self.fixed(n, 2)
+        }
+    }
+
+    /// Formats a token amount with the token's decimals.
+    pub fn amount(&self, amount: f64, token: &TokenInfo) -> String {
+        let configured = self
+            .token_decimals
+            .get(&token.mint)
+            .or_else(|| {
+                let symbol = token.symbol.as_deref()?;
+                self.token_decimals.get(&token_key(symbol))
+            })
+            .copied();
+        if configured.is_none() && self.is_scientific(amount) {
+            return self.scientific(amount);
+        }
+        let decimals =
+            configured.unwrap_or_else(|| self.amount_decimals.max(significant_decimals(amount)));
+        self.fixed(amount, decimals)
+    }
+
+    /// Formats a price with four significant digits below one, compacting
+    /// prices of one and above.
+    pub fn price(&self, price: f64) -> String {
+        if price >= 1.0 || price <= 0.0 {
+            return self.compact(price);
+        }
+        if self.is_scientific(price) {
+            return self.scientific(price);
+        }
+        self.fixed(price, significant_decimals(price))
+    }
+
+    /// Formats a number with a fixed number of decimals, grouping thousands.
+    pub fn fixed(&self, n: f64, decimals: usize) -> String {
+        let formatted = format!("{n:.decimals$}");
+        let (integer, fraction) = match formatted.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (formatted.as_str(), None),
+        };
+        let (sign, digits) = match integer.strip_prefix('-') {
+            Some(digits) => ("-", digits),
+            None => ("", integer),
+        };
+
+        let mut out = String::with_capacity(formatted.len() + digits.len() / 3);
+        out.push_str(sign);
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                if let Some(separator) = self.thousands_separator {
+                    out.push(separator);
+                }
+            }
+            out.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            out.push(self.decimal_separator);
+            out.push_str(fraction);
+        }
+        out
+    }
+
+    /// Returns `true` if a non-zero value is below the scientific threshold.
+    fn is_scientific(&self, n: f64) -> bool {
+        self.scientific_below
+            .is_some_and(|threshold| n != 0.0 && n.abs() < threshold)
+    }
+
+    /// Formats a value in scientific notation with four significant digits.
+    fn scientific(&self, n: f64) -> String {
+        format!("{n:.3e}").replace('.', &self.decimal_separator.to_string())
+    }
+}
+
+/// Returns the installed number format, or the default one.
+pub fn number_format() -> &'static NumberFormat {
+    NUMBER_FORMAT.get_or_init(NumberFormat::default)
+}
+
+/// Returns the decimal and thousands separators of a locale.
+fn locale_separators(locale: &str) -> Option<(char, char)> {
+    match locale.trim().to_lowercase().as_str() {
+        "en" | "en_us" | "en-us" | "en_gb" | "en-gb" => Some(('.', ',')),
+        "de" | "de_de" | "de-de" | "it" | "es" | "nl" | "id" => Some((',', '.')),
+        "fr" | "fr_fr" | "fr-fr" | "ru" | "pl" => Some((',', ' ')),
+        "ch" | "de_ch" | "de-ch" => Some(('.', '\'')),
+        _ => None,
+    }
+}
+
+/// Returns the decimals needed to show four significant digits of a value
+/// below one, or zero.
+fn significant_decimals(n: f64) -> usize {
+    let n = n.abs();
+    if n >= 1.0 || n == 0.0 {
+        return 0;
+    }
+    (-n.log10()).ceil() as usize + SIGNIFICANT_DIGITS - 1
+}
+
+/// Normalizes a token mint or symbol for lookups: symbols are matched
+/// case-insensitively, mints (32+ characters) as is.
+fn token_key(token: &str) -> String {
+    if token.len() >= 32 {
+        token.to_string()
+    } else {
+        token.to_uppercase()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_format() {
+        let format = NumberFormat::default();
+        assert_eq!(format.compact(45_200.0), "45.20K");
+        assert_eq!(format.compact(1_234.0), "1.23K");
+        assert_eq!(format.fixed(11_500.7, 4), "11500.7000");
+
+        let bonk = TokenInfo::new("BonkMint", 12).with_symbol("BONK");
+        assert_eq!(format.amount(11.988, &bonk), "11.9880");
+        assert_eq!(format.amount(0.0000125, &bonk), "0.00001250");
+        assert_eq!(format.amount(0.0, &bonk), "0.0000");
+        assert_eq!(format.price(0.001042), "0.001042");
+    }
+
+    #[test]
+    fn test_configured_format() {
+        let (decimal, thousands) = locale_separators("de").unwrap();
+        let format = NumberFormat {
+            token_decimals: HashMap::from([("BONK".to_string(), 0)]),
+            decimal_separator: decimal,
+            thousands_separator: Some(thousands),
+            scientific_below: Some(0.000001),
+            ..NumberFormat::default()
+        };
+        assert_eq!(format.fixed(-1_234_567.891, 2), "-1.234.567,89");
+        assert_eq!(format.compact(1_234_000.0), "1,23M");
+
+        let bonk = TokenInfo::new("BonkMint", 12).with_symbol("bonk");
+        assert_eq!(format.amount(1_234_567.4, &bonk), "1.234.567");
+        let token = TokenInfo::new("MemeMint", 1);
+        assert_eq!(format.amount(0.000000125, &token), "1,250e-7");
+        assert_eq!(format.amount(0.5, &token), "0,5000");
+        assert_eq!(format.price(0.0000000123), "1,230e-8");
+    }
+}
@@ -0,0 +1,333 @@
+//! Pool creation details.
+//!
+//! Attached to [`EventType::CreatePool`](super::EventType::CreatePool) events so
+//! notifiers receive the new pool's token pair, initial liquidity, creator,
+//! open time, and LP mint instead of just the pool address. With the `holders`
+//! and `authorities` enrichers, the supply share of the new token's largest
+//! holders ([`HolderConcentration`]) and whether its mint and freeze
+//! authorities are revoked ([`TokenAuthorities`]) are attached as well.
+
+use {
+    super::swap_event::{USDC_MINT, USDT_MINT, WSOL_MINT},
+    serde::{Deserialize, Serialize},
+    solana_pubkey::Pubkey,
+};
+
+/// Share of a token's single largest holder above which it is flagged, in percent.
+pub const CONCENTRATED_HOLDER_PCT: f64 = 50.0;
+
+/// Supply share of a new token's largest holders, a common rug heuristic.
+///
+/// The new pool's own vaults are not holders.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HolderConcentration {
+    /// Mint of the token (the pool's non-base token)
+    pub mint: String,
+
+    /// Owner of the largest holding
+    pub top_holder: String,
+
+    /// Share of the supply held by the largest holder, in percent
+    pub top_holder_pct: f64,
+
+    /// Share of the supply held by the 10 largest holders, in percent
+    pub top10_pct: f64,
+
+    /// `true` if the largest holder holds more than [`CONCENTRATED_HOLDER_PCT`]
+    pub concentrated: bool,
+}
+
+impl HolderConcentration {
+    /// Computes the concentration of `mint` from the amounts held per owner.
+    ///
+    /// Returns `None` if the supply is zero or no holder is left.
+    pub fn from_holdings(
+        mint: &str,
+        supply: u64,
+        mut holdings: Vec<(String, u64)>,
+    ) -> Option<Self> {
+        if supply == 0 || holdings.is_empty() {
+            return None;
+        }
+        holdings.sort_by(|a, b| b.1.cmp(&a.1));
+        let share = |amount: u64| (amount as f64 * 100.0 / supply as f64).min(100.0);
+        let top_holder_pct = share(holdings[0].1);
+        let top10: u64 = holdings.iter().take(10).map(|(_, amount)| amount).sum();
+        Some(Self {
+            mint: mint.to_string(),
+            top_holder: holdings.swap_remove(0).0,
+            top_holder_pct,
+            top10_pct: share(top10),
+            concentrated: top_holder_pct > CONCENTRATED_HOLDER_PCT,
+        })
+    }
+
+    /// Returns the text output line of the largest holders' share.
+    pub fn format_line(&self) -> String {
+        let flag = if self.concentrated {
+            " ⚠️ concentrated"
+        } else {
+            ""
+        };
+        format!(
+            "👥 Top holder: {:.1}% · Top 10: {:.1}%{flag}",
+            self.top_holder_pct, self.top10_pct
+        )
+    }
+}
+
+/// Mint and freeze authorities of a new token.
+///
+/// An active mint authority can inflate the supply, and an active freeze
+/// authority can freeze holders' accounts so they cannot sell.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenAuthorities {
+    /// Mint of the token (the pool's non-base token)
+    pub mint: String,
+
+    /// Account allowed to mint new tokens, `None` if revoked
+    #[serde(default)]
+    pub mint_authority: Option<String>,
+
+    /// Account allowed to freeze token accounts, `None` if revoked
+    #[serde(default)]
+    pub freeze_authority: Option<String>,
+}
+
+impl TokenAuthorities {
+    /// Returns `true` if the token's accounts can be frozen.
+    pub fn freezable(&self) -> bool {
+        self.freeze_authority.is_some()
+    }
+
+    /// Returns the text output line of the authorities' status.
+    pub fn format_line(&self) -> String {
+        let status = |authority: &Option<String>| match authority {
+            Some(_) => "⚠️ active",
+            None => "revoked",
+        };
+        format!(
+            "🔐 Mint authority: {} · Freeze authority: {}",
+            status(&self.mint_authority),
+            status(&self.freeze_authority)
+        )
+    }
+}
+
+/// Details of a newly created pool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolCreatedEvent {
+    /// Mint of the first token of the pair (token 0 / coin / base)
+    pub token_mint_a: String,
+
+    /// Mint of the second token of the pair (token 1 / pc / quote)
+    pub token_mint_b: String,
+
+    /// Initial reserve of token A in raw units.
+    /// `None` when the pool is created without liquidity (e.g., CLMM).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_reserve_a: Option<u64>,
+
+    /// Initial reserve of token B in raw units
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub initial_reserve_b: Option<u64>,
+
+    /// Account that created the pool
+    pub creator: String,
+
+    /// Unix timestamp from which swaps are allowed.
+    /// `None` when the pool opens immediately.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_time: Option<u64>,
+
+    /// LP token mint (not applicable to CLMM or bonding curves)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lp_mint: Option<String>,
+
+    /// Supply share of the non-base token's largest holders (`holders` enricher)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub holder_concentration: Option<HolderConcentration>,
+
+    /// Mint and freeze authorities of the non-base token (`authorities` enricher)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub authorities: Option<TokenAuthorities>,
+}
+
+impl PoolCreatedEvent {
+    /// Creates pool creation details for a token pair.
+    ///
+    /// # Arguments
+    ///
+    /// * `token_mint_a` - First token of the pair
+    /// * `token_mint_b` - Second token of the pair
+    /// * `creator` - Account that created the pool
+    pub fn new(token_mint_a: &Pubkey, token_mint_b: &Pubkey, creator: &Pubkey) -> Self {
+        Self {
+            token_mint_a: token_mint_a.to_string(),
+            token_mint_b: token_mint_b.to_string(),
+            initial_reserve_a: None,
+            initial_reserve_b: None,
+            creator: creator.to_string(),
+            open_time: None,
+            lp_mint: None,
+            holder_concentration: None,
+            authorities: None,
+        }
+    }
+
+    /// Sets the initial reserves in raw units.
+    pub fn with_reserves(mut self, reserve_a: u64, reserve_b: u64) -> Self {
+        self.initial_reserve_a = Some(reserve_a);
+        self.initial_reserve_b = Some(reserve_b);
+        self
+    }
+
+    /// Sets the open time. Raydium uses `0` for pools that open immediately,
+    /// which is stored as `None`.
+    pub fn with_open_time(mut self, open_time: u64) -> Self {
+        self.open_time = (open_time > 0).then_some(open_time);
+        self
+    }
+
+    /// Sets the LP token mint.
+    pub fn with_lp_mint(mut self, lp_mint: &Pubkey) -> Self {
+        self.lp_mint = Some(lp_mint.to_string());
+        self
+    }
+
+    /// Returns the mint of the pair that is not SOL, USDC, or USDT, if exactly one is.
+    pub fn listed_mint(&self) -> Option<&str> {
+        let is_base = |mint: &str| matches!(mint, WSOL_MINT | USDC_MINT | USDT_MINT);
+        match (is_base(&self.token_mint_a), is_base(&self.token_mint_b)) {
+            (true, false) => Some(&self.token_mint_b),
+            (false, true) => Some(&self.token_mint_a),
+            _ => None,
+        }
+    }
+
+    /// Returns the text output lines describing the open time, LP mint,
+    /// holder concentration, and token authorities.
+    pub fn format_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        if let Some(open_time) = self.open_time {
+            lines.push(format!("⏰ Opens: {}", format_open_time(open_time)));
+        }
+        if let Some(ref lp_mint) = self.lp_mint {
+            lines.push(format!("🎟️ LP mint: {}", lp_mint));
+        }
+        if let Some(ref holders) = self.holder_concentration {
+            lines.push(holders.format_line());
+        }
+        if let Some(ref authorities) = self.authorities {
+            lines.push(authorities.format_line());
+        }
+        lines
+    }
+}
+
+/// Formats a Unix timestamp as `YYYY-MM-DD HH:MM:SS UTC`.
+fn format_open_time(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let secs = timestamp % 86_400;
+
+    // Civil-from-days (Howard Hinnant), valid for the proleptic Gregorian calendar
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+        secs / 3_600,
+        secs % 3_600 / 60,
+        secs % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::str::FromStr};
+
+    #[test]
+    fn test_format_open_time() {
+        assert_eq!(format_open_time(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(format_open_time(1_709_251_199), "2024-02-29 23:59:59 UTC");
+        assert_eq!(format_open_time(1_735_689_600), "2025-01-01 00:00:00 UTC");
+    }
+
+    #[test]
+    fn test_builder_and_lines() {
+        let (a, b, creator, lp) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        let created = PoolCreatedEvent::new(&a, &b, &creator)
+            .with_reserves(10, 20)
+            .with_open_time(0)
+            .with_lp_mint(&lp);
+        assert_eq!(created.open_time, None);
+        assert_eq!(created.initial_reserve_b, Some(20));
+        assert_eq!(created.format_lines(), vec![format!("🎟️ LP mint: {lp}")]);
+
+        let json = serde_json::to_value(&created).unwrap();
+        assert!(json.get("open_time").is_none());
+        assert_eq!(json["initial_reserve_a"], 10);
+
+        let created = created.with_open_time(1_735_689_600);
+        assert_eq!(
+            created.format_lines()[0],
+            "⏰ Opens: 2025-01-01 00:00:00 UTC"
+        );
+    }
+
+    #[test]
+    fn test_holder_concentration() {
+        let holdings = vec![
+            ("small".to_string(), 100),
+            ("whale".to_string(), 600),
+            ("mid".to_string(), 200),
+        ];
+        let holders = HolderConcentration::from_holdings("mint", 1_000, holdings).unwrap();
+        assert_eq!(holders.top_holder, "whale");
+        assert_eq!(holders.top_holder_pct, 60.0);
+        assert_eq!(holders.top10_pct, 90.0);
+        assert!(holders.concentrated);
+        assert_eq!(
+            holders.format_line(),
+            "👥 Top holder: 60.0% · Top 10: 90.0% ⚠️ concentrated"
+        );
+        assert!(HolderConcentration::from_holdings("mint", 0, vec![]).is_none());
+
+        let token = Pubkey::new_unique();
+        let sol = Pubkey::from_str(WSOL_MINT).unwrap();
+        let created = PoolCreatedEvent::new(&token, &sol, &Pubkey::new_unique());
+        assert_eq!(created.listed_mint(), Some(token.to_string().as_str()));
+        assert_eq!(
+            PoolCreatedEvent::new(&sol, &sol, &token).listed_mint(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_token_authorities() {
+        let authorities = TokenAuthorities {
+            mint: "mint".to_string(),
+            mint_authority: None,
+            freeze_authority: Some("freezer".to_string()),
+        };
+        assert!(authorities.freezable());
+        assert_eq!(
+            authorities.format_line(),
+            "🔐 Mint authority: revoked · Freeze authority: ⚠️ active"
+        );
+        let json = serde_json::to_value(&authorities).unwrap();
+        assert!(json["mint_authority"].is_null());
+    }
+}
@@ -0,0 +1,56 @@
+//! Cumulative per-pool statistics.
+//!
+//! Attached to every event of a pool by the pool stats tracker (`POOL_STATS`),
+//! so receivers get running totals without keeping state of their own.
+
+use {
+    super::swap_event::format_number,
+    serde::{Deserialize, Serialize},
+};
+
+/// Swaps and volume of a pool since it was first seen.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PoolStats {
+    /// Swaps buying the pool's token, including this event
+    pub buys: u64,
+
+    /// Swaps selling the pool's token, including this event
+    pub sells: u64,
+
+    /// USD volume of the pool's swaps (swaps without a USD value count as 0)
+    pub volume_usd: f64,
+
+    /// Time of the pool's first event (Unix seconds)
+    pub first_seen: i64,
+}
+
+impl PoolStats {
+    /// Returns the text output line of the totals.
+    pub fn format_line(&self) -> String {
+        format!(
+            "📈 Pool: {} buys / {} sells · ${} since first seen",
+            self.buys,
+            self.sells,
+            format_number(self.volume_usd)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_line() {
+        let stats = PoolStats {
+            buys: 120,
+            sells: 80,
+            volume_usd: 45_200.0,
+            first_seen: 1_700_000_000,
+        };
+        assert_eq!(
+            stats.format_line(),
+            "📈 Pool: 120 buys / 80 sells · $45.20K since first seen"
+        );
+    }
+}
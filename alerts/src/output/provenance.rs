@@ -0,0 +1,287 @@
+//! Instruction provenance of events.
+//!
+//! Locates the instruction that produced an event within its transaction: the
+//! top-level instruction index, the inner (CPI) instruction path below it, and
+//! the programs on the invocation stack. Together with the signature, this
+//! identifies the exact instruction an explorer shows for the event.
+//!
+//! The invocation stack also tells direct pool trades from routed flow: a
+//! known router (Jupiter, OKX, Raydium's own router) above the instruction is
+//! reported by [`Provenance::router`].
+
+use {
+    carbon_core::instruction::InstructionMetadata,
+    serde::{Deserialize, Serialize},
+    solana_pubkey::Pubkey,
+    std::fmt,
+};
+
+/// Known swap routers (aggregators) by program ID.
+const ROUTERS: &[(&str, &str)] = &[
+    ("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4", "jupiter"),
+    ("JUP4Fb2cqiRUcaTHdrPC8h2gNsA2ETXiPDD33WcGuJB", "jupiter"),
+    ("6m2CDdhRgxpH4WjvdzxAYbGxwdGUz5MziiL5jek2kBma", "okx"),
+    (
+        "routeUGWgWzqBWFcrCfv8tritsqukccJPu3q5GPP3xS",
+        "raydium_route",
+    ),
+];
+
+/// Returns the name of a known router program (e.g., "jupiter").
+pub fn router_name(program: &str) -> Option<&'static str> {
+    ROUTERS
+        .iter()
+        .find(|(id, _)| *id == program)
+        .map(|(_, name)| *name)
+}
+
+/// Where in a transaction an event was produced.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Provenance {
+    /// Index of the top-level instruction within the transaction
+    #[serde(default)]
+    pub instruction_index: u32,
+
+    /// Path of the inner (CPI) instruction below the top-level one (e.g., "1.0").
+    /// Empty for top-level instructions.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub inner_path: String,
+
+    /// Program IDs from the top-level instruction down to the instruction that
+    /// produced the event (e.g., aggregator, then Raydium)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub program_stack: Vec<String>,
+}
+
+impl Provenance {
+    /// Builds the provenance of an instruction from Carbon's instruction metadata.
+    pub fn from_metadata(metadata: &InstructionMetadata) -> Self {
+        Self {
+            instruction_index: metadata.index,
+            inner_path: format_inner_path(&metadata.absolute_path),
+            program_stack: program_stack(metadata)
+                .iter()
+                .map(Pubkey::to_string)
+                .collect(),
+        }
+    }
+
+    /// Returns the program of the top-level instruction if the event's
+    /// instruction was invoked via CPI.
+    pub fn top_level_program(&self) -> Option<&str> {
+        match self.program_stack.as_slice() {
+            [top, _, ..] => Some(top),
+            _ => None,
+        }
+    }
+
+    /// Returns the innermost known router above the event's instruction on
+    /// the invocation stack.
+    pub fn router(&self) -> Option<&'static str> {
+        let (_, callers) = self.program_stack.split_last()?;
+        callers
+            .iter()
+            .rev()
+            .find_map(|program| router_name(program))
+    }
+
+    /// Returns the explorer-style instruction location (e.g., "#2.1.0").
+    pub fn location(&self) -> String {
+        if self.inner_path.is_empty() {
+            format!("#{}", self.instruction_index)
+        } else {
+            format!("#{}.{}", self.instruction_index, self.inner_path)
+        }
+    }
+}
+
+impl fmt::Display for Provenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.location())?;
+        if self.program_stack.len() > 1 {
+            let programs: Vec<&str> = self
+                .program_stack
+                .iter()
+                .map(|program| &program[..program.len().min(6)])
+                .collect();
+            write!(f, " ({})", programs.join(" → "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats the inner part of an instruction's absolute path (e.g., `[2, 1, 0]` -> "1.0").
+fn format_inner_path(absolute_path: &[u8]) -> String {
+    absolute_path
+        .iter()
+        .skip(1)
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Returns the programs invoked from the top-level instruction down to the
+/// instruction described by `metadata`.
+///
+/// Returns the top-level program only if the inner instruction can't be located,
+/// and an empty stack if the transaction message is unavailable.
+fn program_stack(metadata: &InstructionMetadata) -> Vec<Pubkey> {
+    let transaction = &metadata.transaction_metadata;
+    let keys: Vec<&Pubkey> = transaction
+        .message
+        .static_account_keys()
+        .iter()
+        .chain(&transaction.meta.loaded_addresses.writable)
+        .chain(&transaction.meta.loaded_addresses.readonly)
+        .collect();
+    let program = |index: u8| keys.get(index as usize).map(|key| **key);
+
+    let Some(outer) = transaction
+        .message
+        .instructions()
+        .get(metadata.index as usize)
+        .and_then(|ix| program(ix.program_id_index))
+    else {
+        return Vec::new();
+    };
+    if metadata.absolute_path.len() <= 1 {
+        return vec![outer];
+    }
+
+    let inner: Vec<(Option<u32>, Pubkey)> = transaction
+        .meta
+        .inner_instructions
+        .iter()
+        .flatten()
+        .filter(|group| group.index as u32 == metadata.index)
+        .flat_map(|group| &group.instructions)
+        .map(|ix| {
+            (
+                ix.stack_height,
+                program(ix.instruction.program_id_index).unwrap_or_default(),
+            )
+        })
+        .collect();
+
+    locate_program_stack(outer, &inner, &metadata.absolute_path).unwrap_or_else(|| vec![outer])
+}
+
+/// Finds the invocation stack of the inner instruction at `absolute_path`.
+///
+/// `inner` lists the `(stack_height, program)` of every inner instruction below
+/// the top-level instruction, in execution order. Paths are assigned the way
+/// Carbon assigns `InstructionMetadata::absolute_path`: each element counts the
+/// preceding siblings at that depth.
+fn locate_program_stack(
+    outer: Pubkey,
+    inner: &[(Option<u32>, Pubkey)],
+    absolute_path: &[u8],
+) -> Option<Vec<Pubkey>> {
+    let mut path = vec![*absolute_path.first()?];
+    let mut programs = vec![outer];
+    let mut prev_height = 0;
+
+    for &(stack_height, program) in inner {
+        let height = stack_height.unwrap_or(1).max(1) as usize;
+        if path.len() < height {
+            path.resize(height, 0);
+            programs.resize(height, Pubkey::default());
+        }
+        if height > prev_height {
+            path[height - 1] = 0;
+        } else {
+            path[height - 1] = path[height - 1].wrapping_add(1);
+        }
+        programs[height - 1] = program;
+        prev_height = height;
+
+        if path[..height] == *absolute_path {
+            return Some(programs[..height].to_vec());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_inner_path() {
+        assert_eq!(format_inner_path(&[]), "");
+        assert_eq!(format_inner_path(&[3]), "");
+        assert_eq!(format_inner_path(&[3, 1]), "1");
+        assert_eq!(format_inner_path(&[2, 1, 0]), "1.0");
+    }
+
+    #[test]
+    fn test_locate_program_stack() {
+        let (router, raydium, token, event) = (
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        );
+        // Router (top-level #2) -> [Raydium -> [Token, Token, Raydium self-CPI], Token]
+        let inner = [
+            (Some(2), raydium),
+            (Some(3), token),
+            (Some(3), token),
+            (Some(3), event),
+            (Some(2), token),
+        ];
+
+        assert_eq!(
+            locate_program_stack(router, &inner, &[2, 0]),
+            Some(vec![router, raydium])
+        );
+        assert_eq!(
+            locate_program_stack(router, &inner, &[2, 0, 2]),
+            Some(vec![router, raydium, event])
+        );
+        assert_eq!(
+            locate_program_stack(router, &inner, &[2, 1]),
+            Some(vec![router, token])
+        );
+        assert_eq!(locate_program_stack(router, &inner, &[2, 5]), None);
+    }
+
+    #[test]
+    fn test_display() {
+        let provenance = Provenance {
+            instruction_index: 2,
+            inner_path: "0.2".to_string(),
+            program_stack: vec![
+                "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4".to_string(),
+                "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C".to_string(),
+            ],
+        };
+        assert_eq!(provenance.to_string(), "#2.0.2 (JUP6Lk → CPMMoo)");
+        assert_eq!(Provenance::default().location(), "#0");
+    }
+
+    #[test]
+    fn test_router() {
+        let stack = |programs: &[&str]| Provenance {
+            program_stack: programs.iter().map(|p| p.to_string()).collect(),
+            ..Provenance::default()
+        };
+        let (jupiter, cpmm) = (
+            "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4",
+            "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C",
+        );
+
+        let direct = stack(&[cpmm]);
+        assert_eq!((direct.router(), direct.top_level_program()), (None, None));
+
+        let routed = stack(&["bot", jupiter, cpmm, cpmm]);
+        assert_eq!(routed.router(), Some("jupiter"));
+        assert_eq!(routed.top_level_program(), Some("bot"));
+
+        let unknown = stack(&["bot", cpmm]);
+        assert_eq!(unknown.router(), None);
+        assert_eq!(unknown.top_level_program(), Some("bot"));
+        // The router itself is not routed
+        assert_eq!(stack(&[jupiter]).router(), None);
+    }
+}
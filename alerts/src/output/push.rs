@@ -0,0 +1,298 @@
+//! Mobile push notifications through ntfy or Pushover.
+//!
+//! Small setups get phone alerts without running a Telegram bot: with
+//! `NTFY_TOPIC`, alerts are published to an [ntfy](https://ntfy.sh) topic
+//! (on `NTFY_URL`, ntfy.sh by default), and with `PUSHOVER_TOKEN` and
+//! `PUSHOVER_USER`, sent through the Pushover API. Both can be enabled at once.
+//!
+//! The title is the alert's emoji, name, and protocol, and the message is its
+//! text layout (the `push` template, see [`template`](super::template)), with
+//! the transaction as the tap target. High-priority alerts (liquidity
+//! drains) are sent with high priority. `PUSH_MIN_USD` keeps smaller swaps
+//! off the phone; events without a USD value are always pushed.
+
+use {
+    super::{template::PUSH_TEMPLATE, Notifier, SwapEvent},
+    crate::{chain, rules::render_template},
+    async_trait::async_trait,
+    serde_json::json,
+    std::{env, time::Duration},
+};
+
+/// Default ntfy server.
+pub const DEFAULT_NTFY_URL: &str = "https://ntfy.sh";
+
+/// Pushover message API.
+const PUSHOVER_API_URL: &str = "https://api.pushover.net/1/messages.json";
+
+/// Title of push notifications.
+const TITLE_TEMPLATE: &str = "{emoji} {name} [{protocol}]";
+
+/// Request timeout of a push.
+const PUSH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Service delivering push notifications.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PushBackend {
+    /// Publish to an ntfy topic
+    Ntfy {
+        /// Server URL, without a trailing slash
+        url: String,
+        /// Topic subscribed to by the phone
+        topic: String,
+        /// Access token of protected topics
+        token: Option<String>,
+    },
+    /// Send through the Pushover API
+    Pushover {
+        /// Application API token
+        token: String,
+        /// User or group key
+        user: String,
+    },
+}
+
+impl PushBackend {
+    /// Returns the backend name, used in logs.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Ntfy { .. } => "ntfy",
+            Self::Pushover { .. } => "pushover",
+        }
+    }
+}
+
+/// Configuration of one push backend.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PushConfig {
+    /// Service delivering the notifications
+    pub backend: PushBackend,
+    /// Swaps below this USD value are not pushed
+    pub min_usd: Option<f64>,
+}
+
+impl PushConfig {
+    /// Creates the configuration of every enabled push backend from
+    /// environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `NTFY_TOPIC` - Optional: ntfy topic enabling ntfy pushes
+    /// - `NTFY_URL` - Optional: ntfy server (default: https://ntfy.sh)
+    /// - `NTFY_TOKEN` - Optional: Access token of a protected topic
+    /// - `PUSHOVER_TOKEN`, `PUSHOVER_USER` - Optional: Pushover application token and
+    ///   user key, enabling Pushover pushes
+    /// - `PUSH_MIN_USD` - Optional: Smallest swap USD value pushed (default: all)
+    ///
+    /// # Returns
+    ///
+    /// One configuration per enabled backend; empty if none is.
+    pub fn all_from_env() -> Vec<Self> {
+        let min_usd = non_empty("PUSH_MIN_USD").and_then(|value| match value.parse::<f64>() {
+            Ok(min) if min.is_finite() && min >= 0.0 => Some(min),
+            _ => {
+                log::warn!("Invalid PUSH_MIN_USD '{value}', pushing every alert");
+                None
+            }
+        });
+
+        let mut configs = Vec::new();
+        if let Some(topic) = non_empty("NTFY_TOPIC") {
+            let url = non_empty("NTFY_URL").unwrap_or_else(|| DEFAULT_NTFY_URL.to_string());
+            configs.push(Self {
+                backend: PushBackend::Ntfy {
+                    url: url.trim_end_matches('/').to_string(),
+                    topic,
+                    token: non_empty("NTFY_TOKEN"),
+                },
+                min_usd,
+            });
+        }
+        match (non_empty("PUSHOVER_TOKEN"), non_empty("PUSHOVER_USER")) {
+            (Some(token), Some(user)) => configs.push(Self {
+                backend: PushBackend::Pushover { token, user },
+                min_usd,
+            }),
+            (None, None) => {}
+            _ => log::warn!("Pushover needs both PUSHOVER_TOKEN and PUSHOVER_USER, disabled"),
+        }
+        configs
+    }
+
+    /// Returns `true` if an alert is pushed: swaps below `min_usd` are not.
+    pub fn accepts(&self, event: &SwapEvent) -> bool {
+        match (self.min_usd, event.usd_value()) {
+            (Some(min), Some(usd)) => usd >= min,
+            _ => true,
+        }
+    }
+}
+
+/// Returns a trimmed, non-empty environment variable.
+fn non_empty(name: &str) -> Option<String> {
+    env::var(name)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Title, message, and tap target of a push notification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PushMessage {
+    pub title: String,
+    pub message: String,
+    /// Explorer link of the transaction, if the event has one
+    pub click: Option<String>,
+    pub high_priority: bool,
+}
+
+impl PushMessage {
+    /// Renders the push notification of an alert.
+    pub fn new(event: &SwapEvent) -> Self {
+        let (name, rule) = match event.custom {
+            Some(ref custom) => (custom.name.clone(), custom.rule.clone()),
+            None => (event.event_type.to_string(), String::new()),
+        };
+        Self {
+            title: render_template(TITLE_TEMPLATE, event, &name, &rule),
+            message: event.format_text_with(PUSH_TEMPLATE),
+            click: (!event.signature.is_empty())
+                .then(|| chain::profile().explorer_tx(&event.signature)),
+            high_priority: event.event_type.is_high_priority(),
+        }
+    }
+}
+
+/// Sends alerts as mobile push notifications.
+pub struct PushNotifier {
+    client: reqwest::Client,
+    config: PushConfig,
+}
+
+impl PushNotifier {
+    /// Creates a notifier for one backend.
+    pub fn new(config: PushConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(PUSH_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self { client, config }
+    }
+
+    /// Sends a push notification, returning the error of a failed request.
+    async fn push(&self, message: PushMessage) -> Result<(), String> {
+        let request = match self.config.backend {
+            PushBackend::Ntfy {
+                ref url,
+                ref topic,
+                ref token,
+            } => {
+                // Published as JSON, since headers cannot carry emoji titles
+                let mut body = json!({
+                    "topic": topic,
+                    "title": message.title,
+                    "message": message.message,
+                    "priority": if message.high_priority { 5 } else { 3 },
+                });
+                if let Some(click) = message.click {
+                    body["click"] = json!(click);
+                }
+                let request = self.client.post(url).json(&body);
+                match token {
+                    Some(token) => request.bearer_auth(token),
+                    None => request,
+                }
+            }
+            PushBackend::Pushover {
+                ref token,
+                ref user,
+            } => {
+                let priority = if message.high_priority { "1" } else { "0" };
+                let mut form = vec![
+                    ("token", token.clone()),
+                    ("user", user.clone()),
+                    ("title", message.title),
+                    ("message", message.message),
+                    ("priority", priority.to_string()),
+                ];
+                if let Some(click) = message.click {
+                    form.push(("url", click));
+                }
+                self.client.post(PUSHOVER_API_URL).form(&form)
+            }
+        };
+
+        let response = request.send().await.map_err(|e| e.to_string())?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("HTTP {status}: {body}"));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Notifier for PushNotifier {
+    fn name(&self) -> &str {
+        self.config.backend.name()
+    }
+
+    async fn notify(&self, event: &SwapEvent) {
+        if !self.config.accepts(event) {
+            return;
+        }
+        if let Err(e) = self.push(PushMessage::new(event)).await {
+            log::warn!(
+                "Failed to send {} push for {}: {e}",
+                self.config.backend.name(),
+                event.signature
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{swap_event::WSOL_MINT, EventType, Protocol, TokenInfo},
+    };
+
+    #[test]
+    fn test_message_and_min_usd() {
+        let event = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .input_token(TokenInfo::new(WSOL_MINT, 1_000_000_000).with_usd_value(150.0))
+            .output_token(TokenInfo::new("mint", 5))
+            .build();
+
+        let message = PushMessage::new(&event);
+        assert_eq!(message.title, "🔄 SWAP [CPMM]");
+        assert!(message.click.is_some_and(|click| click.ends_with("sig")));
+        assert!(!message.high_priority);
+
+        let mut drain = event.clone();
+        drain.event_type = EventType::LiquidityDrain;
+        assert!(PushMessage::new(&drain).high_priority);
+
+        let config = PushConfig {
+            backend: PushBackend::Ntfy {
+                url: DEFAULT_NTFY_URL.to_string(),
+                topic: "alerts".to_string(),
+                token: None,
+            },
+            min_usd: Some(1000.0),
+        };
+        assert!(!config.accepts(&event));
+        let created = SwapEvent::builder()
+            .event_type(EventType::CreatePool)
+            .protocol(Protocol::Cpmm)
+            .signature("create")
+            .pool("pool")
+            .build();
+        assert!(config.accepts(&created));
+    }
+}
@@ -0,0 +1,263 @@
+//! Raydium AMM V4 `ray_log` parsing for exact swap amounts.
+//!
+//! The AMM V4 program logs every swap as a `Program log: ray_log: <base64>`
+//! line: a bincode-encoded record whose first byte is the log type, followed
+//! by little-endian `u64` fields. The swap records echo the instruction's
+//! amount limits and carry the actual amounts and the pool reserves before
+//! the swap:
+//!
+//! | Type | Record | Fields after the type byte |
+//! |------|--------|----------------------------|
+//! | `3` | `SwapBaseIn` | `amount_in`, `minimum_out`, `direction`, `user_source`, `pool_coin`, `pool_pc`, `out_amount` |
+//! | `4` | `SwapBaseOut` | `max_in`, `amount_out`, `direction`, `user_source`, `pool_coin`, `pool_pc`, `deduct_in` |
+//!
+//! `direction` is `1` for pc to coin and `2` for coin to pc. Unlike the nested
+//! transfers (see [`token_transfer`](super::token_transfer)), the record does
+//! not depend on which accounts the route passes the tokens through, so it
+//! stays exact when an aggregator reuses intermediary token accounts.
+//!
+//! A swap's record is looked up among the lines its own invocation logged,
+//! between its `Program <id> invoke [n]` line and the matching `success` or
+//! `failed` line, so two swaps with the same limits in one transaction each
+//! find their own record.
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+
+/// Prefix of `ray_log` program log lines.
+const RAY_LOG_PREFIX: &str = "Program log: ray_log: ";
+
+/// Log type of `SwapBaseIn` records.
+const SWAP_BASE_IN: u8 = 3;
+
+/// Log type of `SwapBaseOut` records.
+const SWAP_BASE_OUT: u8 = 4;
+
+/// `direction` value of coin to pc swaps.
+const COIN_TO_PC: u64 = 2;
+
+/// Actual amounts and reserves of an AMM V4 swap, from its `ray_log` record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RaySwapLog {
+    /// `true` for `SwapBaseIn` records, `false` for `SwapBaseOut`
+    pub base_in: bool,
+    /// Amount limits of the instruction: `(amount_in, minimum_out)` for
+    /// `SwapBaseIn`, `(max_in, amount_out)` for `SwapBaseOut`
+    pub limits: (u64, u64),
+    /// Amount taken from the user
+    pub amount_in: u64,
+    /// Amount paid to the user
+    pub amount_out: u64,
+    /// `true` if the swap sold the coin (base) token for the pc (quote) token
+    pub coin_to_pc: bool,
+    /// Coin reserve before the swap, net of the pool's pending PnL
+    pub pool_coin: u64,
+    /// Pc reserve before the swap, net of the pool's pending PnL
+    pub pool_pc: u64,
+}
+
+impl RaySwapLog {
+    /// Parses a program log line, returning `None` if it is not a swap `ray_log`.
+    pub fn parse(line: &str) -> Option<Self> {
+        let data = BASE64
+            .decode(line.strip_prefix(RAY_LOG_PREFIX)?.trim())
+            .ok()?;
+        let (&log_type, fields) = data.split_first()?;
+        let field = |index: usize| {
+            let bytes = fields.get(index * 8..index * 8 + 8)?;
+            Some(u64::from_le_bytes(bytes.try_into().ok()?))
+        };
+
+        let limits = (field(0)?, field(1)?);
+        let coin_to_pc = field(2)? == COIN_TO_PC;
+        let (pool_coin, pool_pc) = (field(4)?, field(5)?);
+        let (base_in, amount_in, amount_out) = match log_type {
+            SWAP_BASE_IN => (true, limits.0, field(6)?),
+            SWAP_BASE_OUT => (false, field(6)?, limits.1),
+            _ => return None,
+        };
+        Some(Self {
+            base_in,
+            limits,
+            amount_in,
+            amount_out,
+            coin_to_pc,
+            pool_coin,
+            pool_pc,
+        })
+    }
+
+    /// Finds the record of a swap instruction in a transaction's log messages.
+    ///
+    /// Only the lines logged by the instruction itself are searched, and the
+    /// record must echo the instruction's kind and amount limits.
+    ///
+    /// # Arguments
+    ///
+    /// * `logs` - Log messages of the transaction
+    /// * `path` - Absolute path of the instruction (top-level index, then the
+    ///   index among its siblings at each inner level)
+    /// * `base_in` - `true` for `SwapBaseIn(V2)`, `false` for `SwapBaseOut(V2)`
+    /// * `limits` - Amount limits of the instruction, ordered as in [`Self::limits`]
+    pub fn find(logs: &[String], path: &[u8], base_in: bool, limits: (u64, u64)) -> Option<Self> {
+        instruction_logs(logs, path)?
+            .into_iter()
+            .filter_map(Self::parse)
+            .find(|log| log.base_in == base_in && log.limits == limits)
+    }
+}
+
+/// Returns the status of a `Program <id> <status>` line, or `None` for
+/// `Program log:`, `Program data:`, and `Program return:` lines.
+fn program_status(line: &str) -> Option<&str> {
+    let (program, status) = line.strip_prefix("Program ")?.split_once(' ')?;
+    (!program.ends_with(':')).then_some(status)
+}
+
+/// Returns the stack height of a `Program <id> invoke [n]` line.
+fn invoke_height(line: &str) -> Option<usize> {
+    program_status(line)?
+        .strip_prefix("invoke [")?
+        .strip_suffix(']')?
+        .parse()
+        .ok()
+}
+
+/// Returns `true` for the `success` or `failed` line ending a program invocation.
+fn is_invoke_end(line: &str) -> bool {
+    program_status(line).is_some_and(|status| status == "success" || status.starts_with("failed"))
+}
+
+/// Returns the lines logged directly by the instruction at `path`, without
+/// those of the programs it invokes, or `None` if its invocation is not logged.
+///
+/// Invocations are numbered the way Carbon numbers instructions: each
+/// top-level invocation takes the next top-level index, and an inner one
+/// index 0 below a shallower invocation or the next index after a sibling.
+fn instruction_logs<'a>(logs: &'a [String], path: &[u8]) -> Option<Vec<&'a str>> {
+    let mut current: Vec<u8> = Vec::new();
+    let mut top_level: Option<u8> = None;
+    let start = logs.iter().position(|line| {
+        let Some(height) = invoke_height(line) else {
+            return false;
+        };
+        if height == 1 {
+            let index = top_level.map_or(0, |index| index.wrapping_add(1));
+            top_level = Some(index);
+            current = vec![index];
+        } else if height > current.len() {
+            current.resize(height, 0);
+        } else {
+            current.truncate(height);
+            current[height - 1] = current[height - 1].wrapping_add(1);
+        }
+        current == path
+    })?;
+
+    let mut depth = 0usize;
+    let mut own = Vec::new();
+    for line in &logs[start + 1..] {
+        if invoke_height(line).is_some() {
+            depth += 1;
+        } else if is_invoke_end(line) {
+            if depth == 0 {
+                break;
+            }
+            depth -= 1;
+        } else if depth == 0 {
+            own.push(line.as_str());
+        }
+    }
+    Some(own)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ray_log(log_type: u8, fields: [u64; 7]) -> String {
+        let mut data = vec![log_type];
+        for field in fields {
+            data.extend_from_slice(&field.to_le_bytes());
+        }
+        format!("{RAY_LOG_PREFIX}{}", BASE64.encode(data))
+    }
+
+    const AMM: &str = "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8";
+    const ROUTER: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+    const TOKEN: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+
+    /// The log lines of an AMM V4 swap invoked at `height` and logging `record`.
+    fn swap_logs(height: usize, record: String) -> Vec<String> {
+        vec![
+            format!("Program {AMM} invoke [{height}]"),
+            record,
+            format!("Program {TOKEN} invoke [{}]", height + 1),
+            "Program log: Instruction: Transfer".to_string(),
+            format!("Program {TOKEN} success"),
+            format!("Program {AMM} consumed 30000 of 200000 compute units"),
+            format!("Program {AMM} success"),
+        ]
+    }
+
+    #[test]
+    fn test_parse_and_find() {
+        let mut logs = swap_logs(
+            1,
+            ray_log(
+                SWAP_BASE_IN,
+                [1_000, 900, 2, 5_000, 100_000, 200_000, 1_990],
+            ),
+        );
+        logs.extend(swap_logs(
+            1,
+            ray_log(
+                SWAP_BASE_OUT,
+                [3_100, 6_000, 1, 9_000, 99_000, 201_990, 3_010],
+            ),
+        ));
+        // Deposits and truncated records are skipped
+        logs.push(format!("Program {AMM} invoke [1]"));
+        logs.push(ray_log(1, [0; 7]));
+        logs.push(format!("{RAY_LOG_PREFIX}AwE="));
+        logs.push(format!("Program {AMM} success"));
+
+        let base_in = RaySwapLog::find(&logs, &[0], true, (1_000, 900)).unwrap();
+        assert_eq!((base_in.amount_in, base_in.amount_out), (1_000, 1_990));
+        assert!(base_in.coin_to_pc);
+        assert_eq!((base_in.pool_coin, base_in.pool_pc), (100_000, 200_000));
+
+        let base_out = RaySwapLog::find(&logs, &[1], false, (3_100, 6_000)).unwrap();
+        assert_eq!((base_out.amount_in, base_out.amount_out), (3_010, 6_000));
+        assert!(!base_out.coin_to_pc);
+
+        assert!(RaySwapLog::find(&logs, &[0], true, (1_000, 901)).is_none());
+        assert!(RaySwapLog::find(&logs, &[1], true, (1_000, 900)).is_none());
+        assert!(RaySwapLog::find(&logs, &[3], true, (1_000, 900)).is_none());
+        assert!(RaySwapLog::parse(&logs[0]).is_none());
+        assert!(RaySwapLog::parse(&logs[15]).is_none());
+        assert!(RaySwapLog::parse(&logs[16]).is_none());
+    }
+
+    #[test]
+    fn test_find_scoped_to_instruction() {
+        // A route swapping twice through AMM V4 with the same limits
+        let first = ray_log(SWAP_BASE_IN, [1_000, 1, 2, 5_000, 100_000, 200_000, 1_990]);
+        let second = ray_log(SWAP_BASE_IN, [1_000, 1, 2, 5_000, 101_000, 198_010, 1_950]);
+        let mut logs = vec![
+            "Program ComputeBudget111111111111111111111111111111 invoke [1]".to_string(),
+            "Program ComputeBudget111111111111111111111111111111 success".to_string(),
+            format!("Program {ROUTER} invoke [1]"),
+            "Program log: Instruction: Route".to_string(),
+        ];
+        logs.extend(swap_logs(2, first));
+        logs.extend(swap_logs(2, second));
+        logs.push(format!("Program {ROUTER} success"));
+
+        let find = |path: &[u8]| RaySwapLog::find(&logs, path, true, (1_000, 1));
+        assert_eq!(find(&[1, 0]).unwrap().amount_out, 1_990);
+        assert_eq!(find(&[1, 1]).unwrap().amount_out, 1_950);
+        // Neither the route nor the swap's transfer logs a record
+        assert!(find(&[1]).is_none());
+        assert!(find(&[1, 0, 0]).is_none());
+    }
+}
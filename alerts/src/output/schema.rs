@@ -0,0 +1,601 @@
+//! Versioned JSON layouts of events delivered to webhooks.
+//!
+//! Webhook JSON payloads carry a `schema_version`, and each webhook picks the
+//! version it is served (`WEBHOOK_SCHEMA_VERSION`, or `schema_version` of a
+//! webhooks file entry or rule destination):
+//!
+//! - `1` - The original swap layout, frozen: event type, protocol, signature,
+//!   pool, input and output token (mint, symbol, decimals, raw and decimal
+//!   amount, USD value), direction, fee, maker, market cap, slot, timestamp,
+//!   and the stream `seq` of sequenced webhooks. Fields added to [`SwapEvent`]
+//!   never appear in it, and its event types, protocols, and directions are
+//!   its own enums ([`EventTypeV1`], [`ProtocolV1`], [`SwapDirectionV1`]):
+//!   event types added later are written as `other`.
+//! - `2` (default) - Every field of [`SwapEvent`]: side, labels, provenance,
+//!   enrichment, and the details of new pools, admin actions, MEV, drains,
+//!   summaries, digests, and rule-emitted events.
+//!
+//! A change removing, renaming, or retyping a field of the latest layout adds
+//! a new version instead; new optional fields may be added to it. [`decode`]
+//! reads every version, and payloads without `schema_version` (sent before
+//! versioning) as version 2.
+
+use {
+    super::{EventType, Protocol, SwapDirection, SwapEvent, TokenInfo},
+    serde::{Deserialize, Serialize},
+    std::{env, fmt},
+};
+
+/// Errors that can occur while decoding a versioned event.
+#[derive(Debug)]
+pub enum SchemaError {
+    /// The payload is not valid JSON or does not match its layout
+    Parse(String),
+    /// The payload has an unknown `schema_version`
+    Invalid(String),
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(e) => write!(f, "failed to parse event: {e}"),
+            Self::Invalid(e) => write!(f, "invalid event: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// JSON layout version of webhook events.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Deserialize)]
+#[serde(try_from = "u64")]
+pub enum SchemaVersion {
+    /// The original swap layout ([`SwapEventV1`])
+    V1,
+    /// Every field of [`SwapEvent`]
+    #[default]
+    V2,
+}
+
+impl SchemaVersion {
+    /// Reads the default version of JSON webhooks.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `WEBHOOK_SCHEMA_VERSION` - Optional: `1` or `2` (default: 2); invalid
+    ///   values are logged and ignored
+    pub fn from_env() -> Self {
+        match env::var("WEBHOOK_SCHEMA_VERSION") {
+            Ok(value) if !value.trim().is_empty() => Self::parse(&value).unwrap_or_else(|| {
+                log::warn!("Invalid WEBHOOK_SCHEMA_VERSION '{value}', expected 1 or 2");
+                Self::default()
+            }),
+            _ => Self::default(),
+        }
+    }
+
+    /// Returns the version number written as `schema_version`.
+    pub fn number(self) -> u64 {
+        match self {
+            Self::V1 => 1,
+            Self::V2 => 2,
+        }
+    }
+
+    /// Parses a version number, with or without a `v` prefix (e.g., `1`, `v2`).
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let number = value
+            .strip_prefix(['v', 'V'])
+            .unwrap_or(value)
+            .parse()
+            .ok()?;
+        Self::try_from(number).ok()
+    }
+
+    /// Serializes an event in this layout, `schema_version` first.
+    pub fn to_json(self, event: &SwapEvent) -> serde_json::Result<String> {
+        match self {
+            Self::V1 => serde_json::to_string(&Versioned {
+                schema_version: 1,
+                event: &SwapEventV1::from(event),
+            }),
+            Self::V2 => serde_json::to_string(&Versioned {
+                schema_version: 2,
+                event,
+            }),
+        }
+    }
+}
+
+impl TryFrom<u64> for SchemaVersion {
+    type Error = String;
+
+    fn try_from(number: u64) -> Result<Self, Self::Error> {
+        match number {
+            1 => Ok(Self::V1),
+            2 => Ok(Self::V2),
+            _ => Err(format!("unknown schema version {number}, expected 1 or 2")),
+        }
+    }
+}
+
+impl fmt::Display for SchemaVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}", self.number())
+    }
+}
+
+/// An event with its layout version.
+#[derive(Serialize)]
+struct Versioned<'a, T> {
+    schema_version: u64,
+    #[serde(flatten)]
+    event: &'a T,
+}
+
+/// Event type of a version 1 event: [`EventType`] as of version 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventTypeV1 {
+    Swap,
+    AddLiquidity,
+    RemoveLiquidity,
+    CreatePool,
+    Migrate,
+    Custom,
+    AdminAction,
+    VolumeSummary,
+    MevAlert,
+    LiquidityDrain,
+    ThrottleDigest,
+    /// An event type added after version 1
+    Other,
+}
+
+impl From<EventType> for EventTypeV1 {
+    fn from(event_type: EventType) -> Self {
+        match event_type {
+            EventType::Swap => Self::Swap,
+            EventType::AddLiquidity => Self::AddLiquidity,
+            EventType::RemoveLiquidity => Self::RemoveLiquidity,
+            EventType::CreatePool => Self::CreatePool,
+            EventType::Migrate => Self::Migrate,
+            EventType::Custom => Self::Custom,
+            EventType::AdminAction => Self::AdminAction,
+            EventType::VolumeSummary => Self::VolumeSummary,
+            EventType::MevAlert => Self::MevAlert,
+            EventType::LiquidityDrain => Self::LiquidityDrain,
+            EventType::ThrottleDigest => Self::ThrottleDigest,
+            EventType::LpBurn | EventType::CollectFees => Self::Other,
+        }
+    }
+}
+
+impl TryFrom<EventTypeV1> for EventType {
+    type Error = String;
+
+    fn try_from(event_type: EventTypeV1) -> Result<Self, Self::Error> {
+        Ok(match event_type {
+            EventTypeV1::Swap => Self::Swap,
+            EventTypeV1::AddLiquidity => Self::AddLiquidity,
+            EventTypeV1::RemoveLiquidity => Self::RemoveLiquidity,
+            EventTypeV1::CreatePool => Self::CreatePool,
+            EventTypeV1::Migrate => Self::Migrate,
+            EventTypeV1::Custom => Self::Custom,
+            EventTypeV1::AdminAction => Self::AdminAction,
+            EventTypeV1::VolumeSummary => Self::VolumeSummary,
+            EventTypeV1::MevAlert => Self::MevAlert,
+            EventTypeV1::LiquidityDrain => Self::LiquidityDrain,
+            EventTypeV1::ThrottleDigest => Self::ThrottleDigest,
+            EventTypeV1::Other => return Err("event type `other` is not decodable".to_string()),
+        })
+    }
+}
+
+/// Protocol of a version 1 event: [`Protocol`] as of version 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtocolV1 {
+    Cpmm,
+    Clmm,
+    AmmV4,
+    Launchpad,
+    PumpFun,
+    PumpSwap,
+}
+
+impl From<Protocol> for ProtocolV1 {
+    fn from(protocol: Protocol) -> Self {
+        match protocol {
+            Protocol::Cpmm => Self::Cpmm,
+            Protocol::Clmm => Self::Clmm,
+            Protocol::AmmV4 => Self::AmmV4,
+            Protocol::Launchpad => Self::Launchpad,
+            Protocol::PumpFun => Self::PumpFun,
+            Protocol::PumpSwap => Self::PumpSwap,
+        }
+    }
+}
+
+impl From<ProtocolV1> for Protocol {
+    fn from(protocol: ProtocolV1) -> Self {
+        match protocol {
+            ProtocolV1::Cpmm => Self::Cpmm,
+            ProtocolV1::Clmm => Self::Clmm,
+            ProtocolV1::AmmV4 => Self::AmmV4,
+            ProtocolV1::Launchpad => Self::Launchpad,
+            ProtocolV1::PumpFun => Self::PumpFun,
+            ProtocolV1::PumpSwap => Self::PumpSwap,
+        }
+    }
+}
+
+/// Swap direction of a version 1 event: [`SwapDirection`] as of version 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapDirectionV1 {
+    ExactInput,
+    ExactOutput,
+    Unknown,
+}
+
+impl From<SwapDirection> for SwapDirectionV1 {
+    fn from(direction: SwapDirection) -> Self {
+        match direction {
+            SwapDirection::ExactInput => Self::ExactInput,
+            SwapDirection::ExactOutput => Self::ExactOutput,
+            SwapDirection::Unknown => Self::Unknown,
+        }
+    }
+}
+
+impl From<SwapDirectionV1> for SwapDirection {
+    fn from(direction: SwapDirectionV1) -> Self {
+        match direction {
+            SwapDirectionV1::ExactInput => Self::ExactInput,
+            SwapDirectionV1::ExactOutput => Self::ExactOutput,
+            SwapDirectionV1::Unknown => Self::Unknown,
+        }
+    }
+}
+
+/// Token of a version 1 event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenInfoV1 {
+    /// Token mint address
+    pub mint: String,
+    /// Token symbol, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    /// Token decimals, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<u8>,
+    /// Raw amount in smallest units
+    pub amount_raw: u64,
+    /// Amount in whole tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount: Option<f64>,
+    /// USD value of the amount
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub amount_usd: Option<f64>,
+}
+
+impl From<&TokenInfo> for TokenInfoV1 {
+    fn from(token: &TokenInfo) -> Self {
+        Self {
+            mint: token.mint.clone(),
+            symbol: token.symbol.clone(),
+            decimals: token.decimals,
+            amount_raw: token.amount_raw,
+            amount: token.amount,
+            amount_usd: token.amount_usd,
+        }
+    }
+}
+
+impl From<TokenInfoV1> for TokenInfo {
+    fn from(token: TokenInfoV1) -> Self {
+        Self {
+            mint: token.mint,
+            symbol: token.symbol,
+            decimals: token.decimals,
+            amount_raw: token.amount_raw,
+            amount: token.amount,
+            amount_usd: token.amount_usd,
+        }
+    }
+}
+
+/// Version 1 layout of an event.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SwapEventV1 {
+    /// Event type
+    pub event_type: EventTypeV1,
+    /// Protocol that emitted the event
+    pub protocol: ProtocolV1,
+    /// Transaction signature
+    pub signature: String,
+    /// Pool or AMM address
+    pub pool: String,
+    /// Input token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input_token: Option<TokenInfoV1>,
+    /// Output token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_token: Option<TokenInfoV1>,
+    /// Swap direction
+    pub direction: SwapDirectionV1,
+    /// Trading fee in raw token units
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee: Option<u64>,
+    /// Maker address
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maker: Option<String>,
+    /// Market cap of the non-base token
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub market_cap_usd: Option<f64>,
+    /// Block slot
+    pub slot: u64,
+    /// Unix timestamp
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<i64>,
+    /// Stream sequence number of sequenced webhooks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seq: Option<u64>,
+}
+
+impl From<&SwapEvent> for SwapEventV1 {
+    fn from(event: &SwapEvent) -> Self {
+        Self {
+            event_type: event.event_type.into(),
+            protocol: event.protocol.into(),
+            signature: event.signature.clone(),
+            pool: event.pool.clone(),
+            input_token: event.input_token.as_ref().map(TokenInfoV1::from),
+            output_token: event.output_token.as_ref().map(TokenInfoV1::from),
+            direction: event.direction.into(),
+            fee: event.fee,
+            maker: event.maker.clone(),
+            market_cap_usd: event.market_cap_usd,
+            slot: event.slot,
+            timestamp: event.timestamp,
+            seq: event.seq,
+        }
+    }
+}
+
+impl TryFrom<SwapEventV1> for SwapEvent {
+    type Error = String;
+
+    /// Converts a version 1 event; fields it lacks are left empty, the event
+    /// ID and trade side are derived as for decoded events.
+    ///
+    /// Fails for events of a type added after version 1 (`other`).
+    fn try_from(event: SwapEventV1) -> Result<Self, Self::Error> {
+        let mut builder = SwapEvent::builder()
+            .event_type(event.event_type.try_into()?)
+            .protocol(event.protocol.into())
+            .signature(event.signature)
+            .pool(event.pool)
+            .direction(event.direction.into())
+            .slot(event.slot);
+        if let Some(token) = event.input_token {
+            builder = builder.input_token(token.into());
+        }
+        if let Some(token) = event.output_token {
+            builder = builder.output_token(token.into());
+        }
+        if let Some(fee) = event.fee {
+            builder = builder.fee(fee);
+        }
+        if let Some(maker) = event.maker {
+            builder = builder.maker(maker);
+        }
+        if let Some(market_cap) = event.market_cap_usd {
+            builder = builder.market_cap_usd(market_cap);
+        }
+        if let Some(timestamp) = event.timestamp {
+            builder = builder.timestamp(timestamp);
+        }
+        let mut converted = builder.build();
+        converted.seq = event.seq;
+        Ok(converted)
+    }
+}
+
+/// Decodes a webhook event of any version.
+///
+/// Payloads without `schema_version` are read as version 2.
+#[allow(dead_code)]
+pub fn decode(json: &str) -> Result<SwapEvent, SchemaError> {
+    let mut value: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| SchemaError::Parse(e.to_string()))?;
+    let version = match value
+        .as_object_mut()
+        .and_then(|fields| fields.remove("schema_version"))
+    {
+        Some(number) => number
+            .as_u64()
+            .ok_or_else(|| format!("schema_version {number} is not a version number"))
+            .and_then(SchemaVersion::try_from)
+            .map_err(SchemaError::Invalid)?,
+        None => SchemaVersion::V2,
+    };
+
+    let parse = |e: serde_json::Error| SchemaError::Parse(format!("{version} layout: {e}"));
+    match version {
+        SchemaVersion::V1 => serde_json::from_value::<SwapEventV1>(value)
+            .map_err(parse)?
+            .try_into()
+            .map_err(|e| SchemaError::Parse(format!("{version} layout: {e}"))),
+        SchemaVersion::V2 => serde_json::from_value(value).map_err(parse),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{CustomEvent, TradeSide},
+    };
+
+    fn event() -> SwapEvent {
+        let mut event = SwapEvent::builder()
+            .protocol(Protocol::Clmm)
+            .signature("sig")
+            .pool("PoolA")
+            .input_token(
+                TokenInfo::new("So11111111111111111111111111111111111111112", 2_000_000_000)
+                    .with_decimals(9)
+                    .with_usd_value(300.0),
+            )
+            .output_token(TokenInfo::new("BonkMint", 150_000).with_symbol("BONK"))
+            .direction(SwapDirection::ExactInput)
+            .maker("Maker")
+            .slot(42)
+            .timestamp(1_700_000_000)
+            .build();
+        event.maker_label = Some("whale".to_string());
+        event.seq = Some(7);
+        event.custom = Some(CustomEvent {
+            name: "whale_buy".to_string(),
+            rule: "whales".to_string(),
+            description: "whale bought".to_string(),
+            source_type: EventType::Swap,
+        });
+        event
+    }
+
+    #[test]
+    fn test_v1_layout_is_frozen() {
+        let json = SchemaVersion::V1.to_json(&event()).unwrap();
+        assert_eq!(
+            json,
+            "{\"schema_version\":1,\"event_type\":\"swap\",\"protocol\":\"clmm\",\
+             \"signature\":\"sig\",\"pool\":\"PoolA\",\"input_token\":{\"mint\":\
+             \"So11111111111111111111111111111111111111112\",\"decimals\":9,\
+             \"amount_raw\":2000000000,\"amount\":2.0,\"amount_usd\":300.0},\
+             \"output_token\":{\"mint\":\"BonkMint\",\"symbol\":\"BONK\",\
+             \"amount_raw\":150000},\"direction\":\"exact_input\",\"maker\":\"Maker\",\
+             \"slot\":42,\"timestamp\":1700000000,\"seq\":7}"
+        );
+
+        let decoded = decode(&json).unwrap();
+        assert_eq!(SwapEventV1::from(&decoded), SwapEventV1::from(&event()));
+        assert_eq!(decoded.side, Some(TradeSide::Buy));
+        assert_eq!(decoded.maker_label, None);
+        assert!(decoded.custom.is_none());
+    }
+
+    #[test]
+    fn test_v1_enums_are_frozen() {
+        let mut event = SwapEvent::builder()
+            .event_type(EventType::MevAlert)
+            .protocol(Protocol::PumpSwap)
+            .signature("sig")
+            .pool("PoolB")
+            .direction(SwapDirection::ExactOutput)
+            .slot(43)
+            .build();
+        let json = SchemaVersion::V1.to_json(&event).unwrap();
+        assert_eq!(
+            json,
+            "{\"schema_version\":1,\"event_type\":\"mev_alert\",\"protocol\":\
+             \"pump_swap\",\"signature\":\"sig\",\"pool\":\"PoolB\",\"direction\":\
+             \"exact_output\",\"slot\":43}"
+        );
+        assert_eq!(decode(&json).unwrap().event_type, EventType::MevAlert);
+
+        // Event types added after version 1 are written as `other`
+        event.event_type = EventType::CollectFees;
+        let json = SchemaVersion::V1.to_json(&event).unwrap();
+        assert!(json.contains("\"event_type\":\"other\""));
+        assert!(matches!(decode(&json), Err(SchemaError::Parse(_))));
+
+        let event_types = [
+            EventType::Swap,
+            EventType::AddLiquidity,
+            EventType::RemoveLiquidity,
+            EventType::CreatePool,
+            EventType::Migrate,
+            EventType::Custom,
+            EventType::AdminAction,
+            EventType::VolumeSummary,
+            EventType::MevAlert,
+            EventType::LiquidityDrain,
+            EventType::ThrottleDigest,
+            EventType::LpBurn,
+        ];
+        assert_eq!(
+            serde_json::to_value(event_types.map(EventTypeV1::from)).unwrap(),
+            serde_json::json!([
+                "swap",
+                "add_liquidity",
+                "remove_liquidity",
+                "create_pool",
+                "migrate",
+                "custom",
+                "admin_action",
+                "volume_summary",
+                "mev_alert",
+                "liquidity_drain",
+                "throttle_digest",
+                "other",
+            ])
+        );
+        let protocols = [
+            Protocol::Cpmm,
+            Protocol::Clmm,
+            Protocol::AmmV4,
+            Protocol::Launchpad,
+            Protocol::PumpFun,
+            Protocol::PumpSwap,
+        ];
+        assert_eq!(
+            serde_json::to_value(protocols.map(ProtocolV1::from)).unwrap(),
+            serde_json::json!([
+                "cpmm",
+                "clmm",
+                "amm_v4",
+                "launchpad",
+                "pump_fun",
+                "pump_swap"
+            ])
+        );
+    }
+
+    #[test]
+    fn test_v2_round_trip() {
+        let event = event();
+        let json = SchemaVersion::V2.to_json(&event).unwrap();
+        assert!(json.starts_with("{\"schema_version\":2,"));
+
+        let decoded = decode(&json).unwrap();
+        assert_eq!(
+            serde_json::to_value(&decoded).unwrap(),
+            serde_json::to_value(&event).unwrap()
+        );
+
+        // Payloads sent before versioning are version 2 without the tag
+        let unversioned = serde_json::to_string(&event).unwrap();
+        assert_eq!(decode(&unversioned).unwrap().maker_label.unwrap(), "whale");
+    }
+
+    #[test]
+    fn test_schema_versions() {
+        assert_eq!(SchemaVersion::parse("1"), Some(SchemaVersion::V1));
+        assert_eq!(SchemaVersion::parse(" v2 "), Some(SchemaVersion::V2));
+        assert_eq!(SchemaVersion::parse("3"), None);
+        assert_eq!(SchemaVersion::default(), SchemaVersion::V2);
+
+        assert!(matches!(
+            decode("{\"schema_version\":3,\"slot\":1}"),
+            Err(SchemaError::Invalid(_))
+        ));
+        assert!(matches!(
+            decode("{\"schema_version\":1,\"slot\":1}"),
+            Err(SchemaError::Parse(_))
+        ));
+    }
+}
@@ -0,0 +1,308 @@
+//! Per-stream event sequence numbers.
+//!
+//! Every event queued for a sink stream (one per webhook, keyed by its name)
+//! gets the stream's next `seq`, starting at 1. A consumer seeing a jump in
+//! `seq` has missed deliveries and can fetch them with
+//! `GET /streams/{stream}/events?from_seq=<seq>` while they are still retained.
+//!
+//! Sequence numbers survive restarts: blocks of [`RESERVE_BLOCK`] numbers are
+//! reserved in the state file before use, and a restarted process continues
+//! above the last reservation. A restart can therefore skip up to one block;
+//! such a jump shows up as `first_seq` of the backfill response.
+
+use {
+    super::swap_event::SwapEvent,
+    serde::{Deserialize, Serialize},
+    std::{
+        collections::{BTreeMap, HashMap, VecDeque},
+        env, fmt, fs, io,
+        path::{Path, PathBuf},
+        sync::{Mutex, PoisonError},
+    },
+};
+
+/// Sequence numbers reserved in the state file at a time.
+pub const RESERVE_BLOCK: u64 = 1_000;
+
+/// Default number of events retained per stream for backfill.
+const DEFAULT_BACKFILL: usize = 10_000;
+
+/// Errors loading or saving the sequence state file.
+#[derive(Debug)]
+pub enum SequenceError {
+    /// The state file could not be read
+    Read(String),
+    /// The state file is not valid JSON
+    Parse(String),
+    /// The state file could not be written
+    Write(String),
+}
+
+impl fmt::Display for SequenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(e) => write!(f, "failed to read sequence file: {e}"),
+            Self::Parse(e) => write!(f, "failed to parse sequence file: {e}"),
+            Self::Write(e) => write!(f, "failed to write sequence file: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for SequenceError {}
+
+/// Configuration for stream sequence numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SequenceConfig {
+    /// State file holding the reserved sequence numbers per stream
+    pub path: PathBuf,
+    /// Events retained per stream for backfill
+    pub backfill: usize,
+}
+
+impl SequenceConfig {
+    /// Creates sequencing configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `SEQUENCE_FILE` - State file of the per-stream sequence numbers (e.g., `sequences.json`)
+    /// - `SEQUENCE_BACKFILL` - Optional: Events retained per stream for backfill (default: 10000)
+    ///
+    /// # Returns
+    ///
+    /// `Some(SequenceConfig)` if `SEQUENCE_FILE` is set, `None` otherwise.
+    pub fn from_env() -> Option<Self> {
+        let path = env::var("SEQUENCE_FILE")
+            .ok()
+            .filter(|path| !path.trim().is_empty())?;
+        let backfill = env::var("SEQUENCE_BACKFILL")
+            .ok()
+            .and_then(|n| n.trim().parse().ok())
+            .unwrap_or(DEFAULT_BACKFILL);
+        Some(Self {
+            path: PathBuf::from(path.trim()),
+            backfill,
+        })
+    }
+}
+
+/// Contents of the state file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SequenceState {
+    /// Highest reserved sequence number per stream
+    #[serde(default)]
+    reserved: BTreeMap<String, u64>,
+}
+
+/// Sequence state and retained events of one stream.
+#[derive(Debug)]
+struct Stream {
+    /// Sequence number of the next event
+    next: u64,
+    /// Recent events, oldest first
+    recent: VecDeque<SwapEvent>,
+}
+
+/// Events of a stream starting at a requested sequence number.
+#[derive(Debug, Serialize)]
+pub struct Backfill {
+    /// Stream name
+    pub stream: String,
+    /// Oldest sequence number still retained
+    pub first_seq: u64,
+    /// Sequence number the next event will get
+    pub next_seq: u64,
+    /// Retained events from the requested sequence number, oldest first
+    pub events: Vec<SwapEvent>,
+}
+
+/// Assigns per-stream sequence numbers and retains recent events for backfill.
+pub struct Sequencer {
+    path: PathBuf,
+    backfill: usize,
+    inner: Mutex<(SequenceState, HashMap<String, Stream>)>,
+}
+
+impl Sequencer {
+    /// Opens the sequencer, continuing from the reservations in the state file.
+    ///
+    /// A missing state file starts every stream at 1.
+    pub fn open(config: SequenceConfig) -> Result<Self, SequenceError> {
+        let state = match fs::read_to_string(&config.path) {
+            Ok(json) => {
+                serde_json::from_str(&json).map_err(|e| SequenceError::Parse(e.to_string()))?
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => SequenceState::default(),
+            Err(e) => {
+                return Err(SequenceError::Read(format!(
+                    "{}: {e}",
+                    config.path.display()
+                )))
+            }
+        };
+        Ok(Self {
+            path: config.path,
+            backfill: config.backfill,
+            inner: Mutex::new((state, HashMap::new())),
+        })
+    }
+
+    /// Assigns the stream's next sequence number to `event` and retains a copy.
+    pub fn assign(&self, stream: &str, event: &mut SwapEvent) {
+        let mut inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        let (state, streams) = &mut *inner;
+        let reserved = state.reserved.get(stream).copied().unwrap_or(0);
+        let entry = streams.entry(stream.to_string()).or_insert_with(|| Stream {
+            next: reserved + 1,
+            recent: VecDeque::new(),
+        });
+
+        let seq = entry.next;
+        entry.next += 1;
+        if seq > reserved {
+            state
+                .reserved
+                .insert(stream.to_string(), seq + RESERVE_BLOCK - 1);
+            if let Err(e) = save(&self.path, state) {
+                log::warn!("{e}");
+            }
+        }
+
+        event.seq = Some(seq);
+        if self.backfill > 0 {
+            if entry.recent.len() == self.backfill {
+                entry.recent.pop_front();
+            }
+            entry.recent.push_back(event.clone());
+        }
+    }
+
+    /// Returns up to `limit` retained events of a stream with `seq >= from_seq`.
+    ///
+    /// Returns `None` for a stream without events since startup.
+    pub fn backfill(&self, stream: &str, from_seq: u64, limit: usize) -> Option<Backfill> {
+        let inner = self.inner.lock().unwrap_or_else(PoisonError::into_inner);
+        let entry = inner.1.get(stream)?;
+        let first_seq = entry
+            .recent
+            .front()
+            .and_then(|event| event.seq)
+            .unwrap_or(entry.next);
+        let events = entry
+            .recent
+            .iter()
+            .filter(|event| event.seq.is_some_and(|seq| seq >= from_seq))
+            .take(limit)
+            .cloned()
+            .collect();
+        Some(Backfill {
+            stream: stream.to_string(),
+            first_seq,
+            next_seq: entry.next,
+            events,
+        })
+    }
+}
+
+/// Writes the state file atomically.
+fn save(path: &Path, state: &SequenceState) -> Result<(), SequenceError> {
+    let json =
+        serde_json::to_string_pretty(state).map_err(|e| SequenceError::Write(e.to_string()))?;
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, json)
+        .and_then(|_| fs::rename(&tmp, path))
+        .map_err(|e| SequenceError::Write(format!("{}: {e}", path.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::output::Protocol};
+
+    fn event() -> SwapEvent {
+        SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .build()
+    }
+
+    /// Returns an empty directory for one test.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!(
+            "raydium-alerts-sequence-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn config(dir: &Path, backfill: usize) -> SequenceConfig {
+        SequenceConfig {
+            path: dir.join("sequences.json"),
+            backfill,
+        }
+    }
+
+    fn assign(sequencer: &Sequencer, stream: &str) -> u64 {
+        let mut event = event();
+        sequencer.assign(stream, &mut event);
+        event.seq.unwrap()
+    }
+
+    #[test]
+    fn test_streams_are_numbered_independently() {
+        let dir = test_dir("streams");
+        let sequencer = Sequencer::open(config(&dir, 10)).unwrap();
+        assert_eq!(assign(&sequencer, "discord"), 1);
+        assert_eq!(assign(&sequencer, "discord"), 2);
+        assert_eq!(assign(&sequencer, "ledger"), 1);
+        assert_eq!(assign(&sequencer, "discord"), 3);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_restart_continues_above_reservation() {
+        let dir = test_dir("restart");
+        let sequencer = Sequencer::open(config(&dir, 10)).unwrap();
+        assert_eq!(assign(&sequencer, "ledger"), 1);
+        assert_eq!(assign(&sequencer, "ledger"), 2);
+        drop(sequencer);
+
+        let sequencer = Sequencer::open(config(&dir, 10)).unwrap();
+        assert_eq!(assign(&sequencer, "ledger"), RESERVE_BLOCK + 1);
+        let backfill = sequencer.backfill("ledger", 0, 10).unwrap();
+        assert_eq!(backfill.first_seq, RESERVE_BLOCK + 1);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_backfill_from_seq() {
+        let dir = test_dir("backfill");
+        let sequencer = Sequencer::open(config(&dir, 3)).unwrap();
+        for _ in 0..5 {
+            assign(&sequencer, "ledger");
+        }
+
+        let backfill = sequencer.backfill("ledger", 4, 10).unwrap();
+        assert_eq!(backfill.first_seq, 3);
+        assert_eq!(backfill.next_seq, 6);
+        let seqs: Vec<_> = backfill.events.iter().map(|event| event.seq).collect();
+        assert_eq!(seqs, [Some(4), Some(5)]);
+
+        assert_eq!(sequencer.backfill("ledger", 1, 1).unwrap().events.len(), 1);
+        assert!(sequencer.backfill("unknown", 1, 10).is_none());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_invalid_state_file() {
+        let dir = test_dir("invalid");
+        let config = config(&dir, 10);
+        fs::write(&config.path, "{").unwrap();
+        assert!(matches!(
+            Sequencer::open(config),
+            Err(SequenceError::Parse(_))
+        ));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
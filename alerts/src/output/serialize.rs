@@ -0,0 +1,255 @@
+//! Serialization worker pools of the output sinks.
+//!
+//! Rendering events (JSON layouts, payload templates, text alerts) is CPU
+//! work. Run on the processor or delivery tasks, it shares the async runtime
+//! with ingest, and formatting large batches during bursts delays block
+//! processing. Each sink therefore owns a [`SerializePool`] of worker threads:
+//!
+//! - [`EventLog`] formats the console lines of emitted events, and the lines
+//!   of the NDJSON event file if enabled (`LOG_SERIALIZE_WORKERS`).
+//! - Each webhook renders its request bodies, splitting a batch across its
+//!   workers (`WEBHOOK_SERIALIZE_WORKERS`).
+//!
+//! A pool without workers runs jobs on the calling task. A full queue also
+//! runs them there, which slows the caller down instead of dropping output.
+
+use {
+    super::{EventFile, EventFileConfig, OutputFormat, SwapEvent},
+    crate::server::unix_now,
+    std::{
+        env,
+        panic::{self, AssertUnwindSafe},
+        sync::{mpsc, Arc, Mutex, PoisonError},
+        thread,
+    },
+    tokio::sync::oneshot,
+};
+
+/// Jobs queued per pool before they run on the calling task.
+const QUEUE_CAPACITY: usize = 10_000;
+
+/// A unit of serialization work.
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Worker threads serializing the events of one sink.
+pub struct SerializePool {
+    /// Number of running workers
+    workers: usize,
+    /// Queue shared by the workers; `None` without workers
+    tx: Option<mpsc::SyncSender<Job>>,
+}
+
+impl SerializePool {
+    /// Spawns `workers` threads named `<name>-serialize-<n>`.
+    ///
+    /// Workers exit once the pool is dropped and its queue is drained.
+    pub fn new(name: &str, workers: usize) -> Self {
+        let (tx, rx) = mpsc::sync_channel::<Job>(QUEUE_CAPACITY);
+        let rx = Arc::new(Mutex::new(rx));
+        let mut spawned = 0;
+        for n in 0..workers {
+            let rx = rx.clone();
+            let worker = thread::Builder::new()
+                .name(format!("{name}-serialize-{n}"))
+                .spawn(move || loop {
+                    let job = rx.lock().unwrap_or_else(PoisonError::into_inner).recv();
+                    let Ok(job) = job else {
+                        return;
+                    };
+                    if panic::catch_unwind(AssertUnwindSafe(job)).is_err() {
+                        log::error!("Serialization job panicked");
+                    }
+                });
+            if let Err(e) = worker {
+                log::error!("Failed to spawn {name} serialization worker: {e}");
+                break;
+            }
+            spawned += 1;
+        }
+        Self {
+            workers: spawned,
+            tx: (spawned > 0).then_some(tx),
+        }
+    }
+
+    /// Returns the number of worker threads.
+    pub fn workers(&self) -> usize {
+        self.workers
+    }
+
+    /// Queues a job for the workers, or runs it on the calling task if the
+    /// pool has no workers or its queue is full.
+    pub fn execute(&self, job: impl FnOnce() + Send + 'static) {
+        let Some(ref tx) = self.tx else {
+            return job();
+        };
+        if let Err(mpsc::TrySendError::Full(job) | mpsc::TrySendError::Disconnected(job)) =
+            tx.try_send(Box::new(job))
+        {
+            job();
+        }
+    }
+
+    /// Queues a job and returns the receiver of its result.
+    fn submit<T: Send + 'static>(
+        &self,
+        job: impl FnOnce() -> T + Send + 'static,
+    ) -> oneshot::Receiver<T> {
+        let (tx, rx) = oneshot::channel();
+        self.execute(move || {
+            let _ = tx.send(job());
+        });
+        rx
+    }
+
+    /// Runs a job on a worker and waits for its result without blocking the
+    /// runtime.
+    ///
+    /// Returns `None` if the job panicked.
+    pub async fn run<T: Send + 'static>(
+        &self,
+        job: impl FnOnce() -> T + Send + 'static,
+    ) -> Option<T> {
+        self.submit(job).await.ok()
+    }
+
+    /// Maps items on the workers, one chunk per worker, keeping their order.
+    ///
+    /// Returns `None` if a job panicked.
+    pub async fn run_chunked<I, T, F>(&self, items: Vec<I>, f: F) -> Option<Vec<T>>
+    where
+        I: Send + 'static,
+        T: Send + 'static,
+        F: Fn(I) -> T + Send + Sync + 'static,
+    {
+        let size = items.len().div_ceil(self.workers.max(1)).max(1);
+        let f = Arc::new(f);
+        let mut items = items.into_iter();
+        let mut chunks = Vec::new();
+        loop {
+            let chunk: Vec<I> = items.by_ref().take(size).collect();
+            if chunk.is_empty() {
+                break;
+            }
+            let f = f.clone();
+            chunks.push(self.submit(move || chunk.into_iter().map(&*f).collect()));
+        }
+
+        let mut mapped = Vec::new();
+        for chunk in chunks {
+            mapped.extend::<Vec<T>>(chunk.await.ok()?);
+        }
+        Some(mapped)
+    }
+}
+
+/// Console sink logging emitted events in the output format.
+pub struct EventLog {
+    format: OutputFormat,
+    pool: SerializePool,
+    /// NDJSON file taking the events off the log stream, if enabled
+    file: Option<Arc<Mutex<EventFile>>>,
+}
+
+impl EventLog {
+    /// Creates the console sink with `workers` formatting threads.
+    ///
+    /// With more than one worker, lines of events emitted close together
+    /// may be logged out of order.
+    pub fn new(format: OutputFormat, workers: usize) -> Self {
+        Self {
+            format,
+            pool: SerializePool::new("log", workers),
+            file: None,
+        }
+    }
+
+    /// Writes events to an NDJSON file. With a JSON output format, events are
+    /// no longer logged; text lines are still logged.
+    pub fn with_file(mut self, file: EventFile) -> Self {
+        self.file = Some(Arc::new(Mutex::new(file)));
+        self
+    }
+
+    /// Creates the console sink from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `LOG_SERIALIZE_WORKERS` - Optional: Threads formatting logged events, 0 formats
+    ///   them on the processor task (default: 1)
+    /// - `EVENT_FILE_DIR`, `EVENT_FILE_ROTATION`, `EVENT_FILE_COMPRESS` - Optional: NDJSON
+    ///   event file (see [`EventFileConfig::from_env`])
+    pub fn from_env(format: OutputFormat) -> Self {
+        let workers = env::var("LOG_SERIALIZE_WORKERS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(1);
+        let log = Self::new(format, workers);
+        let Some(config) = EventFileConfig::from_env() else {
+            return log;
+        };
+        match EventFile::open(config) {
+            Ok(file) => {
+                log::info!("Event file enabled: {}", file.dir().display());
+                log.with_file(file)
+            }
+            Err(e) => {
+                log::error!("Failed to open EVENT_FILE_DIR, events stay in the log: {e}");
+                log
+            }
+        }
+    }
+
+    /// Logs an event under `target` (the caller's module path), in the span
+    /// of the caller (e.g., its slot and signature).
+    ///
+    /// With an event file, the event is written to it as a JSON line, and
+    /// logged only in text format.
+    pub fn log(&self, event: &SwapEvent, target: &'static str) {
+        let format = self.format;
+        let event = event.clone();
+        let span = tracing::Span::current();
+        let file = self.file.clone();
+        self.pool.execute(move || {
+            let console = match file {
+                Some(file) => {
+                    let line = event.format(OutputFormat::Json);
+                    let mut file = file.lock().unwrap_or_else(PoisonError::into_inner);
+                    if let Err(e) = file.write(&line, unix_now()) {
+                        log::warn!("Failed to write event to {}: {e}", file.dir().display());
+                    }
+                    format == OutputFormat::Text
+                }
+                None => true,
+            };
+            if console {
+                span.in_scope(|| log::info!(target: target, "{}", event.format(format)));
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_pool_runs_jobs_in_order() {
+        for workers in [0, 1, 3] {
+            let pool = SerializePool::new("test", workers);
+            assert_eq!(pool.workers(), workers);
+            assert_eq!(pool.run(|| 6 * 7).await, Some(42));
+            if workers > 0 {
+                assert_eq!(pool.run(|| -> u32 { panic!("boom") }).await, None);
+            }
+
+            let items: Vec<u32> = (0..10).collect();
+            let doubled = pool.run_chunked(items, |n| n * 2).await.unwrap();
+            assert_eq!(doubled, (0..10).map(|n| n * 2).collect::<Vec<_>>());
+            assert_eq!(
+                pool.run_chunked(Vec::<u32>::new(), |n| n).await,
+                Some(vec![])
+            );
+        }
+    }
+}
@@ -0,0 +1,423 @@
+//! Slot-aligned batch emission.
+//!
+//! Processors publish events as soon as their instructions are handled, so
+//! with several decoders and workers the events of consecutive slots arrive
+//! interleaved. When enabled, [`SlotBatcher`] holds every event until its slot
+//! is considered complete (a fixed delay after the slot's first event), then
+//! emits the whole slot at once: a slot header, followed by the events sorted
+//! by signature and position within the transaction. Slots are emitted in
+//! ascending order.
+//!
+//! An event arriving after its slot was emitted is published immediately as a
+//! batch of its own. On shutdown, every held slot is emitted right away.
+
+use {
+    super::swap_event::{OutputFormat, SwapEvent},
+    crate::clock,
+    serde::Serialize,
+    std::{
+        cmp::Ordering,
+        collections::BTreeMap,
+        env,
+        sync::{Arc, Mutex, PoisonError},
+        time::{Duration, Instant},
+    },
+    tokio::{
+        sync::{mpsc, watch},
+        task::JoinHandle,
+    },
+};
+
+/// Publishes one event through the processor that produced it.
+type Publish = Arc<dyn Fn(SwapEvent) + Send + Sync>;
+
+/// Configuration for slot-aligned batching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotBatchConfig {
+    /// How long after a slot's first event the slot is considered complete
+    pub delay: Duration,
+}
+
+impl SlotBatchConfig {
+    /// Creates slot batching configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `SLOT_BATCH_MS` - Optional: Milliseconds to wait after a slot's first event
+    ///   before emitting the slot's batch. Unset or `0` emits events immediately.
+    ///
+    /// # Returns
+    ///
+    /// `Some(SlotBatchConfig)` if batching is enabled, `None` otherwise.
+    pub fn from_env() -> Option<Self> {
+        let value = env::var("SLOT_BATCH_MS").ok()?;
+        let delay_ms = match value.trim().parse::<u64>() {
+            Ok(ms) => ms,
+            Err(_) => {
+                log::warn!("Invalid SLOT_BATCH_MS '{value}', slot batching disabled");
+                return None;
+            }
+        };
+        (delay_ms > 0).then(|| Self {
+            delay: Duration::from_millis(delay_ms),
+        })
+    }
+}
+
+/// Events of one slot waiting for the slot to complete.
+struct Batch<T> {
+    deadline: Instant,
+    events: Vec<(SwapEvent, T)>,
+}
+
+/// Pending batches keyed by slot.
+struct Batches<T> {
+    delay: Duration,
+    pending: BTreeMap<u64, Batch<T>>,
+    /// Highest slot emitted so far
+    emitted: Option<u64>,
+}
+
+impl<T> Batches<T> {
+    fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            pending: BTreeMap::new(),
+            emitted: None,
+        }
+    }
+
+    /// Adds an event to its slot's batch.
+    ///
+    /// Returns the event as a batch of its own if its slot was already emitted.
+    fn push(
+        &mut self,
+        event: SwapEvent,
+        payload: T,
+        now: Instant,
+    ) -> Option<(u64, Vec<(SwapEvent, T)>)> {
+        let slot = event.slot;
+        if self.emitted.is_some_and(|emitted| slot <= emitted) {
+            return Some((slot, vec![(event, payload)]));
+        }
+        self.pending
+            .entry(slot)
+            .or_insert_with(|| Batch {
+                deadline: now + self.delay,
+                events: Vec::new(),
+            })
+            .events
+            .push((event, payload));
+        None
+    }
+
+    /// Removes the complete batches, lowest slot first, with sorted events.
+    ///
+    /// A batch is only emitted once all lower slots are, so a slot whose
+    /// deadline passed may wait for an earlier one.
+    fn take_due(&mut self, now: Instant) -> Vec<(u64, Vec<(SwapEvent, T)>)> {
+        let mut due = Vec::new();
+        while let Some(entry) = self.pending.first_entry() {
+            if entry.get().deadline > now {
+                break;
+            }
+            let slot = *entry.key();
+            let mut events = entry.remove().events;
+            events.sort_by(|(a, _), (b, _)| event_order(a, b));
+            self.emitted = Some(slot);
+            due.push((slot, events));
+        }
+        due
+    }
+
+    /// Removes all batches regardless of their deadlines.
+    fn take_all(&mut self) -> Vec<(u64, Vec<(SwapEvent, T)>)> {
+        self.take_due(clock::now() + self.delay)
+    }
+
+    /// Returns when the lowest pending slot completes.
+    fn next_deadline(&self) -> Option<Instant> {
+        self.pending.values().next().map(|batch| batch.deadline)
+    }
+}
+
+/// Orders events of a slot by signature, then position within the transaction.
+fn event_order(a: &SwapEvent, b: &SwapEvent) -> Ordering {
+    a.signature
+        .cmp(&b.signature)
+        .then(
+            a.provenance
+                .instruction_index
+                .cmp(&b.provenance.instruction_index),
+        )
+        .then_with(|| a.provenance.inner_path.cmp(&b.provenance.inner_path))
+}
+
+/// Header emitted before a slot's events.
+#[derive(Debug, Serialize)]
+struct SlotHeader {
+    event_type: &'static str,
+    slot: u64,
+    events: usize,
+}
+
+/// Formats the header emitted before a slot's events.
+pub fn slot_header(slot: u64, events: usize, format: OutputFormat) -> String {
+    let header = SlotHeader {
+        event_type: "slot",
+        slot,
+        events,
+    };
+    match format {
+        OutputFormat::Text => format!("📦 SLOT {slot} ({events} events)"),
+        OutputFormat::Json => serde_json::to_string(&header).unwrap_or_default(),
+        OutputFormat::JsonPretty => serde_json::to_string_pretty(&header).unwrap_or_default(),
+    }
+}
+
+/// Buffers the events of every processor and emits them slot by slot.
+///
+/// Processors obtain a [`SlotBatchSender`] with [`SlotBatcher::sender`]; events
+/// are published through the sending processor once their slot is emitted.
+pub struct SlotBatcher {
+    tx: mpsc::UnboundedSender<(SwapEvent, Publish)>,
+    /// Tells the emission task to emit every held slot and stop
+    closing: watch::Sender<bool>,
+    task: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl SlotBatcher {
+    /// Creates a batcher and spawns its emission task.
+    ///
+    /// Slot headers are logged in `output_format`.
+    pub fn spawn(config: SlotBatchConfig, output_format: OutputFormat) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let (closing, closing_rx) = watch::channel(false);
+        let task = tokio::spawn(Self::run(
+            Batches::new(config.delay),
+            rx,
+            closing_rx,
+            output_format,
+        ));
+        Self {
+            tx,
+            closing,
+            task: Mutex::new(Some(task)),
+        }
+    }
+
+    /// Emits every held slot and waits for the emission task to stop.
+    ///
+    /// Events submitted afterwards are published immediately.
+    pub async fn shutdown(&self) {
+        self.closing.send_replace(true);
+        let task = self
+            .task
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+        if let Some(task) = task {
+            let _ = task.await;
+        }
+    }
+
+    /// Returns a sender whose events are published with `publish`.
+    pub fn sender(&self, publish: impl Fn(SwapEvent) + Send + Sync + 'static) -> SlotBatchSender {
+        SlotBatchSender {
+            tx: self.tx.clone(),
+            publish: Arc::new(publish),
+        }
+    }
+
+    /// Emission loop: collects events and emits batches as their slots complete.
+    async fn run(
+        mut batches: Batches<Publish>,
+        mut rx: mpsc::UnboundedReceiver<(SwapEvent, Publish)>,
+        mut closing: watch::Receiver<bool>,
+        output_format: OutputFormat,
+    ) {
+        loop {
+            let deadline = batches.next_deadline();
+            tokio::select! {
+                // Also fires if the batcher was dropped
+                _ = closing.changed() => {
+                    rx.close();
+                    while let Some((event, publish)) = rx.recv().await {
+                        batches.push(event, publish, clock::now());
+                    }
+                    break;
+                }
+                received = rx.recv() => {
+                    let Some((event, publish)) = received else {
+                        break;
+                    };
+                    if let Some(late) = batches.push(event, publish, clock::now()) {
+                        log::debug!("Late event for already emitted slot {}", late.0);
+                        Self::emit(late, output_format);
+                    }
+                }
+                _ = tokio::time::sleep_until(deadline.unwrap_or_else(clock::now).into()),
+                    if deadline.is_some() =>
+                {
+                    for batch in batches.take_due(clock::now()) {
+                        Self::emit(batch, output_format);
+                    }
+                }
+            }
+        }
+
+        for batch in batches.take_all() {
+            Self::emit(batch, output_format);
+        }
+    }
+
+    /// Logs the slot header and publishes the slot's events in order.
+    fn emit((slot, events): (u64, Vec<(SwapEvent, Publish)>), output_format: OutputFormat) {
+        log::info!("{}", slot_header(slot, events.len(), output_format));
+        for (event, publish) in events {
+            publish(event);
+        }
+    }
+}
+
+/// Submits a processor's events to the [`SlotBatcher`].
+#[derive(Clone)]
+pub struct SlotBatchSender {
+    tx: mpsc::UnboundedSender<(SwapEvent, Publish)>,
+    publish: Publish,
+}
+
+impl SlotBatchSender {
+    /// Holds an event until its slot is emitted.
+    ///
+    /// The event is published right away if the batcher has stopped.
+    pub fn submit(&self, event: SwapEvent) {
+        if let Err(mpsc::error::SendError((event, publish))) =
+            self.tx.send((event, self.publish.clone()))
+        {
+            publish(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::output::Protocol};
+
+    fn event(slot: u64, signature: &str, instruction_index: u32) -> SwapEvent {
+        let mut event = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature(signature)
+            .pool("pool")
+            .slot(slot)
+            .build();
+        event.provenance.instruction_index = instruction_index;
+        event
+    }
+
+    fn keys(batch: &(u64, Vec<(SwapEvent, ())>)) -> (u64, Vec<(String, u32)>) {
+        let events = batch
+            .1
+            .iter()
+            .map(|(event, _)| (event.signature.clone(), event.provenance.instruction_index))
+            .collect();
+        (batch.0, events)
+    }
+
+    #[test]
+    fn test_emits_complete_slots_sorted() {
+        let start = Instant::now();
+        let delay = Duration::from_millis(400);
+        let mut batches = Batches::new(delay);
+
+        assert!(batches.push(event(11, "b", 0), (), start).is_none());
+        assert!(batches.push(event(10, "z", 0), (), start).is_none());
+        assert!(batches.push(event(11, "a", 2), (), start).is_none());
+        assert!(batches.push(event(11, "a", 1), (), start).is_none());
+        assert_eq!(batches.next_deadline(), Some(start + delay));
+        assert!(batches.take_due(start).is_empty());
+
+        let due: Vec<_> = batches.take_due(start + delay).iter().map(keys).collect();
+        assert_eq!(
+            due,
+            [
+                (10, vec![("z".to_string(), 0)]),
+                (
+                    11,
+                    vec![
+                        ("a".to_string(), 1),
+                        ("a".to_string(), 2),
+                        ("b".to_string(), 0)
+                    ]
+                ),
+            ]
+        );
+        assert!(batches.next_deadline().is_none());
+    }
+
+    #[test]
+    fn test_lower_slot_holds_back_later_slots() {
+        let start = Instant::now();
+        let delay = Duration::from_millis(400);
+        let mut batches = Batches::new(delay);
+
+        batches.push(event(12, "a", 0), (), start);
+        batches.push(event(11, "b", 0), (), start + delay);
+
+        assert!(batches.take_due(start + delay).is_empty());
+        let slots: Vec<u64> = batches
+            .take_due(start + delay * 2)
+            .iter()
+            .map(|(slot, _)| *slot)
+            .collect();
+        assert_eq!(slots, [11, 12]);
+    }
+
+    #[test]
+    fn test_late_event_is_emitted_alone() {
+        let start = Instant::now();
+        let mut batches = Batches::new(Duration::from_millis(400));
+        batches.push(event(10, "a", 0), (), start);
+        assert_eq!(batches.take_all().len(), 1);
+
+        let late = batches.push(event(10, "b", 0), (), start).unwrap();
+        assert_eq!(keys(&late), (10, vec![("b".to_string(), 0)]));
+        assert!(batches.push(event(11, "c", 0), (), start).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_emits_held_slots() {
+        let batcher = SlotBatcher::spawn(
+            SlotBatchConfig {
+                delay: Duration::from_secs(60),
+            },
+            OutputFormat::Text,
+        );
+        let published = Arc::new(Mutex::new(Vec::new()));
+        let sender = {
+            let published = published.clone();
+            batcher.sender(move |event| published.lock().unwrap().push(event.signature))
+        };
+
+        sender.submit(event(10, "a", 0));
+        sender.submit(event(11, "b", 0));
+        batcher.shutdown().await;
+        assert_eq!(*published.lock().unwrap(), ["a", "b"]);
+
+        // After shutdown, events are published immediately
+        sender.submit(event(12, "c", 0));
+        assert_eq!(published.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_slot_header() {
+        assert_eq!(
+            slot_header(42, 3, OutputFormat::Text),
+            "📦 SLOT 42 (3 events)"
+        );
+        assert_eq!(
+            slot_header(42, 3, OutputFormat::Json),
+            r#"{"event_type":"slot","slot":42,"events":3}"#
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//! Disk spool of webhook payloads awaiting delivery.
+//!
+//! A [`Spool`] is an append-only JSONL file with one request body per line and
+//! a sidecar file (`<spool>.offset`) holding the byte offset of the first
+//! undelivered line. Delivered lines are skipped by advancing the offset, and
+//! both files are removed once everything has been delivered, so replaying a
+//! long outage stays linear in the spool size.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+/// Persistent FIFO queue of request bodies.
+#[derive(Debug)]
+pub struct Spool {
+    path: PathBuf,
+    offset_path: PathBuf,
+    /// Byte offset of the first undelivered line
+    offset: u64,
+    /// Length of the spool file
+    len: u64,
+}
+
+impl Spool {
+    /// Opens the spool at `path`, creating its directory if needed.
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let offset_path = sidecar(&path);
+        let len = match fs::metadata(&path) {
+            Ok(metadata) => metadata.len(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e),
+        };
+        let offset = match fs::read_to_string(&offset_path) {
+            Ok(offset) => offset.trim().parse().unwrap_or(0).min(len),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e),
+        };
+        Ok(Self {
+            path,
+            offset_path,
+            offset,
+            len,
+        })
+    }
+
+    /// Returns the spool file path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns `true` if every spooled body has been delivered.
+    pub fn is_empty(&self) -> bool {
+        self.offset >= self.len
+    }
+
+    /// Appends a body.
+    ///
+    /// Newlines can only be whitespace in a JSON body, so they become spaces.
+    pub fn push(&mut self, body: &str) -> io::Result<()> {
+        let mut line = body.replace('\n', " ");
+        line.push('\n');
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())?;
+        self.len += line.len() as u64;
+        Ok(())
+    }
+
+    /// Reads up to `max` undelivered bodies, oldest first.
+    pub fn peek(&self, max: usize) -> io::Result<Vec<String>> {
+        if self.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(self.offset))?;
+        BufReader::new(file).lines().take(max).collect()
+    }
+
+    /// Marks bodies returned by [`peek`](Self::peek) as delivered.
+    ///
+    /// Removes the spool once it is fully delivered.
+    pub fn consume(&mut self, bodies: &[String]) -> io::Result<()> {
+        let consumed: u64 = bodies.iter().map(|body| body.len() as u64 + 1).sum();
+        self.offset = (self.offset + consumed).min(self.len);
+        if self.is_empty() {
+            self.offset = 0;
+            self.len = 0;
+            remove_if_exists(&self.path)?;
+            remove_if_exists(&self.offset_path)
+        } else {
+            fs::write(&self.offset_path, self.offset.to_string())
+        }
+    }
+}
+
+/// Returns the offset file of a spool (`hooks/default.jsonl.offset`).
+fn sidecar(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".offset");
+    path.with_file_name(name)
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::env};
+
+    /// Returns an empty directory for one test.
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!(
+            "raydium-alerts-spool-{name}-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_fifo_across_reopen() {
+        let dir = test_dir("fifo");
+        let path = dir.join("default.jsonl");
+        let mut spool = Spool::open(&path).unwrap();
+        assert!(spool.is_empty());
+        for body in [r#"{"seq":1}"#, r#"{"seq":2}"#, r#"{"seq":3}"#] {
+            spool.push(body).unwrap();
+        }
+
+        let batch = spool.peek(2).unwrap();
+        assert_eq!(batch, [r#"{"seq":1}"#, r#"{"seq":2}"#]);
+        spool.consume(&batch[..1]).unwrap();
+
+        let mut spool = Spool::open(&path).unwrap();
+        assert_eq!(spool.peek(10).unwrap(), [r#"{"seq":2}"#, r#"{"seq":3}"#]);
+        let rest = spool.peek(10).unwrap();
+        spool.consume(&rest).unwrap();
+        assert!(spool.is_empty());
+        assert!(!path.exists());
+        assert!(!sidecar(&path).exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_push_keeps_one_body_per_line() {
+        let dir = test_dir("newlines");
+        let mut spool = Spool::open(dir.join("hook.jsonl")).unwrap();
+        spool.push("{\n\"a\": 1\n}").unwrap();
+        assert_eq!(spool.peek(10).unwrap(), ["{ \"a\": 1 }"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
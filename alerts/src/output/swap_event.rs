@@ -1,9 +1,16 @@
 //! Normalized swap event data structure.
 //!
 //! This module provides a protocol-agnostic representation of swap events
-//! that works across CPMM, CLMM, and AMM V4.
+//! that works across CPMM, CLMM, AMM V4, Launchpad, Pump.fun, and PumpSwap.
 
 use {
+    super::{
+        number_format::number_format, template, AdminActionEvent, CustomEvent, FeeCollectionEvent,
+        LiquidityDrainEvent, LpBurnEvent, MevAlertEvent, PoolCreatedEvent, PoolStats, Provenance,
+        ThrottleDigest, VolumeSummary,
+    },
+    crate::{chain, labels::AddressLabels},
+    carbon_core::instruction::InstructionMetadata,
     serde::{Deserialize, Serialize},
     solana_pubkey::Pubkey,
     std::{env, fmt, str::FromStr},
@@ -27,6 +34,12 @@ pub enum Protocol {
     Clmm,
     /// AMM V4 (legacy with Serum integration)
     AmmV4,
+    /// Launchpad (LaunchLab) bonding curves
+    Launchpad,
+    /// Pump.fun bonding curves
+    PumpFun,
+    /// PumpSwap AMM (migrated Pump.fun tokens)
+    PumpSwap,
 }
 
 impl fmt::Display for Protocol {
@@ -35,6 +48,9 @@ impl fmt::Display for Protocol {
             Self::Cpmm => write!(f, "CPMM"),
             Self::Clmm => write!(f, "CLMM"),
             Self::AmmV4 => write!(f, "AMM-V4"),
+            Self::Launchpad => write!(f, "LAUNCHPAD"),
+            Self::PumpFun => write!(f, "PUMPFUN"),
+            Self::PumpSwap => write!(f, "PUMPSWAP"),
         }
     }
 }
@@ -62,6 +78,52 @@ impl fmt::Display for SwapDirection {
     }
 }
 
+/// Side of a trade relative to the base token (SOL, USDC, or USDT).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TradeSide {
+    /// Base token in, other token out
+    Buy,
+    /// Other token in, base token out
+    Sell,
+}
+
+impl TradeSide {
+    /// Classifies a swap from the mints of its legs.
+    ///
+    /// Stablecoins rank above SOL, so SOL -> USDC is a sell of SOL. Returns
+    /// `None` when neither or both legs are equally ranked base tokens.
+    pub fn classify(input: &TokenInfo, output: &TokenInfo) -> Option<Self> {
+        let rank = |token: &TokenInfo| match token.mint.as_str() {
+            USDC_MINT | USDT_MINT => 2,
+            WSOL_MINT => 1,
+            _ => 0,
+        };
+        match rank(input).cmp(&rank(output)) {
+            std::cmp::Ordering::Greater => Some(Self::Buy),
+            std::cmp::Ordering::Less => Some(Self::Sell),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    /// Returns the emoji badge shown in text alerts.
+    pub fn badge(self) -> &'static str {
+        match self {
+            Self::Buy => "🟢 BUY",
+            Self::Sell => "🔴 SELL",
+        }
+    }
+}
+
+impl fmt::Display for TradeSide {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Buy => write!(f, "buy"),
+            Self::Sell => write!(f, "sell"),
+        }
+    }
+}
+
 /// Event type for different on-chain events.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
@@ -75,6 +137,24 @@ pub enum EventType {
     RemoveLiquidity,
     /// Pool creation event
     CreatePool,
+    /// Bonding curve migration to an AMM pool
+    Migrate,
+    /// User-defined event emitted by a rule
+    Custom,
+    /// Fee, status, or authority change of a pool or fee config
+    AdminAction,
+    /// Periodic rolling volume of a pool
+    VolumeSummary,
+    /// MEV pattern detected among the swaps of a block
+    MevAlert,
+    /// Large share of a pool's liquidity withdrawn or of a token's supply sold by its creator
+    LiquidityDrain,
+    /// Swaps of a token or pool withheld by the alert throttle during a window
+    ThrottleDigest,
+    /// Most of a new pool's LP supply burned or sent to the incinerator
+    LpBurn,
+    /// Trading fees or rewards collected from a liquidity position or a pool
+    CollectFees,
 }
 
 impl fmt::Display for EventType {
@@ -84,6 +164,110 @@ impl fmt::Display for EventType {
             Self::AddLiquidity => write!(f, "ADD_LP"),
             Self::RemoveLiquidity => write!(f, "REMOVE_LP"),
             Self::CreatePool => write!(f, "CREATE_POOL"),
+            Self::Migrate => write!(f, "MIGRATE"),
+            Self::Custom => write!(f, "CUSTOM"),
+            Self::AdminAction => write!(f, "ADMIN"),
+            Self::VolumeSummary => write!(f, "VOLUME"),
+            Self::MevAlert => write!(f, "MEV"),
+            Self::LiquidityDrain => write!(f, "DRAIN"),
+            Self::ThrottleDigest => write!(f, "THROTTLED"),
+            Self::LpBurn => write!(f, "LP_BURN"),
+            Self::CollectFees => write!(f, "COLLECT_FEES"),
+        }
+    }
+}
+
+impl EventType {
+    /// Returns the emoji heading text alerts of this type.
+    pub fn emoji(self) -> &'static str {
+        match self {
+            Self::Swap => "🔄",
+            Self::AddLiquidity => "💧",
+            Self::RemoveLiquidity => "🔥",
+            Self::CreatePool => "🆕",
+            Self::Migrate => "🎓",
+            Self::Custom => "⚡",
+            Self::AdminAction => "⚠️",
+            Self::VolumeSummary => "📊",
+            Self::MevAlert => "🥪",
+            Self::LiquidityDrain => "🚨",
+            Self::ThrottleDigest => "🔇",
+            Self::LpBurn => "🔒",
+            Self::CollectFees => "💸",
+        }
+    }
+
+    /// Returns `true` for events delivered even to chats over their alert quota.
+    pub fn is_high_priority(self) -> bool {
+        matches!(self, Self::LiquidityDrain)
+    }
+}
+
+/// Where a swap event was observed.
+///
+/// The same swap can be seen as the program instruction itself, as a nested
+/// instruction of an aggregator route, and as the program's self-CPI event
+/// log. Variants are ordered by [`EventSource::precedence`] when collapsing
+/// duplicates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum EventSource {
+    /// Program event log (self-CPI) carrying the actual executed amounts
+    EventLog,
+    /// Top-level program instruction (amounts may be slippage bounds)
+    #[default]
+    Instruction,
+    /// Program instruction invoked via CPI, e.g. from an aggregator route
+    Aggregator,
+}
+
+impl EventSource {
+    /// Returns the precedence of this source; higher wins when deduplicating.
+    pub fn precedence(self) -> u8 {
+        match self {
+            Self::EventLog => 2,
+            Self::Instruction => 1,
+            Self::Aggregator => 0,
+        }
+    }
+}
+
+impl fmt::Display for EventSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EventLog => write!(f, "event_log"),
+            Self::Instruction => write!(f, "instruction"),
+            Self::Aggregator => write!(f, "aggregator"),
+        }
+    }
+}
+
+/// A duplicate variant of a swap that was collapsed into another event.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SuppressedVariant {
+    /// Where the suppressed variant was observed
+    pub source: EventSource,
+    /// Inner instruction path of the suppressed variant
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub inner_path: String,
+    /// Raw input amount reported by the variant
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_amount: Option<u64>,
+    /// Raw output amount reported by the variant
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_amount: Option<u64>,
+    /// Swap direction reported by the variant
+    pub direction: SwapDirection,
+}
+
+impl From<&SwapEvent> for SuppressedVariant {
+    fn from(event: &SwapEvent) -> Self {
+        Self {
+            source: event.source,
+            inner_path: event.provenance.inner_path.clone(),
+            input_amount: event.input_token.as_ref().map(|t| t.amount_raw),
+            output_amount: event.output_token.as_ref().map(|t| t.amount_raw),
+            direction: event.direction,
         }
     }
 }
@@ -154,13 +338,14 @@ impl TokenInfo {
         let symbol = self.symbol.as_deref().unwrap_or(&self.mint[..8]);
 
         let amount_str = if let Some(amount) = self.amount {
-            format!("{:.4}", amount)
+            number_format().amount(amount, self)
         } else {
             format!("{}", self.amount_raw)
         };
 
         if let Some(usd) = self.amount_usd {
-            format!("{} {} {} (${:.2})", emoji, symbol, amount_str, usd)
+            let usd = number_format().fixed(usd, 2);
+            format!("{} {} {} (${})", emoji, symbol, amount_str, usd)
         } else {
             format!("{} {} {}", emoji, symbol, amount_str)
         }
@@ -174,8 +359,9 @@ impl TokenInfo {
 
 /// Normalized swap event that abstracts protocol differences.
 ///
-/// This structure provides a unified view of swap events across CPMM, CLMM, and AMM V4,
-/// making it easy to process, log, and alert on swaps regardless of the underlying protocol.
+/// This structure provides a unified view of swap events across CPMM, CLMM, AMM V4,
+/// Launchpad, Pump.fun, and PumpSwap, making it easy to process, log, and alert on swaps
+/// regardless of the underlying protocol.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SwapEvent {
     /// Event type (Swap, AddLiquidity, RemoveLiquidity, etc.)
@@ -184,9 +370,39 @@ pub struct SwapEvent {
     /// Protocol that emitted this event
     pub protocol: Protocol,
 
+    /// Label of the additional program deployment the event was decoded from
+    /// (e.g., a forked AMM reusing the protocol's instruction layout)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployment: Option<String>,
+
     /// Transaction signature
     pub signature: String,
 
+    /// Stable event identifier: the signature and the instruction location
+    /// (e.g., "5Kq...#2.0.1"), referenced by [`EventUpdate`](super::EventUpdate)s
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub event_id: String,
+
+    /// Instruction that produced the event (index, inner path, program stack)
+    #[serde(default)]
+    pub provenance: Provenance,
+
+    /// Where this event was observed (event log, instruction, or aggregator CPI)
+    #[serde(default)]
+    pub source: EventSource,
+
+    /// Known router (aggregator) that invoked the instruction via CPI (e.g., "jupiter")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub router: Option<String>,
+
+    /// Program of the top-level instruction when the instruction was invoked via CPI
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_level_program: Option<String>,
+
+    /// Duplicate variants of the same swap that were collapsed into this event
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suppressed: Vec<SuppressedVariant>,
+
     /// Pool or AMM address
     pub pool: String,
 
@@ -201,6 +417,10 @@ pub struct SwapEvent {
     /// Swap direction (exact input, exact output, or unknown)
     pub direction: SwapDirection,
 
+    /// Buy or sell relative to the base token (swaps between a base token and another token)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub side: Option<TradeSide>,
+
     /// Trading fee in raw token units (if available)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub fee: Option<u64>,
@@ -209,16 +429,89 @@ pub struct SwapEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub maker: Option<String>,
 
+    /// Label of the maker from the address labels file (if labeled)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maker_label: Option<String>,
+
+    /// Whether the maker signed the transaction, as opposed to a program
+    /// (e.g., a bot or vault acting through a PDA) executing the instruction
+    /// on its behalf (unknown without a maker or the transaction's signers)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signed_by_owner: Option<bool>,
+
+    /// Whether the LP supply of the pool was burned (new CPMM and AMM V4 pools
+    /// tracked by the LP burn tracker only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lp_burned: Option<bool>,
+
+    /// Buys, sells, and USD volume of the pool since it was first seen,
+    /// including this event (set by the pool stats tracker)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_stats: Option<PoolStats>,
+
     /// Market cap of the non-base token (if available)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub market_cap_usd: Option<f64>,
 
+    /// Execution price: base token paid or received per unit of the other token
+    /// (input per output when neither is a base token)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub effective_price: Option<f64>,
+
+    /// Estimated move of the pool price caused by the swap, in percent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_impact_pct: Option<f64>,
+
     /// Block slot number
     pub slot: u64,
 
     /// Unix timestamp (seconds since epoch, if available)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub timestamp: Option<i64>,
+
+    /// Pool creation details (CreatePool events only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_created: Option<PoolCreatedEvent>,
+
+    /// Changed parameter and signer (AdminAction events only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admin: Option<AdminActionEvent>,
+
+    /// Rolling volume per window (VolumeSummary events only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub volume: Option<VolumeSummary>,
+
+    /// Attacker, front-run, victims, and back-run (MevAlert events only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mev: Option<MevAlertEvent>,
+
+    /// Drained reserve or dumped supply share (LiquidityDrain events only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub drain: Option<LiquidityDrainEvent>,
+
+    /// Withheld swaps of a throttled token or pool (ThrottleDigest events only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub throttled: Option<ThrottleDigest>,
+
+    /// LP mint and burned share of the LP supply (LpBurn events only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lp_burn: Option<LpBurnEvent>,
+
+    /// Kind, position, and rewards of a fee collection (CollectFees events only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee_collection: Option<FeeCollectionEvent>,
+
+    /// Name and description of a rule-emitted event (Custom events only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom: Option<CustomEvent>,
+
+    /// Sequence number within the webhook stream the event was queued for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seq: Option<u64>,
+
+    /// Enrichers cut by the enrichment budget, completed in a follow-up update
+    #[serde(skip)]
+    pub pending_enrichment: Vec<&'static str>,
 }
 
 impl SwapEvent {
@@ -227,15 +520,262 @@ impl SwapEvent {
         SwapEventBuilder::default()
     }
 
+    /// Builds a `CreatePool` event from pool creation details.
+    ///
+    /// The input and output tokens carry the initial reserves of tokens A and B
+    /// (zero when the pool is created empty) and the maker is the pool creator.
+    ///
+    /// # Arguments
+    ///
+    /// * `protocol` - Protocol that created the pool
+    /// * `metadata` - Metadata of the creating instruction
+    /// * `pool` - Address of the new pool
+    /// * `created` - Pool creation details
+    pub fn from_pool_created(
+        protocol: Protocol,
+        metadata: &InstructionMetadata,
+        pool: &Pubkey,
+        created: PoolCreatedEvent,
+    ) -> Self {
+        Self::builder()
+            .event_type(EventType::CreatePool)
+            .protocol(protocol)
+            .signature(metadata.transaction_metadata.signature.to_string())
+            .instruction_metadata(metadata)
+            .pool_pubkey(pool)
+            .input_token(TokenInfo::new(
+                created.token_mint_a.clone(),
+                created.initial_reserve_a.unwrap_or(0),
+            ))
+            .output_token(TokenInfo::new(
+                created.token_mint_b.clone(),
+                created.initial_reserve_b.unwrap_or(0),
+            ))
+            .maker(created.creator.clone())
+            .slot(metadata.transaction_metadata.slot)
+            .pool_created(created)
+            .build()
+    }
+
+    /// Builds an `AdminAction` event from administrative action details.
+    ///
+    /// The pool is the changed account (a pool, or a fee config shared by many
+    /// pools) and the maker is the signer of the action.
+    ///
+    /// # Arguments
+    ///
+    /// * `protocol` - Protocol of the changed account
+    /// * `metadata` - Metadata of the administrative instruction
+    /// * `account` - Address of the changed pool or fee config
+    /// * `admin` - Administrative action details
+    pub fn from_admin_action(
+        protocol: Protocol,
+        metadata: &InstructionMetadata,
+        account: &Pubkey,
+        admin: AdminActionEvent,
+    ) -> Self {
+        Self::builder()
+            .event_type(EventType::AdminAction)
+            .protocol(protocol)
+            .signature(metadata.transaction_metadata.signature.to_string())
+            .instruction_metadata(metadata)
+            .pool_pubkey(account)
+            .maker(admin.authority.clone())
+            .slot(metadata.transaction_metadata.slot)
+            .admin(admin)
+            .build()
+    }
+
+    /// Builds a `VolumeSummary` event for a pool.
+    ///
+    /// Summaries aggregate many transactions, so the signature is empty and
+    /// text output omits the transaction link.
+    ///
+    /// # Arguments
+    ///
+    /// * `protocol` - Protocol of the pool
+    /// * `pool` - Pool address
+    /// * `slot` - Slot of the pool's latest swap
+    /// * `timestamp` - Time of the summary (Unix seconds)
+    /// * `volume` - Rolling volume per window
+    pub fn from_volume_summary(
+        protocol: Protocol,
+        pool: impl Into<String>,
+        slot: u64,
+        timestamp: i64,
+        volume: VolumeSummary,
+    ) -> Self {
+        let mut event = Self::builder()
+            .event_type(EventType::VolumeSummary)
+            .protocol(protocol)
+            .signature("")
+            .pool(pool)
+            .slot(slot)
+            .timestamp(timestamp)
+            .build();
+        event.volume = Some(volume);
+        event
+    }
+
+    /// Builds a `MevAlert` event from a detected pattern.
+    ///
+    /// The event takes the pool, slot, and transaction of the swap completing
+    /// the pattern (the back-run of a sandwich), and the maker is the attacker.
+    pub fn from_mev_alert(completing: &SwapEvent, mev: MevAlertEvent) -> Self {
+        let mut event = Self::builder()
+            .event_type(EventType::MevAlert)
+            .protocol(completing.protocol)
+            .signature(completing.signature.clone())
+            .pool(completing.pool.clone())
+            .maker(mev.attacker.clone())
+            .slot(completing.slot)
+            .build();
+        event.deployment = completing.deployment.clone();
+        event.provenance = completing.provenance.clone();
+        event.event_id = completing.event_id.clone();
+        event.timestamp = completing.timestamp;
+        event.mev = Some(mev);
+        event
+    }
+
+    /// Builds a `LiquidityDrain` event from the withdrawal or sell that caused it.
+    ///
+    /// The event keeps the triggering event's pool, transaction, tokens, and maker.
+    pub fn from_liquidity_drain(trigger: &SwapEvent, drain: LiquidityDrainEvent) -> Self {
+        let mut event = Self::builder()
+            .event_type(EventType::LiquidityDrain)
+            .protocol(trigger.protocol)
+            .signature(trigger.signature.clone())
+            .pool(trigger.pool.clone())
+            .slot(trigger.slot)
+            .build();
+        event.input_token = trigger.input_token.clone();
+        event.output_token = trigger.output_token.clone();
+        event.maker = trigger.maker.clone();
+        event.deployment = trigger.deployment.clone();
+        event.provenance = trigger.provenance.clone();
+        event.event_id = trigger.event_id.clone();
+        event.timestamp = trigger.timestamp;
+        event.drain = Some(drain);
+        event
+    }
+
+    /// Builds a `ThrottleDigest` event for a throttled token or pool.
+    ///
+    /// Digests aggregate many transactions, so the signature is empty and text
+    /// output omits the transaction link.
+    ///
+    /// # Arguments
+    ///
+    /// * `protocol` - Protocol of the last withheld swap
+    /// * `pool` - Pool of the last withheld swap
+    /// * `slot` - Slot of the last withheld swap
+    /// * `timestamp` - Time of the digest (Unix seconds)
+    /// * `digest` - Withheld swaps
+    pub fn from_throttle_digest(
+        protocol: Protocol,
+        pool: impl Into<String>,
+        slot: u64,
+        timestamp: i64,
+        digest: ThrottleDigest,
+    ) -> Self {
+        let mut event = Self::builder()
+            .event_type(EventType::ThrottleDigest)
+            .protocol(protocol)
+            .signature("")
+            .pool(pool)
+            .slot(slot)
+            .timestamp(timestamp)
+            .build();
+        event.throttled = Some(digest);
+        event
+    }
+
+    /// Builds an `LpBurn` event for a pool whose LP supply was found burned.
+    ///
+    /// Burns are found by polling the LP supply, so the signature is empty and
+    /// text output omits the transaction link.
+    ///
+    /// # Arguments
+    ///
+    /// * `protocol` - Protocol of the pool
+    /// * `pool` - Pool address
+    /// * `timestamp` - Time the burn was found (Unix seconds)
+    /// * `burn` - LP mint and burned share
+    pub fn from_lp_burn(
+        protocol: Protocol,
+        pool: impl Into<String>,
+        timestamp: i64,
+        burn: LpBurnEvent,
+    ) -> Self {
+        let mut event = Self::builder()
+            .event_type(EventType::LpBurn)
+            .protocol(protocol)
+            .signature("")
+            .pool(pool)
+            .timestamp(timestamp)
+            .build();
+        event.lp_burned = Some(true);
+        event.lp_burn = Some(burn);
+        event
+    }
+
+    /// Builds a `CollectFees` event from a collection of pool fees or rewards.
+    ///
+    /// The input and output tokens carry the collected fees of the pool's
+    /// tokens 0 and 1 (none for reward withdrawals), and the maker is the
+    /// collector: the position owner, fee owner, or reward funder.
+    ///
+    /// # Arguments
+    ///
+    /// * `protocol` - Protocol of the pool
+    /// * `metadata` - Metadata of the collecting instruction
+    /// * `pool` - Pool address
+    /// * `collector` - Account the fees or rewards were collected for
+    /// * `fees` - Collected fees of tokens 0 and 1, if the collection pays fees
+    /// * `collection` - Kind of collection, position, and collected rewards
+    pub fn from_fee_collection(
+        protocol: Protocol,
+        metadata: &InstructionMetadata,
+        pool: &Pubkey,
+        collector: &Pubkey,
+        fees: Option<(TokenInfo, TokenInfo)>,
+        collection: FeeCollectionEvent,
+    ) -> Self {
+        let mut event = Self::builder()
+            .event_type(EventType::CollectFees)
+            .protocol(protocol)
+            .signature(metadata.transaction_metadata.signature.to_string())
+            .instruction_metadata(metadata)
+            .pool_pubkey(pool)
+            .maker_pubkey(collector)
+            .slot(metadata.transaction_metadata.slot)
+            .build();
+        if let Some((fee0, fee1)) = fees {
+            event.input_token = Some(fee0);
+            event.output_token = Some(fee1);
+        }
+        event.fee_collection = Some(collection);
+        event
+    }
+
     /// Formats the swap event according to the specified output format.
+    ///
+    /// Text uses the `console` template, if one is installed.
     pub fn format(&self, format: OutputFormat) -> String {
         match format {
-            OutputFormat::Text => self.format_text(),
+            OutputFormat::Text => self.format_text_with(template::CONSOLE_TEMPLATE),
             OutputFormat::Json => self.format_json(),
             OutputFormat::JsonPretty => self.format_json_pretty(),
         }
     }
 
+    /// Formats as text with the named template, falling back to the `default`
+    /// template and then to the built-in layout (see [`template`]).
+    pub fn format_text_with(&self, name: &str) -> String {
+        template::render(name, self).unwrap_or_else(|| self.format_text())
+    }
+
     /// Formats as emoji-rich human-readable text.
     ///
     /// Example output:
@@ -244,25 +784,40 @@ impl SwapEvent {
     /// 🔄 SWAP [CPMM]
     /// 🔷 SOL 11.9880 ($1491.19)
     /// 🪙 MACARON 11500.70
-    /// 🔎 Maker: 7xKXt...
+    /// 🔎 Maker: 7xKXt...   (or 🏷️ whale-3 for labeled makers)
     /// 📈 MCap: $615,340
+    /// 💱 Price: 0.001042 · Impact: 0.84%
     /// 🔗 https://solscan.io/tx/...
+    /// 📍 Ix: #2.0 (JUP6Lk → CPMMoo) via jupiter
     /// ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━
     /// ```
     fn format_text(&self) -> String {
         let mut lines = Vec::new();
 
         // Header with event type and protocol
-        let event_emoji = match self.event_type {
-            EventType::Swap => "🔄",
-            EventType::AddLiquidity => "💧",
-            EventType::RemoveLiquidity => "🔥",
-            EventType::CreatePool => "🆕",
+        let event_emoji = self.event_type.emoji();
+        // Label of an additional deployment, or the protocol
+        let protocol = match self.deployment {
+            Some(ref label) => label.clone(),
+            None => self.protocol.to_string(),
         };
-        lines.push(format!(
-            "{} {} [{}]",
-            event_emoji, self.event_type, self.protocol
-        ));
+        match self.custom {
+            Some(ref custom) => {
+                lines.push(custom.format_header(&protocol));
+                lines.extend(custom.format_description());
+            }
+            None => {
+                let mut header = format!("{} {} [{}]", event_emoji, self.event_type, protocol);
+                if let Some(side) = self.side {
+                    header.push(' ');
+                    header.push_str(side.badge());
+                }
+                if self.lp_burned == Some(true) && self.lp_burn.is_none() {
+                    header.push_str(" · LP burned ✅");
+                }
+                lines.push(header);
+            }
+        }
 
         // Determine which token is base and which is quote
         let (base_token, quote_token) = self.get_base_quote_tokens();
@@ -275,14 +830,20 @@ impl SwapEvent {
             lines.push(token.format_display(false));
         }
 
-        // Maker address (shortened)
-        if let Some(ref maker) = self.maker {
+        // Maker label, or address (shortened), flagged if it did not sign
+        let delegated = match self.signed_by_owner {
+            Some(false) => " · 🤖 delegated",
+            _ => "",
+        };
+        if let Some(ref label) = self.maker_label {
+            lines.push(format!("🏷️ {}{delegated}", label));
+        } else if let Some(ref maker) = self.maker {
             let short_maker = if maker.len() > 12 {
                 format!("{}...{}", &maker[..6], &maker[maker.len() - 4..])
             } else {
                 maker.clone()
             };
-            lines.push(format!("🔎 Maker: {}", short_maker));
+            lines.push(format!("🔎 Maker: {}{delegated}", short_maker));
         }
 
         // Market cap
@@ -290,24 +851,104 @@ impl SwapEvent {
             lines.push(format!("📈 MCap: ${}", format_number(mcap)));
         }
 
+        // Execution price and impact on the pool price
+        match (self.effective_price, self.price_impact_pct) {
+            (Some(price), Some(impact)) => lines.push(format!(
+                "💱 Price: {} · Impact: {impact:.2}%",
+                format_price(price)
+            )),
+            (Some(price), None) => lines.push(format!("💱 Price: {}", format_price(price))),
+            _ => {}
+        }
+
         // Fee if available
         if let Some(fee) = self.fee {
             lines.push(format!("💰 Fee: {}", fee));
         }
 
+        // Running totals of the pool
+        if let Some(ref stats) = self.pool_stats {
+            lines.push(stats.format_line());
+        }
+
+        // Details of new pools, admin actions, MEV, drains, summaries, digests, LP burns,
+        // and fee harvests
+        lines.extend(self.detail_lines());
+
+        // Summaries, digests, and LP burns have no single transaction
+        if self.volume.is_some() || self.throttled.is_some() || self.lp_burn.is_some() {
+            return lines.join("\n");
+        }
+
         // Transaction link
         let short_sig = if self.signature.len() > 12 {
             format!("{}...", &self.signature[..12])
         } else {
             self.signature.clone()
         };
-        lines.push(format!("🔗 https://solscan.io/tx/{}", short_sig));
+        lines.push(format!("🔗 {}", chain::profile().explorer_tx(&short_sig)));
+        match self.router {
+            Some(ref router) => lines.push(format!("📍 Ix: {} via {router}", self.provenance)),
+            None => lines.push(format!("📍 Ix: {}", self.provenance)),
+        }
 
         lines.join("\n")
     }
 
+    /// Returns the text lines of the details attached to the event type: new
+    /// pools, admin actions, MEV patterns, drains, volume summaries, throttle
+    /// digests, LP burns, and fee harvests. Empty for plain swaps and liquidity
+    /// events.
+    pub fn detail_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+
+        // Open time and LP mint of new pools
+        if let Some(ref created) = self.pool_created {
+            lines.extend(created.format_lines());
+        }
+
+        // Changed parameter of administrative actions
+        if let Some(ref admin) = self.admin {
+            lines.extend(admin.format_lines());
+        }
+
+        // Front-run, victims, and back-run of MEV patterns
+        if let Some(ref mev) = self.mev {
+            lines.extend(mev.format_lines());
+        }
+
+        // Drained reserve or dumped supply share
+        if let Some(ref drain) = self.drain {
+            lines.extend(drain.format_lines());
+        }
+
+        // Rolling volume of summaries
+        if let Some(ref volume) = self.volume {
+            lines.push(format!("🏊 Pool: {}", self.pool));
+            lines.extend(volume.format_lines());
+        }
+
+        // Withheld swaps of digests
+        if let Some(ref throttled) = self.throttled {
+            lines.extend(throttled.format_lines());
+        }
+
+        // Burned share of the LP supply
+        if let Some(ref burn) = self.lp_burn {
+            lines.push(format!("🏊 Pool: {}", self.pool));
+            lines.extend(burn.format_lines());
+        }
+
+        // Kind, position, and rewards of fee collections
+        if let Some(ref collection) = self.fee_collection {
+            lines.extend(collection.format_lines());
+        }
+
+        lines
+    }
+
     /// Gets the base and quote tokens, ordering so base tokens (SOL/USDC) come first.
-    fn get_base_quote_tokens(&self) -> (Option<&TokenInfo>, Option<&TokenInfo>) {
+    pub fn get_base_quote_tokens(&self) -> (Option<&TokenInfo>, Option<&TokenInfo>) {
         match (&self.input_token, &self.output_token) {
             (Some(input), Some(output)) => {
                 // If output is base token (selling quote for base), swap order for display
@@ -323,6 +964,15 @@ impl SwapEvent {
         }
     }
 
+    /// Sets the maker label from the address labels, clearing a stale one.
+    pub fn apply_labels(&mut self, labels: &AddressLabels) {
+        self.maker_label = self
+            .maker
+            .as_deref()
+            .and_then(|maker| labels.get(maker))
+            .map(str::to_string);
+    }
+
     /// Formats as compact JSON.
     fn format_json(&self) -> String {
         serde_json::to_string(self)
@@ -348,6 +998,21 @@ impl SwapEvent {
         Some(output / input)
     }
 
+    /// Calculates the execution price in base token units per unit of the
+    /// other token (input per output when neither or both are base tokens).
+    ///
+    /// Returns `None` if amounts are not available or the divisor is zero.
+    pub fn execution_price(&self) -> Option<f64> {
+        let (Some(base), Some(other)) = self.get_base_quote_tokens() else {
+            return None;
+        };
+        let (base, other) = (base.amount?, other.amount?);
+        if other == 0.0 {
+            return None;
+        }
+        Some(base / other)
+    }
+
     /// Calculates the inverse price (input per output).
     ///
     /// Returns `None` if amounts are not available or output is zero.
@@ -362,7 +1027,6 @@ impl SwapEvent {
     }
 
     /// Gets the total USD value of the swap (input or output, whichever is available).
-    #[allow(dead_code)]
     pub fn usd_value(&self) -> Option<f64> {
         self.input_token
             .as_ref()
@@ -371,17 +1035,20 @@ impl SwapEvent {
     }
 }
 
-/// Formats a number with thousands separators.
-fn format_number(n: f64) -> String {
-    if n >= 1_000_000_000.0 {
-        format!("{:.2}B", n / 1_000_000_000.0)
-    } else if n >= 1_000_000.0 {
-        format!("{:.2}M", n / 1_000_000.0)
-    } else if n >= 1_000.0 {
-        format!("{:.2}K", n / 1_000.0)
-    } else {
-        format!("{:.2}", n)
-    }
+/// Formats a value with `K`/`M`/`B` suffixes in the installed number format.
+pub(crate) fn format_number(n: f64) -> String {
+    number_format().compact(n)
+}
+
+/// Formats a price with four significant digits below one in the installed
+/// number format.
+fn format_price(price: f64) -> String {
+    number_format().price(price)
+}
+
+/// Returns the event identifier of the instruction at `provenance` in a transaction.
+fn event_id(signature: &str, provenance: &Provenance) -> String {
+    format!("{signature}{}", provenance.location())
 }
 
 /// Builder for constructing SwapEvent instances.
@@ -390,6 +1057,10 @@ pub struct SwapEventBuilder {
     event_type: EventType,
     protocol: Option<Protocol>,
     signature: Option<String>,
+    provenance: Provenance,
+    source: Option<EventSource>,
+    nested: bool,
+    signers: Vec<Pubkey>,
     pool: Option<String>,
     input_token: Option<TokenInfo>,
     output_token: Option<TokenInfo>,
@@ -399,6 +1070,8 @@ pub struct SwapEventBuilder {
     market_cap_usd: Option<f64>,
     slot: u64,
     timestamp: Option<i64>,
+    pool_created: Option<PoolCreatedEvent>,
+    admin: Option<AdminActionEvent>,
 }
 
 impl SwapEventBuilder {
@@ -420,6 +1093,27 @@ impl SwapEventBuilder {
         self
     }
 
+    /// Sets the instruction provenance from Carbon's instruction metadata.
+    ///
+    /// The first element of `absolute_path` is the top-level instruction index;
+    /// the remaining elements locate the inner instruction below it. The
+    /// transaction's signers are recorded to derive `signed_by_owner`.
+    pub fn instruction_metadata(mut self, metadata: &InstructionMetadata) -> Self {
+        self.provenance = Provenance::from_metadata(metadata);
+        self.nested = metadata.stack_height > 1;
+        self.signers = transaction_signers(metadata);
+        self
+    }
+
+    /// Sets where the event was observed.
+    ///
+    /// Defaults to [`EventSource::Instruction`], or [`EventSource::Aggregator`]
+    /// when the instruction metadata shows it was invoked via CPI.
+    pub fn source(mut self, source: EventSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
     /// Sets the pool/AMM address.
     pub fn pool(mut self, pool: impl Into<String>) -> Self {
         self.pool = Some(pool.into());
@@ -503,29 +1197,94 @@ impl SwapEventBuilder {
         self
     }
 
+    /// Sets the pool creation details.
+    pub fn pool_created(mut self, created: PoolCreatedEvent) -> Self {
+        self.pool_created = Some(created);
+        self
+    }
+
+    /// Sets the administrative action details.
+    pub fn admin(mut self, admin: AdminActionEvent) -> Self {
+        self.admin = Some(admin);
+        self
+    }
+
     /// Builds the SwapEvent.
     ///
     /// # Panics
     ///
     /// Panics if `protocol`, `signature`, or `pool` are not set.
     pub fn build(self) -> SwapEvent {
+        let side = match (self.event_type, &self.input_token, &self.output_token) {
+            (EventType::Swap, Some(input), Some(output)) => TradeSide::classify(input, output),
+            _ => None,
+        };
+        let signature = self.signature.expect("signature is required");
+        let signed_by_owner = match self.maker {
+            Some(ref maker) if !self.signers.is_empty() => Pubkey::from_str(maker)
+                .ok()
+                .map(|maker| self.signers.contains(&maker)),
+            _ => None,
+        };
         SwapEvent {
             event_type: self.event_type,
             protocol: self.protocol.expect("protocol is required"),
-            signature: self.signature.expect("signature is required"),
+            deployment: None,
+            event_id: event_id(&signature, &self.provenance),
+            signature,
+            router: self.provenance.router().map(str::to_string),
+            top_level_program: self.provenance.top_level_program().map(str::to_string),
+            provenance: self.provenance,
+            source: self.source.unwrap_or(if self.nested {
+                EventSource::Aggregator
+            } else {
+                EventSource::Instruction
+            }),
+            suppressed: Vec::new(),
             pool: self.pool.expect("pool is required"),
             input_token: self.input_token,
             output_token: self.output_token,
             direction: self.direction,
+            side,
             fee: self.fee,
             maker: self.maker,
+            maker_label: None,
+            signed_by_owner,
+            lp_burned: None,
+            pool_stats: None,
             market_cap_usd: self.market_cap_usd,
+            effective_price: None,
+            price_impact_pct: None,
             slot: self.slot,
             timestamp: self.timestamp,
+            pool_created: self.pool_created,
+            admin: self.admin,
+            volume: None,
+            mev: None,
+            drain: None,
+            throttled: None,
+            lp_burn: None,
+            fee_collection: None,
+            custom: None,
+            seq: None,
+            pending_enrichment: Vec::new(),
         }
     }
 }
 
+/// Returns the accounts that signed the transaction of an instruction, or
+/// none if the transaction message is unavailable.
+fn transaction_signers(metadata: &InstructionMetadata) -> Vec<Pubkey> {
+    let message = &metadata.transaction_metadata.message;
+    let signers = message.header().num_required_signatures as usize;
+    message
+        .static_account_keys()
+        .iter()
+        .take(signers)
+        .copied()
+        .collect()
+}
+
 /// Output format for swap events.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum OutputFormat {
@@ -604,7 +1363,7 @@ mod tests {
 
     #[test]
     fn test_token_info_without_usd() {
-        let token = TokenInfo::new("TokenMint123", 11500_700_000)
+        let token = TokenInfo::new("TokenMint123", 11_500_700_000)
             .with_symbol("MACARON")
             .with_decimals(6);
 
@@ -638,13 +1397,137 @@ mod tests {
             .build();
 
         let text = event.format(OutputFormat::Text);
-        assert!(text.contains("🔄 SWAP"));
-        assert!(text.contains("[CPMM]"));
+        assert!(text.contains("🔄 SWAP [CPMM] 🟢 BUY"));
         assert!(text.contains("SOL"));
         assert!(text.contains("MACARON"));
         assert!(text.contains("Maker:"));
         assert!(text.contains("MCap: $615.34K"));
         assert!(text.contains("solscan.io"));
+        assert!(!text.contains("Price:"));
+    }
+
+    #[test]
+    fn test_execution_price_and_impact() {
+        let mut event = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .input_token(TokenInfo::new("TokenMint", 4_000_000).with_decimals(6))
+            .output_token(TokenInfo::new(WSOL_MINT, 1_000_000_000).with_decimals(9))
+            .build();
+        // A sell is still priced in SOL per token
+        assert_eq!(event.execution_price(), Some(0.25));
+
+        event.effective_price = event.execution_price();
+        event.price_impact_pct = Some(1.234);
+        let text = event.format(OutputFormat::Text);
+        assert!(text.contains("💱 Price: 0.2500 · Impact: 1.23%"));
+
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["effective_price"], 0.25);
+        assert_eq!(json["price_impact_pct"], 1.234);
+    }
+
+    #[test]
+    fn test_swap_event_maker_label() {
+        const WHALE: &str = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+        let labels = AddressLabels::parse(&format!("[labels]\n{WHALE} = \"whale-3\"")).unwrap();
+
+        let mut event = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .maker(WHALE)
+            .build();
+        event.apply_labels(&labels);
+        let text = event.format(OutputFormat::Text);
+        assert!(text.contains("🏷️ whale-3"));
+        assert!(!text.contains("Maker:"));
+        assert!(event
+            .format(OutputFormat::Json)
+            .contains("\"maker_label\":\"whale-3\""));
+
+        event.apply_labels(&AddressLabels::default());
+        assert_eq!(event.maker_label, None);
+        assert!(event
+            .format(OutputFormat::Text)
+            .contains("🔎 Maker: 7xKXtg...gAsU"));
+
+        // Without the transaction's signers, whether the maker signed is unknown
+        assert_eq!(event.signed_by_owner, None);
+        event.signed_by_owner = Some(false);
+        assert!(event
+            .format(OutputFormat::Text)
+            .contains("🔎 Maker: 7xKXtg...gAsU · 🤖 delegated"));
+        assert!(event
+            .format(OutputFormat::Json)
+            .contains("\"signed_by_owner\":false"));
+    }
+
+    #[test]
+    fn test_signed_by_owner_from_transaction_signers() {
+        use {
+            carbon_core::transaction::TransactionMetadata,
+            solana_message::{legacy, v0, MessageHeader, VersionedMessage},
+            std::sync::Arc,
+        };
+
+        let [payer, owner, vault, program, loaded] =
+            [1, 2, 3, 4, 5].map(|seed| Pubkey::new_from_array([seed; 32]));
+        // Payer and owner sign; the vault and program are static but unsigned
+        let header = MessageHeader {
+            num_required_signatures: 2,
+            num_readonly_signed_accounts: 0,
+            num_readonly_unsigned_accounts: 1,
+        };
+        let account_keys = vec![payer, owner, vault, program];
+        let signed_by_owner = |message: &VersionedMessage, loaded: &[Pubkey], maker: &Pubkey| {
+            let mut transaction = TransactionMetadata {
+                message: message.clone(),
+                ..Default::default()
+            };
+            transaction.meta.loaded_addresses = v0::LoadedAddresses {
+                writable: loaded.to_vec(),
+                readonly: Vec::new(),
+            };
+            let metadata = InstructionMetadata {
+                transaction_metadata: Arc::new(transaction),
+                stack_height: 1,
+                index: 0,
+                absolute_path: vec![0],
+            };
+            SwapEvent::builder()
+                .protocol(Protocol::Cpmm)
+                .signature("sig")
+                .pool("pool")
+                .instruction_metadata(&metadata)
+                .maker_pubkey(maker)
+                .build()
+                .signed_by_owner
+        };
+
+        let legacy = VersionedMessage::Legacy(legacy::Message {
+            header,
+            account_keys: account_keys.clone(),
+            ..Default::default()
+        });
+        assert_eq!(signed_by_owner(&legacy, &[], &owner), Some(true));
+        assert_eq!(signed_by_owner(&legacy, &[], &vault), Some(false));
+
+        // Addresses loaded from lookup tables follow the static keys and never sign
+        let v0 = VersionedMessage::V0(v0::Message {
+            header,
+            account_keys,
+            address_table_lookups: vec![v0::MessageAddressTableLookup {
+                account_key: Pubkey::new_from_array([9; 32]),
+                writable_indexes: vec![0],
+                readonly_indexes: Vec::new(),
+            }],
+            ..Default::default()
+        });
+        assert_eq!(signed_by_owner(&v0, &[loaded], &owner), Some(true));
+        assert_eq!(signed_by_owner(&v0, &[loaded], &program), Some(false));
+        assert_eq!(signed_by_owner(&v0, &[loaded], &loaded), Some(false));
     }
 
     #[test]
@@ -663,6 +1546,33 @@ mod tests {
         assert!(json.contains("\"signature\":\"sig123\""));
     }
 
+    #[test]
+    fn test_trade_side() {
+        let sol = TokenInfo::new(WSOL_MINT, 1);
+        let usdc = TokenInfo::new(USDC_MINT, 1);
+        let meme = TokenInfo::new("MemeMint", 1);
+
+        assert_eq!(TradeSide::classify(&sol, &meme), Some(TradeSide::Buy));
+        assert_eq!(TradeSide::classify(&meme, &usdc), Some(TradeSide::Sell));
+        assert_eq!(TradeSide::classify(&sol, &usdc), Some(TradeSide::Sell));
+        assert_eq!(TradeSide::classify(&meme, &meme), None);
+
+        // Exact-output sells keep the side of their legs
+        let event = SwapEvent::builder()
+            .protocol(Protocol::Clmm)
+            .signature("sig")
+            .pool("pool")
+            .input_token(meme)
+            .output_token(sol)
+            .direction(SwapDirection::ExactOutput)
+            .build();
+        assert_eq!(event.side, Some(TradeSide::Sell));
+        assert!(event.format(OutputFormat::Text).contains("🔴 SELL"));
+        assert!(event
+            .format(OutputFormat::Json)
+            .contains("\"side\":\"sell\""));
+    }
+
     #[test]
     fn test_format_number() {
         assert_eq!(format_number(500.0), "500.00");
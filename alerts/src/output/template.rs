@@ -0,0 +1,253 @@
+//! Text alert layouts.
+//!
+//! The built-in text layout of alerts can be replaced with templates declared
+//! in a TOML file (`TEMPLATES_FILE`), without rebuilding. Templates use the
+//! rule `emit` placeholders, plus `{emoji}`, `{token_symbol}`, `{token_mint}`
+//! (the non-base token), `{impact}`, `{explorer}` (the chain profile's
+//! transaction link), and `{details}` (the lines specific to new pools, admin
+//! actions, MEV, drains, summaries, and digests):
+//!
+//! ```toml
+//! [templates]
+//! default = """
+//! {emoji} {event_type} [{protocol}] {side}
+//! 💵 ${usd} · {input_amount} {input_symbol} → {output_amount} {output_symbol}
+//! 🔎 {maker}
+//! 📈 MCap: ${market_cap}
+//! {details}
+//! 🦅 https://birdeye.so/token/{token_mint} · 🦎 https://dexscreener.com/solana/{pool}
+//! """
+//! compact = "{emoji} {side} {token_symbol} ${usd} · https://dexscreener.com/solana/{pool}"
+//! ```
+//!
+//! A line whose placeholders are all unknown for an event (e.g., the market
+//! cap line of a token without one) is left out.
+//!
+//! Each notifier picks its template by name: `console` for the log output,
+//! `subscriptions` for Telegram subscription alerts, `push` for ntfy and
+//! Pushover notifications, and the `template` of a Discord, Telegram, email, or
+//! X rule destination. A notifier whose template is not declared uses
+//! `default`, and without one the built-in layout (a compact one for X).
+
+use {
+    super::SwapEvent,
+    crate::rules::{render_text, unknown_placeholder, PLACEHOLDERS},
+    serde::Deserialize,
+    std::{collections::HashMap, env, fmt, fs, path::Path, sync::OnceLock},
+};
+
+/// Template used by notifiers without a template of their own.
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// Template of the log output.
+pub const CONSOLE_TEMPLATE: &str = "console";
+
+/// Template of Telegram subscription alerts.
+pub const SUBSCRIPTIONS_TEMPLATE: &str = "subscriptions";
+
+/// Template of ntfy and Pushover push notifications.
+pub const PUSH_TEMPLATE: &str = "push";
+
+static TEMPLATES: OnceLock<TextTemplates> = OnceLock::new();
+
+/// Errors that can occur while loading a templates file.
+#[derive(Debug)]
+pub enum TemplateError {
+    /// The templates file could not be read
+    Read(String),
+    /// The templates file is not valid TOML or has an unknown shape
+    Parse(String),
+    /// A template is invalid (e.g., empty, unknown placeholder)
+    Invalid(String),
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(e) => write!(f, "failed to read templates file: {e}"),
+            Self::Parse(e) => write!(f, "failed to parse templates file: {e}"),
+            Self::Invalid(e) => write!(f, "invalid template: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+/// Contents of a templates file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TemplatesFile {
+    #[serde(default)]
+    templates: HashMap<String, String>,
+}
+
+/// Named text layouts of alerts.
+#[derive(Debug, Clone, Default)]
+pub struct TextTemplates {
+    templates: HashMap<String, String>,
+}
+
+impl TextTemplates {
+    /// Loads the templates file set with `TEMPLATES_FILE`.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `TEMPLATES_FILE` - Optional: TOML file of named text alert templates
+    ///
+    /// # Returns
+    ///
+    /// `Ok(None)` if `TEMPLATES_FILE` is not set.
+    pub fn from_env() -> Result<Option<Self>, TemplateError> {
+        match env::var("TEMPLATES_FILE") {
+            Ok(path) if !path.trim().is_empty() => Self::load_file(path.trim()).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Reads, parses, and validates a templates file.
+    pub fn load_file(path: impl AsRef<Path>) -> Result<Self, TemplateError> {
+        let path = path.as_ref();
+        let toml = fs::read_to_string(path)
+            .map_err(|e| TemplateError::Read(format!("{}: {e}", path.display())))?;
+        Self::parse(&toml)
+    }
+
+    /// Parses and validates the `[templates]` of a templates file.
+    pub fn parse(toml: &str) -> Result<Self, TemplateError> {
+        let file: TemplatesFile =
+            toml::from_str(toml).map_err(|e| TemplateError::Parse(e.to_string()))?;
+
+        let mut templates = HashMap::with_capacity(file.templates.len());
+        for (name, text) in file.templates {
+            let name = name.trim().to_string();
+            // Multi-line strings usually end with a newline before the closing quotes
+            let text = text.trim_end().to_string();
+            if name.is_empty() || text.is_empty() {
+                return Err(TemplateError::Invalid(format!(
+                    "template '{name}' has no name or no text"
+                )));
+            }
+            if let Some(placeholder) = unknown_placeholder(&text) {
+                return Err(TemplateError::Invalid(format!(
+                    "template '{name}' uses unknown placeholder '{{{placeholder}}}' (expected one of: {})",
+                    PLACEHOLDERS.join(", ")
+                )));
+            }
+            templates.insert(name, text);
+        }
+        Ok(Self { templates })
+    }
+
+    /// Returns the number of templates.
+    pub fn count(&self) -> usize {
+        self.templates.len()
+    }
+
+    /// Installs the templates used to format text alerts.
+    ///
+    /// Must be called before notifiers are set up; later calls fail.
+    pub fn install(self) -> Result<(), TemplateError> {
+        TEMPLATES
+            .set(self)
+            .map_err(|_| TemplateError::Invalid("templates are already installed".to_string()))
+    }
+
+    /// Renders an event with the named template, or with the `default`
+    /// template if there is none of that name.
+    ///
+    /// Returns `None` if neither is declared.
+    pub fn render(&self, name: &str, event: &SwapEvent) -> Option<String> {
+        let template = self
+            .templates
+            .get(name)
+            .or_else(|| self.templates.get(DEFAULT_TEMPLATE))?;
+        let (name, rule) = match event.custom {
+            Some(ref custom) => (custom.name.clone(), custom.rule.clone()),
+            None => (event.event_type.to_string(), String::new()),
+        };
+        Some(render_text(template, event, &name, &rule))
+    }
+}
+
+/// Returns `true` if a template of this name is installed.
+pub fn has_template(name: &str) -> bool {
+    TEMPLATES
+        .get()
+        .is_some_and(|templates| templates.templates.contains_key(name))
+}
+
+/// Renders an event with the installed template of this name (or `default`).
+///
+/// Returns `None` if neither is installed, so the built-in layout is used.
+pub fn render(name: &str, event: &SwapEvent) -> Option<String> {
+    TEMPLATES.get()?.render(name, event)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{Protocol, TokenInfo, TradeSide},
+    };
+
+    #[test]
+    fn test_parse_validates_placeholders() {
+        let templates = TextTemplates::parse(
+            r#"
+            [templates]
+            default = """
+            {emoji} {event_type} [{protocol}]
+            """
+            compact = "{token_symbol} ${usd}"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(templates.count(), 2);
+
+        assert!(matches!(
+            TextTemplates::parse("[templates]\ndefault = \"{volume_24h}\""),
+            Err(TemplateError::Invalid(_))
+        ));
+        assert!(matches!(
+            TextTemplates::parse("[templates]\ndefault = \" \""),
+            Err(TemplateError::Invalid(_))
+        ));
+        assert!(matches!(
+            TextTemplates::parse("[layouts]"),
+            Err(TemplateError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_render_skips_unknown_lines_and_falls_back_to_default() {
+        let templates = TextTemplates::parse(
+            r#"
+            [templates]
+            default = """
+{emoji} {event_type} [{protocol}] {side}
+📈 MCap: ${market_cap}
+🦅 https://birdeye.so/token/{token_mint}"""
+            compact = "{token_symbol} ${usd}"
+            "#,
+        )
+        .unwrap();
+        let mut event = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .input_token(TokenInfo::new(
+                "So11111111111111111111111111111111111111112",
+                1_000_000_000,
+            ))
+            .output_token(TokenInfo::new("BonkMint", 5).with_symbol("BONK"))
+            .build();
+        event.side = Some(TradeSide::Buy);
+
+        assert_eq!(
+            templates.render("console", &event).unwrap(),
+            "🔄 SWAP [CPMM] buy\n🦅 https://birdeye.so/token/BonkMint"
+        );
+        assert_eq!(templates.render("compact", &event).unwrap(), "BONK $?");
+        assert_eq!(TextTemplates::default().render("console", &event), None);
+    }
+}
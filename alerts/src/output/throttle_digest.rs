@@ -0,0 +1,117 @@
+//! Swap alerts withheld by the alert throttle.
+//!
+//! Attached to [`EventType::ThrottleDigest`](super::EventType::ThrottleDigest)
+//! events, which the throttle emits for each token or pool whose swaps
+//! exceeded its alert limit during a window.
+
+use {
+    super::swap_event::format_number,
+    serde::{Deserialize, Serialize},
+    std::fmt,
+};
+
+/// What an alert limit applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ThrottleScope {
+    /// Swaps of a token across all pools
+    Token,
+    /// Swaps of a single pool
+    Pool,
+}
+
+impl fmt::Display for ThrottleScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Token => write!(f, "token"),
+            Self::Pool => write!(f, "pool"),
+        }
+    }
+}
+
+/// Swaps of a token or pool withheld during one throttle window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThrottleDigest {
+    /// Whether the token or the pool limit was exceeded
+    pub scope: ThrottleScope,
+
+    /// Token mint or pool address
+    pub key: String,
+
+    /// Token symbol (token scope only, if known)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+
+    /// Alerts allowed per window
+    pub limit: u32,
+
+    /// Window length in seconds
+    pub window_secs: u64,
+
+    /// Swaps withheld
+    pub suppressed: u64,
+
+    /// Withheld swaps buying the token
+    pub buys: u64,
+
+    /// Withheld swaps selling the token
+    pub sells: u64,
+
+    /// USD volume of the withheld swaps (swaps without a USD value count as 0)
+    pub volume_usd: f64,
+}
+
+impl ThrottleDigest {
+    /// Returns the text output lines: the throttled token or pool, and the
+    /// withheld swaps.
+    pub fn format_lines(&self) -> Vec<String> {
+        let subject = match (self.scope, &self.symbol) {
+            (ThrottleScope::Token, Some(symbol)) => format!("🪙 Token: {symbol}"),
+            (ThrottleScope::Token, None) => format!("🪙 Token: {}", self.key),
+            (ThrottleScope::Pool, _) => format!("🏊 Pool: {}", self.key),
+        };
+        vec![
+            subject,
+            format!(
+                "🔇 {} swap(s) over {} per {}s · ${} · {} buys / {} sells",
+                self.suppressed,
+                self.limit,
+                self.window_secs,
+                format_number(self.volume_usd),
+                self.buys,
+                self.sells
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_lines() {
+        let digest = ThrottleDigest {
+            scope: ThrottleScope::Token,
+            key: "mint".to_string(),
+            symbol: Some("BONK".to_string()),
+            limit: 20,
+            window_secs: 60,
+            suppressed: 1_234,
+            buys: 700,
+            sells: 534,
+            volume_usd: 45_200.0,
+        };
+        assert_eq!(
+            digest.format_lines(),
+            [
+                "🪙 Token: BONK",
+                "🔇 1234 swap(s) over 20 per 60s · $45.20K · 700 buys / 534 sells",
+            ]
+        );
+
+        let json = serde_json::to_value(&digest).unwrap();
+        assert_eq!(json["scope"], "token");
+        assert_eq!(json["suppressed"], 1_234);
+    }
+}
@@ -39,8 +39,6 @@ pub struct TokenTransfer {
     /// Amount transferred in raw units (lamports/smallest unit).
     pub amount: u64,
     /// Token mint address (only available for TransferChecked).
-    /// Reserved for future token metadata lookup.
-    #[allow(dead_code)]
     pub mint: Option<Pubkey>,
     /// Token decimals (only available for TransferChecked).
     /// Reserved for future human-readable amount formatting.
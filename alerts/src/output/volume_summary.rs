@@ -0,0 +1,86 @@
+//! Rolling volume of a pool.
+//!
+//! Attached to [`EventType::VolumeSummary`](super::EventType::VolumeSummary)
+//! events, which the aggregator emits periodically for the busiest pools.
+
+use {
+    super::swap_event::format_number,
+    serde::{Deserialize, Serialize},
+};
+
+/// Volume of a pool over one rolling window.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowVolume {
+    /// Window length (e.g., `1m`, `5m`, `1h`)
+    pub window: String,
+    /// USD volume of the window's swaps (swaps without a USD value count as 0)
+    pub volume_usd: f64,
+    /// Swaps in the window
+    pub trades: u64,
+    /// Distinct makers of the window's swaps
+    pub unique_makers: u64,
+}
+
+/// Rolling volume of a pool, shortest window first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VolumeSummary {
+    /// Volume per window
+    pub windows: Vec<WindowVolume>,
+}
+
+impl VolumeSummary {
+    /// Returns the volume of a window by name.
+    pub fn window(&self, name: &str) -> Option<&WindowVolume> {
+        self.windows.iter().find(|window| window.window == name)
+    }
+
+    /// Returns the text output lines, one per window.
+    pub fn format_lines(&self) -> Vec<String> {
+        self.windows
+            .iter()
+            .map(|window| {
+                format!(
+                    "📊 {}: ${} · {} trades · {} makers",
+                    window.window,
+                    format_number(window.volume_usd),
+                    window.trades,
+                    window.unique_makers
+                )
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_lines() {
+        let summary = VolumeSummary {
+            windows: vec![
+                WindowVolume {
+                    window: "1m".to_string(),
+                    volume_usd: 12_500.0,
+                    trades: 14,
+                    unique_makers: 9,
+                },
+                WindowVolume {
+                    window: "1h".to_string(),
+                    volume_usd: 310_000.0,
+                    trades: 420,
+                    unique_makers: 133,
+                },
+            ],
+        };
+        assert_eq!(
+            summary.format_lines(),
+            [
+                "📊 1m: $12.50K · 14 trades · 9 makers",
+                "📊 1h: $310.00K · 420 trades · 133 makers",
+            ]
+        );
+        assert_eq!(summary.window("1h").unwrap().trades, 420);
+        assert!(summary.window("5m").is_none());
+    }
+}
@@ -1,34 +1,266 @@
 //! Webhook notification support for swap alerts.
 //!
 //! This module provides asynchronous webhook delivery for swap events,
-//! with retry logic and backoff for reliability.
+//! with retry logic and backoff for reliability. Events are posted as JSON,
+//! rendered from a payload template, or formatted as chat messages for
+//! Discord webhooks and the Telegram Bot API.
+//!
+//! Besides `WEBHOOK_URL`, several webhooks can be declared in a TOML file
+//! (`WEBHOOKS_FILE`), each with its own headers, filter expression, and
+//! payload template:
+//!
+//! ```toml
+//! [[webhooks]]
+//! name = "pagerduty"
+//! url = "https://events.pagerduty.com/v2/enqueue"
+//! filter = "usd_value >= 100000"
+//!
+//! [webhooks.payload]
+//! routing_key = "<integration key>"
+//! event_action = "trigger"
+//! payload = { summary = "{maker} {side} ${usd} on {protocol}", source = "{pool}", severity = "critical" }
+//!
+//! [[webhooks]]
+//! name = "collector"
+//! url = "https://collector.internal/events"
+//! headers = { Authorization = "Bearer <token>" }
+//! filter = "usd_value == null || usd_value < 100000"
+//! ```
+//!
+//! Every string of a payload template is rendered with the rule `emit`
+//! placeholders; `{name}` and `{rule}` are those of rule-emitted events.
+//!
+//! Without a template, events are posted in the webhook's [`SchemaVersion`]
+//! layout (`WEBHOOK_SCHEMA_VERSION`, or `schema_version` of an entry).
+//!
+//! With a [`Sequencer`], every event queued for a webhook carries the next
+//! `seq` of that webhook's stream (also available as `{seq}` in templates).
+//!
+//! With `WEBHOOK_BATCH_SIZE`, JSON and template payloads are posted as arrays
+//! of up to that many events, waiting at most `WEBHOOK_BATCH_MS` for a batch
+//! to fill.
+//!
+//! Request bodies are rendered on `WEBHOOK_SERIALIZE_WORKERS` threads per
+//! webhook (a [`SerializePool`]), a batch split across them, so formatting
+//! does not run on the async runtime shared with ingest.
+//!
+//! With `WEBHOOK_SPOOL_DIR`, events that exhausted their retries are spooled to
+//! `<dir>/<name>.jsonl` instead of being dropped and replayed in order once the
+//! webhook accepts requests again, including after a restart.
+//!
+//! [`WebhookNotifier::shutdown`] stops accepting events and waits for the
+//! queued ones to be delivered (or spooled), so stopping the binary does not
+//! drop queued alerts.
+//!
+//! With [`WebhookNotifier::with_metrics`], deliveries are reported as the
+//! `webhook_delivery_ms` histogram (from taking the event off the queue, or
+//! opening its batch, until the webhook accepted it, retries included) and
+//! the `webhook_delivery_failures` counter.
+//!
+//! Deliveries run in a `webhook` span carrying the slot and signature of the
+//! event, or the slot range of the batch, so a failed delivery logs the block
+//! it originates from.
 
 use {
-    super::SwapEvent,
-    std::{env, sync::Arc, time::Duration},
-    tokio::sync::mpsc,
+    super::{
+        spool::Spool, template::DEFAULT_TEMPLATE, Notifier, SchemaVersion, Sequencer,
+        SerializePool, SwapEvent,
+    },
+    crate::rules::{render_template, unknown_placeholder, Expr, PLACEHOLDERS},
+    async_trait::async_trait,
+    carbon_core::metrics::MetricsCollection,
+    reqwest::header::{HeaderName, HeaderValue},
+    serde::Deserialize,
+    serde_json::json,
+    std::{
+        collections::BTreeMap,
+        env, fmt, fs,
+        path::{Path, PathBuf},
+        sync::{Arc, Mutex, OnceLock, PoisonError},
+        time::{Duration, Instant},
+    },
+    tokio::{
+        sync::{mpsc, watch},
+        task::JoinHandle,
+    },
+    tracing::Instrument,
 };
 
+/// Events buffered per webhook; if the buffer fills, `send()` waits for space.
+const QUEUE_CAPACITY: usize = 1000;
+
+/// How often a non-empty spool is retried.
+const SPOOL_REPLAY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Spooled requests read per replay batch.
+const SPOOL_REPLAY_BATCH: usize = 100;
+
+/// Default time a batch waits to fill before it is posted.
+const DEFAULT_BATCH_MS: u64 = 1000;
+
+/// Errors that can occur while loading a webhooks file.
+#[derive(Debug)]
+pub enum WebhookError {
+    /// The webhooks file could not be read
+    Read(String),
+    /// The webhooks file is not valid TOML or has an unknown shape
+    Parse(String),
+    /// A webhook is invalid (e.g., duplicate name, bad header)
+    Invalid(String),
+}
+
+impl fmt::Display for WebhookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(e) => write!(f, "failed to read webhooks file: {e}"),
+            Self::Parse(e) => write!(f, "failed to parse webhooks file: {e}"),
+            Self::Invalid(e) => write!(f, "invalid webhook: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for WebhookError {}
+
+/// Body format of webhook requests.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebhookFormat {
+    /// The event as JSON in a versioned layout
+    Json(SchemaVersion),
+    /// A Discord webhook message with the text-formatted event
+    Discord {
+        /// Text template of the message (default: `default`)
+        template: Option<String>,
+    },
+    /// A Telegram Bot API `sendMessage` request with the text-formatted event
+    Telegram {
+        /// Chat or channel receiving the message
+        chat_id: i64,
+        /// Text template of the message (default: `default`)
+        template: Option<String>,
+    },
+    /// A JSON document whose strings are `{placeholder}` templates
+    Template(serde_json::Value),
+}
+
+impl Default for WebhookFormat {
+    fn default() -> Self {
+        Self::Json(SchemaVersion::default())
+    }
+}
+
+impl WebhookFormat {
+    /// Builds the request body for an event.
+    pub fn payload(&self, event: &SwapEvent) -> serde_json::Result<String> {
+        match self {
+            Self::Json(schema) => schema.to_json(event),
+            Self::Discord { template } => serde_json::to_string(&json!({
+                "content": event.format_text_with(template.as_deref().unwrap_or(DEFAULT_TEMPLATE)),
+            })),
+            Self::Telegram { chat_id, template } => serde_json::to_string(&json!({
+                "chat_id": chat_id,
+                "text": event.format_text_with(template.as_deref().unwrap_or(DEFAULT_TEMPLATE)),
+                "disable_web_page_preview": true,
+            })),
+            Self::Template(template) => serde_json::to_string(&render_payload(template, event)),
+        }
+    }
+
+    /// Returns `true` if payloads can be posted as a JSON array.
+    ///
+    /// Chat APIs accept a single message per request.
+    pub fn supports_batches(&self) -> bool {
+        matches!(self, Self::Json(_) | Self::Template(_))
+    }
+}
+
+/// Renders every string of a payload template for an event; keys are kept as is.
+fn render_payload(template: &serde_json::Value, event: &SwapEvent) -> serde_json::Value {
+    use serde_json::Value;
+    match template {
+        Value::String(text) => {
+            let (name, rule) = match event.custom {
+                Some(ref custom) => (custom.name.clone(), custom.rule.clone()),
+                None => (event.event_type.to_string(), String::new()),
+            };
+            Value::String(render_template(text, event, &name, &rule))
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| render_payload(item, event))
+                .collect(),
+        ),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| (key.clone(), render_payload(value, event)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Returns the first unknown placeholder used by a payload template.
+fn unknown_payload_placeholder(template: &serde_json::Value) -> Option<String> {
+    use serde_json::Value;
+    match template {
+        Value::String(text) => unknown_placeholder(text).map(str::to_string),
+        Value::Array(items) => items.iter().find_map(unknown_payload_placeholder),
+        Value::Object(fields) => fields.values().find_map(unknown_payload_placeholder),
+        _ => None,
+    }
+}
+
+/// Limits of the batches posted to a webhook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WebhookBatch {
+    /// Events per request at most
+    pub max_events: usize,
+    /// How long the oldest event of a batch waits for the batch to fill
+    pub max_wait: Duration,
+}
+
 /// Configuration for webhook notifications.
 #[derive(Debug, Clone)]
 pub struct WebhookConfig {
+    /// Name shown in delivery logs
+    pub name: String,
     /// Webhook URL to POST events to
     pub url: String,
+    /// Additional request headers (e.g., `Authorization`)
+    pub headers: Vec<(String, String)>,
+    /// Only events matching this expression are delivered (default: all)
+    pub filter: Option<Expr>,
     /// Request timeout
     pub timeout: Duration,
     /// Maximum retry attempts for failed deliveries
     pub max_retries: u32,
     /// Initial backoff duration between retries
     pub retry_backoff: Duration,
+    /// Request body format
+    pub format: WebhookFormat,
+    /// Posts events in batches instead of one request each (default: disabled)
+    pub batch: Option<WebhookBatch>,
+    /// Directory spooling events that exhausted their retries (default: dropped)
+    pub spool_dir: Option<PathBuf>,
+    /// Threads rendering request bodies; 0 renders on the delivery task (default: 1)
+    pub serialize_workers: usize,
 }
 
 impl Default for WebhookConfig {
     fn default() -> Self {
         Self {
+            name: "webhook".to_string(),
             url: String::new(),
+            headers: Vec::new(),
+            filter: None,
             timeout: Duration::from_secs(10),
             max_retries: 3,
             retry_backoff: Duration::from_millis(500),
+            format: WebhookFormat::default(),
+            batch: None,
+            spool_dir: None,
+            serialize_workers: 1,
         }
     }
 }
@@ -42,6 +274,12 @@ impl WebhookConfig {
     /// - `WEBHOOK_TIMEOUT_SECS` - Optional: Request timeout in seconds (default: 10)
     /// - `WEBHOOK_MAX_RETRIES` - Optional: Max retry attempts (default: 3)
     /// - `WEBHOOK_RETRY_BACKOFF_MS` - Optional: Initial backoff in ms (default: 500)
+    /// - `WEBHOOK_BATCH_SIZE` - Optional: Events posted per request as a JSON array (default: 1)
+    /// - `WEBHOOK_BATCH_MS` - Optional: Max wait in ms for a batch to fill (default: 1000)
+    /// - `WEBHOOK_SPOOL_DIR` - Optional: Directory spooling undeliverable events for replay
+    /// - `WEBHOOK_SCHEMA_VERSION` - Optional: JSON layout of events, `1` or `2` (default: 2)
+    /// - `WEBHOOK_SERIALIZE_WORKERS` - Optional: Threads rendering request bodies per webhook,
+    ///   0 renders them on the delivery task (default: 1)
     ///
     /// # Returns
     ///
@@ -52,6 +290,19 @@ impl WebhookConfig {
             return None;
         }
 
+        Some(Self {
+            name: "default".to_string(),
+            url,
+            ..Self::delivery_from_env()
+        })
+    }
+
+    /// Returns the default configuration with the delivery settings
+    /// (`WEBHOOK_TIMEOUT_SECS`, `WEBHOOK_MAX_RETRIES`, `WEBHOOK_RETRY_BACKOFF_MS`,
+    /// `WEBHOOK_BATCH_SIZE`, `WEBHOOK_BATCH_MS`, `WEBHOOK_SPOOL_DIR`,
+    /// `WEBHOOK_SERIALIZE_WORKERS`) and the JSON layout
+    /// (`WEBHOOK_SCHEMA_VERSION`) read from the environment.
+    fn delivery_from_env() -> Self {
         let timeout_secs: u64 = env::var("WEBHOOK_TIMEOUT_SECS")
             .ok()
             .and_then(|v| v.parse().ok())
@@ -67,19 +318,183 @@ impl WebhookConfig {
             .and_then(|v| v.parse().ok())
             .unwrap_or(500);
 
-        Some(Self {
-            url,
+        let batch_size: usize = env::var("WEBHOOK_BATCH_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1);
+
+        let batch_ms: u64 = env::var("WEBHOOK_BATCH_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_BATCH_MS);
+
+        let spool_dir = env::var("WEBHOOK_SPOOL_DIR")
+            .ok()
+            .filter(|dir| !dir.trim().is_empty())
+            .map(|dir| PathBuf::from(dir.trim()));
+
+        let serialize_workers: usize = env::var("WEBHOOK_SERIALIZE_WORKERS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1);
+
+        Self {
             timeout: Duration::from_secs(timeout_secs),
             max_retries,
             retry_backoff: Duration::from_millis(retry_backoff_ms),
-        })
+            batch: (batch_size > 1).then(|| WebhookBatch {
+                max_events: batch_size,
+                max_wait: Duration::from_millis(batch_ms),
+            }),
+            spool_dir,
+            serialize_workers,
+            format: WebhookFormat::Json(SchemaVersion::from_env()),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the spool file of this webhook (`<spool_dir>/<name>.jsonl`).
+    ///
+    /// Characters other than ASCII alphanumerics, `-`, and `_` in the name
+    /// become `_`.
+    fn spool_path(&self) -> Option<PathBuf> {
+        let name: String = self
+            .name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        Some(self.spool_dir.as_ref()?.join(format!("{name}.jsonl")))
+    }
+
+    /// Loads every globally configured webhook: `WEBHOOK_URL` followed by the
+    /// webhooks declared in `WEBHOOKS_FILE`.
+    pub fn all_from_env() -> Result<Vec<Self>, WebhookError> {
+        let mut webhooks: Vec<Self> = Self::from_env().into_iter().collect();
+        if let Ok(path) = env::var("WEBHOOKS_FILE") {
+            if !path.trim().is_empty() {
+                webhooks.extend(Self::load_file(path.trim())?);
+            }
+        }
+        Ok(webhooks)
+    }
+
+    /// Reads, parses, and validates a webhooks file.
+    pub fn load_file(path: impl AsRef<Path>) -> Result<Vec<Self>, WebhookError> {
+        let path = path.as_ref();
+        let toml = fs::read_to_string(path)
+            .map_err(|e| WebhookError::Read(format!("{}: {e}", path.display())))?;
+        Self::parse_file(&toml)
+    }
+
+    /// Parses and validates the `[[webhooks]]` of a webhooks file.
+    ///
+    /// Delivery settings, and the JSON layout of webhooks without a
+    /// `schema_version`, are taken from the `WEBHOOK_*` environment variables.
+    pub fn parse_file(toml: &str) -> Result<Vec<Self>, WebhookError> {
+        let file: WebhooksFile =
+            toml::from_str(toml).map_err(|e| WebhookError::Parse(e.to_string()))?;
+
+        let mut names = std::collections::HashSet::new();
+        let mut webhooks = Vec::with_capacity(file.webhooks.len());
+        for entry in file.webhooks {
+            let name = entry.name.trim().to_string();
+            if name.is_empty() {
+                return Err(WebhookError::Invalid("webhook without a name".to_string()));
+            }
+            if !names.insert(name.clone()) {
+                return Err(WebhookError::Invalid(format!(
+                    "duplicate webhook name '{name}'"
+                )));
+            }
+            if entry.url.trim().is_empty() {
+                return Err(WebhookError::Invalid(format!(
+                    "webhook '{name}' has no url"
+                )));
+            }
+            for (header, value) in &entry.headers {
+                if HeaderName::from_bytes(header.as_bytes()).is_err()
+                    || HeaderValue::from_str(value).is_err()
+                {
+                    return Err(WebhookError::Invalid(format!(
+                        "webhook '{name}' has an invalid header '{header}'"
+                    )));
+                }
+            }
+            if let Some(placeholder) = entry.payload.as_ref().and_then(unknown_payload_placeholder)
+            {
+                return Err(WebhookError::Invalid(format!(
+                    "webhook '{name}' uses unknown placeholder '{{{placeholder}}}' (expected one of: {})",
+                    PLACEHOLDERS.join(", ")
+                )));
+            }
+
+            let delivery = Self::delivery_from_env();
+            let format = match (entry.payload, entry.schema_version) {
+                (Some(_), Some(_)) => {
+                    return Err(WebhookError::Invalid(format!(
+                        "webhook '{name}' has a payload template and a schema_version, \
+                         which applies to JSON events only"
+                    )))
+                }
+                (Some(template), None) => WebhookFormat::Template(template),
+                (None, Some(schema)) => WebhookFormat::Json(schema),
+                (None, None) => delivery.format.clone(),
+            };
+
+            webhooks.push(Self {
+                name,
+                url: entry.url.trim().to_string(),
+                headers: entry.headers.into_iter().collect(),
+                filter: entry.filter,
+                format,
+                ..delivery
+            });
+        }
+        Ok(webhooks)
     }
 }
 
-/// Asynchronous webhook notifier that delivers swap events to a configured endpoint.
+/// Contents of a webhooks file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WebhooksFile {
+    #[serde(default)]
+    webhooks: Vec<WebhookEntry>,
+}
+
+/// A webhook as declared in a webhooks file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WebhookEntry {
+    /// Unique name, used in logs
+    name: String,
+    /// URL to POST events to
+    url: String,
+    /// Additional request headers
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+    /// Expression an event must match (default: every event)
+    #[serde(default)]
+    filter: Option<Expr>,
+    /// JSON payload template (default: the event as JSON)
+    #[serde(default)]
+    payload: Option<serde_json::Value>,
+    /// JSON layout of events (default: `WEBHOOK_SCHEMA_VERSION`)
+    #[serde(default)]
+    schema_version: Option<SchemaVersion>,
+}
+
+/// Asynchronous webhook notifier that delivers swap events to one or more endpoints.
 ///
-/// Uses a background task with a channel to decouple event production from delivery,
-/// preventing webhook latency from blocking swap processing.
+/// Uses a background task per webhook with a channel to decouple event
+/// production from delivery, preventing webhook latency from blocking swap
+/// processing or the other webhooks.
 ///
 /// # Example
 ///
@@ -92,15 +507,35 @@ impl WebhookConfig {
 ///
 /// // Send events (non-blocking)
 /// notifier.send(swap_event).await;
-///
-/// // Graceful shutdown
-/// notifier.shutdown().await;
 /// ```
 pub struct WebhookNotifier {
+    routes: Vec<Route>,
+    /// Optional sequencer numbering the events queued per webhook
+    sequencer: Option<Arc<Sequencer>>,
+    /// Tells the delivery tasks to stop accepting events and drain their queues
+    closing: watch::Sender<bool>,
+    /// Delivery tasks, awaited on shutdown
+    tasks: Mutex<Vec<JoinHandle<()>>>,
+    /// Metrics the delivery tasks report to, once set
+    metrics: Arc<OnceLock<Arc<MetricsCollection>>>,
+}
+
+/// One webhook's filter and delivery queue.
+struct Route {
+    /// Webhook name, identifying its sequence stream
+    name: String,
+    /// Only matching events are queued
+    filter: Option<Expr>,
     /// Channel sender for queuing events
     tx: mpsc::Sender<SwapEvent>,
-    /// Handle to the background delivery task
-    _task_handle: tokio::task::JoinHandle<()>,
+}
+
+impl Route {
+    fn accepts(&self, event: &SwapEvent) -> bool {
+        self.filter
+            .as_ref()
+            .is_none_or(|filter| filter.matches(event))
+    }
 }
 
 impl WebhookNotifier {
@@ -113,23 +548,65 @@ impl WebhookNotifier {
     ///
     /// * `config` - Webhook configuration including URL and retry settings
     pub fn new(config: WebhookConfig) -> Self {
-        // Channel buffer size: 1000 events should handle burst traffic
-        // If the buffer fills, send() will block until space is available
-        let (tx, rx) = mpsc::channel::<SwapEvent>(1000);
-        let config = Arc::new(config);
-
-        let task_handle = tokio::spawn(Self::delivery_task(rx, config));
+        Self::fan_out(vec![config])
+    }
 
+    /// Creates a notifier delivering each event to every webhook whose filter
+    /// matches it, with a queue and background task per webhook.
+    pub fn fan_out(configs: Vec<WebhookConfig>) -> Self {
+        let (closing, _) = watch::channel(false);
+        let metrics = Arc::new(OnceLock::new());
+        let mut tasks = Vec::with_capacity(configs.len());
+        let routes = configs
+            .into_iter()
+            .map(|config| {
+                let (tx, rx) = mpsc::channel::<SwapEvent>(QUEUE_CAPACITY);
+                let name = config.name.clone();
+                let filter = config.filter.clone();
+                tasks.push(tokio::spawn(Self::delivery_task(
+                    rx,
+                    closing.subscribe(),
+                    Arc::new(config),
+                    metrics.clone(),
+                )));
+                Route { name, filter, tx }
+            })
+            .collect();
         Self {
-            tx,
-            _task_handle: task_handle,
+            routes,
+            sequencer: None,
+            closing,
+            tasks: Mutex::new(tasks),
+            metrics,
         }
     }
 
-    /// Queues a swap event for webhook delivery.
+    /// Numbers the events queued for each webhook with `sequencer`.
+    pub fn with_sequencer(mut self, sequencer: Arc<Sequencer>) -> Self {
+        self.sequencer = Some(sequencer);
+        self
+    }
+
+    /// Reports deliveries to `metrics` (`webhook_delivery_ms`,
+    /// `webhook_delivery_failures`).
+    pub fn with_metrics(self, metrics: Arc<MetricsCollection>) -> Self {
+        let _ = self.metrics.set(metrics);
+        self
+    }
+
+    /// Copies an event for a route, assigning the route's next sequence number.
+    fn event_for(&self, route: &Route, event: &SwapEvent) -> SwapEvent {
+        let mut event = event.clone();
+        if let Some(ref sequencer) = self.sequencer {
+            sequencer.assign(&route.name, &mut event);
+        }
+        event
+    }
+
+    /// Queues a swap event for delivery to every matching webhook.
     ///
-    /// This is non-blocking unless the internal buffer is full.
-    /// Events are delivered asynchronously by the background task.
+    /// This is non-blocking unless an internal buffer is full.
+    /// Events are delivered asynchronously by the background tasks.
     ///
     /// # Arguments
     ///
@@ -137,13 +614,21 @@ impl WebhookNotifier {
     ///
     /// # Returns
     ///
-    /// `Ok(())` if queued successfully, `Err` if the channel is closed.
-    #[allow(dead_code)]
+    /// `Ok(())` if queued successfully, `Err` if a channel is closed.
     pub async fn send(&self, event: SwapEvent) -> Result<(), mpsc::error::SendError<SwapEvent>> {
-        self.tx.send(event).await
+        let mut result = Ok(());
+        for route in self.routes.iter().filter(|route| route.accepts(&event)) {
+            if let Err(e) = route.tx.send(self.event_for(route, &event)).await {
+                result = result.and(Err(e));
+            }
+        }
+        result
     }
 
-    /// Tries to queue a swap event without blocking.
+    /// Tries to queue a swap event for every matching webhook without blocking.
+    ///
+    /// A full queue only drops the event for its own webhook; with a sequencer
+    /// the dropped event keeps its `seq` and can be backfilled.
     ///
     /// # Arguments
     ///
@@ -151,106 +636,711 @@ impl WebhookNotifier {
     ///
     /// # Returns
     ///
-    /// `Ok(())` if queued successfully, `Err` if the channel is full or closed.
+    /// `Ok(())` if queued successfully, the first error if a channel is full or closed.
     #[allow(clippy::result_large_err)]
     pub fn try_send(&self, event: SwapEvent) -> Result<(), mpsc::error::TrySendError<SwapEvent>> {
-        self.tx.try_send(event)
+        let mut result = Ok(());
+        for route in self.routes.iter().filter(|route| route.accepts(&event)) {
+            if let Err(e) = route.tx.try_send(self.event_for(route, &event)) {
+                result = result.and(Err(e));
+            }
+        }
+        result
     }
 
     /// Background task that processes the event queue and delivers webhooks.
-    async fn delivery_task(mut rx: mpsc::Receiver<SwapEvent>, config: Arc<WebhookConfig>) {
-        // Create HTTP client with timeout
-        // Note: reqwest is not in dependencies, so we use a simple approach
-        // For production, add reqwest and use it instead
-        let client = match reqwest::Client::builder()
-            .timeout(config.timeout)
-            .build()
-        {
+    ///
+    /// With batching, events are collected until the batch is full or its
+    /// oldest event waited [`WebhookBatch::max_wait`], then posted as one array.
+    ///
+    /// With a spool, requests still undelivered after the last retry are
+    /// written to disk, and while the spool holds requests new ones are
+    /// appended behind them. The spool is replayed every
+    /// [`SPOOL_REPLAY_INTERVAL`] until the webhook accepts requests again.
+    ///
+    /// Once `closing` is set, the queue stops accepting events; the queued
+    /// ones are delivered (without replaying the spool) before the task ends.
+    async fn delivery_task(
+        mut rx: mpsc::Receiver<SwapEvent>,
+        mut closing: watch::Receiver<bool>,
+        config: Arc<WebhookConfig>,
+        metrics: Arc<OnceLock<Arc<MetricsCollection>>>,
+    ) {
+        let client = match reqwest::Client::builder().timeout(config.timeout).build() {
             Ok(c) => c,
             Err(e) => {
                 log::error!("Failed to create HTTP client for webhooks: {e}");
                 return;
             }
         };
+        let mut delivery = Delivery::new(client, config);
+        delivery.metrics = metrics;
+        let mut replay = tokio::time::interval(SPOOL_REPLAY_INTERVAL);
+        replay.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut closed = false;
+
+        loop {
+            let spooling = delivery.spooling() && !closed;
+            let deadline = delivery.batch_deadline;
+            tokio::select! {
+                received = rx.recv() => {
+                    let Some(event) = received else {
+                        break;
+                    };
+                    delivery.accept(event).await;
+                }
+                // Also fires if the notifier was dropped
+                _ = closing.changed(), if !closed => {
+                    rx.close();
+                    closed = true;
+                }
+                _ = tokio::time::sleep_until(deadline.unwrap_or_else(Instant::now).into()),
+                    if deadline.is_some() =>
+                {
+                    delivery.flush_batch().await;
+                }
+                _ = replay.tick(), if spooling => {
+                    delivery.replay(&mut rx).await;
+                }
+            }
+        }
+
+        delivery.flush_batch().await;
+        log::info!(
+            "Webhook '{}' delivery task shutting down",
+            delivery.config.name
+        );
+    }
+
+    /// Stops accepting events and waits up to `timeout` for the queued ones
+    /// to be delivered.
+    ///
+    /// Events sent afterwards are rejected. Requests still undeliverable after
+    /// their retries are spooled if a spool is configured; whatever is left
+    /// when the timeout expires is dropped.
+    pub async fn shutdown(&self, timeout: Duration) {
+        let queued = self.queue_len();
+        self.closing.send_replace(true);
+        let tasks = std::mem::take(&mut *self.tasks.lock().unwrap_or_else(PoisonError::into_inner));
+        if tasks.is_empty() {
+            return;
+        }
+        log::info!("Draining {queued} queued webhook event(s)...");
 
-        while let Some(event) = rx.recv().await {
-            let json = match serde_json::to_string(&event) {
-                Ok(j) => j,
+        let aborts: Vec<_> = tasks.iter().map(JoinHandle::abort_handle).collect();
+        let drained = tokio::time::timeout(timeout, async {
+            for task in tasks {
+                let _ = task.await;
+            }
+        })
+        .await;
+        if drained.is_err() {
+            log::warn!(
+                "Webhook drain timed out after {timeout:?}, dropping {} queued event(s)",
+                self.queue_len()
+            );
+            for abort in aborts {
+                abort.abort();
+            }
+        }
+    }
+
+    /// Returns the number of events currently queued for delivery across webhooks.
+    pub fn queue_len(&self) -> usize {
+        // capacity() - permits available = current queue size
+        // Note: This is an approximation as the channels may change between calls
+        self.routes
+            .iter()
+            .map(|route| QUEUE_CAPACITY - route.tx.capacity())
+            .sum()
+    }
+
+    /// Returns true if every webhook queue is empty.
+    pub fn is_queue_empty(&self) -> bool {
+        self.routes
+            .iter()
+            .all(|route| route.tx.capacity() == QUEUE_CAPACITY)
+    }
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn notify(&self, event: &SwapEvent) {
+        if let Err(e) = self.try_send(event.clone()) {
+            log::warn!("Failed to queue webhook notification: {e}");
+        }
+    }
+
+    async fn shutdown(&self, timeout: Duration) {
+        WebhookNotifier::shutdown(self, timeout).await;
+    }
+}
+
+/// Delivery state of one webhook: its pending batch and spool.
+struct Delivery {
+    client: reqwest::Client,
+    config: Arc<WebhookConfig>,
+    /// Workers rendering request bodies
+    serializer: SerializePool,
+    /// Effective batching; `None` for formats that cannot be batched
+    batch: Option<WebhookBatch>,
+    /// Events collected for the next batch
+    pending: Vec<SwapEvent>,
+    /// Lowest and highest slot of the pending events
+    pending_slots: Option<(u64, u64)>,
+    /// When the pending batch is posted at the latest
+    batch_deadline: Option<Instant>,
+    spool: Option<Spool>,
+    /// Metrics deliveries are reported to, once set
+    metrics: Arc<OnceLock<Arc<MetricsCollection>>>,
+}
+
+impl Delivery {
+    /// Creates the delivery state, starting the webhook's serialization
+    /// workers and opening its spool if configured.
+    ///
+    /// A spool that cannot be opened is logged and undeliverable requests are dropped.
+    fn new(client: reqwest::Client, config: Arc<WebhookConfig>) -> Self {
+        let spool = config
+            .spool_path()
+            .and_then(|path| match Spool::open(&path) {
+                Ok(spool) => Some(spool),
                 Err(e) => {
-                    log::error!("Failed to serialize swap event: {e}");
-                    continue;
+                    log::error!(
+                        "Webhook '{}' spool {} unavailable, failed deliveries will be dropped: {e}",
+                        config.name,
+                        path.display()
+                    );
+                    None
                 }
+            });
+        let batch = config.batch.filter(|_| config.format.supports_batches());
+        if config.batch.is_some() && batch.is_none() {
+            log::warn!(
+                "Webhook '{}' posts chat messages, batching disabled",
+                config.name
+            );
+        }
+        let serializer = SerializePool::new(
+            &format!("webhook-{}", config.name),
+            config.serialize_workers,
+        );
+        Self {
+            client,
+            config,
+            serializer,
+            batch,
+            pending: Vec::new(),
+            pending_slots: None,
+            batch_deadline: None,
+            spool,
+            metrics: Arc::default(),
+        }
+    }
+
+    /// Returns `true` while spooled requests wait for replay.
+    fn spooling(&self) -> bool {
+        self.spool.as_ref().is_some_and(|spool| !spool.is_empty())
+    }
+
+    /// Delivers an event, or adds it to the pending batch.
+    ///
+    /// The event is delivered in a `webhook` span carrying its slot and
+    /// signature.
+    async fn accept(&mut self, event: SwapEvent) {
+        let accepted = Instant::now();
+        let Some(batch) = self.batch else {
+            let span = tracing::info_span!(
+                "webhook",
+                webhook = %self.config.name,
+                slot = event.slot,
+                signature = %event.signature,
+            );
+            let what = format!("slot={} sig={}", event.slot, event.signature);
+            let Some(json) = self.render(vec![event]).await.pop() else {
+                return;
             };
+            self.dispatch(json, &what, accepted).instrument(span).await;
+            return;
+        };
+        if self.pending.is_empty() {
+            self.batch_deadline = Some(accepted + batch.max_wait);
+        }
+        self.pending_slots = Some(match self.pending_slots {
+            Some((first, last)) => (first.min(event.slot), last.max(event.slot)),
+            None => (event.slot, event.slot),
+        });
+        self.pending.push(event);
+        if self.pending.len() >= batch.max_events {
+            self.flush_batch().await;
+        }
+    }
 
-            // Retry loop with exponential backoff
-            let mut attempt = 0;
-            let mut backoff = config.retry_backoff;
-
-            loop {
-                attempt += 1;
-                match client
-                    .post(&config.url)
-                    .header("Content-Type", "application/json")
-                    .body(json.clone())
-                    .send()
+    /// Posts the pending batch as a JSON array.
+    ///
+    /// The batch is delivered in a `webhook` span carrying the slot range of
+    /// its events.
+    async fn flush_batch(&mut self) {
+        let deadline = self.batch_deadline.take();
+        if self.pending.is_empty() {
+            return;
+        }
+        let opened = deadline
+            .zip(self.batch)
+            .and_then(|(deadline, batch)| deadline.checked_sub(batch.max_wait))
+            .unwrap_or_else(Instant::now);
+        let events = std::mem::take(&mut self.pending);
+        let (first_slot, last_slot) = self.pending_slots.take().unwrap_or_default();
+        let events = self.render(events).await;
+        if events.is_empty() {
+            return;
+        }
+        let span = tracing::info_span!(
+            "webhook",
+            webhook = %self.config.name,
+            events = events.len(),
+            first_slot,
+            last_slot,
+        );
+        let json = format!("[{}]", events.join(","));
+        let what = format!(
+            "batch of {} events, slots={first_slot}..={last_slot}",
+            events.len()
+        );
+        self.dispatch(json, &what, opened).instrument(span).await;
+    }
+
+    /// Renders the request bodies of events on the serialization workers.
+    ///
+    /// Events that fail to serialize are logged and left out.
+    async fn render(&self, events: Vec<SwapEvent>) -> Vec<String> {
+        let config = self.config.clone();
+        let rendered = self
+            .serializer
+            .run_chunked(events, move |event| config.format.payload(&event))
+            .await;
+        let Some(rendered) = rendered else {
+            log::error!(
+                "Webhook '{}' failed to render request bodies",
+                self.config.name
+            );
+            return Vec::new();
+        };
+        rendered
+            .into_iter()
+            .filter_map(|json| {
+                json.map_err(|e| log::error!("Failed to serialize swap event: {e}"))
+                    .ok()
+            })
+            .collect()
+    }
+
+    /// Posts a request body with retries, spooling it if undeliverable, and
+    /// reports the delivery time since `since`.
+    ///
+    /// While the spool holds requests, the body is queued behind them instead.
+    async fn dispatch(&mut self, json: String, what: &str, since: Instant) {
+        // Keep delivery order: requests queue behind the spooled ones
+        if self.spooling() {
+            self.spill(&json);
+            return;
+        }
+        let delivered = self.deliver(&json, what).await;
+        if let Some(metrics) = self.metrics.get() {
+            let _ = if delivered {
+                let ms = since.elapsed().as_secs_f64() * 1000.0;
+                metrics.record_histogram("webhook_delivery_ms", ms).await
+            } else {
+                metrics
+                    .increment_counter("webhook_delivery_failures", 1)
                     .await
-                {
-                    Ok(resp) if resp.status().is_success() => {
-                        log::debug!(
-                            "Webhook delivered: sig={}, status={}",
-                            event.signature,
-                            resp.status()
-                        );
-                        break;
-                    }
-                    Ok(resp) => {
-                        log::warn!(
-                            "Webhook failed: sig={}, status={}, attempt={}/{}",
-                            event.signature,
-                            resp.status(),
-                            attempt,
-                            config.max_retries + 1
-                        );
-                    }
-                    Err(e) => {
-                        log::warn!(
-                            "Webhook error: sig={}, err={e}, attempt={}/{}",
-                            event.signature,
-                            attempt,
-                            config.max_retries + 1
-                        );
-                    }
+            };
+        }
+        if !delivered {
+            self.spill(&json);
+        }
+    }
+
+    /// Posts a request body, retrying with exponential backoff.
+    ///
+    /// Returns `true` once the webhook accepted the body.
+    async fn deliver(&self, json: &str, what: &str) -> bool {
+        let config = &self.config;
+        let mut attempt = 0;
+        let mut backoff = config.retry_backoff;
+
+        loop {
+            attempt += 1;
+            match self.post(json).await {
+                Ok(status) => {
+                    log::debug!(
+                        "Webhook '{}' delivered: {what}, status={status}",
+                        config.name
+                    );
+                    return true;
+                }
+                Err(e) => {
+                    log::warn!(
+                        "Webhook '{}' failed: {what}, {e}, attempt={}/{}",
+                        config.name,
+                        attempt,
+                        config.max_retries + 1
+                    );
                 }
+            }
+
+            if attempt > config.max_retries {
+                log::error!(
+                    "Webhook '{}' delivery failed after {} attempts: {what}",
+                    config.name,
+                    attempt
+                );
+                return false;
+            }
 
-                if attempt > config.max_retries {
+            // Exponential backoff
+            tokio::time::sleep(backoff).await;
+            backoff *= 2;
+        }
+    }
+
+    /// Posts a request body once, returning the success status or the failure.
+    async fn post(&self, json: &str) -> Result<reqwest::StatusCode, String> {
+        let mut request = self
+            .client
+            .post(&self.config.url)
+            .header("Content-Type", "application/json");
+        for (name, value) in &self.config.headers {
+            request = request.header(name, value);
+        }
+        match request.body(json.to_string()).send().await {
+            Ok(resp) if resp.status().is_success() => Ok(resp.status()),
+            Ok(resp) => Err(format!("status={}", resp.status())),
+            // The URL may contain credentials (e.g., a Telegram bot token)
+            Err(e) => Err(format!("err={}", e.without_url())),
+        }
+    }
+
+    /// Writes an undelivered body to the spool, or drops it without one.
+    fn spill(&mut self, json: &str) {
+        let Some(ref mut spool) = self.spool else {
+            return;
+        };
+        if let Err(e) = spool.push(json) {
+            log::error!(
+                "Webhook '{}' failed to spool request to {}: {e}",
+                self.config.name,
+                spool.path().display()
+            );
+        }
+    }
+
+    /// Delivers spooled bodies in order until the spool is empty or a delivery fails.
+    ///
+    /// Events received meanwhile are accepted between batches, i.e. appended
+    /// to the spool or the pending batch.
+    async fn replay(&mut self, rx: &mut mpsc::Receiver<SwapEvent>) {
+        let config = self.config.clone();
+        let mut replayed = 0;
+        while self.spooling() {
+            let Some(ref spool) = self.spool else {
+                return;
+            };
+            let batch = match spool.peek(SPOOL_REPLAY_BATCH) {
+                Ok(batch) => batch,
+                Err(e) => {
                     log::error!(
-                        "Webhook delivery failed after {} attempts: sig={}",
-                        attempt,
-                        event.signature
+                        "Webhook '{}' failed to read spool {}: {e}",
+                        config.name,
+                        spool.path().display()
                     );
+                    return;
+                }
+            };
+
+            let mut delivered = 0;
+            for json in &batch {
+                if let Err(e) = self.post(json).await {
+                    log::debug!("Webhook '{}' still unavailable: {e}", config.name);
                     break;
                 }
+                delivered += 1;
+            }
+            let Some(ref mut spool) = self.spool else {
+                return;
+            };
+            if let Err(e) = spool.consume(&batch[..delivered]) {
+                log::error!(
+                    "Webhook '{}' failed to update spool {}: {e}",
+                    config.name,
+                    spool.path().display()
+                );
+                return;
+            }
+            replayed += delivered;
+            if delivered < batch.len() {
+                break;
+            }
 
-                // Exponential backoff
-                tokio::time::sleep(backoff).await;
-                backoff *= 2;
+            while let Ok(event) = rx.try_recv() {
+                self.accept(event).await;
             }
         }
 
-        log::info!("Webhook delivery task shutting down");
+        if replayed > 0 {
+            log::info!(
+                "Webhook '{}' replayed {replayed} spooled request(s){}",
+                config.name,
+                if self.spooling() {
+                    ", more pending"
+                } else {
+                    ""
+                }
+            );
+        }
     }
+}
 
-    /// Returns the number of events currently queued for delivery.
-    #[allow(dead_code)]
-    pub fn queue_len(&self) -> usize {
-        // capacity() - permits available = current queue size
-        // Note: This is an approximation as the channel may change between calls
-        1000 - self.tx.capacity()
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::output::Protocol};
+
+    #[test]
+    fn test_payload_formats() {
+        let event = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig123")
+            .pool("pool")
+            .build();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&WebhookFormat::default().payload(&event).unwrap()).unwrap();
+        assert_eq!(json["signature"], "sig123");
+        assert_eq!(json["schema_version"], 2);
+
+        let json: serde_json::Value = serde_json::from_str(
+            &WebhookFormat::Json(SchemaVersion::V1)
+                .payload(&event)
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(json["schema_version"], 1);
+        assert!(json.get("event_id").is_none());
+
+        let discord: serde_json::Value = serde_json::from_str(
+            &WebhookFormat::Discord { template: None }
+                .payload(&event)
+                .unwrap(),
+        )
+        .unwrap();
+        assert!(discord["content"]
+            .as_str()
+            .unwrap()
+            .contains("🔄 SWAP [CPMM]"));
+
+        let telegram: serde_json::Value = serde_json::from_str(
+            &WebhookFormat::Telegram {
+                chat_id: -100,
+                template: None,
+            }
+            .payload(&event)
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(telegram["chat_id"], -100);
+        assert!(telegram["text"].as_str().unwrap().contains("[CPMM]"));
     }
 
-    /// Returns true if the webhook queue is empty.
-    #[allow(dead_code)]
-    pub fn is_queue_empty(&self) -> bool {
-        self.tx.capacity() == 1000
+    #[tokio::test]
+    async fn test_undeliverable_batch_is_spooled_as_array() {
+        let dir = std::env::temp_dir().join(format!(
+            "raydium-alerts-webhook-batch-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        let config = WebhookConfig {
+            name: "collector".to_string(),
+            // Nothing listens on the discard port
+            url: "http://127.0.0.1:9/events".to_string(),
+            max_retries: 0,
+            batch: Some(WebhookBatch {
+                max_events: 2,
+                max_wait: Duration::from_secs(60),
+            }),
+            spool_dir: Some(dir.clone()),
+            ..WebhookConfig::default()
+        };
+        let mut delivery = Delivery::new(reqwest::Client::new(), Arc::new(config));
+
+        for signature in ["sig1", "sig2"] {
+            let event = SwapEvent::builder()
+                .protocol(Protocol::Cpmm)
+                .signature(signature)
+                .pool("pool")
+                .build();
+            delivery.accept(event).await;
+        }
+        assert!(delivery.pending.is_empty());
+        assert!(delivery.batch_deadline.is_none());
+        assert!(delivery.spooling());
+
+        let spooled = delivery.spool.as_ref().unwrap().peek(10).unwrap();
+        assert_eq!(spooled.len(), 1);
+        let batch: Vec<serde_json::Value> = serde_json::from_str(&spooled[0]).unwrap();
+        assert_eq!(batch[0]["signature"], "sig1");
+        assert_eq!(batch[1]["signature"], "sig2");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_drains_queued_events() {
+        let dir = std::env::temp_dir().join(format!(
+            "raydium-alerts-webhook-shutdown-{}",
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        let config = WebhookConfig {
+            name: "collector".to_string(),
+            // Nothing listens on the discard port
+            url: "http://127.0.0.1:9/events".to_string(),
+            max_retries: 0,
+            batch: Some(WebhookBatch {
+                max_events: 10,
+                max_wait: Duration::from_secs(60),
+            }),
+            spool_dir: Some(dir.clone()),
+            ..WebhookConfig::default()
+        };
+        let spool_path = config.spool_path().unwrap();
+        let notifier = WebhookNotifier::new(config);
+        let event = |signature| {
+            SwapEvent::builder()
+                .protocol(Protocol::Cpmm)
+                .signature(signature)
+                .pool("pool")
+                .build()
+        };
+
+        notifier.try_send(event("sig1")).unwrap();
+        notifier.try_send(event("sig2")).unwrap();
+        // The partial batch is flushed (and spooled) instead of waiting 60s
+        notifier.shutdown(Duration::from_secs(10)).await;
+        assert!(notifier.try_send(event("sig3")).is_err());
+
+        let spooled = Spool::open(&spool_path).unwrap().peek(10).unwrap();
+        assert_eq!(spooled.len(), 1);
+        let batch: Vec<serde_json::Value> = serde_json::from_str(&spooled[0]).unwrap();
+        assert_eq!(batch.len(), 2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_chat_formats_are_not_batched() {
+        assert!(WebhookFormat::default().supports_batches());
+        assert!(!WebhookFormat::Discord { template: None }.supports_batches());
+        assert!(!WebhookFormat::Telegram {
+            chat_id: 1,
+            template: None
+        }
+        .supports_batches());
+    }
+
+    const WEBHOOKS: &str = r#"
+        [[webhooks]]
+        name = "pagerduty"
+        url = "https://events.pagerduty.com/v2/enqueue"
+        filter = "usd_value >= 100000"
+
+        [webhooks.payload]
+        event_action = "trigger"
+        payload = { summary = "{event_type} of ${usd} in {pool}", tags = ["{protocol}"], severity = 1 }
+
+        [[webhooks]]
+        name = "collector"
+        url = "https://collector.internal/events"
+        headers = { Authorization = "Bearer secret" }
+        schema_version = 1
+    "#;
+
+    #[test]
+    fn test_parse_webhooks_file() {
+        let webhooks = WebhookConfig::parse_file(WEBHOOKS).unwrap();
+        assert_eq!(webhooks.len(), 2);
+
+        let mut input = crate::output::TokenInfo::new("MintA", 1);
+        input.amount_usd = Some(250_000.0);
+        let whale = SwapEvent::builder()
+            .protocol(Protocol::Clmm)
+            .signature("sig")
+            .pool("PoolA")
+            .input_token(input)
+            .build();
+        let small = SwapEvent::builder().signature("sig").pool("PoolA").build();
+
+        let pagerduty = &webhooks[0];
+        let filter = pagerduty.filter.as_ref().unwrap();
+        assert!(filter.matches(&whale));
+        assert!(!filter.matches(&small));
+        let payload: serde_json::Value =
+            serde_json::from_str(&pagerduty.format.payload(&whale).unwrap()).unwrap();
+        assert_eq!(
+            payload,
+            json!({
+                "event_action": "trigger",
+                "payload": {
+                    "summary": "SWAP of $250.00K in PoolA",
+                    "tags": ["CLMM"],
+                    "severity": 1,
+                },
+            })
+        );
+
+        let collector = &webhooks[1];
+        assert!(collector.filter.is_none());
+        assert_eq!(collector.format, WebhookFormat::Json(SchemaVersion::V1));
+        assert_eq!(
+            collector.headers,
+            [("Authorization".to_string(), "Bearer secret".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_spool_path() {
+        let config = WebhookConfig {
+            name: "ops/alerts v2".to_string(),
+            ..WebhookConfig::default()
+        };
+        assert_eq!(config.spool_path(), None);
+
+        let config = WebhookConfig {
+            spool_dir: Some(PathBuf::from("spool")),
+            ..config
+        };
+        assert_eq!(
+            config.spool_path(),
+            Some(PathBuf::from("spool/ops_alerts_v2.jsonl"))
+        );
+    }
+
+    #[test]
+    fn test_parse_webhooks_file_rejects_invalid() {
+        let invalid = [
+            "[[webhooks]]\nname = \"a\"\nurl = \"\"",
+            "[[webhooks]]\nname = \"a\"\nurl = \"u\"\n[[webhooks]]\nname = \"a\"\nurl = \"u\"",
+            "[[webhooks]]\nname = \"a\"\nurl = \"u\"\nheaders = { \"bad header\" = \"x\" }",
+            "[[webhooks]]\nname = \"a\"\nurl = \"u\"\nfilter = \"usd_value >\"",
+            "[[webhooks]]\nname = \"a\"\nurl = \"u\"\npayload = { text = \"{bogus}\" }",
+            "[[webhooks]]\nname = \"a\"\nurl = \"u\"\nretries = 3",
+            "[[webhooks]]\nname = \"a\"\nurl = \"u\"\nschema_version = 3",
+            "[[webhooks]]\nname = \"a\"\nurl = \"u\"\nschema_version = 1\npayload = { a = \"{pool}\" }",
+        ];
+        for toml in invalid {
+            assert!(WebhookConfig::parse_file(toml).is_err(), "{toml}");
+        }
+        assert!(WebhookConfig::parse_file("").unwrap().is_empty());
     }
 }
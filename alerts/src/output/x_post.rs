@@ -0,0 +1,441 @@
+//! Auto-posting of rule alerts to X (Twitter).
+//!
+//! Rules post whale swaps or new pools to an X account with an `x`
+//! destination, so only the alerts a rule selects are posted:
+//!
+//! ```toml
+//! destinations = [{ type = "x", template = "tweet" }]
+//! ```
+//!
+//! Posts are created with the v2 API (`POST /2/tweets`), signed with the
+//! OAuth 1.0a user credentials of the posting account (`X_API_KEY`,
+//! `X_API_SECRET`, `X_ACCESS_TOKEN`, `X_ACCESS_SECRET`). The text is the
+//! destination's `template` (or `default`), falling back to a compact built-in
+//! layout, truncated to 280 characters as X counts them: links count as 23,
+//! and emoji and CJK characters as 2.
+//!
+//! At most `X_MAX_POSTS_PER_DAY` posts are made in any 24 hours, shared by
+//! every X destination and kept across rule reloads; alerts over the limit
+//! are dropped.
+
+use {
+    super::{template, SwapEvent},
+    crate::{clock, rules::render_text},
+    base64::{engine::general_purpose::STANDARD as BASE64, Engine},
+    hmac::{Hmac, Mac},
+    serde_json::json,
+    sha1::Sha1,
+    std::{
+        collections::VecDeque,
+        env,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Mutex, PoisonError,
+        },
+        time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+    },
+    tokio::sync::mpsc,
+};
+
+/// Default X API server.
+pub const DEFAULT_API_URL: &str = "https://api.x.com";
+
+/// Layout of posts without a template.
+const DEFAULT_POST: &str = "{emoji} {name} [{protocol}]
+💵 ${usd} · {side} {token_symbol}
+{explorer}";
+
+/// Longest post, in X's weighted characters.
+pub const MAX_POST_WEIGHT: usize = 280;
+
+/// Weight of a link, whatever its length.
+const URL_WEIGHT: usize = 23;
+
+/// Default posts per 24 hours (the limit of the free API tier).
+const DEFAULT_MAX_POSTS_PER_DAY: usize = 17;
+
+/// Window of the post limit.
+const POST_WINDOW: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Alerts queued per X destination before new ones are dropped.
+const QUEUE_CAPACITY: usize = 100;
+
+/// Request timeout of a post.
+const POST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Times of the posts of the last 24 hours, across destinations.
+static RECENT_POSTS: Mutex<VecDeque<Instant>> = Mutex::new(VecDeque::new());
+
+/// Makes nonces of requests in the same nanosecond unique.
+static NONCE_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+/// OAuth 1.0a user credentials of the posting account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XCredentials {
+    /// API key (consumer key) of the app
+    pub api_key: String,
+    /// API key secret of the app
+    pub api_secret: String,
+    /// Access token of the account
+    pub access_token: String,
+    /// Access token secret of the account
+    pub access_secret: String,
+}
+
+/// Configuration of one X destination.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XConfig {
+    /// Credentials of the posting account
+    pub credentials: XCredentials,
+    /// API server, without a trailing slash
+    pub api_url: String,
+    /// Most posts in any 24 hours
+    pub max_posts_per_day: usize,
+    /// Text template of the posts (default: `default`)
+    pub template: Option<String>,
+}
+
+impl XConfig {
+    /// Creates the configuration of an X destination from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `X_API_KEY`, `X_API_SECRET` - API key and secret of the X app
+    /// - `X_ACCESS_TOKEN`, `X_ACCESS_SECRET` - Access token and secret of the posting account
+    /// - `X_API_URL` - Optional: API server (default: https://api.x.com)
+    /// - `X_MAX_POSTS_PER_DAY` - Optional: Most posts in any 24 hours (default: 17)
+    ///
+    /// # Returns
+    ///
+    /// `None` if any of the credentials is missing.
+    pub fn from_env(template: Option<String>) -> Option<Self> {
+        let credentials = XCredentials {
+            api_key: non_empty("X_API_KEY")?,
+            api_secret: non_empty("X_API_SECRET")?,
+            access_token: non_empty("X_ACCESS_TOKEN")?,
+            access_secret: non_empty("X_ACCESS_SECRET")?,
+        };
+        let api_url = non_empty("X_API_URL").unwrap_or_else(|| DEFAULT_API_URL.to_string());
+        let max_posts_per_day = non_empty("X_MAX_POSTS_PER_DAY")
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(DEFAULT_MAX_POSTS_PER_DAY);
+        Some(Self {
+            credentials,
+            api_url: api_url.trim_end_matches('/').to_string(),
+            max_posts_per_day,
+            template,
+        })
+    }
+
+    /// Renders the text of a post of an alert, truncated to [`MAX_POST_WEIGHT`].
+    pub fn render(&self, event: &SwapEvent) -> String {
+        let name = self
+            .template
+            .as_deref()
+            .unwrap_or(template::DEFAULT_TEMPLATE);
+        let text = template::render(name, event).unwrap_or_else(|| {
+            let (name, rule) = match event.custom {
+                Some(ref custom) => (custom.name.clone(), custom.rule.clone()),
+                None => (event.event_type.to_string(), String::new()),
+            };
+            render_text(DEFAULT_POST, event, &name, &rule)
+        });
+        truncate(&text, MAX_POST_WEIGHT)
+    }
+}
+
+/// Returns a trimmed, non-empty environment variable.
+fn non_empty(name: &str) -> Option<String> {
+    env::var(name)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Returns the weight X counts for a character: 1 for Latin and common
+/// punctuation, 2 for everything else (emoji, CJK).
+fn char_weight(c: char) -> usize {
+    match c as u32 {
+        0..=0x10FF | 0x2000..=0x200D | 0x2010..=0x201F | 0x2032..=0x2037 => 1,
+        _ => 2,
+    }
+}
+
+/// Returns `true` if a whitespace-free word is a link.
+fn is_url(word: &str) -> bool {
+    word.starts_with("https://") || word.starts_with("http://")
+}
+
+/// Returns the weight of a whitespace-free word; links count [`URL_WEIGHT`].
+fn word_weight(word: &str) -> usize {
+    if is_url(word) {
+        URL_WEIGHT
+    } else {
+        word.chars().map(char_weight).sum()
+    }
+}
+
+/// Returns the weighted length of a post.
+pub fn post_weight(text: &str) -> usize {
+    text.split_inclusive(char::is_whitespace)
+        .map(|token| {
+            let word = token.trim_end_matches(char::is_whitespace);
+            word_weight(word) + (token.len() - word.len())
+        })
+        .sum()
+}
+
+/// Truncates a post to `max` weighted characters, ending it with `…`.
+///
+/// Links are never cut: a link that does not fit is left out.
+pub fn truncate(text: &str, max: usize) -> String {
+    let text = text.trim_end();
+    if post_weight(text) <= max {
+        return text.to_string();
+    }
+    let budget = max.saturating_sub(char_weight('…'));
+    let mut truncated = String::new();
+    let mut weight = 0;
+    for token in text.split_inclusive(char::is_whitespace) {
+        let word = token.trim_end_matches(char::is_whitespace);
+        let token_weight = word_weight(word) + (token.len() - word.len());
+        if weight + token_weight <= budget {
+            truncated.push_str(token);
+            weight += token_weight;
+            continue;
+        }
+        if !is_url(word) {
+            for c in token.chars() {
+                if weight + char_weight(c) > budget {
+                    break;
+                }
+                truncated.push(c);
+                weight += char_weight(c);
+            }
+        }
+        break;
+    }
+    let mut truncated = truncated.trim_end().to_string();
+    truncated.push('…');
+    truncated
+}
+
+/// Percent-encodes a value as OAuth 1.0a requires (RFC 3986 unreserved kept).
+fn percent_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Returns the OAuth 1.0a HMAC-SHA1 signature of a request with `params`
+/// (OAuth and query parameters; JSON bodies are not signed).
+fn signature(
+    method: &str,
+    url: &str,
+    params: &[(&str, &str)],
+    consumer_secret: &str,
+    token_secret: &str,
+) -> String {
+    let mut encoded: Vec<(String, String)> = params
+        .iter()
+        .map(|(key, value)| (percent_encode(key), percent_encode(value)))
+        .collect();
+    encoded.sort();
+    let parameters = encoded
+        .iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<_>>()
+        .join("&");
+    let base = format!(
+        "{method}&{}&{}",
+        percent_encode(url),
+        percent_encode(&parameters)
+    );
+    let key = format!(
+        "{}&{}",
+        percent_encode(consumer_secret),
+        percent_encode(token_secret)
+    );
+    let mut mac = Hmac::<Sha1>::new_from_slice(key.as_bytes()).expect("HMAC accepts any key");
+    mac.update(base.as_bytes());
+    BASE64.encode(mac.finalize().into_bytes())
+}
+
+/// Returns the OAuth 1.0a `Authorization` header of a POST to `url`.
+fn authorization(credentials: &XCredentials, url: &str) -> String {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let timestamp = now.as_secs().to_string();
+    let nonce = format!(
+        "{:x}{:x}",
+        now.as_nanos(),
+        NONCE_COUNTER.fetch_add(1, Ordering::Relaxed)
+    );
+    let mut params = vec![
+        ("oauth_consumer_key", credentials.api_key.as_str()),
+        ("oauth_nonce", nonce.as_str()),
+        ("oauth_signature_method", "HMAC-SHA1"),
+        ("oauth_timestamp", timestamp.as_str()),
+        ("oauth_token", credentials.access_token.as_str()),
+        ("oauth_version", "1.0"),
+    ];
+    let signature = signature(
+        "POST",
+        url,
+        &params,
+        &credentials.api_secret,
+        &credentials.access_secret,
+    );
+    params.push(("oauth_signature", signature.as_str()));
+    let fields = params
+        .iter()
+        .map(|(key, value)| format!("{key}=\"{}\"", percent_encode(value)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("OAuth {fields}")
+}
+
+/// Takes a slot of the shared post limit, or returns `false` if the limit
+/// is reached. The window follows the installed [`clock`].
+fn take_post_slot(max_posts_per_day: usize) -> bool {
+    let now = clock::now();
+    let mut posts = RECENT_POSTS.lock().unwrap_or_else(PoisonError::into_inner);
+    while posts
+        .front()
+        .is_some_and(|&posted| now.duration_since(posted) >= POST_WINDOW)
+    {
+        posts.pop_front();
+    }
+    if posts.len() >= max_posts_per_day {
+        return false;
+    }
+    posts.push_back(now);
+    true
+}
+
+/// Posts rule alerts to X.
+pub struct XNotifier {
+    tx: mpsc::Sender<SwapEvent>,
+}
+
+impl XNotifier {
+    /// Creates the notifier and spawns its posting task.
+    ///
+    /// Must be called within a Tokio runtime.
+    pub fn new(config: XConfig) -> Self {
+        let (tx, rx) = mpsc::channel(QUEUE_CAPACITY);
+        tokio::spawn(Self::posting_task(rx, config));
+        Self { tx }
+    }
+
+    /// Tries to queue an alert without blocking.
+    #[allow(clippy::result_large_err)]
+    pub fn try_send(&self, event: SwapEvent) -> Result<(), mpsc::error::TrySendError<SwapEvent>> {
+        self.tx.try_send(event)
+    }
+
+    /// Posts queued alerts while the post limit allows.
+    async fn posting_task(mut rx: mpsc::Receiver<SwapEvent>, config: XConfig) {
+        let client = reqwest::Client::builder()
+            .timeout(POST_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        let url = format!("{}/2/tweets", config.api_url);
+
+        while let Some(event) = rx.recv().await {
+            if !take_post_slot(config.max_posts_per_day) {
+                log::warn!(
+                    "X post limit of {} per day reached, dropping {}",
+                    config.max_posts_per_day,
+                    event.signature
+                );
+                continue;
+            }
+            let result = client
+                .post(&url)
+                .header("Authorization", authorization(&config.credentials, &url))
+                .json(&json!({ "text": config.render(&event) }))
+                .send()
+                .await;
+            match result {
+                Ok(response) if response.status().is_success() => {
+                    log::debug!("Posted {} to X", event.signature);
+                }
+                Ok(response) => {
+                    let status = response.status();
+                    let body = response.text().await.unwrap_or_default();
+                    log::warn!(
+                        "Failed to post {} to X: HTTP {status}: {body}",
+                        event.signature
+                    );
+                }
+                Err(e) => log::warn!("Failed to post {} to X: {e}", event.signature),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oauth_signature() {
+        // Example request of the X OAuth 1.0a signing documentation
+        let params = [
+            (
+                "status",
+                "Hello Ladies + Gentlemen, a signed OAuth request!",
+            ),
+            ("include_entities", "true"),
+            ("oauth_consumer_key", "xvz1evFS4wEEPTGEFPHBog"),
+            ("oauth_nonce", "kYjzVBB8Y0ZFabxSWbWovY3uYSQ2pTgmZeNu2VS4cg"),
+            ("oauth_signature_method", "HMAC-SHA1"),
+            ("oauth_timestamp", "1318622958"),
+            (
+                "oauth_token",
+                "370773112-GmHxMAgYyLbNEtIKZeRNFsMKPR9EyMZeS9weJAEb",
+            ),
+            ("oauth_version", "1.0"),
+        ];
+        assert_eq!(
+            signature(
+                "POST",
+                "https://api.twitter.com/1.1/statuses/update.json",
+                &params,
+                "kAcSOqF21Fu85e7zjz7ZN2U4ZRhfV3WpwPAoE3Z7kBw",
+                "LswwdoUaIvS8ltyTt5jkRh4J50vUPVVHtR2YPi5kE",
+            ),
+            "hCtSmYh+iHYCEqBWrE7C7hYmtUk="
+        );
+    }
+
+    #[test]
+    fn test_truncate_by_weight() {
+        let link = "https://solscan.io/tx/5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW";
+        assert_eq!(
+            post_weight(&format!("🐋 buy {link}")),
+            2 + 1 + 3 + 1 + URL_WEIGHT
+        );
+
+        let short = format!("🐋 buy\n{link}");
+        assert_eq!(truncate(&short, MAX_POST_WEIGHT), short);
+
+        let long = format!("{} {link}", "a".repeat(270));
+        let truncated = truncate(&long, MAX_POST_WEIGHT);
+        assert_eq!(truncated, format!("{}…", "a".repeat(270)));
+        assert!(post_weight(&truncated) <= MAX_POST_WEIGHT);
+
+        let words = "word ".repeat(100);
+        assert!(post_weight(&truncate(&words, MAX_POST_WEIGHT)) <= MAX_POST_WEIGHT);
+    }
+}
@@ -0,0 +1,741 @@
+//! Embeddable monitoring pipeline.
+//!
+//! [`PipelineBuilder`] wires the selected market processors to the outputs
+//! given to it (alert channels, storage, streaming hub, detectors)
+//! and builds a Carbon [`Pipeline`] over any datasource. The binary builds
+//! every component from environment variables (see [`crate::app`]); an
+//! embedding project can pass its own instead, e.g. a custom
+//! [`StorageSink`] or a [`BatchWriter`](crate::storage::BatchWriter) spawned
+//! with [`BatchSink::spawn`](crate::storage::BatchSink::spawn), or an alert
+//! channel implementing [`Notifier`]:
+//!
+//! ```ignore
+//! let sink = BatchSink::spawn(MyWriter::new(), StorageConfig::default(), None);
+//! let pipeline = PipelineBuilder::new(MarketType::ALL.into_iter().collect(), config, OutputFormat::Json)
+//!     .with_storage(Arc::new(sink))
+//!     .with_notifier(Arc::new(MyNotifier::new()))
+//!     .build(datasource)?;
+//! pipeline.run().await?;
+//! ```
+//!
+//! The chain profile, clock, and number format are process-wide and are
+//! installed before the builder is used; the defaults apply otherwise.
+
+use {
+    crate::{
+        aggregation::VolumeAggregator,
+        chain::{self, Deployed, Deployment},
+        config::{ConcurrencyConfig, MarketType},
+        drain::DrainDetector,
+        enrichment::{EnrichmentCache, EnrichmentPipeline},
+        listings::ListingTracker,
+        lp_burn::LpBurnTracker,
+        metrics::{Activity, Heartbeat, SummaryConfig, SummaryMetrics},
+        mev::MevDetector,
+        output::{DedupConfig, EventLog, Notifier, Notifiers, OutputFormat, SlotBatcher},
+        pool_stats::PoolStatsTracker,
+        processors::{
+            EventEmitter, FilterSet, PumpFunInstructionProcessor, PumpSwapInstructionProcessor,
+            RaydiumAmmV4InstructionProcessor, RaydiumClmmInstructionProcessor,
+            RaydiumCpmmInstructionProcessor, RaydiumLaunchpadInstructionProcessor,
+            ShardedProcessor,
+        },
+        reload::SharedConfig,
+        server::StreamHub,
+        stats::StatsStore,
+        storage::StorageSink,
+        throttle::AlertThrottle,
+    },
+    carbon_core::{
+        datasource::Datasource, error::CarbonResult, metrics::Metrics, pipeline::Pipeline,
+    },
+    carbon_log_metrics::LogMetrics,
+    carbon_pump_swap_decoder::PumpSwapDecoder,
+    carbon_pumpfun_decoder::PumpfunDecoder,
+    carbon_raydium_amm_v4_decoder::RaydiumAmmV4Decoder,
+    carbon_raydium_clmm_decoder::RaydiumClmmDecoder,
+    carbon_raydium_cpmm_decoder::RaydiumCpmmDecoder,
+    carbon_raydium_launchpad_decoder::RaydiumLaunchpadDecoder,
+    std::{
+        collections::{HashMap, HashSet},
+        sync::{Arc, OnceLock},
+    },
+};
+
+/// Processors of the selected markets and the outputs they publish events to.
+pub struct PipelineBuilder {
+    filter_markets: HashSet<MarketType>,
+    config: SharedConfig,
+    output_format: OutputFormat,
+    /// Worker settings per market, the defaults for markets not set
+    concurrency: HashMap<MarketType, ConcurrencyConfig>,
+    event_log: Arc<EventLog>,
+    notifiers: Vec<Arc<dyn Notifier>>,
+    /// Dispatch tasks of `notifiers`, spawned by the first build
+    dispatch: OnceLock<Arc<Notifiers>>,
+    stream_hub: Option<Arc<StreamHub>>,
+    stats: Option<Arc<StatsStore>>,
+    aggregator: Option<Arc<VolumeAggregator>>,
+    mev: Option<Arc<MevDetector>>,
+    drain: Option<Arc<DrainDetector>>,
+    listings: Option<Arc<ListingTracker>>,
+    lp_burn: Option<Arc<LpBurnTracker>>,
+    pool_stats: Option<Arc<PoolStatsTracker>>,
+    throttle: Option<Arc<AlertThrottle>>,
+    storage_sink: Option<Arc<dyn StorageSink>>,
+    enrichment: Option<Arc<EnrichmentPipeline>>,
+    enrichment_cache: Option<Arc<EnrichmentCache>>,
+    dedup: Option<DedupConfig>,
+    slot_batcher: Option<SlotBatcher>,
+    metrics: Vec<Arc<dyn Metrics>>,
+    summary: SummaryConfig,
+    activity: Arc<Activity>,
+    heartbeat: Arc<Heartbeat>,
+}
+
+impl PipelineBuilder {
+    /// Creates a builder for the processors of `markets`, filtering and
+    /// routing events with the live `config` and logging them in `output_format`.
+    pub fn new(
+        markets: HashSet<MarketType>,
+        config: SharedConfig,
+        output_format: OutputFormat,
+    ) -> Self {
+        Self {
+            filter_markets: markets,
+            config,
+            output_format,
+            concurrency: HashMap::new(),
+            event_log: Arc::new(EventLog::new(output_format, 1)),
+            notifiers: Vec::new(),
+            dispatch: OnceLock::new(),
+            stream_hub: None,
+            stats: None,
+            aggregator: None,
+            mev: None,
+            drain: None,
+            listings: None,
+            lp_burn: None,
+            pool_stats: None,
+            throttle: None,
+            storage_sink: None,
+            enrichment: None,
+            enrichment_cache: None,
+            dedup: None,
+            slot_batcher: None,
+            metrics: Vec::new(),
+            summary: SummaryConfig::default(),
+            activity: Arc::new(Activity::new()),
+            heartbeat: Arc::new(Heartbeat::new()),
+        }
+    }
+
+    /// Runs the processors of `market` on `concurrency` workers (default: one
+    /// worker with a 1000-instruction channel).
+    pub fn with_concurrency(mut self, market: MarketType, concurrency: ConcurrencyConfig) -> Self {
+        self.concurrency.insert(market, concurrency);
+        self
+    }
+
+    /// Logs emitted events with `event_log` (default: one formatting thread).
+    pub fn with_event_log(mut self, event_log: Arc<EventLog>) -> Self {
+        self.event_log = event_log;
+        self
+    }
+
+    /// Sends alerts to an alert channel, such as a
+    /// [`WebhookNotifier`](crate::output::WebhookNotifier), the
+    /// [`SubscriptionBus`](crate::subscriptions::SubscriptionBus), or a custom
+    /// [`Notifier`]. Each notifier gets every alert, on its own task.
+    pub fn with_notifier(mut self, notifier: Arc<dyn Notifier>) -> Self {
+        self.notifiers.push(notifier);
+        self
+    }
+
+    /// Publishes events to streaming clients and the query API.
+    pub fn with_stream_hub(mut self, hub: Arc<StreamHub>) -> Self {
+        self.stream_hub = Some(hub);
+        self
+    }
+
+    /// Records per-token statistics.
+    pub fn with_stats(mut self, stats: Arc<StatsStore>) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    /// Records rolling per-pool volume.
+    pub fn with_aggregator(mut self, aggregator: Arc<VolumeAggregator>) -> Self {
+        self.aggregator = Some(aggregator);
+        self
+    }
+
+    /// Detects sandwiches among swaps.
+    pub fn with_mev(mut self, mev: Arc<MevDetector>) -> Self {
+        self.mev = Some(mev);
+        self
+    }
+
+    /// Detects liquidity drains.
+    pub fn with_drain(mut self, drain: Arc<DrainDetector>) -> Self {
+        self.drain = Some(drain);
+        self
+    }
+
+    /// Only emits swaps on recently created pools and tokens.
+    pub fn with_listings(mut self, listings: Arc<ListingTracker>) -> Self {
+        self.listings = Some(listings);
+        self
+    }
+
+    /// Badges the events of new pools whose LP supply was burned.
+    pub fn with_lp_burn(mut self, lp_burn: Arc<LpBurnTracker>) -> Self {
+        self.lp_burn = Some(lp_burn);
+        self
+    }
+
+    /// Attaches per-pool running totals to every published event.
+    pub fn with_pool_stats(mut self, pool_stats: Arc<PoolStatsTracker>) -> Self {
+        self.pool_stats = Some(pool_stats);
+        self
+    }
+
+    /// Withholds alerts over the token and pool limits.
+    pub fn with_throttle(mut self, throttle: Arc<AlertThrottle>) -> Self {
+        self.throttle = Some(throttle);
+        self
+    }
+
+    /// Persists events to a storage sink, such as a custom
+    /// [`BatchWriter`](crate::storage::BatchWriter) or several sinks combined
+    /// with [`FanoutSink`](crate::storage::FanoutSink).
+    pub fn with_storage(mut self, sink: Arc<dyn StorageSink>) -> Self {
+        self.storage_sink = Some(sink);
+        self
+    }
+
+    /// Enriches events before they are published.
+    pub fn with_enrichment(mut self, pipeline: Arc<EnrichmentPipeline>) -> Self {
+        self.enrichment = Some(pipeline);
+        self
+    }
+
+    /// Resolves CLMM, Launchpad, and PumpSwap pools and AMM V4 accounts from
+    /// an RPC-backed cache.
+    pub fn with_enrichment_cache(mut self, cache: Arc<EnrichmentCache>) -> Self {
+        self.enrichment_cache = Some(cache);
+        self
+    }
+
+    /// Collapses duplicate swaps within a window.
+    pub fn with_dedup(mut self, config: DedupConfig) -> Self {
+        self.dedup = Some(config);
+        self
+    }
+
+    /// Holds events until their slot is complete.
+    pub fn with_slot_batcher(mut self, batcher: SlotBatcher) -> Self {
+        self.slot_batcher = Some(batcher);
+        self
+    }
+
+    /// Reports the pipeline metrics to another backend (e.g., StatsD), next
+    /// to the log and activity summary metrics.
+    pub fn with_metrics(mut self, metrics: Arc<dyn Metrics>) -> Self {
+        self.metrics.push(metrics);
+        self
+    }
+
+    /// Sets the activity summary logged on each metrics flush.
+    pub fn with_summary(mut self, summary: SummaryConfig) -> Self {
+        self.summary = summary;
+        self
+    }
+
+    /// Returns the notifiers of the built pipeline, to drain on shutdown.
+    pub fn notifiers(&self) -> Option<&Arc<Notifiers>> {
+        self.dispatch.get()
+    }
+
+    /// Returns the slot batcher, to flush on shutdown.
+    pub fn slot_batcher(&self) -> Option<&SlotBatcher> {
+        self.slot_batcher.as_ref()
+    }
+
+    /// Returns the time of the last metric update, for a watchdog.
+    pub fn heartbeat(&self) -> &Arc<Heartbeat> {
+        &self.heartbeat
+    }
+
+    /// Returns the worker settings of a market's processors.
+    fn concurrency(&self, market: MarketType) -> ConcurrencyConfig {
+        self.concurrency.get(&market).copied().unwrap_or_default()
+    }
+
+    /// Returns the live filters of a market's processors.
+    fn filters(&self, market: MarketType) -> FilterSet {
+        FilterSet::new(self.config.clone(), market).with_activity(self.activity.clone())
+    }
+
+    /// Returns the emit path of the processor of one program deployment.
+    ///
+    /// AMM V4 swaps have no event-log variants to collapse, so they are not
+    /// deduplicated.
+    fn emitter(&self, deployment: &Deployment, notifiers: &Arc<Notifiers>) -> EventEmitter {
+        let mut emitter = EventEmitter::new(
+            deployment.market,
+            self.config.clone(),
+            self.event_log.clone(),
+            notifiers.clone(),
+        );
+        if let Some(ref label) = deployment.label {
+            emitter = emitter.with_deployment(label.clone());
+        }
+        if let Some(ref sink) = self.storage_sink {
+            emitter = emitter.with_storage(sink.clone());
+        }
+        if let Some(ref pipeline) = self.enrichment {
+            emitter = emitter.with_enrichment(pipeline.clone());
+        }
+        if let Some(ref hub) = self.stream_hub {
+            emitter = emitter.with_stream_hub(hub.clone());
+        }
+        if let Some(ref stats) = self.stats {
+            emitter = emitter.with_stats(stats.clone());
+        }
+        if let Some(ref aggregator) = self.aggregator {
+            emitter = emitter.with_aggregator(aggregator.clone());
+        }
+        if let Some(ref mev) = self.mev {
+            emitter = emitter.with_mev(mev.clone());
+        }
+        if let Some(ref drain) = self.drain {
+            emitter = emitter.with_drain(drain.clone());
+        }
+        if let Some(ref listings) = self.listings {
+            emitter = emitter.with_listings(listings.clone());
+        }
+        if let Some(ref lp_burn) = self.lp_burn {
+            emitter = emitter.with_lp_burn(lp_burn.clone());
+        }
+        if let Some(ref pool_stats) = self.pool_stats {
+            emitter = emitter.with_pool_stats(pool_stats.clone());
+        }
+        if let Some(ref throttle) = self.throttle {
+            emitter = emitter.with_throttle(throttle.clone());
+        }
+        if let Some(ref batcher) = self.slot_batcher {
+            emitter = emitter.with_slot_batch(batcher);
+        }
+        match self.dedup {
+            Some(dedup) if deployment.market != MarketType::AmmV4 => emitter.with_dedup(dedup),
+            _ => emitter,
+        }
+    }
+
+    /// Builds the pipeline over `datasource` with only the selected market processors.
+    ///
+    /// Each market's processor runs once per deployment of the chain profile,
+    /// on the workers set with [`with_concurrency`](Self::with_concurrency).
+    pub fn build(&self, datasource: impl Datasource + 'static) -> CarbonResult<Pipeline> {
+        let mut builder = Pipeline::builder()
+            .datasource(datasource)
+            .metrics(Arc::new(LogMetrics::new()))
+            .metrics(Arc::new(SummaryMetrics::new(
+                self.summary,
+                self.activity.clone(),
+            )))
+            .metrics(self.heartbeat.clone())
+            .metrics_flush_interval(3);
+        for metrics in &self.metrics {
+            builder = builder.metrics(metrics.clone());
+        }
+        let notifiers = self
+            .dispatch
+            .get_or_init(|| Arc::new(Notifiers::spawn(self.notifiers.clone())));
+
+        // Add CPMM decoder if enabled
+        if self.filter_markets.contains(&MarketType::Cpmm) {
+            let concurrency = self.concurrency(MarketType::Cpmm);
+            for deployment in chain::profile().deployments(MarketType::Cpmm) {
+                let processor = RaydiumCpmmInstructionProcessor::new(
+                    self.filters(MarketType::Cpmm),
+                    self.emitter(&deployment, notifiers),
+                );
+                builder = builder.instruction(
+                    Deployed::new(RaydiumCpmmDecoder, &deployment),
+                    ShardedProcessor::new("cpmm", processor, concurrency)
+                        .with_activity(self.activity.clone()),
+                );
+            }
+            log::info!(
+                "CPMM processor: enabled (workers={}, channel_capacity={})",
+                concurrency.workers,
+                concurrency.channel_capacity
+            );
+        } else {
+            log::info!("CPMM processor: disabled");
+        }
+
+        // Add CLMM decoder if enabled
+        if self.filter_markets.contains(&MarketType::Clmm) {
+            let concurrency = self.concurrency(MarketType::Clmm);
+            for deployment in chain::profile().deployments(MarketType::Clmm) {
+                let mut processor = RaydiumClmmInstructionProcessor::new(
+                    self.filters(MarketType::Clmm),
+                    self.emitter(&deployment, notifiers),
+                );
+                if let Some(ref cache) = self.enrichment_cache {
+                    processor = processor.with_pool_cache(cache.clone());
+                }
+                builder = builder.instruction(
+                    Deployed::new(RaydiumClmmDecoder, &deployment),
+                    ShardedProcessor::new("clmm", processor, concurrency)
+                        .with_activity(self.activity.clone()),
+                );
+            }
+            log::info!(
+                "CLMM processor: enabled (workers={}, channel_capacity={})",
+                concurrency.workers,
+                concurrency.channel_capacity
+            );
+        } else {
+            log::info!("CLMM processor: disabled");
+        }
+
+        // Add AMM V4 decoder if enabled
+        if self.filter_markets.contains(&MarketType::AmmV4) {
+            let concurrency = self.concurrency(MarketType::AmmV4);
+            for deployment in chain::profile().deployments(MarketType::AmmV4) {
+                let mut processor = RaydiumAmmV4InstructionProcessor::new(
+                    self.filters(MarketType::AmmV4),
+                    self.emitter(&deployment, notifiers),
+                );
+                if let Some(ref cache) = self.enrichment_cache {
+                    processor = processor.with_account_cache(cache.clone());
+                }
+                builder = builder.instruction(
+                    Deployed::new(RaydiumAmmV4Decoder, &deployment),
+                    ShardedProcessor::new("amm_v4", processor, concurrency)
+                        .with_activity(self.activity.clone()),
+                );
+            }
+            log::info!(
+                "AMM V4 processor: enabled (workers={}, channel_capacity={})",
+                concurrency.workers,
+                concurrency.channel_capacity
+            );
+        } else {
+            log::info!("AMM V4 processor: disabled");
+        }
+
+        // Add Launchpad decoder if enabled
+        if self.filter_markets.contains(&MarketType::Launchpad) {
+            let concurrency = self.concurrency(MarketType::Launchpad);
+            for deployment in chain::profile().deployments(MarketType::Launchpad) {
+                let mut processor = RaydiumLaunchpadInstructionProcessor::new(
+                    self.filters(MarketType::Launchpad),
+                    self.emitter(&deployment, notifiers),
+                );
+                if let Some(ref cache) = self.enrichment_cache {
+                    processor = processor.with_pool_cache(cache.clone());
+                }
+                builder = builder.instruction(
+                    Deployed::new(RaydiumLaunchpadDecoder, &deployment),
+                    ShardedProcessor::new("launchpad", processor, concurrency)
+                        .with_activity(self.activity.clone()),
+                );
+            }
+            log::info!(
+                "Launchpad processor: enabled (workers={}, channel_capacity={})",
+                concurrency.workers,
+                concurrency.channel_capacity
+            );
+        } else {
+            log::info!("Launchpad processor: disabled");
+        }
+
+        // Add Pump.fun decoder if enabled
+        if self.filter_markets.contains(&MarketType::PumpFun) {
+            let concurrency = self.concurrency(MarketType::PumpFun);
+            for deployment in chain::profile().deployments(MarketType::PumpFun) {
+                let processor = PumpFunInstructionProcessor::new(
+                    self.filters(MarketType::PumpFun),
+                    self.emitter(&deployment, notifiers),
+                );
+                builder = builder.instruction(
+                    Deployed::new(PumpfunDecoder, &deployment),
+                    ShardedProcessor::new("pumpfun", processor, concurrency)
+                        .with_activity(self.activity.clone()),
+                );
+            }
+            log::info!(
+                "Pump.fun processor: enabled (workers={}, channel_capacity={})",
+                concurrency.workers,
+                concurrency.channel_capacity
+            );
+        } else {
+            log::info!("Pump.fun processor: disabled");
+        }
+
+        // Add PumpSwap decoder if enabled
+        if self.filter_markets.contains(&MarketType::PumpSwap) {
+            let concurrency = self.concurrency(MarketType::PumpSwap);
+            for deployment in chain::profile().deployments(MarketType::PumpSwap) {
+                let mut processor = PumpSwapInstructionProcessor::new(
+                    self.filters(MarketType::PumpSwap),
+                    self.emitter(&deployment, notifiers),
+                );
+                if let Some(ref cache) = self.enrichment_cache {
+                    processor = processor.with_pool_cache(cache.clone());
+                }
+                builder = builder.instruction(
+                    Deployed::new(PumpSwapDecoder, &deployment),
+                    ShardedProcessor::new("pumpswap", processor, concurrency)
+                        .with_activity(self.activity.clone()),
+                );
+            }
+            log::info!(
+                "PumpSwap processor: enabled (workers={}, channel_capacity={})",
+                concurrency.workers,
+                concurrency.channel_capacity
+            );
+        } else {
+            log::info!("PumpSwap processor: disabled");
+        }
+
+        builder
+            .shutdown_strategy(carbon_core::pipeline::ShutdownStrategy::Immediate)
+            .build()
+    }
+
+    /// Logs the chain, filters, and enabled outputs.
+    pub fn log_startup_info(&self, rpc_ws_url: &str) {
+        log::info!("=== Raydium Alert System ===");
+        let chain = chain::profile();
+        log::info!("Chain: {}", chain.name);
+        for market in MarketType::ALL {
+            for deployment in chain.deployments(market) {
+                match deployment.label {
+                    Some(label) => log::info!(
+                        "{} program ID: {} ({label})",
+                        market.name(),
+                        deployment.program_id
+                    ),
+                    None => log::info!("{} program ID: {}", market.name(), deployment.program_id),
+                }
+            }
+        }
+
+        // Log market filter status
+        let market_names: Vec<&str> = MarketType::ALL
+            .iter()
+            .filter(|market| self.filter_markets.contains(market))
+            .map(MarketType::name)
+            .collect();
+        log::info!("Markets filter: {:?}", market_names);
+
+        // Log token filter status
+        let live = self.config.load();
+        let filter_tokens = &live.filter_tokens;
+        if filter_tokens.is_empty() {
+            log::info!("Token filter: disabled (tracking all tokens)");
+        } else {
+            log::info!(
+                "Token filter: {} token(s) - {:?}",
+                filter_tokens.len(),
+                filter_tokens
+            );
+        }
+
+        // Log AMM/pool filter status
+        let filter_amms = &live.filter_amms;
+        if filter_amms.is_empty() {
+            log::info!("AMM/Pool filter: disabled (tracking all AMMs/pools)");
+        } else {
+            log::info!(
+                "AMM/Pool filter: {} address(es) - {:?}",
+                filter_amms.len(),
+                filter_amms
+            );
+        }
+
+        // Log wallet watchlist and labels
+        if live.watch_wallets.is_empty() {
+            log::info!("Wallet watchlist: disabled");
+        } else {
+            log::info!(
+                "Wallet watchlist: {} wallet(s) - {:?}",
+                live.watch_wallets.len(),
+                live.watch_wallets
+            );
+        }
+        log::info!("Address labels: {}", live.labels.len());
+
+        // Log output settings
+        log::info!(
+            "Output format: {:?}",
+            match self.output_format {
+                OutputFormat::Text => "text",
+                OutputFormat::Json => "json",
+                OutputFormat::JsonPretty => "json_pretty",
+            }
+        );
+        log::info!(
+            "Notifiers: {}",
+            if self.notifiers.is_empty() {
+                "none".to_string()
+            } else {
+                self.notifiers
+                    .iter()
+                    .map(|notifier| notifier.name())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }
+        );
+
+        log::info!(
+            "Alert rules: {}",
+            live.rules
+                .as_ref()
+                .map_or("disabled".to_string(), |rules| format!(
+                    "{} rule(s)",
+                    rules.len()
+                ))
+        );
+        log::info!(
+            "Storage: {}",
+            self.storage_sink
+                .as_ref()
+                .map_or("disabled", |sink| sink.name())
+        );
+        log::info!(
+            "Swap deduplication: {}",
+            self.dedup.map_or("disabled".to_string(), |config| format!(
+                "enabled (window={:?})",
+                config.window
+            ))
+        );
+        log::info!(
+            "Enrichers: {}",
+            self.enrichment
+                .as_ref()
+                .map_or("none".to_string(), |pipeline| pipeline.names().join(" -> "))
+        );
+
+        log::info!("RPC WebSocket: {rpc_ws_url}");
+        log::info!("============================");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::reload::LiveConfig,
+        arc_swap::ArcSwap,
+        async_trait::async_trait,
+        carbon_core::{
+            datasource::{DatasourceId, Update, UpdateType},
+            metrics::MetricsCollection,
+        },
+        tokio::sync::mpsc,
+        tokio_util::sync::CancellationToken,
+    };
+
+    /// Notifier dropping every alert.
+    struct Silent;
+
+    #[async_trait]
+    impl Notifier for Silent {
+        fn name(&self) -> &str {
+            "silent"
+        }
+
+        async fn notify(&self, _event: &crate::output::SwapEvent) {}
+    }
+
+    /// Datasource sending no updates.
+    struct NoUpdates;
+
+    #[async_trait]
+    impl Datasource for NoUpdates {
+        async fn consume(
+            &self,
+            _id: DatasourceId,
+            _sender: mpsc::Sender<(Update, DatasourceId)>,
+            _cancellation_token: CancellationToken,
+            _metrics: Arc<MetricsCollection>,
+        ) -> CarbonResult<()> {
+            Ok(())
+        }
+
+        fn update_types(&self) -> Vec<UpdateType> {
+            vec![UpdateType::Transaction]
+        }
+    }
+
+    fn builder(markets: &[MarketType]) -> PipelineBuilder {
+        PipelineBuilder::new(
+            markets.iter().copied().collect(),
+            Arc::new(ArcSwap::from_pointee(LiveConfig::default())),
+            OutputFormat::Json,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_build_registers_selected_markets_only() {
+        let deployments = |market| chain::profile().deployments(market).len();
+
+        for market in MarketType::ALL {
+            let pipeline = builder(&[market]).build(NoUpdates).unwrap();
+            assert_eq!(
+                pipeline.instruction_pipes.len(),
+                deployments(market),
+                "{}",
+                market.name()
+            );
+        }
+
+        let pipeline = builder(&[MarketType::Cpmm, MarketType::PumpSwap])
+            .build(NoUpdates)
+            .unwrap();
+        assert_eq!(
+            pipeline.instruction_pipes.len(),
+            deployments(MarketType::Cpmm) + deployments(MarketType::PumpSwap)
+        );
+        assert!(builder(&[])
+            .build(NoUpdates)
+            .unwrap()
+            .instruction_pipes
+            .is_empty());
+    }
+
+    #[test]
+    fn test_with_concurrency() {
+        let concurrency = ConcurrencyConfig {
+            workers: 4,
+            channel_capacity: 500,
+        };
+        let builder = builder(&MarketType::ALL).with_concurrency(MarketType::Clmm, concurrency);
+        assert_eq!(builder.concurrency(MarketType::Clmm), concurrency);
+        assert_eq!(
+            builder.concurrency(MarketType::Cpmm),
+            ConcurrencyConfig::default()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_spawns_notifiers_once() {
+        let builder = builder(&[MarketType::Cpmm]).with_notifier(Arc::new(Silent));
+        assert!(builder.notifiers().is_none());
+
+        builder.build(NoUpdates).unwrap();
+        let notifiers = builder.notifiers().unwrap().clone();
+        assert_eq!(notifiers.names(), vec!["silent"]);
+
+        // Rebuilt pipelines share the dispatch tasks
+        builder.build(NoUpdates).unwrap();
+        assert!(Arc::ptr_eq(builder.notifiers().unwrap(), &notifiers));
+    }
+}
@@ -0,0 +1,182 @@
+//! Cumulative per-pool statistics attached to emitted events.
+//!
+//! This module provides:
+//! - [`PoolStatsConfig`] - Maximum number of tracked pools
+//! - [`PoolStatsTracker`] - Buys, sells, and USD volume of each pool since it was first seen
+//!
+//! The tracker is fed by the processors with every published event, in
+//! order, and sets the pool's running totals, including the event itself, as
+//! `pool_stats` on it before it is stored, streamed, or alerted on. Totals
+//! start when a pool is first seen, not at its creation, and are lost on
+//! restart. Swaps without a USD value add to the counts but not the volume.
+
+use {
+    crate::{
+        clock,
+        output::{EventType, PoolStats, SwapEvent, TradeSide},
+    },
+    std::{
+        collections::HashMap,
+        env,
+        sync::{Mutex, PoisonError},
+    },
+};
+
+/// Default maximum number of tracked pools.
+const DEFAULT_MAX_POOLS: usize = 50_000;
+
+/// Configuration for per-pool statistics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolStatsConfig {
+    /// Most pools tracked; the least recently seen pool is evicted beyond it
+    pub max_pools: usize,
+}
+
+impl PoolStatsConfig {
+    /// Creates pool statistics configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `POOL_STATS` - `true` to attach per-pool totals to events
+    /// - `POOL_STATS_MAX_POOLS` - Optional: Most pools tracked (default: 50000)
+    ///
+    /// # Returns
+    ///
+    /// `Some(PoolStatsConfig)` if `POOL_STATS` is `true` or `1`, `None` otherwise.
+    pub fn from_env() -> Option<Self> {
+        let enabled = env::var("POOL_STATS")
+            .is_ok_and(|v| matches!(v.trim().to_lowercase().as_str(), "true" | "1"));
+        if !enabled {
+            return None;
+        }
+        let max_pools = match env::var("POOL_STATS_MAX_POOLS") {
+            Ok(value) => match value.trim().parse::<usize>() {
+                Ok(max) if max > 0 => max,
+                _ => {
+                    log::warn!("Invalid POOL_STATS_MAX_POOLS '{value}', using {DEFAULT_MAX_POOLS}");
+                    DEFAULT_MAX_POOLS
+                }
+            },
+            Err(_) => DEFAULT_MAX_POOLS,
+        };
+        Some(Self { max_pools })
+    }
+}
+
+/// Totals of one pool.
+#[derive(Debug)]
+struct PoolEntry {
+    stats: PoolStats,
+    last_seen: i64,
+}
+
+/// Running buys, sells, and volume per pool.
+pub struct PoolStatsTracker {
+    config: PoolStatsConfig,
+    pools: Mutex<HashMap<String, PoolEntry>>,
+}
+
+impl PoolStatsTracker {
+    /// Creates a tracker with no pools.
+    pub fn new(config: PoolStatsConfig) -> Self {
+        Self {
+            config,
+            pools: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Adds a swap to its pool's totals, and sets the pool's totals on the event.
+    ///
+    /// Events of other types get the totals without changing them.
+    pub fn record(&self, event: &mut SwapEvent) {
+        let timestamp = event.timestamp.unwrap_or_else(clock::unix_now);
+        let mut pools = self.pools.lock().unwrap_or_else(PoisonError::into_inner);
+        if !pools.contains_key(&event.pool) && pools.len() >= self.config.max_pools {
+            let oldest = pools
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_seen)
+                .map(|(pool, _)| pool.clone());
+            if let Some(oldest) = oldest {
+                pools.remove(&oldest);
+            }
+        }
+
+        let entry = pools
+            .entry(event.pool.clone())
+            .or_insert_with(|| PoolEntry {
+                stats: PoolStats {
+                    buys: 0,
+                    sells: 0,
+                    volume_usd: 0.0,
+                    first_seen: timestamp,
+                },
+                last_seen: timestamp,
+            });
+        entry.last_seen = entry.last_seen.max(timestamp);
+        if event.event_type == EventType::Swap {
+            match event.side {
+                Some(TradeSide::Buy) => entry.stats.buys += 1,
+                Some(TradeSide::Sell) => entry.stats.sells += 1,
+                None => {}
+            }
+            entry.stats.volume_usd += event.usd_value().unwrap_or_default();
+        }
+        event.pool_stats = Some(entry.stats);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{swap_event::WSOL_MINT, Protocol, TokenInfo},
+    };
+
+    fn buy(pool: &str, usd: f64, timestamp: i64) -> SwapEvent {
+        SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature(format!("sig-{timestamp}"))
+            .pool(pool)
+            .input_token(TokenInfo::new(WSOL_MINT, 9).with_usd_value(usd))
+            .output_token(TokenInfo::new("mint", 6).with_usd_value(usd))
+            .timestamp(timestamp)
+            .build()
+    }
+
+    #[test]
+    fn test_record_accumulates_per_pool() {
+        let tracker = PoolStatsTracker::new(PoolStatsConfig { max_pools: 2 });
+
+        let mut first = buy("pool-a", 10.0, 100);
+        tracker.record(&mut first);
+        let mut second = buy("pool-a", 5.0, 160);
+        tracker.record(&mut second);
+        assert_eq!(
+            second.pool_stats,
+            Some(PoolStats {
+                buys: 2,
+                sells: 0,
+                volume_usd: 15.0,
+                first_seen: 100,
+            })
+        );
+
+        // Non-swap events carry the totals unchanged
+        let mut created = SwapEvent::builder()
+            .event_type(EventType::CreatePool)
+            .protocol(Protocol::Cpmm)
+            .signature("create")
+            .pool("pool-a")
+            .timestamp(170)
+            .build();
+        tracker.record(&mut created);
+        assert_eq!(created.pool_stats.map(|stats| stats.buys), Some(2));
+
+        // The least recently seen pool is evicted beyond the limit
+        tracker.record(&mut buy("pool-b", 1.0, 200));
+        tracker.record(&mut buy("pool-c", 1.0, 300));
+        let mut again = buy("pool-a", 1.0, 400);
+        tracker.record(&mut again);
+        assert_eq!(again.pool_stats.map(|stats| stats.first_seen), Some(400));
+    }
+}
@@ -1,15 +1,26 @@
 //! Raydium AMM V4 instruction processor.
 //!
 //! This module handles decoded instructions from the Raydium AMM V4 program,
-//! with optional filtering by AMM pool addresses.
+//! with optional filtering by token mints and AMM pool addresses.
 //!
-//! Note: AMM V4 doesn't include token mint addresses directly in instruction accounts.
-//! It uses token accounts which would require on-chain lookup to get the mint.
+//! AMM V4 swap instructions reference the user's token accounts rather than
+//! mints. With an [`EnrichmentCache`], the token accounts are resolved to their
+//! mints via RPC (cached in an LRU), falling back to the pool state for an
+//! account that was already closed (e.g., a temporary wSOL account).
+//!
+//! Swap amounts are taken from the program's `ray_log` record of the swap
+//! (see [`RaySwapLog`]), which also carries the pool reserves before the swap;
+//! swaps without a record in the transaction logs fall back to the nested
+//! token transfers.
 
 use {
-    crate::output::{
-        extract_swap_amounts, EventType, OutputFormat, Protocol, SwapDirection, SwapEvent,
-        TokenInfo, WebhookNotifier,
+    super::{EventEmitter, FilterSet},
+    crate::{
+        enrichment::EnrichmentCache,
+        output::{
+            extract_swap_amounts, AdminActionEvent, EventType, PoolCreatedEvent, Protocol,
+            RaySwapLog, SwapDirection, SwapEvent, TokenInfo,
+        },
     },
     async_trait::async_trait,
     carbon_core::{
@@ -18,64 +29,140 @@ use {
         metrics::MetricsCollection, processor::Processor,
     },
     carbon_raydium_amm_v4_decoder::instructions::{
+        initialize::Initialize, initialize2::Initialize2, set_params::SetParams,
         swap_base_in::SwapBaseIn, swap_base_in_v2::SwapBaseInV2, swap_base_out::SwapBaseOut,
-        swap_base_out_v2::SwapBaseOutV2, RaydiumAmmV4Instruction,
+        swap_base_out_v2::SwapBaseOutV2, update_config_account::UpdateConfigAccount,
+        RaydiumAmmV4Instruction,
     },
     solana_pubkey::Pubkey,
-    std::{collections::HashSet, sync::Arc},
+    std::sync::Arc,
 };
 
-/// Processor for Raydium AMM V4 instructions with optional AMM filtering.
+/// Processor for Raydium AMM V4 instructions with optional token and AMM filtering.
 ///
-/// Supports filtering swaps by AMM/pool addresses only.
-/// Token filtering is not available because AMM V4 instructions use token accounts
-/// rather than mint addresses directly.
+/// Token filtering requires [`with_account_cache`](Self::with_account_cache) to
+/// resolve swap mints; swaps whose mints cannot be resolved are matched by AMM only.
+#[derive(Clone)]
 pub struct RaydiumAmmV4InstructionProcessor {
-    /// Set of AMM addresses to filter. Empty means no filter (track all).
-    filter_amms: HashSet<Pubkey>,
-    /// Output format for swap events.
-    output_format: OutputFormat,
-    /// Optional webhook notifier for sending alerts.
-    webhook_notifier: Option<Arc<WebhookNotifier>>,
+    /// Live token, pool, and wallet filters.
+    filters: FilterSet,
+    /// Emit path publishing matched events to the outputs.
+    emitter: EventEmitter,
+    /// Optional cache resolving swap token accounts to mints.
+    account_cache: Option<Arc<EnrichmentCache>>,
 }
 
 impl RaydiumAmmV4InstructionProcessor {
-    /// Creates a new processor with optional AMM filtering and output configuration.
-    ///
-    /// # Arguments
-    ///
-    /// * `filter_amms` - Set of AMM addresses to track. Empty set tracks all AMMs.
-    /// * `output_format` - Format for swap event output (text, json, json_pretty).
-    /// * `webhook_notifier` - Optional webhook notifier for sending alerts.
-    pub fn new(
-        filter_amms: HashSet<Pubkey>,
-        output_format: OutputFormat,
-        webhook_notifier: Option<Arc<WebhookNotifier>>,
-    ) -> Self {
+    /// Creates a processor checking instructions against `filters` and
+    /// publishing the resulting events through `emitter`.
+    pub fn new(filters: FilterSet, emitter: EventEmitter) -> Self {
         Self {
-            filter_amms,
-            output_format,
-            webhook_notifier,
+            filters,
+            emitter,
+            account_cache: None,
         }
     }
 
-    /// Checks if an AMM matches the filter.
-    fn matches_amm_filter(&self, amm: &Pubkey) -> bool {
-        if self.filter_amms.is_empty() {
-            return true;
-        }
-        self.filter_amms.contains(amm)
+    /// Resolves swap token accounts to mints, enabling token filtering and
+    /// reporting mints instead of token accounts.
+    pub fn with_account_cache(mut self, cache: Arc<EnrichmentCache>) -> Self {
+        self.account_cache = Some(cache);
+        self
     }
 
-    /// Outputs a swap event and optionally sends to webhook.
-    async fn emit_event(&self, event: SwapEvent) {
-        log::info!("{}", event.format(self.output_format));
+    /// Resolves the input and output mints of a swap from the user's token accounts.
+    ///
+    /// If only one side resolves (the other account was closed in the same
+    /// transaction), the pool's other mint identifies the missing side.
+    async fn resolve_swap_mints(
+        &self,
+        amm: &Pubkey,
+        source: &Pubkey,
+        destination: &Pubkey,
+    ) -> Option<(Pubkey, Pubkey)> {
+        let cache = self.account_cache.as_ref()?;
+        let mints = cache
+            .token_accounts()
+            .resolve(&[*source, *destination])
+            .await;
 
-        if let Some(ref notifier) = self.webhook_notifier {
-            if let Err(e) = notifier.try_send(event) {
-                log::warn!("Failed to queue webhook notification: {e}");
+        match (mints.get(source).copied(), mints.get(destination).copied()) {
+            (Some(input), Some(output)) => Some((input, output)),
+            (Some(input), None) => {
+                let info = cache.pool_or_resolve(amm).await?;
+                Some((input, info.other_mint(&input)?))
+            }
+            (None, Some(output)) => {
+                let info = cache.pool_or_resolve(amm).await?;
+                Some((info.other_mint(&output)?, output))
             }
+            (None, None) => None,
+        }
+    }
+
+    /// Resolves a swap's mints and applies the filters.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the swap is filtered out; otherwise the `(input, output)`
+    /// mints, or the `(source, destination)` token accounts if unresolved.
+    /// Swaps whose `owner` is a watched wallet are never filtered out.
+    async fn filter_swap(
+        &self,
+        amm: &Pubkey,
+        source: &Pubkey,
+        destination: &Pubkey,
+        owner: &Pubkey,
+    ) -> Option<(Pubkey, Pubkey)> {
+        // Swaps by watched wallets always match; skip lookups for swaps the
+        // AMM filter alone rules out
+        let (watched, filters_tokens) = {
+            let config = self.filters.config().load();
+            (
+                config.watches(Some(owner)),
+                !config.filter_tokens.is_empty(),
+            )
+        };
+        if !watched && !filters_tokens && !self.filters.passes_amm(amm) {
+            self.filters.record(amm, false);
+            return None;
+        }
+
+        let mints = self.resolve_swap_mints(amm, source, destination).await;
+        let matches = watched
+            || match mints {
+                Some((ref input, ref output)) => self.filters.passes(amm, &[input, output], None),
+                None => self.filters.passes_amm(amm),
+            };
+        self.filters
+            .record(amm, matches)
+            .then(|| mints.unwrap_or((*source, *destination)))
+    }
+
+    /// Finds the `ray_log` record of a swap among the lines it logged.
+    ///
+    /// The record's reserves before the swap replace the cached reserves of the
+    /// pool, so the impact enricher estimates the swap against the exact reserves.
+    ///
+    /// # Arguments
+    ///
+    /// * `metadata` - Metadata of the swap instruction
+    /// * `amm` - AMM pool of the swap
+    /// * `base_in` - `true` for `SwapBaseIn(V2)`, `false` for `SwapBaseOut(V2)`
+    /// * `limits` - Amount limits of the instruction (see [`RaySwapLog::limits`])
+    fn ray_swap_log(
+        &self,
+        metadata: &InstructionMetadata,
+        amm: &Pubkey,
+        base_in: bool,
+        limits: (u64, u64),
+    ) -> Option<RaySwapLog> {
+        let logs = metadata.transaction_metadata.meta.log_messages.as_deref()?;
+        let log = RaySwapLog::find(logs, &metadata.absolute_path, base_in, limits)?;
+        if let Some(ref cache) = self.account_cache {
+            cache.set_reserves(amm, log.pool_coin, log.pool_pc);
         }
+        Some(log)
     }
 }
 
@@ -91,7 +178,7 @@ impl Processor for RaydiumAmmV4InstructionProcessor {
     async fn process(
         &mut self,
         (metadata, instruction, nested_instructions, raw_instruction): Self::InputType,
-        _metrics: Arc<MetricsCollection>,
+        metrics: Arc<MetricsCollection>,
     ) -> CarbonResult<()> {
         let signature = metadata.transaction_metadata.signature.to_string();
         let slot = metadata.transaction_metadata.slot;
@@ -100,16 +187,32 @@ impl Processor for RaydiumAmmV4InstructionProcessor {
             // SwapBaseIn - Legacy swap with Serum
             RaydiumAmmV4Instruction::SwapBaseIn(ref swap) => {
                 if let Some(accounts) = SwapBaseIn::arrange_accounts(&raw_instruction.accounts) {
-                    if self.matches_amm_filter(&accounts.amm) {
-                        // Extract actual amounts from nested token transfers
-                        // The instruction's minimum_amount_out is just slippage protection
-                        let (actual_input, actual_output) = extract_swap_amounts(
-                            &nested_instructions,
+                    if let Some((input_mint, output_mint)) = self
+                        .filter_swap(
+                            &accounts.amm,
                             &accounts.user_source_token_account,
                             &accounts.user_destination_token_account,
-                            swap.amount_in,           // fallback to instruction amount
-                            swap.minimum_amount_out,  // fallback to min (not ideal)
-                        );
+                            &accounts.user_source_owner,
+                        )
+                        .await
+                    {
+                        // Actual amounts from the swap's ray_log, or the nested token transfers
+                        // The instruction's minimum_amount_out is just slippage protection
+                        let (actual_input, actual_output) = match self.ray_swap_log(
+                            &metadata,
+                            &accounts.amm,
+                            true,
+                            (swap.amount_in, swap.minimum_amount_out),
+                        ) {
+                            Some(log) => (log.amount_in, log.amount_out),
+                            None => extract_swap_amounts(
+                                &nested_instructions,
+                                &accounts.user_source_token_account,
+                                &accounts.user_destination_token_account,
+                                swap.amount_in,
+                                swap.minimum_amount_out,
+                            ),
+                        };
 
                         log::debug!(
                             "[AMM-V4] SwapBaseIn: sig={}, amm={}, input={} (instr={}), output={} (min={})",
@@ -125,37 +228,48 @@ impl Processor for RaydiumAmmV4InstructionProcessor {
                             .event_type(EventType::Swap)
                             .protocol(Protocol::AmmV4)
                             .signature(&signature)
+                            .instruction_metadata(&metadata)
                             .pool_pubkey(&accounts.amm)
-                            .input_token(TokenInfo::new(
-                                accounts.user_source_token_account.to_string(),
-                                actual_input,
-                            ))
-                            .output_token(TokenInfo::new(
-                                accounts.user_destination_token_account.to_string(),
-                                actual_output,
-                            ))
+                            .input_token(TokenInfo::from_pubkey(&input_mint, actual_input))
+                            .output_token(TokenInfo::from_pubkey(&output_mint, actual_output))
                             .direction(SwapDirection::ExactInput)
                             .maker_pubkey(&accounts.user_source_owner)
                             .slot(slot)
                             .build();
 
-                        self.emit_event(event).await;
+                        self.emitter.emit(event, &metrics).await;
                     }
                 }
             }
             // SwapBaseOut - Legacy swap with Serum
             RaydiumAmmV4Instruction::SwapBaseOut(ref swap) => {
                 if let Some(accounts) = SwapBaseOut::arrange_accounts(&raw_instruction.accounts) {
-                    if self.matches_amm_filter(&accounts.amm) {
-                        // Extract actual amounts from nested token transfers
-                        // The instruction's max_amount_in is just slippage protection
-                        let (actual_input, actual_output) = extract_swap_amounts(
-                            &nested_instructions,
+                    if let Some((input_mint, output_mint)) = self
+                        .filter_swap(
+                            &accounts.amm,
                             &accounts.user_source_token_account,
                             &accounts.user_destination_token_account,
-                            swap.max_amount_in,  // fallback to max (not ideal)
-                            swap.amount_out,     // fallback to instruction amount
-                        );
+                            &accounts.user_source_owner,
+                        )
+                        .await
+                    {
+                        // Actual amounts from the swap's ray_log, or the nested token transfers
+                        // The instruction's max_amount_in is just slippage protection
+                        let (actual_input, actual_output) = match self.ray_swap_log(
+                            &metadata,
+                            &accounts.amm,
+                            false,
+                            (swap.max_amount_in, swap.amount_out),
+                        ) {
+                            Some(log) => (log.amount_in, log.amount_out),
+                            None => extract_swap_amounts(
+                                &nested_instructions,
+                                &accounts.user_source_token_account,
+                                &accounts.user_destination_token_account,
+                                swap.max_amount_in,
+                                swap.amount_out,
+                            ),
+                        };
 
                         log::debug!(
                             "[AMM-V4] SwapBaseOut: sig={}, amm={}, input={} (max={}), output={} (instr={})",
@@ -171,36 +285,47 @@ impl Processor for RaydiumAmmV4InstructionProcessor {
                             .event_type(EventType::Swap)
                             .protocol(Protocol::AmmV4)
                             .signature(&signature)
+                            .instruction_metadata(&metadata)
                             .pool_pubkey(&accounts.amm)
-                            .input_token(TokenInfo::new(
-                                accounts.user_source_token_account.to_string(),
-                                actual_input,
-                            ))
-                            .output_token(TokenInfo::new(
-                                accounts.user_destination_token_account.to_string(),
-                                actual_output,
-                            ))
+                            .input_token(TokenInfo::from_pubkey(&input_mint, actual_input))
+                            .output_token(TokenInfo::from_pubkey(&output_mint, actual_output))
                             .direction(SwapDirection::ExactOutput)
                             .maker_pubkey(&accounts.user_source_owner)
                             .slot(slot)
                             .build();
 
-                        self.emit_event(event).await;
+                        self.emitter.emit(event, &metrics).await;
                     }
                 }
             }
             // SwapBaseInV2 - Newer swap without Serum
             RaydiumAmmV4Instruction::SwapBaseInV2(ref swap) => {
                 if let Some(accounts) = SwapBaseInV2::arrange_accounts(&raw_instruction.accounts) {
-                    if self.matches_amm_filter(&accounts.amm) {
-                        // Extract actual amounts from nested token transfers
-                        let (actual_input, actual_output) = extract_swap_amounts(
-                            &nested_instructions,
+                    if let Some((input_mint, output_mint)) = self
+                        .filter_swap(
+                            &accounts.amm,
                             &accounts.user_source_token_account,
                             &accounts.user_destination_token_account,
-                            swap.amount_in,
-                            swap.minimum_amount_out,
-                        );
+                            &accounts.user_source_owner,
+                        )
+                        .await
+                    {
+                        // Actual amounts from the swap's ray_log, or the nested token transfers
+                        let (actual_input, actual_output) = match self.ray_swap_log(
+                            &metadata,
+                            &accounts.amm,
+                            true,
+                            (swap.amount_in, swap.minimum_amount_out),
+                        ) {
+                            Some(log) => (log.amount_in, log.amount_out),
+                            None => extract_swap_amounts(
+                                &nested_instructions,
+                                &accounts.user_source_token_account,
+                                &accounts.user_destination_token_account,
+                                swap.amount_in,
+                                swap.minimum_amount_out,
+                            ),
+                        };
 
                         log::debug!(
                             "[AMM-V4] SwapBaseInV2: sig={}, amm={}, input={} (instr={}), output={} (min={})",
@@ -216,36 +341,47 @@ impl Processor for RaydiumAmmV4InstructionProcessor {
                             .event_type(EventType::Swap)
                             .protocol(Protocol::AmmV4)
                             .signature(&signature)
+                            .instruction_metadata(&metadata)
                             .pool_pubkey(&accounts.amm)
-                            .input_token(TokenInfo::new(
-                                accounts.user_source_token_account.to_string(),
-                                actual_input,
-                            ))
-                            .output_token(TokenInfo::new(
-                                accounts.user_destination_token_account.to_string(),
-                                actual_output,
-                            ))
+                            .input_token(TokenInfo::from_pubkey(&input_mint, actual_input))
+                            .output_token(TokenInfo::from_pubkey(&output_mint, actual_output))
                             .direction(SwapDirection::ExactInput)
                             .maker_pubkey(&accounts.user_source_owner)
                             .slot(slot)
                             .build();
 
-                        self.emit_event(event).await;
+                        self.emitter.emit(event, &metrics).await;
                     }
                 }
             }
             // SwapBaseOutV2 - Newer swap without Serum
             RaydiumAmmV4Instruction::SwapBaseOutV2(ref swap) => {
                 if let Some(accounts) = SwapBaseOutV2::arrange_accounts(&raw_instruction.accounts) {
-                    if self.matches_amm_filter(&accounts.amm) {
-                        // Extract actual amounts from nested token transfers
-                        let (actual_input, actual_output) = extract_swap_amounts(
-                            &nested_instructions,
+                    if let Some((input_mint, output_mint)) = self
+                        .filter_swap(
+                            &accounts.amm,
                             &accounts.user_source_token_account,
                             &accounts.user_destination_token_account,
-                            swap.max_amount_in,
-                            swap.amount_out,
-                        );
+                            &accounts.user_source_owner,
+                        )
+                        .await
+                    {
+                        // Actual amounts from the swap's ray_log, or the nested token transfers
+                        let (actual_input, actual_output) = match self.ray_swap_log(
+                            &metadata,
+                            &accounts.amm,
+                            false,
+                            (swap.max_amount_in, swap.amount_out),
+                        ) {
+                            Some(log) => (log.amount_in, log.amount_out),
+                            None => extract_swap_amounts(
+                                &nested_instructions,
+                                &accounts.user_source_token_account,
+                                &accounts.user_destination_token_account,
+                                swap.max_amount_in,
+                                swap.amount_out,
+                            ),
+                        };
 
                         log::debug!(
                             "[AMM-V4] SwapBaseOutV2: sig={}, amm={}, input={} (max={}), output={} (instr={})",
@@ -261,39 +397,69 @@ impl Processor for RaydiumAmmV4InstructionProcessor {
                             .event_type(EventType::Swap)
                             .protocol(Protocol::AmmV4)
                             .signature(&signature)
+                            .instruction_metadata(&metadata)
                             .pool_pubkey(&accounts.amm)
-                            .input_token(TokenInfo::new(
-                                accounts.user_source_token_account.to_string(),
-                                actual_input,
-                            ))
-                            .output_token(TokenInfo::new(
-                                accounts.user_destination_token_account.to_string(),
-                                actual_output,
-                            ))
+                            .input_token(TokenInfo::from_pubkey(&input_mint, actual_input))
+                            .output_token(TokenInfo::from_pubkey(&output_mint, actual_output))
                             .direction(SwapDirection::ExactOutput)
                             .maker_pubkey(&accounts.user_source_owner)
                             .slot(slot)
                             .build();
 
-                        self.emit_event(event).await;
+                        self.emitter.emit(event, &metrics).await;
                     }
                 }
             }
-            // Initialize events
+            // Initialize - legacy pool creation (liquidity is deposited separately)
             RaydiumAmmV4Instruction::Initialize(ref init) => {
-                log::info!(
-                    "[AMM-V4] 🆕 Initialize: sig={}, nonce={}",
-                    signature,
-                    init.nonce
-                );
+                if let Some(accounts) = Initialize::arrange_accounts(&raw_instruction.accounts) {
+                    if self.filters.matches(
+                        &accounts.amm,
+                        &[&accounts.coin_mint_address, &accounts.pc_mint_address],
+                        Some(&accounts.user_wallet),
+                    ) {
+                        let created = PoolCreatedEvent::new(
+                            &accounts.coin_mint_address,
+                            &accounts.pc_mint_address,
+                            &accounts.user_wallet,
+                        )
+                        .with_open_time(init.open_time)
+                        .with_lp_mint(&accounts.lp_mint_address);
+                        let event = SwapEvent::from_pool_created(
+                            Protocol::AmmV4,
+                            &metadata,
+                            &accounts.amm,
+                            created,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
             }
+            // Initialize2 - pool creation with initial liquidity
             RaydiumAmmV4Instruction::Initialize2(ref init) => {
-                log::info!(
-                    "[AMM-V4] 🆕 Initialize2: sig={}, nonce={}, open_time={}",
-                    signature,
-                    init.nonce,
-                    init.open_time
-                );
+                if let Some(accounts) = Initialize2::arrange_accounts(&raw_instruction.accounts) {
+                    if self.filters.matches(
+                        &accounts.amm,
+                        &[&accounts.coin_mint, &accounts.pc_mint],
+                        Some(&accounts.user_wallet),
+                    ) {
+                        let created = PoolCreatedEvent::new(
+                            &accounts.coin_mint,
+                            &accounts.pc_mint,
+                            &accounts.user_wallet,
+                        )
+                        .with_reserves(init.init_coin_amount, init.init_pc_amount)
+                        .with_open_time(init.open_time)
+                        .with_lp_mint(&accounts.lp_mint);
+                        let event = SwapEvent::from_pool_created(
+                            Protocol::AmmV4,
+                            &metadata,
+                            &accounts.amm,
+                            created,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
             }
             // Liquidity events
             RaydiumAmmV4Instruction::Deposit(ref deposit) => {
@@ -312,6 +478,56 @@ impl Processor for RaydiumAmmV4InstructionProcessor {
                     withdraw.amount
                 );
             }
+            // SetParams - Pool status, fee, and owner changes by the AMM admin
+            RaydiumAmmV4Instruction::SetParams(ref params) => {
+                if let Some(accounts) = SetParams::arrange_accounts(&raw_instruction.accounts) {
+                    if let Some(admin) = set_params_change(params, &accounts.amm_admin_account) {
+                        if self
+                            .filters
+                            .matches_pool(&accounts.amm, Some(&accounts.amm_admin_account))
+                        {
+                            let event = SwapEvent::from_admin_action(
+                                Protocol::AmmV4,
+                                &metadata,
+                                &accounts.amm,
+                                admin,
+                            );
+                            self.emitter.emit(event, &metrics).await;
+                        }
+                    }
+                }
+            }
+            // UpdateConfigAccount - Owner change of the global AMM config
+            RaydiumAmmV4Instruction::UpdateConfigAccount(ref update) => {
+                if let Some(accounts) =
+                    UpdateConfigAccount::arrange_accounts(&raw_instruction.accounts)
+                {
+                    let param = match update.param {
+                        0 => Some("pnl_owner"),
+                        1 => Some("cancel_owner"),
+                        _ => None,
+                    };
+                    if let Some(param) = param {
+                        if self
+                            .filters
+                            .matches_pool(&accounts.amm_config, Some(&accounts.admin))
+                        {
+                            let admin = AdminActionEvent::authority_transfer(
+                                param,
+                                Some(&update.owner),
+                                &accounts.admin,
+                            );
+                            let event = SwapEvent::from_admin_action(
+                                Protocol::AmmV4,
+                                &metadata,
+                                &accounts.amm_config,
+                                admin,
+                            );
+                            self.emitter.emit(event, &metrics).await;
+                        }
+                    }
+                }
+            }
             // Skip other events
             _ => {}
         };
@@ -319,3 +535,40 @@ impl Processor for RaydiumAmmV4InstructionProcessor {
         Ok(())
     }
 }
+
+/// Describes a `SetParams` change, or `None` for order book and timing parameters.
+///
+/// Swap fees are reported as a rate in millionths (as CPMM and CLMM fee rates
+/// are), computed from the new numerator and denominator.
+fn set_params_change(params: &SetParams, admin: &Pubkey) -> Option<AdminActionEvent> {
+    match params.param {
+        // Status: swaps are allowed when Initialized (1), SwapOnly (6), or WaitingTrade (7)
+        0 => {
+            let status = params.value?;
+            let swaps_disabled = !matches!(status, 1 | 6 | 7);
+            Some(AdminActionEvent::status_change(
+                status,
+                swaps_disabled,
+                admin,
+            ))
+        }
+        // Fees
+        9 => {
+            let fees = params.fees.as_ref()?;
+            let rate = u128::from(fees.swap_fee_numerator) * 1_000_000
+                / u128::from(fees.swap_fee_denominator.max(1));
+            Some(AdminActionEvent::fee_change(
+                "swap_fee_rate",
+                u64::try_from(rate).unwrap_or(u64::MAX),
+                admin,
+            ))
+        }
+        // AmmOwner
+        10 => Some(AdminActionEvent::authority_transfer(
+            "amm_owner",
+            params.new_pubkey.as_ref(),
+            admin,
+        )),
+        _ => None,
+    }
+}
@@ -7,114 +7,99 @@
 //! more capital-efficient liquidity positions.
 
 use {
-    crate::output::{
-        EventType, OutputFormat, Protocol, SwapDirection, SwapEvent, TokenInfo, WebhookNotifier,
+    super::{EventEmitter, FilterSet},
+    crate::{
+        enrichment::EnrichmentCache,
+        output::{
+            extract_swap_amounts, token_transfer::parse_token_transfers_from_nested,
+            AdminActionEvent, EventSource, EventType, FeeCollectionEvent, FeeCollectionKind,
+            PoolCreatedEvent, Protocol, SwapDirection, SwapEvent, TokenInfo,
+        },
     },
     async_trait::async_trait,
     carbon_core::{
-        deserialize::ArrangeAccounts, error::CarbonResult, instruction::DecodedInstruction,
-        instruction::InstructionMetadata, instruction::NestedInstructions,
-        metrics::MetricsCollection, processor::Processor,
+        deserialize::{ArrangeAccounts, CarbonDeserialize},
+        error::CarbonResult,
+        instruction::DecodedInstruction,
+        instruction::InstructionMetadata,
+        instruction::NestedInstructions,
+        metrics::MetricsCollection,
+        processor::Processor,
     },
     carbon_raydium_clmm_decoder::instructions::{
-        create_pool::CreatePool, swap::Swap, swap_v2::SwapV2, RaydiumClmmInstruction,
+        collect_fund_fee::CollectFundFee, collect_personal_fee_event::CollectPersonalFeeEvent,
+        collect_protocol_fee::CollectProtocolFee,
+        collect_remaining_rewards::CollectRemainingRewards, create_pool::CreatePool,
+        decrease_liquidity::DecreaseLiquidity, decrease_liquidity_v2::DecreaseLiquidityV2,
+        swap::Swap, swap_v2::SwapV2, transfer_reward_owner::TransferRewardOwner,
+        update_amm_config::UpdateAmmConfig, update_pool_status::UpdatePoolStatus,
+        RaydiumClmmInstruction,
     },
     solana_pubkey::Pubkey,
-    std::{collections::HashSet, sync::Arc},
+    std::sync::Arc,
 };
 
 /// Processor for Raydium CLMM instructions with optional token and pool filtering.
 ///
 /// Supports filtering swaps by:
-/// - Token mint addresses (input or output) - only available for SwapV2
+/// - Token mint addresses (input or output) - for legacy Swap and SwapEvent,
+///   only when the pool state can be resolved (see [`Self::with_pool_cache`])
 /// - Pool addresses
 ///
 /// Uses OR logic: a swap is logged if it matches ANY of the configured filters.
 /// If no filters are configured, all swaps are logged.
+#[derive(Clone)]
 pub struct RaydiumClmmInstructionProcessor {
-    /// Set of token mint addresses to filter. Empty means no filter (track all).
-    filter_tokens: HashSet<Pubkey>,
-    /// Set of pool addresses to filter. Empty means no filter (track all).
-    filter_pools: HashSet<Pubkey>,
-    /// Output format for swap events.
-    output_format: OutputFormat,
-    /// Optional webhook notifier for sending alerts.
-    webhook_notifier: Option<Arc<WebhookNotifier>>,
+    /// Live token, pool, and wallet filters.
+    filters: FilterSet,
+    /// Emit path publishing matched events to the outputs.
+    emitter: EventEmitter,
+    /// Optional pool-state cache resolving token mints for legacy `Swap` instructions
+    /// and `SwapEvent` logs.
+    pool_cache: Option<Arc<EnrichmentCache>>,
 }
 
 impl RaydiumClmmInstructionProcessor {
-    /// Creates a new processor with optional filtering and output configuration.
-    ///
-    /// # Arguments
-    ///
-    /// * `filter_tokens` - Set of token mints to track. Empty set tracks all tokens.
-    /// * `filter_pools` - Set of pool addresses to track. Empty set tracks all pools.
-    /// * `output_format` - Format for swap event output (text, json, json_pretty).
-    /// * `webhook_notifier` - Optional webhook notifier for sending alerts.
-    pub fn new(
-        filter_tokens: HashSet<Pubkey>,
-        filter_pools: HashSet<Pubkey>,
-        output_format: OutputFormat,
-        webhook_notifier: Option<Arc<WebhookNotifier>>,
-    ) -> Self {
+    /// Creates a processor checking instructions against `filters` and
+    /// publishing the resulting events through `emitter`.
+    pub fn new(filters: FilterSet, emitter: EventEmitter) -> Self {
         Self {
-            filter_tokens,
-            filter_pools,
-            output_format,
-            webhook_notifier,
+            filters,
+            emitter,
+            pool_cache: None,
         }
     }
 
-    /// Checks if a swap matches any of the configured filters (OR logic).
-    fn matches_filter(
-        &self,
-        pool: &Pubkey,
-        input_mint: Option<&Pubkey>,
-        output_mint: Option<&Pubkey>,
-    ) -> bool {
-        // If no filters configured, track everything
-        if self.filter_pools.is_empty() && self.filter_tokens.is_empty() {
-            return true;
-        }
-        // Match if pool is in filter list
-        if self.filter_pools.contains(pool) {
-            return true;
-        }
-        // Match if either token is in filter list
-        if let Some(input) = input_mint {
-            if self.filter_tokens.contains(input) {
-                return true;
-            }
-        }
-        if let Some(output) = output_mint {
-            if self.filter_tokens.contains(output) {
-                return true;
-            }
-        }
-        false
+    /// Resolves token mints of legacy `Swap` instructions and `SwapEvent` logs
+    /// from the pool state.
+    ///
+    /// Legacy swaps only reference the pool vaults, and swap events the user's
+    /// token accounts; the pool's `PoolState` is fetched once per pool and
+    /// cached to map them to the token mints, enabling token filtering for
+    /// these swaps.
+    pub fn with_pool_cache(mut self, cache: Arc<EnrichmentCache>) -> Self {
+        self.pool_cache = Some(cache);
+        self
     }
 
-    /// Checks if a pool matches the filter (for instructions without token mints).
-    fn matches_pool_filter(&self, pool: &Pubkey) -> bool {
-        if self.filter_pools.is_empty() && self.filter_tokens.is_empty() {
-            return true;
-        }
-        // When we don't have token info, only match by pool
-        if self.filter_pools.is_empty() {
-            return true;
-        }
-        self.filter_pools.contains(pool)
+    /// Resolves the token 0 and token 1 mints of a pool from its state.
+    async fn resolve_pool_mints(&self, pool: &Pubkey) -> Option<(Pubkey, Pubkey)> {
+        let info = self.pool_cache.as_ref()?.pool_or_resolve(pool).await?;
+        Some((info.token_mint0, info.token_mint1))
     }
 
-    /// Outputs a swap event and optionally sends to webhook.
-    async fn emit_event(&self, event: SwapEvent) {
-        log::info!("{}", event.format(self.output_format));
-
-        if let Some(ref notifier) = self.webhook_notifier {
-            if let Err(e) = notifier.try_send(event) {
-                log::warn!("Failed to queue webhook notification: {e}");
-            }
-        }
+    /// Resolves the input and output mints of a legacy swap from its vaults.
+    async fn resolve_swap_mints(
+        &self,
+        pool: &Pubkey,
+        input_vault: &Pubkey,
+        output_vault: &Pubkey,
+    ) -> Option<(Pubkey, Pubkey)> {
+        let info = self.pool_cache.as_ref()?.pool_or_resolve(pool).await?;
+        Some((
+            info.mint_for_vault(input_vault)?,
+            info.mint_for_vault(output_vault)?,
+        ))
     }
 }
 
@@ -129,58 +114,95 @@ impl Processor for RaydiumClmmInstructionProcessor {
 
     async fn process(
         &mut self,
-        (metadata, instruction, _nested_instructions, raw_instruction): Self::InputType,
-        _metrics: Arc<MetricsCollection>,
+        (metadata, instruction, nested_instructions, raw_instruction): Self::InputType,
+        metrics: Arc<MetricsCollection>,
     ) -> CarbonResult<()> {
         let signature = metadata.transaction_metadata.signature.to_string();
         let slot = metadata.transaction_metadata.slot;
 
         match instruction.data {
-            // Legacy Swap - doesn't include token mints
+            // Legacy Swap - token mints are resolved from the pool state
             RaydiumClmmInstruction::Swap(ref swap) => {
                 if let Some(accounts) = Swap::arrange_accounts(&raw_instruction.accounts) {
-                    if self.matches_pool_filter(&accounts.pool_state) {
+                    let mints = self
+                        .resolve_swap_mints(
+                            &accounts.pool_state,
+                            &accounts.input_vault,
+                            &accounts.output_vault,
+                        )
+                        .await;
+                    let matches = match mints {
+                        Some((ref input_mint, ref output_mint)) => self.filters.matches(
+                            &accounts.pool_state,
+                            &[input_mint, output_mint],
+                            Some(&accounts.payer),
+                        ),
+                        None => self
+                            .filters
+                            .matches_pool(&accounts.pool_state, Some(&accounts.payer)),
+                    };
+
+                    if matches {
                         let direction = if swap.is_base_input {
                             SwapDirection::ExactInput
                         } else {
                             SwapDirection::ExactOutput
                         };
 
-                        let (input_amount, output_amount) = if swap.is_base_input {
+                        // Concentrated-liquidity fills often differ widely from
+                        // other_amount_threshold, which is only slippage protection;
+                        // prefer the amounts of the nested token transfers
+                        let (fallback_input, fallback_output) = if swap.is_base_input {
                             (swap.amount, swap.other_amount_threshold)
                         } else {
                             (swap.other_amount_threshold, swap.amount)
                         };
+                        let (input_amount, output_amount) = extract_swap_amounts(
+                            &nested_instructions,
+                            &accounts.input_token_account,
+                            &accounts.output_token_account,
+                            fallback_input,
+                            fallback_output,
+                        );
+
+                        log::debug!(
+                            "[CLMM] Swap: sig={}, pool={}, input={} (instr={}), output={} (instr={})",
+                            signature,
+                            accounts.pool_state,
+                            input_amount,
+                            fallback_input,
+                            output_amount,
+                            fallback_output
+                        );
+
+                        // Without a resolved pool state, the vaults identify the tokens
+                        let (input_mint, output_mint) =
+                            mints.unwrap_or((accounts.input_vault, accounts.output_vault));
 
                         let event = SwapEvent::builder()
                             .event_type(EventType::Swap)
                             .protocol(Protocol::Clmm)
                             .signature(&signature)
+                            .instruction_metadata(&metadata)
                             .pool_pubkey(&accounts.pool_state)
-                            .input_token(TokenInfo::new(
-                                accounts.pool_state.to_string(), // No mint available
-                                input_amount,
-                            ))
-                            .output_token(TokenInfo::new(
-                                accounts.pool_state.to_string(),
-                                output_amount,
-                            ))
+                            .input_token(TokenInfo::from_pubkey(&input_mint, input_amount))
+                            .output_token(TokenInfo::from_pubkey(&output_mint, output_amount))
                             .direction(direction)
                             .maker_pubkey(&accounts.payer)
                             .slot(slot)
                             .build();
 
-                        self.emit_event(event).await;
+                        self.emitter.emit(event, &metrics).await;
                     }
                 }
             }
             // SwapV2 - includes token mints
             RaydiumClmmInstruction::SwapV2(ref swap) => {
                 if let Some(accounts) = SwapV2::arrange_accounts(&raw_instruction.accounts) {
-                    if self.matches_filter(
+                    if self.filters.matches(
                         &accounts.pool_state,
-                        Some(&accounts.input_vault_mint),
-                        Some(&accounts.output_vault_mint),
+                        &[&accounts.input_vault_mint, &accounts.output_vault_mint],
+                        Some(&accounts.payer),
                     ) {
                         let direction = if swap.is_base_input {
                             SwapDirection::ExactInput
@@ -188,16 +210,35 @@ impl Processor for RaydiumClmmInstructionProcessor {
                             SwapDirection::ExactOutput
                         };
 
-                        let (input_amount, output_amount) = if swap.is_base_input {
+                        // Actual amounts from the nested transfers, as for legacy Swap
+                        let (fallback_input, fallback_output) = if swap.is_base_input {
                             (swap.amount, swap.other_amount_threshold)
                         } else {
                             (swap.other_amount_threshold, swap.amount)
                         };
+                        let (input_amount, output_amount) = extract_swap_amounts(
+                            &nested_instructions,
+                            &accounts.input_token_account,
+                            &accounts.output_token_account,
+                            fallback_input,
+                            fallback_output,
+                        );
+
+                        log::debug!(
+                            "[CLMM] SwapV2: sig={}, pool={}, input={} (instr={}), output={} (instr={})",
+                            signature,
+                            accounts.pool_state,
+                            input_amount,
+                            fallback_input,
+                            output_amount,
+                            fallback_output
+                        );
 
                         let event = SwapEvent::builder()
                             .event_type(EventType::Swap)
                             .protocol(Protocol::Clmm)
                             .signature(&signature)
+                            .instruction_metadata(&metadata)
                             .pool_pubkey(&accounts.pool_state)
                             .input_token(TokenInfo::from_pubkey(
                                 &accounts.input_vault_mint,
@@ -212,60 +253,87 @@ impl Processor for RaydiumClmmInstructionProcessor {
                             .slot(slot)
                             .build();
 
-                        self.emit_event(event).await;
+                        self.emitter.emit(event, &metrics).await;
                     }
                 }
             }
-            // SwapEvent - actual amounts
+            // SwapEvent - actual amounts, with the token mints resolved from the pool state
             RaydiumClmmInstruction::SwapEvent(ref swap_event) => {
-                if self.matches_pool_filter(&swap_event.pool_state) {
-                    let (input_amount, output_amount) = if swap_event.zero_for_one {
-                        (swap_event.amount0, swap_event.amount1)
+                let pool = swap_event.pool_state;
+                let mints = self.resolve_pool_mints(&pool).await;
+                let matches = match mints {
+                    Some((ref mint0, ref mint1)) => {
+                        self.filters
+                            .matches(&pool, &[mint0, mint1], Some(&swap_event.sender))
+                    }
+                    // With a token filter, leave unresolved swaps to their
+                    // instruction, which names the mints
+                    None => {
+                        self.filters.config().load().filter_tokens.is_empty()
+                            && self.filters.matches_pool(&pool, Some(&swap_event.sender))
+                    }
+                };
+
+                if matches {
+                    // Token 0 and 1 are the user's accounts when the mints are unknown
+                    let (token0, token1) =
+                        mints.unwrap_or((swap_event.token_account0, swap_event.token_account1));
+                    let (input, output) = if swap_event.zero_for_one {
+                        (
+                            TokenInfo::from_pubkey(&token0, swap_event.amount0),
+                            TokenInfo::from_pubkey(&token1, swap_event.amount1),
+                        )
                     } else {
-                        (swap_event.amount1, swap_event.amount0)
+                        (
+                            TokenInfo::from_pubkey(&token1, swap_event.amount1),
+                            TokenInfo::from_pubkey(&token0, swap_event.amount0),
+                        )
                     };
 
                     let event = SwapEvent::builder()
                         .event_type(EventType::Swap)
                         .protocol(Protocol::Clmm)
                         .signature(&signature)
-                        .pool(swap_event.pool_state.to_string())
-                        .input_token(TokenInfo::new(
-                            swap_event.token_account0.to_string(),
-                            input_amount,
-                        ))
-                        .output_token(TokenInfo::new(
-                            swap_event.token_account1.to_string(),
-                            output_amount,
-                        ))
+                        .instruction_metadata(&metadata)
+                        .source(EventSource::EventLog)
+                        .pool_pubkey(&pool)
+                        .input_token(input)
+                        .output_token(output)
                         .direction(SwapDirection::Unknown)
                         .maker_pubkey(&swap_event.sender)
                         .slot(slot)
                         .build();
 
-                    self.emit_event(event).await;
+                    self.emitter.emit(event, &metrics).await;
                 }
             }
-            // CreatePool
+            // CreatePool - the pool starts empty; liquidity comes with the first position
             RaydiumClmmInstruction::CreatePool(ref create_pool) => {
                 if let Some(accounts) = CreatePool::arrange_accounts(&raw_instruction.accounts) {
-                    let event = SwapEvent::builder()
-                        .event_type(EventType::CreatePool)
-                        .protocol(Protocol::Clmm)
-                        .signature(&signature)
-                        .pool_pubkey(&accounts.pool_state)
-                        .input_token(TokenInfo::from_pubkey(&accounts.token_mint0, 0))
-                        .output_token(TokenInfo::from_pubkey(&accounts.token_mint1, 0))
-                        .maker_pubkey(&accounts.pool_creator)
-                        .slot(slot)
-                        .build();
-
-                    log::info!(
-                        "[CLMM] 🆕 CreatePool: sqrt_price={}, open_time={}",
-                        create_pool.sqrt_price_x64,
-                        create_pool.open_time
-                    );
-                    self.emit_event(event).await;
+                    if self.filters.matches(
+                        &accounts.pool_state,
+                        &[&accounts.token_mint0, &accounts.token_mint1],
+                        Some(&accounts.pool_creator),
+                    ) {
+                        log::debug!(
+                            "[CLMM] CreatePool: sig={}, sqrt_price={}",
+                            signature,
+                            create_pool.sqrt_price_x64
+                        );
+                        let created = PoolCreatedEvent::new(
+                            &accounts.token_mint0,
+                            &accounts.token_mint1,
+                            &accounts.pool_creator,
+                        )
+                        .with_open_time(create_pool.open_time);
+                        let event = SwapEvent::from_pool_created(
+                            Protocol::Clmm,
+                            &metadata,
+                            &accounts.pool_state,
+                            created,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
                 }
             }
             // PoolCreatedEvent
@@ -305,6 +373,55 @@ impl Processor for RaydiumClmmInstructionProcessor {
                     liq.amount0_min,
                     liq.amount1_min
                 );
+
+                // Decreases collect the position's fees and rewards: all of the
+                // vault transfers when no liquidity is withdrawn, otherwise the
+                // fees named by the program's CollectPersonalFeeEvent
+                let fees = personal_fees(&nested_instructions, &raw_instruction.program_id);
+                if liq.liquidity == 0 || fees.is_some() {
+                    if let Some(accounts) =
+                        DecreaseLiquidity::arrange_accounts(&raw_instruction.accounts)
+                    {
+                        let mints = self
+                            .resolve_swap_mints(
+                                &accounts.pool_state,
+                                &accounts.token_vault0,
+                                &accounts.token_vault1,
+                            )
+                            .await;
+                        let matches = match mints {
+                            Some((ref mint0, ref mint1)) => self.filters.matches(
+                                &accounts.pool_state,
+                                &[mint0, mint1],
+                                Some(&accounts.nft_owner),
+                            ),
+                            None => self
+                                .filters
+                                .matches_pool(&accounts.pool_state, Some(&accounts.nft_owner)),
+                        };
+
+                        if matches {
+                            // Without a resolved pool state, the vaults identify the tokens
+                            let (mint0, mint1) =
+                                mints.unwrap_or((accounts.token_vault0, accounts.token_vault1));
+                            let harvest = Harvest {
+                                kind: FeeCollectionKind::Position,
+                                pool: accounts.pool_state,
+                                collector: accounts.nft_owner,
+                                position: Some(accounts.personal_position),
+                                tokens: Some([
+                                    (accounts.token_vault0, mint0),
+                                    (accounts.token_vault1, mint1),
+                                ]),
+                                fees,
+                            };
+                            if let Some(event) = harvest.into_event(&metadata, &nested_instructions)
+                            {
+                                self.emitter.emit(event, &metrics).await;
+                            }
+                        }
+                    }
+                }
             }
             RaydiumClmmInstruction::DecreaseLiquidityV2(ref liq) => {
                 log::info!(
@@ -314,6 +431,112 @@ impl Processor for RaydiumClmmInstructionProcessor {
                     liq.amount0_min,
                     liq.amount1_min
                 );
+
+                // Decreases collect the position's fees and rewards (see DecreaseLiquidity)
+                let fees = personal_fees(&nested_instructions, &raw_instruction.program_id);
+                if liq.liquidity == 0 || fees.is_some() {
+                    if let Some(accounts) =
+                        DecreaseLiquidityV2::arrange_accounts(&raw_instruction.accounts)
+                    {
+                        if self.filters.matches(
+                            &accounts.pool_state,
+                            &[&accounts.vault0_mint, &accounts.vault1_mint],
+                            Some(&accounts.nft_owner),
+                        ) {
+                            let harvest = Harvest {
+                                kind: FeeCollectionKind::Position,
+                                pool: accounts.pool_state,
+                                collector: accounts.nft_owner,
+                                position: Some(accounts.personal_position),
+                                tokens: Some([
+                                    (accounts.token_vault0, accounts.vault0_mint),
+                                    (accounts.token_vault1, accounts.vault1_mint),
+                                ]),
+                                fees,
+                            };
+                            if let Some(event) = harvest.into_event(&metadata, &nested_instructions)
+                            {
+                                self.emitter.emit(event, &metrics).await;
+                            }
+                        }
+                    }
+                }
+            }
+            // CollectProtocolFee / CollectFundFee - Pool fees paid to the config's fee owners
+            RaydiumClmmInstruction::CollectProtocolFee(_) => {
+                if let Some(accounts) =
+                    CollectProtocolFee::arrange_accounts(&raw_instruction.accounts)
+                {
+                    if self.filters.matches(
+                        &accounts.pool_state,
+                        &[&accounts.vault0_mint, &accounts.vault1_mint],
+                        Some(&accounts.owner),
+                    ) {
+                        let harvest = Harvest {
+                            kind: FeeCollectionKind::Protocol,
+                            pool: accounts.pool_state,
+                            collector: accounts.owner,
+                            position: None,
+                            tokens: Some([
+                                (accounts.token_vault0, accounts.vault0_mint),
+                                (accounts.token_vault1, accounts.vault1_mint),
+                            ]),
+                            fees: None,
+                        };
+                        if let Some(event) = harvest.into_event(&metadata, &nested_instructions) {
+                            self.emitter.emit(event, &metrics).await;
+                        }
+                    }
+                }
+            }
+            RaydiumClmmInstruction::CollectFundFee(_) => {
+                if let Some(accounts) = CollectFundFee::arrange_accounts(&raw_instruction.accounts)
+                {
+                    if self.filters.matches(
+                        &accounts.pool_state,
+                        &[&accounts.vault0_mint, &accounts.vault1_mint],
+                        Some(&accounts.owner),
+                    ) {
+                        let harvest = Harvest {
+                            kind: FeeCollectionKind::Fund,
+                            pool: accounts.pool_state,
+                            collector: accounts.owner,
+                            position: None,
+                            tokens: Some([
+                                (accounts.token_vault0, accounts.vault0_mint),
+                                (accounts.token_vault1, accounts.vault1_mint),
+                            ]),
+                            fees: None,
+                        };
+                        if let Some(event) = harvest.into_event(&metadata, &nested_instructions) {
+                            self.emitter.emit(event, &metrics).await;
+                        }
+                    }
+                }
+            }
+            // CollectRemainingRewards - Rewards left in a vault after the farm ended
+            RaydiumClmmInstruction::CollectRemainingRewards(_) => {
+                if let Some(accounts) =
+                    CollectRemainingRewards::arrange_accounts(&raw_instruction.accounts)
+                {
+                    if self.filters.matches(
+                        &accounts.pool_state,
+                        &[&accounts.reward_vault_mint],
+                        Some(&accounts.reward_funder),
+                    ) {
+                        let harvest = Harvest {
+                            kind: FeeCollectionKind::RemainingRewards,
+                            pool: accounts.pool_state,
+                            collector: accounts.reward_funder,
+                            position: None,
+                            tokens: None,
+                            fees: None,
+                        };
+                        if let Some(event) = harvest.into_event(&metadata, &nested_instructions) {
+                            self.emitter.emit(event, &metrics).await;
+                        }
+                    }
+                }
             }
             RaydiumClmmInstruction::LiquidityChangeEvent(ref event) => {
                 // Determine direction based on liquidity change
@@ -326,7 +549,11 @@ impl Processor for RaydiumClmmInstructionProcessor {
 
                 log::info!(
                     "[CLMM] {} LiquidityChangeEvent: sig={}, pool={}, liquidity_delta={}, tick={}",
-                    if event_type == EventType::AddLiquidity { "💧" } else { "🔥" },
+                    if event_type == EventType::AddLiquidity {
+                        "💧"
+                    } else {
+                        "🔥"
+                    },
                     signature,
                     event.pool_state,
                     liquidity_delta,
@@ -353,6 +580,79 @@ impl Processor for RaydiumClmmInstructionProcessor {
             RaydiumClmmInstruction::ClosePosition(_) => {
                 log::info!("[CLMM] ❌ ClosePosition: sig={}", signature);
             }
+            // UpdatePoolStatus - Enable or disable liquidity, fee collection, and swaps
+            RaydiumClmmInstruction::UpdatePoolStatus(ref update) => {
+                if let Some(accounts) =
+                    UpdatePoolStatus::arrange_accounts(&raw_instruction.accounts)
+                {
+                    if self
+                        .filters
+                        .matches_pool(&accounts.pool_state, Some(&accounts.authority))
+                    {
+                        // Status bit 4 disables swaps
+                        let admin = AdminActionEvent::status_change(
+                            u64::from(update.status),
+                            update.status & (1 << 4) != 0,
+                            &accounts.authority,
+                        );
+                        let event = SwapEvent::from_admin_action(
+                            Protocol::Clmm,
+                            &metadata,
+                            &accounts.pool_state,
+                            admin,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            // UpdateAmmConfig - Fee or owner change of a fee config shared by many pools
+            RaydiumClmmInstruction::UpdateAmmConfig(ref update) => {
+                if let Some(accounts) = UpdateAmmConfig::arrange_accounts(&raw_instruction.accounts)
+                {
+                    // The new owner is passed as the first remaining account
+                    let new_owner = raw_instruction.accounts.get(2).map(|meta| &meta.pubkey);
+                    if let Some(admin) =
+                        amm_config_change(update.param, update.value, new_owner, &accounts.owner)
+                    {
+                        if self
+                            .filters
+                            .matches_pool(&accounts.amm_config, Some(&accounts.owner))
+                        {
+                            let event = SwapEvent::from_admin_action(
+                                Protocol::Clmm,
+                                &metadata,
+                                &accounts.amm_config,
+                                admin,
+                            );
+                            self.emitter.emit(event, &metrics).await;
+                        }
+                    }
+                }
+            }
+            // TransferRewardOwner - Hand the pool's reward authority to another account
+            RaydiumClmmInstruction::TransferRewardOwner(ref transfer) => {
+                if let Some(accounts) =
+                    TransferRewardOwner::arrange_accounts(&raw_instruction.accounts)
+                {
+                    if self
+                        .filters
+                        .matches_pool(&accounts.pool_state, Some(&accounts.authority))
+                    {
+                        let admin = AdminActionEvent::authority_transfer(
+                            "reward_owner",
+                            Some(&transfer.new_owner),
+                            &accounts.authority,
+                        );
+                        let event = SwapEvent::from_admin_action(
+                            Protocol::Clmm,
+                            &metadata,
+                            &accounts.pool_state,
+                            admin,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
             // Skip other events
             _ => {}
         };
@@ -360,3 +660,338 @@ impl Processor for RaydiumClmmInstructionProcessor {
         Ok(())
     }
 }
+
+/// Accounts of a collection of fees or rewards out of a pool's vaults.
+struct Harvest {
+    kind: FeeCollectionKind,
+    pool: Pubkey,
+    /// Position owner, fee owner, or reward funder
+    collector: Pubkey,
+    /// Personal position of position collections
+    position: Option<Pubkey>,
+    /// Vault and mint of tokens 0 and 1 (the vault again when the pool state
+    /// is unresolved), or `None` if the collection pays rewards only
+    tokens: Option<[(Pubkey, Pubkey); 2]>,
+    /// Fees of tokens 0 and 1 logged by the program, which replace the vault
+    /// transfers when liquidity is withdrawn with them
+    fees: Option<(u64, u64)>,
+}
+
+impl Harvest {
+    /// Builds the `CollectFees` event of the collection from the nested transfers.
+    ///
+    /// Transfers out of the token vaults are the fees of tokens 0 and 1, unless
+    /// the program logged them; every other transfer pays out a farm reward,
+    /// identified by its mint when the transfer names it (`TransferChecked`),
+    /// or by the reward vault.
+    ///
+    /// # Returns
+    ///
+    /// `None` if there was nothing to collect.
+    fn into_event(
+        self,
+        metadata: &InstructionMetadata,
+        nested_instructions: &NestedInstructions,
+    ) -> Option<SwapEvent> {
+        let (mut fee0, mut fee1) = (0u64, 0u64);
+        let mut rewards: Vec<TokenInfo> = Vec::new();
+        for transfer in parse_token_transfers_from_nested(nested_instructions) {
+            if transfer.amount == 0 {
+                continue;
+            }
+            match self.tokens {
+                Some([(vault0, _), _]) if transfer.source == vault0 => {
+                    fee0 = fee0.saturating_add(transfer.amount)
+                }
+                Some([_, (vault1, _)]) if transfer.source == vault1 => {
+                    fee1 = fee1.saturating_add(transfer.amount)
+                }
+                _ => {
+                    let mint = transfer.mint.unwrap_or(transfer.source).to_string();
+                    match rewards.iter_mut().find(|reward| reward.mint == mint) {
+                        Some(reward) => {
+                            reward.amount_raw = reward.amount_raw.saturating_add(transfer.amount)
+                        }
+                        None => rewards.push(TokenInfo::new(mint, transfer.amount)),
+                    }
+                }
+            }
+        }
+        let (fee0, fee1) = self.fees.unwrap_or((fee0, fee1));
+        if fee0 == 0 && fee1 == 0 && rewards.is_empty() {
+            return None;
+        }
+
+        log::debug!(
+            "[CLMM] CollectFees: sig={}, kind={}, pool={}, fee0={}, fee1={}, rewards={}",
+            metadata.transaction_metadata.signature,
+            self.kind,
+            self.pool,
+            fee0,
+            fee1,
+            rewards.len()
+        );
+
+        let fees = self.tokens.map(|[(_, mint0), (_, mint1)]| {
+            (
+                TokenInfo::from_pubkey(&mint0, fee0),
+                TokenInfo::from_pubkey(&mint1, fee1),
+            )
+        });
+        Some(SwapEvent::from_fee_collection(
+            Protocol::Clmm,
+            metadata,
+            &self.pool,
+            &self.collector,
+            fees,
+            FeeCollectionEvent {
+                kind: self.kind,
+                position: self.position.map(|position| position.to_string()),
+                rewards,
+            },
+        ))
+    }
+}
+
+/// Returns the fees of tokens 0 and 1 of the `CollectPersonalFeeEvent` logged
+/// by the program under a liquidity decrease, if any.
+fn personal_fees(nested: &NestedInstructions, program_id: &Pubkey) -> Option<(u64, u64)> {
+    nested.iter().find_map(|nested_ix| {
+        let ix = &nested_ix.instruction;
+        let event = if ix.program_id == *program_id {
+            CollectPersonalFeeEvent::deserialize(&ix.data)
+        } else {
+            None
+        };
+        event
+            .map(|event| (event.amount0, event.amount1))
+            .or_else(|| personal_fees(&nested_ix.inner_instructions, program_id))
+    })
+}
+
+/// Describes an `UpdateAmmConfig` change, or `None` for unknown parameters.
+fn amm_config_change(
+    param: u8,
+    value: u32,
+    new_owner: Option<&Pubkey>,
+    owner: &Pubkey,
+) -> Option<AdminActionEvent> {
+    let fee = |name| Some(AdminActionEvent::fee_change(name, u64::from(value), owner));
+    match param {
+        0 => fee("trade_fee_rate"),
+        1 => fee("protocol_fee_rate"),
+        2 => fee("fund_fee_rate"),
+        3 => Some(AdminActionEvent::authority_transfer(
+            "owner", new_owner, owner,
+        )),
+        4 => Some(AdminActionEvent::authority_transfer(
+            "fund_owner",
+            new_owner,
+            owner,
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::token_transfer::SPL_TOKEN_PROGRAM_ID,
+        carbon_core::{instruction::NestedInstruction, transaction::TransactionMetadata},
+        solana_instruction::{AccountMeta, Instruction},
+        std::str::FromStr,
+    };
+
+    const POOL: u8 = 1;
+    const OWNER: u8 = 2;
+    const POSITION: u8 = 3;
+    const VAULT0: u8 = 4;
+    const VAULT1: u8 = 5;
+    const MINT0: u8 = 6;
+    const MINT1: u8 = 7;
+    const REWARD_VAULT: u8 = 8;
+    const REWARD_MINT: u8 = 9;
+    const USER: u8 = 10;
+
+    fn key(seed: u8) -> Pubkey {
+        Pubkey::new_from_array([seed; 32])
+    }
+
+    fn metadata() -> InstructionMetadata {
+        InstructionMetadata {
+            transaction_metadata: Arc::new(TransactionMetadata::default()),
+            stack_height: 1,
+            index: 0,
+            absolute_path: vec![0],
+        }
+    }
+
+    fn nested(instructions: Vec<Instruction>) -> NestedInstructions {
+        NestedInstructions(
+            instructions
+                .into_iter()
+                .map(|instruction| NestedInstruction {
+                    metadata: metadata(),
+                    instruction,
+                    inner_instructions: NestedInstructions(Vec::new()),
+                })
+                .collect(),
+        )
+    }
+
+    /// A token transfer out of `source`, naming `mint` if given (`TransferChecked`).
+    fn transfer(source: Pubkey, mint: Option<Pubkey>, amount: u64) -> Instruction {
+        let (data, accounts) = match mint {
+            Some(mint) => {
+                let mut data = vec![12u8];
+                data.extend_from_slice(&amount.to_le_bytes());
+                data.push(6);
+                let accounts = vec![
+                    AccountMeta::new(source, false),
+                    AccountMeta::new_readonly(mint, false),
+                    AccountMeta::new(key(USER), false),
+                ];
+                (data, accounts)
+            }
+            None => {
+                let mut data = vec![3u8];
+                data.extend_from_slice(&amount.to_le_bytes());
+                let accounts = vec![
+                    AccountMeta::new(source, false),
+                    AccountMeta::new(key(USER), false),
+                ];
+                (data, accounts)
+            }
+        };
+        Instruction {
+            program_id: Pubkey::from_str(SPL_TOKEN_PROGRAM_ID).unwrap(),
+            accounts,
+            data,
+        }
+    }
+
+    fn harvest(kind: FeeCollectionKind, fees: Option<(u64, u64)>) -> Harvest {
+        Harvest {
+            kind,
+            pool: key(POOL),
+            collector: key(OWNER),
+            position: (kind == FeeCollectionKind::Position).then(|| key(POSITION)),
+            tokens: Some([(key(VAULT0), key(MINT0)), (key(VAULT1), key(MINT1))]),
+            fees,
+        }
+    }
+
+    #[test]
+    fn test_harvest_splits_fees_and_rewards() {
+        let transfers = nested(vec![
+            transfer(key(VAULT0), None, 100),
+            transfer(key(VAULT1), None, 200),
+            transfer(key(REWARD_VAULT), Some(key(REWARD_MINT)), 30),
+            transfer(key(REWARD_VAULT), Some(key(REWARD_MINT)), 20),
+            transfer(key(VAULT0), None, 0),
+        ]);
+        let event = harvest(FeeCollectionKind::Position, None)
+            .into_event(&metadata(), &transfers)
+            .unwrap();
+
+        assert_eq!(event.event_type, EventType::CollectFees);
+        assert_eq!(event.pool, key(POOL).to_string());
+        assert_eq!(event.maker, Some(key(OWNER).to_string()));
+        let input = event.input_token.as_ref().unwrap();
+        let output = event.output_token.as_ref().unwrap();
+        assert_eq!(
+            (input.mint.clone(), input.amount_raw),
+            (key(MINT0).to_string(), 100)
+        );
+        assert_eq!(
+            (output.mint.clone(), output.amount_raw),
+            (key(MINT1).to_string(), 200)
+        );
+
+        let collection = event.fee_collection.unwrap();
+        assert_eq!(collection.kind, FeeCollectionKind::Position);
+        assert_eq!(collection.position, Some(key(POSITION).to_string()));
+        assert_eq!(collection.rewards.len(), 1);
+        assert_eq!(collection.rewards[0].mint, key(REWARD_MINT).to_string());
+        assert_eq!(collection.rewards[0].amount_raw, 50);
+    }
+
+    #[test]
+    fn test_harvest_prefers_logged_fees() {
+        // The vault transfers of a decrease also pay out the withdrawn liquidity
+        let transfers = nested(vec![
+            transfer(key(VAULT0), None, 1_000_100),
+            transfer(key(VAULT1), None, 2_000_200),
+        ]);
+        let event = harvest(FeeCollectionKind::Position, Some((100, 200)))
+            .into_event(&metadata(), &transfers)
+            .unwrap();
+        assert_eq!(event.input_token.unwrap().amount_raw, 100);
+        assert_eq!(event.output_token.unwrap().amount_raw, 200);
+        assert!(event.fee_collection.unwrap().rewards.is_empty());
+    }
+
+    #[test]
+    fn test_pool_and_reward_collections() {
+        let transfers = nested(vec![transfer(key(VAULT1), None, 700)]);
+        let event = harvest(FeeCollectionKind::Protocol, None)
+            .into_event(&metadata(), &transfers)
+            .unwrap();
+        assert_eq!(event.input_token.unwrap().amount_raw, 0);
+        assert_eq!(event.output_token.unwrap().amount_raw, 700);
+        let collection = event.fee_collection.unwrap();
+        assert_eq!(collection.kind, FeeCollectionKind::Protocol);
+        assert_eq!(collection.position, None);
+
+        // Reward withdrawals pay no fees
+        let rewards = Harvest {
+            tokens: None,
+            ..harvest(FeeCollectionKind::RemainingRewards, None)
+        };
+        let transfers = nested(vec![transfer(
+            key(REWARD_VAULT),
+            Some(key(REWARD_MINT)),
+            500,
+        )]);
+        let event = rewards.into_event(&metadata(), &transfers).unwrap();
+        assert!(event.input_token.is_none() && event.output_token.is_none());
+        let collection = event.fee_collection.unwrap();
+        assert_eq!(collection.kind, FeeCollectionKind::RemainingRewards);
+        assert_eq!(collection.rewards[0].amount_raw, 500);
+
+        // Nothing to collect
+        assert!(harvest(FeeCollectionKind::Fund, None)
+            .into_event(&metadata(), &nested(Vec::new()))
+            .is_none());
+        assert!(harvest(FeeCollectionKind::Position, Some((0, 0)))
+            .into_event(&metadata(), &nested(Vec::new()))
+            .is_none());
+    }
+
+    #[test]
+    fn test_personal_fees() {
+        let program = key(POOL);
+        let mut data = CollectPersonalFeeEvent::DISCRIMINATOR.to_vec();
+        for account in [key(POSITION), key(USER), key(USER)] {
+            data.extend_from_slice(account.as_ref());
+        }
+        data.extend_from_slice(&100u64.to_le_bytes());
+        data.extend_from_slice(&200u64.to_le_bytes());
+        let event = Instruction {
+            program_id: program,
+            accounts: Vec::new(),
+            data,
+        };
+
+        let logged = nested(vec![transfer(key(VAULT0), None, 100), event.clone()]);
+        assert_eq!(personal_fees(&logged, &program), Some((100, 200)));
+
+        // Only the program's own instructions are events
+        let other = Instruction {
+            program_id: key(USER),
+            ..event
+        };
+        assert_eq!(personal_fees(&nested(vec![other]), &program), None);
+    }
+}
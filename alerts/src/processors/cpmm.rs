@@ -4,8 +4,10 @@
 //! with optional filtering by token mints and AMM pool addresses.
 
 use {
+    super::{EventEmitter, FilterSet},
     crate::output::{
-        EventType, OutputFormat, Protocol, SwapDirection, SwapEvent, TokenInfo, WebhookNotifier,
+        extract_swap_amounts, AdminActionEvent, EventSource, EventType, PoolCreatedEvent, Protocol,
+        SwapDirection, SwapEvent, TokenInfo,
     },
     async_trait::async_trait,
     carbon_core::{
@@ -14,11 +16,13 @@ use {
         metrics::MetricsCollection, processor::Processor,
     },
     carbon_raydium_cpmm_decoder::instructions::{
-        deposit::Deposit, swap_base_input::SwapBaseInput, swap_base_output::SwapBaseOutput,
-        withdraw::Withdraw, RaydiumCpmmInstruction,
+        deposit::Deposit, initialize::Initialize,
+        initialize_with_permission::InitializeWithPermission, swap_base_input::SwapBaseInput,
+        swap_base_output::SwapBaseOutput, update_amm_config::UpdateAmmConfig,
+        update_pool_status::UpdatePoolStatus, withdraw::Withdraw, RaydiumCpmmInstruction,
     },
     solana_pubkey::Pubkey,
-    std::{collections::HashSet, sync::Arc},
+    std::sync::Arc,
 };
 
 /// Processor for Raydium CPMM instructions with optional token and AMM filtering.
@@ -29,74 +33,19 @@ use {
 ///
 /// Uses OR logic: a swap is logged if it matches ANY of the configured filters.
 /// If no filters are configured, all swaps are logged.
+#[derive(Clone)]
 pub struct RaydiumCpmmInstructionProcessor {
-    /// Set of token mint addresses to filter. Empty means no filter (track all).
-    filter_tokens: HashSet<Pubkey>,
-    /// Set of AMM/pool addresses to filter. Empty means no filter (track all).
-    filter_amms: HashSet<Pubkey>,
-    /// Output format for swap events.
-    output_format: OutputFormat,
-    /// Optional webhook notifier for sending alerts.
-    webhook_notifier: Option<Arc<WebhookNotifier>>,
+    /// Live token, pool, and wallet filters.
+    filters: FilterSet,
+    /// Emit path publishing matched events to the outputs.
+    emitter: EventEmitter,
 }
 
 impl RaydiumCpmmInstructionProcessor {
-    /// Creates a new processor with optional filtering and output configuration.
-    ///
-    /// # Arguments
-    ///
-    /// * `filter_tokens` - Set of token mints to track. Empty set tracks all tokens.
-    /// * `filter_amms` - Set of AMM/pool addresses to track. Empty set tracks all AMMs.
-    /// * `output_format` - Format for swap event output (text, json, json_pretty).
-    /// * `webhook_notifier` - Optional webhook notifier for sending alerts.
-    pub fn new(
-        filter_tokens: HashSet<Pubkey>,
-        filter_amms: HashSet<Pubkey>,
-        output_format: OutputFormat,
-        webhook_notifier: Option<Arc<WebhookNotifier>>,
-    ) -> Self {
-        Self {
-            filter_tokens,
-            filter_amms,
-            output_format,
-            webhook_notifier,
-        }
-    }
-
-    /// Checks if a swap matches any of the configured filters (OR logic).
-    ///
-    /// Returns `true` if:
-    /// - Both filters are empty (no filtering - track all), OR
-    /// - AMM matches `filter_amms`, OR
-    /// - Either input or output token matches `filter_tokens`
-    fn matches_filter(&self, amm: &Pubkey, input_mint: &Pubkey, output_mint: &Pubkey) -> bool {
-        // If no filters configured, track everything
-        if self.filter_amms.is_empty() && self.filter_tokens.is_empty() {
-            return true;
-        }
-        // Match if AMM is in filter list
-        if self.filter_amms.contains(amm) {
-            return true;
-        }
-        // Match if either token is in filter list
-        if self.filter_tokens.contains(input_mint) || self.filter_tokens.contains(output_mint) {
-            return true;
-        }
-        false
-    }
-
-    /// Outputs a swap event and optionally sends to webhook.
-    async fn emit_event(&self, event: SwapEvent) {
-        // Log the event
-        log::info!("{}", event.format(self.output_format));
-
-        // Send to webhook if configured
-        if let Some(ref notifier) = self.webhook_notifier {
-            // Use try_send to avoid blocking the processor
-            if let Err(e) = notifier.try_send(event) {
-                log::warn!("Failed to queue webhook notification: {e}");
-            }
-        }
+    /// Creates a processor checking instructions against `filters` and
+    /// publishing the resulting events through `emitter`.
+    pub fn new(filters: FilterSet, emitter: EventEmitter) -> Self {
+        Self { filters, emitter }
     }
 }
 
@@ -111,8 +60,8 @@ impl Processor for RaydiumCpmmInstructionProcessor {
 
     async fn process(
         &mut self,
-        (metadata, instruction, _nested_instructions, raw_instruction): Self::InputType,
-        _metrics: Arc<MetricsCollection>,
+        (metadata, instruction, nested_instructions, raw_instruction): Self::InputType,
+        metrics: Arc<MetricsCollection>,
     ) -> CarbonResult<()> {
         let signature = metadata.transaction_metadata.signature.to_string();
         let slot = metadata.transaction_metadata.slot;
@@ -121,30 +70,51 @@ impl Processor for RaydiumCpmmInstructionProcessor {
             // SwapBaseInput - exact input amount swap
             RaydiumCpmmInstruction::SwapBaseInput(ref swap_data) => {
                 if let Some(accounts) = SwapBaseInput::arrange_accounts(&raw_instruction.accounts) {
-                    if self.matches_filter(
+                    if self.filters.matches(
                         &accounts.pool_state,
-                        &accounts.input_token_mint,
-                        &accounts.output_token_mint,
+                        &[&accounts.input_token_mint, &accounts.output_token_mint],
+                        Some(&accounts.payer),
                     ) {
+                        // Extract actual amounts from nested token transfers
+                        // The instruction's minimum_amount_out is just slippage protection
+                        let (actual_input, actual_output) = extract_swap_amounts(
+                            &nested_instructions,
+                            &accounts.input_token_account,
+                            &accounts.output_token_account,
+                            swap_data.amount_in, // fallback to instruction amount
+                            swap_data.minimum_amount_out, // fallback to min (not ideal)
+                        );
+
+                        log::debug!(
+                            "[CPMM] SwapBaseInput: sig={}, pool={}, input={} (instr={}), output={} (min={})",
+                            signature,
+                            accounts.pool_state,
+                            actual_input,
+                            swap_data.amount_in,
+                            actual_output,
+                            swap_data.minimum_amount_out
+                        );
+
                         let event = SwapEvent::builder()
                             .event_type(EventType::Swap)
                             .protocol(Protocol::Cpmm)
                             .signature(&signature)
+                            .instruction_metadata(&metadata)
                             .pool_pubkey(&accounts.pool_state)
                             .input_token(TokenInfo::from_pubkey(
                                 &accounts.input_token_mint,
-                                swap_data.amount_in,
+                                actual_input,
                             ))
                             .output_token(TokenInfo::from_pubkey(
                                 &accounts.output_token_mint,
-                                swap_data.minimum_amount_out,
+                                actual_output,
                             ))
                             .direction(SwapDirection::ExactInput)
                             .maker_pubkey(&accounts.payer)
                             .slot(slot)
                             .build();
 
-                        self.emit_event(event).await;
+                        self.emitter.emit(event, &metrics).await;
                     }
                 }
             }
@@ -152,60 +122,83 @@ impl Processor for RaydiumCpmmInstructionProcessor {
             RaydiumCpmmInstruction::SwapBaseOutput(ref swap_data) => {
                 if let Some(accounts) = SwapBaseOutput::arrange_accounts(&raw_instruction.accounts)
                 {
-                    if self.matches_filter(
+                    if self.filters.matches(
                         &accounts.pool_state,
-                        &accounts.input_token_mint,
-                        &accounts.output_token_mint,
+                        &[&accounts.input_token_mint, &accounts.output_token_mint],
+                        Some(&accounts.payer),
                     ) {
+                        // Extract actual amounts from nested token transfers
+                        // The instruction's max_amount_in is just slippage protection
+                        let (actual_input, actual_output) = extract_swap_amounts(
+                            &nested_instructions,
+                            &accounts.input_token_account,
+                            &accounts.output_token_account,
+                            swap_data.max_amount_in, // fallback to max (not ideal)
+                            swap_data.amount_out,    // fallback to instruction amount
+                        );
+
+                        log::debug!(
+                            "[CPMM] SwapBaseOutput: sig={}, pool={}, input={} (max={}), output={} (instr={})",
+                            signature,
+                            accounts.pool_state,
+                            actual_input,
+                            swap_data.max_amount_in,
+                            actual_output,
+                            swap_data.amount_out
+                        );
+
                         let event = SwapEvent::builder()
                             .event_type(EventType::Swap)
                             .protocol(Protocol::Cpmm)
                             .signature(&signature)
+                            .instruction_metadata(&metadata)
                             .pool_pubkey(&accounts.pool_state)
                             .input_token(TokenInfo::from_pubkey(
                                 &accounts.input_token_mint,
-                                swap_data.max_amount_in,
+                                actual_input,
                             ))
                             .output_token(TokenInfo::from_pubkey(
                                 &accounts.output_token_mint,
-                                swap_data.amount_out,
+                                actual_output,
                             ))
                             .direction(SwapDirection::ExactOutput)
                             .maker_pubkey(&accounts.payer)
                             .slot(slot)
                             .build();
 
-                        self.emit_event(event).await;
+                        self.emitter.emit(event, &metrics).await;
                     }
                 }
             }
             // SwapEvent - contains actual amounts (not estimates)
-            RaydiumCpmmInstruction::SwapEvent(ref swap_event) => {
-                if self.matches_filter(
+            RaydiumCpmmInstruction::SwapEvent(ref swap_event)
+                if self.filters.matches(
                     &swap_event.pool_id,
-                    &swap_event.input_mint,
-                    &swap_event.output_mint,
-                ) {
-                    let event = SwapEvent::builder()
-                        .event_type(EventType::Swap)
-                        .protocol(Protocol::Cpmm)
-                        .signature(&signature)
-                        .pool(swap_event.pool_id.to_string())
-                        .input_token(TokenInfo::from_pubkey(
-                            &swap_event.input_mint,
-                            swap_event.input_amount,
-                        ))
-                        .output_token(TokenInfo::from_pubkey(
-                            &swap_event.output_mint,
-                            swap_event.output_amount,
-                        ))
-                        .direction(SwapDirection::Unknown)
-                        .fee(swap_event.trade_fee)
-                        .slot(slot)
-                        .build();
+                    &[&swap_event.input_mint, &swap_event.output_mint],
+                    None,
+                ) =>
+            {
+                let event = SwapEvent::builder()
+                    .event_type(EventType::Swap)
+                    .protocol(Protocol::Cpmm)
+                    .signature(&signature)
+                    .instruction_metadata(&metadata)
+                    .source(EventSource::EventLog)
+                    .pool(swap_event.pool_id.to_string())
+                    .input_token(TokenInfo::from_pubkey(
+                        &swap_event.input_mint,
+                        swap_event.input_amount,
+                    ))
+                    .output_token(TokenInfo::from_pubkey(
+                        &swap_event.output_mint,
+                        swap_event.output_amount,
+                    ))
+                    .direction(SwapDirection::Unknown)
+                    .fee(swap_event.trade_fee)
+                    .slot(slot)
+                    .build();
 
-                    self.emit_event(event).await;
-                }
+                self.emitter.emit(event, &metrics).await;
             }
             // Deposit - Add liquidity
             RaydiumCpmmInstruction::Deposit(ref deposit_data) => {
@@ -214,6 +207,7 @@ impl Processor for RaydiumCpmmInstructionProcessor {
                         .event_type(EventType::AddLiquidity)
                         .protocol(Protocol::Cpmm)
                         .signature(&signature)
+                        .instruction_metadata(&metadata)
                         .pool_pubkey(&accounts.pool_state)
                         .input_token(TokenInfo::from_pubkey(
                             &accounts.vault_0_mint,
@@ -227,7 +221,7 @@ impl Processor for RaydiumCpmmInstructionProcessor {
                         .slot(slot)
                         .build();
 
-                    self.emit_event(event).await;
+                    self.emitter.emit(event, &metrics).await;
                 }
             }
             // Withdraw - Remove liquidity
@@ -237,6 +231,7 @@ impl Processor for RaydiumCpmmInstructionProcessor {
                         .event_type(EventType::RemoveLiquidity)
                         .protocol(Protocol::Cpmm)
                         .signature(&signature)
+                        .instruction_metadata(&metadata)
                         .pool_pubkey(&accounts.pool_state)
                         .input_token(TokenInfo::from_pubkey(
                             &accounts.vault_0_mint,
@@ -250,7 +245,7 @@ impl Processor for RaydiumCpmmInstructionProcessor {
                         .slot(slot)
                         .build();
 
-                    self.emit_event(event).await;
+                    self.emitter.emit(event, &metrics).await;
                 }
             }
             // LpChangeEvent - LP change event with actual amounts
@@ -266,6 +261,7 @@ impl Processor for RaydiumCpmmInstructionProcessor {
                     .event_type(event_type)
                     .protocol(Protocol::Cpmm)
                     .signature(&signature)
+                    .instruction_metadata(&metadata)
                     .pool(lp_event.pool_id.to_string())
                     .input_token(TokenInfo::new(
                         lp_event.pool_id.to_string(), // No mint in event
@@ -278,21 +274,144 @@ impl Processor for RaydiumCpmmInstructionProcessor {
                     .slot(slot)
                     .build();
 
-                self.emit_event(event).await;
+                self.emitter.emit(event, &metrics).await;
             }
-            // Initialize - Pool creation
+            // Initialize - Pool creation with initial liquidity
             RaydiumCpmmInstruction::Initialize(ref init) => {
-                log::info!(
-                    "[CPMM] 🆕 Initialize: sig={}, init_amount_0={}, init_amount_1={}",
-                    signature,
-                    init.init_amount_0,
-                    init.init_amount_1
-                );
+                if let Some(accounts) = Initialize::arrange_accounts(&raw_instruction.accounts) {
+                    if self.filters.matches(
+                        &accounts.pool_state,
+                        &[&accounts.token_0_mint, &accounts.token_1_mint],
+                        Some(&accounts.creator),
+                    ) {
+                        let created = PoolCreatedEvent::new(
+                            &accounts.token_0_mint,
+                            &accounts.token_1_mint,
+                            &accounts.creator,
+                        )
+                        .with_reserves(init.init_amount_0, init.init_amount_1)
+                        .with_open_time(init.open_time)
+                        .with_lp_mint(&accounts.lp_mint);
+                        let event = SwapEvent::from_pool_created(
+                            Protocol::Cpmm,
+                            &metadata,
+                            &accounts.pool_state,
+                            created,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            // InitializeWithPermission - Pool creation by a permissioned payer
+            RaydiumCpmmInstruction::InitializeWithPermission(ref init) => {
+                if let Some(accounts) =
+                    InitializeWithPermission::arrange_accounts(&raw_instruction.accounts)
+                {
+                    if self.filters.matches(
+                        &accounts.pool_state,
+                        &[&accounts.token_0_mint, &accounts.token_1_mint],
+                        Some(&accounts.creator),
+                    ) {
+                        let created = PoolCreatedEvent::new(
+                            &accounts.token_0_mint,
+                            &accounts.token_1_mint,
+                            &accounts.creator,
+                        )
+                        .with_reserves(init.init_amount_0, init.init_amount_1)
+                        .with_open_time(init.open_time)
+                        .with_lp_mint(&accounts.lp_mint);
+                        let event = SwapEvent::from_pool_created(
+                            Protocol::Cpmm,
+                            &metadata,
+                            &accounts.pool_state,
+                            created,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            // UpdatePoolStatus - Enable or disable deposits, withdrawals, and swaps
+            RaydiumCpmmInstruction::UpdatePoolStatus(ref update) => {
+                if let Some(accounts) =
+                    UpdatePoolStatus::arrange_accounts(&raw_instruction.accounts)
+                {
+                    if self
+                        .filters
+                        .matches_pool(&accounts.pool_state, Some(&accounts.authority))
+                    {
+                        // Status bits: 0 = deposit, 1 = withdraw, 2 = swap disabled
+                        let admin = AdminActionEvent::status_change(
+                            u64::from(update.status),
+                            update.status & (1 << 2) != 0,
+                            &accounts.authority,
+                        );
+                        let event = SwapEvent::from_admin_action(
+                            Protocol::Cpmm,
+                            &metadata,
+                            &accounts.pool_state,
+                            admin,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            // UpdateAmmConfig - Fee or owner change of a fee config shared by many pools
+            RaydiumCpmmInstruction::UpdateAmmConfig(ref update) => {
+                if let Some(accounts) = UpdateAmmConfig::arrange_accounts(&raw_instruction.accounts)
+                {
+                    // The new owner is passed as the first remaining account
+                    let new_owner = raw_instruction.accounts.get(2).map(|meta| &meta.pubkey);
+                    if let Some(admin) =
+                        amm_config_change(update.param, update.value, new_owner, &accounts.owner)
+                    {
+                        if self
+                            .filters
+                            .matches_pool(&accounts.amm_config, Some(&accounts.owner))
+                        {
+                            let event = SwapEvent::from_admin_action(
+                                Protocol::Cpmm,
+                                &metadata,
+                                &accounts.amm_config,
+                                admin,
+                            );
+                            self.emitter.emit(event, &metrics).await;
+                        }
+                    }
+                }
             }
-            // Skip administrative events to reduce noise
+            // Skip remaining administrative events to reduce noise
             _ => {}
         };
 
         Ok(())
     }
 }
+
+/// Describes an `UpdateAmmConfig` change, or `None` for parameters that do not
+/// affect fees or ownership (e.g., disabling pool creation).
+fn amm_config_change(
+    param: u8,
+    value: u64,
+    new_owner: Option<&Pubkey>,
+    owner: &Pubkey,
+) -> Option<AdminActionEvent> {
+    let fee = |name| Some(AdminActionEvent::fee_change(name, value, owner));
+    match param {
+        0 => fee("trade_fee_rate"),
+        1 => fee("protocol_fee_rate"),
+        2 => fee("fund_fee_rate"),
+        3 => Some(AdminActionEvent::authority_transfer(
+            "protocol_owner",
+            new_owner,
+            owner,
+        )),
+        4 => Some(AdminActionEvent::authority_transfer(
+            "fund_owner",
+            new_owner,
+            owner,
+        )),
+        5 => fee("create_pool_fee"),
+        7 => fee("creator_fee_rate"),
+        _ => None,
+    }
+}
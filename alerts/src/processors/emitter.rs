@@ -0,0 +1,581 @@
+//! Filtering and event emission shared by the processors.
+//!
+//! Every processor decodes its own program's instructions, but checks them
+//! against the same live filters and publishes the resulting events through
+//! the same outputs. [`FilterSet`] holds the filter checks and [`EventEmitter`]
+//! the emit path, so a fix to either applies to every market.
+
+use {
+    super::log_target,
+    crate::{
+        aggregation::VolumeAggregator,
+        clock,
+        config::MarketType,
+        drain::DrainDetector,
+        enrichment::EnrichmentPipeline,
+        listings::ListingTracker,
+        lp_burn::LpBurnTracker,
+        metrics::Activity,
+        mev::MevDetector,
+        output::{
+            DedupConfig, Deduplicator, EventLog, Notifiers, SlotBatchSender, SlotBatcher, SwapEvent,
+        },
+        pool_stats::PoolStatsTracker,
+        reload::SharedConfig,
+        server::StreamHub,
+        stats::StatsStore,
+        storage::StorageSink,
+        throttle::AlertThrottle,
+    },
+    carbon_core::metrics::MetricsCollection,
+    solana_pubkey::Pubkey,
+    std::sync::Arc,
+};
+
+/// Live token, pool, and wallet filters of one market.
+///
+/// An event matches if ANY configured filter matches (OR logic); if no filters
+/// are configured, every event matches. Decisions made through
+/// [`matches`](Self::matches) and [`matches_pool`](Self::matches_pool) are
+/// recorded in the activity summary.
+#[derive(Clone)]
+pub struct FilterSet {
+    /// Live filters, swapped atomically on reload.
+    config: SharedConfig,
+    /// Market the decisions are recorded under.
+    market: MarketType,
+    /// Optional activity window recording filter decisions per pool.
+    activity: Option<Arc<Activity>>,
+}
+
+impl FilterSet {
+    /// Creates the filters of `market` from the live configuration.
+    pub fn new(config: SharedConfig, market: MarketType) -> Self {
+        Self {
+            config,
+            market,
+            activity: None,
+        }
+    }
+
+    /// Records filter decisions in the periodic activity summary.
+    pub fn with_activity(mut self, activity: Arc<Activity>) -> Self {
+        self.activity = Some(activity);
+        self
+    }
+
+    /// Returns the live configuration the filters are read from.
+    pub fn config(&self) -> &SharedConfig {
+        &self.config
+    }
+
+    /// Checks the filters and records the decision in the activity summary.
+    pub fn matches(&self, pool: &Pubkey, mints: &[&Pubkey], maker: Option<&Pubkey>) -> bool {
+        let matched = self.passes(pool, mints, maker);
+        self.record(pool, matched)
+    }
+
+    /// Checks the pool filter and records the decision in the activity summary.
+    pub fn matches_pool(&self, pool: &Pubkey, maker: Option<&Pubkey>) -> bool {
+        let matched = self.passes_pool(pool, maker);
+        self.record(pool, matched)
+    }
+
+    /// Checks if an event matches any of the configured filters.
+    ///
+    /// Returns `true` if:
+    /// - All filters are empty (no filtering - track all), OR
+    /// - The maker is a watched wallet, OR
+    /// - The pool matches `filter_amms`, OR
+    /// - Any of the event's token mints matches `filter_tokens`
+    pub fn passes(&self, pool: &Pubkey, mints: &[&Pubkey], maker: Option<&Pubkey>) -> bool {
+        let config = self.config.load();
+        config.tracks_all()
+            || config.watches(maker)
+            || config.filter_amms.contains(pool)
+            || mints
+                .iter()
+                .any(|mint| config.filter_tokens.contains(*mint))
+    }
+
+    /// Checks if a pool matches the filter (for instructions without token mints).
+    ///
+    /// Without token mints, only the pool filter applies: the event matches
+    /// if no pools are filtered, even if tokens are.
+    pub fn passes_pool(&self, pool: &Pubkey, maker: Option<&Pubkey>) -> bool {
+        let config = self.config.load();
+        config.tracks_all() || config.watches(maker) || self.passes_amm(pool)
+    }
+
+    /// Checks if a pool is in `filter_amms`, or no pools are filtered.
+    pub fn passes_amm(&self, pool: &Pubkey) -> bool {
+        let config = self.config.load();
+        config.filter_amms.is_empty() || config.filter_amms.contains(pool)
+    }
+
+    /// Records a filter decision for `pool` in the activity summary and returns it.
+    pub fn record(&self, pool: &Pubkey, matched: bool) -> bool {
+        if let Some(ref activity) = self.activity {
+            activity.record_filter(self.market.name(), pool, matched);
+        }
+        matched
+    }
+}
+
+/// Emit path of one market's processor: enrichment, deduplication, detectors,
+/// slot batching, and the outputs every emitted event is published to.
+#[derive(Clone)]
+pub struct EventEmitter {
+    /// Live rules and labels, swapped atomically on reload.
+    config: SharedConfig,
+    /// Log target of the events, the module path of the market's processor.
+    target: &'static str,
+    /// Console sink logging swap events in the output format.
+    event_log: Arc<EventLog>,
+    /// Alert channels (webhooks, Telegram subscriptions, ...) emitted events are sent to.
+    notifiers: Arc<Notifiers>,
+    /// Optional hub broadcasting derived topics (price ticks) to stream clients.
+    stream_hub: Option<Arc<StreamHub>>,
+    /// Optional per-token statistics updated with every emitted event.
+    stats: Option<Arc<StatsStore>>,
+    /// Optional rolling per-pool volume updated with every emitted swap.
+    aggregator: Option<Arc<VolumeAggregator>>,
+    /// Optional sandwich detector fed with every delivered swap.
+    mev: Option<Arc<MevDetector>>,
+    /// Optional liquidity drain detector fed with every delivered event.
+    drain: Option<Arc<DrainDetector>>,
+    /// Optional new listing filter dropping swaps on pools and tokens over the maximum age.
+    listings: Option<Arc<ListingTracker>>,
+    /// Optional LP burn tracker following new pools and badging swaps on burned ones.
+    lp_burn: Option<Arc<LpBurnTracker>>,
+    /// Optional tracker attaching per-pool running totals to every published event.
+    pool_stats: Option<Arc<PoolStatsTracker>>,
+    /// Optional throttle withholding swap alerts over the per-token and per-pool limits.
+    throttle: Option<Arc<AlertThrottle>>,
+    /// Label of the additional program deployment the processor decodes, if any.
+    deployment: Option<String>,
+    /// Optional slot batcher holding events until their slot is complete.
+    slot_batch: Option<SlotBatchSender>,
+    /// Optional storage sink for persisting events.
+    storage_sink: Option<Arc<dyn StorageSink>>,
+    /// Optional enrichment pipeline run on every event before it is emitted.
+    enrichment: Option<Arc<EnrichmentPipeline>>,
+    /// Optional deduplicator collapsing instruction and event-log variants of a swap.
+    dedup: Option<Arc<Deduplicator>>,
+}
+
+impl EventEmitter {
+    /// Creates an emitter logging and alerting on the events of `market`.
+    ///
+    /// # Arguments
+    ///
+    /// * `market` - Market whose processor the events come from.
+    /// * `config` - Live rules and labels, reloadable at runtime.
+    /// * `event_log` - Console sink logging events in the output format (text, json, json_pretty).
+    /// * `notifiers` - Alert channels emitted events are sent to.
+    pub fn new(
+        market: MarketType,
+        config: SharedConfig,
+        event_log: Arc<EventLog>,
+        notifiers: Arc<Notifiers>,
+    ) -> Self {
+        Self {
+            config,
+            target: log_target(market),
+            event_log,
+            notifiers,
+            stream_hub: None,
+            stats: None,
+            aggregator: None,
+            mev: None,
+            drain: None,
+            listings: None,
+            lp_burn: None,
+            pool_stats: None,
+            throttle: None,
+            deployment: None,
+            slot_batch: None,
+            storage_sink: None,
+            enrichment: None,
+            dedup: None,
+        }
+    }
+
+    /// Persists emitted events to a storage sink.
+    pub fn with_storage(mut self, sink: Arc<dyn StorageSink>) -> Self {
+        self.storage_sink = Some(sink);
+        self
+    }
+
+    /// Runs the enrichment pipeline on every event before it is emitted.
+    pub fn with_enrichment(mut self, pipeline: Arc<EnrichmentPipeline>) -> Self {
+        self.enrichment = Some(pipeline);
+        self
+    }
+
+    /// Broadcasts derived topics (e.g., `price:<pool>`) of emitted events to stream clients.
+    pub fn with_stream_hub(mut self, hub: Arc<StreamHub>) -> Self {
+        self.stream_hub = Some(hub);
+        self
+    }
+
+    /// Records emitted events in per-token statistics.
+    pub fn with_stats(mut self, stats: Arc<StatsStore>) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    /// Adds emitted swaps to the rolling per-pool volume.
+    pub fn with_aggregator(mut self, aggregator: Arc<VolumeAggregator>) -> Self {
+        self.aggregator = Some(aggregator);
+        self
+    }
+
+    /// Checks delivered swaps for sandwiches and delivers the resulting alerts.
+    pub fn with_mev(mut self, mev: Arc<MevDetector>) -> Self {
+        self.mev = Some(mev);
+        self
+    }
+
+    /// Checks delivered withdrawals and creator sells for liquidity drains and
+    /// delivers the resulting alerts.
+    pub fn with_drain(mut self, drain: Arc<DrainDetector>) -> Self {
+        self.drain = Some(drain);
+        self
+    }
+
+    /// Only emits swaps on pools and tokens created within the listing age.
+    pub fn with_listings(mut self, listings: Arc<ListingTracker>) -> Self {
+        self.listings = Some(listings);
+        self
+    }
+
+    /// Tracks the LP supply of new pools and sets `lp_burned` on their events.
+    pub fn with_lp_burn(mut self, lp_burn: Arc<LpBurnTracker>) -> Self {
+        self.lp_burn = Some(lp_burn);
+        self
+    }
+
+    /// Sets the pool's buys, sells, and volume since first seen on every published event.
+    pub fn with_pool_stats(mut self, pool_stats: Arc<PoolStatsTracker>) -> Self {
+        self.pool_stats = Some(pool_stats);
+        self
+    }
+
+    /// Withholds alerts on swaps over the per-token and per-pool limits; they
+    /// are still stored, streamed, and counted in statistics.
+    pub fn with_throttle(mut self, throttle: Arc<AlertThrottle>) -> Self {
+        self.throttle = Some(throttle);
+        self
+    }
+
+    /// Labels events as decoded from an additional program deployment.
+    pub fn with_deployment(mut self, label: impl Into<String>) -> Self {
+        self.deployment = Some(label.into());
+        self
+    }
+
+    /// Holds emitted events until their slot is complete, then emits the slot as a batch.
+    ///
+    /// Events are published by a copy of the emitter as configured at this point.
+    pub fn with_slot_batch(mut self, batcher: &SlotBatcher) -> Self {
+        let publisher = self.clone();
+        self.slot_batch = Some(batcher.sender(move |event| publisher.publish(event)));
+        self
+    }
+
+    /// Enables deduplication of swap variants (instruction, event log, aggregator CPI).
+    ///
+    /// Swaps are held for the configured window and emitted once, preferring
+    /// the event-log variant with actual amounts. Call after the other `with_*`
+    /// methods: collapsed events are published by a copy of the emitter as
+    /// configured at this point.
+    pub fn with_dedup(mut self, config: DedupConfig) -> Self {
+        let (dedup, mut collapsed) = Deduplicator::new(config);
+        let publisher = self.clone();
+        tokio::spawn(async move {
+            while let Some(event) = collapsed.recv().await {
+                publisher.deliver(event);
+            }
+        });
+        self.dedup = Some(Arc::new(dedup));
+        self
+    }
+
+    /// Counts an event (`events_emitted`), labels its deployment, enriches it,
+    /// and emits it, routing through the deduplicator if enabled.
+    pub async fn emit(&self, mut event: SwapEvent, metrics: &MetricsCollection) {
+        // Swaps on pools and tokens older than the listing age are dropped
+        // like unmatched ones
+        if !self
+            .listings
+            .as_ref()
+            .is_none_or(|listings| listings.observe(&event))
+        {
+            return;
+        }
+        let _ = metrics.increment_counter("events_emitted", 1).await;
+        event.deployment = self.deployment.clone();
+        if let Some(ref lp_burn) = self.lp_burn {
+            lp_burn.observe(&mut event);
+        }
+        if let Some(ref pipeline) = self.enrichment {
+            pipeline.run(&mut event, metrics).await;
+        }
+        // New tokens are screened on the authorities set by enrichment
+        if !self.config.load().passes_authorities(&event) {
+            return;
+        }
+
+        match self.dedup {
+            Some(ref dedup) => dedup.submit(event),
+            None => self.deliver(event),
+        }
+    }
+
+    /// Publishes an event, or hands it to the slot batcher if enabled.
+    ///
+    /// Swaps are checked for sandwiches here, while they are still in block order,
+    /// and withdrawals and creator sells for liquidity drains.
+    fn deliver(&self, event: SwapEvent) {
+        let mev_alert = self.mev.as_ref().and_then(|mev| mev.observe(&event));
+        let drain_alert = self.drain.as_ref().and_then(|drain| drain.observe(&event));
+        match self.slot_batch {
+            Some(ref batch) => batch.submit(event),
+            None => self.publish(event),
+        }
+        for alert in [mev_alert, drain_alert].into_iter().flatten() {
+            self.deliver(alert);
+        }
+    }
+
+    /// Sends an event to storage, streams, and statistics, then logs it and
+    /// sends it to the alert channels if it passes the side filter and throttle.
+    fn publish(&self, mut event: SwapEvent) {
+        let config = self.config.load();
+
+        // Show labels instead of addresses
+        event.apply_labels(&config.labels);
+
+        // Attach the pool's running totals
+        if let Some(ref pool_stats) = self.pool_stats {
+            pool_stats.record(&mut event);
+        }
+
+        // Persist if storage is configured
+        if let Some(ref sink) = self.storage_sink {
+            if let Err(e) = sink.try_store(event.clone()) {
+                log::warn!("Failed to queue event for {} storage: {e}", sink.name());
+            }
+        }
+
+        // Broadcast derived topics to stream clients
+        if let Some(ref hub) = self.stream_hub {
+            hub.publish(&event);
+        }
+
+        // Complete enrichment cut short and patch the stored and streamed event
+        if let Some(ref pipeline) = self.enrichment {
+            pipeline.spawn_follow_up(&event);
+        }
+
+        // Update per-token statistics
+        if let Some(ref stats) = self.stats {
+            stats.record(&event);
+        }
+
+        // Update rolling per-pool volume
+        if let Some(ref aggregator) = self.aggregator {
+            aggregator.record(&event);
+        }
+
+        // Alert only on swaps on the side filter's side; the other side is
+        // still stored, streamed, counted, and checked for sandwiches
+        if !config.passes_side(&event) {
+            return;
+        }
+
+        // Alert only on swaps within the token and pool limits
+        if let Some(ref throttle) = self.throttle {
+            if !throttle.admit(&event, clock::now()) {
+                return;
+            }
+        }
+
+        // Log the event
+        self.event_log.log(&event, self.target);
+
+        // Route to the destinations of matching rules
+        if let Some(ref rules) = config.rules {
+            rules.dispatch(&event);
+        }
+
+        // Send to the alert channels
+        self.notifiers.dispatch(&event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            mev::{MevConfig, MevDetector},
+            output::{
+                swap_event::WSOL_MINT, EventType, Notifier, OutputFormat, Protocol, TokenInfo,
+                TradeSide,
+            },
+            reload::LiveConfig,
+            storage::StorageError,
+        },
+        arc_swap::ArcSwap,
+        async_trait::async_trait,
+        std::{collections::HashSet, sync::Mutex, time::Duration},
+    };
+
+    /// Storage sink and notifier recording the events they were sent.
+    #[derive(Default)]
+    struct Recording {
+        events: Mutex<Vec<(String, EventType)>>,
+    }
+
+    impl Recording {
+        fn record(&self, event: &SwapEvent) {
+            let entry = (event.signature.clone(), event.event_type);
+            self.events.lock().unwrap().push(entry);
+        }
+
+        fn events(&self) -> Vec<(String, EventType)> {
+            self.events.lock().unwrap().clone()
+        }
+    }
+
+    impl StorageSink for Recording {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        fn try_store(&self, event: SwapEvent) -> Result<(), StorageError> {
+            self.record(&event);
+            Ok(())
+        }
+
+        fn try_update(&self, _event: SwapEvent) -> Result<(), StorageError> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl Notifier for Recording {
+        fn name(&self) -> &str {
+            "recording"
+        }
+
+        async fn notify(&self, event: &SwapEvent) {
+            self.record(event);
+        }
+    }
+
+    /// A swap of SOL for a token, or back when `side` is a sell.
+    fn swap(sig: &str, maker: &str, side: TradeSide, tokens: u64) -> SwapEvent {
+        let sol = TokenInfo::new(WSOL_MINT, 1_000_000_000).with_usd_value(150.0);
+        let token = TokenInfo::new("TokenMint", tokens);
+        let (input, output) = match side {
+            TradeSide::Buy => (sol, token),
+            TradeSide::Sell => (token, sol),
+        };
+        SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature(sig)
+            .pool("pool")
+            .input_token(input)
+            .output_token(output)
+            .maker(maker)
+            .slot(100)
+            .build()
+    }
+
+    fn filters(tokens: &[Pubkey], amms: &[Pubkey], wallets: &[Pubkey]) -> FilterSet {
+        let config = LiveConfig {
+            filter_tokens: tokens.iter().copied().collect::<HashSet<_>>(),
+            filter_amms: amms.iter().copied().collect(),
+            watch_wallets: wallets.iter().copied().collect(),
+            ..LiveConfig::default()
+        };
+        FilterSet::new(Arc::new(ArcSwap::from_pointee(config)), MarketType::Cpmm)
+    }
+
+    #[test]
+    fn test_filters_match_any_configured_filter() {
+        let [pool, other_pool, mint, other_mint, wallet] =
+            std::array::from_fn(|i| Pubkey::new_from_array([i as u8 + 1; 32]));
+
+        // No filters track everything
+        let all = filters(&[], &[], &[]);
+        assert!(all.passes(&pool, &[&mint], None));
+        assert!(all.passes_pool(&pool, None));
+
+        let by_token = filters(&[mint], &[], &[wallet]);
+        assert!(by_token.passes(&pool, &[&other_mint, &mint], None));
+        assert!(!by_token.passes(&pool, &[&other_mint], None));
+        assert!(by_token.passes(&pool, &[&other_mint], Some(&wallet)));
+        // Without mints, a token filter alone does not rule out a pool
+        assert!(by_token.passes_pool(&pool, None));
+
+        let by_pool = filters(&[mint], &[pool], &[]);
+        assert!(by_pool.passes(&pool, &[], None));
+        assert!(by_pool.passes(&other_pool, &[&mint], None));
+        assert!(!by_pool.passes(&other_pool, &[&other_mint], None));
+        assert!(by_pool.passes_pool(&pool, None));
+        assert!(!by_pool.passes_pool(&other_pool, None));
+        assert!(by_pool.passes_amm(&pool));
+        assert!(!by_pool.passes_amm(&other_pool));
+    }
+
+    #[tokio::test]
+    async fn test_side_filter_applies_to_alerts_only() {
+        let config = LiveConfig {
+            filter_side: Some(TradeSide::Sell),
+            ..LiveConfig::default()
+        };
+        let storage = Arc::new(Recording::default());
+        let alerts = Arc::new(Recording::default());
+        let notifiers = Arc::new(Notifiers::spawn(vec![alerts.clone()]));
+        let emitter = EventEmitter::new(
+            MarketType::Cpmm,
+            Arc::new(ArcSwap::from_pointee(config)),
+            Arc::new(EventLog::new(OutputFormat::Json, 1)),
+            notifiers.clone(),
+        )
+        .with_storage(storage.clone())
+        .with_mev(Arc::new(MevDetector::new(MevConfig {
+            min_victim_usd: 0.0,
+            size_tolerance_pct: 10.0,
+        })));
+
+        // A buy sandwich: only its back-run is a sell
+        let metrics = MetricsCollection::new(vec![]);
+        for event in [
+            swap("front", "bot", TradeSide::Buy, 1_000),
+            swap("victim", "alice", TradeSide::Buy, 400),
+            swap("back", "bot", TradeSide::Sell, 1_000),
+        ] {
+            emitter.emit(event, &metrics).await;
+        }
+        notifiers.shutdown(Duration::from_secs(5)).await;
+
+        // The detector saw the buys, and storage keeps both sides
+        let swap_entry = |sig: &str| (sig.to_string(), EventType::Swap);
+        let mev_alert = ("back".to_string(), EventType::MevAlert);
+        assert_eq!(
+            storage.events(),
+            vec![
+                swap_entry("front"),
+                swap_entry("victim"),
+                swap_entry("back"),
+                mev_alert.clone(),
+            ]
+        );
+        assert_eq!(alerts.events(), vec![swap_entry("back"), mev_alert]);
+    }
+}
@@ -0,0 +1,428 @@
+//! Raydium Launchpad (LaunchLab) instruction processor.
+//!
+//! This module handles decoded instructions from the Raydium Launchpad program:
+//! bonding curve buys and sells, token launches, and migrations of completed
+//! curves to AMM V4 or CPMM pools. Buys spend the quote token (usually SOL) for
+//! the launched base token; sells do the reverse.
+
+use {
+    super::{EventEmitter, FilterSet},
+    crate::{
+        enrichment::EnrichmentCache,
+        output::{
+            EventSource, EventType, PoolCreatedEvent, Protocol, SwapDirection, SwapEvent, TokenInfo,
+        },
+    },
+    async_trait::async_trait,
+    carbon_core::{
+        deserialize::ArrangeAccounts, error::CarbonResult, instruction::DecodedInstruction,
+        instruction::InstructionMetadata, instruction::NestedInstructions,
+        metrics::MetricsCollection, processor::Processor,
+    },
+    carbon_raydium_launchpad_decoder::{
+        instructions::{
+            buy_exact_in::BuyExactIn, buy_exact_out::BuyExactOut, initialize::Initialize,
+            initialize_v2::InitializeV2, initialize_with_token_2022::InitializeWithToken2022,
+            migrate_to_amm::MigrateToAmm, migrate_to_cpswap::MigrateToCpswap,
+            sell_exact_in::SellExactIn, sell_exact_out::SellExactOut, RaydiumLaunchpadInstruction,
+        },
+        types::{MintParams, TradeDirection},
+    },
+    solana_pubkey::Pubkey,
+    std::sync::Arc,
+};
+
+/// Processor for Raydium Launchpad instructions with optional token and pool filtering.
+///
+/// Emits:
+/// - `Swap` events for bonding curve buys and sells, from both the instructions
+///   (slippage bounds) and the `TradeEvent` log (actual amounts)
+/// - `CreatePool` events for token launches
+/// - `Migrate` events when a completed curve migrates to AMM V4 or CPMM
+///
+/// Uses OR logic: an event is logged if it matches ANY of the configured filters.
+/// If no filters are configured, all events are logged.
+#[derive(Clone)]
+pub struct RaydiumLaunchpadInstructionProcessor {
+    /// Live token, pool, and wallet filters.
+    filters: FilterSet,
+    /// Emit path publishing matched events to the outputs.
+    emitter: EventEmitter,
+    /// Optional pool cache resolving the mints of `TradeEvent` logs.
+    pool_cache: Option<Arc<EnrichmentCache>>,
+}
+
+impl RaydiumLaunchpadInstructionProcessor {
+    /// Creates a processor checking instructions against `filters` and
+    /// publishing the resulting events through `emitter`.
+    pub fn new(filters: FilterSet, emitter: EventEmitter) -> Self {
+        Self {
+            filters,
+            emitter,
+            pool_cache: None,
+        }
+    }
+
+    /// Resolves `TradeEvent` mints from the pool state.
+    ///
+    /// Trade event logs only reference the pool. Its base and quote mints are
+    /// fetched once per pool and cached; trade events of pools that can't be
+    /// resolved are skipped (the instruction variant is still emitted).
+    pub fn with_pool_cache(mut self, cache: Arc<EnrichmentCache>) -> Self {
+        self.pool_cache = Some(cache);
+        self
+    }
+
+    /// Resolves the base and quote mints of a pool from the pool cache.
+    async fn resolve_pool_mints(&self, pool: &Pubkey) -> Option<(Pubkey, Pubkey)> {
+        let info = self.pool_cache.as_ref()?.pool_or_resolve(pool).await?;
+        Some((info.token_mint0, info.token_mint1))
+    }
+}
+
+/// Builds a bonding curve trade event from a buy or sell instruction.
+fn trade_event(
+    metadata: &InstructionMetadata,
+    pool: &Pubkey,
+    input_token: TokenInfo,
+    output_token: TokenInfo,
+    direction: SwapDirection,
+    maker: &Pubkey,
+) -> SwapEvent {
+    SwapEvent::builder()
+        .event_type(EventType::Swap)
+        .protocol(Protocol::Launchpad)
+        .signature(metadata.transaction_metadata.signature.to_string())
+        .instruction_metadata(metadata)
+        .pool_pubkey(pool)
+        .input_token(input_token)
+        .output_token(output_token)
+        .direction(direction)
+        .maker_pubkey(maker)
+        .slot(metadata.transaction_metadata.slot)
+        .build()
+}
+
+/// Builds a token launch event; the launched token carries its symbol and decimals.
+fn launch_event(
+    metadata: &InstructionMetadata,
+    pool: &Pubkey,
+    base_mint: &Pubkey,
+    quote_mint: &Pubkey,
+    mint_params: &MintParams,
+    creator: &Pubkey,
+) -> SwapEvent {
+    SwapEvent::builder()
+        .event_type(EventType::CreatePool)
+        .protocol(Protocol::Launchpad)
+        .signature(metadata.transaction_metadata.signature.to_string())
+        .instruction_metadata(metadata)
+        .pool_pubkey(pool)
+        .input_token(
+            TokenInfo::from_pubkey(base_mint, 0)
+                .with_symbol(&mint_params.symbol)
+                .with_decimals(mint_params.decimals),
+        )
+        .output_token(TokenInfo::from_pubkey(quote_mint, 0))
+        .maker_pubkey(creator)
+        .slot(metadata.transaction_metadata.slot)
+        .pool_created(PoolCreatedEvent::new(base_mint, quote_mint, creator))
+        .build()
+}
+
+/// Builds a migration event for a completed bonding curve.
+fn migrate_event(
+    metadata: &InstructionMetadata,
+    pool: &Pubkey,
+    base_mint: &Pubkey,
+    quote_mint: &Pubkey,
+    payer: &Pubkey,
+) -> SwapEvent {
+    SwapEvent::builder()
+        .event_type(EventType::Migrate)
+        .protocol(Protocol::Launchpad)
+        .signature(metadata.transaction_metadata.signature.to_string())
+        .instruction_metadata(metadata)
+        .pool_pubkey(pool)
+        .input_token(TokenInfo::from_pubkey(base_mint, 0))
+        .output_token(TokenInfo::from_pubkey(quote_mint, 0))
+        .maker_pubkey(payer)
+        .slot(metadata.transaction_metadata.slot)
+        .build()
+}
+
+#[async_trait]
+impl Processor for RaydiumLaunchpadInstructionProcessor {
+    type InputType = (
+        InstructionMetadata,
+        DecodedInstruction<RaydiumLaunchpadInstruction>,
+        NestedInstructions,
+        solana_instruction::Instruction,
+    );
+
+    async fn process(
+        &mut self,
+        (metadata, instruction, _nested_instructions, raw_instruction): Self::InputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let signature = metadata.transaction_metadata.signature.to_string();
+        let slot = metadata.transaction_metadata.slot;
+
+        match instruction.data {
+            // BuyExactIn - spend an exact quote amount on the base token
+            RaydiumLaunchpadInstruction::BuyExactIn(ref buy) => {
+                if let Some(accounts) = BuyExactIn::arrange_accounts(&raw_instruction.accounts) {
+                    if self.filters.matches(
+                        &accounts.pool_state,
+                        &[&accounts.base_token_mint, &accounts.quote_token_mint],
+                        Some(&accounts.payer),
+                    ) {
+                        let event = trade_event(
+                            &metadata,
+                            &accounts.pool_state,
+                            TokenInfo::from_pubkey(&accounts.quote_token_mint, buy.amount_in),
+                            TokenInfo::from_pubkey(
+                                &accounts.base_token_mint,
+                                buy.minimum_amount_out,
+                            ),
+                            SwapDirection::ExactInput,
+                            &accounts.payer,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            // BuyExactOut - buy an exact base amount
+            RaydiumLaunchpadInstruction::BuyExactOut(ref buy) => {
+                if let Some(accounts) = BuyExactOut::arrange_accounts(&raw_instruction.accounts) {
+                    if self.filters.matches(
+                        &accounts.pool_state,
+                        &[&accounts.base_token_mint, &accounts.quote_token_mint],
+                        Some(&accounts.payer),
+                    ) {
+                        let event = trade_event(
+                            &metadata,
+                            &accounts.pool_state,
+                            TokenInfo::from_pubkey(
+                                &accounts.quote_token_mint,
+                                buy.maximum_amount_in,
+                            ),
+                            TokenInfo::from_pubkey(&accounts.base_token_mint, buy.amount_out),
+                            SwapDirection::ExactOutput,
+                            &accounts.payer,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            // SellExactIn - sell an exact base amount
+            RaydiumLaunchpadInstruction::SellExactIn(ref sell) => {
+                if let Some(accounts) = SellExactIn::arrange_accounts(&raw_instruction.accounts) {
+                    if self.filters.matches(
+                        &accounts.pool_state,
+                        &[&accounts.base_token_mint, &accounts.quote_token_mint],
+                        Some(&accounts.payer),
+                    ) {
+                        let event = trade_event(
+                            &metadata,
+                            &accounts.pool_state,
+                            TokenInfo::from_pubkey(&accounts.base_token_mint, sell.amount_in),
+                            TokenInfo::from_pubkey(
+                                &accounts.quote_token_mint,
+                                sell.minimum_amount_out,
+                            ),
+                            SwapDirection::ExactInput,
+                            &accounts.payer,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            // SellExactOut - sell base for an exact quote amount
+            RaydiumLaunchpadInstruction::SellExactOut(ref sell) => {
+                if let Some(accounts) = SellExactOut::arrange_accounts(&raw_instruction.accounts) {
+                    if self.filters.matches(
+                        &accounts.pool_state,
+                        &[&accounts.base_token_mint, &accounts.quote_token_mint],
+                        Some(&accounts.payer),
+                    ) {
+                        let event = trade_event(
+                            &metadata,
+                            &accounts.pool_state,
+                            TokenInfo::from_pubkey(
+                                &accounts.base_token_mint,
+                                sell.maximum_amount_in,
+                            ),
+                            TokenInfo::from_pubkey(&accounts.quote_token_mint, sell.amount_out),
+                            SwapDirection::ExactOutput,
+                            &accounts.payer,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            // TradeEvent - contains actual amounts; mints are resolved from the pool state
+            RaydiumLaunchpadInstruction::TradeEvent(ref trade) => {
+                let Some((base_mint, quote_mint)) =
+                    self.resolve_pool_mints(&trade.pool_state).await
+                else {
+                    log::debug!(
+                        "[LAUNCHPAD] Skipping trade event of unresolved pool {}",
+                        trade.pool_state
+                    );
+                    return Ok(());
+                };
+                if !self
+                    .filters
+                    .matches(&trade.pool_state, &[&base_mint, &quote_mint], None)
+                {
+                    return Ok(());
+                }
+
+                let (input_mint, output_mint) = match trade.trade_direction {
+                    TradeDirection::Buy => (quote_mint, base_mint),
+                    TradeDirection::Sell => (base_mint, quote_mint),
+                };
+                let direction = if trade.exact_in {
+                    SwapDirection::ExactInput
+                } else {
+                    SwapDirection::ExactOutput
+                };
+                // All fees are charged in the quote token
+                let fee =
+                    trade.protocol_fee + trade.platform_fee + trade.creator_fee + trade.share_fee;
+
+                let event = SwapEvent::builder()
+                    .event_type(EventType::Swap)
+                    .protocol(Protocol::Launchpad)
+                    .signature(&signature)
+                    .instruction_metadata(&metadata)
+                    .source(EventSource::EventLog)
+                    .pool_pubkey(&trade.pool_state)
+                    .input_token(TokenInfo::from_pubkey(&input_mint, trade.amount_in))
+                    .output_token(TokenInfo::from_pubkey(&output_mint, trade.amount_out))
+                    .direction(direction)
+                    .fee(fee)
+                    .slot(slot)
+                    .build();
+
+                self.emitter.emit(event, &metrics).await;
+            }
+            // Initialize - token launch with a new bonding curve
+            RaydiumLaunchpadInstruction::Initialize(ref init) => {
+                if let Some(accounts) = Initialize::arrange_accounts(&raw_instruction.accounts) {
+                    if self.filters.matches(
+                        &accounts.pool_state,
+                        &[&accounts.base_mint, &accounts.quote_mint],
+                        Some(&accounts.creator),
+                    ) {
+                        let event = launch_event(
+                            &metadata,
+                            &accounts.pool_state,
+                            &accounts.base_mint,
+                            &accounts.quote_mint,
+                            &init.base_mint_param,
+                            &accounts.creator,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            RaydiumLaunchpadInstruction::InitializeV2(ref init) => {
+                if let Some(accounts) = InitializeV2::arrange_accounts(&raw_instruction.accounts) {
+                    if self.filters.matches(
+                        &accounts.pool_state,
+                        &[&accounts.base_mint, &accounts.quote_mint],
+                        Some(&accounts.creator),
+                    ) {
+                        let event = launch_event(
+                            &metadata,
+                            &accounts.pool_state,
+                            &accounts.base_mint,
+                            &accounts.quote_mint,
+                            &init.base_mint_param,
+                            &accounts.creator,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            RaydiumLaunchpadInstruction::InitializeWithToken2022(ref init) => {
+                if let Some(accounts) =
+                    InitializeWithToken2022::arrange_accounts(&raw_instruction.accounts)
+                {
+                    if self.filters.matches(
+                        &accounts.pool_state,
+                        &[&accounts.base_mint, &accounts.quote_mint],
+                        Some(&accounts.creator),
+                    ) {
+                        let event = launch_event(
+                            &metadata,
+                            &accounts.pool_state,
+                            &accounts.base_mint,
+                            &accounts.quote_mint,
+                            &init.base_mint_param,
+                            &accounts.creator,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            // MigrateToAmm - completed curve migrates to an AMM V4 pool
+            RaydiumLaunchpadInstruction::MigrateToAmm(_) => {
+                if let Some(accounts) = MigrateToAmm::arrange_accounts(&raw_instruction.accounts) {
+                    if self.filters.matches(
+                        &accounts.pool_state,
+                        &[&accounts.base_mint, &accounts.quote_mint],
+                        Some(&accounts.payer),
+                    ) {
+                        log::info!(
+                            "[LAUNCHPAD] 🎓 Migrate: sig={}, pool={}, amm_v4_pool={}",
+                            signature,
+                            accounts.pool_state,
+                            accounts.amm_pool
+                        );
+                        let event = migrate_event(
+                            &metadata,
+                            &accounts.pool_state,
+                            &accounts.base_mint,
+                            &accounts.quote_mint,
+                            &accounts.payer,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            // MigrateToCpswap - completed curve migrates to a CPMM pool
+            RaydiumLaunchpadInstruction::MigrateToCpswap(_) => {
+                if let Some(accounts) = MigrateToCpswap::arrange_accounts(&raw_instruction.accounts)
+                {
+                    if self.filters.matches(
+                        &accounts.pool_state,
+                        &[&accounts.base_mint, &accounts.quote_mint],
+                        Some(&accounts.payer),
+                    ) {
+                        log::info!(
+                            "[LAUNCHPAD] 🎓 Migrate: sig={}, pool={}, cpmm_pool={}",
+                            signature,
+                            accounts.pool_state,
+                            accounts.cpswap_pool
+                        );
+                        let event = migrate_event(
+                            &metadata,
+                            &accounts.pool_state,
+                            &accounts.base_mint,
+                            &accounts.quote_mint,
+                            &accounts.payer,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            // Skip administrative instructions and events to reduce noise
+            _ => {}
+        };
+
+        Ok(())
+    }
+}
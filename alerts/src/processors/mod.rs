@@ -1,16 +1,58 @@
-//! Instruction processors for Raydium DEX protocols.
+//! Instruction processors for Raydium and Pump.fun DEX protocols.
 //!
 //! This module contains processor implementations for handling decoded instructions
-//! from various Raydium AMM programs:
+//! from the monitored programs:
 //!
 //! - [`cpmm`] - Raydium CPMM (Constant Product Market Maker) processor
 //! - [`clmm`] - Raydium CLMM (Concentrated Liquidity Market Maker) processor
 //! - [`amm_v4`] - Raydium AMM V4 processor
+//! - [`launchpad`] - Raydium Launchpad (LaunchLab) bonding curve processor
+//! - [`pumpfun`] - Pump.fun bonding curve processor
+//! - [`pumpswap`] - PumpSwap AMM processor
+//! - [`sharded`] - Worker pool wrapper for concurrent processing
+//!
+//! The processors share their filter checks ([`FilterSet`]) and emit path
+//! ([`EventEmitter`]); each only decodes its program's instructions into events.
 
 mod amm_v4;
 mod clmm;
 mod cpmm;
+mod emitter;
+mod launchpad;
+mod pumpfun;
+mod pumpswap;
+mod sharded;
+
+use {
+    crate::config::MarketType,
+    carbon_core::instruction::{DecodedInstruction, InstructionMetadata, NestedInstructions},
+};
 
 pub use amm_v4::RaydiumAmmV4InstructionProcessor;
 pub use clmm::RaydiumClmmInstructionProcessor;
 pub use cpmm::RaydiumCpmmInstructionProcessor;
+pub use emitter::{EventEmitter, FilterSet};
+pub use launchpad::RaydiumLaunchpadInstructionProcessor;
+pub use pumpfun::PumpFunInstructionProcessor;
+pub use pumpswap::PumpSwapInstructionProcessor;
+pub use sharded::ShardedProcessor;
+
+/// Input tuple delivered by Carbon to instruction processors.
+pub type InstructionInput<T> = (
+    InstructionMetadata,
+    DecodedInstruction<T>,
+    NestedInstructions,
+    solana_instruction::Instruction,
+);
+
+/// Returns the log target of a market's events: the module path of its processor.
+fn log_target(market: MarketType) -> &'static str {
+    match market {
+        MarketType::Cpmm => concat!(module_path!(), "::cpmm"),
+        MarketType::Clmm => concat!(module_path!(), "::clmm"),
+        MarketType::AmmV4 => concat!(module_path!(), "::amm_v4"),
+        MarketType::Launchpad => concat!(module_path!(), "::launchpad"),
+        MarketType::PumpFun => concat!(module_path!(), "::pumpfun"),
+        MarketType::PumpSwap => concat!(module_path!(), "::pumpswap"),
+    }
+}
@@ -0,0 +1,296 @@
+//! Pump.fun bonding curve instruction processor.
+//!
+//! This module handles decoded instructions from the Pump.fun program: bonding
+//! curve buys and sells, token launches, and migrations of completed curves to
+//! PumpSwap pools. Every curve trades its token against SOL; the bonding curve
+//! account serves as the event's pool.
+
+use {
+    super::{EventEmitter, FilterSet},
+    crate::output::{
+        swap_event::WSOL_MINT, EventSource, EventType, PoolCreatedEvent, Protocol, SwapDirection,
+        SwapEvent, TokenInfo,
+    },
+    async_trait::async_trait,
+    carbon_core::{
+        deserialize::ArrangeAccounts, error::CarbonResult, instruction::DecodedInstruction,
+        instruction::InstructionMetadata, instruction::NestedInstructions,
+        metrics::MetricsCollection, processor::Processor,
+    },
+    carbon_pumpfun_decoder::instructions::{
+        buy::Buy, create::Create, create_v2::CreateV2, migrate::Migrate, sell::Sell,
+        PumpfunInstruction,
+    },
+    solana_pubkey::Pubkey,
+    std::sync::Arc,
+};
+
+/// Wrapped SOL mint, the quote side of every bonding curve.
+const SOL_MINT: Pubkey = Pubkey::from_str_const(WSOL_MINT);
+
+/// Decimals of tokens launched on Pump.fun.
+const PUMPFUN_TOKEN_DECIMALS: u8 = 6;
+
+/// Processor for Pump.fun instructions with optional token and pool filtering.
+///
+/// Emits:
+/// - `Swap` events for bonding curve buys and sells, from both the instructions
+///   (slippage bounds) and the `TradeEvent` log (actual amounts)
+/// - `CreatePool` events for token launches
+/// - `Migrate` events when a completed curve migrates to PumpSwap
+///
+/// Uses OR logic: an event is logged if it matches ANY of the configured filters.
+/// If no filters are configured, all events are logged.
+#[derive(Clone)]
+pub struct PumpFunInstructionProcessor {
+    /// Live token, pool, and wallet filters.
+    filters: FilterSet,
+    /// Emit path publishing matched events to the outputs.
+    emitter: EventEmitter,
+}
+
+impl PumpFunInstructionProcessor {
+    /// Creates a processor checking instructions against `filters` and
+    /// publishing the resulting events through `emitter`.
+    pub fn new(filters: FilterSet, emitter: EventEmitter) -> Self {
+        Self { filters, emitter }
+    }
+
+    /// Checks the filters and records the decision in the activity summary.
+    ///
+    /// Every bonding curve trades its token against SOL, so a filtered SOL
+    /// mint matches all events.
+    fn matches_filter(
+        &self,
+        bonding_curve: &Pubkey,
+        mint: &Pubkey,
+        maker: Option<&Pubkey>,
+    ) -> bool {
+        self.filters
+            .matches(bonding_curve, &[mint, &SOL_MINT], maker)
+    }
+}
+
+/// Derives the bonding curve account of a mint under the decoded program.
+///
+/// Trade event logs only carry the mint; the curve is needed so that they share
+/// the pool of the instruction variant they are deduplicated against.
+fn bonding_curve_address(program_id: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"bonding-curve", mint.as_ref()], program_id).0
+}
+
+/// Returns the launched token, which always has Pump.fun's fixed decimals.
+fn pump_token(mint: &Pubkey, amount: u64) -> TokenInfo {
+    TokenInfo::from_pubkey(mint, amount).with_decimals(PUMPFUN_TOKEN_DECIMALS)
+}
+
+/// Builds a bonding curve trade event from a buy or sell instruction.
+fn trade_event(
+    metadata: &InstructionMetadata,
+    bonding_curve: &Pubkey,
+    input_token: TokenInfo,
+    output_token: TokenInfo,
+    direction: SwapDirection,
+    user: &Pubkey,
+) -> SwapEvent {
+    SwapEvent::builder()
+        .event_type(EventType::Swap)
+        .protocol(Protocol::PumpFun)
+        .signature(metadata.transaction_metadata.signature.to_string())
+        .instruction_metadata(metadata)
+        .pool_pubkey(bonding_curve)
+        .input_token(input_token)
+        .output_token(output_token)
+        .direction(direction)
+        .maker_pubkey(user)
+        .slot(metadata.transaction_metadata.slot)
+        .build()
+}
+
+/// Builds a token launch event; the launched token carries its symbol.
+fn launch_event(
+    metadata: &InstructionMetadata,
+    bonding_curve: &Pubkey,
+    mint: &Pubkey,
+    symbol: &str,
+    creator: &Pubkey,
+) -> SwapEvent {
+    SwapEvent::builder()
+        .event_type(EventType::CreatePool)
+        .protocol(Protocol::PumpFun)
+        .signature(metadata.transaction_metadata.signature.to_string())
+        .instruction_metadata(metadata)
+        .pool_pubkey(bonding_curve)
+        .input_token(pump_token(mint, 0).with_symbol(symbol))
+        .output_token(TokenInfo::from_pubkey(&SOL_MINT, 0))
+        .maker_pubkey(creator)
+        .slot(metadata.transaction_metadata.slot)
+        .pool_created(PoolCreatedEvent::new(mint, &SOL_MINT, creator))
+        .build()
+}
+
+#[async_trait]
+impl Processor for PumpFunInstructionProcessor {
+    type InputType = (
+        InstructionMetadata,
+        DecodedInstruction<PumpfunInstruction>,
+        NestedInstructions,
+        solana_instruction::Instruction,
+    );
+
+    async fn process(
+        &mut self,
+        (metadata, instruction, _nested_instructions, raw_instruction): Self::InputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let signature = metadata.transaction_metadata.signature.to_string();
+        let slot = metadata.transaction_metadata.slot;
+
+        match instruction.data {
+            // Buy - buy an exact token amount for at most `max_sol_cost`
+            PumpfunInstruction::Buy(ref buy) => {
+                if let Some(accounts) = Buy::arrange_accounts(&raw_instruction.accounts) {
+                    if self.matches_filter(
+                        &accounts.bonding_curve,
+                        &accounts.mint,
+                        Some(&accounts.user),
+                    ) {
+                        let event = trade_event(
+                            &metadata,
+                            &accounts.bonding_curve,
+                            TokenInfo::from_pubkey(&SOL_MINT, buy.max_sol_cost),
+                            pump_token(&accounts.mint, buy.amount),
+                            SwapDirection::ExactOutput,
+                            &accounts.user,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            // Sell - sell an exact token amount for at least `min_sol_output`
+            PumpfunInstruction::Sell(ref sell) => {
+                if let Some(accounts) = Sell::arrange_accounts(&raw_instruction.accounts) {
+                    if self.matches_filter(
+                        &accounts.bonding_curve,
+                        &accounts.mint,
+                        Some(&accounts.user),
+                    ) {
+                        let event = trade_event(
+                            &metadata,
+                            &accounts.bonding_curve,
+                            pump_token(&accounts.mint, sell.amount),
+                            TokenInfo::from_pubkey(&SOL_MINT, sell.min_sol_output),
+                            SwapDirection::ExactInput,
+                            &accounts.user,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            // TradeEvent - contains actual amounts for all buys and sells, including
+            // `buy_exact_sol_in`, which the decoder doesn't decode as an instruction
+            PumpfunInstruction::TradeEvent(ref trade) => {
+                let bonding_curve = bonding_curve_address(&instruction.program_id, &trade.mint);
+                if !self.matches_filter(&bonding_curve, &trade.mint, Some(&trade.user)) {
+                    return Ok(());
+                }
+
+                let sol = TokenInfo::from_pubkey(&SOL_MINT, trade.sol_amount);
+                let token = pump_token(&trade.mint, trade.token_amount);
+                let (input_token, output_token) = if trade.is_buy {
+                    (sol, token)
+                } else {
+                    (token, sol)
+                };
+
+                let event = SwapEvent::builder()
+                    .event_type(EventType::Swap)
+                    .protocol(Protocol::PumpFun)
+                    .signature(&signature)
+                    .instruction_metadata(&metadata)
+                    .source(EventSource::EventLog)
+                    .pool_pubkey(&bonding_curve)
+                    .input_token(input_token)
+                    .output_token(output_token)
+                    // Protocol and creator fees are charged in SOL
+                    .fee(trade.fee + trade.creator_fee)
+                    .maker_pubkey(&trade.user)
+                    .slot(slot)
+                    .timestamp(trade.timestamp)
+                    .build();
+
+                self.emitter.emit(event, &metrics).await;
+            }
+            // Create - token launch with a new bonding curve
+            PumpfunInstruction::Create(ref create) => {
+                if let Some(accounts) = Create::arrange_accounts(&raw_instruction.accounts) {
+                    if self.matches_filter(
+                        &accounts.bonding_curve,
+                        &accounts.mint,
+                        Some(&create.creator),
+                    ) {
+                        let event = launch_event(
+                            &metadata,
+                            &accounts.bonding_curve,
+                            &accounts.mint,
+                            &create.symbol,
+                            &create.creator,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            PumpfunInstruction::CreateV2(ref create) => {
+                if let Some(accounts) = CreateV2::arrange_accounts(&raw_instruction.accounts) {
+                    if self.matches_filter(
+                        &accounts.bonding_curve,
+                        &accounts.mint,
+                        Some(&create.creator),
+                    ) {
+                        let event = launch_event(
+                            &metadata,
+                            &accounts.bonding_curve,
+                            &accounts.mint,
+                            &create.symbol,
+                            &create.creator,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            // Migrate - completed curve migrates to a PumpSwap pool
+            PumpfunInstruction::Migrate(_) => {
+                if let Some(accounts) = Migrate::arrange_accounts(&raw_instruction.accounts) {
+                    if self.matches_filter(
+                        &accounts.bonding_curve,
+                        &accounts.mint,
+                        Some(&accounts.user),
+                    ) {
+                        log::info!(
+                            "[PUMPFUN] 🎓 Migrate: sig={}, bonding_curve={}, pumpswap_pool={}",
+                            signature,
+                            accounts.bonding_curve,
+                            accounts.pool
+                        );
+                        let event = SwapEvent::builder()
+                            .event_type(EventType::Migrate)
+                            .protocol(Protocol::PumpFun)
+                            .signature(&signature)
+                            .instruction_metadata(&metadata)
+                            .pool_pubkey(&accounts.bonding_curve)
+                            .input_token(pump_token(&accounts.mint, 0))
+                            .output_token(TokenInfo::from_pubkey(&SOL_MINT, 0))
+                            .maker_pubkey(&accounts.user)
+                            .slot(slot)
+                            .build();
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            // Skip administrative instructions and events to reduce noise
+            _ => {}
+        };
+
+        Ok(())
+    }
+}
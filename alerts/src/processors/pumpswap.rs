@@ -0,0 +1,289 @@
+//! PumpSwap AMM instruction processor.
+//!
+//! This module handles decoded instructions from the PumpSwap program, the
+//! constant product AMM that completed Pump.fun curves migrate to. Pools trade
+//! a base token (the launched token) against a quote token (usually SOL); buys
+//! spend the quote token, sells spend the base token.
+
+use {
+    super::{EventEmitter, FilterSet},
+    crate::{
+        enrichment::EnrichmentCache,
+        output::{
+            EventSource, EventType, PoolCreatedEvent, Protocol, SwapDirection, SwapEvent, TokenInfo,
+        },
+    },
+    async_trait::async_trait,
+    carbon_core::{
+        deserialize::ArrangeAccounts, error::CarbonResult, instruction::DecodedInstruction,
+        instruction::InstructionMetadata, instruction::NestedInstructions,
+        metrics::MetricsCollection, processor::Processor,
+    },
+    carbon_pump_swap_decoder::instructions::{
+        buy::Buy, buy_exact_quote_in::BuyExactQuoteIn, create_pool::CreatePool, sell::Sell,
+        PumpSwapInstruction,
+    },
+    solana_pubkey::Pubkey,
+    std::sync::Arc,
+};
+
+/// Processor for PumpSwap instructions with optional token and pool filtering.
+///
+/// Emits:
+/// - `Swap` events for buys and sells, from both the instructions (slippage
+///   bounds) and the `BuyEvent`/`SellEvent` logs (actual amounts)
+/// - `CreatePool` events for new pools, including migrated Pump.fun curves
+///
+/// Uses OR logic: an event is logged if it matches ANY of the configured filters.
+/// If no filters are configured, all events are logged.
+#[derive(Clone)]
+pub struct PumpSwapInstructionProcessor {
+    /// Live token, pool, and wallet filters.
+    filters: FilterSet,
+    /// Emit path publishing matched events to the outputs.
+    emitter: EventEmitter,
+    /// Optional pool cache resolving the mints of `BuyEvent`/`SellEvent` logs.
+    pool_cache: Option<Arc<EnrichmentCache>>,
+}
+
+impl PumpSwapInstructionProcessor {
+    /// Creates a processor checking instructions against `filters` and
+    /// publishing the resulting events through `emitter`.
+    pub fn new(filters: FilterSet, emitter: EventEmitter) -> Self {
+        Self {
+            filters,
+            emitter,
+            pool_cache: None,
+        }
+    }
+
+    /// Resolves `BuyEvent`/`SellEvent` mints from the pool account.
+    ///
+    /// Event logs only reference the pool. Its base and quote mints are fetched
+    /// once per pool and cached; events of pools that can't be resolved are
+    /// skipped (the instruction variant is still emitted).
+    pub fn with_pool_cache(mut self, cache: Arc<EnrichmentCache>) -> Self {
+        self.pool_cache = Some(cache);
+        self
+    }
+
+    /// Resolves the base and quote mints of a pool from the pool cache.
+    async fn resolve_pool_mints(&self, pool: &Pubkey) -> Option<(Pubkey, Pubkey)> {
+        let info = self.pool_cache.as_ref()?.pool_or_resolve(pool).await?;
+        Some((info.token_mint0, info.token_mint1))
+    }
+}
+
+/// Builds a swap event from a buy or sell instruction.
+fn trade_event(
+    metadata: &InstructionMetadata,
+    pool: &Pubkey,
+    input_token: TokenInfo,
+    output_token: TokenInfo,
+    direction: SwapDirection,
+    user: &Pubkey,
+) -> SwapEvent {
+    SwapEvent::builder()
+        .event_type(EventType::Swap)
+        .protocol(Protocol::PumpSwap)
+        .signature(metadata.transaction_metadata.signature.to_string())
+        .instruction_metadata(metadata)
+        .pool_pubkey(pool)
+        .input_token(input_token)
+        .output_token(output_token)
+        .direction(direction)
+        .maker_pubkey(user)
+        .slot(metadata.transaction_metadata.slot)
+        .build()
+}
+
+/// Builds a swap event from a `BuyEvent` or `SellEvent` log.
+fn trade_log_event(
+    metadata: &InstructionMetadata,
+    pool: &Pubkey,
+    input_token: TokenInfo,
+    output_token: TokenInfo,
+    fee: u64,
+    user: &Pubkey,
+    timestamp: i64,
+) -> SwapEvent {
+    SwapEvent::builder()
+        .event_type(EventType::Swap)
+        .protocol(Protocol::PumpSwap)
+        .signature(metadata.transaction_metadata.signature.to_string())
+        .instruction_metadata(metadata)
+        .source(EventSource::EventLog)
+        .pool_pubkey(pool)
+        .input_token(input_token)
+        .output_token(output_token)
+        .fee(fee)
+        .maker_pubkey(user)
+        .slot(metadata.transaction_metadata.slot)
+        .timestamp(timestamp)
+        .build()
+}
+
+#[async_trait]
+impl Processor for PumpSwapInstructionProcessor {
+    type InputType = (
+        InstructionMetadata,
+        DecodedInstruction<PumpSwapInstruction>,
+        NestedInstructions,
+        solana_instruction::Instruction,
+    );
+
+    async fn process(
+        &mut self,
+        (metadata, instruction, _nested_instructions, raw_instruction): Self::InputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        match instruction.data {
+            // Buy - buy an exact base amount for at most `max_quote_amount_in`
+            PumpSwapInstruction::Buy(ref buy) => {
+                if let Some(accounts) = Buy::arrange_accounts(&raw_instruction.accounts) {
+                    if self.filters.matches(
+                        &accounts.pool,
+                        &[&accounts.base_mint, &accounts.quote_mint],
+                        Some(&accounts.user),
+                    ) {
+                        let event = trade_event(
+                            &metadata,
+                            &accounts.pool,
+                            TokenInfo::from_pubkey(&accounts.quote_mint, buy.max_quote_amount_in),
+                            TokenInfo::from_pubkey(&accounts.base_mint, buy.base_amount_out),
+                            SwapDirection::ExactOutput,
+                            &accounts.user,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            // BuyExactQuoteIn - spend an exact quote amount on the base token
+            PumpSwapInstruction::BuyExactQuoteIn(ref buy) => {
+                if let Some(accounts) = BuyExactQuoteIn::arrange_accounts(&raw_instruction.accounts)
+                {
+                    if self.filters.matches(
+                        &accounts.pool,
+                        &[&accounts.base_mint, &accounts.quote_mint],
+                        Some(&accounts.user),
+                    ) {
+                        let event = trade_event(
+                            &metadata,
+                            &accounts.pool,
+                            TokenInfo::from_pubkey(&accounts.quote_mint, buy.spendable_quote_in),
+                            TokenInfo::from_pubkey(&accounts.base_mint, buy.min_base_amount_out),
+                            SwapDirection::ExactInput,
+                            &accounts.user,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            // Sell - sell an exact base amount for at least `min_quote_amount_out`
+            PumpSwapInstruction::Sell(ref sell) => {
+                if let Some(accounts) = Sell::arrange_accounts(&raw_instruction.accounts) {
+                    if self.filters.matches(
+                        &accounts.pool,
+                        &[&accounts.base_mint, &accounts.quote_mint],
+                        Some(&accounts.user),
+                    ) {
+                        let event = trade_event(
+                            &metadata,
+                            &accounts.pool,
+                            TokenInfo::from_pubkey(&accounts.base_mint, sell.base_amount_in),
+                            TokenInfo::from_pubkey(&accounts.quote_mint, sell.min_quote_amount_out),
+                            SwapDirection::ExactInput,
+                            &accounts.user,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            // BuyEvent - actual amounts of a buy; mints are resolved from the pool
+            PumpSwapInstruction::BuyEvent(ref buy) => {
+                let Some((base_mint, quote_mint)) = self.resolve_pool_mints(&buy.pool).await else {
+                    log::debug!(
+                        "[PUMPSWAP] Skipping buy event of unresolved pool {}",
+                        buy.pool
+                    );
+                    return Ok(());
+                };
+                if !self
+                    .filters
+                    .matches(&buy.pool, &[&base_mint, &quote_mint], Some(&buy.user))
+                {
+                    return Ok(());
+                }
+
+                // All fees are charged in the quote token
+                let event = trade_log_event(
+                    &metadata,
+                    &buy.pool,
+                    TokenInfo::from_pubkey(&quote_mint, buy.user_quote_amount_in),
+                    TokenInfo::from_pubkey(&base_mint, buy.base_amount_out),
+                    buy.lp_fee + buy.protocol_fee + buy.coin_creator_fee,
+                    &buy.user,
+                    buy.timestamp,
+                );
+                self.emitter.emit(event, &metrics).await;
+            }
+            // SellEvent - actual amounts of a sell; mints are resolved from the pool
+            PumpSwapInstruction::SellEvent(ref sell) => {
+                let Some((base_mint, quote_mint)) = self.resolve_pool_mints(&sell.pool).await
+                else {
+                    log::debug!(
+                        "[PUMPSWAP] Skipping sell event of unresolved pool {}",
+                        sell.pool
+                    );
+                    return Ok(());
+                };
+                if !self
+                    .filters
+                    .matches(&sell.pool, &[&base_mint, &quote_mint], Some(&sell.user))
+                {
+                    return Ok(());
+                }
+
+                let event = trade_log_event(
+                    &metadata,
+                    &sell.pool,
+                    TokenInfo::from_pubkey(&base_mint, sell.base_amount_in),
+                    TokenInfo::from_pubkey(&quote_mint, sell.user_quote_amount_out),
+                    sell.lp_fee + sell.protocol_fee + sell.coin_creator_fee,
+                    &sell.user,
+                    sell.timestamp,
+                );
+                self.emitter.emit(event, &metrics).await;
+            }
+            // CreatePool - new pool seeded with initial base and quote liquidity
+            PumpSwapInstruction::CreatePool(ref create) => {
+                if let Some(accounts) = CreatePool::arrange_accounts(&raw_instruction.accounts) {
+                    if self.filters.matches(
+                        &accounts.pool,
+                        &[&accounts.base_mint, &accounts.quote_mint],
+                        Some(&accounts.creator),
+                    ) {
+                        let created = PoolCreatedEvent::new(
+                            &accounts.base_mint,
+                            &accounts.quote_mint,
+                            &accounts.creator,
+                        )
+                        .with_reserves(create.base_amount_in, create.quote_amount_in)
+                        .with_lp_mint(&accounts.lp_mint);
+                        let event = SwapEvent::from_pool_created(
+                            Protocol::PumpSwap,
+                            &metadata,
+                            &accounts.pool,
+                            created,
+                        );
+                        self.emitter.emit(event, &metrics).await;
+                    }
+                }
+            }
+            // Skip administrative instructions and events to reduce noise
+            _ => {}
+        };
+
+        Ok(())
+    }
+}
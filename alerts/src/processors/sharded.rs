@@ -0,0 +1,205 @@
+//! Sharded worker pool wrapper for instruction processors.
+//!
+//! Carbon drives each processor sequentially: the pipeline awaits `process()` for
+//! one instruction before handing over the next. For busy protocols (CLMM in
+//! particular) this makes the processor the bottleneck.
+//!
+//! [`ShardedProcessor`] fans decoded instructions out to a fixed number of worker
+//! tasks, each owning its own clone of the inner processor. Instructions are
+//! sharded by transaction signature so all instructions of one transaction are
+//! handled by the same worker, in order.
+//!
+//! Each instruction is processed in a `transaction` span carrying the protocol,
+//! block slot, and transaction signature, so every record logged while
+//! processing it (including its alert) can be correlated with its block.
+
+use {
+    super::InstructionInput,
+    crate::{config::ConcurrencyConfig, metrics::Activity},
+    async_trait::async_trait,
+    carbon_core::{
+        error::{CarbonResult, Error},
+        metrics::MetricsCollection,
+        processor::Processor,
+    },
+    std::{
+        collections::hash_map::DefaultHasher,
+        fmt,
+        hash::{Hash, Hasher},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Instant,
+    },
+    tokio::sync::mpsc,
+    tracing::Instrument,
+};
+
+/// A queued unit of work for a shard worker.
+type Job<T> = (InstructionInput<T>, Arc<MetricsCollection>);
+
+/// Processor wrapper that distributes instructions across a pool of workers.
+///
+/// Reports per-protocol utilization through the pipeline metrics:
+/// - `{label}_queue_depth` (gauge) - instructions waiting across all shards
+/// - `{label}_busy_workers` (gauge) - workers currently processing an instruction
+/// - `{label}_instructions_processed` (counter) - instructions handled by workers
+/// - `{label}_instructions_failed` (counter) - instructions whose processing failed
+/// - `{label}_process_time_us` (histogram) - per-instruction processing time
+///
+/// With [`with_activity`](Self::with_activity), the kind of every dispatched
+/// instruction is also recorded for the periodic activity summary.
+pub struct ShardedProcessor<T: Send + 'static> {
+    /// Metric name prefix (e.g., "cpmm").
+    label: &'static str,
+    /// One channel sender per worker.
+    shards: Vec<mpsc::Sender<Job<T>>>,
+    /// Total channel capacity across all shards.
+    total_capacity: usize,
+    /// Number of workers currently processing an instruction.
+    busy_workers: Arc<AtomicUsize>,
+    /// Optional activity window recording instruction kinds.
+    activity: Option<Arc<Activity>>,
+}
+
+impl<T: Send + 'static> ShardedProcessor<T> {
+    /// Creates a sharded processor and spawns its worker tasks.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - Metric name prefix identifying the protocol
+    /// * `processor` - Inner processor; each worker gets its own clone
+    /// * `config` - Worker count and per-worker channel capacity
+    pub fn new<P>(label: &'static str, processor: P, config: ConcurrencyConfig) -> Self
+    where
+        P: Processor<InputType = InstructionInput<T>> + Clone + Send + Sync + 'static,
+    {
+        let workers = config.workers.max(1);
+        let capacity = config.channel_capacity.max(1);
+        let busy_workers = Arc::new(AtomicUsize::new(0));
+
+        let shards = (0..workers)
+            .map(|worker_id| {
+                let (tx, rx) = mpsc::channel::<Job<T>>(capacity);
+                tokio::spawn(Self::worker_task(
+                    label,
+                    worker_id,
+                    processor.clone(),
+                    rx,
+                    busy_workers.clone(),
+                ));
+                tx
+            })
+            .collect();
+
+        Self {
+            label,
+            shards,
+            total_capacity: workers * capacity,
+            busy_workers,
+            activity: None,
+        }
+    }
+
+    /// Records the kind of every dispatched instruction in `activity`.
+    pub fn with_activity(mut self, activity: Arc<Activity>) -> Self {
+        self.activity = Some(activity);
+        self
+    }
+
+    /// Picks the shard for a transaction signature.
+    fn shard_index(&self, signature: &impl Hash) -> usize {
+        let mut hasher = DefaultHasher::new();
+        signature.hash(&mut hasher);
+        (hasher.finish() % self.shards.len() as u64) as usize
+    }
+
+    /// Returns the number of instructions waiting across all shards.
+    fn queue_depth(&self) -> usize {
+        let available: usize = self.shards.iter().map(|tx| tx.capacity()).sum();
+        self.total_capacity - available
+    }
+
+    /// Worker loop: processes jobs from a single shard until the channel closes.
+    async fn worker_task<P>(
+        label: &'static str,
+        worker_id: usize,
+        mut processor: P,
+        mut rx: mpsc::Receiver<Job<T>>,
+        busy_workers: Arc<AtomicUsize>,
+    ) where
+        P: Processor<InputType = InstructionInput<T>> + Send,
+    {
+        while let Some((input, metrics)) = rx.recv().await {
+            let span = tracing::info_span!(
+                "transaction",
+                protocol = label,
+                slot = input.0.transaction_metadata.slot,
+                signature = %input.0.transaction_metadata.signature,
+            );
+
+            busy_workers.fetch_add(1, Ordering::Relaxed);
+            let start = Instant::now();
+            let result = processor
+                .process(input, metrics.clone())
+                .instrument(span.clone())
+                .await;
+            let elapsed_us = start.elapsed().as_micros() as f64;
+            busy_workers.fetch_sub(1, Ordering::Relaxed);
+
+            let counter = match result {
+                Ok(()) => format!("{label}_instructions_processed"),
+                Err(e) => {
+                    span.in_scope(|| {
+                        log::warn!(
+                            "[{label}] worker {worker_id} failed to process instruction: {e}"
+                        )
+                    });
+                    format!("{label}_instructions_failed")
+                }
+            };
+            let _ = metrics.increment_counter(&counter, 1).await;
+            let _ = metrics
+                .record_histogram(&format!("{label}_process_time_us"), elapsed_us)
+                .await;
+        }
+
+        log::debug!("[{label}] worker {worker_id} shutting down");
+    }
+}
+
+#[async_trait]
+impl<T: fmt::Debug + Send + Sync + 'static> Processor for ShardedProcessor<T> {
+    type InputType = InstructionInput<T>;
+
+    async fn process(
+        &mut self,
+        input: Self::InputType,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let shard = self.shard_index(&input.0.transaction_metadata.signature);
+        if let Some(ref activity) = self.activity {
+            activity.record_instruction(self.label, &input.1.data);
+        }
+
+        metrics
+            .update_gauge(
+                &format!("{}_queue_depth", self.label),
+                self.queue_depth() as f64,
+            )
+            .await?;
+        metrics
+            .update_gauge(
+                &format!("{}_busy_workers", self.label),
+                self.busy_workers.load(Ordering::Relaxed) as f64,
+            )
+            .await?;
+
+        // Blocks when the shard is full, applying backpressure to the pipeline
+        self.shards[shard]
+            .send((input, metrics))
+            .await
+            .map_err(|_| Error::Custom(format!("{} worker {shard} has stopped", self.label)))
+    }
+}
@@ -0,0 +1,717 @@
+//! Runtime reload of filters and alert rules.
+//!
+//! `FILTER_TOKENS`, `FILTER_AMMS`, `WATCH_WALLETS`, `FILTER_SIDE`,
+//! `SUPPRESS_FREEZABLE_TOKENS`, the rules file, and the labels file can change
+//! without
+//! restarting the pipeline (which would drop the WebSocket subscription and
+//! miss events). Processors read them through a [`SharedConfig`], and the
+//! [`ConfigWatcher`] swaps in a new [`LiveConfig`] atomically when:
+//!
+//! - the process receives `SIGHUP`, or
+//! - the config file (`.env` or `CONFIG_FILE`), the rules file, or the labels
+//!   file is modified.
+//!
+//! The remote labels list (`LABELS_URL`) is fetched on `SIGHUP` and every
+//! `LABELS_REFRESH_SECS`. When labels change, the watcher's relabel hook
+//! re-annotates events already held in memory.
+//!
+//! On reload, values in the config file take precedence over the process
+//! environment. A reload that fails (e.g., an invalid rules file) is logged
+//! and the previous configuration stays active. Other settings, such as
+//! `FILTER_MARKETS`, still require a restart.
+
+use {
+    crate::{
+        config::{parse_pubkey_filter, parse_pubkey_list, parse_side_filter},
+        labels::{AddressLabels, LabelsError},
+        output::{SwapEvent, TradeSide},
+        rules::{RuleEngine, RulesError},
+    },
+    arc_swap::ArcSwap,
+    solana_pubkey::Pubkey,
+    std::{
+        collections::{HashMap, HashSet},
+        env, fmt,
+        path::{Path, PathBuf},
+        sync::Arc,
+        time::{Duration, SystemTime},
+    },
+};
+
+/// Default interval between config file modification checks.
+const DEFAULT_WATCH_INTERVAL_MS: u64 = 2_000;
+
+/// Default interval between remote labels list fetches.
+const DEFAULT_LABELS_REFRESH_SECS: u64 = 3_600;
+
+/// Timeout of a remote labels list fetch.
+const LABELS_FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Errors that can occur while loading the live configuration.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The config file could not be read or parsed
+    File(String),
+    /// The rules file is invalid
+    Rules(RulesError),
+    /// The labels file is invalid
+    Labels(LabelsError),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(e) => write!(f, "failed to load config file: {e}"),
+            Self::Rules(e) => write!(f, "{e}"),
+            Self::Labels(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<RulesError> for ConfigError {
+    fn from(e: RulesError) -> Self {
+        Self::Rules(e)
+    }
+}
+
+impl From<LabelsError> for ConfigError {
+    fn from(e: LabelsError) -> Self {
+        Self::Labels(e)
+    }
+}
+
+/// Filters, rules, and labels that can be reloaded at runtime.
+#[derive(Clone, Default)]
+pub struct LiveConfig {
+    /// Token mints to track. Empty means no filter (track all).
+    pub filter_tokens: HashSet<Pubkey>,
+    /// AMM/pool addresses to track. Empty means no filter (track all).
+    pub filter_amms: HashSet<Pubkey>,
+    /// Wallets whose swaps always alert, whatever the token and AMM filters.
+    pub watch_wallets: HashSet<Pubkey>,
+    /// Trade side swaps are kept for. `None` keeps both.
+    pub filter_side: Option<TradeSide>,
+    /// Drop pool creations of tokens whose freeze authority is active.
+    pub suppress_freezable: bool,
+    /// Optional rule engine routing events to per-rule destinations.
+    pub rules: Option<Arc<RuleEngine>>,
+    /// Rules file the rules were loaded from
+    pub rules_file: Option<PathBuf>,
+    /// Labels shown instead of addresses in alerts
+    pub labels: AddressLabels,
+    /// Labels file the labels were loaded from
+    pub labels_file: Option<PathBuf>,
+    /// Remote labels list fetched into the labels' remote layer
+    pub labels_url: Option<String>,
+}
+
+/// Parses an opt-in flag (`true` or `1`).
+fn parse_flag(value: Option<String>) -> bool {
+    value.is_some_and(|v| matches!(v.trim().to_lowercase().as_str(), "true" | "1"))
+}
+
+/// Live configuration shared by all processors.
+pub type SharedConfig = Arc<ArcSwap<LiveConfig>>;
+
+impl LiveConfig {
+    /// Loads the configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `FILTER_TOKENS` - Optional: Comma-separated token mints
+    /// - `FILTER_AMMS` - Optional: Comma-separated AMM/pool addresses
+    /// - `WATCH_WALLETS` - Optional: Comma-separated wallets whose swaps always alert
+    /// - `FILTER_SIDE` - Optional: `buy`, `sell`, or `both` (default: both)
+    /// - `SUPPRESS_FREEZABLE_TOKENS` - Optional: `true` to drop pool creations of
+    ///   tokens whose freeze authority is active (default: false)
+    /// - `RULES_FILE` - Optional: Path to a TOML rules file
+    /// - `LABELS_FILE` - Optional: Path to a TOML address labels file
+    /// - `LABELS_URL` - Optional: URL of a remote labels list in the same format
+    pub fn from_env() -> Result<Self, ConfigError> {
+        let config = Self::build(
+            parse_pubkey_filter("FILTER_TOKENS"),
+            parse_pubkey_filter("FILTER_AMMS"),
+            parse_pubkey_filter("WATCH_WALLETS"),
+            env::var("FILTER_SIDE")
+                .ok()
+                .and_then(|side| parse_side_filter("FILTER_SIDE", &side)),
+            env::var("RULES_FILE").ok(),
+            env::var("LABELS_FILE").ok(),
+            env::var("LABELS_URL").ok(),
+        )?;
+        Ok(Self {
+            suppress_freezable: parse_flag(env::var("SUPPRESS_FREEZABLE_TOKENS").ok()),
+            ..config
+        })
+    }
+
+    /// Loads the configuration from an env-format file, falling back to the
+    /// process environment for settings the file does not define.
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        // `dotenv::from_path` never overrides variables that are already set,
+        // so it cannot pick up changes; read the file's pairs directly instead.
+        #[allow(deprecated)]
+        let iter = dotenv::from_path_iter(path)
+            .map_err(|e| ConfigError::File(format!("{}: {e}", path.display())))?;
+        let mut values = HashMap::new();
+        for item in iter {
+            let (key, value) =
+                item.map_err(|e| ConfigError::File(format!("{}: {e}", path.display())))?;
+            values.insert(key, value);
+        }
+
+        let lookup = |name: &str| values.get(name).cloned().or_else(|| env::var(name).ok());
+        let filter = |name| {
+            lookup(name)
+                .map(|value| parse_pubkey_list(name, &value))
+                .unwrap_or_default()
+        };
+        let config = Self::build(
+            filter("FILTER_TOKENS"),
+            filter("FILTER_AMMS"),
+            filter("WATCH_WALLETS"),
+            lookup("FILTER_SIDE").and_then(|side| parse_side_filter("FILTER_SIDE", &side)),
+            lookup("RULES_FILE"),
+            lookup("LABELS_FILE"),
+            lookup("LABELS_URL"),
+        )?;
+        Ok(Self {
+            suppress_freezable: parse_flag(lookup("SUPPRESS_FREEZABLE_TOKENS")),
+            ..config
+        })
+    }
+
+    /// Builds the configuration, loading rules from `rules_file` and labels
+    /// from `labels_file` if set.
+    ///
+    /// The remote labels list is not fetched here; the [`ConfigWatcher`]
+    /// fetches it into the labels' remote layer.
+    ///
+    /// Must be called within a Tokio runtime when a rules file is configured.
+    fn build(
+        filter_tokens: HashSet<Pubkey>,
+        filter_amms: HashSet<Pubkey>,
+        watch_wallets: HashSet<Pubkey>,
+        filter_side: Option<TradeSide>,
+        rules_file: Option<String>,
+        labels_file: Option<String>,
+        labels_url: Option<String>,
+    ) -> Result<Self, ConfigError> {
+        let path = |file: Option<String>| {
+            file.filter(|path| !path.trim().is_empty())
+                .map(|path| PathBuf::from(path.trim()))
+        };
+        let rules_file = path(rules_file);
+        let rules = rules_file
+            .as_ref()
+            .map(RuleEngine::load)
+            .transpose()?
+            .map(Arc::new);
+        let labels_file = path(labels_file);
+        let labels = labels_file
+            .as_ref()
+            .map(AddressLabels::load)
+            .transpose()?
+            .unwrap_or_default();
+        let labels_url = labels_url
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty());
+
+        Ok(Self {
+            filter_tokens,
+            filter_amms,
+            watch_wallets,
+            filter_side,
+            suppress_freezable: false,
+            rules,
+            rules_file,
+            labels,
+            labels_file,
+            labels_url,
+        })
+    }
+
+    /// Returns `true` if no token, AMM, or wallet filter is configured.
+    pub fn tracks_all(&self) -> bool {
+        self.filter_tokens.is_empty()
+            && self.filter_amms.is_empty()
+            && self.watch_wallets.is_empty()
+    }
+
+    /// Returns `true` if the event is on the side of the side filter.
+    ///
+    /// Events without a side (non-swaps, and swaps between two base tokens or
+    /// two other tokens) always pass.
+    pub fn passes_side(&self, event: &SwapEvent) -> bool {
+        match (self.filter_side, event.side) {
+            (Some(side), Some(event_side)) => side == event_side,
+            _ => true,
+        }
+    }
+
+    /// Returns `true` unless the event is a pool creation of a token whose
+    /// freeze authority is active and such tokens are suppressed.
+    ///
+    /// Pool creations whose authorities are unknown (`authorities` enricher
+    /// disabled or timed out) pass.
+    pub fn passes_authorities(&self, event: &SwapEvent) -> bool {
+        !self.suppress_freezable
+            || !event
+                .pool_created
+                .as_ref()
+                .and_then(|created| created.authorities.as_ref())
+                .is_some_and(|authorities| authorities.freezable())
+    }
+
+    /// Returns `true` if `maker` is a watched wallet.
+    pub fn watches(&self, maker: Option<&Pubkey>) -> bool {
+        maker.is_some_and(|maker| self.watch_wallets.contains(maker))
+    }
+
+    /// Returns a one-line summary for logging.
+    pub fn summary(&self) -> String {
+        let shadow = match self.rules.as_ref().map_or(0, |rules| rules.shadow_len()) {
+            0 => String::new(),
+            n => format!(" ({n} shadow)"),
+        };
+        let side = match self.filter_side {
+            Some(side) => format!(", {side}s only"),
+            None => String::new(),
+        };
+        let freezable = if self.suppress_freezable {
+            ", freezable tokens suppressed"
+        } else {
+            ""
+        };
+        format!(
+            "{} token(s), {} AMM/pool(s), {} watched wallet(s){side}{freezable}, {} rule(s){shadow}, {} label(s)",
+            self.filter_tokens.len(),
+            self.filter_amms.len(),
+            self.watch_wallets.len(),
+            self.rules.as_ref().map_or(0, |rules| rules.len()),
+            self.labels.len()
+        )
+    }
+}
+
+/// Called with the new labels after they change.
+pub type RelabelHook = Box<dyn Fn(&AddressLabels) + Send + Sync>;
+
+/// Watches for reload triggers and swaps in new configurations.
+pub struct ConfigWatcher {
+    config: SharedConfig,
+    config_file: Option<PathBuf>,
+    interval: Option<Duration>,
+    labels_interval: Duration,
+    client: reqwest::Client,
+    relabel: Option<RelabelHook>,
+}
+
+impl ConfigWatcher {
+    /// Creates a watcher for the shared configuration.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - Configuration shared with the processors
+    /// * `config_file` - Env-format config file to reload from, if any
+    ///
+    /// # Environment Variables
+    ///
+    /// - `CONFIG_WATCH_INTERVAL_MS` - Optional: File modification check interval
+    ///   (default: 2000; 0 disables file watching, leaving `SIGHUP`)
+    /// - `LABELS_REFRESH_SECS` - Optional: Interval between fetches of the remote
+    ///   labels list (default: 3600)
+    pub fn new(config: SharedConfig, config_file: Option<PathBuf>) -> Self {
+        let interval_ms = env::var("CONFIG_WATCH_INTERVAL_MS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(DEFAULT_WATCH_INTERVAL_MS);
+        let labels_secs = env::var("LABELS_REFRESH_SECS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_LABELS_REFRESH_SECS);
+
+        Self {
+            config,
+            config_file,
+            interval: (interval_ms > 0).then(|| Duration::from_millis(interval_ms)),
+            labels_interval: Duration::from_secs(labels_secs),
+            client: reqwest::Client::builder()
+                .timeout(LABELS_FETCH_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            relabel: None,
+        }
+    }
+
+    /// Sets a hook re-annotating in-memory state with new labels.
+    pub fn with_relabel(
+        mut self,
+        relabel: impl Fn(&AddressLabels) + Send + Sync + 'static,
+    ) -> Self {
+        self.relabel = Some(Box::new(relabel));
+        self
+    }
+
+    /// Spawns the background watch task.
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    /// Waits for `SIGHUP`, file changes, or the labels refresh interval and
+    /// reloads on each. The first labels refresh runs immediately.
+    async fn run(self) {
+        let mut hangup = hangup_signal();
+        let mut ticker = self.interval.map(tokio::time::interval);
+        let mut labels_ticker = tokio::time::interval(self.labels_interval);
+        let mut modified = self.modified_times();
+
+        loop {
+            tokio::select! {
+                _ = recv_hangup(&mut hangup) => {
+                    self.reload("SIGHUP").await;
+                    self.refresh_labels().await;
+                }
+                _ = tick(&mut ticker) => {
+                    let current = self.modified_times();
+                    if current == modified {
+                        continue;
+                    }
+                    self.reload("file change").await;
+                }
+                _ = labels_ticker.tick() => self.refresh_labels().await,
+            }
+            modified = self.modified_times();
+        }
+    }
+
+    /// Rebuilds the configuration and swaps it in, keeping the old one on error.
+    ///
+    /// Remote labels are kept while `LABELS_URL` is unchanged, and fetched
+    /// again when it changes.
+    async fn reload(&self, reason: &str) {
+        let result = match self.config_file {
+            Some(ref path) => LiveConfig::from_file(path),
+            None => LiveConfig::from_env(),
+        };
+
+        match result {
+            Ok(mut config) => {
+                let previous = self.config.load();
+                let url_changed = config.labels_url != previous.labels_url;
+                if !url_changed {
+                    config.labels = config.labels.with_remote_of(&previous.labels);
+                }
+                log::info!("Configuration reloaded ({reason}): {}", config.summary());
+                self.store(config);
+                if url_changed {
+                    self.refresh_labels().await;
+                }
+            }
+            Err(e) => log::error!("Configuration reload ({reason}) failed, keeping previous: {e}"),
+        }
+    }
+
+    /// Fetches the remote labels list and swaps it in, keeping the previous
+    /// remote labels on error.
+    async fn refresh_labels(&self) {
+        let current = self.config.load_full();
+        let Some(ref url) = current.labels_url else {
+            return;
+        };
+
+        match AddressLabels::fetch(&self.client, url).await {
+            Ok(remote) => {
+                let mut config = LiveConfig::clone(&current);
+                config.labels = config.labels.with_remote(&remote);
+                log::info!(
+                    "Remote labels refreshed: {} remote, {} total",
+                    remote.len(),
+                    config.labels.len()
+                );
+                self.store(config);
+            }
+            Err(e) => log::warn!("Remote labels refresh failed, keeping previous: {e}"),
+        }
+    }
+
+    /// Swaps in a configuration, running the relabel hook if labels changed.
+    fn store(&self, config: LiveConfig) {
+        let relabel = self
+            .relabel
+            .as_ref()
+            .filter(|_| config.labels != self.config.load().labels);
+        let config = Arc::new(config);
+        self.config.store(config.clone());
+        if let Some(relabel) = relabel {
+            relabel(&config.labels);
+        }
+    }
+
+    /// Returns the modification times of the watched files.
+    fn modified_times(&self) -> Vec<Option<SystemTime>> {
+        let config = self.config.load();
+        [
+            self.config_file.as_ref(),
+            config.rules_file.as_ref(),
+            config.labels_file.as_ref(),
+        ]
+        .into_iter()
+        .map(|path| {
+            path.and_then(|p| std::fs::metadata(p).ok())
+                .and_then(|m| m.modified().ok())
+        })
+        .collect()
+    }
+}
+
+/// Waits for the next watch interval, or forever if file watching is disabled.
+async fn tick(ticker: &mut Option<tokio::time::Interval>) {
+    match ticker {
+        Some(ticker) => {
+            ticker.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(unix)]
+type Hangup = Option<tokio::signal::unix::Signal>;
+
+#[cfg(not(unix))]
+type Hangup = ();
+
+/// Registers the `SIGHUP` handler.
+#[cfg(unix)]
+fn hangup_signal() -> Hangup {
+    use tokio::signal::unix::{signal, SignalKind};
+    match signal(SignalKind::hangup()) {
+        Ok(signal) => Some(signal),
+        Err(e) => {
+            log::warn!("Failed to register SIGHUP handler: {e}");
+            None
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn hangup_signal() -> Hangup {}
+
+/// Waits for the next `SIGHUP`, or forever if unavailable.
+#[cfg(unix)]
+async fn recv_hangup(hangup: &mut Hangup) {
+    match hangup {
+        Some(signal) => {
+            if signal.recv().await.is_none() {
+                std::future::pending::<()>().await;
+            }
+        }
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(not(unix))]
+async fn recv_hangup(_: &mut Hangup) {
+    std::future::pending().await
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::io::Write};
+
+    const WSOL: &str = "So11111111111111111111111111111111111111112";
+    const USDC: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+    /// Writes `contents` to a unique temporary file.
+    fn temp_file(name: &str, contents: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("raydium-alerts-{}-{name}", std::process::id()));
+        let mut file = std::fs::File::create(&path).unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_build_without_rules() {
+        let config = LiveConfig::build(
+            parse_pubkey_list("FILTER_TOKENS", &format!("{WSOL}, invalid")),
+            HashSet::new(),
+            HashSet::new(),
+            None,
+            Some(" ".to_string()),
+            None,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(config.filter_tokens.len(), 1);
+        assert!(config.rules.is_none());
+        assert!(config.rules_file.is_none());
+        assert!(!config.tracks_all());
+        assert_eq!(
+            config.summary(),
+            "1 token(s), 0 AMM/pool(s), 0 watched wallet(s), 0 rule(s), 0 label(s)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_from_file_with_rules() {
+        let rules = temp_file(
+            "rules.toml",
+            r#"
+            [[rules]]
+            name = "clmm"
+            when = { protocol = "clmm" }
+            destinations = [{ type = "webhook", url = "https://a.example" }]
+            "#,
+        );
+        let env_file = temp_file(
+            "reload.env",
+            &format!(
+                "FILTER_TOKENS={WSOL},{USDC}\nFILTER_SIDE=sell\nSUPPRESS_FREEZABLE_TOKENS=true\nRULES_FILE={}\n",
+                rules.display()
+            ),
+        );
+
+        let config = LiveConfig::from_file(&env_file).unwrap();
+        assert_eq!(config.filter_tokens.len(), 2);
+        assert_eq!(config.rules.as_ref().map(|r| r.len()), Some(1));
+        assert_eq!(config.rules_file.as_deref(), Some(rules.as_path()));
+
+        // Buys are filtered out, events without a side pass
+        let swap = |input: &str, output: &str| {
+            SwapEvent::builder()
+                .protocol(crate::output::Protocol::Cpmm)
+                .signature("sig")
+                .pool("pool")
+                .input_token(crate::output::TokenInfo::new(input, 1))
+                .output_token(crate::output::TokenInfo::new(output, 1))
+                .build()
+        };
+        assert_eq!(config.filter_side, Some(TradeSide::Sell));
+        assert!(!config.passes_side(&swap(WSOL, "token")));
+        assert!(config.passes_side(&swap("token", WSOL)));
+        assert!(config.passes_side(&swap("token", "other")));
+
+        // Pool creations of freezable tokens are dropped once their authorities are known
+        assert!(config.suppress_freezable);
+        let mut created = SwapEvent::builder()
+            .event_type(crate::output::EventType::CreatePool)
+            .protocol(crate::output::Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .pool_created(crate::output::PoolCreatedEvent::new(
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+                &Pubkey::new_unique(),
+            ))
+            .build();
+        assert!(config.passes_authorities(&created));
+        created.pool_created.as_mut().unwrap().authorities =
+            Some(crate::output::TokenAuthorities {
+                mint: "token".to_string(),
+                mint_authority: None,
+                freeze_authority: Some("freezer".to_string()),
+            });
+        assert!(!config.passes_authorities(&created));
+
+        // An invalid rules file fails the reload instead of dropping the rules
+        std::fs::write(&rules, "not toml [").unwrap();
+        assert!(matches!(
+            LiveConfig::from_file(&env_file),
+            Err(ConfigError::Rules(RulesError::Parse(_)))
+        ));
+
+        std::fs::remove_file(rules).ok();
+        std::fs::remove_file(env_file).ok();
+    }
+
+    #[test]
+    fn test_watch_wallets_and_labels() {
+        let labels = temp_file("labels.toml", &format!("[labels]\n{WSOL} = \"whale-3\"\n"));
+        let env_file = temp_file(
+            "labels.env",
+            &format!("WATCH_WALLETS={WSOL}\nLABELS_FILE={}\n", labels.display()),
+        );
+
+        let config = LiveConfig::from_file(&env_file).unwrap();
+        let wallet = Pubkey::from_str_const(WSOL);
+        assert!(!config.tracks_all());
+        assert!(config.watches(Some(&wallet)));
+        assert!(!config.watches(None));
+        assert_eq!(config.labels.get(WSOL), Some("whale-3"));
+        assert_eq!(config.labels_file.as_deref(), Some(labels.as_path()));
+
+        std::fs::write(&labels, "[labels]\nbad = \"x\"").unwrap();
+        assert!(matches!(
+            LiveConfig::from_file(&env_file),
+            Err(ConfigError::Labels(LabelsError::Invalid(_)))
+        ));
+
+        std::fs::remove_file(labels).ok();
+        std::fs::remove_file(env_file).ok();
+    }
+
+    #[tokio::test]
+    async fn test_watcher_reload_keeps_previous_on_error() {
+        let env_file = temp_file("watch.env", "RULES_FILE=/nonexistent/rules.toml\n");
+        let shared: SharedConfig = Arc::new(ArcSwap::from_pointee(LiveConfig {
+            filter_tokens: parse_pubkey_list("FILTER_TOKENS", WSOL),
+            ..LiveConfig::default()
+        }));
+        let watcher = ConfigWatcher::new(shared.clone(), Some(env_file.clone()));
+
+        watcher.reload("test").await;
+        assert_eq!(shared.load().filter_tokens.len(), 1);
+
+        std::fs::write(&env_file, format!("FILTER_AMMS={USDC}\nRULES_FILE=\n")).unwrap();
+        watcher.reload("test").await;
+        assert!(shared.load().filter_tokens.is_empty());
+        assert_eq!(shared.load().filter_amms.len(), 1);
+
+        std::fs::remove_file(env_file).ok();
+    }
+
+    #[tokio::test]
+    async fn test_reload_keeps_remote_labels_and_relabels() {
+        use std::sync::Mutex;
+
+        let url = "http://127.0.0.1:9/labels.toml";
+        let labels = temp_file("relabel.toml", &format!("[labels]\n{WSOL} = \"whale-3\"\n"));
+        let env_file = temp_file(
+            "relabel.env",
+            &format!("LABELS_FILE={}\nLABELS_URL={url}\n", labels.display()),
+        );
+        let remote =
+            AddressLabels::parse(&format!("[labels]\n{USDC} = \"usdc-treasury\"")).unwrap();
+        let shared: SharedConfig = Arc::new(ArcSwap::from_pointee(LiveConfig {
+            labels: AddressLabels::default().with_remote(&remote),
+            labels_url: Some(url.to_string()),
+            ..LiveConfig::default()
+        }));
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let hook_seen = seen.clone();
+        let watcher = ConfigWatcher::new(shared.clone(), Some(env_file.clone())).with_relabel(
+            move |labels| {
+                hook_seen
+                    .lock()
+                    .unwrap()
+                    .push((labels.get(WSOL).map(str::to_string), labels.len()))
+            },
+        );
+
+        // The URL is unchanged, so the remote labels survive the file reload
+        watcher.reload("test").await;
+        assert_eq!(shared.load().labels.get(USDC), Some("usdc-treasury"));
+        assert_eq!(*seen.lock().unwrap(), [(Some("whale-3".to_string()), 2)]);
+
+        // Unchanged labels do not run the hook again
+        watcher.reload("test").await;
+        assert_eq!(seen.lock().unwrap().len(), 1);
+
+        std::fs::remove_file(labels).ok();
+        std::fs::remove_file(env_file).ok();
+    }
+}
@@ -0,0 +1,358 @@
+//! Replay of archived events through the analytics subsystems.
+//!
+//! Live, candles are closed, volume summaries emitted, and stale pools
+//! forgotten on wall-clock tickers. A replay runs them on a [`VirtualClock`]
+//! advanced by the events' block times instead, so replaying a day of history
+//! produces the candles, `VolumeSummary` events, and `MevAlert` events the live
+//! run did with the same configuration, however fast it runs:
+//!
+//! - candles whose interval (plus the close grace) ended before the clock are
+//!   closed before each event, as the storage flush ticker closes them
+//! - a volume summary is due every `AGGREGATION_INTERVAL_SECS` of virtual time,
+//!   the first one interval after the first event, as the live ticker
+//! - swaps are checked for sandwiches in archive (block) order
+//!
+//! Swaps are valued at their archived USD value; live summaries may rank by
+//! the price service's prices at the time. Liquidity drain detection needs
+//! pool state from RPC and is not replayed.
+//!
+//! A [`Pacer`] scales the replay to a multiple of real time.
+
+use {
+    crate::{
+        aggregation::{AggregationConfig, VolumeAggregator},
+        candles::{Candle, CandleBuilder, Interval},
+        mev::{MevConfig, MevDetector},
+        output::SwapEvent,
+        server::PriceTick,
+    },
+    serde::Serialize,
+    std::time::{Duration, Instant},
+};
+
+/// Time derived from the block times of replayed events.
+///
+/// The clock never goes back, so an event whose block time is slightly older
+/// than an earlier one's does not reopen closed intervals.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct VirtualClock {
+    now: Option<i64>,
+}
+
+impl VirtualClock {
+    /// Creates a clock that has not seen a block time yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advances the clock to a block time (Unix seconds) and returns the new time.
+    pub fn advance(&mut self, block_time: i64) -> i64 {
+        let now = self.now.map_or(block_time, |now| now.max(block_time));
+        self.now = Some(now);
+        now
+    }
+
+    /// Returns the current time, or `None` before the first block time.
+    pub fn now(&self) -> Option<i64> {
+        self.now
+    }
+}
+
+/// Paces a replay at a multiple of real time.
+#[derive(Debug, Clone, Copy)]
+pub struct Pacer {
+    speed: f64,
+    /// Real and virtual time of the first paced event
+    origin: Option<(Instant, i64)>,
+}
+
+impl Pacer {
+    /// Creates a pacer replaying `speed` seconds of block time per second.
+    pub fn new(speed: f64) -> Self {
+        Self {
+            speed,
+            origin: None,
+        }
+    }
+
+    /// Returns how long to wait at real time `at` before replaying virtual time `now`.
+    pub fn delay(&mut self, now: i64, at: Instant) -> Duration {
+        let (start, origin) = *self.origin.get_or_insert((at, now));
+        let elapsed = (now - origin).max(0) as f64 / self.speed;
+        (start + Duration::from_secs_f64(elapsed)).saturating_duration_since(at)
+    }
+}
+
+/// A closed candle with the pool and interval it belongs to.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReplayCandle {
+    /// Pool address
+    pub pool: String,
+    /// Candle resolution (e.g., `1m`)
+    pub interval: String,
+    #[serde(flatten)]
+    pub candle: Candle,
+}
+
+impl From<Candle> for ReplayCandle {
+    fn from(candle: Candle) -> Self {
+        Self {
+            pool: candle.pool.clone(),
+            interval: candle.interval.to_string(),
+            candle,
+        }
+    }
+}
+
+/// Output of a replay, serialized as a JSON line tagged with `kind`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReplayOutput {
+    /// A derived event (volume summary or MEV alert)
+    Event(SwapEvent),
+    /// A closed candle
+    Candle(ReplayCandle),
+}
+
+/// Volume summaries on the virtual clock.
+struct Summaries {
+    config: AggregationConfig,
+    aggregator: VolumeAggregator,
+    /// Virtual time of the next summary
+    next: Option<i64>,
+}
+
+impl Summaries {
+    fn interval(&self) -> i64 {
+        self.config.interval.as_secs().max(1) as i64
+    }
+}
+
+/// Drives candles, volume summaries, and MEV detection with archived events.
+pub struct Replay {
+    clock: VirtualClock,
+    candles: CandleBuilder,
+    summaries: Option<Summaries>,
+    mev: Option<MevDetector>,
+}
+
+impl Replay {
+    /// Creates a replay building candles of the given intervals.
+    pub fn new(intervals: Vec<Interval>) -> Self {
+        Self {
+            clock: VirtualClock::new(),
+            candles: CandleBuilder::new(intervals),
+            summaries: None,
+            mev: None,
+        }
+    }
+
+    /// Emits volume summaries every `config.interval` of virtual time.
+    pub fn with_aggregation(mut self, config: AggregationConfig) -> Self {
+        self.summaries = Some(Summaries {
+            config,
+            aggregator: VolumeAggregator::new(),
+            next: None,
+        });
+        self
+    }
+
+    /// Detects sandwich attacks among the replayed swaps.
+    pub fn with_mev(mut self, config: MevConfig) -> Self {
+        self.mev = Some(MevDetector::new(config));
+        self
+    }
+
+    /// Returns the virtual clock.
+    pub fn clock(&self) -> VirtualClock {
+        self.clock
+    }
+
+    /// Replays an event and returns the outputs due by its block time.
+    ///
+    /// Events without a block time are replayed at the clock's time; before
+    /// the first block time, they only reach MEV detection.
+    pub fn process(&mut self, event: &SwapEvent) -> Vec<ReplayOutput> {
+        let mut outputs = Vec::new();
+        let now = match event.timestamp {
+            Some(block_time) => Some(self.clock.advance(block_time)),
+            None => self.clock.now(),
+        };
+
+        if let Some(now) = now {
+            self.summarize_due(now, &mut outputs);
+            Self::push_candles(&mut outputs, self.candles.close_expired(now));
+
+            let mut event = event.clone();
+            event.timestamp.get_or_insert(now);
+            if let Some(tick) = PriceTick::from_event(&event) {
+                Self::push_candles(&mut outputs, self.candles.update(&event.pool, &tick));
+            }
+            if let Some(ref summaries) = self.summaries {
+                summaries.aggregator.record(&event);
+            }
+        }
+
+        if let Some(alert) = self.mev.as_ref().and_then(|mev| mev.observe(event)) {
+            outputs.push(ReplayOutput::Event(alert));
+        }
+        outputs
+    }
+
+    /// Ends the replay: emits the summary due after the last event and closes
+    /// the open candles.
+    pub fn finish(mut self) -> Vec<ReplayOutput> {
+        let mut outputs = Vec::new();
+        if let Some(next) = self.summaries.as_ref().and_then(|s| s.next) {
+            self.summarize_due(next, &mut outputs);
+        }
+        let mut open = self.candles.close_expired(i64::MAX);
+        open.sort_by(|a, b| {
+            (a.open_time, &a.pool, a.interval).cmp(&(b.open_time, &b.pool, b.interval))
+        });
+        Self::push_candles(&mut outputs, open);
+        outputs
+    }
+
+    /// Emits the volume summary if one is due by `now`.
+    ///
+    /// Only the first due summary can have traded pools, so the schedule then
+    /// skips the intervals without events.
+    fn summarize_due(&mut self, now: i64, outputs: &mut Vec<ReplayOutput>) {
+        let Some(ref mut summaries) = self.summaries else {
+            return;
+        };
+        let interval = summaries.interval();
+        let next = *summaries.next.get_or_insert(now + interval);
+        if next > now {
+            return;
+        }
+        outputs.extend(
+            summaries
+                .aggregator
+                .summarize(&summaries.config, next)
+                .into_iter()
+                .map(ReplayOutput::Event),
+        );
+        let missed = (now - next) / interval + 1;
+        summaries.next = Some(next + missed * interval);
+    }
+
+    fn push_candles(outputs: &mut Vec<ReplayOutput>, candles: Vec<Candle>) {
+        outputs.extend(
+            candles
+                .into_iter()
+                .map(|candle| ReplayOutput::Candle(candle.into())),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{EventType, Protocol, TokenInfo},
+    };
+
+    fn swap(pool: &str, price: u64, block_time: Option<i64>) -> SwapEvent {
+        let mut builder = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature(format!("sig-{block_time:?}-{price}"))
+            .pool(pool)
+            .input_token(
+                TokenInfo::new("base", price)
+                    .with_decimals(0)
+                    .with_usd_value(price as f64),
+            )
+            .output_token(TokenInfo::new("quote", 1).with_decimals(0))
+            .maker("maker")
+            .slot(block_time.unwrap_or_default() as u64);
+        if let Some(block_time) = block_time {
+            builder = builder.timestamp(block_time);
+        }
+        builder.build()
+    }
+
+    fn candles(outputs: &[ReplayOutput]) -> Vec<(i64, f64, f64)> {
+        outputs
+            .iter()
+            .filter_map(|output| match output {
+                ReplayOutput::Candle(c) => {
+                    Some((c.candle.open_time, c.candle.open, c.candle.close))
+                }
+                ReplayOutput::Event(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_clock_and_pacer() {
+        let mut clock = VirtualClock::new();
+        assert_eq!(clock.now(), None);
+        assert_eq!(clock.advance(100), 100);
+        assert_eq!(clock.advance(90), 100);
+        assert_eq!(clock.advance(130), 130);
+
+        let start = Instant::now();
+        let mut pacer = Pacer::new(10.0);
+        assert_eq!(pacer.delay(1_000, start), Duration::ZERO);
+        assert_eq!(pacer.delay(1_020, start), Duration::from_secs(2));
+        // Behind schedule, so no wait
+        assert_eq!(
+            pacer.delay(1_020, start + Duration::from_secs(5)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_candles_close_on_block_time() {
+        let mut replay = Replay::new(vec![Interval::M1]);
+        assert!(replay.process(&swap("P", 10, Some(60))).is_empty());
+        assert!(replay.process(&swap("P", 12, Some(90))).is_empty());
+        // Another pool's block past the interval and its grace closes P's candle
+        let outputs = replay.process(&swap("Q", 5, Some(130)));
+        assert_eq!(candles(&outputs), [(60, 10.0, 12.0)]);
+        // An event without a block time lands at the clock's time
+        assert!(replay.process(&swap("Q", 7, None)).is_empty());
+
+        let outputs = replay.finish();
+        assert_eq!(candles(&outputs), [(120, 5.0, 7.0)]);
+        let json = serde_json::to_value(&outputs[0]).unwrap();
+        assert_eq!(json["kind"], "candle");
+        assert_eq!(
+            (&json["pool"], &json["interval"]),
+            (&"Q".into(), &"1m".into())
+        );
+    }
+
+    #[test]
+    fn test_summaries_on_virtual_interval() {
+        let config = AggregationConfig {
+            interval: Duration::from_secs(60),
+            top_pools: 10,
+            min_volume_usd: 0.0,
+        };
+        let mut replay = Replay::new(Vec::new()).with_aggregation(config);
+        let summaries = |outputs: Vec<ReplayOutput>| -> Vec<(i64, String)> {
+            outputs
+                .into_iter()
+                .filter_map(|output| match output {
+                    ReplayOutput::Event(e) if e.event_type == EventType::VolumeSummary => {
+                        Some((e.timestamp.unwrap(), e.pool))
+                    }
+                    _ => None,
+                })
+                .collect()
+        };
+
+        assert!(summaries(replay.process(&swap("P", 10, Some(1_000)))).is_empty());
+        assert!(summaries(replay.process(&swap("P", 10, Some(1_059)))).is_empty());
+        // Due at 1_060; the swap at 1_500 is not included
+        assert_eq!(
+            summaries(replay.process(&swap("Q", 10, Some(1_500)))),
+            [(1_060, "P".to_string())]
+        );
+        // The schedule skipped to 1_540
+        assert_eq!(summaries(replay.finish()), [(1_540, "Q".to_string())]);
+    }
+}
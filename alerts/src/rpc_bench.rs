@@ -0,0 +1,287 @@
+//! Side-by-side comparison of WebSocket RPC providers.
+//!
+//! The `rpc-bench` subcommand subscribes to blocks on several endpoints at
+//! once, with the same subscription filter as the pipeline, and after a test
+//! window reports per provider:
+//!
+//! - blocks delivered, and blocks only other providers delivered (missing)
+//! - how often it delivered a block first (ties count for each provider)
+//! - its delivery lag behind the first provider to deliver each block
+//! - its lag behind the block's on-chain time, at one-second resolution
+//!
+//! Only blocks first delivered within the window are compared; deliveries
+//! are collected for a short grace period after it, so a block that arrived
+//! just before the end is not counted as missing from slower providers.
+
+use {
+    crate::{
+        chain,
+        config::{block_filter, parse_market_filter},
+    },
+    solana_client::{
+        nonblocking::pubsub_client::PubsubClient, rpc_config::RpcBlockSubscribeConfig,
+    },
+    std::{
+        collections::HashMap,
+        fmt,
+        time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+    },
+    tokio::sync::mpsc,
+    tokio_stream::StreamExt,
+};
+
+/// Time deliveries are still collected after the test window.
+const GRACE: Duration = Duration::from_secs(5);
+
+/// A block delivered by a provider.
+#[derive(Debug)]
+struct Delivery {
+    provider: usize,
+    slot: u64,
+    at: Instant,
+    /// Time since the block's on-chain time, if the block has one
+    chain_lag: Option<Duration>,
+}
+
+/// Delivery statistics of one provider.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProviderReport {
+    /// Provider endpoint
+    pub url: String,
+    /// Blocks delivered
+    pub blocks: u64,
+    /// Blocks delivered by other providers only
+    pub missing: u64,
+    /// Blocks this provider delivered first
+    pub first: u64,
+    /// Median lag behind the first delivery
+    pub lag_p50: Duration,
+    /// 95th percentile lag behind the first delivery
+    pub lag_p95: Duration,
+    /// Maximum lag behind the first delivery
+    pub lag_max: Duration,
+    /// Median lag behind the blocks' on-chain time
+    pub chain_lag_p50: Option<Duration>,
+}
+
+impl ProviderReport {
+    /// Returns the header line matching [`ProviderReport`]'s `Display` output.
+    pub fn header() -> String {
+        format!(
+            "{:<48} {:>7} {:>7} {:>7} {:>8} {:>8} {:>8} {:>9}",
+            "provider", "blocks", "missing", "first", "lag p50", "lag p95", "lag max", "chain p50"
+        )
+    }
+}
+
+impl fmt::Display for ProviderReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ms = |lag: Duration| format!("{}ms", lag.as_millis());
+        write!(
+            f,
+            "{:<48} {:>7} {:>7} {:>7} {:>8} {:>8} {:>8} {:>9}",
+            self.url,
+            self.blocks,
+            self.missing,
+            self.first,
+            ms(self.lag_p50),
+            ms(self.lag_p95),
+            ms(self.lag_max),
+            self.chain_lag_p50.map_or("n/a".to_string(), ms)
+        )
+    }
+}
+
+/// Block arrival times of each provider.
+#[derive(Debug)]
+pub struct Comparison {
+    urls: Vec<String>,
+    arrivals: Vec<HashMap<u64, Instant>>,
+    chain_lags: Vec<Vec<Duration>>,
+}
+
+impl Comparison {
+    /// Creates an empty comparison of the given providers.
+    pub fn new(urls: Vec<String>) -> Self {
+        Self {
+            arrivals: vec![HashMap::new(); urls.len()],
+            chain_lags: vec![Vec::new(); urls.len()],
+            urls,
+        }
+    }
+
+    /// Records the delivery of a block by a provider. Repeated deliveries of
+    /// a slot are ignored.
+    pub fn record(&mut self, provider: usize, slot: u64, at: Instant, chain_lag: Option<Duration>) {
+        if self.arrivals[provider].contains_key(&slot) {
+            return;
+        }
+        self.arrivals[provider].insert(slot, at);
+        self.chain_lags[provider].extend(chain_lag);
+    }
+
+    /// Reports each provider's deliveries of the blocks first delivered before `until`.
+    pub fn report(&self, until: Instant) -> Vec<ProviderReport> {
+        let mut earliest: HashMap<u64, Instant> = HashMap::new();
+        for arrivals in &self.arrivals {
+            for (&slot, &at) in arrivals {
+                earliest
+                    .entry(slot)
+                    .and_modify(|first| *first = (*first).min(at))
+                    .or_insert(at);
+            }
+        }
+        earliest.retain(|_, first| *first < until);
+
+        self.urls
+            .iter()
+            .zip(&self.arrivals)
+            .zip(&self.chain_lags)
+            .map(|((url, arrivals), chain_lags)| {
+                let mut lags = Vec::new();
+                let mut missing = 0;
+                for (slot, first) in &earliest {
+                    match arrivals.get(slot) {
+                        Some(at) => lags.push(at.duration_since(*first)),
+                        None => missing += 1,
+                    }
+                }
+                lags.sort();
+                let mut chain_lags = chain_lags.clone();
+                chain_lags.sort();
+
+                ProviderReport {
+                    url: url.clone(),
+                    blocks: lags.len() as u64,
+                    missing,
+                    first: lags.iter().filter(|lag| lag.is_zero()).count() as u64,
+                    lag_p50: percentile(&lags, 50).unwrap_or_default(),
+                    lag_p95: percentile(&lags, 95).unwrap_or_default(),
+                    lag_max: lags.last().copied().unwrap_or_default(),
+                    chain_lag_p50: percentile(&chain_lags, 50),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Returns the `pct` percentile of sorted durations.
+fn percentile(sorted: &[Duration], pct: usize) -> Option<Duration> {
+    let last = sorted.len().checked_sub(1)?;
+    sorted.get(last * pct / 100).copied()
+}
+
+/// Subscribes to blocks on every provider for `duration` and reports their deliveries.
+///
+/// Blocks are filtered like the pipeline's subscription (by `FILTER_MARKETS`).
+/// Fails if a provider cannot be subscribed to; a subscription closed during
+/// the window counts the remaining blocks as missing.
+pub async fn run(urls: Vec<String>, duration: Duration) -> Result<Vec<ProviderReport>, String> {
+    let filter = block_filter(&chain::profile().deployed(parse_market_filter("FILTER_MARKETS")));
+    let (tx, mut rx) = mpsc::unbounded_channel::<Result<Delivery, String>>();
+
+    let tasks: Vec<_> = urls
+        .iter()
+        .enumerate()
+        .map(|(provider, url)| {
+            let (url, filter, tx) = (url.clone(), filter.clone(), tx.clone());
+            tokio::spawn(async move {
+                let client = match PubsubClient::new(&url).await {
+                    Ok(client) => client,
+                    Err(e) => {
+                        let _ = tx.send(Err(format!("{url}: {e}")));
+                        return;
+                    }
+                };
+                let config = RpcBlockSubscribeConfig {
+                    max_supported_transaction_version: Some(0),
+                    ..RpcBlockSubscribeConfig::default()
+                };
+                let (mut blocks, _unsubscribe) =
+                    match client.block_subscribe(filter, Some(config)).await {
+                        Ok(subscription) => subscription,
+                        Err(e) => {
+                            let _ = tx.send(Err(format!("{url}: {e}")));
+                            return;
+                        }
+                    };
+
+                while let Some(update) = blocks.next().await {
+                    let at = Instant::now();
+                    let chain_lag = update
+                        .value
+                        .block
+                        .and_then(|block| block.block_time)
+                        .and_then(|time| {
+                            let time = UNIX_EPOCH + Duration::from_secs(time.max(0) as u64);
+                            SystemTime::now().duration_since(time).ok()
+                        });
+                    let delivery = Delivery {
+                        provider,
+                        slot: update.value.slot,
+                        at,
+                        chain_lag,
+                    };
+                    if tx.send(Ok(delivery)).is_err() {
+                        return;
+                    }
+                }
+                log::warn!("{url}: block subscription closed");
+            })
+        })
+        .collect();
+    drop(tx);
+
+    let mut comparison = Comparison::new(urls);
+    let until = Instant::now() + duration;
+    let deadline = tokio::time::sleep_until((until + GRACE).into());
+    tokio::pin!(deadline);
+    let result = loop {
+        tokio::select! {
+            delivery = rx.recv() => match delivery {
+                Some(Ok(d)) => comparison.record(d.provider, d.slot, d.at, d.chain_lag),
+                Some(Err(e)) => break Err(e),
+                None => break Ok(()),
+            },
+            _ = &mut deadline => break Ok(()),
+        }
+    };
+
+    for task in tasks {
+        task.abort();
+    }
+    result.map(|()| comparison.report(until))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report_lags_and_missing_blocks() {
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+        let mut comparison = Comparison::new(vec!["a".to_string(), "b".to_string()]);
+
+        comparison.record(0, 1, start, Some(ms(400)));
+        comparison.record(1, 1, start + ms(30), None);
+        comparison.record(1, 2, start + ms(10), None);
+        comparison.record(0, 2, start + ms(20), Some(ms(600)));
+        // Only `b` delivered slot 3
+        comparison.record(1, 3, start + ms(40), None);
+        // A repeated delivery keeps the first arrival
+        comparison.record(1, 3, start + ms(90), None);
+        // First delivered after the window, so not compared
+        comparison.record(0, 4, start + ms(200), None);
+
+        let reports = comparison.report(start + ms(100));
+        let (a, b) = (&reports[0], &reports[1]);
+        assert_eq!((a.blocks, a.missing, a.first), (2, 1, 1));
+        assert_eq!((a.lag_p50, a.lag_max), (ms(0), ms(10)));
+        assert_eq!(a.chain_lag_p50, Some(ms(400)));
+        assert_eq!((b.blocks, b.missing, b.first), (3, 0, 2));
+        assert_eq!((b.lag_p50, b.lag_p95, b.lag_max), (ms(0), ms(0), ms(30)));
+        assert_eq!(b.chain_lag_p50, None);
+        assert!(b.to_string().starts_with("b "));
+    }
+}
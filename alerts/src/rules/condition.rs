@@ -0,0 +1,218 @@
+//! Rule conditions evaluated against swap events.
+
+use {
+    super::expr::Expr,
+    crate::output::{EventType, Protocol, SwapEvent, TradeSide},
+    serde::Deserialize,
+    std::collections::HashSet,
+};
+
+/// A condition on a swap event, combinable with `all`/`any`/`not`.
+///
+/// In TOML each condition is a single-key table, e.g.:
+///
+/// ```toml
+/// when = { all = [{ protocol = "clmm" }, { usd_above = 10000 }] }
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum Condition {
+    /// Every inner condition matches
+    All(Vec<Condition>),
+    /// At least one inner condition matches
+    Any(Vec<Condition>),
+    /// The inner condition does not match
+    Not(Box<Condition>),
+    /// The event comes from this protocol
+    Protocol(Protocol),
+    /// The event is of this type
+    EventType(EventType),
+    /// The event is a swap on this side
+    Side(TradeSide),
+    /// The input or output mint is in the list
+    TokenIn(HashSet<String>),
+    /// The pool is in the list
+    PoolIn(HashSet<String>),
+    /// The maker is in the list (watchlist)
+    MakerIn(HashSet<String>),
+    /// The USD value is known and strictly above the threshold
+    UsdAbove(f64),
+    /// The USD value is known and strictly below the threshold
+    UsdBelow(f64),
+    /// The event is a custom event with this name, emitted by another rule
+    Custom(String),
+    /// The expression evaluates to true (see [`Expr`])
+    Expr(Expr),
+}
+
+impl Condition {
+    /// Evaluates the condition against an event.
+    pub fn matches(&self, event: &SwapEvent) -> bool {
+        match self {
+            Self::All(conditions) => conditions.iter().all(|c| c.matches(event)),
+            Self::Any(conditions) => conditions.iter().any(|c| c.matches(event)),
+            Self::Not(condition) => !condition.matches(event),
+            Self::Protocol(protocol) => event.protocol == *protocol,
+            Self::EventType(event_type) => event.event_type == *event_type,
+            Self::Side(side) => event.side == Some(*side),
+            Self::TokenIn(mints) => [&event.input_token, &event.output_token]
+                .into_iter()
+                .flatten()
+                .any(|token| mints.contains(&token.mint)),
+            Self::PoolIn(pools) => pools.contains(&event.pool),
+            Self::MakerIn(makers) => event
+                .maker
+                .as_ref()
+                .is_some_and(|maker| makers.contains(maker)),
+            Self::UsdAbove(threshold) => event.usd_value().is_some_and(|usd| usd > *threshold),
+            Self::UsdBelow(threshold) => event.usd_value().is_some_and(|usd| usd < *threshold),
+            Self::Custom(name) => event
+                .custom
+                .as_ref()
+                .is_some_and(|custom| custom.name == *name),
+            Self::Expr(expr) => expr.matches(event),
+        }
+    }
+
+    /// Returns `true` if the condition selects custom events by name.
+    pub fn selects_custom(&self) -> bool {
+        match self {
+            Self::All(conditions) | Self::Any(conditions) => {
+                conditions.iter().any(Condition::selects_custom)
+            }
+            Self::Not(condition) => condition.selects_custom(),
+            Self::Custom(_) => true,
+            Self::Expr(expr) => expr.uses("custom"),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::output::TokenInfo};
+
+    fn test_event(protocol: Protocol, usd: Option<f64>) -> SwapEvent {
+        let mut input = TokenInfo::new("mint_in", 100);
+        input.amount_usd = usd;
+        SwapEvent::builder()
+            .protocol(protocol)
+            .signature("sig")
+            .pool("pool")
+            .input_token(input)
+            .output_token(TokenInfo::new("mint_out", 200))
+            .maker("whale")
+            .build()
+    }
+
+    fn parse(toml: &str) -> Condition {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            when: Condition,
+        }
+        toml::from_str::<Wrapper>(toml).unwrap().when
+    }
+
+    #[test]
+    fn test_leaf_conditions() {
+        let event = test_event(Protocol::Clmm, Some(5_000.0));
+
+        assert!(Condition::Protocol(Protocol::Clmm).matches(&event));
+        assert!(!Condition::Protocol(Protocol::Cpmm).matches(&event));
+        assert!(Condition::EventType(EventType::Swap).matches(&event));
+        assert!(Condition::TokenIn(["mint_out".to_string()].into()).matches(&event));
+        assert!(!Condition::TokenIn(["other".to_string()].into()).matches(&event));
+        assert!(Condition::PoolIn(["pool".to_string()].into()).matches(&event));
+        assert!(Condition::MakerIn(["whale".to_string()].into()).matches(&event));
+        assert!(Condition::UsdAbove(1_000.0).matches(&event));
+        assert!(!Condition::UsdAbove(5_000.0).matches(&event));
+        assert!(Condition::UsdBelow(10_000.0).matches(&event));
+        assert!(!Condition::Side(TradeSide::Buy).matches(&event));
+        assert!(!parse(r#"when = { side = "sell" }"#).matches(&event));
+    }
+
+    #[test]
+    fn test_side_condition() {
+        let event = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .input_token(TokenInfo::new(
+                "So11111111111111111111111111111111111111112",
+                100,
+            ))
+            .output_token(TokenInfo::new("mint_out", 200))
+            .build();
+        assert!(parse(r#"when = { side = "buy" }"#).matches(&event));
+        assert!(!Condition::Side(TradeSide::Sell).matches(&event));
+    }
+
+    #[test]
+    fn test_expr_condition() {
+        let condition = parse(
+            r#"when = { all = [{ protocol = "clmm" }, { expr = "usd_value > 1000 && fee == null" }] }"#,
+        );
+        assert!(condition.matches(&test_event(Protocol::Clmm, Some(5_000.0))));
+        assert!(!condition.matches(&test_event(Protocol::Clmm, Some(500.0))));
+        assert!(!condition.selects_custom());
+
+        #[derive(Debug, Deserialize)]
+        struct Wrapper {
+            #[allow(dead_code)]
+            when: Condition,
+        }
+        let error = toml::from_str::<Wrapper>(r#"when = { expr = "maker_is_bot" }"#).unwrap_err();
+        assert!(error.to_string().contains("unknown field 'maker_is_bot'"));
+    }
+
+    #[test]
+    fn test_custom_condition() {
+        let mut event = test_event(Protocol::Clmm, None);
+        let condition =
+            parse(r#"when = { all = [{ custom = "WhaleExit" }, { protocol = "clmm" }] }"#);
+        assert!(condition.selects_custom());
+        assert!(!Condition::Protocol(Protocol::Clmm).selects_custom());
+        assert!(!condition.matches(&event));
+
+        event.custom = Some(crate::output::CustomEvent {
+            name: "WhaleExit".to_string(),
+            rule: "whales".to_string(),
+            description: String::new(),
+            source_type: EventType::Swap,
+        });
+        assert!(condition.matches(&event));
+    }
+
+    #[test]
+    fn test_usd_conditions_require_known_value() {
+        let event = test_event(Protocol::Clmm, None);
+        assert!(!Condition::UsdAbove(0.0).matches(&event));
+        assert!(!Condition::UsdBelow(f64::MAX).matches(&event));
+    }
+
+    #[test]
+    fn test_parse_and_evaluate_nested_conditions() {
+        let condition = parse(
+            r#"
+            when = { all = [
+                { protocol = "clmm" },
+                { any = [{ usd_above = 10000 }, { maker_in = ["whale"] }] },
+                { not = { pool_in = ["blocked"] } },
+            ] }
+            "#,
+        );
+
+        assert!(condition.matches(&test_event(Protocol::Clmm, Some(50.0))));
+        assert!(!condition.matches(&test_event(Protocol::Cpmm, Some(50.0))));
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_condition() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Wrapper {
+            when: Condition,
+        }
+        assert!(toml::from_str::<Wrapper>("when = { volume_above = 1 }").is_err());
+    }
+}
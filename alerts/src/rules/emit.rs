@@ -0,0 +1,279 @@
+//! Rule actions emitting user-defined events.
+//!
+//! A rule with an `emit` action delivers a named [`CustomEvent`] instead of
+//! the matched event. Its description is a template whose `{placeholders}`
+//! are filled from the matched event:
+//!
+//! ```toml
+//! emit = { name = "WhaleExit", description = "{maker} sold {input_amount} {input_symbol} (${usd})" }
+//! ```
+
+use {
+    super::RulesError,
+    crate::{
+        chain,
+        output::{
+            number_format::number_format, swap_event::format_number, CustomEvent, EventType,
+            SwapEvent, TokenInfo,
+        },
+    },
+    serde::Deserialize,
+};
+
+/// Placeholders available in description templates.
+pub const PLACEHOLDERS: &[&str] = &[
+    "name",
+    "rule",
+    "event_type",
+    "protocol",
+    "pool",
+    "signature",
+    "slot",
+    "seq",
+    "maker",
+    "maker_address",
+    "side",
+    "input_symbol",
+    "input_amount",
+    "output_symbol",
+    "output_amount",
+    "usd",
+    "price",
+    "market_cap",
+    "fee",
+    "emoji",
+    "token_symbol",
+    "token_mint",
+    "impact",
+    "explorer",
+    "details",
+];
+
+/// Value rendered for placeholders whose field is unknown.
+const UNKNOWN: &str = "?";
+
+/// A rule action emitting a named event.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EmitConfig {
+    /// Event name (e.g., "WhaleExit")
+    pub name: String,
+    /// Description template with `{placeholder}` fields
+    #[serde(default)]
+    pub description: String,
+}
+
+impl EmitConfig {
+    /// Checks the name is set and the template only uses known placeholders.
+    pub fn validate(&self, rule: &str) -> Result<(), RulesError> {
+        if self.name.trim().is_empty() {
+            return Err(RulesError::Invalid(format!(
+                "rule '{rule}' emits an event without a name"
+            )));
+        }
+        if let Some(placeholder) = unknown_placeholder(&self.description) {
+            return Err(RulesError::Invalid(format!(
+                "rule '{rule}' uses unknown placeholder '{{{placeholder}}}' (expected one of: {})",
+                PLACEHOLDERS.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    /// Derives the custom event emitted for a matched event.
+    pub fn derive(&self, event: &SwapEvent, rule: &str) -> SwapEvent {
+        let mut derived = event.clone();
+        derived.event_type = EventType::Custom;
+        derived.custom = Some(CustomEvent {
+            name: self.name.clone(),
+            rule: rule.to_string(),
+            description: self.render(event, rule),
+            source_type: event.event_type,
+        });
+        derived
+    }
+
+    /// Renders the description template for an event.
+    fn render(&self, event: &SwapEvent, rule: &str) -> String {
+        render_template(&self.description, event, &self.name, rule)
+    }
+}
+
+/// Fills a template's `{placeholders}` from an event.
+///
+/// `name` and `rule` are the values of the `{name}` and `{rule}` placeholders.
+pub fn render_template(template: &str, event: &SwapEvent, name: &str, rule: &str) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some((before, placeholder, after)) = next_placeholder(rest) {
+        rendered.push_str(before);
+        match value(placeholder, event, name, rule) {
+            Some(value) => rendered.push_str(&value),
+            None => rendered.push_str(UNKNOWN),
+        }
+        rest = after;
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Fills a multi-line template's `{placeholders}` from an event, leaving out
+/// the lines whose placeholders are all unknown for the event (e.g., a market
+/// cap line for a token without one).
+pub fn render_text(template: &str, event: &SwapEvent, name: &str, rule: &str) -> String {
+    template
+        .lines()
+        .filter(|line| {
+            let mut used = placeholders(line).peekable();
+            used.peek().is_none() || used.any(|p| value(p, event, name, rule).is_some())
+        })
+        .map(|line| render_template(line, event, name, rule))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Returns the first placeholder of a template that is not in [`PLACEHOLDERS`].
+pub fn unknown_placeholder(template: &str) -> Option<&str> {
+    placeholders(template).find(|placeholder| !PLACEHOLDERS.contains(placeholder))
+}
+
+/// Returns the value of a placeholder for an event, or `None` if its field is unknown.
+fn value(placeholder: &str, event: &SwapEvent, name: &str, rule: &str) -> Option<String> {
+    // The non-base token of the pair
+    let token = || {
+        let (base, quote) = event.get_base_quote_tokens();
+        quote.or(base.filter(|token| !token.is_base_token()))
+    };
+    match placeholder {
+        "name" => Some(name.to_string()),
+        "rule" => Some(rule.to_string()),
+        "event_type" => Some(event.event_type.to_string()),
+        "protocol" => Some(event.protocol.to_string()),
+        "pool" => Some(event.pool.clone()),
+        "signature" => Some(event.signature.clone()),
+        "slot" => Some(event.slot.to_string()),
+        "seq" => event.seq.map(|seq| seq.to_string()),
+        "maker" => event.maker_label.clone().or_else(|| event.maker.clone()),
+        "maker_address" => event.maker.clone(),
+        "side" => event.side.map(|side| side.to_string()),
+        "input_symbol" => event.input_token.as_ref().map(token_symbol),
+        "input_amount" => event.input_token.as_ref().map(token_amount),
+        "output_symbol" => event.output_token.as_ref().map(token_symbol),
+        "output_amount" => event.output_token.as_ref().map(token_amount),
+        "usd" => event.usd_value().map(format_number),
+        "price" => event.price().map(|price| format!("{price:.6}")),
+        "market_cap" => event.market_cap_usd.map(format_number),
+        "fee" => event.fee.map(|fee| fee.to_string()),
+        "emoji" => Some(event.event_type.emoji().to_string()),
+        "token_symbol" => token().map(token_symbol),
+        "token_mint" => token().map(|token| token.mint.clone()),
+        "impact" => event.price_impact_pct.map(|impact| format!("{impact:.2}")),
+        // Summaries and digests have no single transaction
+        "explorer" => {
+            (!event.signature.is_empty()).then(|| chain::profile().explorer_tx(&event.signature))
+        }
+        "details" => Some(event.detail_lines().join("\n")).filter(|lines| !lines.is_empty()),
+        // Rejected by validation; kept verbatim if a template skipped it
+        _ => Some(format!("{{{placeholder}}}")),
+    }
+}
+
+/// Returns the symbol of a token, or the start of its mint if unknown.
+fn token_symbol(token: &TokenInfo) -> String {
+    token
+        .symbol
+        .clone()
+        .unwrap_or_else(|| token.mint.get(..8).unwrap_or(&token.mint).to_string())
+}
+
+/// Returns the human-readable amount of a token, or the raw amount if unknown.
+fn token_amount(token: &TokenInfo) -> String {
+    token.amount.map_or_else(
+        || token.amount_raw.to_string(),
+        |amount| number_format().amount(amount, token),
+    )
+}
+
+/// Splits a template at its next `{placeholder}` into `(before, placeholder, after)`.
+fn next_placeholder(template: &str) -> Option<(&str, &str, &str)> {
+    let start = template.find('{')?;
+    let len = template[start + 1..].find('}')?;
+    Some((
+        &template[..start],
+        &template[start + 1..start + 1 + len],
+        &template[start + 2 + len..],
+    ))
+}
+
+/// Returns the placeholders used in a template.
+fn placeholders(template: &str) -> impl Iterator<Item = &str> {
+    let mut rest = template;
+    std::iter::from_fn(move || {
+        let (_, placeholder, after) = next_placeholder(rest)?;
+        rest = after;
+        Some(placeholder)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::output::Protocol};
+
+    fn emit(description: &str) -> EmitConfig {
+        EmitConfig {
+            name: "WhaleExit".to_string(),
+            description: description.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_validate_placeholders() {
+        assert!(emit("{maker} sold {input_amount} {input_symbol} (${usd})")
+            .validate("r")
+            .is_ok());
+        assert!(matches!(
+            emit("{volume_24h}").validate("r"),
+            Err(RulesError::Invalid(_))
+        ));
+        assert!(matches!(
+            EmitConfig {
+                name: " ".to_string(),
+                description: String::new(),
+            }
+            .validate("r"),
+            Err(RulesError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_derive_renders_description() {
+        let mut input = TokenInfo::new("TokenMint123", 2_500_000)
+            .with_symbol("BONK")
+            .with_decimals(6);
+        input.amount_usd = Some(12_500.0);
+        let mut event = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .input_token(input)
+            .output_token(TokenInfo::new(
+                "So11111111111111111111111111111111111111112",
+                1,
+            ))
+            .maker("7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU")
+            .build();
+        event.maker_label = Some("whale-3".to_string());
+
+        let derived = emit("{maker} sold {input_amount} {input_symbol} (${usd}), fee {fee} {oops")
+            .derive(&event, "whales");
+        assert_eq!(derived.event_type, EventType::Custom);
+        let custom = derived.custom.unwrap();
+        assert_eq!(custom.name, "WhaleExit");
+        assert_eq!(custom.rule, "whales");
+        assert_eq!(custom.source_type, EventType::Swap);
+        assert_eq!(
+            custom.description,
+            "whale-3 sold 2.5000 BONK ($12.50K), fee ? {oops"
+        );
+    }
+}
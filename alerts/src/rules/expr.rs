@@ -0,0 +1,484 @@
+//! Expression conditions evaluated against swap events.
+//!
+//! Expressions combine event fields with comparisons and boolean operators,
+//! for conditions that have no dedicated predicate:
+//!
+//! ```toml
+//! when = { expr = "usd_value > 5000 && event_type == 'swap' && !(pool == 'Pool1')" }
+//! ```
+//!
+//! Operators are `||`, `&&`, `!`, `==`, `!=`, `<`, `<=`, `>`, `>=` and
+//! parentheses; literals are numbers, `'single'` or `"double"` quoted strings,
+//! `true`, `false`, and `null`. Fields whose value is unknown are `null`.
+//! `==` and `!=` compare any values (`null` only equals `null`); ordering
+//! comparisons involving `null` or mismatched types are false.
+
+use {
+    crate::output::{SwapEvent, TokenInfo},
+    serde::{Deserialize, Serialize},
+    std::{cmp::Ordering, fmt},
+};
+
+/// Event fields available to expressions.
+pub const VARIABLES: &[&str] = &[
+    "usd_value",
+    "price",
+    "effective_price",
+    "price_impact_pct",
+    "market_cap",
+    "fee",
+    "slot",
+    "timestamp",
+    "input_amount",
+    "output_amount",
+    "input_mint",
+    "output_mint",
+    "input_symbol",
+    "output_symbol",
+    "protocol",
+    "deployment",
+    "event_type",
+    "direction",
+    "side",
+    "pool",
+    "maker",
+    "maker_label",
+    "signed_by_owner",
+    "router",
+    "top_level_program",
+    "signature",
+    "custom",
+];
+
+/// A parsed expression condition.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Expr {
+    source: String,
+    root: Node,
+}
+
+impl Expr {
+    /// Parses an expression, rejecting unknown fields.
+    pub fn parse(source: &str) -> Result<Self, String> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, pos: 0 };
+        let root = parser.or()?;
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            return Err(format!("unexpected {token} in expression '{source}'"));
+        }
+        Ok(Self {
+            source: source.to_string(),
+            root,
+        })
+    }
+
+    /// Evaluates the expression against an event; non-boolean results are
+    /// truthy unless `null`, `0`, or empty.
+    pub fn matches(&self, event: &SwapEvent) -> bool {
+        self.root.eval(event).truthy()
+    }
+
+    /// Returns `true` if the expression reads a field.
+    pub fn uses(&self, variable: &str) -> bool {
+        self.root.uses(variable)
+    }
+}
+
+impl TryFrom<String> for Expr {
+    type Error = String;
+
+    fn try_from(source: String) -> Result<Self, Self::Error> {
+        Self::parse(&source)
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+/// A value produced while evaluating an expression.
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    Str(String),
+}
+
+impl Value {
+    fn truthy(&self) -> bool {
+        match self {
+            Self::Null => false,
+            Self::Bool(b) => *b,
+            Self::Number(n) => *n != 0.0,
+            Self::Str(s) => !s.is_empty(),
+        }
+    }
+}
+
+impl From<Option<f64>> for Value {
+    fn from(value: Option<f64>) -> Self {
+        value.map_or(Self::Null, Self::Number)
+    }
+}
+
+impl From<Option<String>> for Value {
+    fn from(value: Option<String>) -> Self {
+        value.map_or(Self::Null, Self::Str)
+    }
+}
+
+/// Comparison operators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Compare {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+/// A node of the expression tree.
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Literal(Value),
+    Variable(&'static str),
+    Not(Box<Node>),
+    And(Box<Node>, Box<Node>),
+    Or(Box<Node>, Box<Node>),
+    Compare(Compare, Box<Node>, Box<Node>),
+}
+
+impl Node {
+    fn eval(&self, event: &SwapEvent) -> Value {
+        match self {
+            Self::Literal(value) => value.clone(),
+            Self::Variable(name) => variable(name, event),
+            Self::Not(node) => Value::Bool(!node.eval(event).truthy()),
+            Self::And(a, b) => Value::Bool(a.eval(event).truthy() && b.eval(event).truthy()),
+            Self::Or(a, b) => Value::Bool(a.eval(event).truthy() || b.eval(event).truthy()),
+            Self::Compare(op, a, b) => Value::Bool(compare(*op, &a.eval(event), &b.eval(event))),
+        }
+    }
+
+    fn uses(&self, variable: &str) -> bool {
+        match self {
+            Self::Literal(_) => false,
+            Self::Variable(name) => *name == variable,
+            Self::Not(node) => node.uses(variable),
+            Self::And(a, b) | Self::Or(a, b) | Self::Compare(_, a, b) => {
+                a.uses(variable) || b.uses(variable)
+            }
+        }
+    }
+}
+
+/// Compares two values; `null` and mismatched types are unequal and unordered.
+fn compare(op: Compare, a: &Value, b: &Value) -> bool {
+    let ordering = match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a.partial_cmp(b),
+        (Value::Str(a), Value::Str(b)) => Some(a.cmp(b)),
+        (Value::Bool(a), Value::Bool(b)) => Some(a.cmp(b)),
+        _ => None,
+    };
+    match op {
+        Compare::Eq => a == b,
+        Compare::Ne => a != b,
+        Compare::Lt => ordering.is_some_and(Ordering::is_lt),
+        Compare::Le => ordering.is_some_and(Ordering::is_le),
+        Compare::Gt => ordering.is_some_and(Ordering::is_gt),
+        Compare::Ge => ordering.is_some_and(Ordering::is_ge),
+    }
+}
+
+/// Reads an event field.
+fn variable(name: &str, event: &SwapEvent) -> Value {
+    let amount = |token: &Option<TokenInfo>| token.as_ref().and_then(|t| t.amount);
+    let mint = |token: &Option<TokenInfo>| token.as_ref().map(|t| t.mint.clone());
+    let symbol = |token: &Option<TokenInfo>| token.as_ref().and_then(|t| t.symbol.clone());
+    match name {
+        "usd_value" => event.usd_value().into(),
+        "price" => event.price().into(),
+        "effective_price" => event.effective_price.into(),
+        "price_impact_pct" => event.price_impact_pct.into(),
+        "market_cap" => event.market_cap_usd.into(),
+        "fee" => event.fee.map(|fee| fee as f64).into(),
+        "slot" => Value::Number(event.slot as f64),
+        "timestamp" => event.timestamp.map(|ts| ts as f64).into(),
+        "input_amount" => amount(&event.input_token).into(),
+        "output_amount" => amount(&event.output_token).into(),
+        "input_mint" => mint(&event.input_token).into(),
+        "output_mint" => mint(&event.output_token).into(),
+        "input_symbol" => symbol(&event.input_token).into(),
+        "output_symbol" => symbol(&event.output_token).into(),
+        "protocol" => serde_name(event.protocol),
+        "deployment" => event.deployment.clone().into(),
+        "event_type" => serde_name(event.event_type),
+        "direction" => serde_name(event.direction),
+        "side" => event.side.map(|side| side.to_string()).into(),
+        "pool" => Value::Str(event.pool.clone()),
+        "maker" => event.maker.clone().into(),
+        "maker_label" => event.maker_label.clone().into(),
+        "signed_by_owner" => event.signed_by_owner.map_or(Value::Null, Value::Bool),
+        "router" => event.router.clone().into(),
+        "top_level_program" => event.top_level_program.clone().into(),
+        "signature" => Value::Str(event.signature.clone()),
+        "custom" => event.custom.as_ref().map(|c| c.name.clone()).into(),
+        _ => Value::Null,
+    }
+}
+
+/// Returns the configuration name of an enum value (e.g., `"amm_v4"`).
+fn serde_name(value: impl Serialize) -> Value {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => Value::Str(name),
+        _ => Value::Null,
+    }
+}
+
+/// A lexical token.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Str(String),
+    Ident(String),
+    Op(&'static str),
+    Open,
+    Close,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "number {n}"),
+            Self::Str(s) => write!(f, "string '{s}'"),
+            Self::Ident(name) => write!(f, "'{name}'"),
+            Self::Op(op) => write!(f, "'{op}'"),
+            Self::Open => write!(f, "'('"),
+            Self::Close => write!(f, "')'"),
+        }
+    }
+}
+
+/// Operators, longest first so `>=` is not read as `>`.
+const OPERATORS: &[&str] = &["||", "&&", "==", "!=", "<=", ">=", "<", ">", "!", "-"];
+
+fn tokenize(source: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+    while let Some(c) = rest.chars().next() {
+        if c.is_whitespace() {
+            rest = &rest[c.len_utf8()..];
+        } else if c == '(' || c == ')' {
+            tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            rest = &rest[1..];
+        } else if c == '"' || c == '\'' {
+            let end = rest[1..]
+                .find(c)
+                .ok_or_else(|| format!("unterminated string in expression '{source}'"))?;
+            tokens.push(Token::Str(rest[1..=end].to_string()));
+            rest = &rest[end + 2..];
+        } else if c.is_ascii_digit() || c == '.' {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '_'))
+                .unwrap_or(rest.len());
+            let number = rest[..end].replace('_', "");
+            let number = number
+                .parse()
+                .map_err(|_| format!("invalid number '{}' in expression", &rest[..end]))?;
+            tokens.push(Token::Number(number));
+            rest = &rest[end..];
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            tokens.push(Token::Ident(rest[..end].to_string()));
+            rest = &rest[end..];
+        } else {
+            let op = OPERATORS
+                .iter()
+                .find(|op| rest.starts_with(**op))
+                .ok_or_else(|| format!("unexpected '{c}' in expression '{source}'"))?;
+            tokens.push(Token::Op(*op));
+            rest = &rest[op.len()..];
+        }
+    }
+    Ok(tokens)
+}
+
+/// Recursive-descent parser; precedence from lowest: `||`, `&&`, `!`, comparisons.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek_op(&self, op: &str) -> bool {
+        matches!(self.tokens.get(self.pos), Some(Token::Op(o)) if *o == op)
+    }
+
+    fn next(&mut self) -> Result<Token, String> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| "unexpected end of expression".to_string())?;
+        self.pos += 1;
+        Ok(token)
+    }
+
+    fn or(&mut self) -> Result<Node, String> {
+        let mut node = self.and()?;
+        while self.peek_op("||") {
+            self.pos += 1;
+            node = Node::Or(Box::new(node), Box::new(self.and()?));
+        }
+        Ok(node)
+    }
+
+    fn and(&mut self) -> Result<Node, String> {
+        let mut node = self.not()?;
+        while self.peek_op("&&") {
+            self.pos += 1;
+            node = Node::And(Box::new(node), Box::new(self.not()?));
+        }
+        Ok(node)
+    }
+
+    fn not(&mut self) -> Result<Node, String> {
+        if self.peek_op("!") {
+            self.pos += 1;
+            return Ok(Node::Not(Box::new(self.not()?)));
+        }
+        self.comparison()
+    }
+
+    fn comparison(&mut self) -> Result<Node, String> {
+        let left = self.primary()?;
+        let op = match self.tokens.get(self.pos) {
+            Some(Token::Op("==")) => Compare::Eq,
+            Some(Token::Op("!=")) => Compare::Ne,
+            Some(Token::Op("<")) => Compare::Lt,
+            Some(Token::Op("<=")) => Compare::Le,
+            Some(Token::Op(">")) => Compare::Gt,
+            Some(Token::Op(">=")) => Compare::Ge,
+            _ => return Ok(left),
+        };
+        self.pos += 1;
+        let right = self.primary()?;
+        Ok(Node::Compare(op, Box::new(left), Box::new(right)))
+    }
+
+    fn primary(&mut self) -> Result<Node, String> {
+        match self.next()? {
+            Token::Number(n) => Ok(Node::Literal(Value::Number(n))),
+            Token::Str(s) => Ok(Node::Literal(Value::Str(s))),
+            Token::Op("-") => match self.next()? {
+                Token::Number(n) => Ok(Node::Literal(Value::Number(-n))),
+                token => Err(format!("expected a number after '-', found {token}")),
+            },
+            Token::Open => {
+                let node = self.or()?;
+                match self.next()? {
+                    Token::Close => Ok(node),
+                    token => Err(format!("expected ')', found {token}")),
+                }
+            }
+            Token::Ident(name) => match name.as_str() {
+                "true" => Ok(Node::Literal(Value::Bool(true))),
+                "false" => Ok(Node::Literal(Value::Bool(false))),
+                "null" => Ok(Node::Literal(Value::Null)),
+                _ => VARIABLES
+                    .iter()
+                    .copied()
+                    .find(|v| *v == name.as_str())
+                    .map(Node::Variable)
+                    .ok_or_else(|| {
+                        format!(
+                            "unknown field '{name}' (expected one of: {})",
+                            VARIABLES.join(", ")
+                        )
+                    }),
+            },
+            token => Err(format!("unexpected {token} in expression")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{EventType, Protocol},
+    };
+
+    fn event() -> SwapEvent {
+        let mut input = TokenInfo::new("MintA", 2_000_000).with_decimals(6);
+        input.amount_usd = Some(7_500.0);
+        SwapEvent::builder()
+            .protocol(Protocol::AmmV4)
+            .signature("sig")
+            .pool("Pool1")
+            .input_token(input)
+            .fee(25)
+            .build()
+    }
+
+    fn eval(source: &str) -> bool {
+        Expr::parse(source).unwrap().matches(&event())
+    }
+
+    #[test]
+    fn test_expressions() {
+        assert!(eval("usd_value > 5000 && protocol == 'amm_v4'"));
+        assert!(eval("event_type == \"swap\" && input_amount == 2"));
+        assert!(eval("usd_value >= 7_500 && !(pool == 'Pool2')"));
+        assert!(eval("fee < 10 || input_mint == 'MintA'"));
+        assert!(eval("side == null && signed_by_owner == null"));
+        assert!(eval("!maker && market_cap == null || false"));
+        assert!(!eval("usd_value < -1"));
+
+        // Unknown values are only equal to null and never ordered
+        assert!(!eval("market_cap > 0"));
+        assert!(!eval("market_cap <= 0"));
+        assert!(eval("market_cap != 0 && market_cap == null"));
+        assert!(!eval("usd_value == '7500'"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for source in [
+            "usd_value >",
+            "liquidity > 1.5",
+            "(usd_value > 1",
+            "usd_value > 1 1",
+            "pool == 'Pool1",
+            "usd_value # 1",
+            "",
+        ] {
+            assert!(Expr::parse(source).is_err(), "{source}");
+        }
+    }
+
+    #[test]
+    fn test_uses_and_custom() {
+        let expr = Expr::parse("custom == 'WhaleExit'").unwrap();
+        assert!(expr.uses("custom"));
+        assert!(!expr.uses("pool"));
+
+        let mut event = event();
+        assert!(!expr.matches(&event));
+        event.event_type = EventType::Custom;
+        event.custom = Some(crate::output::CustomEvent {
+            name: "WhaleExit".to_string(),
+            rule: "r".to_string(),
+            description: String::new(),
+            source_type: EventType::Swap,
+        });
+        assert!(expr.matches(&event));
+    }
+}
@@ -0,0 +1,121 @@
+//! Example-event fixtures asserting whether rules fire.
+//!
+//! Fixtures live next to the rule they test and are run by
+//! `raydium-alerts rules test`:
+//!
+//! ```toml
+//! [[rules.fixtures]]
+//! name = "large clmm swap"
+//! event = '{"protocol": "clmm", "input_token": {"mint": "...", "amount_raw": 1, "amount_usd": 25000}}'
+//! matches = true
+//! ```
+//!
+//! Events are JSON [`SwapEvent`]s whose omitted fields take the defaults of a
+//! CPMM swap. Sequence rules see the `after` events first.
+
+use {
+    crate::output::{swap_event::TradeSide, EventType, Protocol, SwapEvent},
+    serde::Deserialize,
+};
+
+/// An example event and whether the rule is expected to fire on it.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FixtureConfig {
+    /// Name shown in test output (default: the fixture's position)
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Events observed before `event`, as JSON (for sequence rules)
+    #[serde(default)]
+    pub after: Vec<String>,
+    /// The event under test, as JSON
+    pub event: String,
+    /// Whether the rule should fire on `event`
+    pub matches: bool,
+}
+
+impl FixtureConfig {
+    /// Parses the `after` events followed by the event under test.
+    pub fn events(&self) -> Result<Vec<SwapEvent>, String> {
+        self.after
+            .iter()
+            .chain(std::iter::once(&self.event))
+            .map(|json| parse_event(json))
+            .collect()
+    }
+}
+
+/// Result of running one fixture.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixtureOutcome {
+    /// Rule under test
+    pub rule: String,
+    /// Fixture name
+    pub fixture: String,
+    /// Whether the rule was expected to fire
+    pub expected: bool,
+    /// Whether the rule fired
+    pub actual: bool,
+}
+
+impl FixtureOutcome {
+    /// Returns `true` if the rule behaved as expected.
+    pub fn passed(&self) -> bool {
+        self.expected == self.actual
+    }
+}
+
+/// Parses a fixture event, filling omitted fields from a default CPMM swap.
+///
+/// The trade side is derived from the legs unless given.
+pub fn parse_event(json: &str) -> Result<SwapEvent, String> {
+    let fields: serde_json::Value =
+        serde_json::from_str(json).map_err(|e| format!("invalid event JSON: {e}"))?;
+    let serde_json::Value::Object(fields) = fields else {
+        return Err("event must be a JSON object".to_string());
+    };
+
+    let defaults = SwapEvent::builder()
+        .protocol(Protocol::Cpmm)
+        .signature("fixture")
+        .pool("fixture")
+        .build();
+    let mut event = serde_json::to_value(defaults).map_err(|e| e.to_string())?;
+    if let Some(event) = event.as_object_mut() {
+        event.extend(fields);
+    }
+
+    let mut event: SwapEvent =
+        serde_json::from_value(event).map_err(|e| format!("invalid event: {e}"))?;
+    if event.side.is_none() && event.event_type == EventType::Swap {
+        if let (Some(input), Some(output)) = (&event.input_token, &event.output_token) {
+            event.side = TradeSide::classify(input, output);
+        }
+    }
+    Ok(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::output::swap_event::WSOL_MINT};
+
+    #[test]
+    fn test_parse_event_fills_defaults() {
+        let event = parse_event(&format!(
+            r#"{{"protocol": "clmm", "maker": "whale",
+                "input_token": {{"mint": "{WSOL_MINT}", "amount_raw": 1, "amount_usd": 200}},
+                "output_token": {{"mint": "Meme", "amount_raw": 5}}}}"#
+        ))
+        .unwrap();
+        assert_eq!(event.protocol, Protocol::Clmm);
+        assert_eq!(event.event_type, EventType::Swap);
+        assert_eq!(event.pool, "fixture");
+        assert_eq!(event.maker.as_deref(), Some("whale"));
+        assert_eq!(event.usd_value(), Some(200.0));
+        assert_eq!(event.side, Some(TradeSide::Buy));
+
+        assert!(parse_event("[]").is_err());
+        assert!(parse_event("{").is_err());
+        assert!(parse_event(r#"{"protocol": "uniswap"}"#).is_err());
+    }
+}
@@ -0,0 +1,1264 @@
+//! Rules-based alert routing.
+//!
+//! Rules are declared in a TOML file (`RULES_FILE`). Each rule combines
+//! [`Condition`]s with `all`/`any`/`not` and routes matching events to its own
+//! destinations, independently of the global `WEBHOOK_URL`:
+//!
+//! ```toml
+//! [[rules]]
+//! name = "clmm-whales"
+//! when = { all = [{ protocol = "clmm" }, { usd_above = 10000 }] }
+//! destinations = [{ type = "webhook", url = "https://example.com/client-a" }]
+//! ```
+//!
+//! Conditions without a dedicated predicate can be written as expressions,
+//! e.g. `{ expr = "usd_value > 5000 && direction == 'exact_output'" }`.
+//!
+//! Rules see the events that pass the global `FILTER_*` filters.
+//!
+//! A rule can emit a named event instead of delivering the matched one. The
+//! custom event is delivered to the rule's destinations and to rules that
+//! select it with `{ custom = "<name>" }`:
+//!
+//! ```toml
+//! [[rules]]
+//! name = "whale-exits"
+//! when = { all = [{ maker_in = ["<wallet>"] }, { usd_above = 10000 }] }
+//! emit = { name = "WhaleExit", description = "{maker} sold {input_amount} {input_symbol}" }
+//! destinations = [{ type = "discord", url = "https://discord.com/api/webhooks/..." }]
+//! ```
+//!
+//! A rule with a `then` condition correlates two events on the same pool: it
+//! fires when an event matching `when` is followed by one matching `then`
+//! within `within_secs` (default: 600), delivering a combined alert:
+//!
+//! ```toml
+//! [[rules]]
+//! name = "buy-then-pull"
+//! when = { all = [{ event_type = "swap" }, { usd_above = 10000 }] }
+//! then = { event_type = "remove_liquidity" }
+//! within_secs = 600
+//! destinations = [{ type = "webhook", url = "https://example.com/client-a" }]
+//! ```
+//!
+//! A rule with `mode = "shadow"` is evaluated like an active rule, but the
+//! alerts it would deliver are only logged and counted in the
+//! `rule_shadow_matches` metrics, so an aggressive rule can be tried on live
+//! traffic before it is switched to `active`.
+//!
+//! Bridges mirror everything delivered to one destination onto others, each
+//! formatted for its own channel, without repeating rule definitions:
+//!
+//! ```toml
+//! [[bridges]]
+//! from = { type = "discord", url = "https://discord.com/api/webhooks/..." }
+//! to = [{ type = "telegram", chat_id = -1001234567890 }]
+//! ```
+//!
+//! Email destinations send rare, high-severity alerts through the `SMTP_*`
+//! server, batching bursts into digests (see [`EmailNotifier`]):
+//!
+//! ```toml
+//! destinations = [{ type = "email", to = ["oncall@example.com"], subject = "{emoji} {name} on {pool}" }]
+//! ```
+//!
+//! X destinations post a rule's alerts to the account of the `X_*`
+//! credentials, within a daily post limit (see [`XNotifier`]).
+
+mod condition;
+mod emit;
+mod expr;
+mod fixtures;
+mod sequence;
+
+pub use {
+    condition::Condition,
+    emit::{render_template, render_text, unknown_placeholder, EmitConfig, PLACEHOLDERS},
+    expr::Expr,
+    fixtures::{FixtureConfig, FixtureOutcome},
+};
+
+use {
+    self::sequence::Sequence,
+    crate::{
+        output::{
+            email::{self, EmailConfig, EmailNotifier, SmtpConfig},
+            template, SchemaVersion, SwapEvent, WebhookConfig, WebhookFormat, WebhookNotifier,
+            XConfig, XNotifier,
+        },
+        subscriptions::DEFAULT_API_URL,
+    },
+    carbon_core::metrics::MetricsCollection,
+    serde::Deserialize,
+    std::{
+        collections::{HashMap, HashSet},
+        env, fmt, fs,
+        path::Path,
+        sync::{Arc, OnceLock},
+    },
+    tokio::sync::mpsc,
+};
+
+static METRICS: OnceLock<Arc<MetricsCollection>> = OnceLock::new();
+
+/// Reports the matches of shadow rules to `metrics` (`rule_shadow_matches`,
+/// and `rule_shadow_matches.<rule>` per rule).
+///
+/// Later calls are ignored.
+pub fn install_metrics(metrics: Arc<MetricsCollection>) {
+    if METRICS.set(metrics).is_err() {
+        log::warn!("Rule metrics already installed, ignoring");
+    }
+}
+
+/// Errors that can occur while loading rules.
+#[derive(Debug)]
+pub enum RulesError {
+    /// The rules file could not be read
+    Read(String),
+    /// The rules file is not valid TOML or has an unknown shape
+    Parse(String),
+    /// A rule or bridge is invalid (e.g., duplicate name, no destinations)
+    Invalid(String),
+}
+
+impl fmt::Display for RulesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(e) => write!(f, "failed to read rules file: {e}"),
+            Self::Parse(e) => write!(f, "failed to parse rules file: {e}"),
+            Self::Invalid(e) => write!(f, "invalid rule: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for RulesError {}
+
+/// Where a matching event is delivered.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Destination {
+    /// POST the event as JSON to a webhook URL
+    Webhook {
+        /// Webhook URL
+        url: String,
+        /// JSON layout of the events (default: `WEBHOOK_SCHEMA_VERSION`)
+        #[serde(default)]
+        schema_version: Option<SchemaVersion>,
+    },
+    /// Post the text-formatted event to a Discord webhook
+    Discord {
+        /// Discord webhook URL
+        url: String,
+        /// Text template of the messages (default: `default`)
+        #[serde(default)]
+        template: Option<String>,
+    },
+    /// Send the text-formatted event to a Telegram chat or channel
+    Telegram {
+        /// Chat ID (`-100...` for channels, where the bot must be an admin)
+        chat_id: i64,
+        /// Bot token (default: `TELEGRAM_BOT_TOKEN`)
+        #[serde(default)]
+        bot_token: Option<String>,
+        /// Text template of the messages (default: `default`)
+        #[serde(default)]
+        template: Option<String>,
+    },
+    /// Email the text-formatted event through the `SMTP_*` server
+    Email {
+        /// Recipient addresses
+        to: Vec<String>,
+        /// Subject template (default: `{emoji} {name} [{protocol}] {pool}`)
+        #[serde(default)]
+        subject: Option<String>,
+        /// Text template of the body (default: `default`)
+        #[serde(default)]
+        template: Option<String>,
+    },
+    /// Post the text-formatted event to X with the `X_*` credentials
+    X {
+        /// Text template of the posts (default: `default`, then a compact layout)
+        #[serde(default)]
+        template: Option<String>,
+    },
+}
+
+/// How a destination is delivered to.
+#[derive(Debug)]
+enum DeliveryConfig {
+    Webhook(WebhookConfig),
+    Email(EmailConfig),
+    X(XConfig),
+}
+
+/// The notifier of a destination.
+enum DestinationNotifier {
+    Webhook(WebhookNotifier),
+    Email(EmailNotifier),
+    X(XNotifier),
+}
+
+impl DestinationNotifier {
+    /// Creates the notifier of a destination. Must be called within a Tokio runtime.
+    fn new(config: DeliveryConfig) -> Result<Self, RulesError> {
+        match config {
+            DeliveryConfig::Webhook(config) => Ok(Self::Webhook(WebhookNotifier::new(config))),
+            DeliveryConfig::Email(config) => EmailNotifier::new(config)
+                .map(Self::Email)
+                .map_err(|e| RulesError::Invalid(e.to_string())),
+            DeliveryConfig::X(config) => Ok(Self::X(XNotifier::new(config))),
+        }
+    }
+
+    /// Tries to queue an event without blocking.
+    #[allow(clippy::result_large_err)]
+    fn try_send(&self, event: SwapEvent) -> Result<(), mpsc::error::TrySendError<SwapEvent>> {
+        match self {
+            Self::Webhook(notifier) => notifier.try_send(event),
+            Self::Email(notifier) => notifier.try_send(event),
+            Self::X(notifier) => notifier.try_send(event),
+        }
+    }
+}
+
+impl Destination {
+    /// Builds the configuration delivering to this destination.
+    fn delivery_config(&self) -> Result<DeliveryConfig, RulesError> {
+        match self {
+            Self::Email { .. } => self.email_config().map(DeliveryConfig::Email),
+            Self::X { template } => {
+                if let Some(name) = template {
+                    if !template::has_template(name) {
+                        return Err(RulesError::Invalid(format!(
+                            "{self} uses template '{name}', which is not declared in TEMPLATES_FILE"
+                        )));
+                    }
+                }
+                XConfig::from_env(template.clone())
+                    .map(DeliveryConfig::X)
+                    .ok_or_else(|| {
+                        RulesError::Invalid(format!(
+                            "{self} needs X_API_KEY, X_API_SECRET, X_ACCESS_TOKEN, and X_ACCESS_SECRET"
+                        ))
+                    })
+            }
+            _ => self.webhook_config().map(DeliveryConfig::Webhook),
+        }
+    }
+
+    /// Builds the email configuration of an email destination.
+    ///
+    /// Recipients must be valid addresses, the `subject` may only use known
+    /// placeholders, and `SMTP_HOST` and `SMTP_FROM` must be set.
+    fn email_config(&self) -> Result<EmailConfig, RulesError> {
+        let Self::Email {
+            to,
+            subject,
+            template,
+        } = self
+        else {
+            return Err(RulesError::Invalid(format!(
+                "{self} is not an email destination"
+            )));
+        };
+        if to.is_empty() {
+            return Err(RulesError::Invalid(format!("{self} has no recipients")));
+        }
+        let to = to
+            .iter()
+            .map(|address| email::parse_mailbox(address))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| RulesError::Invalid(format!("{self}: {e}")))?;
+        let subject = subject
+            .clone()
+            .unwrap_or_else(|| email::DEFAULT_SUBJECT.to_string());
+        if let Some(placeholder) = unknown_placeholder(&subject) {
+            return Err(RulesError::Invalid(format!(
+                "{self} subject uses unknown placeholder '{{{placeholder}}}' (expected one of: {})",
+                PLACEHOLDERS.join(", ")
+            )));
+        }
+        if let Some(name) = template {
+            if !template::has_template(name) {
+                return Err(RulesError::Invalid(format!(
+                    "{self} uses template '{name}', which is not declared in TEMPLATES_FILE"
+                )));
+            }
+        }
+        let smtp = SmtpConfig::from_env()
+            .map_err(|e| RulesError::Invalid(format!("{self}: {e}")))?
+            .ok_or_else(|| RulesError::Invalid(format!("{self} needs SMTP_HOST and SMTP_FROM")))?;
+        Ok(EmailConfig {
+            smtp,
+            to,
+            subject,
+            template: template.clone(),
+        })
+    }
+
+    /// Builds the webhook configuration delivering to this destination.
+    ///
+    /// Telegram destinations are sent through the Bot API at `TELEGRAM_API_URL`
+    /// (default: https://api.telegram.org). The `template` of Discord and
+    /// Telegram destinations must be declared in `TEMPLATES_FILE`.
+    fn webhook_config(&self) -> Result<WebhookConfig, RulesError> {
+        let non_empty = |value: Option<String>| {
+            value
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        if let Self::Discord {
+            template: Some(name),
+            ..
+        }
+        | Self::Telegram {
+            template: Some(name),
+            ..
+        } = self
+        {
+            if !template::has_template(name) {
+                return Err(RulesError::Invalid(format!(
+                    "{self} uses template '{name}', which is not declared in TEMPLATES_FILE"
+                )));
+            }
+        }
+        let (url, format) = match self {
+            Self::Webhook {
+                url,
+                schema_version,
+            } => (
+                url.clone(),
+                WebhookFormat::Json(schema_version.unwrap_or_else(SchemaVersion::from_env)),
+            ),
+            Self::Discord { url, template } => (
+                url.clone(),
+                WebhookFormat::Discord {
+                    template: template.clone(),
+                },
+            ),
+            Self::Telegram {
+                chat_id,
+                bot_token,
+                template,
+            } => {
+                let token = non_empty(bot_token.clone())
+                    .or_else(|| non_empty(env::var("TELEGRAM_BOT_TOKEN").ok()))
+                    .ok_or_else(|| {
+                        RulesError::Invalid(format!(
+                            "{self} needs a bot_token or TELEGRAM_BOT_TOKEN"
+                        ))
+                    })?;
+                let api_url = non_empty(env::var("TELEGRAM_API_URL").ok())
+                    .unwrap_or_else(|| DEFAULT_API_URL.to_string());
+                (
+                    format!("{}/bot{token}/sendMessage", api_url.trim_end_matches('/')),
+                    WebhookFormat::Telegram {
+                        chat_id: *chat_id,
+                        template: template.clone(),
+                    },
+                )
+            }
+            Self::Email { .. } | Self::X { .. } => {
+                return Err(RulesError::Invalid(format!("{self} is not a webhook")));
+            }
+        };
+
+        Ok(WebhookConfig {
+            url,
+            format,
+            ..WebhookConfig::default()
+        })
+    }
+}
+
+impl fmt::Display for Destination {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Webhook { url, .. } => write!(f, "webhook:{url}"),
+            Self::Discord { url, .. } => write!(f, "discord:{url}"),
+            // The bot token is a secret and never displayed
+            Self::Telegram { chat_id, .. } => write!(f, "telegram:{chat_id}"),
+            Self::Email { to, .. } => write!(f, "email:{}", to.join(",")),
+            Self::X { template } => {
+                write!(
+                    f,
+                    "x:{}",
+                    template.as_deref().unwrap_or(template::DEFAULT_TEMPLATE)
+                )
+            }
+        }
+    }
+}
+
+/// How a rule's matches are handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleMode {
+    /// Matches are delivered to the rule's destinations (default)
+    #[default]
+    Active,
+    /// Matches are logged and counted, but not delivered
+    Shadow,
+}
+
+/// A rule as declared in the rules file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleConfig {
+    /// Unique rule name, used in logs
+    pub name: String,
+    /// Condition an event must match
+    pub when: Condition,
+    /// Condition of a follow-up event on the same pool completing a sequence
+    #[serde(default)]
+    pub then: Option<Condition>,
+    /// Window for the follow-up event, in seconds (default: 600)
+    #[serde(default)]
+    pub within_secs: Option<u64>,
+    /// Destinations for matching events
+    pub destinations: Vec<Destination>,
+    /// Optional custom event delivered instead of the matched event
+    #[serde(default)]
+    pub emit: Option<EmitConfig>,
+    /// Whether the rule is evaluated (default: true)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Whether matches are delivered or only logged and counted (default: active)
+    #[serde(default)]
+    pub mode: RuleMode,
+    /// Example events with expected outcomes, run by `rules test`
+    #[serde(default)]
+    pub fixtures: Vec<FixtureConfig>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// A bridge mirroring deliveries to one destination onto others.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BridgeConfig {
+    /// Destination whose deliveries are mirrored
+    pub from: Destination,
+    /// Destinations receiving the mirrored deliveries, in their own formats
+    pub to: Vec<Destination>,
+}
+
+/// Contents of a rules file.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RulesFile {
+    /// Declared rules
+    #[serde(default)]
+    pub rules: Vec<RuleConfig>,
+    /// Declared bridges between destinations
+    #[serde(default)]
+    pub bridges: Vec<BridgeConfig>,
+}
+
+impl RulesFile {
+    /// Parses and validates rules from TOML.
+    pub fn parse(toml: &str) -> Result<Self, RulesError> {
+        let file: Self = toml::from_str(toml).map_err(|e| RulesError::Parse(e.to_string()))?;
+        file.validate()?;
+        Ok(file)
+    }
+
+    /// Reads, parses, and validates a rules file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RulesError> {
+        let path = path.as_ref();
+        let toml = fs::read_to_string(path)
+            .map_err(|e| RulesError::Read(format!("{}: {e}", path.display())))?;
+        Self::parse(&toml)
+    }
+
+    /// Checks rule names are unique, every rule has a destination, every
+    /// bridge mirrors a destination that receives events, and every
+    /// destination can be delivered to.
+    fn validate(&self) -> Result<(), RulesError> {
+        let mut names = HashSet::new();
+        for rule in &self.rules {
+            if rule.name.trim().is_empty() {
+                return Err(RulesError::Invalid("rule name must not be empty".into()));
+            }
+            if !names.insert(rule.name.as_str()) {
+                return Err(RulesError::Invalid(format!(
+                    "duplicate rule name '{}'",
+                    rule.name
+                )));
+            }
+            if rule.destinations.is_empty() {
+                return Err(RulesError::Invalid(format!(
+                    "rule '{}' has no destinations",
+                    rule.name
+                )));
+            }
+            if let Some(ref emit) = rule.emit {
+                emit.validate(&rule.name)?;
+            }
+            match (&rule.then, rule.within_secs) {
+                (None, Some(_)) => {
+                    return Err(RulesError::Invalid(format!(
+                        "rule '{}' sets within_secs without a then condition",
+                        rule.name
+                    )));
+                }
+                (Some(_), Some(0)) => {
+                    return Err(RulesError::Invalid(format!(
+                        "rule '{}' has a zero within_secs window",
+                        rule.name
+                    )));
+                }
+                _ => {}
+            }
+            for (i, fixture) in rule.fixtures.iter().enumerate() {
+                fixture.events().map_err(|e| {
+                    RulesError::Invalid(format!(
+                        "rule '{}' fixture '{}': {e}",
+                        rule.name,
+                        fixture_name(fixture, i)
+                    ))
+                })?;
+            }
+        }
+
+        let reachable: HashSet<&Destination> = self
+            .rules
+            .iter()
+            .flat_map(|rule| &rule.destinations)
+            .chain(self.bridges.iter().flat_map(|bridge| &bridge.to))
+            .collect();
+        for bridge in &self.bridges {
+            if bridge.to.is_empty() {
+                return Err(RulesError::Invalid(format!(
+                    "bridge from {} has no destinations",
+                    bridge.from
+                )));
+            }
+            if bridge.to.contains(&bridge.from) {
+                return Err(RulesError::Invalid(format!(
+                    "bridge from {} mirrors to itself",
+                    bridge.from
+                )));
+            }
+            if !reachable.contains(&bridge.from) {
+                return Err(RulesError::Invalid(format!(
+                    "bridge from {} is not a destination of any rule or bridge",
+                    bridge.from
+                )));
+            }
+        }
+
+        for destination in reachable {
+            destination.delivery_config()?;
+        }
+        Ok(())
+    }
+
+    /// Runs every rule's fixtures, including those of disabled rules.
+    ///
+    /// Each fixture is evaluated against a fresh rule, so sequence state does
+    /// not leak between fixtures.
+    pub fn run_fixtures(&self) -> Vec<FixtureOutcome> {
+        let mut outcomes = Vec::new();
+        for config in &self.rules {
+            for (i, fixture) in config.fixtures.iter().enumerate() {
+                // Fixtures were parsed by `validate`
+                let Ok(events) = fixture.events() else {
+                    continue;
+                };
+                let rule = Rule::new(config.clone());
+                let (event, after) = events.split_last().expect("fixture has an event");
+                for event in after {
+                    rule.fire(event);
+                }
+                outcomes.push(FixtureOutcome {
+                    rule: config.name.clone(),
+                    fixture: fixture_name(fixture, i),
+                    expected: fixture.matches,
+                    actual: rule.fire(event).is_some(),
+                });
+            }
+        }
+        outcomes
+    }
+}
+
+/// Returns a fixture's name, or its 1-based position.
+fn fixture_name(fixture: &FixtureConfig, index: usize) -> String {
+    fixture
+        .name
+        .clone()
+        .unwrap_or_else(|| format!("#{}", index + 1))
+}
+
+/// An enabled rule and its destinations.
+struct Rule {
+    name: String,
+    condition: Condition,
+    destinations: Vec<Destination>,
+    emit: Option<EmitConfig>,
+    /// Follow-up correlation; the rule fires on completed sequences only
+    sequence: Option<Sequence>,
+    /// Whether the rule routes custom events emitted by other rules
+    selects_custom: bool,
+    /// Whether matches are only logged and counted
+    shadow: bool,
+}
+
+impl Rule {
+    /// Creates a rule from its configuration.
+    fn new(config: RuleConfig) -> Self {
+        Self {
+            selects_custom: config.when.selects_custom(),
+            sequence: config.then.map(|then| {
+                Sequence::new(
+                    then,
+                    config.within_secs.unwrap_or(sequence::DEFAULT_WINDOW_SECS),
+                )
+            }),
+            name: config.name,
+            condition: config.when,
+            destinations: config.destinations,
+            emit: config.emit,
+            shadow: config.mode == RuleMode::Shadow,
+        }
+    }
+
+    /// Returns the event a rule delivers for an event, if it fires.
+    ///
+    /// That is the event itself, a combined alert for a completed sequence, or
+    /// the custom event of an `emit` action.
+    fn fire(&self, event: &SwapEvent) -> Option<SwapEvent> {
+        let fired = match self.sequence {
+            Some(ref sequence) => sequence.observe(&self.condition, event, &self.name)?,
+            None if self.condition.matches(event) => event.clone(),
+            None => return None,
+        };
+        Some(match self.emit {
+            Some(ref emit) => emit.derive(event, &self.name),
+            None => fired,
+        })
+    }
+
+    /// Logs and counts an alert of a shadow rule instead of delivering it.
+    fn record_shadow(&self, fired: &SwapEvent) {
+        log::info!(
+            "Shadow rule '{}' would deliver {} {} to {} destination(s)",
+            self.name,
+            fired.event_type,
+            fired.signature,
+            self.destinations.len()
+        );
+        if let Some(metrics) = METRICS.get() {
+            let metrics = metrics.clone();
+            let per_rule = format!("rule_shadow_matches.{}", metric_name(&self.name));
+            tokio::spawn(async move {
+                let _ = metrics.increment_counter("rule_shadow_matches", 1).await;
+                let _ = metrics.increment_counter(&per_rule, 1).await;
+            });
+        }
+    }
+}
+
+/// Returns a rule name with the characters StatsD reserves replaced.
+fn metric_name(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Evaluates rules against events and delivers matches to each rule's destinations.
+pub struct RuleEngine {
+    rules: Vec<Rule>,
+    /// Mirror destinations of each bridged destination
+    bridges: HashMap<Destination, Vec<Destination>>,
+    /// One notifier per distinct destination, shared by rules and bridges
+    notifiers: HashMap<Destination, DestinationNotifier>,
+}
+
+impl RuleEngine {
+    /// Creates an engine from parsed rules, spawning one notifier per destination.
+    ///
+    /// Disabled rules are skipped, and destinations only used by shadow rules
+    /// get no notifier. Must be called within a Tokio runtime.
+    pub fn new(file: RulesFile) -> Self {
+        let rules: Vec<Rule> = file
+            .rules
+            .into_iter()
+            .filter(|rule| rule.enabled)
+            .map(Rule::new)
+            .collect();
+
+        let mut bridges: HashMap<Destination, Vec<Destination>> = HashMap::new();
+        for bridge in file.bridges {
+            bridges.entry(bridge.from).or_default().extend(bridge.to);
+        }
+
+        let mut notifiers = HashMap::new();
+        for destination in rules
+            .iter()
+            .filter(|r| !r.shadow)
+            .flat_map(|r| &r.destinations)
+            .chain(bridges.values().flatten())
+        {
+            if notifiers.contains_key(destination) {
+                continue;
+            }
+            match destination
+                .delivery_config()
+                .and_then(DestinationNotifier::new)
+            {
+                Ok(notifier) => {
+                    notifiers.insert(destination.clone(), notifier);
+                }
+                Err(e) => log::warn!("Skipping destination: {e}"),
+            }
+        }
+
+        Self {
+            rules,
+            bridges,
+            notifiers,
+        }
+    }
+
+    /// Loads rules from a TOML file, spawning one notifier per destination.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, RulesError> {
+        RulesFile::load(path).map(Self::new)
+    }
+
+    /// Returns the number of evaluated rules, including shadow rules.
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Returns the number of shadow rules.
+    pub fn shadow_len(&self) -> usize {
+        self.rules.iter().filter(|rule| rule.shadow).count()
+    }
+
+    /// Returns the names of the rules whose condition matches an event,
+    /// without delivering it or advancing their sequences.
+    pub fn matching_names(&self, event: &SwapEvent) -> Vec<&str> {
+        self.matching(event)
+            .map(|rule| rule.name.as_str())
+            .collect()
+    }
+
+    /// Returns the rules matching an event.
+    fn matching<'a>(&'a self, event: &'a SwapEvent) -> impl Iterator<Item = &'a Rule> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.condition.matches(event))
+    }
+
+    /// Delivers an event to the destinations of every matching rule, and
+    /// through bridges to their mirrors.
+    ///
+    /// A destination reached by several matching rules or bridges receives the
+    /// event once. Rules with an `emit` action or a completed sequence deliver
+    /// their custom event instead, which is then routed to the rules selecting
+    /// it by name. Shadow rules only log and count what they would deliver, and
+    /// their custom events are not routed.
+    pub fn dispatch(&self, event: &SwapEvent) {
+        let mut delivered = HashSet::new();
+        let mut emitted = Vec::new();
+        for rule in &self.rules {
+            let Some(fired) = rule.fire(event) else {
+                continue;
+            };
+            if rule.shadow {
+                rule.record_shadow(&fired);
+                continue;
+            }
+            log::debug!("Rule '{}' matched {}", rule.name, event.signature);
+            if fired.custom.is_some() {
+                let mut custom_delivered = HashSet::new();
+                for destination in &rule.destinations {
+                    self.deliver(destination, &fired, &rule.name, &mut custom_delivered);
+                }
+                emitted.push((fired, custom_delivered));
+            } else {
+                for destination in &rule.destinations {
+                    self.deliver(destination, event, &rule.name, &mut delivered);
+                }
+            }
+        }
+
+        // Custom events are not re-emitted, which keeps routing finite
+        for (custom, mut delivered) in emitted {
+            for rule in self.matching(&custom) {
+                if !rule.selects_custom || rule.emit.is_some() || rule.sequence.is_some() {
+                    continue;
+                }
+                if rule.shadow {
+                    rule.record_shadow(&custom);
+                    continue;
+                }
+                log::debug!(
+                    "Rule '{}' matched custom event {}",
+                    rule.name,
+                    custom.signature
+                );
+                for destination in &rule.destinations {
+                    self.deliver(destination, &custom, &rule.name, &mut delivered);
+                }
+            }
+        }
+    }
+
+    /// Queues an event for a destination and the destinations bridged from it.
+    fn deliver<'a>(
+        &'a self,
+        destination: &'a Destination,
+        event: &SwapEvent,
+        rule: &str,
+        delivered: &mut HashSet<&'a Destination>,
+    ) {
+        if !delivered.insert(destination) {
+            return;
+        }
+        if let Some(notifier) = self.notifiers.get(destination) {
+            if let Err(e) = notifier.try_send(event.clone()) {
+                log::warn!("Failed to queue event for rule '{rule}' ({destination}): {e}");
+            }
+        }
+        for mirror in self.bridges.get(destination).into_iter().flatten() {
+            self.deliver(mirror, event, rule, delivered);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{EventType, Protocol, TokenInfo},
+    };
+
+    const RULES: &str = r#"
+        [[rules]]
+        name = "clmm"
+        when = { protocol = "clmm" }
+        destinations = [{ type = "webhook", url = "https://a.example" }]
+
+        [[rules]]
+        name = "whales"
+        when = { maker_in = ["whale"] }
+        destinations = [
+            { type = "webhook", url = "https://a.example" },
+            { type = "webhook", url = "https://b.example" },
+        ]
+
+        [[rules]]
+        name = "disabled"
+        enabled = false
+        when = { protocol = "cpmm" }
+        destinations = [{ type = "webhook", url = "https://c.example" }]
+    "#;
+
+    fn test_event(protocol: Protocol, maker: &str) -> SwapEvent {
+        SwapEvent::builder()
+            .protocol(protocol)
+            .signature("sig")
+            .pool("pool")
+            .input_token(TokenInfo::new("mint", 1))
+            .maker(maker)
+            .build()
+    }
+
+    #[test]
+    fn test_parse_rules_file() {
+        let file = RulesFile::parse(RULES).unwrap();
+        assert_eq!(file.rules.len(), 3);
+        assert!(!file.rules[2].enabled);
+        assert_eq!(
+            file.rules[0].destinations,
+            vec![Destination::Webhook {
+                url: "https://a.example".to_string(),
+                schema_version: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_example_rules_file_is_valid() {
+        let file = RulesFile::parse(include_str!("../../rules.example.toml")).unwrap();
+        assert_eq!(file.rules.len(), 8);
+        assert!(file.run_fixtures().iter().all(FixtureOutcome::passed));
+        assert_eq!(file.bridges.len(), 1);
+    }
+
+    #[test]
+    fn test_validate_bridges() {
+        let rule = r#"
+            [[rules]]
+            name = "a"
+            when = { protocol = "clmm" }
+            destinations = [{ type = "discord", url = "https://discord.example" }]
+        "#;
+        let bridge = |bridge: &str| format!("{rule}\n[[bridges]]\n{bridge}");
+
+        let file = RulesFile::parse(&bridge(
+            r#"
+            from = { type = "discord", url = "https://discord.example" }
+            to = [{ type = "telegram", chat_id = -100, bot_token = "token" }]
+            "#,
+        ))
+        .unwrap();
+        assert_eq!(
+            file.bridges[0].to[0].webhook_config().unwrap().format,
+            WebhookFormat::Telegram {
+                chat_id: -100,
+                template: None,
+            }
+        );
+        assert_eq!(file.bridges[0].to[0].to_string(), "telegram:-100");
+
+        for invalid in [
+            // Mirrors a destination that never receives events
+            r#"
+            from = { type = "webhook", url = "https://other.example" }
+            to = [{ type = "webhook", url = "https://b.example" }]
+            "#,
+            // Mirrors to itself
+            r#"
+            from = { type = "discord", url = "https://discord.example" }
+            to = [{ type = "discord", url = "https://discord.example" }]
+            "#,
+            // No mirrors
+            r#"
+            from = { type = "discord", url = "https://discord.example" }
+            to = []
+            "#,
+        ] {
+            assert!(matches!(
+                RulesFile::parse(&bridge(invalid)),
+                Err(RulesError::Invalid(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_validate_email_and_x_destinations() {
+        let rule = |destination: &str| {
+            format!(
+                "[[rules]]\nname = \"drains\"\nwhen = {{ event_type = \"liquidity_drain\" }}\n\
+                 destinations = [{destination}]"
+            )
+        };
+        for (destination, error) in [
+            (r#"{ type = "email", to = [] }"#, "no recipients"),
+            (
+                r#"{ type = "email", to = ["not an address"] }"#,
+                "invalid email address",
+            ),
+            (
+                r#"{ type = "email", to = ["a@example.com"], subject = "{volume_24h}" }"#,
+                "unknown placeholder",
+            ),
+            (r#"{ type = "x" }"#, "needs X_API_KEY"),
+        ] {
+            match RulesFile::parse(&rule(destination)) {
+                Err(RulesError::Invalid(e)) => assert!(e.contains(error), "{e}"),
+                other => panic!("expected invalid rules, got {other:?}"),
+            }
+        }
+
+        let destination = Destination::Email {
+            to: vec!["a@example.com".to_string(), "b@example.com".to_string()],
+            subject: None,
+            template: None,
+        };
+        assert_eq!(destination.to_string(), "email:a@example.com,b@example.com");
+        assert_eq!(Destination::X { template: None }.to_string(), "x:default");
+    }
+
+    #[test]
+    fn test_validate_rejects_duplicates_and_missing_destinations() {
+        let duplicate = r#"
+            [[rules]]
+            name = "a"
+            when = { protocol = "clmm" }
+            destinations = [{ type = "webhook", url = "https://a.example" }]
+            [[rules]]
+            name = "a"
+            when = { protocol = "cpmm" }
+            destinations = [{ type = "webhook", url = "https://a.example" }]
+        "#;
+        assert!(matches!(
+            RulesFile::parse(duplicate),
+            Err(RulesError::Invalid(_))
+        ));
+
+        let no_destination = r#"
+            [[rules]]
+            name = "a"
+            when = { protocol = "clmm" }
+            destinations = []
+        "#;
+        assert!(matches!(
+            RulesFile::parse(no_destination),
+            Err(RulesError::Invalid(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_engine_matches_enabled_rules() {
+        let engine = RuleEngine::new(RulesFile::parse(RULES).unwrap());
+        assert_eq!(engine.len(), 2);
+        assert_eq!(engine.notifiers.len(), 2);
+
+        let event = test_event(Protocol::Clmm, "whale");
+        assert_eq!(engine.matching_names(&event), vec!["clmm", "whales"]);
+
+        let event = test_event(Protocol::Cpmm, "someone");
+        assert_eq!(engine.matching(&event).count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_engine_delivers_to_bridged_destinations_once() {
+        let file = RulesFile::parse(&format!(
+            r#"{RULES}
+            [[bridges]]
+            from = {{ type = "webhook", url = "https://a.example" }}
+            to = [{{ type = "discord", url = "https://d.example" }}]
+
+            [[bridges]]
+            from = {{ type = "discord", url = "https://d.example" }}
+            to = [{{ type = "webhook", url = "https://b.example" }}]
+            "#
+        ))
+        .unwrap();
+        let engine = RuleEngine::new(file);
+        assert_eq!(engine.notifiers.len(), 3);
+
+        let event = test_event(Protocol::Clmm, "whale");
+        let mut delivered = HashSet::new();
+        for rule in engine.matching(&event) {
+            for destination in &rule.destinations {
+                engine.deliver(destination, &event, &rule.name, &mut delivered);
+            }
+        }
+        let mut delivered: Vec<String> = delivered.iter().map(|d| d.to_string()).collect();
+        delivered.sort();
+        assert_eq!(
+            delivered,
+            vec![
+                "discord:https://d.example",
+                "webhook:https://a.example",
+                "webhook:https://b.example",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sequence_rules() {
+        let file = RulesFile::parse(
+            r#"
+            [[rules]]
+            name = "buy-then-pull"
+            when = { event_type = "swap" }
+            then = { event_type = "remove_liquidity" }
+            within_secs = 60
+            destinations = [{ type = "webhook", url = "https://a.example" }]
+            "#,
+        )
+        .unwrap();
+        let engine = RuleEngine::new(file);
+        let rule = &engine.rules[0];
+
+        let mut swap = test_event(Protocol::Cpmm, "maker");
+        swap.timestamp = Some(1_000);
+        let mut pull = swap.clone();
+        pull.event_type = EventType::RemoveLiquidity;
+        pull.timestamp = Some(1_030);
+
+        assert!(rule.fire(&swap).is_none());
+        let combined = rule.fire(&pull).unwrap();
+        assert_eq!(combined.custom.unwrap().name, "buy-then-pull");
+        assert!(rule.fire(&pull).is_none());
+
+        for (toml, error) in [
+            ("within_secs = 60", "without a then condition"),
+            (
+                "then = { event_type = \"swap\" }\nwithin_secs = 0",
+                "zero within_secs",
+            ),
+        ] {
+            let rules = format!(
+                "[[rules]]\nname = \"r\"\nwhen = {{ protocol = \"clmm\" }}\n{toml}\n\
+                 destinations = [{{ type = \"webhook\", url = \"https://a.example\" }}]"
+            );
+            match RulesFile::parse(&rules) {
+                Err(RulesError::Invalid(e)) => assert!(e.contains(error), "{e}"),
+                other => panic!("expected invalid rules, got {other:?}"),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shadow_rules_are_not_delivered() {
+        let file = RulesFile::parse(
+            r#"
+            [[rules]]
+            name = "clmm"
+            when = { protocol = "clmm" }
+            destinations = [{ type = "webhook", url = "https://a.example" }]
+
+            [[rules]]
+            name = "all-clmm: aggressive"
+            mode = "shadow"
+            when = { protocol = "clmm" }
+            destinations = [{ type = "webhook", url = "https://b.example" }]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(file.rules[0].mode, RuleMode::Active);
+        assert_eq!(file.rules[1].mode, RuleMode::Shadow);
+
+        let engine = RuleEngine::new(file);
+        assert_eq!((engine.len(), engine.shadow_len()), (2, 1));
+        assert_eq!(engine.notifiers.len(), 1);
+        assert!(engine.rules[1]
+            .fire(&test_event(Protocol::Clmm, "maker"))
+            .is_some());
+        engine.dispatch(&test_event(Protocol::Clmm, "maker"));
+        assert_eq!(metric_name("all-clmm: aggressive"), "all-clmm__aggressive");
+    }
+
+    #[test]
+    fn test_run_fixtures() {
+        let file = RulesFile::parse(
+            r#"
+            [[rules]]
+            name = "whales"
+            when = { all = [{ maker_in = ["whale"] }, { usd_above = 1000 }] }
+            destinations = [{ type = "webhook", url = "https://a.example" }]
+
+            [[rules.fixtures]]
+            name = "large whale swap"
+            event = '{"maker": "whale", "input_token": {"mint": "m", "amount_raw": 1, "amount_usd": 5000}}'
+            matches = true
+
+            [[rules.fixtures]]
+            event = '{"maker": "whale"}'
+            matches = true
+
+            [[rules]]
+            name = "buy-then-pull"
+            enabled = false
+            when = { event_type = "swap" }
+            then = { event_type = "remove_liquidity" }
+            destinations = [{ type = "webhook", url = "https://a.example" }]
+
+            [[rules.fixtures]]
+            after = ['{"timestamp": 100}']
+            event = '{"event_type": "remove_liquidity", "timestamp": 160}'
+            matches = true
+
+            [[rules.fixtures]]
+            event = '{"event_type": "remove_liquidity", "timestamp": 160}'
+            matches = false
+            "#,
+        )
+        .unwrap();
+
+        let outcomes = file.run_fixtures();
+        let summary: Vec<(&str, &str, bool)> = outcomes
+            .iter()
+            .map(|o| (o.rule.as_str(), o.fixture.as_str(), o.passed()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("whales", "large whale swap", true),
+                ("whales", "#2", false),
+                ("buy-then-pull", "#1", true),
+                ("buy-then-pull", "#2", true),
+            ]
+        );
+
+        let invalid = RulesFile::parse(
+            r#"
+            [[rules]]
+            name = "r"
+            when = { protocol = "clmm" }
+            destinations = [{ type = "webhook", url = "https://a.example" }]
+            fixtures = [{ event = '{"protocol": 1}', matches = true }]
+            "#,
+        );
+        assert!(matches!(invalid, Err(RulesError::Invalid(e)) if e.contains("fixture '#1'")));
+    }
+
+    #[tokio::test]
+    async fn test_emit_and_route_custom_events() {
+        let file = RulesFile::parse(
+            r#"
+            [[rules]]
+            name = "whale-exits"
+            when = { maker_in = ["whale"] }
+            emit = { name = "WhaleExit", description = "{maker} left {pool}" }
+            destinations = [{ type = "webhook", url = "https://a.example" }]
+
+            [[rules]]
+            name = "whale-exit-archive"
+            when = { custom = "WhaleExit" }
+            destinations = [{ type = "webhook", url = "https://b.example" }]
+            "#,
+        )
+        .unwrap();
+        assert!(file.rules[0].emit.is_some());
+
+        let engine = RuleEngine::new(file);
+        let event = test_event(Protocol::Clmm, "whale");
+        assert_eq!(
+            engine
+                .matching(&event)
+                .map(|rule| rule.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["whale-exits"]
+        );
+
+        let custom = engine.rules[0]
+            .emit
+            .as_ref()
+            .unwrap()
+            .derive(&event, "whale-exits");
+        assert_eq!(
+            custom.custom.as_ref().unwrap().description,
+            "whale left pool"
+        );
+        let routed: Vec<&str> = engine
+            .matching(&custom)
+            .filter(|rule| rule.selects_custom)
+            .map(|rule| rule.name.as_str())
+            .collect();
+        assert_eq!(routed, vec!["whale-exit-archive"]);
+        assert!(custom
+            .format(crate::output::OutputFormat::Text)
+            .starts_with("⚡ WhaleExit [CLMM]\n📝 whale left pool"));
+
+        assert!(matches!(
+            RulesFile::parse(
+                r#"
+                [[rules]]
+                name = "bad"
+                when = { protocol = "clmm" }
+                emit = { name = "X", description = "{unknown}" }
+                destinations = [{ type = "webhook", url = "https://a.example" }]
+                "#
+            ),
+            Err(RulesError::Invalid(_))
+        ));
+    }
+}
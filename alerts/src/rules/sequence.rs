@@ -0,0 +1,185 @@
+//! Sliding-window correlation of events on the same pool.
+//!
+//! A sequence rule fires when an event matching its `when` condition is
+//! followed, on the same pool, by an event matching `then` within
+//! `within_secs`:
+//!
+//! ```toml
+//! [[rules]]
+//! name = "buy-then-pull"
+//! when = { all = [{ event_type = "swap" }, { usd_above = 10000 }] }
+//! then = { event_type = "remove_liquidity" }
+//! within_secs = 600
+//! ```
+
+use {
+    super::Condition,
+    crate::{
+        output::{CustomEvent, EventType, SwapEvent},
+        server::unix_now,
+    },
+    std::{collections::HashMap, sync::Mutex},
+};
+
+/// Window used when a sequence rule does not set `within_secs`.
+pub const DEFAULT_WINDOW_SECS: u64 = 600;
+
+/// Pools with a pending first event above which expired entries are pruned.
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// The first event of a sequence, waiting for its follow-up.
+#[derive(Debug, Clone)]
+struct Pending {
+    timestamp: i64,
+    signature: String,
+    event_type: EventType,
+}
+
+/// Windowed state machine correlating two conditions per pool.
+pub struct Sequence {
+    then: Condition,
+    window: i64,
+    /// Latest first event of each pool
+    pending: Mutex<HashMap<String, Pending>>,
+}
+
+impl Sequence {
+    /// Creates a sequence completed by `then` within `within_secs` of the first event.
+    pub fn new(then: Condition, within_secs: u64) -> Self {
+        Self {
+            then,
+            window: i64::try_from(within_secs).unwrap_or(i64::MAX),
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Feeds an event through the sequence.
+    ///
+    /// Returns the combined alert when the event completes a sequence started
+    /// on its pool within the window; the pool then waits for a new first
+    /// event. An event matching `first` (re)starts the window of its pool.
+    /// Events are timed by block time, or the current time if unknown.
+    pub fn observe(&self, first: &Condition, event: &SwapEvent, rule: &str) -> Option<SwapEvent> {
+        let completes = self.then.matches(event);
+        let starts = first.matches(event);
+        if !completes && !starts {
+            return None;
+        }
+
+        let now = event.timestamp.unwrap_or_else(unix_now);
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+
+        if completes {
+            if let Some(started) = pending.remove(&event.pool) {
+                let elapsed = now - started.timestamp;
+                if (0..=self.window).contains(&elapsed) {
+                    return Some(combine(event, &started, elapsed, rule));
+                }
+            }
+        }
+
+        if starts {
+            if pending.len() >= PRUNE_THRESHOLD {
+                pending.retain(|_, started| now - started.timestamp <= self.window);
+            }
+            pending.insert(
+                event.pool.clone(),
+                Pending {
+                    timestamp: now,
+                    signature: event.signature.clone(),
+                    event_type: event.event_type,
+                },
+            );
+        }
+        None
+    }
+}
+
+/// Builds the combined alert from the completing event.
+fn combine(event: &SwapEvent, started: &Pending, elapsed: i64, rule: &str) -> SwapEvent {
+    let short = |signature: &str| signature.get(..8).unwrap_or(signature).to_string();
+    let mut combined = event.clone();
+    combined.event_type = EventType::Custom;
+    combined.custom = Some(CustomEvent {
+        name: rule.to_string(),
+        rule: rule.to_string(),
+        description: format!(
+            "{} {} followed by {} {} after {elapsed}s",
+            started.event_type,
+            short(&started.signature),
+            event.event_type,
+            short(&event.signature)
+        ),
+        source_type: event.event_type,
+    });
+    combined
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::output::Protocol};
+
+    fn event(event_type: EventType, pool: &str, signature: &str, timestamp: i64) -> SwapEvent {
+        SwapEvent::builder()
+            .event_type(event_type)
+            .protocol(Protocol::Cpmm)
+            .signature(signature)
+            .pool(pool)
+            .timestamp(timestamp)
+            .build()
+    }
+
+    #[test]
+    fn test_sequence_within_window() {
+        let first = Condition::EventType(EventType::Swap);
+        let sequence = Sequence::new(Condition::EventType(EventType::RemoveLiquidity), 600);
+        let observe = |event: &SwapEvent| sequence.observe(&first, event, "buy-then-pull");
+
+        // Follow-up without a first event, and on another pool
+        assert!(observe(&event(EventType::RemoveLiquidity, "A", "r0", 0)).is_none());
+        assert!(observe(&event(EventType::Swap, "A", "swapsig1", 100)).is_none());
+        assert!(observe(&event(EventType::RemoveLiquidity, "B", "r1", 200)).is_none());
+
+        let combined = observe(&event(EventType::RemoveLiquidity, "A", "pullsig1", 400)).unwrap();
+        assert_eq!(combined.event_type, EventType::Custom);
+        assert_eq!(combined.signature, "pullsig1");
+        let custom = combined.custom.unwrap();
+        assert_eq!(custom.name, "buy-then-pull");
+        assert_eq!(custom.source_type, EventType::RemoveLiquidity);
+        assert_eq!(
+            custom.description,
+            "SWAP swapsig1 followed by REMOVE_LP pullsig1 after 300s"
+        );
+
+        // The sequence is consumed
+        assert!(observe(&event(EventType::RemoveLiquidity, "A", "r2", 450)).is_none());
+    }
+
+    #[test]
+    fn test_sequence_expires() {
+        let first = Condition::EventType(EventType::Swap);
+        let sequence = Sequence::new(Condition::EventType(EventType::RemoveLiquidity), 60);
+
+        assert!(sequence
+            .observe(&first, &event(EventType::Swap, "A", "s", 0), "r")
+            .is_none());
+        assert!(sequence
+            .observe(
+                &first,
+                &event(EventType::RemoveLiquidity, "A", "l", 61),
+                "r"
+            )
+            .is_none());
+
+        // A later first event restarts the window
+        sequence.observe(&first, &event(EventType::Swap, "A", "s", 100), "r");
+        sequence.observe(&first, &event(EventType::Swap, "A", "s", 150), "r");
+        assert!(sequence
+            .observe(
+                &first,
+                &event(EventType::RemoveLiquidity, "A", "l", 200),
+                "r"
+            )
+            .is_some());
+    }
+}
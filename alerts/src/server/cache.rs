@@ -0,0 +1,282 @@
+//! Response cache of the query API.
+//!
+//! Dashboards poll `/swaps`, `/pools/{pool}/swaps`, `/candles`, and the
+//! Grafana query endpoint far more often than the data behind them changes.
+//! Responses are cached by route and query for a per-route TTL, so polling
+//! clients share one computation instead of each scanning the recent swaps
+//! buffer (locked by ingest) or querying the database.
+//!
+//! Each entry is tagged with the pools and mints it covers, and a published
+//! event drops the entries of its pool and mints, so responses scoped to a
+//! pool or token never outlive a change to them. Entries covering all swaps
+//! (e.g., `/swaps` without a token) are not dropped per event and expire
+//! with their TTL.
+
+use {
+    crate::{clock, output::SwapEvent},
+    axum::body::Bytes,
+    lru::LruCache,
+    std::{
+        collections::{HashMap, HashSet},
+        env,
+        num::NonZeroUsize,
+        sync::Mutex,
+        time::{Duration, Instant},
+    },
+};
+
+/// Configuration for the response cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheConfig {
+    /// TTL of recent swap responses
+    pub ttl: Duration,
+    /// TTL of candle and Grafana responses
+    pub candles_ttl: Duration,
+    /// Maximum cached responses; 0 disables the cache
+    pub max_entries: usize,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_millis(1_000),
+            candles_ttl: Duration::from_millis(10_000),
+            max_entries: 1_024,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Creates cache configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `SERVER_CACHE_TTL_MS` - Optional: TTL of `/swaps` and `/pools/{pool}/swaps`
+    ///   responses (default: 1000)
+    /// - `SERVER_CACHE_CANDLES_TTL_MS` - Optional: TTL of `/candles` and Grafana query
+    ///   responses (default: 10000)
+    /// - `SERVER_CACHE_MAX_ENTRIES` - Optional: Cached responses, 0 disables (default: 1024)
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let var = |name| env::var(name).ok().and_then(|v| v.trim().parse().ok());
+        Self {
+            ttl: var("SERVER_CACHE_TTL_MS").map_or(defaults.ttl, Duration::from_millis),
+            candles_ttl: var("SERVER_CACHE_CANDLES_TTL_MS")
+                .map_or(defaults.candles_ttl, Duration::from_millis),
+            max_entries: var("SERVER_CACHE_MAX_ENTRIES")
+                .map_or(defaults.max_entries, |n: u64| n as usize),
+        }
+    }
+}
+
+/// Returns the tag of a pool.
+pub fn pool_tag(pool: &str) -> String {
+    format!("pool:{pool}")
+}
+
+/// Returns the tag of a token mint.
+pub fn mint_tag(mint: &str) -> String {
+    format!("mint:{mint}")
+}
+
+/// A cached response body.
+struct Entry {
+    body: Bytes,
+    expires: Instant,
+    tags: Vec<String>,
+}
+
+/// Cached entries and the keys of each tag.
+struct Inner {
+    entries: LruCache<String, Entry>,
+    tags: HashMap<String, HashSet<String>>,
+}
+
+impl Inner {
+    /// Removes a key from the index of its tags.
+    fn untag(&mut self, key: &str, tags: &[String]) {
+        for tag in tags {
+            if let Some(keys) = self.tags.get_mut(tag) {
+                keys.remove(key);
+                if keys.is_empty() {
+                    self.tags.remove(tag);
+                }
+            }
+        }
+    }
+}
+
+/// Serialized API responses, least recently used evicted first.
+pub struct ResponseCache {
+    config: CacheConfig,
+    inner: Option<Mutex<Inner>>,
+}
+
+impl Default for ResponseCache {
+    /// A disabled cache.
+    fn default() -> Self {
+        Self::new(CacheConfig {
+            max_entries: 0,
+            ..CacheConfig::default()
+        })
+    }
+}
+
+impl ResponseCache {
+    /// Creates a cache; `max_entries == 0` disables it.
+    pub fn new(config: CacheConfig) -> Self {
+        let inner = NonZeroUsize::new(config.max_entries).map(|capacity| {
+            Mutex::new(Inner {
+                entries: LruCache::new(capacity),
+                tags: HashMap::new(),
+            })
+        });
+        Self { config, inner }
+    }
+
+    /// Returns the cache configuration.
+    pub fn config(&self) -> &CacheConfig {
+        &self.config
+    }
+
+    /// Returns a cached response body that has not expired.
+    pub fn get(&self, key: &str) -> Option<Bytes> {
+        let mut inner = self
+            .inner
+            .as_ref()?
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let entry = inner.entries.get(key)?;
+        if entry.expires > clock::now() {
+            return Some(entry.body.clone());
+        }
+        if let Some(entry) = inner.entries.pop(key) {
+            inner.untag(key, &entry.tags);
+        }
+        None
+    }
+
+    /// Caches a response body for `ttl`, tagged with the pools and mints it covers.
+    ///
+    /// A zero TTL is not cached.
+    pub fn insert(&self, key: String, body: Bytes, ttl: Duration, tags: Vec<String>) {
+        let Some(ref inner) = self.inner else {
+            return;
+        };
+        if ttl.is_zero() {
+            return;
+        }
+        let mut inner = inner.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(old) = inner.entries.pop(&key) {
+            inner.untag(&key, &old.tags);
+        }
+        for tag in &tags {
+            inner
+                .tags
+                .entry(tag.clone())
+                .or_default()
+                .insert(key.clone());
+        }
+        let entry = Entry {
+            body,
+            expires: clock::now() + ttl,
+            tags,
+        };
+        if let Some((evicted, old)) = inner.entries.push(key, entry) {
+            inner.untag(&evicted, &old.tags);
+        }
+    }
+
+    /// Drops the entries covering an event's pool or mints.
+    pub fn invalidate(&self, event: &SwapEvent) {
+        let Some(ref inner) = self.inner else {
+            return;
+        };
+        let mut inner = inner.lock().unwrap_or_else(|e| e.into_inner());
+        if inner.tags.is_empty() {
+            return;
+        }
+
+        let mints = [&event.input_token, &event.output_token]
+            .into_iter()
+            .flatten()
+            .map(|token| mint_tag(&token.mint));
+        for tag in std::iter::once(pool_tag(&event.pool)).chain(mints) {
+            let Some(keys) = inner.tags.remove(&tag) else {
+                continue;
+            };
+            for key in keys {
+                if let Some(entry) = inner.entries.pop(&key) {
+                    inner.untag(&key, &entry.tags);
+                }
+            }
+        }
+    }
+
+    /// Drops every entry (e.g., after address labels changed).
+    pub fn clear(&self) {
+        if let Some(ref inner) = self.inner {
+            let mut inner = inner.lock().unwrap_or_else(|e| e.into_inner());
+            inner.entries.clear();
+            inner.tags.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{Protocol, TokenInfo},
+    };
+
+    #[test]
+    fn test_cache_expires_and_invalidates_by_tag() {
+        let cache = ResponseCache::new(CacheConfig::default());
+        let ttl = Duration::from_secs(60);
+        cache.insert("all".into(), Bytes::from("1"), ttl, Vec::new());
+        cache.insert(
+            "pool".into(),
+            Bytes::from("2"),
+            ttl,
+            vec![pool_tag("PoolA")],
+        );
+        cache.insert(
+            "token".into(),
+            Bytes::from("3"),
+            ttl,
+            vec![mint_tag("MemeMint")],
+        );
+        cache.insert(
+            "other".into(),
+            Bytes::from("4"),
+            ttl,
+            vec![pool_tag("PoolB")],
+        );
+        cache.insert(
+            "expired".into(),
+            Bytes::from("5"),
+            Duration::from_nanos(1),
+            Vec::new(),
+        );
+        std::thread::sleep(Duration::from_millis(1));
+        assert_eq!(cache.get("pool"), Some(Bytes::from("2")));
+        assert_eq!(cache.get("expired"), None);
+
+        let event = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("PoolA")
+            .input_token(TokenInfo::new("MemeMint", 1))
+            .build();
+        cache.invalidate(&event);
+        assert_eq!(cache.get("pool"), None);
+        assert_eq!(cache.get("token"), None);
+        assert_eq!(cache.get("all"), Some(Bytes::from("1")));
+        assert_eq!(cache.get("other"), Some(Bytes::from("4")));
+
+        let disabled = ResponseCache::default();
+        disabled.insert("all".into(), Bytes::from("1"), ttl, Vec::new());
+        assert_eq!(disabled.get("all"), None);
+    }
+}
@@ -0,0 +1,520 @@
+//! Grafana JSON datasource endpoints.
+//!
+//! Implements the contract of the simple-JSON and Infinity datasource plugins
+//! so Grafana can chart tracked pools and alert counts without a separate
+//! database. Point the datasource at `http://<SERVER_ADDR>/grafana`:
+//!
+//! - `GET /grafana/` - Connection test
+//! - `POST /grafana/search` - Metric names containing `{"target": "<text>"}`
+//! - `POST /grafana/query` - Time series of the requested targets
+//!
+//! Metrics are named `<metric>:<address>`:
+//!
+//! - `price:<pool>`, `volume:<pool>`, `trades:<pool>` - Candle close, USD
+//!   volume, and swap count at the finest candle interval fitting the panel
+//!   (requires storage)
+//! - `alerts:<mint>`, `token_volume:<mint>` - Hourly alerted swaps and USD
+//!   volume of a token over the last 24 hours
+
+use {
+    super::{
+        cache::{mint_tag, pool_tag},
+        cached, AppState, MAX_CANDLES,
+    },
+    crate::candles::Interval,
+    axum::{body::Bytes, extract::State, http::StatusCode, response::Response, Json},
+    serde::{Deserialize, Serialize},
+    std::fmt,
+};
+
+/// Pools and tokens listed by one `/search` request.
+const SEARCH_LIMIT: usize = 100;
+
+/// Series kind served by the datasource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Metric {
+    /// Candle close price of a pool
+    Price,
+    /// Candle USD volume of a pool
+    Volume,
+    /// Candle swap count of a pool
+    Trades,
+    /// Hourly alerted swaps of a token
+    Alerts,
+    /// Hourly USD volume of a token
+    TokenVolume,
+}
+
+impl Metric {
+    /// Metrics of a pool, backed by candles.
+    const POOL: [Metric; 3] = [Self::Price, Self::Volume, Self::Trades];
+
+    /// Metrics of a token, backed by the per-token stats.
+    const TOKEN: [Metric; 2] = [Self::Alerts, Self::TokenVolume];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Price => "price",
+            Self::Volume => "volume",
+            Self::Trades => "trades",
+            Self::Alerts => "alerts",
+            Self::TokenVolume => "token_volume",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        Self::POOL
+            .into_iter()
+            .chain(Self::TOKEN)
+            .find(|metric| metric.name() == name)
+    }
+}
+
+/// A metric of one pool or token, e.g. `price:<pool>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Target {
+    metric: Metric,
+    address: String,
+}
+
+impl Target {
+    fn parse(name: &str) -> Result<Self, String> {
+        let name = name.trim();
+        let (metric, address) = name
+            .split_once(':')
+            .ok_or_else(|| format!("target '{name}' must be '<metric>:<address>'"))?;
+        let metric = Metric::parse(metric).ok_or_else(|| format!("unknown metric '{metric}'"))?;
+        if address.is_empty() {
+            return Err(format!("target '{name}' has no address"));
+        }
+        Ok(Self {
+            metric,
+            address: address.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.metric.name(), self.address)
+    }
+}
+
+/// Body of `/search`.
+#[derive(Debug, Default, Deserialize)]
+struct SearchRequest {
+    /// Text typed in the query editor
+    #[serde(default)]
+    target: String,
+}
+
+/// Body of `/query`; fields Grafana sends beyond these are ignored.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct QueryRequest {
+    range: TimeRange,
+    /// Panel resolution in milliseconds
+    #[serde(default)]
+    interval_ms: i64,
+    /// Maximum points the panel can show
+    #[serde(default)]
+    max_data_points: Option<usize>,
+    targets: Vec<QueryTarget>,
+}
+
+/// Dashboard time range as RFC 3339 timestamps.
+#[derive(Debug, Deserialize)]
+struct TimeRange {
+    from: String,
+    to: String,
+}
+
+impl TimeRange {
+    /// Returns `(from, to)` in Unix milliseconds.
+    fn resolve(&self) -> Result<(i64, i64), String> {
+        let parse = |value: &str| {
+            parse_time(value).ok_or_else(|| format!("invalid time '{value}' in range"))
+        };
+        let (from, to) = (parse(&self.from)?, parse(&self.to)?);
+        if from > to {
+            return Err("'from' must not be after 'to'".to_string());
+        }
+        Ok((from, to))
+    }
+}
+
+/// One query of a panel.
+#[derive(Debug, Deserialize)]
+struct QueryTarget {
+    #[serde(default)]
+    target: Option<String>,
+    /// Set for queries disabled in the editor
+    #[serde(default)]
+    hide: bool,
+}
+
+/// One time series of the `/query` response.
+#[derive(Debug, PartialEq, Serialize)]
+struct Series {
+    target: String,
+    /// `[value, unix_ms]` pairs, oldest first
+    datapoints: Vec<(f64, i64)>,
+}
+
+type ApiError = (StatusCode, String);
+
+fn bad_request(message: String) -> ApiError {
+    (StatusCode::BAD_REQUEST, message)
+}
+
+pub(super) async fn test_handler() -> StatusCode {
+    StatusCode::OK
+}
+
+pub(super) async fn search_handler(
+    State(state): State<AppState>,
+    body: Bytes,
+) -> Result<Json<Vec<String>>, ApiError> {
+    // The simple-JSON plugin may send an empty body
+    let request: SearchRequest = if body.is_empty() {
+        SearchRequest::default()
+    } else {
+        serde_json::from_slice(&body).map_err(|e| bad_request(format!("invalid search: {e}")))?
+    };
+
+    let pools = match state.candles {
+        Some(_) => state.hub.recent().pools(SEARCH_LIMIT),
+        None => Vec::new(),
+    };
+    let mints: Vec<String> = state
+        .stats
+        .search("", SEARCH_LIMIT)
+        .into_iter()
+        .map(|stats| stats.mint)
+        .collect();
+    Ok(Json(metric_names(&pools, &mints, &request.target)))
+}
+
+/// Lists the metrics of the given pools and tokens whose name contains `query`.
+fn metric_names(pools: &[String], mints: &[String], query: &str) -> Vec<String> {
+    let query = query.trim().to_lowercase();
+    let pool_targets = pools.iter().flat_map(|pool| {
+        Metric::POOL.into_iter().map(|metric| Target {
+            metric,
+            address: pool.clone(),
+        })
+    });
+    let token_targets = mints.iter().flat_map(|mint| {
+        Metric::TOKEN.into_iter().map(|metric| Target {
+            metric,
+            address: mint.clone(),
+        })
+    });
+    pool_targets
+        .chain(token_targets)
+        .map(|target| target.to_string())
+        .filter(|name| name.to_lowercase().contains(&query))
+        .collect()
+}
+
+/// Serves `/query` from the response cache; identical queries of several
+/// dashboard viewers share one result until an event touches its pools or tokens.
+pub(super) async fn query_handler(
+    State(state): State<AppState>,
+    body: Bytes,
+) -> Result<Response, ApiError> {
+    let request: QueryRequest =
+        serde_json::from_slice(&body).map_err(|e| bad_request(format!("invalid query: {e}")))?;
+    let tags = request
+        .targets
+        .iter()
+        .filter_map(|query| Target::parse(query.target.as_deref()?).ok())
+        .map(|target| {
+            if Metric::POOL.contains(&target.metric) {
+                pool_tag(&target.address)
+            } else {
+                mint_tag(&target.address)
+            }
+        })
+        .collect();
+
+    let hub = state.hub.clone();
+    let cache = hub.cache();
+    let key = format!("grafana/query:{}", String::from_utf8_lossy(&body));
+    cached(
+        cache,
+        key,
+        cache.config().candles_ttl,
+        tags,
+        query_series(&state, &request),
+    )
+    .await
+}
+
+/// Builds the time series of a `/query` request.
+async fn query_series(state: &AppState, request: &QueryRequest) -> Result<Vec<Series>, ApiError> {
+    let (from, to) = request.range.resolve().map_err(bad_request)?;
+    let (from_secs, to_secs) = (from.div_euclid(1000), to.div_euclid(1000));
+
+    let mut series = Vec::new();
+    for query in request.targets.iter().filter(|query| !query.hide) {
+        let Some(name) = query
+            .target
+            .as_deref()
+            .filter(|name| !name.trim().is_empty())
+        else {
+            continue;
+        };
+        let target = Target::parse(name).map_err(bad_request)?;
+
+        let datapoints = if Metric::POOL.contains(&target.metric) {
+            let Some(ref service) = state.candles else {
+                return Err((
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "candle storage is not configured".to_string(),
+                ));
+            };
+            let Some(interval) = pick_interval(
+                service.intervals(),
+                to_secs - from_secs,
+                request.interval_ms,
+                request.max_data_points,
+            ) else {
+                return Err(bad_request("no candle interval is enabled".to_string()));
+            };
+
+            let candles = service
+                .query(
+                    &target.address,
+                    interval,
+                    interval.bucket(from_secs),
+                    to_secs,
+                    MAX_CANDLES,
+                )
+                .await
+                .map_err(|e| {
+                    log::error!("Failed to load candles for {}: {e}", target.address);
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "failed to load candles".to_string(),
+                    )
+                })?;
+            candles
+                .iter()
+                .filter_map(|candle| {
+                    let value = match target.metric {
+                        Metric::Price => Some(candle.close),
+                        Metric::Volume => candle.volume_usd,
+                        _ => Some(candle.trades as f64),
+                    };
+                    value.map(|value| (value, candle.open_time * 1000))
+                })
+                .collect()
+        } else {
+            state
+                .stats
+                .hourly(&target.address)
+                .into_iter()
+                .filter(|hour| {
+                    (from_secs.div_euclid(3600) * 3600..=to_secs).contains(&hour.hour_start)
+                })
+                .map(|hour| {
+                    let value = match target.metric {
+                        Metric::Alerts => hour.trades as f64,
+                        _ => hour.usd,
+                    };
+                    (value, hour.hour_start * 1000)
+                })
+                .collect()
+        };
+
+        series.push(Series {
+            target: target.to_string(),
+            datapoints,
+        });
+    }
+    Ok(series)
+}
+
+/// Picks the shortest enabled interval at least as long as the panel
+/// resolution that covers `span_secs` within the panel's point budget,
+/// falling back to the longest enabled interval.
+fn pick_interval(
+    enabled: &[Interval],
+    span_secs: i64,
+    interval_ms: i64,
+    max_points: Option<usize>,
+) -> Option<Interval> {
+    let max_points = max_points.unwrap_or(MAX_CANDLES).clamp(1, MAX_CANDLES) as i64;
+    let mut intervals = enabled.to_vec();
+    intervals.sort();
+    intervals
+        .iter()
+        .copied()
+        .find(|interval| {
+            interval.seconds() * 1000 >= interval_ms && span_secs / interval.seconds() < max_points
+        })
+        .or(intervals.last().copied())
+}
+
+/// Parses an RFC 3339 timestamp such as `2024-02-29T23:59:59.123Z` into Unix
+/// milliseconds. Digits beyond milliseconds are truncated.
+fn parse_time(value: &str) -> Option<i64> {
+    let (date, time) = value.trim().split_once(['T', 't', ' '])?;
+
+    let mut date = date.splitn(3, '-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+
+    let (clock, offset) = match time.strip_suffix(['Z', 'z']) {
+        Some(clock) => (clock, 0),
+        None => {
+            let (clock, offset) = time.split_at(time.rfind(['+', '-'])?);
+            let sign = if offset.starts_with('-') { -1 } else { 1 };
+            let (hours, minutes) = offset[1..].split_once(':')?;
+            let offset = hours.parse::<i64>().ok()? * 3_600 + minutes.parse::<i64>().ok()? * 60;
+            (clock, sign * offset)
+        }
+    };
+
+    let (clock, fraction) = clock.split_once('.').unwrap_or((clock, ""));
+    if !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let millis: i64 = format!("{fraction:0<3}")[..3].parse().ok()?;
+
+    let mut clock = clock.splitn(3, ':').map(|part| part.parse::<i64>().ok());
+    let (hour, minute, second) = (clock.next()??, clock.next()??, clock.next()??);
+
+    let valid = (1..=12).contains(&month)
+        && (1..=31).contains(&day)
+        && (0..24).contains(&hour)
+        && (0..60).contains(&minute)
+        && (0..=60).contains(&second);
+    if !valid {
+        return None;
+    }
+
+    let secs =
+        days_from_civil(year, month, day) * 86_400 + hour * 3_600 + minute * 60 + second - offset;
+    Some(secs * 1000 + millis)
+}
+
+/// Days since the Unix epoch of a proleptic Gregorian date (Howard Hinnant).
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_target() {
+        let target = Target::parse(" price:PoolA ").unwrap();
+        assert_eq!(target.metric, Metric::Price);
+        assert_eq!(target.address, "PoolA");
+        assert_eq!(target.to_string(), "price:PoolA");
+        assert_eq!(
+            Target::parse("token_volume:Mint").unwrap().metric,
+            Metric::TokenVolume
+        );
+
+        assert!(Target::parse("price").is_err());
+        assert!(Target::parse("price:").is_err());
+        assert!(Target::parse("bogus:PoolA").is_err());
+    }
+
+    #[test]
+    fn test_metric_names() {
+        let pools = vec!["PoolA".to_string()];
+        let mints = vec!["MintB".to_string()];
+        assert_eq!(
+            metric_names(&pools, &mints, ""),
+            [
+                "price:PoolA",
+                "volume:PoolA",
+                "trades:PoolA",
+                "alerts:MintB",
+                "token_volume:MintB"
+            ]
+        );
+        assert_eq!(
+            metric_names(&pools, &mints, "VOLUME"),
+            ["volume:PoolA", "token_volume:MintB"]
+        );
+    }
+
+    #[test]
+    fn test_parse_query_request() {
+        let request: QueryRequest = serde_json::from_str(
+            r#"{
+                "panelId": 1,
+                "range": {"from": "2016-10-31T06:33:44.866Z",
+                          "to": "2016-10-31T12:33:44.866Z", "raw": {}},
+                "intervalMs": 30000,
+                "maxDataPoints": 550,
+                "targets": [{"target": "price:PoolA", "refId": "A", "type": "timeserie"},
+                            {"refId": "B", "hide": true}]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            request.range.resolve(),
+            Ok((1_477_895_624_866, 1_477_917_224_866))
+        );
+        assert_eq!(request.interval_ms, 30_000);
+        assert_eq!(request.max_data_points, Some(550));
+        assert_eq!(request.targets[0].target.as_deref(), Some("price:PoolA"));
+        assert!(request.targets[1].hide);
+    }
+
+    #[test]
+    fn test_pick_interval() {
+        let all = Interval::ALL;
+        // 6 hours at 30s resolution: 1m gives 360 points
+        assert_eq!(
+            pick_interval(&all, 6 * 3_600, 30_000, Some(550)),
+            Some(Interval::M1)
+        );
+        // Respects the panel resolution and point budget
+        assert_eq!(
+            pick_interval(&all, 6 * 3_600, 120_000, None),
+            Some(Interval::M5)
+        );
+        assert_eq!(
+            pick_interval(&all, 6 * 3_600, 0, Some(100)),
+            Some(Interval::M5)
+        );
+        // Falls back to the longest interval
+        assert_eq!(
+            pick_interval(&[Interval::M5, Interval::M1], 365 * 86_400, 0, None),
+            Some(Interval::M5)
+        );
+        assert_eq!(pick_interval(&[], 60, 0, None), None);
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(parse_time("1970-01-01T00:00:00Z"), Some(0));
+        assert_eq!(parse_time("2024-02-29T23:59:59Z"), Some(1_709_251_199_000));
+        assert_eq!(
+            parse_time("2016-10-31T06:33:44.866123Z"),
+            Some(1_477_895_624_866)
+        );
+        assert_eq!(
+            parse_time("2024-03-01T01:00:00.5+02:00"),
+            Some(1_709_247_600_500)
+        );
+        assert_eq!(parse_time("1969-12-31T23:59:59Z"), Some(-1_000));
+
+        assert_eq!(parse_time("2024-13-01T00:00:00Z"), None);
+        assert_eq!(parse_time("2024-01-01T00:00:00"), None);
+        assert_eq!(parse_time("2024-01-01"), None);
+        assert_eq!(parse_time("yesterday"), None);
+    }
+}
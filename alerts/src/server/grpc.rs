@@ -0,0 +1,212 @@
+//! gRPC streaming API of normalized events (`grpc` feature).
+//!
+//! `raydium_alerts.v1.SwapStream/SubscribeSwaps` streams the events of the
+//! live feed matching a [`SwapFilter`](proto::SwapFilter) (token, pool, and
+//! minimum USD value, as on `/ws/swaps`) as typed messages. The service
+//! definitions are shipped in `proto/raydium_alerts.proto` for clients to
+//! generate code from.
+
+use {
+    super::{
+        hub::{FeedMessage, StreamHub},
+        ws::FeedFilter,
+    },
+    crate::output::{EventType, Protocol, SwapDirection, SwapEvent, TokenInfo, TradeSide},
+    proto::swap_stream_server::{SwapStream, SwapStreamServer},
+    std::{net::SocketAddr, pin::Pin, sync::Arc},
+    tokio::net::TcpListener,
+    tokio_stream::{
+        wrappers::{errors::BroadcastStreamRecvError, BroadcastStream, TcpListenerStream},
+        Stream, StreamExt,
+    },
+    tonic::{Request, Response, Status},
+};
+
+/// Code generated from `proto/raydium_alerts.proto`.
+pub mod proto {
+    tonic::include_proto!("raydium_alerts.v1");
+}
+
+impl From<proto::SwapFilter> for FeedFilter {
+    fn from(filter: proto::SwapFilter) -> Self {
+        Self {
+            token: filter.token,
+            pool: filter.pool,
+            min_usd: filter.min_usd,
+        }
+    }
+}
+
+impl From<Protocol> for proto::Protocol {
+    fn from(protocol: Protocol) -> Self {
+        match protocol {
+            Protocol::Cpmm => Self::Cpmm,
+            Protocol::Clmm => Self::Clmm,
+            Protocol::AmmV4 => Self::AmmV4,
+            Protocol::Launchpad => Self::Launchpad,
+            Protocol::PumpFun => Self::PumpFun,
+            Protocol::PumpSwap => Self::PumpSwap,
+        }
+    }
+}
+
+impl From<EventType> for proto::EventType {
+    fn from(event_type: EventType) -> Self {
+        match event_type {
+            EventType::Swap => Self::Swap,
+            EventType::AddLiquidity => Self::AddLiquidity,
+            EventType::RemoveLiquidity => Self::RemoveLiquidity,
+            EventType::CreatePool => Self::CreatePool,
+            EventType::Migrate => Self::Migrate,
+            EventType::Custom => Self::Custom,
+            EventType::AdminAction => Self::AdminAction,
+            EventType::VolumeSummary => Self::VolumeSummary,
+            EventType::MevAlert => Self::MevAlert,
+            EventType::LiquidityDrain => Self::LiquidityDrain,
+            EventType::ThrottleDigest => Self::ThrottleDigest,
+            EventType::LpBurn => Self::LpBurn,
+            EventType::CollectFees => Self::CollectFees,
+        }
+    }
+}
+
+impl From<SwapDirection> for proto::SwapDirection {
+    fn from(direction: SwapDirection) -> Self {
+        match direction {
+            SwapDirection::ExactInput => Self::ExactInput,
+            SwapDirection::ExactOutput => Self::ExactOutput,
+            SwapDirection::Unknown => Self::Unknown,
+        }
+    }
+}
+
+impl From<TradeSide> for proto::TradeSide {
+    fn from(side: TradeSide) -> Self {
+        match side {
+            TradeSide::Buy => Self::Buy,
+            TradeSide::Sell => Self::Sell,
+        }
+    }
+}
+
+impl From<&TokenInfo> for proto::TokenInfo {
+    fn from(token: &TokenInfo) -> Self {
+        Self {
+            mint: token.mint.clone(),
+            symbol: token.symbol.clone(),
+            decimals: token.decimals.map(u32::from),
+            amount_raw: token.amount_raw,
+            amount: token.amount,
+            amount_usd: token.amount_usd,
+        }
+    }
+}
+
+impl From<&FeedMessage> for proto::SwapEvent {
+    fn from(message: &FeedMessage) -> Self {
+        let event: &SwapEvent = &message.event;
+        Self {
+            event_id: event.event_id.clone(),
+            event_type: proto::EventType::from(event.event_type).into(),
+            protocol: proto::Protocol::from(event.protocol).into(),
+            deployment: event.deployment.clone(),
+            signature: event.signature.clone(),
+            slot: event.slot,
+            timestamp: event.timestamp,
+            pool: event.pool.clone(),
+            input_token: event.input_token.as_ref().map(Into::into),
+            output_token: event.output_token.as_ref().map(Into::into),
+            direction: proto::SwapDirection::from(event.direction).into(),
+            side: event.side.map(|side| proto::TradeSide::from(side).into()),
+            fee: event.fee,
+            maker: event.maker.clone(),
+            maker_label: event.maker_label.clone(),
+            router: event.router.clone(),
+            usd_value: event.usd_value(),
+            market_cap_usd: event.market_cap_usd,
+            effective_price: event.effective_price,
+            price_impact_pct: event.price_impact_pct,
+            json: message.json.clone(),
+        }
+    }
+}
+
+/// `SwapStream` service streaming the events published to the hub.
+struct SwapStreamService {
+    hub: Arc<StreamHub>,
+}
+
+#[tonic::async_trait]
+impl SwapStream for SwapStreamService {
+    type SubscribeSwapsStream =
+        Pin<Box<dyn Stream<Item = Result<proto::SwapEvent, Status>> + Send>>;
+
+    async fn subscribe_swaps(
+        &self,
+        request: Request<proto::SwapFilter>,
+    ) -> Result<Response<Self::SubscribeSwapsStream>, Status> {
+        let filter = FeedFilter::from(request.into_inner()).normalize();
+        let feed = BroadcastStream::new(self.hub.subscribe_feed());
+        let stream = feed.filter_map(move |message| match message {
+            Ok(message) => filter
+                .matches(&message.event)
+                .then(|| Ok(proto::SwapEvent::from(message.as_ref()))),
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                log::debug!("gRPC feed client lagged, skipped {skipped} event(s)");
+                None
+            }
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+/// Binds `addr` and serves the gRPC API in the background.
+pub(super) async fn spawn(addr: SocketAddr, hub: Arc<StreamHub>) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    log::info!("gRPC server listening on {}", listener.local_addr()?);
+
+    let service = SwapStreamServer::new(SwapStreamService { hub });
+    tokio::spawn(async move {
+        if let Err(e) = tonic::transport::Server::builder()
+            .add_service(service)
+            .serve_with_incoming(TcpListenerStream::new(listener))
+            .await
+        {
+            log::error!("gRPC server stopped: {e}");
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feed_message_to_proto() {
+        let mut input = TokenInfo::new("MintA", 1_000);
+        input.decimals = Some(6);
+        input.amount_usd = Some(250.0);
+        let event = SwapEvent::builder()
+            .protocol(Protocol::AmmV4)
+            .signature("sig")
+            .pool("PoolA")
+            .input_token(input)
+            .build();
+        let message = FeedMessage {
+            json: serde_json::to_string(&event).unwrap(),
+            event,
+        };
+
+        let proto = proto::SwapEvent::from(&message);
+        assert_eq!(proto.protocol(), proto::Protocol::AmmV4);
+        assert_eq!(proto.event_type(), proto::EventType::Swap);
+        assert_eq!(proto.pool, "PoolA");
+        assert_eq!(proto.usd_value, Some(250.0));
+        let input = proto.input_token.unwrap();
+        assert_eq!((input.mint.as_str(), input.decimals), ("MintA", Some(6)));
+        assert!(proto.output_token.is_none());
+        assert!(proto.side.is_none());
+        assert_eq!(proto.json, message.json);
+    }
+}
@@ -0,0 +1,181 @@
+//! Fan-out of derived topic messages to connected clients.
+
+use {
+    super::{
+        cache::ResponseCache,
+        recent::RecentSwaps,
+        topics::{PriceTick, Topic},
+    },
+    crate::output::{EventUpdate, SwapEvent},
+    serde::Serialize,
+    std::sync::Arc,
+    tokio::sync::broadcast,
+};
+
+/// A serialized message for one topic.
+#[derive(Debug, Clone)]
+pub struct TopicMessage {
+    /// Topic the message belongs to
+    pub topic: Topic,
+    /// JSON payload: `{"topic": "...", "data": {...}}`
+    pub json: String,
+}
+
+/// A normalized event and its JSON, for the live event feed.
+#[derive(Debug, Clone)]
+pub struct FeedMessage {
+    /// The event, for per-connection filtering
+    pub event: SwapEvent,
+    /// JSON-serialized event
+    pub json: String,
+}
+
+/// Broadcasts topic messages derived from emitted events, and the events
+/// themselves, to all subscribers.
+///
+/// Messages are serialized once and shared; each connection filters them by
+/// its own subscriptions. Slow subscribers that fall more than the channel
+/// capacity behind skip the missed messages. Swaps are also kept in a ring
+/// buffer for the query API, whose cached responses each event invalidates.
+pub struct StreamHub {
+    tx: broadcast::Sender<Arc<TopicMessage>>,
+    feed: broadcast::Sender<Arc<FeedMessage>>,
+    recent: RecentSwaps,
+    cache: ResponseCache,
+}
+
+impl StreamHub {
+    /// Creates a hub buffering up to `capacity` messages per subscriber and the
+    /// last `recent_capacity` swaps.
+    pub fn new(capacity: usize, recent_capacity: usize) -> Self {
+        let (tx, _) = broadcast::channel(capacity.max(1));
+        let (feed, _) = broadcast::channel(capacity.max(1));
+        Self {
+            tx,
+            feed,
+            recent: RecentSwaps::new(recent_capacity),
+            cache: ResponseCache::default(),
+        }
+    }
+
+    /// Caches query API responses (disabled by default).
+    pub fn with_cache(mut self, cache: ResponseCache) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Returns the buffer of recent swaps.
+    pub fn recent(&self) -> &RecentSwaps {
+        &self.recent
+    }
+
+    /// Returns the query API response cache.
+    pub fn cache(&self) -> &ResponseCache {
+        &self.cache
+    }
+
+    /// Subscribes to all topic messages.
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<TopicMessage>> {
+        self.tx.subscribe()
+    }
+
+    /// Subscribes to all emitted events.
+    pub fn subscribe_feed(&self) -> broadcast::Receiver<Arc<FeedMessage>> {
+        self.feed.subscribe()
+    }
+
+    /// Records swaps and drops the cached responses covering them, broadcasts
+    /// the event to feed subscribers, then derives topic messages from it and
+    /// broadcasts them.
+    ///
+    /// Each broadcast is skipped when no client is connected to it.
+    pub fn publish(&self, event: &SwapEvent) {
+        self.recent.record(event);
+        self.cache.invalidate(event);
+
+        if self.feed.receiver_count() > 0 {
+            match serde_json::to_string(event) {
+                Ok(json) => {
+                    let _ = self.feed.send(Arc::new(FeedMessage {
+                        event: event.clone(),
+                        json,
+                    }));
+                }
+                Err(e) => log::warn!("Failed to serialize feed event: {e}"),
+            }
+        }
+
+        if self.tx.receiver_count() == 0 {
+            return;
+        }
+
+        if let Some(tick) = PriceTick::from_event(event) {
+            self.send(Topic::Price(event.pool.clone()), &tick);
+        }
+    }
+
+    /// Broadcasts the fields of an emitted event resolved after the fact on
+    /// the `updates` topic.
+    pub fn publish_update(&self, update: &EventUpdate) {
+        if self.tx.receiver_count() > 0 {
+            self.send(Topic::Updates, update);
+        }
+    }
+
+    /// Serializes and broadcasts a message for a topic.
+    fn send(&self, topic: Topic, data: &impl Serialize) {
+        #[derive(Serialize)]
+        struct Envelope<'a, T> {
+            topic: String,
+            data: &'a T,
+        }
+
+        let envelope = Envelope {
+            topic: topic.to_string(),
+            data,
+        };
+        match serde_json::to_string(&envelope) {
+            Ok(json) => {
+                let _ = self.tx.send(Arc::new(TopicMessage { topic, json }));
+            }
+            Err(e) => log::warn!("Failed to serialize {topic} message: {e}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{swap_event::USDC_MINT, Protocol, TokenInfo},
+    };
+
+    #[test]
+    fn test_publish_price_tick() {
+        let hub = StreamHub::new(16, 16);
+        let mut rx = hub.subscribe();
+        let mut feed = hub.subscribe_feed();
+
+        let event = SwapEvent::builder()
+            .protocol(Protocol::Clmm)
+            .signature("sig")
+            .pool("PoolA")
+            .input_token(TokenInfo::new(USDC_MINT, 10_000_000))
+            .output_token(TokenInfo::new("MemeMint", 5_000).with_decimals(3))
+            .timestamp(1)
+            .build();
+        hub.publish(&event);
+
+        let message = rx.try_recv().unwrap();
+        assert_eq!(message.topic, Topic::Price("PoolA".to_string()));
+        let json: serde_json::Value = serde_json::from_str(&message.json).unwrap();
+        assert_eq!(json["topic"], "price:PoolA");
+        assert_eq!(json["data"]["price"], 2.0);
+        assert_eq!(json["data"]["volume"], 10.0);
+        assert_eq!(hub.recent().len(), 1);
+
+        let message = feed.try_recv().unwrap();
+        assert_eq!(message.event.signature, "sig");
+        assert!(message.json.contains(r#""pool":"PoolA""#));
+    }
+}
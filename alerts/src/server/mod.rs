@@ -0,0 +1,804 @@
+//! Streaming server for live topic feeds.
+//!
+//! Exposes derived topics over WebSocket and Server-Sent Events so lightweight
+//! clients (e.g., charting frontends) can subscribe without receiving full
+//! swap payloads:
+//!
+//! - `GET /ws?topics=price:<pool>,...` - WebSocket; further subscriptions via
+//!   `{"op": "subscribe" | "unsubscribe", "topics": ["price:<pool>"]}`
+//! - `GET /sse?topics=price:<pool>,...` - Server-Sent Events
+//!
+//! Every message is `{"topic": "...", "data": {...}}`.
+//!
+//! Full events are streamed to clients filtering by token, pool, and USD value:
+//!
+//! - `GET /ws/swaps?token=<mint>&pool=<pool>&min_usd=<usd>` - WebSocket; the
+//!   filter is replaced by sending `{"token": ..., "pool": ..., "min_usd": ...}`
+//!
+//! Recent swaps are kept in memory and served newest first:
+//!
+//! - `GET /swaps?token=<mint>&limit=100` - Swaps, optionally trading a token
+//! - `GET /pools/{pool}/swaps?limit=100` - Swaps in a pool
+//! - `GET /health` - Liveness and buffered swap count
+//!
+//! With `SEQUENCE_FILE`, events retained per webhook stream are served from a
+//! sequence number, so consumers can backfill gaps in `seq`:
+//!
+//! - `GET /streams/{stream}/events?from_seq=<seq>&limit=100` - Events with
+//!   `seq >= from_seq`, oldest first
+//!
+//! When storage is configured, historical OHLCV candles are also served:
+//!
+//! - `GET /candles?pool=<pool>&interval=1m&from=<unix>&to=<unix>`
+//!
+//! Past the storage rollup boundary, sub-hour candles are served as hourly
+//! candles and the response names the boundary in a `rollup` field.
+//!
+//! With a database, stored events are served in the order they were stored,
+//! each with the store's `seq`, so consumers can resume after an outage:
+//!
+//! - `GET /events/backfill?from_seq=<seq>&from_slot=<slot>&limit=100` - Events
+//!   with `seq >= from_seq` and `slot >= from_slot`, plus a `continuation`
+//!   token; `GET /events/backfill?continuation=<token>` returns the next page
+//!
+//! Candles and per-token stats are charted by Grafana through the JSON
+//! datasource endpoints under `/grafana` (see [`grafana`]).
+//!
+//! Swap, candle, and Grafana query responses are cached, so polling
+//! dashboards do not compete with ingest (see [`cache`]).
+//!
+//! Built with the `grpc` feature and `GRPC_ADDR` set, the live feed is also
+//! served as the `SubscribeSwaps` server-streaming RPC (see `grpc`).
+
+mod cache;
+mod grafana;
+#[cfg(feature = "grpc")]
+mod grpc;
+mod hub;
+mod recent;
+mod topics;
+mod ws;
+
+pub(crate) use topics::unix_now;
+pub use {
+    cache::{CacheConfig, ResponseCache},
+    hub::StreamHub,
+    topics::{PriceTick, Topic},
+};
+
+use {
+    crate::{
+        candles::{CandleService, Interval, ROLLUP_INTERVAL},
+        output::{Sequencer, SwapEvent},
+        stats::StatsStore,
+        storage::EventStore,
+    },
+    axum::{
+        body::Bytes,
+        extract::{
+            ws::{Message, WebSocket, WebSocketUpgrade},
+            Path, Query, State,
+        },
+        http::{header, StatusCode},
+        response::{
+            sse::{Event, KeepAlive, Sse},
+            IntoResponse, Response,
+        },
+        routing::{get, post},
+        Json, Router,
+    },
+    cache::{mint_tag, pool_tag},
+    serde::{Deserialize, Serialize},
+    std::{
+        collections::HashSet, convert::Infallible, env, fmt, future::Future, net::SocketAddr,
+        str::FromStr, sync::Arc, time::Duration,
+    },
+    tokio::{net::TcpListener, sync::broadcast::error::RecvError},
+    tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt},
+};
+
+/// Configuration for the streaming server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerConfig {
+    /// Address to listen on
+    pub bind: SocketAddr,
+    /// Messages buffered per subscriber before it starts skipping
+    pub channel_capacity: usize,
+    /// Swaps kept in memory for `/swaps`
+    pub recent_capacity: usize,
+    /// Query API response cache
+    pub cache: CacheConfig,
+    /// Address the gRPC API listens on (requires the `grpc` feature)
+    pub grpc: Option<SocketAddr>,
+}
+
+impl ServerConfig {
+    /// Creates server configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `SERVER_ADDR` - Listen address (e.g., `0.0.0.0:8080`)
+    /// - `HTTP_PORT` - Port to listen on all interfaces, if `SERVER_ADDR` is unset
+    /// - `SERVER_CHANNEL_CAPACITY` - Optional: Per-subscriber buffer (default: 1024)
+    /// - `SERVER_RECENT_SWAPS` - Optional: Swaps kept for `/swaps` (default: 10000)
+    /// - `SERVER_CACHE_*` - Optional: Response cache TTLs and size (see [`CacheConfig::from_env`])
+    /// - `GRPC_ADDR` - Optional: Listen address of the gRPC API (requires the `grpc` feature)
+    ///
+    /// # Returns
+    ///
+    /// `Some(ServerConfig)` if `SERVER_ADDR` or `HTTP_PORT` is set to a valid value,
+    /// `None` otherwise.
+    pub fn from_env() -> Option<Self> {
+        let var = |name| env::var(name).ok().filter(|v| !v.trim().is_empty());
+        let (name, addr) = match var("SERVER_ADDR") {
+            Some(addr) => ("SERVER_ADDR", addr),
+            None => ("HTTP_PORT", format!("0.0.0.0:{}", var("HTTP_PORT")?.trim())),
+        };
+        let bind = match addr.trim().parse() {
+            Ok(bind) => bind,
+            Err(e) => {
+                log::warn!("Invalid {name} '{addr}': {e}, server disabled");
+                return None;
+            }
+        };
+
+        let channel_capacity = env::var("SERVER_CHANNEL_CAPACITY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(1024);
+
+        let recent_capacity = env::var("SERVER_RECENT_SWAPS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(10_000);
+
+        let grpc = var("GRPC_ADDR").and_then(|addr| match addr.trim().parse() {
+            Ok(grpc) => Some(grpc),
+            Err(e) => {
+                log::warn!("Invalid GRPC_ADDR '{addr}': {e}, gRPC API disabled");
+                None
+            }
+        });
+
+        Some(Self {
+            bind,
+            channel_capacity,
+            recent_capacity,
+            cache: CacheConfig::from_env(),
+            grpc,
+        })
+    }
+}
+
+/// Maximum candles returned by one `/candles` request.
+const MAX_CANDLES: usize = 1_000;
+
+/// Swaps returned by `/swaps` when no limit is given.
+const DEFAULT_SWAPS_LIMIT: usize = 100;
+
+/// Maximum swaps returned by one `/swaps` request.
+const MAX_SWAPS: usize = 1_000;
+
+/// Shared state of the HTTP handlers.
+#[derive(Clone)]
+struct AppState {
+    hub: Arc<StreamHub>,
+    candles: Option<Arc<CandleService>>,
+    stats: Arc<StatsStore>,
+    sequencer: Option<Arc<Sequencer>>,
+    events: Option<Arc<dyn EventStore>>,
+}
+
+/// Binds the listener and serves the streaming endpoints in a background task.
+///
+/// Binding happens before returning so address errors surface at startup.
+///
+/// # Arguments
+///
+/// * `config` - Listen addresses and channel capacity
+/// * `hub` - Topic hub fed by the processors
+/// * `candles` - Candle service backing `/candles`; `None` if storage is disabled
+/// * `stats` - Per-token stats charted by the Grafana datasource
+/// * `sequencer` - Webhook stream sequencer backing `/streams`; `None` if disabled
+/// * `events` - Stored events backing `/events/backfill`; `None` without a database
+pub async fn spawn(
+    config: ServerConfig,
+    hub: Arc<StreamHub>,
+    candles: Option<Arc<CandleService>>,
+    stats: Arc<StatsStore>,
+    sequencer: Option<Arc<Sequencer>>,
+    events: Option<Arc<dyn EventStore>>,
+) -> std::io::Result<()> {
+    let listener = TcpListener::bind(config.bind).await?;
+    log::info!("Streaming server listening on {}", listener.local_addr()?);
+
+    if let Some(addr) = config.grpc {
+        #[cfg(feature = "grpc")]
+        grpc::spawn(addr, hub.clone()).await?;
+        #[cfg(not(feature = "grpc"))]
+        log::warn!(
+            "GRPC_ADDR is set to {addr}, but the gRPC API requires building with \
+             `--features grpc`; not serving it"
+        );
+    }
+
+    let state = AppState {
+        hub,
+        candles,
+        stats,
+        sequencer,
+        events,
+    };
+    tokio::spawn(async move {
+        if let Err(e) = axum::serve(listener, router(state)).await {
+            log::error!("Streaming server stopped: {e}");
+        }
+    });
+    Ok(())
+}
+
+/// Builds the HTTP routes.
+fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/ws", get(ws_handler))
+        .route("/ws/swaps", get(ws::feed_handler))
+        .route("/sse", get(sse_handler))
+        .route("/candles", get(candles_handler))
+        .route("/swaps", get(swaps_handler))
+        .route("/pools/{pool}/swaps", get(pool_swaps_handler))
+        .route("/streams/{stream}/events", get(stream_events_handler))
+        .route("/events/backfill", get(backfill_handler))
+        .route("/health", get(health_handler))
+        .route("/grafana", get(grafana::test_handler))
+        .route("/grafana/", get(grafana::test_handler))
+        .route("/grafana/search", post(grafana::search_handler))
+        .route("/grafana/query", post(grafana::query_handler))
+        .with_state(state)
+}
+
+/// Serves a JSON response from the response cache, or computes and caches it
+/// for `ttl`, tagged with the pools and mints it covers.
+///
+/// `compute` only runs on a miss; errors are not cached.
+async fn cached<T: Serialize>(
+    cache: &ResponseCache,
+    key: String,
+    ttl: Duration,
+    tags: Vec<String>,
+    compute: impl Future<Output = Result<T, (StatusCode, String)>>,
+) -> Result<Response, (StatusCode, String)> {
+    if let Some(body) = cache.get(&key) {
+        return Ok(json_response(body));
+    }
+    let body = serde_json::to_vec(&compute.await?).map_err(|e| {
+        log::error!("Failed to serialize response: {e}");
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to serialize response".to_string(),
+        )
+    })?;
+    let body = Bytes::from(body);
+    cache.insert(key, body.clone(), ttl, tags);
+    Ok(json_response(body))
+}
+
+/// Builds a JSON response from a serialized body.
+fn json_response(body: Bytes) -> Response {
+    ([(header::CONTENT_TYPE, "application/json")], body).into_response()
+}
+
+/// Query string for initial subscriptions.
+#[derive(Debug, Default, Deserialize)]
+struct TopicsQuery {
+    /// Comma-separated topic names
+    #[serde(default)]
+    topics: String,
+}
+
+/// Parses topic names, rejecting unknown ones.
+fn parse_topics<'a>(names: impl IntoIterator<Item = &'a str>) -> Result<Vec<Topic>, String> {
+    names
+        .into_iter()
+        .filter(|name| !name.trim().is_empty())
+        .map(|name| Topic::parse(name).ok_or_else(|| format!("unknown topic '{}'", name.trim())))
+        .collect()
+}
+
+/// Subscription command sent by WebSocket clients.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+enum Command {
+    Subscribe { topics: Vec<String> },
+    Unsubscribe { topics: Vec<String> },
+}
+
+async fn ws_handler(
+    ws: WebSocketUpgrade,
+    Query(query): Query<TopicsQuery>,
+    State(state): State<AppState>,
+) -> Response {
+    match parse_topics(query.topics.split(',')) {
+        Ok(topics) => ws.on_upgrade(move |socket| handle_socket(socket, state.hub, topics)),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
+/// Forwards subscribed topic messages and handles subscription commands.
+async fn handle_socket(mut socket: WebSocket, hub: Arc<StreamHub>, initial: Vec<Topic>) {
+    let mut topics: HashSet<Topic> = initial.into_iter().collect();
+    let mut rx = hub.subscribe();
+
+    loop {
+        tokio::select! {
+            message = rx.recv() => match message {
+                Ok(message) => {
+                    if topics.contains(&message.topic)
+                        && socket.send(Message::Text(message.json.clone().into())).await.is_err()
+                    {
+                        break;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    log::debug!("WebSocket client lagged, skipped {skipped} message(s)");
+                }
+                Err(RecvError::Closed) => break,
+            },
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Text(text))) => {
+                    let reply = apply_command(&text, &mut topics);
+                    if socket.send(Message::Text(reply.into())).await.is_err() {
+                        break;
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+/// Applies a subscription command and returns the JSON reply.
+fn apply_command(text: &str, topics: &mut HashSet<Topic>) -> String {
+    let result = serde_json::from_str::<Command>(text)
+        .map_err(|e| format!("invalid command: {e}"))
+        .and_then(|command| match command {
+            Command::Subscribe { topics: names } => {
+                topics.extend(parse_topics(names.iter().map(String::as_str))?);
+                Ok(())
+            }
+            Command::Unsubscribe { topics: names } => {
+                for topic in parse_topics(names.iter().map(String::as_str))? {
+                    topics.remove(&topic);
+                }
+                Ok(())
+            }
+        });
+
+    match result {
+        Ok(()) => {
+            let mut names: Vec<String> = topics.iter().map(Topic::to_string).collect();
+            names.sort();
+            serde_json::json!({ "subscribed": names }).to_string()
+        }
+        Err(e) => serde_json::json!({ "error": e }).to_string(),
+    }
+}
+
+async fn sse_handler(
+    Query(query): Query<TopicsQuery>,
+    State(state): State<AppState>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, (StatusCode, String)> {
+    let topics: HashSet<Topic> = parse_topics(query.topics.split(','))
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?
+        .into_iter()
+        .collect();
+    if topics.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "no topics requested".to_string()));
+    }
+
+    // Lagged receivers yield an error item, which is skipped
+    let stream = BroadcastStream::new(state.hub.subscribe()).filter_map(move |message| {
+        let message = message.ok()?;
+        topics
+            .contains(&message.topic)
+            .then(|| Ok(Event::default().data(message.json.clone())))
+    });
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+/// Query string for `/candles`.
+#[derive(Debug, Deserialize)]
+struct CandlesQuery {
+    /// Pool address
+    pool: String,
+    /// Interval name (default: `1m`)
+    interval: Option<String>,
+    /// First open time, Unix seconds (default: `MAX_CANDLES` intervals before `to`)
+    from: Option<i64>,
+    /// Last open time, Unix seconds (default: now)
+    to: Option<i64>,
+}
+
+impl CandlesQuery {
+    /// Validates the query into `(interval, from, to)`.
+    fn resolve(&self, now: i64) -> Result<(Interval, i64, i64), String> {
+        let name = self.interval.as_deref().unwrap_or("1m");
+        let interval = Interval::parse(name).ok_or_else(|| format!("unknown interval '{name}'"))?;
+        let to = self.to.unwrap_or(now);
+        let from = self
+            .from
+            .unwrap_or(to - interval.seconds() * MAX_CANDLES as i64);
+        if from > to {
+            return Err("'from' must not be after 'to'".to_string());
+        }
+        Ok((interval, from, to))
+    }
+}
+
+async fn candles_handler(
+    Query(query): Query<CandlesQuery>,
+    State(state): State<AppState>,
+) -> Result<Response, (StatusCode, String)> {
+    let cache = state.hub.cache();
+    let key = format!(
+        "candles?pool={}&interval={:?}&from={:?}&to={:?}",
+        query.pool, query.interval, query.from, query.to
+    );
+    let tags = vec![pool_tag(&query.pool)];
+    let ttl = cache.config().candles_ttl;
+    cached(cache, key, ttl, tags, load_candles(&query, &state)).await
+}
+
+/// Loads the candles of a `/candles` query.
+async fn load_candles(
+    query: &CandlesQuery,
+    state: &AppState,
+) -> Result<serde_json::Value, (StatusCode, String)> {
+    let Some(ref service) = state.candles else {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            "candle storage is not configured".to_string(),
+        ));
+    };
+
+    let now = unix_now();
+    let (interval, from, to) = query
+        .resolve(now)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    if !service.supports(interval) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("interval '{interval}' is not enabled"),
+        ));
+    }
+
+    let candles = service
+        .query(&query.pool, interval, from, to, MAX_CANDLES)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to load candles for {}: {e}", query.pool);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to load candles".to_string(),
+            )
+        })?;
+
+    let mut body = serde_json::json!({
+        "pool": query.pool,
+        "interval": interval.to_string(),
+        "candles": candles,
+    });
+    // Tell clients which part of the range was served at hourly resolution
+    if let Some(before) = service
+        .rollup_before(interval, now)
+        .filter(|before| from < *before)
+    {
+        body["rollup"] = serde_json::json!({
+            "interval": ROLLUP_INTERVAL.to_string(),
+            "before": before,
+        });
+    }
+    Ok(body)
+}
+
+/// Query string for `/swaps` and `/pools/{pool}/swaps`.
+#[derive(Debug, Default, Deserialize)]
+struct SwapsQuery {
+    /// Only swaps trading this mint
+    token: Option<String>,
+    /// Maximum swaps returned (default: `DEFAULT_SWAPS_LIMIT`, capped at `MAX_SWAPS`)
+    limit: Option<usize>,
+}
+
+impl SwapsQuery {
+    /// Returns the number of swaps to return.
+    fn limit(&self) -> usize {
+        self.limit.unwrap_or(DEFAULT_SWAPS_LIMIT).min(MAX_SWAPS)
+    }
+
+    /// Returns `true` if a swap matches the query (and pool, if given).
+    fn matches(&self, event: &SwapEvent, pool: Option<&str>) -> bool {
+        pool.is_none_or(|pool| event.pool == pool)
+            && self
+                .token
+                .as_deref()
+                .is_none_or(|mint| recent::trades_token(event, mint))
+    }
+}
+
+async fn swaps_handler(
+    Query(query): Query<SwapsQuery>,
+    State(state): State<AppState>,
+) -> Result<Response, (StatusCode, String)> {
+    let hub = &state.hub;
+    let key = format!("swaps?token={:?}&limit={}", query.token, query.limit());
+    let tags = query.token.as_deref().map(mint_tag).into_iter().collect();
+    cached(hub.cache(), key, hub.cache().config().ttl, tags, async {
+        let swaps = hub
+            .recent()
+            .query(query.limit(), |event| query.matches(event, None));
+        Ok(serde_json::json!({ "swaps": swaps }))
+    })
+    .await
+}
+
+async fn pool_swaps_handler(
+    Path(pool): Path<String>,
+    Query(query): Query<SwapsQuery>,
+    State(state): State<AppState>,
+) -> Result<Response, (StatusCode, String)> {
+    let hub = &state.hub;
+    let key = format!(
+        "pools/{pool}/swaps?token={:?}&limit={}",
+        query.token,
+        query.limit()
+    );
+    let tags = vec![pool_tag(&pool)];
+    cached(hub.cache(), key, hub.cache().config().ttl, tags, async {
+        let swaps = hub
+            .recent()
+            .query(query.limit(), |event| query.matches(event, Some(&pool)));
+        Ok(serde_json::json!({ "pool": pool, "swaps": swaps }))
+    })
+    .await
+}
+
+/// Query string for `/streams/{stream}/events`.
+#[derive(Debug, Default, Deserialize)]
+struct StreamEventsQuery {
+    /// First sequence number returned (default: the oldest retained)
+    #[serde(default)]
+    from_seq: u64,
+    /// Maximum events returned (default: `DEFAULT_SWAPS_LIMIT`, capped at `MAX_SWAPS`)
+    limit: Option<usize>,
+}
+
+async fn stream_events_handler(
+    Path(stream): Path<String>,
+    Query(query): Query<StreamEventsQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let Some(sequencer) = state.sequencer else {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            "stream sequencing is not configured".to_string(),
+        ));
+    };
+    let limit = query.limit.unwrap_or(DEFAULT_SWAPS_LIMIT).min(MAX_SWAPS);
+    let backfill = sequencer
+        .backfill(&stream, query.from_seq, limit)
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("unknown stream '{stream}'")))?;
+    Ok(Json(serde_json::json!(backfill)))
+}
+
+/// Position after the last event returned by `/events/backfill`.
+///
+/// Serialized as the `continuation` token `<after_seq>-<from_slot>`, so the
+/// slot filter carries over to the next page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct BackfillCursor {
+    /// Events with a higher `seq` are returned next
+    after_seq: u64,
+    /// Events below this slot are skipped
+    from_slot: u64,
+}
+
+impl fmt::Display for BackfillCursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.after_seq, self.from_slot)
+    }
+}
+
+impl FromStr for BackfillCursor {
+    type Err = String;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid continuation token '{token}'");
+        let (after_seq, from_slot) = token.split_once('-').ok_or_else(invalid)?;
+        Ok(Self {
+            after_seq: after_seq.parse().map_err(|_| invalid())?,
+            from_slot: from_slot.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// Query string for `/events/backfill`.
+#[derive(Debug, Default, Deserialize)]
+struct BackfillQuery {
+    /// First store sequence number returned (default: the oldest stored)
+    from_seq: Option<u64>,
+    /// Lowest slot returned (default: any)
+    from_slot: Option<u64>,
+    /// Token of a previous response; replaces `from_seq` and `from_slot`
+    continuation: Option<String>,
+    /// Maximum events returned (default: `DEFAULT_SWAPS_LIMIT`, capped at `MAX_SWAPS`)
+    limit: Option<usize>,
+}
+
+impl BackfillQuery {
+    /// Returns where the requested page starts.
+    fn cursor(&self) -> Result<BackfillCursor, String> {
+        match self.continuation {
+            Some(ref token) => token.parse(),
+            None => Ok(BackfillCursor {
+                after_seq: self.from_seq.unwrap_or(0).saturating_sub(1),
+                from_slot: self.from_slot.unwrap_or(0),
+            }),
+        }
+    }
+}
+
+async fn backfill_handler(
+    Query(query): Query<BackfillQuery>,
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let Some(store) = state.events else {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            "event storage is not configured".to_string(),
+        ));
+    };
+    let cursor = query.cursor().map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+    let limit = query.limit.unwrap_or(DEFAULT_SWAPS_LIMIT).min(MAX_SWAPS);
+
+    let events = store
+        .events_after(cursor.after_seq, cursor.from_slot, limit)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to load events after seq {}: {e}", cursor.after_seq);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to load events".to_string(),
+            )
+        })?;
+
+    // Without new events the same token is returned, so consumers can poll it
+    let next = BackfillCursor {
+        after_seq: events.last().map_or(cursor.after_seq, |stored| stored.seq),
+        ..cursor
+    };
+    Ok(Json(serde_json::json!({
+        "events": events,
+        "continuation": next.to_string(),
+    })))
+}
+
+async fn health_handler(State(state): State<AppState>) -> Json<serde_json::Value> {
+    let recent = state.hub.recent();
+    Json(serde_json::json!({
+        "status": "ok",
+        "recent_swaps": recent.len(),
+        "recent_capacity": recent.capacity(),
+        "candles": state.candles.is_some(),
+        "clock_offset_secs": crate::clock::drift().offset(),
+        "clock_drifting": crate::clock::drift().is_drifting(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_topics() {
+        assert_eq!(
+            parse_topics("price:A,,price:B".split(',')).unwrap(),
+            vec![Topic::Price("A".into()), Topic::Price("B".into())]
+        );
+        assert!(parse_topics("price:A,bogus".split(',')).is_err());
+        assert!(parse_topics("".split(',')).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_apply_command() {
+        let mut topics = HashSet::new();
+
+        let reply = apply_command(
+            r#"{"op":"subscribe","topics":["price:B","price:A"]}"#,
+            &mut topics,
+        );
+        assert_eq!(reply, r#"{"subscribed":["price:A","price:B"]}"#);
+
+        let reply = apply_command(r#"{"op":"unsubscribe","topics":["price:A"]}"#, &mut topics);
+        assert_eq!(reply, r#"{"subscribed":["price:B"]}"#);
+
+        let reply = apply_command(r#"{"op":"subscribe","topics":["nope"]}"#, &mut topics);
+        assert!(reply.contains("unknown topic"));
+        assert_eq!(topics.len(), 1);
+
+        assert!(apply_command("not json", &mut topics).contains("invalid command"));
+    }
+
+    #[test]
+    fn test_backfill_cursor() {
+        let query = |from_seq, from_slot, continuation: Option<&str>| BackfillQuery {
+            from_seq,
+            from_slot,
+            continuation: continuation.map(str::to_string),
+            limit: None,
+        };
+
+        let cursor = query(Some(10), Some(300), None).cursor().unwrap();
+        assert_eq!(
+            cursor,
+            BackfillCursor {
+                after_seq: 9,
+                from_slot: 300
+            }
+        );
+        assert_eq!(cursor.to_string(), "9-300");
+
+        let start = query(None, None, None).cursor().unwrap();
+        assert_eq!((start.after_seq, start.from_slot), (0, 0));
+
+        // The token takes precedence over the other parameters
+        let resumed = query(Some(1), None, Some("42-300")).cursor().unwrap();
+        assert_eq!((resumed.after_seq, resumed.from_slot), (42, 300));
+        assert!(query(None, None, Some("42")).cursor().is_err());
+        assert!(query(None, None, Some("a-b")).cursor().is_err());
+    }
+
+    #[test]
+    fn test_candles_query_resolve() {
+        let query = |interval: Option<&str>, from, to| CandlesQuery {
+            pool: "pool".to_string(),
+            interval: interval.map(str::to_string),
+            from,
+            to,
+        };
+
+        assert_eq!(
+            query(None, None, None).resolve(100_000),
+            Ok((Interval::M1, 100_000 - 60 * MAX_CANDLES as i64, 100_000))
+        );
+        assert_eq!(
+            query(Some("1h"), Some(10), Some(20)).resolve(0),
+            Ok((Interval::H1, 10, 20))
+        );
+        assert!(query(Some("7m"), None, None).resolve(0).is_err());
+        assert!(query(None, Some(20), Some(10)).resolve(0).is_err());
+    }
+
+    #[test]
+    fn test_swaps_query() {
+        let event = SwapEvent::builder()
+            .signature("sig")
+            .pool("PoolA")
+            .input_token(crate::output::TokenInfo::new("MintA", 1))
+            .build();
+
+        let query = SwapsQuery::default();
+        assert_eq!(query.limit(), DEFAULT_SWAPS_LIMIT);
+        assert!(query.matches(&event, None));
+        assert!(query.matches(&event, Some("PoolA")));
+        assert!(!query.matches(&event, Some("PoolB")));
+
+        let query = SwapsQuery {
+            token: Some("MintB".to_string()),
+            limit: Some(50_000),
+        };
+        assert_eq!(query.limit(), MAX_SWAPS);
+        assert!(!query.matches(&event, None));
+    }
+}
@@ -0,0 +1,164 @@
+//! In-memory ring buffer of recent swaps served by the query API.
+
+use {
+    crate::{
+        labels::AddressLabels,
+        output::{EventType, SwapEvent},
+    },
+    std::{
+        collections::{HashSet, VecDeque},
+        sync::Mutex,
+    },
+};
+
+/// Keeps the most recent swaps, evicting the oldest once full.
+pub struct RecentSwaps {
+    capacity: usize,
+    swaps: Mutex<VecDeque<SwapEvent>>,
+}
+
+impl RecentSwaps {
+    /// Creates a buffer holding up to `capacity` swaps.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            swaps: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Records an event; anything other than a swap is ignored.
+    pub fn record(&self, event: &SwapEvent) {
+        if self.capacity == 0 || event.event_type != EventType::Swap {
+            return;
+        }
+
+        let mut swaps = self.swaps.lock().unwrap_or_else(|e| e.into_inner());
+        if swaps.len() == self.capacity {
+            swaps.pop_front();
+        }
+        swaps.push_back(event.clone());
+    }
+
+    /// Returns up to `limit` swaps matching a predicate, newest first.
+    pub fn query(&self, limit: usize, predicate: impl Fn(&SwapEvent) -> bool) -> Vec<SwapEvent> {
+        let swaps = self.swaps.lock().unwrap_or_else(|e| e.into_inner());
+        swaps
+            .iter()
+            .rev()
+            .filter(|event| predicate(event))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns up to `limit` distinct pools of buffered swaps, most recently
+    /// traded first.
+    pub fn pools(&self, limit: usize) -> Vec<String> {
+        let swaps = self.swaps.lock().unwrap_or_else(|e| e.into_inner());
+        let mut seen = HashSet::new();
+        swaps
+            .iter()
+            .rev()
+            .filter(|event| seen.insert(event.pool.as_str()))
+            .take(limit)
+            .map(|event| event.pool.clone())
+            .collect()
+    }
+
+    /// Re-annotates buffered swaps with new address labels.
+    pub fn relabel(&self, labels: &AddressLabels) {
+        let mut swaps = self.swaps.lock().unwrap_or_else(|e| e.into_inner());
+        for event in swaps.iter_mut() {
+            event.apply_labels(labels);
+        }
+    }
+
+    /// Returns the number of buffered swaps.
+    pub fn len(&self) -> usize {
+        self.swaps.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Returns the maximum number of buffered swaps.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+}
+
+/// Returns `true` if a swap trades the given mint on either side.
+pub fn trades_token(event: &SwapEvent, mint: &str) -> bool {
+    [&event.input_token, &event.output_token]
+        .into_iter()
+        .flatten()
+        .any(|token| token.mint == mint)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{Protocol, TokenInfo},
+    };
+
+    fn swap(signature: &str, pool: &str, mint: &str) -> SwapEvent {
+        SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature(signature)
+            .pool(pool)
+            .input_token(TokenInfo::new(mint, 1))
+            .output_token(TokenInfo::new(
+                "So11111111111111111111111111111111111111112",
+                1,
+            ))
+            .build()
+    }
+
+    #[test]
+    fn test_ring_buffer_evicts_oldest() {
+        let recent = RecentSwaps::new(2);
+        recent.record(&swap("a", "P1", "X"));
+        recent.record(&swap("b", "P2", "Y"));
+        recent.record(&swap("c", "P1", "Y"));
+
+        let mut created = swap("d", "P1", "X");
+        created.event_type = EventType::CreatePool;
+        recent.record(&created);
+
+        assert_eq!(recent.len(), 2);
+        let signatures: Vec<_> = recent
+            .query(10, |_| true)
+            .iter()
+            .map(|event| event.signature.clone())
+            .collect();
+        assert_eq!(signatures, vec!["c", "b"]);
+
+        assert_eq!(recent.query(10, |event| event.pool == "P1").len(), 1);
+        assert_eq!(recent.query(1, |event| trades_token(event, "Y")).len(), 1);
+        assert!(recent
+            .query(10, |event| trades_token(event, "X"))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_pools_most_recent_first() {
+        let recent = RecentSwaps::new(10);
+        recent.record(&swap("a", "P1", "X"));
+        recent.record(&swap("b", "P2", "X"));
+        recent.record(&swap("c", "P1", "X"));
+        assert_eq!(recent.pools(10), vec!["P1", "P2"]);
+        assert_eq!(recent.pools(1), vec!["P1"]);
+    }
+
+    #[test]
+    fn test_relabel_buffered_swaps() {
+        const WHALE: &str = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+        let recent = RecentSwaps::new(10);
+        let mut event = swap("a", "P1", "X");
+        event.maker = Some(WHALE.to_string());
+        recent.record(&event);
+
+        let labels = AddressLabels::parse(&format!("[labels]\n{WHALE} = \"whale-3\"")).unwrap();
+        recent.relabel(&labels);
+        let swaps = recent.query(10, |_| true);
+        assert_eq!(swaps[0].maker_label.as_deref(), Some("whale-3"));
+    }
+}
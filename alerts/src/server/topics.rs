@@ -0,0 +1,171 @@
+//! Stream topics and their derived payloads.
+
+use {
+    crate::output::{
+        swap_event::{USDC_MINT, USDT_MINT, WSOL_MINT},
+        EventType, SwapEvent, TokenInfo,
+    },
+    serde::Serialize,
+    std::fmt,
+};
+
+/// A topic clients can subscribe to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Topic {
+    /// Price ticks for one pool (`price:<pool>`)
+    Price(String),
+    /// Fields of emitted events resolved after the fact (`updates`)
+    Updates,
+}
+
+impl Topic {
+    /// Parses a topic name such as `price:<pool>` or `updates`.
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim();
+        if name == "updates" {
+            return Some(Self::Updates);
+        }
+        let (kind, arg) = name.split_once(':')?;
+        match kind {
+            "price" if !arg.is_empty() => Some(Self::Price(arg.to_string())),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Topic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Price(pool) => write!(f, "price:{pool}"),
+            Self::Updates => write!(f, "updates"),
+        }
+    }
+}
+
+/// Compact price update derived from a swap.
+///
+/// `price` is the pool's non-base token priced in the base token (SOL, USDC,
+/// or USDT when one side is a base token, otherwise the input token).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PriceTick {
+    /// Price of one quote token in base token units
+    pub price: f64,
+    /// Price of one quote token in USD, if the base token's USD value is known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price_usd: Option<f64>,
+    /// Swap volume in base token units
+    pub volume: f64,
+    /// Swap volume in USD, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub volume_usd: Option<f64>,
+    /// Base token mint the price is denominated in
+    pub base_mint: String,
+    /// Unix timestamp in seconds
+    pub ts: i64,
+    /// Block slot
+    pub slot: u64,
+}
+
+impl PriceTick {
+    /// Derives a price tick from a swap event.
+    ///
+    /// Returns `None` for non-swap events or when either human-readable
+    /// amount is unknown (decimals not resolved) or zero.
+    pub fn from_event(event: &SwapEvent) -> Option<Self> {
+        if event.event_type != EventType::Swap {
+            return None;
+        }
+        let (Some(base), Some(quote)) = event.get_base_quote_tokens() else {
+            return None;
+        };
+
+        let volume = ui_amount(base)?;
+        let quote_amount = ui_amount(quote)?;
+        if volume <= 0.0 || quote_amount <= 0.0 {
+            return None;
+        }
+
+        let volume_usd = event.usd_value();
+        Some(Self {
+            price: volume / quote_amount,
+            price_usd: volume_usd.map(|usd| usd / quote_amount),
+            volume,
+            volume_usd,
+            base_mint: base.mint.clone(),
+            ts: event.timestamp.unwrap_or_else(unix_now),
+            slot: event.slot,
+        })
+    }
+}
+
+/// Returns the human-readable amount, falling back to well-known decimals.
+fn ui_amount(token: &TokenInfo) -> Option<f64> {
+    token.amount.or_else(|| {
+        let decimals = match token.mint.as_str() {
+            WSOL_MINT => i32::from(crate::chain::profile().native_decimals),
+            USDC_MINT | USDT_MINT => 6,
+            _ => return None,
+        };
+        Some(token.amount_raw as f64 / 10_f64.powi(decimals))
+    })
+}
+
+/// Current Unix time in seconds, from the installed [`clock`](crate::clock).
+pub(crate) fn unix_now() -> i64 {
+    crate::clock::unix_now()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::output::Protocol};
+
+    #[test]
+    fn test_topic_parse() {
+        assert_eq!(
+            Topic::parse("price:Pool111"),
+            Some(Topic::Price("Pool111".to_string()))
+        );
+        assert_eq!(Topic::parse("price:"), None);
+        assert_eq!(Topic::parse("candles:Pool111"), None);
+        assert_eq!(Topic::parse("price"), None);
+        assert_eq!(Topic::Price("P".to_string()).to_string(), "price:P");
+        assert_eq!(Topic::parse(" updates "), Some(Topic::Updates));
+        assert_eq!(Topic::parse("updates:P"), None);
+        assert_eq!(Topic::Updates.to_string(), "updates");
+    }
+
+    #[test]
+    fn test_price_tick_from_swap() {
+        let mut quote = TokenInfo::new("MemeMint", 2_000_000).with_decimals(6);
+        quote.amount_usd = Some(300.0);
+        let event = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .input_token(quote)
+            .output_token(TokenInfo::new(WSOL_MINT, 2_000_000_000))
+            .slot(7)
+            .timestamp(1_700_000_000)
+            .build();
+
+        let tick = PriceTick::from_event(&event).unwrap();
+        assert_eq!(tick.price, 1.0);
+        assert_eq!(tick.volume, 2.0);
+        assert_eq!(tick.price_usd, Some(150.0));
+        assert_eq!(tick.base_mint, WSOL_MINT);
+        assert_eq!(tick.ts, 1_700_000_000);
+        assert_eq!(tick.slot, 7);
+    }
+
+    #[test]
+    fn test_price_tick_requires_decimals() {
+        let event = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .input_token(TokenInfo::new("MemeMint", 2_000_000))
+            .output_token(TokenInfo::new(WSOL_MINT, 2_000_000_000))
+            .build();
+        assert!(PriceTick::from_event(&event).is_none());
+    }
+}
@@ -0,0 +1,163 @@
+//! Live WebSocket feed of normalized events.
+//!
+//! `GET /ws/swaps?token=<mint>&pool=<pool>&min_usd=1000` streams every emitted
+//! [`SwapEvent`] matching the connection's filter as JSON. Clients replace
+//! their filter by sending it as a message, e.g. `{"pool": "<pool>"}`; the
+//! server replies with `{"filter": {...}}` or `{"error": "..."}`.
+
+use {
+    super::{hub::FeedMessage, recent::trades_token, AppState},
+    crate::output::SwapEvent,
+    axum::{
+        extract::{
+            ws::{Message, WebSocket, WebSocketUpgrade},
+            Query, State,
+        },
+        response::Response,
+    },
+    serde::{Deserialize, Serialize},
+    std::sync::Arc,
+    tokio::sync::broadcast::{error::RecvError, Receiver},
+};
+
+/// Per-connection filter of the live feed; unset fields match everything.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeedFilter {
+    /// Only events trading this mint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Only events in this pool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool: Option<String>,
+    /// Only events with a known USD value of at least this much
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_usd: Option<f64>,
+}
+
+impl FeedFilter {
+    /// Returns `true` if an event passes the filter.
+    pub fn matches(&self, event: &SwapEvent) -> bool {
+        self.pool.as_deref().is_none_or(|pool| event.pool == pool)
+            && self
+                .token
+                .as_deref()
+                .is_none_or(|mint| trades_token(event, mint))
+            && self
+                .min_usd
+                .is_none_or(|min| event.usd_value().is_some_and(|usd| usd >= min))
+    }
+
+    /// Drops empty fields, so `?token=` matches every token.
+    pub(super) fn normalize(mut self) -> Self {
+        self.token = self.token.filter(|token| !token.trim().is_empty());
+        self.pool = self.pool.filter(|pool| !pool.trim().is_empty());
+        self
+    }
+}
+
+pub(super) async fn feed_handler(
+    ws: WebSocketUpgrade,
+    Query(filter): Query<FeedFilter>,
+    State(state): State<AppState>,
+) -> Response {
+    let feed = state.hub.subscribe_feed();
+    ws.on_upgrade(move |socket| handle_socket(socket, feed, filter.normalize()))
+}
+
+/// Forwards matching events and handles filter updates.
+async fn handle_socket(
+    mut socket: WebSocket,
+    mut feed: Receiver<Arc<FeedMessage>>,
+    mut filter: FeedFilter,
+) {
+    loop {
+        tokio::select! {
+            message = feed.recv() => match message {
+                Ok(message) => {
+                    if filter.matches(&message.event)
+                        && socket.send(Message::Text(message.json.clone().into())).await.is_err()
+                    {
+                        break;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    log::debug!("Feed client lagged, skipped {skipped} event(s)");
+                }
+                Err(RecvError::Closed) => break,
+            },
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Text(text))) => {
+                    let reply = apply_filter(&text, &mut filter);
+                    if socket.send(Message::Text(reply.into())).await.is_err() {
+                        break;
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+/// Replaces the filter with one sent by the client and returns the JSON reply.
+fn apply_filter(text: &str, filter: &mut FeedFilter) -> String {
+    match serde_json::from_str::<FeedFilter>(text) {
+        Ok(update) => {
+            *filter = update.normalize();
+            serde_json::json!({ "filter": filter }).to_string()
+        }
+        Err(e) => serde_json::json!({ "error": format!("invalid filter: {e}") }).to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{Protocol, TokenInfo},
+    };
+
+    fn event() -> SwapEvent {
+        let mut input = TokenInfo::new("MintA", 1);
+        input.amount_usd = Some(500.0);
+        SwapEvent::builder()
+            .protocol(Protocol::Clmm)
+            .signature("sig")
+            .pool("PoolA")
+            .input_token(input)
+            .build()
+    }
+
+    #[test]
+    fn test_feed_filter_matches() {
+        let event = event();
+        assert!(FeedFilter::default().matches(&event));
+
+        let filter = |token: Option<&str>, pool: Option<&str>, min_usd| {
+            FeedFilter {
+                token: token.map(str::to_string),
+                pool: pool.map(str::to_string),
+                min_usd,
+            }
+            .normalize()
+        };
+        assert!(filter(Some("MintA"), Some("PoolA"), Some(500.0)).matches(&event));
+        assert!(filter(Some(""), None, None).matches(&event));
+        assert!(!filter(Some("MintB"), None, None).matches(&event));
+        assert!(!filter(None, Some("PoolB"), None).matches(&event));
+        assert!(!filter(None, None, Some(501.0)).matches(&event));
+    }
+
+    #[test]
+    fn test_apply_filter() {
+        let mut filter = FeedFilter::default();
+
+        let reply = apply_filter(r#"{"pool":"PoolA","min_usd":100}"#, &mut filter);
+        assert_eq!(reply, r#"{"filter":{"min_usd":100.0,"pool":"PoolA"}}"#);
+        assert_eq!(filter.pool.as_deref(), Some("PoolA"));
+
+        assert!(apply_filter(r#"{"bogus":1}"#, &mut filter).contains("invalid filter"));
+        assert_eq!(filter.min_usd, Some(100.0));
+    }
+}
@@ -0,0 +1,292 @@
+//! Integration with system service supervisors.
+//!
+//! Under systemd (`Type=notify`), readiness and shutdown are reported over
+//! `NOTIFY_SOCKET`, and with `WatchdogSec=` set the watchdog is pinged at half
+//! its interval as long as the pipeline makes progress (see
+//! [`Heartbeat`]). When the pipeline stalls for `WATCHDOG_STALL_SECS`, pings
+//! stop and systemd restarts the process.
+//!
+//! On Windows, [`start`] connects to the service control manager when the
+//! process was started as a service; a stop or system shutdown request then
+//! shuts the pipeline down like Ctrl+C.
+//!
+//! Outside a supervisor every function is a no-op.
+
+use {
+    crate::metrics::Heartbeat,
+    std::{env, sync::Arc, time::Duration},
+};
+
+/// Default time without pipeline progress after which watchdog pings stop.
+const DEFAULT_STALL: Duration = Duration::from_secs(120);
+
+/// Service watchdog configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WatchdogConfig {
+    /// Interval between watchdog pings
+    pub interval: Duration,
+    /// Time without pipeline progress after which pings stop
+    pub stall: Duration,
+}
+
+impl WatchdogConfig {
+    /// Creates watchdog configuration from environment variables, or `None`
+    /// if the supervisor did not enable a watchdog for this process.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `WATCHDOG_USEC`, `WATCHDOG_PID` - Set by systemd from `WatchdogSec=`; pings are
+    ///   sent at half the interval
+    /// - `WATCHDOG_STALL_SECS` - Optional: Seconds without pipeline progress after which
+    ///   pings stop (default: 120)
+    pub fn from_env() -> Option<Self> {
+        let usec: u64 = env::var("WATCHDOG_USEC").ok()?.trim().parse().ok()?;
+        if let Ok(pid) = env::var("WATCHDOG_PID") {
+            if pid.trim().parse::<u32>().ok() != Some(std::process::id()) {
+                return None;
+            }
+        }
+        let stall = env::var("WATCHDOG_STALL_SECS")
+            .ok()
+            .and_then(|secs| secs.trim().parse().ok())
+            .map_or(DEFAULT_STALL, Duration::from_secs);
+        Some(Self {
+            interval: Duration::from_micros(usec / 2).max(Duration::from_millis(100)),
+            stall,
+        })
+    }
+}
+
+/// Connects to the Windows service control manager if started as a service.
+pub fn start() {
+    #[cfg(windows)]
+    windows::start();
+}
+
+/// Reports that the pipeline is built and about to run.
+pub fn ready() {
+    #[cfg(unix)]
+    notify("READY=1");
+    #[cfg(windows)]
+    windows::ready();
+}
+
+/// Reports that shutdown started and may take up to `timeout`.
+pub fn stopping(timeout: Duration) {
+    #[cfg(unix)]
+    notify("STOPPING=1");
+    #[cfg(windows)]
+    windows::stopping(timeout);
+    #[cfg(not(windows))]
+    let _ = timeout;
+}
+
+/// Reports that shutdown finished, with the pipeline's outcome.
+pub fn stopped(success: bool) {
+    #[cfg(windows)]
+    windows::stopped(success);
+    #[cfg(not(windows))]
+    let _ = success;
+}
+
+/// Waits for the service control manager to request a stop; never returns
+/// outside a Windows service.
+#[cfg(not(unix))]
+pub async fn stop_requested() {
+    #[cfg(windows)]
+    windows::stop_requested().await;
+    #[cfg(not(windows))]
+    std::future::pending::<()>().await;
+}
+
+/// Spawns the task pinging the systemd watchdog while `heartbeat` beats.
+pub fn spawn_watchdog(config: WatchdogConfig, heartbeat: Arc<Heartbeat>) {
+    log::info!(
+        "Service watchdog enabled (ping every {:?}, stall after {:?})",
+        config.interval,
+        config.stall
+    );
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(config.interval);
+        let mut stalled = false;
+        loop {
+            interval.tick().await;
+            let age = heartbeat.age();
+            if age < config.stall {
+                stalled = false;
+                #[cfg(unix)]
+                notify("WATCHDOG=1");
+            } else if !stalled {
+                stalled = true;
+                log::error!("No pipeline progress for {age:?}, withholding watchdog pings");
+            }
+        }
+    });
+}
+
+/// Sends a state to systemd if it started the process with `NOTIFY_SOCKET`.
+#[cfg(unix)]
+fn notify(state: &str) {
+    let Some(path) = env::var_os("NOTIFY_SOCKET") else {
+        return;
+    };
+    if let Err(e) = send_notify(&path, state) {
+        log::warn!("Failed to notify systemd ({state}): {e}");
+    }
+}
+
+/// Sends a state datagram to a notify socket path, or to an abstract socket
+/// if the path starts with `@`.
+#[cfg(unix)]
+fn send_notify(path: &std::ffi::OsStr, state: &str) -> std::io::Result<()> {
+    use std::os::unix::{ffi::OsStrExt, net::UnixDatagram};
+
+    let socket = UnixDatagram::unbound()?;
+    #[cfg(target_os = "linux")]
+    if let Some(name) = path.as_bytes().strip_prefix(b"@") {
+        use std::os::{linux::net::SocketAddrExt, unix::net::SocketAddr};
+        let addr = SocketAddr::from_abstract_name(name)?;
+        socket.send_to_addr(state.as_bytes(), &addr)?;
+        return Ok(());
+    }
+    socket.send_to(state.as_bytes(), path)?;
+    Ok(())
+}
+
+/// Windows service control manager integration.
+#[cfg(windows)]
+mod windows {
+    use {
+        std::{
+            ffi::OsString,
+            sync::{
+                atomic::{AtomicBool, Ordering},
+                OnceLock,
+            },
+            time::Duration,
+        },
+        tokio::sync::Notify,
+        windows_service::{
+            define_windows_service,
+            service::{
+                ServiceControl, ServiceControlAccept, ServiceExitCode, ServiceState, ServiceStatus,
+                ServiceType,
+            },
+            service_control_handler::{self, ServiceControlHandlerResult, ServiceStatusHandle},
+            service_dispatcher,
+        },
+    };
+
+    /// Service name; ignored by the control manager for own-process services.
+    const SERVICE_NAME: &str = "raydium-alerts";
+
+    /// Time the control manager waits for the pipeline to be built.
+    const START_WAIT_HINT: Duration = Duration::from_secs(30);
+
+    static STATUS: OnceLock<ServiceStatusHandle> = OnceLock::new();
+    static READY: AtomicBool = AtomicBool::new(false);
+
+    define_windows_service!(ffi_service_main, service_main);
+
+    fn stop() -> &'static Notify {
+        static STOP: OnceLock<Notify> = OnceLock::new();
+        STOP.get_or_init(Notify::new)
+    }
+
+    /// Runs the service dispatcher on its own thread; it fails right away
+    /// when the process was not started by the control manager.
+    pub fn start() {
+        std::thread::spawn(|| {
+            if let Err(e) = service_dispatcher::start(SERVICE_NAME, ffi_service_main) {
+                log::debug!("Not running as a Windows service: {e}");
+            }
+        });
+    }
+
+    fn service_main(_arguments: Vec<OsString>) {
+        let handler = |control| match control {
+            ServiceControl::Stop | ServiceControl::Shutdown => {
+                stop().notify_one();
+                ServiceControlHandlerResult::NoError
+            }
+            ServiceControl::Interrogate => ServiceControlHandlerResult::NoError,
+            _ => ServiceControlHandlerResult::NotImplemented,
+        };
+        match service_control_handler::register(SERVICE_NAME, handler) {
+            Ok(handle) => {
+                let _ = STATUS.set(handle);
+                if READY.load(Ordering::SeqCst) {
+                    ready();
+                } else {
+                    set(ServiceState::StartPending, START_WAIT_HINT, 0);
+                }
+            }
+            Err(e) => log::error!("Failed to register the service control handler: {e}"),
+        }
+    }
+
+    /// Reports `state` if running as a service.
+    fn set(state: ServiceState, wait_hint: Duration, exit_code: u32) {
+        let Some(handle) = STATUS.get() else {
+            return;
+        };
+        let controls_accepted = match state {
+            ServiceState::Running => ServiceControlAccept::STOP | ServiceControlAccept::SHUTDOWN,
+            _ => ServiceControlAccept::empty(),
+        };
+        let status = ServiceStatus {
+            service_type: ServiceType::OWN_PROCESS,
+            current_state: state,
+            controls_accepted,
+            exit_code: ServiceExitCode::Win32(exit_code),
+            checkpoint: 0,
+            wait_hint,
+            process_id: None,
+        };
+        if let Err(e) = handle.set_service_status(status) {
+            log::warn!("Failed to report service status {state:?}: {e}");
+        }
+    }
+
+    pub fn ready() {
+        READY.store(true, Ordering::SeqCst);
+        set(ServiceState::Running, Duration::ZERO, 0);
+    }
+
+    pub fn stopping(timeout: Duration) {
+        set(ServiceState::StopPending, timeout + START_WAIT_HINT, 0);
+    }
+
+    pub fn stopped(success: bool) {
+        set(
+            ServiceState::Stopped,
+            Duration::ZERO,
+            if success { 0 } else { 1 },
+        );
+    }
+
+    pub async fn stop_requested() {
+        stop().notified().await;
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use {super::*, std::os::unix::net::UnixDatagram};
+
+    #[test]
+    fn test_send_notify() {
+        let dir = env::temp_dir().join(format!("raydium-alerts-notify-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("notify.sock");
+        let _ = std::fs::remove_file(&path);
+        let socket = UnixDatagram::bind(&path).unwrap();
+
+        send_notify(path.as_os_str(), "READY=1").unwrap();
+        let mut buf = [0; 16];
+        let len = socket.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..len], b"READY=1");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
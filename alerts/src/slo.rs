@@ -0,0 +1,706 @@
+//! In-process service level objectives.
+//!
+//! Operational guarantees are configured in the TOML file at `SLO_FILE` and
+//! evaluated continuously on the metrics the monitor records:
+//!
+//! ```toml
+//! # At least 100 events per minute during US market hours (UTC)
+//! [[slo]]
+//! name = "throughput"
+//! metric = "events_emitted"
+//! stat = "rate"
+//! min = 100
+//! hours = "13:30-20:00"
+//! days = ["mon", "tue", "wed", "thu", "fri"]
+//!
+//! # Webhook p95 delivery under 2 seconds
+//! [[slo]]
+//! name = "webhook-latency"
+//! metric = "webhook_delivery_ms"
+//! stat = "p95"
+//! max = 2000
+//! window_secs = 300
+//! ```
+//!
+//! [`SloMonitor`] is registered as a metrics backend, so any pipeline counter,
+//! gauge, or histogram can be an SLO metric; the webhook notifier reports its
+//! deliveries to it as well. Every [`EVALUATION_INTERVAL`], each SLO within
+//! its schedule is evaluated over its sliding window, once the window has
+//! been observed in full. A breach and the recovery after it are logged and
+//! posted as an [`SloAlert`] to `SLO_WEBHOOK_URL`.
+
+use {
+    crate::server::unix_now,
+    async_trait::async_trait,
+    carbon_core::{error::CarbonResult, metrics::Metrics},
+    serde::{Deserialize, Serialize},
+    std::{
+        collections::VecDeque,
+        env, fmt, fs,
+        path::Path,
+        sync::{Arc, Mutex, PoisonError},
+        time::Duration,
+    },
+};
+
+/// Time between evaluations of the SLOs.
+pub const EVALUATION_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Default sliding window of an SLO.
+const DEFAULT_WINDOW_SECS: u64 = 60;
+
+/// Samples kept per SLO; the oldest are dropped beyond it.
+const MAX_SAMPLES: usize = 100_000;
+
+/// Timeout of alert webhook requests.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Errors that can occur while loading SLOs.
+#[derive(Debug)]
+pub enum SloError {
+    /// The SLO file could not be read
+    Read(String),
+    /// The SLO file is not valid TOML or has an unknown shape
+    Parse(String),
+    /// An SLO is invalid (e.g., unknown stat, no objective)
+    Invalid(String),
+}
+
+impl fmt::Display for SloError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(e) => write!(f, "failed to read SLO file: {e}"),
+            Self::Parse(e) => write!(f, "failed to parse SLO file: {e}"),
+            Self::Invalid(e) => write!(f, "invalid SLO: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for SloError {}
+
+/// Statistic of a metric's samples an objective applies to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stat {
+    /// Counter increments (or histogram samples) per minute
+    Rate,
+    /// Counter increments (or histogram samples) in the window
+    Count,
+    /// Mean sample value
+    Mean,
+    /// Largest sample value
+    Max,
+    /// Sample value at a percentile (e.g., 95 for `p95`)
+    Percentile(u8),
+}
+
+impl Stat {
+    /// Parses a statistic name: `rate`, `count`, `mean`, `max`, or `p<N>`.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "rate" => Some(Self::Rate),
+            "count" => Some(Self::Count),
+            "mean" | "avg" => Some(Self::Mean),
+            "max" => Some(Self::Max),
+            other => other
+                .strip_prefix('p')
+                .and_then(|pct| pct.parse().ok())
+                .filter(|pct| (1..=100).contains(pct))
+                .map(Self::Percentile),
+        }
+    }
+}
+
+impl fmt::Display for Stat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Rate => write!(f, "rate"),
+            Self::Count => write!(f, "count"),
+            Self::Mean => write!(f, "mean"),
+            Self::Max => write!(f, "max"),
+            Self::Percentile(pct) => write!(f, "p{pct}"),
+        }
+    }
+}
+
+/// Bound an SLO's statistic must stay within.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Objective {
+    /// The statistic must be at least the value
+    AtLeast(f64),
+    /// The statistic must be at most the value
+    AtMost(f64),
+}
+
+impl Objective {
+    /// Returns `true` if `value` meets the objective.
+    pub fn is_met(self, value: f64) -> bool {
+        match self {
+            Self::AtLeast(min) => value >= min,
+            Self::AtMost(max) => value <= max,
+        }
+    }
+}
+
+impl fmt::Display for Objective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::AtLeast(min) => write!(f, ">= {min}"),
+            Self::AtMost(max) => write!(f, "<= {max}"),
+        }
+    }
+}
+
+/// UTC hours and weekdays during which an SLO applies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Schedule {
+    /// Start and end minute of the day; the end may be before the start
+    /// for hours past midnight
+    hours: Option<(u32, u32)>,
+    /// Weekday bitmask, Monday first
+    days: Option<u8>,
+}
+
+impl Schedule {
+    const DAYS: [&'static str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+    /// Parses hours (`HH:MM-HH:MM`) and weekday names (`mon` .. `sun`).
+    pub fn parse(hours: Option<&str>, days: Option<&[String]>) -> Result<Self, String> {
+        let hours = hours
+            .map(|range| {
+                let (start, end) = range
+                    .split_once('-')
+                    .ok_or_else(|| format!("hours '{range}' must be HH:MM-HH:MM"))?;
+                Ok::<_, String>((minute_of_day(start)?, minute_of_day(end)?))
+            })
+            .transpose()?;
+        let days = days
+            .map(|days| {
+                days.iter().try_fold(0u8, |mask, day| {
+                    let index = Self::DAYS
+                        .iter()
+                        .position(|name| day.trim().eq_ignore_ascii_case(name))
+                        .ok_or_else(|| format!("unknown day '{day}'"))?;
+                    Ok::<_, String>(mask | (1 << index))
+                })
+            })
+            .transpose()?;
+        Ok(Self { hours, days })
+    }
+
+    /// Returns `true` if the SLO applies at Unix time `now`.
+    pub fn contains(&self, now: i64) -> bool {
+        let day = now.div_euclid(86_400);
+        let minute = (now.rem_euclid(86_400) / 60) as u32;
+        // 1970-01-01 was a Thursday
+        let weekday = (day + 3).rem_euclid(7) as u8;
+        let in_days = self.days.is_none_or(|mask| mask & (1 << weekday) != 0);
+        let in_hours = self.hours.is_none_or(|(start, end)| {
+            if start <= end {
+                (start..end).contains(&minute)
+            } else {
+                minute >= start || minute < end
+            }
+        });
+        in_days && in_hours
+    }
+}
+
+/// Parses `HH:MM` into the minute of the day.
+fn minute_of_day(time: &str) -> Result<u32, String> {
+    let invalid = || format!("invalid time '{}'", time.trim());
+    let (hour, minute) = time.trim().split_once(':').ok_or_else(invalid)?;
+    let hour: u32 = hour.parse().map_err(|_| invalid())?;
+    let minute: u32 = minute.parse().map_err(|_| invalid())?;
+    if hour > 24 || minute > 59 || (hour == 24 && minute > 0) {
+        return Err(invalid());
+    }
+    Ok(hour * 60 + minute)
+}
+
+/// A service level objective.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Slo {
+    /// Name shown in alerts
+    pub name: String,
+    /// Counter, gauge, or histogram the SLO is evaluated on
+    pub metric: String,
+    /// Statistic of the metric's samples in the window
+    pub stat: Stat,
+    /// Bound the statistic must stay within
+    pub objective: Objective,
+    /// Sliding window the statistic is computed over
+    pub window: Duration,
+    /// When the SLO applies
+    pub schedule: Schedule,
+}
+
+/// An SLO as written in the SLO file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SloEntry {
+    name: String,
+    metric: String,
+    stat: String,
+    #[serde(default)]
+    min: Option<f64>,
+    #[serde(default)]
+    max: Option<f64>,
+    #[serde(default)]
+    window_secs: Option<u64>,
+    #[serde(default)]
+    hours: Option<String>,
+    #[serde(default)]
+    days: Option<Vec<String>>,
+}
+
+/// Contents of the SLO file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SloFile {
+    #[serde(default)]
+    slo: Vec<SloEntry>,
+}
+
+impl TryFrom<SloEntry> for Slo {
+    type Error = SloError;
+
+    fn try_from(entry: SloEntry) -> Result<Self, SloError> {
+        let invalid = |e: String| SloError::Invalid(format!("{}: {e}", entry.name));
+        let stat = Stat::parse(&entry.stat)
+            .ok_or_else(|| invalid(format!("unknown stat '{}'", entry.stat)))?;
+        let objective = match (entry.min, entry.max) {
+            (Some(min), None) => Objective::AtLeast(min),
+            (None, Some(max)) => Objective::AtMost(max),
+            _ => {
+                return Err(invalid(
+                    "exactly one of min and max is required".to_string(),
+                ))
+            }
+        };
+        let window_secs = entry.window_secs.unwrap_or(DEFAULT_WINDOW_SECS);
+        if window_secs == 0 {
+            return Err(invalid("window_secs must be positive".to_string()));
+        }
+        let schedule =
+            Schedule::parse(entry.hours.as_deref(), entry.days.as_deref()).map_err(invalid)?;
+        Ok(Self {
+            name: entry.name,
+            metric: entry.metric,
+            stat,
+            objective,
+            window: Duration::from_secs(window_secs),
+            schedule,
+        })
+    }
+}
+
+/// SLOs and where their alerts are posted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SloConfig {
+    /// Objectives to evaluate
+    pub slos: Vec<Slo>,
+    /// URL breach and recovery alerts are posted to as JSON
+    pub webhook_url: Option<String>,
+}
+
+impl SloConfig {
+    /// Creates SLO configuration from environment variables.
+    ///
+    /// Returns `Ok(None)` if `SLO_FILE` is not set.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `SLO_FILE` - Optional: TOML file of SLOs; enables the monitor
+    /// - `SLO_WEBHOOK_URL` - Optional: URL breach and recovery alerts are posted to
+    pub fn from_env() -> Result<Option<Self>, SloError> {
+        let Some(path) = env::var("SLO_FILE").ok().filter(|p| !p.trim().is_empty()) else {
+            return Ok(None);
+        };
+        let slos = Self::load(path.trim())?;
+        let webhook_url = env::var("SLO_WEBHOOK_URL")
+            .ok()
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty());
+        Ok(Some(Self { slos, webhook_url }))
+    }
+
+    /// Loads SLOs from a TOML file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Vec<Slo>, SloError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)
+            .map_err(|e| SloError::Read(format!("{}: {e}", path.display())))?;
+        Self::parse(&content)
+    }
+
+    /// Parses SLOs from TOML content.
+    pub fn parse(content: &str) -> Result<Vec<Slo>, SloError> {
+        let file: SloFile = toml::from_str(content).map_err(|e| SloError::Parse(e.to_string()))?;
+        file.slo.into_iter().map(Slo::try_from).collect()
+    }
+}
+
+/// Whether an alert reports a breach or a recovery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SloStatus {
+    /// The objective stopped being met
+    Breached,
+    /// The objective is met again after a breach
+    Recovered,
+}
+
+/// A change of an SLO's status, posted as JSON to the alert webhook.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SloAlert {
+    /// SLO name
+    pub slo: String,
+    /// New status
+    pub status: SloStatus,
+    /// Evaluated metric
+    pub metric: String,
+    /// Evaluated statistic (e.g., `p95`)
+    pub stat: String,
+    /// Value of the statistic
+    pub value: f64,
+    /// Objective (e.g., `>= 100`)
+    pub objective: String,
+    /// Sliding window in seconds
+    pub window_secs: u64,
+    /// Unix time of the evaluation
+    pub timestamp: i64,
+}
+
+impl fmt::Display for SloAlert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self.status {
+            SloStatus::Breached => "breached",
+            SloStatus::Recovered => "recovered",
+        };
+        write!(
+            f,
+            "SLO '{}' {status}: {} {} {:.2}, objective {} over {}s",
+            self.slo, self.metric, self.stat, self.value, self.objective, self.window_secs
+        )
+    }
+}
+
+/// A recorded metric update.
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    /// Unix time of the update
+    at: i64,
+    /// Sample value, or counter increment
+    value: f64,
+    /// Occurrences: the counter increment, or 1 for a sample
+    count: u64,
+}
+
+/// Samples and status of one SLO.
+#[derive(Debug, Default)]
+struct SloState {
+    samples: VecDeque<Sample>,
+    /// Start of the current stretch within the schedule
+    active_since: Option<i64>,
+    breached: bool,
+}
+
+impl SloState {
+    fn push(&mut self, sample: Sample, counter: bool) {
+        // Counter increments within a second are summed
+        if counter {
+            if let Some(last) = self.samples.back_mut().filter(|last| last.at == sample.at) {
+                last.value += sample.value;
+                last.count += sample.count;
+                return;
+            }
+        }
+        if self.samples.len() >= MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Drops the samples before `since`.
+    fn prune(&mut self, since: i64) {
+        while self.samples.front().is_some_and(|s| s.at < since) {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Computes a statistic over the samples; `None` without samples for
+    /// value statistics.
+    fn stat(&self, stat: Stat, window: Duration) -> Option<f64> {
+        let count: u64 = self.samples.iter().map(|s| s.count).sum();
+        match stat {
+            Stat::Rate => Some(count as f64 * 60.0 / window.as_secs_f64()),
+            Stat::Count => Some(count as f64),
+            _ if self.samples.is_empty() => None,
+            Stat::Mean => {
+                let sum: f64 = self.samples.iter().map(|s| s.value).sum();
+                Some(sum / self.samples.len() as f64)
+            }
+            Stat::Max => self.samples.iter().map(|s| s.value).reduce(f64::max),
+            Stat::Percentile(pct) => {
+                let mut values: Vec<f64> = self.samples.iter().map(|s| s.value).collect();
+                values.sort_by(f64::total_cmp);
+                let rank = (values.len() * usize::from(pct)).div_ceil(100).max(1);
+                values.get(rank - 1).copied()
+            }
+        }
+    }
+}
+
+/// Records metric updates and evaluates SLOs on them.
+pub struct SloMonitor {
+    slos: Vec<Slo>,
+    states: Mutex<Vec<SloState>>,
+}
+
+impl SloMonitor {
+    /// Creates a monitor of the given SLOs.
+    pub fn new(slos: Vec<Slo>) -> Self {
+        let states = slos.iter().map(|_| SloState::default()).collect();
+        Self {
+            slos,
+            states: Mutex::new(states),
+        }
+    }
+
+    /// Records an update of `metric` at Unix time `now`. Updates of metrics
+    /// without an SLO are ignored.
+    pub fn record(&self, metric: &str, value: f64, count: u64, counter: bool, now: i64) {
+        if !self.slos.iter().any(|slo| slo.metric == metric) {
+            return;
+        }
+        let mut states = self.states.lock().unwrap_or_else(PoisonError::into_inner);
+        for (slo, state) in self.slos.iter().zip(states.iter_mut()) {
+            if slo.metric == metric {
+                let sample = Sample {
+                    at: now,
+                    value,
+                    count,
+                };
+                state.push(sample, counter);
+            }
+        }
+    }
+
+    /// Evaluates every SLO at Unix time `now` and returns the status changes.
+    ///
+    /// An SLO is only evaluated within its schedule and once its window has
+    /// been observed in full since it entered the schedule; leaving the
+    /// schedule clears a breach without a recovery alert.
+    pub fn evaluate(&self, now: i64) -> Vec<SloAlert> {
+        let mut states = self.states.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut alerts = Vec::new();
+        for (slo, state) in self.slos.iter().zip(states.iter_mut()) {
+            let window_secs = slo.window.as_secs() as i64;
+            state.prune(now - window_secs);
+            if !slo.schedule.contains(now) {
+                state.active_since = None;
+                state.breached = false;
+                continue;
+            }
+            let since = *state.active_since.get_or_insert(now);
+            if now - since < window_secs {
+                continue;
+            }
+            let Some(value) = state.stat(slo.stat, slo.window) else {
+                continue;
+            };
+
+            let status = match (slo.objective.is_met(value), state.breached) {
+                (false, false) => SloStatus::Breached,
+                (true, true) => SloStatus::Recovered,
+                _ => continue,
+            };
+            state.breached = status == SloStatus::Breached;
+            alerts.push(SloAlert {
+                slo: slo.name.clone(),
+                status,
+                metric: slo.metric.clone(),
+                stat: slo.stat.to_string(),
+                value,
+                objective: slo.objective.to_string(),
+                window_secs: slo.window.as_secs(),
+                timestamp: now,
+            });
+        }
+        alerts
+    }
+
+    /// Spawns the task evaluating the SLOs every [`EVALUATION_INTERVAL`],
+    /// logging alerts and posting them to `webhook_url`.
+    pub fn spawn(self: Arc<Self>, webhook_url: Option<String>) {
+        let client = match reqwest::Client::builder().timeout(WEBHOOK_TIMEOUT).build() {
+            Ok(client) => client,
+            Err(e) => {
+                log::error!("Failed to create HTTP client for SLO alerts: {e}");
+                return;
+            }
+        };
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(EVALUATION_INTERVAL);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                for alert in self.evaluate(unix_now()) {
+                    match alert.status {
+                        SloStatus::Breached => log::error!("{alert}"),
+                        SloStatus::Recovered => log::info!("{alert}"),
+                    }
+                    let Some(ref url) = webhook_url else {
+                        continue;
+                    };
+                    match client.post(url).json(&alert).send().await {
+                        Ok(resp) if resp.status().is_success() => {}
+                        Ok(resp) => {
+                            log::warn!("Failed to post SLO alert: status={}", resp.status())
+                        }
+                        Err(e) => log::warn!("Failed to post SLO alert: {}", e.without_url()),
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl Metrics for SloMonitor {
+    async fn initialize(&self) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn flush(&self) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn update_gauge(&self, name: &str, value: f64) -> CarbonResult<()> {
+        self.record(name, value, 1, false, unix_now());
+        Ok(())
+    }
+
+    async fn increment_counter(&self, name: &str, value: u64) -> CarbonResult<()> {
+        self.record(name, value as f64, value, true, unix_now());
+        Ok(())
+    }
+
+    async fn record_histogram(&self, name: &str, value: f64) -> CarbonResult<()> {
+        self.record(name, value, 1, false, unix_now());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Monday 2024-01-01 00:00 UTC
+    const MONDAY: i64 = 1_704_067_200;
+
+    #[test]
+    fn test_parse_slos() {
+        let slos = SloConfig::parse(
+            r#"
+            [[slo]]
+            name = "throughput"
+            metric = "events_emitted"
+            stat = "rate"
+            min = 100
+            hours = "13:30-20:00"
+            days = ["mon", "fri"]
+
+            [[slo]]
+            name = "latency"
+            metric = "webhook_delivery_ms"
+            stat = "p95"
+            max = 2000
+            window_secs = 300
+            "#,
+        )
+        .unwrap();
+        assert_eq!(slos.len(), 2);
+        assert_eq!(slos[0].objective, Objective::AtLeast(100.0));
+        assert_eq!(slos[0].window, Duration::from_secs(60));
+        assert_eq!(slos[1].stat, Stat::Percentile(95));
+        assert_eq!(slos[1].objective.to_string(), "<= 2000");
+
+        let schedule = slos[0].schedule;
+        assert!(schedule.contains(MONDAY + 14 * 3600));
+        assert!(!schedule.contains(MONDAY + 20 * 3600));
+        // Tuesday
+        assert!(!schedule.contains(MONDAY + 86_400 + 14 * 3600));
+
+        let overnight = Schedule::parse(Some("22:00-02:00"), None).unwrap();
+        assert!(overnight.contains(MONDAY + 3600));
+        assert!(!overnight.contains(MONDAY + 12 * 3600));
+
+        for invalid in [
+            "[[slo]]\nname = \"x\"\nmetric = \"m\"\nstat = \"p0\"\nmax = 1",
+            "[[slo]]\nname = \"x\"\nmetric = \"m\"\nstat = \"rate\"",
+            "[[slo]]\nname = \"x\"\nmetric = \"m\"\nstat = \"rate\"\nmin = 1\nhours = \"9-17\"",
+        ] {
+            assert!(matches!(
+                SloConfig::parse(invalid),
+                Err(SloError::Invalid(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_rate_breach_and_recovery() {
+        let slos = SloConfig::parse(
+            "[[slo]]\nname = \"throughput\"\nmetric = \"events_emitted\"\nstat = \"rate\"\nmin = 100",
+        )
+        .unwrap();
+        let monitor = SloMonitor::new(slos);
+        let start = MONDAY;
+
+        // The first evaluation starts the window; a partial window is not evaluated
+        assert!(monitor.evaluate(start).is_empty());
+        monitor.record("events_emitted", 50.0, 50, true, start + 10);
+        monitor.record("other", 500.0, 500, true, start + 10);
+        assert!(monitor.evaluate(start + 30).is_empty());
+
+        let alerts = monitor.evaluate(start + 60);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].status, SloStatus::Breached);
+        assert_eq!(alerts[0].value, 50.0);
+        // Breaches are reported once
+        assert!(monitor.evaluate(start + 65).is_empty());
+
+        monitor.record("events_emitted", 80.0, 80, true, start + 70);
+        monitor.record("events_emitted", 40.0, 40, true, start + 70);
+        let alerts = monitor.evaluate(start + 90);
+        assert_eq!(alerts[0].status, SloStatus::Recovered);
+        assert_eq!(alerts[0].value, 120.0);
+    }
+
+    #[test]
+    fn test_percentile_over_window() {
+        let slos = SloConfig::parse(
+            "[[slo]]\nname = \"latency\"\nmetric = \"webhook_delivery_ms\"\nstat = \"p95\"\nmax = 2000",
+        )
+        .unwrap();
+        let monitor = SloMonitor::new(slos);
+        assert!(monitor.evaluate(MONDAY).is_empty());
+        for ms in 1..=100 {
+            let ms = if ms > 95 { 5_000.0 } else { ms as f64 };
+            monitor.record("webhook_delivery_ms", ms, 1, false, MONDAY + 30);
+        }
+        // p95 is the 95th smallest sample
+        assert!(monitor.evaluate(MONDAY + 60).is_empty());
+
+        monitor.record("webhook_delivery_ms", 5_000.0, 1, false, MONDAY + 61);
+        let alerts = monitor.evaluate(MONDAY + 62);
+        assert_eq!(alerts[0].status, SloStatus::Breached);
+        assert_eq!(alerts[0].value, 5_000.0);
+
+        // The slow samples leave the window; without samples, nothing is evaluated
+        assert!(monitor.evaluate(MONDAY + 200).is_empty());
+    }
+}
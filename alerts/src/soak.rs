@@ -0,0 +1,658 @@
+//! Soak testing of the pipeline on recorded blocks.
+//!
+//! The `soak` subcommand builds the pipeline exactly like a live run, from
+//! the same environment, but feeds it recorded block notifications instead of
+//! the block subscription. The recording is replayed in a loop, as fast as the
+//! pipeline takes it, with slots and block times shifted on each pass so the
+//! pipeline sees a continuous chain.
+//!
+//! A recording is a file (or a directory of `.jsonl` files) with one
+//! `blockSubscribe` notification value per line, recorded with `base64`
+//! transaction encoding:
+//!
+//! ```json
+//! {"slot":301234567,"block":{"blockhash":"...","transactions":[...],"blockTime":1718000000,...},"err":null}
+//! ```
+//!
+//! Every sampling interval, the process's resident memory, the runtime's
+//! alive tasks, and the transactions and events processed are recorded. After
+//! the warm-up, the first and last quarter of the samples are compared:
+//!
+//! - memory: median resident memory may grow by at most `--max-memory-growth` percent
+//! - tasks: the alive tasks of the last quarter may not exceed those of the first
+//! - throughput: transactions per second may drop by at most `--max-throughput-drop` percent
+//!
+//! After the pipeline is stopped and the outputs drained, every task it
+//! spawned must have ended. The report lists the checks and samples, and the
+//! test fails if any check does.
+
+use {
+    async_trait::async_trait,
+    carbon_core::{
+        datasource::{Datasource, DatasourceId, TransactionUpdate, Update, UpdateType},
+        error::CarbonResult,
+        metrics::{Metrics, MetricsCollection},
+        transformers::transaction_metadata_from_original_meta,
+    },
+    serde::Serialize,
+    solana_client::rpc_response::RpcBlockUpdate,
+    solana_transaction_status_client_types::EncodedTransactionWithStatusMeta,
+    std::{
+        fmt, fs,
+        io::{BufRead, BufReader},
+        path::{Path, PathBuf},
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, Mutex,
+        },
+        time::{Duration, Instant},
+    },
+    tokio::sync::mpsc,
+    tokio_util::sync::CancellationToken,
+};
+
+/// Time allowed for the pipeline's tasks to end after it is stopped.
+const SETTLE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Soak test settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoakConfig {
+    /// Recording file or directory
+    pub recording: PathBuf,
+    /// Test duration
+    pub duration: Duration,
+    /// Time between samples
+    pub interval: Duration,
+    /// Time before the first sample compared
+    pub warmup: Duration,
+    /// Maximum growth of resident memory, in percent
+    pub max_memory_growth_pct: f64,
+    /// Maximum drop of throughput, in percent
+    pub max_throughput_drop_pct: f64,
+    /// Optional file the JSON report is written to
+    pub report: Option<PathBuf>,
+}
+
+/// Transactions forwarded and events emitted during a soak test.
+///
+/// Registered as a metrics backend to count `events_emitted`.
+#[derive(Debug, Default)]
+pub struct SoakCounters {
+    transactions: AtomicU64,
+    events: AtomicU64,
+    passes: AtomicU64,
+}
+
+#[async_trait]
+impl Metrics for SoakCounters {
+    async fn initialize(&self) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn flush(&self) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn shutdown(&self) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn update_gauge(&self, _name: &str, _value: f64) -> CarbonResult<()> {
+        Ok(())
+    }
+
+    async fn increment_counter(&self, name: &str, value: u64) -> CarbonResult<()> {
+        if name == "events_emitted" {
+            self.events.fetch_add(value, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    async fn record_histogram(&self, _name: &str, _value: f64) -> CarbonResult<()> {
+        Ok(())
+    }
+}
+
+/// A recorded block's transactions.
+#[derive(Debug, Clone)]
+struct RecordedBlock {
+    slot: u64,
+    block_time: Option<i64>,
+    transactions: Vec<TransactionUpdate>,
+}
+
+/// Datasource replaying recorded blocks in a loop until the pipeline stops.
+pub struct RecordedBlocks {
+    blocks: Arc<Vec<RecordedBlock>>,
+    counters: Arc<SoakCounters>,
+}
+
+impl RecordedBlocks {
+    /// Returns the slots and seconds of block time each pass is shifted by.
+    fn pass_span(&self) -> (u64, i64) {
+        let (Some(first), Some(last)) = (self.blocks.first(), self.blocks.last()) else {
+            return (0, 0);
+        };
+        let times = (first.block_time, last.block_time);
+        let seconds = match times {
+            (Some(first), Some(last)) => last - first + 1,
+            _ => 0,
+        };
+        (last.slot - first.slot + 1, seconds)
+    }
+}
+
+#[async_trait]
+impl Datasource for RecordedBlocks {
+    async fn consume(
+        &self,
+        id: DatasourceId,
+        sender: mpsc::Sender<(Update, DatasourceId)>,
+        cancellation_token: CancellationToken,
+        _metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let (slots, seconds) = self.pass_span();
+        let mut pass = 0;
+        loop {
+            for block in self.blocks.iter() {
+                for transaction in &block.transactions {
+                    let mut update = transaction.clone();
+                    update.slot += pass * slots;
+                    update.block_time = update.block_time.map(|t| t + pass as i64 * seconds);
+                    let update = Update::Transaction(Box::new(update));
+                    tokio::select! {
+                        _ = cancellation_token.cancelled() => return Ok(()),
+                        sent = sender.send((update, id.clone())) => if sent.is_err() {
+                            return Ok(());
+                        },
+                    }
+                    self.counters.transactions.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            pass += 1;
+            self.counters.passes.store(pass, Ordering::Relaxed);
+        }
+    }
+
+    fn update_types(&self) -> Vec<UpdateType> {
+        vec![UpdateType::Transaction]
+    }
+}
+
+/// Resource usage at one point of a soak test.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Sample {
+    /// Seconds since the pipeline started
+    pub elapsed_secs: f64,
+    /// Resident memory in KiB, where the platform reports it
+    pub rss_kib: Option<u64>,
+    /// Alive tasks of the runtime
+    pub tasks: usize,
+    /// Transactions forwarded to the pipeline so far
+    pub transactions: u64,
+    /// Events emitted so far
+    pub events: u64,
+}
+
+/// Outcome of one soak test check.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Check {
+    /// Check name
+    pub name: &'static str,
+    /// Whether the check passed
+    pub passed: bool,
+    /// Measured values
+    pub detail: String,
+}
+
+impl Check {
+    fn new(name: &'static str, passed: bool, detail: String) -> Self {
+        Self {
+            name,
+            passed,
+            detail,
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = if self.passed { "ok  " } else { "FAIL" };
+        write!(f, "{status} {}: {}", self.name, self.detail)
+    }
+}
+
+/// Result of a soak test.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SoakReport {
+    /// Seconds the pipeline ran
+    pub duration_secs: f64,
+    /// Complete passes over the recording
+    pub passes: u64,
+    /// Transactions forwarded to the pipeline
+    pub transactions: u64,
+    /// Events emitted
+    pub events: u64,
+    /// Checks, in the order they ran
+    pub checks: Vec<Check>,
+    /// Samples, oldest first
+    pub samples: Vec<Sample>,
+}
+
+impl SoakReport {
+    /// Returns `true` if every check passed.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+    }
+}
+
+/// A soak test: the recording, the counters, and the samples taken.
+pub struct Soak {
+    config: SoakConfig,
+    blocks: Arc<Vec<RecordedBlock>>,
+    counters: Arc<SoakCounters>,
+    samples: Mutex<Vec<Sample>>,
+    /// Alive tasks before the pipeline started
+    baseline_tasks: usize,
+    started: Instant,
+}
+
+impl Soak {
+    /// Loads the recording of a soak test.
+    pub fn load(config: SoakConfig) -> Result<Self, String> {
+        let blocks = load_recording(&config.recording)?;
+        let transactions: usize = blocks.iter().map(|b| b.transactions.len()).sum();
+        if transactions == 0 {
+            return Err(format!(
+                "{}: no transactions recorded",
+                config.recording.display()
+            ));
+        }
+        eprintln!(
+            "Loaded {} block(s), {transactions} transaction(s) from {}",
+            blocks.len(),
+            config.recording.display()
+        );
+        Ok(Self {
+            config,
+            blocks: Arc::new(blocks),
+            counters: Arc::new(SoakCounters::default()),
+            samples: Mutex::new(Vec::new()),
+            baseline_tasks: 0,
+            started: Instant::now(),
+        })
+    }
+
+    /// Returns the counters to register as a pipeline metrics backend.
+    pub fn counters(&self) -> Arc<SoakCounters> {
+        self.counters.clone()
+    }
+
+    /// Returns a datasource replaying the recording.
+    pub fn datasource(&self) -> RecordedBlocks {
+        RecordedBlocks {
+            blocks: self.blocks.clone(),
+            counters: self.counters.clone(),
+        }
+    }
+
+    /// Records the alive tasks the pipeline's tasks are checked against.
+    ///
+    /// Must be called right before the pipeline runs.
+    pub fn begin(&mut self) {
+        self.baseline_tasks = alive_tasks();
+        self.started = Instant::now();
+        eprintln!(
+            "Soak test: {}s, sampling every {}s after a {}s warm-up",
+            self.config.duration.as_secs(),
+            self.config.interval.as_secs(),
+            self.config.warmup.as_secs()
+        );
+    }
+
+    /// Samples resource usage every interval until the test duration elapsed.
+    pub async fn run(&self) {
+        let mut ticks = tokio::time::interval(self.config.interval);
+        loop {
+            ticks.tick().await;
+            let sample = self.sample();
+            eprintln!(
+                "[{:>6.0}s] rss={} tasks={} transactions={} events={}",
+                sample.elapsed_secs,
+                sample
+                    .rss_kib
+                    .map_or("n/a".to_string(), |kib| format!("{}MiB", kib / 1024)),
+                sample.tasks,
+                sample.transactions,
+                sample.events
+            );
+            self.samples
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(sample);
+            if self.started.elapsed() >= self.config.duration {
+                return;
+            }
+        }
+    }
+
+    /// Checks the samples and the tasks left once the pipeline is stopped,
+    /// prints the report, and writes it as JSON if configured.
+    ///
+    /// Must be called after the pipeline is dropped and the outputs drained.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a check failed or the report could not be written.
+    pub async fn finish(self) -> Result<(), String> {
+        let duration_secs = self.started.elapsed().as_secs_f64();
+        let tasks_left = settle(self.baseline_tasks).await;
+        let samples = self.samples.into_inner().unwrap_or_else(|e| e.into_inner());
+
+        let mut checks = check_samples(&samples, &self.config);
+        checks.push(Check::new(
+            "task leaks",
+            tasks_left <= self.baseline_tasks,
+            format!(
+                "{tasks_left} task(s) alive after shutdown, {} before the pipeline started",
+                self.baseline_tasks
+            ),
+        ));
+        let report = SoakReport {
+            duration_secs,
+            passes: self.counters.passes.load(Ordering::Relaxed),
+            transactions: self.counters.transactions.load(Ordering::Relaxed),
+            events: self.counters.events.load(Ordering::Relaxed),
+            checks,
+            samples,
+        };
+
+        println!(
+            "{:.0}s, {} pass(es), {} transaction(s), {} event(s)",
+            report.duration_secs, report.passes, report.transactions, report.events
+        );
+        for check in &report.checks {
+            println!("{check}");
+        }
+        if let Some(ref path) = self.config.report {
+            let json = serde_json::to_string_pretty(&report).map_err(|e| e.to_string())?;
+            fs::write(path, json).map_err(|e| format!("{}: {e}", path.display()))?;
+            eprintln!("Report written to {}", path.display());
+        }
+
+        let failed = report.checks.iter().filter(|check| !check.passed).count();
+        match failed {
+            0 => Ok(()),
+            _ => Err(format!("{failed} soak test check(s) failed")),
+        }
+    }
+
+    fn sample(&self) -> Sample {
+        Sample {
+            elapsed_secs: self.started.elapsed().as_secs_f64(),
+            rss_kib: resident_kib(),
+            tasks: alive_tasks(),
+            transactions: self.counters.transactions.load(Ordering::Relaxed),
+            events: self.counters.events.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Compares the first and last quarter of the samples taken after the warm-up.
+fn check_samples(samples: &[Sample], config: &SoakConfig) -> Vec<Check> {
+    let warmup = config.warmup.as_secs_f64();
+    // Throughput between consecutive samples, attributed to the later one
+    let measured: Vec<(Sample, f64)> = samples
+        .windows(2)
+        .filter(|pair| pair[1].elapsed_secs >= warmup)
+        .map(|pair| {
+            let secs = (pair[1].elapsed_secs - pair[0].elapsed_secs).max(f64::EPSILON);
+            let rate = (pair[1].transactions - pair[0].transactions) as f64 / secs;
+            (pair[1], rate)
+        })
+        .collect();
+    if measured.len() < 2 {
+        return vec![Check::new(
+            "samples",
+            false,
+            format!(
+                "{} sample(s) after the warm-up, at least 2 needed",
+                measured.len()
+            ),
+        )];
+    }
+    let quarter = (measured.len() / 4).max(1);
+    let (first, last) = (&measured[..quarter], &measured[measured.len() - quarter..]);
+
+    let mut checks = Vec::new();
+
+    let rss = |window: &[(Sample, f64)]| {
+        let values: Option<Vec<u64>> = window.iter().map(|(s, _)| s.rss_kib).collect();
+        values.map(|mut values| {
+            values.sort_unstable();
+            values[values.len() / 2]
+        })
+    };
+    checks.push(match (rss(first), rss(last)) {
+        (Some(before), Some(after)) => {
+            let growth = (after as f64 - before as f64) / before.max(1) as f64 * 100.0;
+            Check::new(
+                "memory",
+                growth <= config.max_memory_growth_pct,
+                format!(
+                    "median rss {}MiB -> {}MiB ({growth:+.1}%, max +{}%)",
+                    before / 1024,
+                    after / 1024,
+                    config.max_memory_growth_pct
+                ),
+            )
+        }
+        _ => Check::new(
+            "memory",
+            true,
+            "resident memory is not reported on this platform".to_string(),
+        ),
+    });
+
+    let tasks = |window: &[(Sample, f64)]| window.iter().map(|(s, _)| s.tasks).max().unwrap_or(0);
+    let (before, after) = (tasks(first), tasks(last));
+    checks.push(Check::new(
+        "tasks",
+        after <= before,
+        format!("max alive tasks {before} -> {after}"),
+    ));
+
+    let rate = |window: &[(Sample, f64)]| {
+        window.iter().map(|(_, rate)| rate).sum::<f64>() / window.len() as f64
+    };
+    let (before, after) = (rate(first), rate(last));
+    checks.push(if before > 0.0 {
+        let drop = (before - after) / before * 100.0;
+        Check::new(
+            "throughput",
+            drop <= config.max_throughput_drop_pct,
+            format!(
+                "{before:.1} -> {after:.1} transactions/s ({:+.1}%, max -{}%)",
+                -drop, config.max_throughput_drop_pct
+            ),
+        )
+    } else {
+        Check::new(
+            "throughput",
+            false,
+            "no transactions processed after the warm-up".to_string(),
+        )
+    });
+
+    checks
+}
+
+/// Waits up to [`SETTLE_TIMEOUT`] for the alive tasks to drop to `baseline`
+/// and returns the tasks left.
+async fn settle(baseline: usize) -> usize {
+    let deadline = Instant::now() + SETTLE_TIMEOUT;
+    loop {
+        let tasks = alive_tasks();
+        if tasks <= baseline || Instant::now() >= deadline {
+            return tasks;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+/// Returns the alive tasks of the current runtime.
+fn alive_tasks() -> usize {
+    tokio::runtime::Handle::current()
+        .metrics()
+        .num_alive_tasks()
+}
+
+/// Returns the resident memory of the process in KiB (Linux only).
+fn resident_kib() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Reads the recorded blocks of a file, or of the `.jsonl` files of a
+/// directory in name order, sorted by slot.
+fn load_recording(path: &Path) -> Result<Vec<RecordedBlock>, String> {
+    let files = if path.is_dir() {
+        let mut files: Vec<PathBuf> = fs::read_dir(path)
+            .map_err(|e| format!("{}: {e}", path.display()))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|file| file.extension().is_some_and(|ext| ext == "jsonl"))
+            .collect();
+        files.sort();
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    let mut blocks = Vec::new();
+    let mut skipped = 0;
+    for file in &files {
+        let reader = fs::File::open(file)
+            .map(BufReader::new)
+            .map_err(|e| format!("{}: {e}", file.display()))?;
+        for (number, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| format!("{}: {e}", file.display()))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let update: RpcBlockUpdate = serde_json::from_str(&line)
+                .map_err(|e| format!("{}:{}: {e}", file.display(), number + 1))?;
+            let Some(block) = update.block else {
+                continue;
+            };
+            let block_time = block.block_time;
+            let mut transactions = Vec::new();
+            for transaction in block.transactions.unwrap_or_default() {
+                match transaction_update(transaction, update.slot, block_time) {
+                    Some(transaction) => transactions.push(transaction),
+                    None => skipped += 1,
+                }
+            }
+            blocks.push(RecordedBlock {
+                slot: update.slot,
+                block_time,
+                transactions,
+            });
+        }
+    }
+    if skipped > 0 {
+        log::warn!(
+            "Skipped {skipped} recorded transaction(s) without status or in an encoding other \
+             than base64/base58"
+        );
+    }
+
+    blocks.sort_by_key(|block| block.slot);
+    Ok(blocks)
+}
+
+/// Converts a recorded transaction into a pipeline update.
+pub(crate) fn transaction_update(
+    transaction: EncodedTransactionWithStatusMeta,
+    slot: u64,
+    block_time: Option<i64>,
+) -> Option<TransactionUpdate> {
+    let meta = transaction_metadata_from_original_meta(transaction.meta?).ok()?;
+    let transaction = transaction.transaction.decode()?;
+    Some(TransactionUpdate {
+        signature: *transaction.signatures.first()?,
+        transaction,
+        meta,
+        is_vote: false,
+        slot,
+        block_time,
+        block_hash: None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> SoakConfig {
+        SoakConfig {
+            recording: PathBuf::from("recording.jsonl"),
+            duration: Duration::from_secs(600),
+            interval: Duration::from_secs(60),
+            warmup: Duration::from_secs(60),
+            max_memory_growth_pct: 10.0,
+            max_throughput_drop_pct: 10.0,
+            report: None,
+        }
+    }
+
+    fn samples(rss: &[u64], tasks: &[usize], rates: &[u64]) -> Vec<Sample> {
+        let mut transactions = 0;
+        (0..rss.len())
+            .map(|i| {
+                transactions += rates[i] * 60;
+                Sample {
+                    elapsed_secs: i as f64 * 60.0,
+                    rss_kib: Some(rss[i]),
+                    tasks: tasks[i],
+                    transactions,
+                    events: transactions / 10,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_check_samples() {
+        let stable = samples(
+            &[50_000, 100_000, 102_000, 101_000, 103_000, 104_000],
+            &[40, 52, 55, 51, 53, 52],
+            &[0, 1_000, 1_010, 990, 1_000, 995],
+        );
+        let checks = check_samples(&stable, &config());
+        assert!(checks.iter().all(|check| check.passed), "{checks:?}");
+        assert_eq!(checks.len(), 3);
+
+        let leaking = samples(
+            &[50_000, 100_000, 110_000, 120_000, 130_000, 140_000],
+            &[40, 52, 60, 70, 80, 90],
+            &[0, 1_000, 950, 900, 850, 800],
+        );
+        let failed: Vec<_> = check_samples(&leaking, &config())
+            .into_iter()
+            .filter(|check| !check.passed)
+            .map(|check| check.name)
+            .collect();
+        assert_eq!(failed, ["memory", "tasks", "throughput"]);
+
+        let checks = check_samples(&stable[..2], &config());
+        assert_eq!(checks[0].name, "samples");
+        assert!(!checks[0].passed);
+    }
+}
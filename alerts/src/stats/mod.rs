@@ -0,0 +1,482 @@
+//! In-memory per-token statistics of emitted events.
+//!
+//! This module provides:
+//! - [`StatsStore`] - Latest USD price, rolling 24h volume, and recent alerts per mint
+//! - [`TokenStats`] - Snapshot of a token's statistics
+//! - [`RecentAlert`] - Summary of an event that involved a token
+//! - [`HourlyVolume`] - One hour of a token's volume
+//!
+//! The store is fed by the processors with every emitted event and backs
+//! ad-hoc lookups such as Telegram inline queries. Volume is kept in hourly
+//! buckets, so the 24h window advances by the hour.
+//!
+//! All volumes are in USD, so tokens traded against SOL and against
+//! stablecoins rank on the same scale. Swaps reaching the store without a USD
+//! value are valued at the price service's current prices when set with
+//! [`StatsStore::with_prices`].
+
+use {
+    crate::{
+        enrichment::PriceEnricher,
+        output::{swap_event::format_number, EventType, Protocol, SwapEvent, TokenInfo},
+        server::unix_now,
+    },
+    std::{
+        collections::{HashMap, VecDeque},
+        sync::{Arc, PoisonError, RwLock},
+    },
+};
+
+/// Number of hourly volume buckets (24h window).
+const VOLUME_BUCKETS: usize = 24;
+
+/// Recent alerts kept per token.
+const RECENT_ALERTS: usize = 5;
+
+/// Default maximum number of tracked tokens.
+const DEFAULT_MAX_TOKENS: usize = 10_000;
+
+/// Summary of an event that involved a token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecentAlert {
+    /// Event type
+    pub event_type: EventType,
+    /// Protocol that emitted the event
+    pub protocol: Protocol,
+    /// Transaction signature
+    pub signature: String,
+    /// USD value of the event (if known)
+    pub usd_value: Option<f64>,
+    /// Unix timestamp of the event
+    pub timestamp: i64,
+}
+
+/// Snapshot of a token's statistics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenStats {
+    /// Token mint address
+    pub mint: String,
+    /// Token symbol (if known)
+    pub symbol: Option<String>,
+    /// Latest observed USD price per token
+    pub price_usd: Option<f64>,
+    /// USD volume over the last 24 hours
+    pub volume_24h_usd: f64,
+    /// Swaps over the last 24 hours
+    pub trades_24h: u64,
+    /// Most recent events involving the token, newest first
+    pub recent: Vec<RecentAlert>,
+}
+
+impl TokenStats {
+    /// Returns the symbol, or the shortened mint if the symbol is unknown.
+    pub fn name(&self) -> String {
+        match self.symbol {
+            Some(ref symbol) => symbol.clone(),
+            None => short_address(&self.mint),
+        }
+    }
+
+    /// Returns a one-line summary of price and volume.
+    pub fn summary(&self) -> String {
+        let price = self.price_usd.map_or("n/a".to_string(), |price| {
+            format!("${}", format_price(price))
+        });
+        format!(
+            "{price} · 24h vol ${} ({} trades)",
+            format_number(self.volume_24h_usd),
+            self.trades_24h
+        )
+    }
+
+    /// Formats the statistics as a multi-line message, with alert ages relative to `now`.
+    pub fn format_text(&self, now: i64) -> String {
+        let mut lines = vec![format!("🪙 {} ({})", self.name(), self.mint)];
+        if let Some(price) = self.price_usd {
+            lines.push(format!("💵 Price: ${}", format_price(price)));
+        }
+        lines.push(format!(
+            "📊 24h volume: ${} ({} trades)",
+            format_number(self.volume_24h_usd),
+            self.trades_24h
+        ));
+        if !self.recent.is_empty() {
+            lines.push("🕒 Latest alerts:".to_string());
+            for alert in &self.recent {
+                let usd = alert
+                    .usd_value
+                    .map(|usd| format!(" ${}", format_number(usd)))
+                    .unwrap_or_default();
+                lines.push(format!(
+                    "• {} [{}]{usd} · {} ago · {}",
+                    alert.event_type,
+                    alert.protocol,
+                    format_age(now - alert.timestamp),
+                    short_address(&alert.signature)
+                ));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// Volume of a token over one hour.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HourlyVolume {
+    /// Start of the hour (Unix seconds)
+    pub hour_start: i64,
+    /// USD volume of the hour's swaps
+    pub usd: f64,
+    /// Swaps in the hour
+    pub trades: u64,
+}
+
+/// Volume of one hour.
+#[derive(Debug, Clone, Copy, Default)]
+struct VolumeBucket {
+    /// Hours since the Unix epoch
+    hour: i64,
+    usd: f64,
+    trades: u64,
+}
+
+/// Statistics tracked for one token.
+#[derive(Debug, Default)]
+struct TokenEntry {
+    symbol: Option<String>,
+    price_usd: Option<f64>,
+    buckets: [VolumeBucket; VOLUME_BUCKETS],
+    recent: VecDeque<RecentAlert>,
+    last_seen: i64,
+}
+
+impl TokenEntry {
+    fn add_volume(&mut self, timestamp: i64, usd: Option<f64>) {
+        let hour = timestamp.div_euclid(3600);
+        let bucket = &mut self.buckets[hour.rem_euclid(VOLUME_BUCKETS as i64) as usize];
+        if bucket.hour != hour {
+            *bucket = VolumeBucket {
+                hour,
+                ..Default::default()
+            };
+        }
+        bucket.usd += usd.unwrap_or_default();
+        bucket.trades += 1;
+    }
+
+    /// Returns the buckets with swaps in the 24h window ending at `now`.
+    fn window(&self, now: i64) -> impl Iterator<Item = &VolumeBucket> {
+        let current_hour = now.div_euclid(3600);
+        self.buckets
+            .iter()
+            .filter(move |b| b.trades > 0 && current_hour - b.hour < VOLUME_BUCKETS as i64)
+    }
+
+    fn snapshot(&self, mint: &str, now: i64) -> TokenStats {
+        let (volume_24h_usd, trades_24h) = self.window(now).fold((0.0, 0), |(usd, trades), b| {
+            (usd + b.usd, trades + b.trades)
+        });
+
+        TokenStats {
+            mint: mint.to_string(),
+            symbol: self.symbol.clone(),
+            price_usd: self.price_usd,
+            volume_24h_usd,
+            trades_24h,
+            recent: self.recent.iter().cloned().collect(),
+        }
+    }
+}
+
+/// Per-token statistics of emitted events.
+pub struct StatsStore {
+    max_tokens: usize,
+    tokens: RwLock<HashMap<String, TokenEntry>>,
+    /// Values swaps without a USD value at trade time
+    prices: Option<Arc<PriceEnricher>>,
+}
+
+impl Default for StatsStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_TOKENS)
+    }
+}
+
+impl StatsStore {
+    /// Creates a store tracking at most `max_tokens` tokens; the least
+    /// recently seen token is evicted when the limit is reached.
+    pub fn new(max_tokens: usize) -> Self {
+        Self {
+            max_tokens: max_tokens.max(1),
+            tokens: RwLock::new(HashMap::new()),
+            prices: None,
+        }
+    }
+
+    /// Values swaps that reach the store without a USD value at the price
+    /// service's current prices.
+    pub fn with_prices(mut self, prices: Arc<PriceEnricher>) -> Self {
+        self.prices = Some(prices);
+        self
+    }
+
+    /// Returns the USD value of an event, normalized at trade time for swaps.
+    fn usd_value(&self, event: &SwapEvent) -> Option<f64> {
+        match self.prices {
+            Some(ref prices) if event.event_type == EventType::Swap => prices.swap_value_usd(event),
+            _ => event.usd_value(),
+        }
+    }
+
+    /// Records an emitted event for each of its tokens.
+    ///
+    /// Swaps add to the tokens' volume and update their USD price; every
+    /// event is added to the tokens' recent alerts.
+    pub fn record(&self, event: &SwapEvent) {
+        let timestamp = event.timestamp.unwrap_or_else(unix_now);
+        let usd_value = self.usd_value(event);
+        let alert = RecentAlert {
+            event_type: event.event_type,
+            protocol: event.protocol,
+            signature: event.signature.clone(),
+            usd_value,
+            timestamp,
+        };
+
+        let mut tokens = self.tokens.write().unwrap_or_else(PoisonError::into_inner);
+        for token in [&event.input_token, &event.output_token]
+            .into_iter()
+            .flatten()
+        {
+            if !tokens.contains_key(&token.mint) && tokens.len() >= self.max_tokens {
+                let oldest = tokens
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_seen)
+                    .map(|(mint, _)| mint.clone());
+                if let Some(oldest) = oldest {
+                    tokens.remove(&oldest);
+                }
+            }
+
+            let entry = tokens.entry(token.mint.clone()).or_default();
+            entry.last_seen = entry.last_seen.max(timestamp);
+            if token.symbol.is_some() {
+                entry.symbol = token.symbol.clone();
+            }
+            if event.event_type == EventType::Swap {
+                if let Some(price) = unit_price(token, usd_value) {
+                    entry.price_usd = Some(price);
+                }
+                entry.add_volume(timestamp, usd_value);
+            }
+            entry.recent.push_front(alert.clone());
+            entry.recent.truncate(RECENT_ALERTS);
+        }
+    }
+
+    /// Returns the statistics of a mint.
+    pub fn get(&self, mint: &str) -> Option<TokenStats> {
+        let tokens = self.tokens.read().unwrap_or_else(PoisonError::into_inner);
+        tokens
+            .get(mint)
+            .map(|entry| entry.snapshot(mint, unix_now()))
+    }
+
+    /// Returns the hourly volume of a mint over the last 24 hours, oldest
+    /// first; hours without swaps are omitted.
+    pub fn hourly(&self, mint: &str) -> Vec<HourlyVolume> {
+        let tokens = self.tokens.read().unwrap_or_else(PoisonError::into_inner);
+        let Some(entry) = tokens.get(mint) else {
+            return Vec::new();
+        };
+        let mut hours: Vec<HourlyVolume> = entry
+            .window(unix_now())
+            .map(|b| HourlyVolume {
+                hour_start: b.hour * 3600,
+                usd: b.usd,
+                trades: b.trades,
+            })
+            .collect();
+        hours.sort_by_key(|h| h.hour_start);
+        hours
+    }
+
+    /// Finds tokens by mint address or symbol, busiest first.
+    ///
+    /// An exact mint match is returned alone. Otherwise tokens whose symbol
+    /// starts with the query (case-insensitive) are returned, exact symbol
+    /// matches first; an empty query returns the busiest tokens.
+    pub fn search(&self, query: &str, limit: usize) -> Vec<TokenStats> {
+        let query = query.trim();
+        if let Some(stats) = self.get(query) {
+            return vec![stats];
+        }
+
+        let needle = query.to_lowercase();
+        let now = unix_now();
+        let tokens = self.tokens.read().unwrap_or_else(PoisonError::into_inner);
+        let mut matches: Vec<(bool, TokenStats)> = tokens
+            .iter()
+            .filter_map(|(mint, entry)| {
+                let symbol = entry.symbol.as_deref().unwrap_or_default().to_lowercase();
+                symbol
+                    .starts_with(&needle)
+                    .then(|| (symbol == needle, entry.snapshot(mint, now)))
+            })
+            .collect();
+        matches.sort_by(|(a_exact, a), (b_exact, b)| {
+            b_exact
+                .cmp(a_exact)
+                .then(b.volume_24h_usd.total_cmp(&a.volume_24h_usd))
+                .then(b.trades_24h.cmp(&a.trades_24h))
+        });
+        matches
+            .into_iter()
+            .take(limit)
+            .map(|(_, stats)| stats)
+            .collect()
+    }
+}
+
+/// Returns the USD price of one token from a swap leg, valuing a leg without
+/// a USD value at the swap's value.
+fn unit_price(token: &TokenInfo, swap_usd: Option<f64>) -> Option<f64> {
+    let amount = token.amount.filter(|amount| *amount > 0.0)?;
+    token.amount_usd.or(swap_usd).map(|usd| usd / amount)
+}
+
+/// Formats a USD price with four significant digits for sub-dollar prices.
+fn format_price(price: f64) -> String {
+    if price >= 1.0 || price <= 0.0 {
+        return format!("{price:.2}");
+    }
+    let decimals = (-price.log10()).ceil() as usize + 3;
+    format!("{price:.decimals$}")
+}
+
+/// Formats an age in seconds as `42s`, `5m`, `3h`, or `2d`.
+fn format_age(secs: i64) -> String {
+    let secs = secs.max(0);
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m", secs / 60),
+        3600..86400 => format!("{}h", secs / 3600),
+        _ => format!("{}d", secs / 86400),
+    }
+}
+
+/// Shortens an address or signature to `abcdef...wxyz`.
+fn short_address(address: &str) -> String {
+    if address.len() > 12 {
+        format!("{}...{}", &address[..6], &address[address.len() - 4..])
+    } else {
+        address.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::output::swap_event::WSOL_MINT, std::time::Duration};
+
+    fn swap(mint: &str, symbol: &str, amount: u64, usd: f64, timestamp: i64) -> SwapEvent {
+        SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature(format!("sig-{timestamp}"))
+            .pool("pool")
+            .input_token(
+                TokenInfo::new(WSOL_MINT, 1)
+                    .with_symbol("SOL")
+                    .with_decimals(0)
+                    .with_usd_value(usd),
+            )
+            .output_token(
+                TokenInfo::new(mint, amount)
+                    .with_symbol(symbol)
+                    .with_decimals(0)
+                    .with_usd_value(usd),
+            )
+            .timestamp(timestamp)
+            .build()
+    }
+
+    #[test]
+    fn test_records_price_volume_and_recent_alerts() {
+        let store = StatsStore::default();
+        let now = unix_now();
+        store.record(&swap("bonk", "BONK", 1000, 20.0, now - 25 * 3600));
+        store.record(&swap("bonk", "BONK", 1000, 10.0, now - 60));
+        store.record(&swap("bonk", "BONK", 2000, 30.0, now));
+
+        let stats = store.get("bonk").unwrap();
+        assert_eq!(stats.price_usd, Some(0.015));
+        // The swap from 25h ago is outside the window
+        assert_eq!(stats.volume_24h_usd, 40.0);
+        assert_eq!(stats.trades_24h, 2);
+        assert_eq!(stats.recent.len(), 3);
+        assert_eq!(stats.recent[0].timestamp, now);
+        assert!(store.get("missing").is_none());
+
+        let hourly = store.hourly("bonk");
+        assert_eq!(hourly.iter().map(|h| h.trades).sum::<u64>(), 2);
+        assert!(hourly.windows(2).all(|w| w[0].hour_start < w[1].hour_start));
+        assert_eq!(
+            hourly.last().unwrap().hour_start,
+            now.div_euclid(3600) * 3600
+        );
+        assert!(store.hourly("missing").is_empty());
+    }
+
+    #[test]
+    fn test_search_by_mint_and_symbol() {
+        let store = StatsStore::default();
+        let now = unix_now();
+        store.record(&swap("mint-bonk", "BONK", 1, 10.0, now));
+        store.record(&swap("mint-bonkfork", "BONKFORK", 1, 500.0, now));
+        store.record(&swap("mint-wif", "WIF", 1, 5.0, now));
+
+        assert_eq!(store.search("mint-wif", 10)[0].name(), "WIF");
+        let names: Vec<String> = store.search("bonk", 10).iter().map(|s| s.name()).collect();
+        assert_eq!(names, ["BONK", "BONKFORK"]);
+        let busiest = store.search("", 1);
+        assert_eq!(busiest[0].name(), "SOL");
+    }
+
+    #[test]
+    fn test_unpriced_swaps_are_valued_at_trade_time() {
+        let prices = Arc::new(PriceEnricher::new(Some(150.0), Duration::from_secs(300)));
+        let store = StatsStore::default().with_prices(prices);
+        let event = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .input_token(TokenInfo::new(WSOL_MINT, 2_000_000_000))
+            .output_token(TokenInfo::new("bonk", 600).with_decimals(0))
+            .timestamp(unix_now())
+            .build();
+        store.record(&event);
+
+        let stats = store.get("bonk").unwrap();
+        assert_eq!(stats.volume_24h_usd, 300.0);
+        assert_eq!(stats.price_usd, Some(0.5));
+        assert_eq!(stats.recent[0].usd_value, Some(300.0));
+        assert_eq!(store.get(WSOL_MINT).unwrap().volume_24h_usd, 300.0);
+    }
+
+    #[test]
+    fn test_evicts_least_recently_seen() {
+        let store = StatsStore::new(2);
+        store.record(&swap("bonk", "BONK", 1, 1.0, 100));
+        store.record(&swap("wif", "WIF", 1, 1.0, 200));
+        assert!(store.get("bonk").is_none());
+        assert!(store.get("wif").is_some());
+    }
+
+    #[test]
+    fn test_formatting() {
+        assert_eq!(format_price(0.000023456), "0.00002346");
+        assert_eq!(format_price(0.5), "0.5000");
+        assert_eq!(format_price(152.456), "152.46");
+        assert_eq!(format_age(42), "42s");
+        assert_eq!(format_age(7200), "2h");
+    }
+}
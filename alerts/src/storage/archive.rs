@@ -0,0 +1,612 @@
+//! Compressed file archive of swap events.
+//!
+//! Each flushed batch becomes one block: events are transposed into columns
+//! (one JSON array per field, so similar values sit next to each other) and
+//! compressed with zstd. Blocks are appended to segment files that roll over
+//! at a configured size, and `index.jsonl` records every block's segment, byte
+//! range, and slot range so readers only decompress the blocks a query needs.
+//!
+//! ```text
+//! <ARCHIVE_DIR>/
+//! ├── index.jsonl               # One BlockIndex per line
+//! ├── swaps-000301234567.zst    # Segment named after its first slot
+//! └── swaps-000301290001.zst
+//! ```
+//!
+//! Segments are plain concatenations of zstd frames, so `zstd -dc` on a
+//! segment yields its blocks' JSON without this tool.
+
+use {
+    super::{BatchWriter, PruneStats, RetentionPolicy, StorageError},
+    crate::output::SwapEvent,
+    async_trait::async_trait,
+    serde::{Deserialize, Serialize},
+    serde_json::{Map, Value},
+    std::{
+        collections::{BTreeMap, HashSet},
+        env,
+        fs::File,
+        io::{BufRead, BufReader, Read, Seek, SeekFrom},
+        path::{Path, PathBuf},
+        time::Duration,
+    },
+    tokio::io::AsyncWriteExt,
+};
+
+/// Index file name inside the archive directory.
+const INDEX_FILE: &str = "index.jsonl";
+
+/// Prefix and extension of segment file names.
+const SEGMENT_PREFIX: &str = "swaps-";
+const SEGMENT_EXTENSION: &str = ".zst";
+
+/// Configuration for the file archive.
+#[derive(Debug, Clone)]
+pub struct ArchiveConfig {
+    /// Directory holding segments and the index
+    pub dir: PathBuf,
+    /// Segment size (bytes) after which a new segment is started
+    pub segment_size: u64,
+    /// zstd compression level (1-22)
+    pub compression_level: i32,
+    /// Maximum events per block
+    pub batch_size: usize,
+    /// Maximum time an event waits before its block is written
+    pub flush_interval: Duration,
+}
+
+impl ArchiveConfig {
+    /// Creates archive configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `ARCHIVE_DIR` - Optional: Directory enabling the archive
+    /// - `ARCHIVE_SEGMENT_SIZE_MB` - Optional: Segment roll-over size (default: 256)
+    /// - `ARCHIVE_COMPRESSION_LEVEL` - Optional: zstd level 1-22 (default: 3)
+    /// - `ARCHIVE_BATCH_SIZE` - Optional: Events per block (default: 1000)
+    /// - `ARCHIVE_FLUSH_INTERVAL_MS` - Optional: Block flush interval in ms (default: 10000)
+    ///
+    /// # Returns
+    ///
+    /// `None` if `ARCHIVE_DIR` is not set.
+    pub fn from_env() -> Option<Self> {
+        let dir = env::var("ARCHIVE_DIR")
+            .ok()
+            .filter(|v| !v.trim().is_empty())?;
+
+        let segment_size_mb: u64 = env::var("ARCHIVE_SEGMENT_SIZE_MB")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(256);
+
+        let compression_level: i32 = env::var("ARCHIVE_COMPRESSION_LEVEL")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|n| (1..=22).contains(n))
+            .unwrap_or(3);
+
+        let batch_size: usize = env::var("ARCHIVE_BATCH_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(1000);
+
+        let flush_interval_ms: u64 = env::var("ARCHIVE_FLUSH_INTERVAL_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(10_000);
+
+        Some(Self {
+            dir: PathBuf::from(dir.trim()),
+            segment_size: segment_size_mb * 1024 * 1024,
+            compression_level,
+            batch_size,
+            flush_interval: Duration::from_millis(flush_interval_ms),
+        })
+    }
+}
+
+/// Index entry describing one compressed block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockIndex {
+    /// Segment file name, relative to the archive directory
+    pub segment: String,
+    /// Byte offset of the block in the segment
+    pub offset: u64,
+    /// Compressed block length in bytes
+    pub length: u64,
+    /// Lowest slot in the block
+    pub min_slot: u64,
+    /// Highest slot in the block
+    pub max_slot: u64,
+    /// Number of events in the block
+    pub events: usize,
+}
+
+impl BlockIndex {
+    /// Returns `true` if the block may contain slots in `from..=to`.
+    fn overlaps(&self, from: u64, to: u64) -> bool {
+        self.min_slot <= to && self.max_slot >= from
+    }
+}
+
+/// Columnar block layout before compression.
+#[derive(Serialize, Deserialize)]
+struct Block {
+    /// Number of events
+    rows: usize,
+    /// Field name to per-event values (`null` where the field is absent)
+    columns: BTreeMap<String, Vec<Value>>,
+}
+
+/// Encodes events into a compressed columnar block.
+fn encode_block(events: &[SwapEvent], level: i32) -> Result<Vec<u8>, StorageError> {
+    let mut columns: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+
+    for (row, event) in events.iter().enumerate() {
+        let Value::Object(fields) =
+            serde_json::to_value(event).map_err(|e| StorageError::Write(e.to_string()))?
+        else {
+            return Err(StorageError::Write(
+                "event is not a JSON object".to_string(),
+            ));
+        };
+        for (field, value) in fields {
+            columns
+                .entry(field)
+                .or_insert_with(|| vec![Value::Null; row])
+                .push(value);
+        }
+        // Pad columns for fields this event skipped
+        for column in columns.values_mut() {
+            column.resize(row + 1, Value::Null);
+        }
+    }
+
+    let block = Block {
+        rows: events.len(),
+        columns,
+    };
+    let json = serde_json::to_vec(&block).map_err(|e| StorageError::Write(e.to_string()))?;
+    zstd::bulk::compress(&json, level).map_err(|e| StorageError::Write(e.to_string()))
+}
+
+/// Decodes a compressed columnar block back into events.
+fn decode_block(bytes: &[u8]) -> Result<Vec<SwapEvent>, StorageError> {
+    let json = zstd::decode_all(bytes).map_err(|e| StorageError::Read(e.to_string()))?;
+    let block: Block =
+        serde_json::from_slice(&json).map_err(|e| StorageError::Read(e.to_string()))?;
+
+    (0..block.rows)
+        .map(|row| {
+            let fields: Map<String, Value> = block
+                .columns
+                .iter()
+                .filter_map(|(field, values)| match values.get(row) {
+                    Some(Value::Null) | None => None,
+                    Some(value) => Some((field.clone(), value.clone())),
+                })
+                .collect();
+            serde_json::from_value(Value::Object(fields))
+                .map_err(|e| StorageError::Read(e.to_string()))
+        })
+        .collect()
+}
+
+/// Segment currently being appended to.
+struct Segment {
+    name: String,
+    size: u64,
+}
+
+/// Append-only archive writer.
+///
+/// Blocks are never rewritten, so an event is archived once per batch it was
+/// flushed in.
+pub struct ArchiveSink {
+    config: ArchiveConfig,
+    segment: Option<Segment>,
+}
+
+impl ArchiveSink {
+    /// Creates the archive directory if needed.
+    pub async fn open(config: ArchiveConfig) -> Result<Self, StorageError> {
+        tokio::fs::create_dir_all(&config.dir)
+            .await
+            .map_err(|e| StorageError::Connection(format!("{}: {e}", config.dir.display())))?;
+        log::info!("Archive enabled: {}", config.dir.display());
+
+        Ok(Self {
+            config,
+            segment: None,
+        })
+    }
+
+    /// Returns the segment to append `length` bytes to, rolling over when full.
+    async fn segment_for(&mut self, length: u64, first_slot: u64) -> &mut Segment {
+        let full = self
+            .segment
+            .as_ref()
+            .is_none_or(|s| s.size > 0 && s.size + length > self.config.segment_size);
+
+        if full {
+            let name = format!("{SEGMENT_PREFIX}{first_slot:012}{SEGMENT_EXTENSION}");
+            // Appending to an existing segment (e.g., after a restart) is fine
+            let size = tokio::fs::metadata(self.config.dir.join(&name))
+                .await
+                .map_or(0, |m| m.len());
+            self.segment = Some(Segment { name, size });
+        }
+
+        self.segment.as_mut().expect("segment was just set")
+    }
+}
+
+/// Segment file found on disk.
+struct SegmentFile {
+    name: String,
+    size: u64,
+    /// Last write time (Unix seconds)
+    modified: i64,
+}
+
+/// Lists segment files, oldest (lowest first slot) first.
+async fn list_segments(dir: &Path) -> Result<Vec<SegmentFile>, StorageError> {
+    let read_err = |e: std::io::Error| StorageError::Read(format!("{}: {e}", dir.display()));
+    let mut entries = tokio::fs::read_dir(dir).await.map_err(read_err)?;
+    let mut segments = Vec::new();
+
+    while let Some(entry) = entries.next_entry().await.map_err(read_err)? {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if !name.starts_with(SEGMENT_PREFIX) || !name.ends_with(SEGMENT_EXTENSION) {
+            continue;
+        }
+        let metadata = entry.metadata().await.map_err(read_err)?;
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_secs() as i64);
+        segments.push(SegmentFile {
+            name,
+            size: metadata.len(),
+            modified,
+        });
+    }
+
+    // Names embed the zero-padded first slot, so they sort chronologically
+    segments.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(segments)
+}
+
+/// Appends bytes to a file, creating it if needed.
+async fn append(path: &Path, bytes: &[u8]) -> Result<(), StorageError> {
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await
+        .map_err(|e| StorageError::Write(format!("{}: {e}", path.display())))?;
+    file.write_all(bytes)
+        .await
+        .map_err(|e| StorageError::Write(format!("{}: {e}", path.display())))?;
+    file.flush()
+        .await
+        .map_err(|e| StorageError::Write(format!("{}: {e}", path.display())))
+}
+
+#[async_trait]
+impl BatchWriter for ArchiveSink {
+    fn name(&self) -> &'static str {
+        "archive"
+    }
+
+    async fn write_batch(&mut self, events: &[SwapEvent]) -> Result<(), StorageError> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        let block = encode_block(events, self.config.compression_level)?;
+        let min_slot = events.iter().map(|e| e.slot).min().unwrap_or_default();
+        let max_slot = events.iter().map(|e| e.slot).max().unwrap_or_default();
+
+        let dir = self.config.dir.clone();
+        let segment = self.segment_for(block.len() as u64, min_slot).await;
+        append(&dir.join(&segment.name), &block).await?;
+
+        let entry = BlockIndex {
+            segment: segment.name.clone(),
+            offset: segment.size,
+            length: block.len() as u64,
+            min_slot,
+            max_slot,
+            events: events.len(),
+        };
+        segment.size += entry.length;
+
+        // The index is written after the block, so it never points at missing data
+        let mut line =
+            serde_json::to_vec(&entry).map_err(|e| StorageError::Write(e.to_string()))?;
+        line.push(b'\n');
+        append(&dir.join(INDEX_FILE), &line).await
+    }
+
+    /// Deletes whole segments, oldest first, that were last written before the
+    /// age cutoff or while the archive exceeds the size limit. The segment
+    /// being written is kept. The index is rewritten without their blocks.
+    async fn prune(
+        &mut self,
+        policy: &RetentionPolicy,
+        now: i64,
+    ) -> Result<PruneStats, StorageError> {
+        if policy.max_age.is_none() && policy.max_size.is_none() {
+            return Ok(PruneStats::default());
+        }
+        let dir = self.config.dir.clone();
+        let segments = list_segments(&dir).await?;
+        let current = self.segment.as_ref().map(|s| s.name.as_str());
+        let cutoff = policy.event_cutoff(now);
+        let mut total: u64 = segments.iter().map(|s| s.size).sum();
+        let mut removed = HashSet::new();
+
+        for segment in &segments {
+            if Some(segment.name.as_str()) == current {
+                continue;
+            }
+            let expired = cutoff.is_some_and(|cutoff| segment.modified < cutoff);
+            let oversize = policy.max_size.is_some_and(|max| total > max);
+            if !expired && !oversize {
+                continue;
+            }
+            tokio::fs::remove_file(dir.join(&segment.name))
+                .await
+                .map_err(|e| StorageError::Write(format!("{}: {e}", segment.name)))?;
+            total -= segment.size;
+            removed.insert(segment.name.clone());
+        }
+
+        if removed.is_empty() {
+            return Ok(PruneStats::default());
+        }
+
+        // Rewrite the index atomically without the removed segments' blocks
+        let (kept, dropped): (Vec<_>, Vec<_>) = ArchiveReader::new(&dir)
+            .blocks()?
+            .into_iter()
+            .partition(|block| !removed.contains(&block.segment));
+        let mut index = Vec::new();
+        for block in &kept {
+            serde_json::to_writer(&mut index, block)
+                .map_err(|e| StorageError::Write(e.to_string()))?;
+            index.push(b'\n');
+        }
+        let tmp = dir.join(format!("{INDEX_FILE}.tmp"));
+        tokio::fs::write(&tmp, &index)
+            .await
+            .map_err(|e| StorageError::Write(format!("{}: {e}", tmp.display())))?;
+        tokio::fs::rename(&tmp, dir.join(INDEX_FILE))
+            .await
+            .map_err(|e| StorageError::Write(format!("{INDEX_FILE}: {e}")))?;
+
+        Ok(PruneStats {
+            events: dropped.iter().map(|b| b.events as u64).sum(),
+            candles: 0,
+            files: removed.len() as u64,
+        })
+    }
+}
+
+/// Filter for reading events back from an archive.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArchiveQuery {
+    /// Lowest slot to include
+    pub from_slot: Option<u64>,
+    /// Highest slot to include
+    pub to_slot: Option<u64>,
+    /// Only include events for this pool
+    pub pool: Option<String>,
+    /// Maximum number of events to return
+    pub limit: Option<usize>,
+}
+
+impl ArchiveQuery {
+    /// Returns `true` if the event matches the query.
+    fn matches(&self, event: &SwapEvent) -> bool {
+        self.from_slot.is_none_or(|from| event.slot >= from)
+            && self.to_slot.is_none_or(|to| event.slot <= to)
+            && self.pool.as_ref().is_none_or(|pool| &event.pool == pool)
+    }
+}
+
+/// Reads events from an archive directory.
+pub struct ArchiveReader {
+    dir: PathBuf,
+}
+
+impl ArchiveReader {
+    /// Creates a reader for the archive in `dir`.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Returns all block index entries, in write order.
+    pub fn blocks(&self) -> Result<Vec<BlockIndex>, StorageError> {
+        let path = self.dir.join(INDEX_FILE);
+        let file = File::open(&path)
+            .map_err(|e| StorageError::Read(format!("{}: {e}", path.display())))?;
+
+        let mut blocks = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|e| StorageError::Read(e.to_string()))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line) {
+                Ok(entry) => blocks.push(entry),
+                // A crash mid-write can leave a truncated last line
+                Err(e) => log::warn!("Skipping invalid archive index line: {e}"),
+            }
+        }
+        Ok(blocks)
+    }
+
+    /// Calls `f` for every archived event matching `query`, in write order.
+    ///
+    /// Only blocks whose slot range overlaps the query are decompressed.
+    ///
+    /// # Returns
+    ///
+    /// The number of matching events.
+    pub fn for_each(
+        &self,
+        query: &ArchiveQuery,
+        mut f: impl FnMut(&SwapEvent),
+    ) -> Result<usize, StorageError> {
+        let from = query.from_slot.unwrap_or(0);
+        let to = query.to_slot.unwrap_or(u64::MAX);
+        let limit = query.limit.unwrap_or(usize::MAX);
+        let mut count = 0;
+
+        for block in self.blocks()?.iter().filter(|b| b.overlaps(from, to)) {
+            for event in self.read_block(block)? {
+                if count >= limit {
+                    return Ok(count);
+                }
+                if query.matches(&event) {
+                    f(&event);
+                    count += 1;
+                }
+            }
+        }
+        Ok(count)
+    }
+
+    /// Reads and decodes one block.
+    fn read_block(&self, block: &BlockIndex) -> Result<Vec<SwapEvent>, StorageError> {
+        let path = self.dir.join(&block.segment);
+        let read_err = |e: std::io::Error| StorageError::Read(format!("{}: {e}", path.display()));
+
+        let mut file = File::open(&path).map_err(read_err)?;
+        file.seek(SeekFrom::Start(block.offset)).map_err(read_err)?;
+        let mut bytes = vec![0; block.length as usize];
+        file.read_exact(&mut bytes).map_err(read_err)?;
+        decode_block(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{Protocol, TokenInfo},
+    };
+
+    fn event(signature: &str, pool: &str, slot: u64) -> SwapEvent {
+        SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature(signature)
+            .pool(pool)
+            .input_token(TokenInfo::new(
+                "So11111111111111111111111111111111111111112",
+                1_000,
+            ))
+            .slot(slot)
+            .build()
+    }
+
+    #[test]
+    fn test_block_round_trip() {
+        let mut with_maker = event("sig2", "pool-b", 11);
+        with_maker.maker = Some("maker".to_string());
+        let events = vec![event("sig1", "pool-a", 10), with_maker];
+
+        let decoded = decode_block(&encode_block(&events, 3).unwrap()).unwrap();
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].signature, "sig1");
+        assert_eq!(decoded[0].maker, None);
+        assert_eq!(decoded[1].maker.as_deref(), Some("maker"));
+        assert_eq!(
+            decoded[0].input_token.as_ref().map(|t| t.amount_raw),
+            Some(1_000)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_archive_write_and_query() {
+        let dir = env::temp_dir().join(format!("raydium-alerts-archive-{}", std::process::id()));
+        let config = ArchiveConfig {
+            dir: dir.clone(),
+            segment_size: 1,
+            compression_level: 3,
+            batch_size: 10,
+            flush_interval: Duration::from_secs(1),
+        };
+        let mut sink = ArchiveSink::open(config).await.unwrap();
+        sink.write_batch(&[event("a", "pool-a", 100), event("b", "pool-b", 105)])
+            .await
+            .unwrap();
+        sink.write_batch(&[event("c", "pool-a", 200)])
+            .await
+            .unwrap();
+
+        let reader = ArchiveReader::new(&dir);
+        let blocks = reader.blocks().unwrap();
+        assert_eq!(blocks.len(), 2);
+        // Tiny segment size rolls over after every block
+        assert_ne!(blocks[0].segment, blocks[1].segment);
+        assert_eq!((blocks[0].min_slot, blocks[0].max_slot), (100, 105));
+
+        let mut signatures = Vec::new();
+        let query = ArchiveQuery {
+            from_slot: Some(101),
+            ..ArchiveQuery::default()
+        };
+        let count = reader
+            .for_each(&query, |e| signatures.push(e.signature.clone()))
+            .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(signatures, ["b", "c"]);
+
+        let query = ArchiveQuery {
+            pool: Some("pool-a".to_string()),
+            limit: Some(1),
+            ..ArchiveQuery::default()
+        };
+        assert_eq!(reader.for_each(&query, |_| {}).unwrap(), 1);
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_archive_prune_by_size_keeps_current_segment() {
+        let dir = env::temp_dir().join(format!("raydium-alerts-prune-{}", std::process::id()));
+        let config = ArchiveConfig {
+            dir: dir.clone(),
+            segment_size: 1,
+            compression_level: 3,
+            batch_size: 10,
+            flush_interval: Duration::from_secs(1),
+        };
+        let mut sink = ArchiveSink::open(config).await.unwrap();
+        for slot in [100, 200, 300] {
+            sink.write_batch(&[event("sig", "pool", slot)])
+                .await
+                .unwrap();
+        }
+
+        let policy = RetentionPolicy {
+            max_size: Some(1),
+            ..RetentionPolicy::default()
+        };
+        let stats = sink.prune(&policy, 0).await.unwrap();
+        assert_eq!((stats.events, stats.files), (2, 2));
+
+        let blocks = ArchiveReader::new(&dir).blocks().unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].min_slot, 300);
+        assert_eq!(list_segments(&dir).await.unwrap().len(), 1);
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
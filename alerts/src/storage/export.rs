@@ -0,0 +1,722 @@
+//! Rotating CSV and Parquet export files of swap events.
+//!
+//! Events are appended as flat rows (one column per common field, plus the
+//! full event as JSON in `payload`) to files that rotate every hour or day,
+//! and when they reach a size cap, for batch analysis with pandas or DuckDB:
+//!
+//! ```text
+//! <EXPORT_DIR>/
+//! ├── swaps-2026-10-15T13-000.csv
+//! ├── swaps-2026-10-15T14-000.csv
+//! └── swaps-2026-10-15T14-001.csv   # Second part after the size cap
+//! ```
+//!
+//! Files are rotated on the wall clock at write time; each row carries the
+//! event's own `block_time`. A Parquet file is only readable once its footer
+//! is written, so it is written as `<name>.parquet.tmp` and renamed when it is
+//! closed (on rotation or shutdown). Parquet output requires building with
+//! `--features parquet`.
+
+use {
+    super::{BatchWriter, StorageError},
+    crate::{output::SwapEvent, server::unix_now},
+    async_trait::async_trait,
+    std::{
+        env, fmt,
+        fs::{File, OpenOptions},
+        io::Write,
+        path::{Path, PathBuf},
+        time::Duration,
+    },
+};
+
+/// Prefix of export file names.
+const FILE_PREFIX: &str = "swaps-";
+
+/// Suffix of Parquet files not yet closed.
+const TMP_EXTENSION: &str = ".tmp";
+
+/// Export file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    /// Comma-separated values with a header row
+    #[default]
+    Csv,
+    /// Apache Parquet, one row group per batch (requires the `parquet` feature)
+    Parquet,
+}
+
+impl ExportFormat {
+    /// Parses a format name (`csv` or `parquet`).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "parquet" => Some(Self::Parquet),
+            _ => None,
+        }
+    }
+
+    /// Returns the file extension.
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Parquet => "parquet",
+        }
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.extension())
+    }
+}
+
+/// How often a new export or event file is started.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rotation {
+    /// One file per UTC hour
+    #[default]
+    Hourly,
+    /// One file per UTC day
+    Daily,
+}
+
+impl Rotation {
+    /// Parses a rotation name (`hourly` or `daily`).
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "hourly" | "hour" | "1h" => Some(Self::Hourly),
+            "daily" | "day" | "1d" => Some(Self::Daily),
+            _ => None,
+        }
+    }
+
+    /// Returns the period a Unix time falls in, as used in file names
+    /// (e.g., `2026-10-15T13` hourly, `2026-10-15` daily).
+    pub(crate) fn period(self, now: i64) -> String {
+        let (year, month, day) = civil_from_days(now.div_euclid(86_400));
+        match self {
+            Self::Hourly => format!(
+                "{year:04}-{month:02}-{day:02}T{:02}",
+                now.rem_euclid(86_400) / 3_600
+            ),
+            Self::Daily => format!("{year:04}-{month:02}-{day:02}"),
+        }
+    }
+}
+
+/// Configuration for the export files.
+#[derive(Debug, Clone)]
+pub struct ExportConfig {
+    /// Directory the files are written to
+    pub dir: PathBuf,
+    /// File format
+    pub format: ExportFormat,
+    /// Time-based rotation
+    pub rotation: Rotation,
+    /// Size (bytes) after which a file is closed and the next part started
+    pub file_size: Option<u64>,
+    /// Maximum events per write (a Parquet row group)
+    pub batch_size: usize,
+    /// Maximum time an event waits before it is written
+    pub flush_interval: Duration,
+}
+
+impl ExportConfig {
+    /// Creates export configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `EXPORT_DIR` - Optional: Directory enabling the export files
+    /// - `EXPORT_FORMAT` - Optional: `csv` or `parquet` (default: csv)
+    /// - `EXPORT_ROTATION` - Optional: `hourly` or `daily` (default: hourly)
+    /// - `EXPORT_FILE_SIZE_MB` - Optional: Size after which the next part is started
+    ///   (default: unlimited)
+    /// - `EXPORT_BATCH_SIZE` - Optional: Events per write (default: 1000)
+    /// - `EXPORT_FLUSH_INTERVAL_MS` - Optional: Write interval in ms (default: 10000)
+    ///
+    /// # Returns
+    ///
+    /// `None` if `EXPORT_DIR` is not set.
+    pub fn from_env() -> Option<Self> {
+        let dir = env::var("EXPORT_DIR")
+            .ok()
+            .filter(|v| !v.trim().is_empty())?;
+
+        let format = match env::var("EXPORT_FORMAT") {
+            Ok(value) if !value.trim().is_empty() => {
+                ExportFormat::parse(&value).unwrap_or_else(|| {
+                    log::warn!("Invalid EXPORT_FORMAT '{value}', expected csv or parquet");
+                    ExportFormat::default()
+                })
+            }
+            _ => ExportFormat::default(),
+        };
+
+        let rotation = match env::var("EXPORT_ROTATION") {
+            Ok(value) if !value.trim().is_empty() => Rotation::parse(&value).unwrap_or_else(|| {
+                log::warn!("Invalid EXPORT_ROTATION '{value}', expected hourly or daily");
+                Rotation::default()
+            }),
+            _ => Rotation::default(),
+        };
+
+        let file_size = env::var("EXPORT_FILE_SIZE_MB")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .filter(|&n| n > 0)
+            .map(|mb| mb * 1024 * 1024);
+
+        let batch_size: usize = env::var("EXPORT_BATCH_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(1000);
+
+        let flush_interval_ms: u64 = env::var("EXPORT_FLUSH_INTERVAL_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(10_000);
+
+        Some(Self {
+            dir: PathBuf::from(dir.trim()),
+            format,
+            rotation,
+            file_size,
+            batch_size,
+            flush_interval: Duration::from_millis(flush_interval_ms),
+        })
+    }
+}
+
+/// Value type of an export column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnType {
+    Text,
+    UInt,
+    Int,
+    Float,
+}
+
+/// Export columns, in file order.
+const COLUMNS: &[(&str, ColumnType)] = &[
+    ("slot", ColumnType::UInt),
+    ("block_time", ColumnType::Int),
+    ("signature", ColumnType::Text),
+    ("event_id", ColumnType::Text),
+    ("event_type", ColumnType::Text),
+    ("protocol", ColumnType::Text),
+    ("pool", ColumnType::Text),
+    ("direction", ColumnType::Text),
+    ("side", ColumnType::Text),
+    ("input_mint", ColumnType::Text),
+    ("input_symbol", ColumnType::Text),
+    ("input_amount_raw", ColumnType::UInt),
+    ("input_amount", ColumnType::Float),
+    ("input_amount_usd", ColumnType::Float),
+    ("output_mint", ColumnType::Text),
+    ("output_symbol", ColumnType::Text),
+    ("output_amount_raw", ColumnType::UInt),
+    ("output_amount", ColumnType::Float),
+    ("output_amount_usd", ColumnType::Float),
+    ("fee", ColumnType::UInt),
+    ("maker", ColumnType::Text),
+    ("maker_label", ColumnType::Text),
+    ("effective_price", ColumnType::Float),
+    ("price_impact_pct", ColumnType::Float),
+    ("market_cap_usd", ColumnType::Float),
+    ("usd_value", ColumnType::Float),
+    ("payload", ColumnType::Text),
+];
+
+/// A value of an export row.
+#[derive(Debug, Clone, PartialEq)]
+enum Cell {
+    Text(Option<String>),
+    UInt(Option<u64>),
+    Int(Option<i64>),
+    Float(Option<f64>),
+}
+
+/// Flattens an event into a row matching [`COLUMNS`].
+fn row(event: &SwapEvent) -> Vec<Cell> {
+    let input = event.input_token.as_ref();
+    let output = event.output_token.as_ref();
+    vec![
+        Cell::UInt(Some(event.slot)),
+        Cell::Int(event.timestamp),
+        Cell::Text(Some(event.signature.clone())),
+        Cell::Text(Some(event.event_id.clone())),
+        Cell::Text(Some(event.event_type.to_string())),
+        Cell::Text(Some(event.protocol.to_string())),
+        Cell::Text(Some(event.pool.clone())),
+        Cell::Text(Some(event.direction.to_string())),
+        Cell::Text(event.side.map(|side| side.to_string())),
+        Cell::Text(input.map(|t| t.mint.clone())),
+        Cell::Text(input.and_then(|t| t.symbol.clone())),
+        Cell::UInt(input.map(|t| t.amount_raw)),
+        Cell::Float(input.and_then(|t| t.amount)),
+        Cell::Float(input.and_then(|t| t.amount_usd)),
+        Cell::Text(output.map(|t| t.mint.clone())),
+        Cell::Text(output.and_then(|t| t.symbol.clone())),
+        Cell::UInt(output.map(|t| t.amount_raw)),
+        Cell::Float(output.and_then(|t| t.amount)),
+        Cell::Float(output.and_then(|t| t.amount_usd)),
+        Cell::UInt(event.fee),
+        Cell::Text(event.maker.clone()),
+        Cell::Text(event.maker_label.clone()),
+        Cell::Float(event.effective_price),
+        Cell::Float(event.price_impact_pct),
+        Cell::Float(event.market_cap_usd),
+        Cell::Float(event.usd_value()),
+        Cell::Text(serde_json::to_string(event).ok()),
+    ]
+}
+
+/// Quotes a CSV field if it contains a separator, quote, or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Formats rows as CSV lines; missing values are empty fields.
+fn csv_lines(rows: &[Vec<Cell>]) -> String {
+    let mut out = String::new();
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            match cell {
+                Cell::Text(Some(v)) => out.push_str(&csv_field(v)),
+                Cell::UInt(Some(v)) => out.push_str(&v.to_string()),
+                Cell::Int(Some(v)) => out.push_str(&v.to_string()),
+                Cell::Float(Some(v)) => out.push_str(&v.to_string()),
+                _ => {}
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Writer of the file being appended to.
+enum FileWriter {
+    Csv(File),
+    #[cfg(feature = "parquet")]
+    Parquet(parquet_file::ParquetWriter),
+}
+
+/// Export file being appended to.
+struct OpenFile {
+    period: String,
+    part: u32,
+    /// Final path of the file
+    path: PathBuf,
+    /// Bytes written so far
+    size: u64,
+    writer: FileWriter,
+}
+
+impl OpenFile {
+    /// Creates the next free part of a period.
+    fn create(config: &ExportConfig, period: String, mut part: u32) -> Result<Self, StorageError> {
+        let write_err = |path: &Path, e: std::io::Error| {
+            StorageError::Write(format!("{}: {e}", path.display()))
+        };
+        let extension = config.format.extension();
+        // Earlier runs may have written parts of this period
+        let path = loop {
+            let path = config
+                .dir
+                .join(format!("{FILE_PREFIX}{period}-{part:03}.{extension}"));
+            let tmp = tmp_path(&path);
+            if !path.exists() && !tmp.exists() {
+                break path;
+            }
+            part += 1;
+        };
+
+        let writer = match config.format {
+            ExportFormat::Csv => {
+                let mut file = OpenOptions::new()
+                    .create_new(true)
+                    .write(true)
+                    .open(&path)
+                    .map_err(|e| write_err(&path, e))?;
+                let header: Vec<&str> = COLUMNS.iter().map(|(name, _)| *name).collect();
+                writeln!(file, "{}", header.join(",")).map_err(|e| write_err(&path, e))?;
+                FileWriter::Csv(file)
+            }
+            #[cfg(feature = "parquet")]
+            ExportFormat::Parquet => {
+                let tmp = tmp_path(&path);
+                let file = File::create(&tmp).map_err(|e| write_err(&tmp, e))?;
+                FileWriter::Parquet(parquet_file::ParquetWriter::new(file)?)
+            }
+            #[cfg(not(feature = "parquet"))]
+            ExportFormat::Parquet => return Err(parquet_unsupported()),
+        };
+
+        log::debug!("Export file started: {}", path.display());
+        Ok(Self {
+            period,
+            part,
+            path,
+            size: 0,
+            writer,
+        })
+    }
+
+    /// Appends rows to the file.
+    fn write(&mut self, rows: &[Vec<Cell>]) -> Result<(), StorageError> {
+        match self.writer {
+            FileWriter::Csv(ref mut file) => {
+                let lines = csv_lines(rows);
+                file.write_all(lines.as_bytes())
+                    .map_err(|e| StorageError::Write(format!("{}: {e}", self.path.display())))?;
+                self.size += lines.len() as u64;
+            }
+            #[cfg(feature = "parquet")]
+            FileWriter::Parquet(ref mut writer) => self.size = writer.write(rows)?,
+        }
+        Ok(())
+    }
+
+    /// Completes the file (a Parquet file's footer) and moves it to its final path.
+    fn finish(self) -> Result<(), StorageError> {
+        match self.writer {
+            FileWriter::Csv(mut file) => file
+                .flush()
+                .map_err(|e| StorageError::Write(format!("{}: {e}", self.path.display()))),
+            #[cfg(feature = "parquet")]
+            FileWriter::Parquet(writer) => {
+                writer.close()?;
+                std::fs::rename(tmp_path(&self.path), &self.path)
+                    .map_err(|e| StorageError::Write(format!("{}: {e}", self.path.display())))
+            }
+        }
+    }
+}
+
+/// Returns the path a Parquet file is written to until it is closed.
+fn tmp_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(TMP_EXTENSION);
+    PathBuf::from(name)
+}
+
+/// Error returned for Parquet output in builds without the `parquet` feature.
+#[cfg(not(feature = "parquet"))]
+fn parquet_unsupported() -> StorageError {
+    StorageError::Connection(
+        "EXPORT_FORMAT=parquet requires building with `--features parquet`".to_string(),
+    )
+}
+
+/// Rotating export files.
+struct ExportFiles {
+    config: ExportConfig,
+    current: Option<OpenFile>,
+}
+
+impl ExportFiles {
+    /// Appends rows to the file of the period `now` falls in, rotating files
+    /// by period and size first.
+    fn write(&mut self, rows: &[Vec<Cell>], now: i64) -> Result<(), StorageError> {
+        let period = self.config.rotation.period(now);
+        let rotate = self.current.as_ref().map(|file| {
+            let full = self.config.file_size.is_some_and(|max| file.size >= max);
+            (file.period != period, full)
+        });
+        let part = match rotate {
+            None | Some((true, _)) => Some(0),
+            Some((false, true)) => self.current.as_ref().map(|file| file.part + 1),
+            Some((false, false)) => None,
+        };
+
+        if let Some(part) = part {
+            if let Some(file) = self.current.take() {
+                file.finish()?;
+            }
+            self.current = Some(OpenFile::create(&self.config, period, part)?);
+        }
+        self.current.as_mut().expect("a file is open").write(rows)
+    }
+}
+
+impl Drop for ExportFiles {
+    /// Closes the current file, so a Parquet file gets its footer on shutdown.
+    fn drop(&mut self) {
+        if let Some(file) = self.current.take() {
+            if let Err(e) = file.finish() {
+                log::warn!("Failed to close export file: {e}");
+            }
+        }
+    }
+}
+
+/// Batch writer appending events to rotating CSV or Parquet files.
+///
+/// Files are written on a blocking thread, so encoding a Parquet row group
+/// never stalls the runtime.
+pub struct ExportSink {
+    files: Option<ExportFiles>,
+}
+
+impl ExportSink {
+    /// Creates the export directory if needed.
+    pub async fn open(config: ExportConfig) -> Result<Self, StorageError> {
+        #[cfg(not(feature = "parquet"))]
+        if config.format == ExportFormat::Parquet {
+            return Err(parquet_unsupported());
+        }
+        tokio::fs::create_dir_all(&config.dir)
+            .await
+            .map_err(|e| StorageError::Connection(format!("{}: {e}", config.dir.display())))?;
+        log::info!(
+            "Export enabled: {} ({}, {:?})",
+            config.dir.display(),
+            config.format,
+            config.rotation
+        );
+
+        Ok(Self {
+            files: Some(ExportFiles {
+                config,
+                current: None,
+            }),
+        })
+    }
+}
+
+#[async_trait]
+impl BatchWriter for ExportSink {
+    fn name(&self) -> &'static str {
+        "export"
+    }
+
+    async fn write_batch(&mut self, events: &[SwapEvent]) -> Result<(), StorageError> {
+        if events.is_empty() {
+            return Ok(());
+        }
+        let rows: Vec<Vec<Cell>> = events.iter().map(row).collect();
+        let mut files = self.files.take().ok_or(StorageError::Closed)?;
+        let (files, result) = tokio::task::spawn_blocking(move || {
+            let result = files.write(&rows, unix_now());
+            (files, result)
+        })
+        .await
+        .map_err(|e| StorageError::Write(e.to_string()))?;
+        self.files = Some(files);
+        result
+    }
+}
+
+/// Year, month, and day of a number of days since the Unix epoch (Howard Hinnant).
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Parquet encoding of export rows.
+#[cfg(feature = "parquet")]
+mod parquet_file {
+    use {
+        super::{Cell, ColumnType, StorageError, COLUMNS},
+        arrow_array::{ArrayRef, Float64Array, Int64Array, RecordBatch, StringArray, UInt64Array},
+        arrow_schema::{DataType, Field, Schema},
+        parquet::{
+            arrow::ArrowWriter,
+            basic::{Compression, ZstdLevel},
+            file::properties::WriterProperties,
+        },
+        std::{fs::File, sync::Arc},
+    };
+
+    /// Parquet file writer with the export schema.
+    pub struct ParquetWriter {
+        schema: Arc<Schema>,
+        writer: ArrowWriter<File>,
+    }
+
+    impl ParquetWriter {
+        /// Starts a Parquet file with zstd-compressed columns.
+        pub fn new(file: File) -> Result<Self, StorageError> {
+            let fields: Vec<Field> = COLUMNS
+                .iter()
+                .map(|&(name, column)| {
+                    let data_type = match column {
+                        ColumnType::Text => DataType::Utf8,
+                        ColumnType::UInt => DataType::UInt64,
+                        ColumnType::Int => DataType::Int64,
+                        ColumnType::Float => DataType::Float64,
+                    };
+                    Field::new(name, data_type, true)
+                })
+                .collect();
+            let schema = Arc::new(Schema::new(fields));
+            let properties = WriterProperties::builder()
+                .set_compression(Compression::ZSTD(ZstdLevel::default()))
+                .build();
+            let writer = ArrowWriter::try_new(file, schema.clone(), Some(properties))
+                .map_err(|e| StorageError::Write(e.to_string()))?;
+            Ok(Self { schema, writer })
+        }
+
+        /// Writes rows as one row group, returning the bytes written so far.
+        pub fn write(&mut self, rows: &[Vec<Cell>]) -> Result<u64, StorageError> {
+            let columns: Vec<ArrayRef> = COLUMNS
+                .iter()
+                .enumerate()
+                .map(|(i, &(_, column))| -> ArrayRef {
+                    match column {
+                        ColumnType::Text => {
+                            Arc::new(StringArray::from_iter(rows.iter().map(|row| {
+                                match row[i] {
+                                    Cell::Text(ref v) => v.clone(),
+                                    _ => None,
+                                }
+                            })))
+                        }
+                        ColumnType::UInt => {
+                            Arc::new(UInt64Array::from_iter(rows.iter().map(|row| {
+                                match row[i] {
+                                    Cell::UInt(v) => v,
+                                    _ => None,
+                                }
+                            })))
+                        }
+                        ColumnType::Int => {
+                            Arc::new(Int64Array::from_iter(rows.iter().map(|row| match row[i] {
+                                Cell::Int(v) => v,
+                                _ => None,
+                            })))
+                        }
+                        ColumnType::Float => {
+                            Arc::new(Float64Array::from_iter(rows.iter().map(|row| {
+                                match row[i] {
+                                    Cell::Float(v) => v,
+                                    _ => None,
+                                }
+                            })))
+                        }
+                    }
+                })
+                .collect();
+            let batch = RecordBatch::try_new(self.schema.clone(), columns)
+                .map_err(|e| StorageError::Write(e.to_string()))?;
+            self.writer
+                .write(&batch)
+                .map_err(|e| StorageError::Write(e.to_string()))?;
+            self.writer
+                .flush()
+                .map_err(|e| StorageError::Write(e.to_string()))?;
+            Ok(self.writer.bytes_written() as u64)
+        }
+
+        /// Writes the file footer.
+        pub fn close(self) -> Result<(), StorageError> {
+            self.writer
+                .close()
+                .map(|_| ())
+                .map_err(|e| StorageError::Write(e.to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{Protocol, TokenInfo},
+        std::fs,
+    };
+
+    fn event(signature: &str) -> SwapEvent {
+        SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature(signature)
+            .pool("pool")
+            .input_token(TokenInfo::new("mint_in", 1_000))
+            .slot(42)
+            .build()
+    }
+
+    #[test]
+    fn test_rotation_periods() {
+        // 2026-10-15 13:20:00 UTC
+        let now = 1_792_070_400;
+        assert_eq!(Rotation::Hourly.period(now), "2026-10-15T13");
+        assert_eq!(Rotation::Daily.period(now), "2026-10-15");
+        assert_eq!(Rotation::Daily.period(0), "1970-01-01");
+    }
+
+    #[test]
+    fn test_csv_rows() {
+        let mut event = event("sig");
+        event.maker_label = Some("desk, \"A\"".to_string());
+        let lines = csv_lines(&[row(&event)]);
+        let fields: Vec<&str> = lines.split(',').take(3).collect();
+        assert_eq!(fields, ["42", "", "sig"]);
+        assert!(lines.contains(",\"desk, \"\"A\"\"\","));
+        assert!(lines.ends_with('\n'));
+        assert_eq!(row(&event).len(), COLUMNS.len());
+    }
+
+    #[test]
+    fn test_files_rotate_by_period_and_size() {
+        let dir = env::temp_dir().join(format!("raydium-alerts-export-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let mut files = ExportFiles {
+            config: ExportConfig {
+                dir: dir.clone(),
+                format: ExportFormat::Csv,
+                rotation: Rotation::Hourly,
+                file_size: Some(1),
+                batch_size: 10,
+                flush_interval: Duration::from_secs(1),
+            },
+            current: None,
+        };
+        let rows = vec![row(&event("a"))];
+        let hour = 1_792_069_200; // 2026-10-15 13:00:00 UTC
+        files.write(&rows, hour).unwrap();
+        // Over the size cap: second part of the same hour
+        files.write(&rows, hour + 60).unwrap();
+        // Next hour
+        files.write(&rows, hour + 3_600).unwrap();
+        drop(files);
+
+        let mut names: Vec<String> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "swaps-2026-10-15T13-000.csv",
+                "swaps-2026-10-15T13-001.csv",
+                "swaps-2026-10-15T14-000.csv"
+            ]
+        );
+        let csv = fs::read_to_string(dir.join(&names[0])).unwrap();
+        assert!(csv.starts_with("slot,block_time,signature,"));
+        assert_eq!(csv.lines().count(), 2);
+
+        fs::remove_dir_all(dir).ok();
+    }
+}
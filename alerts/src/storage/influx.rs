@@ -0,0 +1,271 @@
+//! InfluxDB writer of per-swap points in line protocol.
+//!
+//! Each swap is written as one point, so Grafana charts volume straight from
+//! InfluxDB instead of a receiver aggregating webhooks:
+//!
+//! ```text
+//! raydium_swap,protocol=cpmm,pool=<POOL>,mint=<MINT>,side=buy amount_raw=1250000i,amount=1.25,usd=187.5 1792069200000000000
+//! ```
+//!
+//! `mint` is the traded token (the non-base token of the pair), and `amount`
+//! its UI amount when its decimals are known. Points are timestamped with the
+//! block time in nanoseconds; consecutive swaps of a second are spread a
+//! nanosecond apart, since points of a series with the same timestamp
+//! overwrite each other. Batches are posted to the v2 write API
+//! (`/api/v2/write`), served by InfluxDB 2.x and 3.x.
+
+use {
+    super::{BatchWriter, StorageError},
+    crate::{
+        output::{EventType, SwapEvent},
+        server::unix_now,
+    },
+    async_trait::async_trait,
+    std::{env, time::Duration},
+};
+
+/// Request timeout of the write API.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Configuration for the InfluxDB writer.
+#[derive(Debug, Clone)]
+pub struct InfluxConfig {
+    /// Base URL of the server (e.g., `http://localhost:8086`)
+    pub url: String,
+    /// Bucket (or v3 database) the points are written to
+    pub bucket: String,
+    /// Organization of the bucket (ignored by InfluxDB 3)
+    pub org: Option<String>,
+    /// API token, sent as `Authorization: Token <token>`
+    pub token: Option<String>,
+    /// Measurement name of the points
+    pub measurement: String,
+    /// Maximum points per write
+    pub batch_size: usize,
+    /// Maximum time a point waits before it is written
+    pub flush_interval: Duration,
+}
+
+impl InfluxConfig {
+    /// Creates InfluxDB configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `INFLUX_URL` - Optional: Server URL enabling the writer
+    /// - `INFLUX_BUCKET` - Bucket (or database) the points are written to
+    /// - `INFLUX_ORG` - Optional: Organization of the bucket
+    /// - `INFLUX_TOKEN` - Optional: API token
+    /// - `INFLUX_MEASUREMENT` - Optional: Measurement name (default: raydium_swap)
+    /// - `INFLUX_BATCH_SIZE` - Optional: Points per write (default: 500)
+    /// - `INFLUX_FLUSH_INTERVAL_MS` - Optional: Write interval in ms (default: 1000)
+    ///
+    /// # Returns
+    ///
+    /// `None` if `INFLUX_URL` is not set, or `INFLUX_BUCKET` is missing.
+    pub fn from_env() -> Option<Self> {
+        let url = env::var("INFLUX_URL")
+            .ok()
+            .map(|v| v.trim().trim_end_matches('/').to_string())
+            .filter(|v| !v.is_empty())?;
+
+        let Some(bucket) = non_empty("INFLUX_BUCKET") else {
+            log::warn!("INFLUX_URL is set without INFLUX_BUCKET, InfluxDB writer disabled");
+            return None;
+        };
+
+        let measurement =
+            non_empty("INFLUX_MEASUREMENT").unwrap_or_else(|| "raydium_swap".to_string());
+
+        let batch_size: usize = env::var("INFLUX_BATCH_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(500);
+
+        let flush_interval_ms: u64 = env::var("INFLUX_FLUSH_INTERVAL_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(1000);
+
+        Some(Self {
+            url,
+            bucket,
+            org: non_empty("INFLUX_ORG"),
+            token: non_empty("INFLUX_TOKEN"),
+            measurement,
+            batch_size,
+            flush_interval: Duration::from_millis(flush_interval_ms),
+        })
+    }
+}
+
+/// Returns a trimmed, non-empty environment variable.
+fn non_empty(name: &str) -> Option<String> {
+    env::var(name)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Escapes a measurement name (commas and spaces).
+fn escape_measurement(value: &str) -> String {
+    value.replace(',', "\\,").replace(' ', "\\ ")
+}
+
+/// Escapes a tag value (commas, equals signs, and spaces).
+fn escape_tag(value: &str) -> String {
+    value
+        .replace(',', "\\,")
+        .replace('=', "\\=")
+        .replace(' ', "\\ ")
+}
+
+/// Returns the line protocol point of a swap, timestamped `timestamp_ns`, or
+/// `None` for other events and swaps without tokens.
+pub fn point(measurement: &str, event: &SwapEvent, timestamp_ns: i64) -> Option<String> {
+    if event.event_type != EventType::Swap {
+        return None;
+    }
+    let (_, token) = event.get_base_quote_tokens();
+    let token = token?;
+
+    let mut line = format!(
+        "{},protocol={},pool={},mint={}",
+        escape_measurement(measurement),
+        escape_tag(&event.protocol.to_string().to_lowercase()),
+        escape_tag(&event.pool),
+        escape_tag(&token.mint)
+    );
+    if let Some(side) = event.side {
+        line.push_str(&format!(",side={side}"));
+    }
+
+    let amount_raw = i64::try_from(token.amount_raw).unwrap_or(i64::MAX);
+    line.push_str(&format!(" amount_raw={amount_raw}i"));
+    if let Some(amount) = token.amount.filter(|amount| amount.is_finite()) {
+        line.push_str(&format!(",amount={amount}"));
+    }
+    if let Some(usd) = event.usd_value().filter(|usd| usd.is_finite()) {
+        line.push_str(&format!(",usd={usd}"));
+    }
+    line.push_str(&format!(" {timestamp_ns}"));
+    Some(line)
+}
+
+/// Writes swap points to InfluxDB.
+pub struct InfluxSink {
+    client: reqwest::Client,
+    write_url: String,
+    config: InfluxConfig,
+    /// Block time of the last point written, and points written at it
+    last_second: (i64, i64),
+}
+
+impl InfluxSink {
+    /// Creates the writer; the server is first contacted by the first write.
+    pub fn connect(config: InfluxConfig) -> Result<Self, StorageError> {
+        let client = reqwest::Client::builder()
+            .timeout(WRITE_TIMEOUT)
+            .build()
+            .map_err(|e| StorageError::Connection(e.to_string()))?;
+        let write_url = format!("{}/api/v2/write", config.url);
+        log::info!(
+            "InfluxDB writer enabled: {} (bucket {}, measurement {})",
+            config.url,
+            config.bucket,
+            config.measurement
+        );
+        Ok(Self {
+            client,
+            write_url,
+            config,
+            last_second: (0, 0),
+        })
+    }
+
+    /// Returns the timestamp of a point of `event`: its block time, plus a
+    /// nanosecond per point already written in the same second.
+    fn timestamp_ns(&mut self, event: &SwapEvent) -> i64 {
+        let second = event.timestamp.unwrap_or_else(unix_now);
+        let (last, written) = self.last_second;
+        let offset = if second == last { written } else { 0 };
+        self.last_second = (second, offset + 1);
+        second.saturating_mul(1_000_000_000).saturating_add(offset)
+    }
+}
+
+#[async_trait]
+impl BatchWriter for InfluxSink {
+    fn name(&self) -> &'static str {
+        "influxdb"
+    }
+
+    async fn write_batch(&mut self, events: &[SwapEvent]) -> Result<(), StorageError> {
+        let mut lines = Vec::new();
+        for event in events {
+            if event.event_type != EventType::Swap {
+                continue;
+            }
+            let timestamp_ns = self.timestamp_ns(event);
+            lines.extend(point(&self.config.measurement, event, timestamp_ns));
+        }
+        if lines.is_empty() {
+            return Ok(());
+        }
+
+        let mut query = vec![("bucket", self.config.bucket.as_str()), ("precision", "ns")];
+        if let Some(ref org) = self.config.org {
+            query.push(("org", org.as_str()));
+        }
+        let mut request = self
+            .client
+            .post(&self.write_url)
+            .query(&query)
+            .header("Content-Type", "text/plain; charset=utf-8")
+            .body(lines.join("\n"));
+        if let Some(ref token) = self.config.token {
+            request = request.header("Authorization", format!("Token {token}"));
+        }
+        let response = request
+            .send()
+            .await
+            .map_err(|e| StorageError::Write(e.to_string()))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(StorageError::Write(format!("HTTP {status}: {body}")));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{swap_event::WSOL_MINT, Protocol, TokenInfo},
+    };
+
+    #[test]
+    fn test_point_line_protocol() {
+        let event = SwapEvent::builder()
+            .protocol(Protocol::AmmV4)
+            .signature("sig")
+            .pool("pool 1")
+            .input_token(TokenInfo::new(WSOL_MINT, 1_500_000_000).with_usd_value(187.5))
+            .output_token(TokenInfo::new("mint,a", 1_250_000).with_decimals(6))
+            .build();
+        assert_eq!(
+            point("raydium_swap", &event, 42).as_deref(),
+            Some(
+                "raydium_swap,protocol=amm-v4,pool=pool\\ 1,mint=mint\\,a,side=buy \
+                 amount_raw=1250000i,amount=1.25,usd=187.5 42"
+            )
+        );
+
+        let mut created = event;
+        created.event_type = EventType::CreatePool;
+        assert_eq!(point("raydium_swap", &created, 42), None);
+    }
+}
@@ -0,0 +1,90 @@
+//! Schema versioning shared by the database sinks.
+//!
+//! Each database sink embeds an ordered list of migrations; the index + 1 of
+//! a migration is the schema version it produces. On connect, the sink reads
+//! the version stored in the database and checks it against the migrations
+//! this binary knows:
+//!
+//! - A new, empty database is created at the latest version.
+//! - A database at the latest version is used as is.
+//! - A database with pending migrations is only migrated with `--migrate`
+//!   ([`MigrationMode::Apply`]); otherwise startup fails, so an upgraded binary
+//!   never writes into a table layout it was not built for.
+//! - A database at a version newer than this binary knows (written by a newer
+//!   release) is always refused.
+
+use super::StorageError;
+
+/// How a database sink handles pending migrations on connect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MigrationMode {
+    /// Fail if an existing database needs migrations (default)
+    #[default]
+    Check,
+    /// Apply pending migrations (`--migrate`)
+    Apply,
+}
+
+/// Checks a database's schema version before its pending migrations are applied.
+///
+/// # Arguments
+///
+/// * `backend` - Database name for messages (e.g., "PostgreSQL")
+/// * `current` - Schema version stored in the database
+/// * `latest` - Number of migrations this binary knows
+/// * `fresh` - `true` if the database has no tables of the sink yet
+/// * `mode` - How pending migrations are handled
+///
+/// # Returns
+///
+/// `Ok(())` if the pending migrations (if any) may be applied.
+pub(super) fn check_version(
+    backend: &str,
+    current: i64,
+    latest: usize,
+    fresh: bool,
+    mode: MigrationMode,
+) -> Result<(), StorageError> {
+    let latest = latest as i64;
+    if current > latest {
+        return Err(StorageError::Schema(format!(
+            "{backend} schema is at version {current}, newer than version {latest} \
+             supported by this binary; upgrade the binary"
+        )));
+    }
+    if current < latest && !fresh && mode == MigrationMode::Check {
+        return Err(StorageError::Schema(format!(
+            "{backend} schema is at version {current}, this binary needs version {latest} \
+             ({} pending migration(s)); back up the database and restart with --migrate",
+            latest - current
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_version() {
+        use MigrationMode::{Apply, Check};
+
+        // Up to date, or a new database
+        assert!(check_version("SQLite", 4, 4, false, Check).is_ok());
+        assert!(check_version("SQLite", 0, 4, true, Check).is_ok());
+
+        // Pending migrations need --migrate
+        assert!(matches!(
+            check_version("SQLite", 2, 4, false, Check),
+            Err(StorageError::Schema(_))
+        ));
+        assert!(check_version("SQLite", 2, 4, false, Apply).is_ok());
+
+        // A newer schema is never written to
+        assert!(matches!(
+            check_version("SQLite", 5, 4, false, Apply),
+            Err(StorageError::Schema(_))
+        ));
+    }
+}
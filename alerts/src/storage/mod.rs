@@ -0,0 +1,796 @@
+//! Persistent storage sinks for normalized swap events.
+//!
+//! This module provides:
+//! - [`StorageSink`] - Non-blocking interface used by processors to persist events
+//! - [`BatchSink`] - Channel-backed sink that batches events for a [`BatchWriter`]
+//! - [`EventStore`] - Reads stored events in `seq` order for consumer backfill
+//! - [`PostgresSink`] - PostgreSQL writer (sqlx) for queryable swap history
+//! - [`SqliteSink`] - Local SQLite file writer for lightweight deployments
+//! - [`ArchiveSink`] - zstd-compressed file archive with a slot-range index
+//! - [`ExportSink`] - Rotating CSV or Parquet files for batch analysis
+//! - [`NatsSink`] - Publishes events to NATS subjects, optionally persisted in JetStream
+//! - [`AmqpSink`] - Publishes events to a RabbitMQ topic exchange with publisher confirms
+//! - [`MqttSink`] - Publishes compact events to MQTT topics per pool and token
+//! - [`InfluxSink`] - Writes per-swap points to InfluxDB for time-series charts
+//! - [`FanoutSink`] - Sends each event to several sinks
+//! - [`RetentionPolicy`] - Age and size limits applied by each sink's flush task
+//! - [`MigrationMode`] - Whether database sinks apply pending schema migrations
+//!
+//! Sinks follow the same pattern as the webhook notifier: processors queue events
+//! with `try_store`, and a background task flushes them in batches so database
+//! latency never blocks instruction processing. The same task feeds stored swaps
+//! into a [`CandleBuilder`] and persists the candles it closes.
+//!
+//! Database sinks also accept updates (`try_update`): a later, more complete
+//! version of a stored event, such as the follow-up of an event emitted before
+//! its enrichment finished, replaces the stored payload.
+
+mod amqp;
+mod archive;
+mod export;
+mod influx;
+mod migrations;
+mod mqtt;
+mod nats;
+mod postgres;
+mod retention;
+mod sqlite;
+
+pub use {
+    amqp::{AmqpConfig, AmqpSink},
+    archive::{ArchiveConfig, ArchiveQuery, ArchiveReader, ArchiveSink},
+    export::{ExportConfig, ExportSink, Rotation},
+    influx::{InfluxConfig, InfluxSink},
+    migrations::MigrationMode,
+    mqtt::{CompactEvent, MqttConfig, MqttPayload, MqttSink},
+    nats::{NatsConfig, NatsSink},
+    postgres::PostgresSink,
+    retention::{PruneStats, RetentionPolicy},
+    sqlite::SqliteSink,
+};
+
+use {
+    crate::{
+        candles::{Candle, CandleBuilder, CandleStore, MemoryCandles},
+        output::SwapEvent,
+        server::{unix_now, PriceTick},
+    },
+    async_trait::async_trait,
+    serde::Serialize,
+    std::{env, fmt, sync::Arc, time::Duration},
+    tokio::sync::mpsc,
+};
+
+/// Errors returned by storage sinks.
+#[derive(Debug)]
+pub enum StorageError {
+    /// Failed to connect to or initialize the backing store
+    Connection(String),
+    /// Failed to write a batch of events
+    Write(String),
+    /// Failed to read from the backing store
+    Read(String),
+    /// The database schema is not at the version this binary writes
+    Schema(String),
+    /// The sink's queue is full; the event was dropped
+    QueueFull,
+    /// The sink's background task has stopped
+    Closed,
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Connection(e) => write!(f, "storage connection error: {e}"),
+            Self::Write(e) => write!(f, "storage write error: {e}"),
+            Self::Read(e) => write!(f, "storage read error: {e}"),
+            Self::Schema(e) => write!(f, "storage schema error: {e}"),
+            Self::QueueFull => write!(f, "storage queue is full"),
+            Self::Closed => write!(f, "storage sink is closed"),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+/// Non-blocking interface for persisting swap events.
+pub trait StorageSink: Send + Sync {
+    /// Returns a short name for logging (e.g., "postgres").
+    fn name(&self) -> &str;
+
+    /// Queues an event for persistence without blocking.
+    fn try_store(&self, event: SwapEvent) -> Result<(), StorageError>;
+
+    /// Queues a more complete version of a stored event without blocking.
+    ///
+    /// Sinks that cannot update stored events ignore it.
+    fn try_update(&self, event: SwapEvent) -> Result<(), StorageError>;
+}
+
+/// Writes batches of events to a backing store.
+#[async_trait]
+pub trait BatchWriter: Send + 'static {
+    /// Returns a short name for logging (e.g., "postgres").
+    fn name(&self) -> &'static str;
+
+    /// Writes a batch of events. Events already stored must be ignored.
+    async fn write_batch(&mut self, events: &[SwapEvent]) -> Result<(), StorageError>;
+
+    /// Returns `true` if the writer can update stored events; updates are
+    /// not queued for writers that cannot.
+    fn supports_updates(&self) -> bool {
+        false
+    }
+
+    /// Replaces stored events with the given versions. Events not stored must
+    /// be ignored.
+    async fn update_batch(&mut self, _events: &[SwapEvent]) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    /// Writes closed candles, merging them into candles already stored for the
+    /// same pool, interval, and open time.
+    async fn write_candles(&mut self, _candles: &[Candle]) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    /// Deletes data outside the retention policy. `now` is the Unix time in seconds.
+    async fn prune(
+        &mut self,
+        _policy: &RetentionPolicy,
+        _now: i64,
+    ) -> Result<PruneStats, StorageError> {
+        Ok(PruneStats::default())
+    }
+}
+
+/// An event read back from a database with its position in the store.
+#[derive(Debug, Clone, Serialize)]
+pub struct StoredEvent {
+    /// Increasing number assigned when the event was stored
+    pub seq: u64,
+    /// The stored event payload
+    pub event: serde_json::Value,
+}
+
+/// Read access to stored events, keyed by the store's `seq`.
+#[async_trait]
+pub trait EventStore: Send + Sync {
+    /// Returns at most `limit` events with `seq > after_seq` and
+    /// `slot >= from_slot`, in `seq` order.
+    async fn events_after(
+        &self,
+        after_seq: u64,
+        from_slot: u64,
+        limit: usize,
+    ) -> Result<Vec<StoredEvent>, StorageError>;
+}
+
+/// Batching configuration shared by all storage sinks.
+#[derive(Debug, Clone)]
+pub struct StorageConfig {
+    /// Maximum events per write
+    pub batch_size: usize,
+    /// Maximum time an event waits before its batch is flushed
+    pub flush_interval: Duration,
+    /// Maximum events queued before new events are dropped
+    pub queue_capacity: usize,
+    /// Age and size limits applied periodically
+    pub retention: RetentionPolicy,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 100,
+            flush_interval: Duration::from_millis(1000),
+            queue_capacity: 10_000,
+            retention: RetentionPolicy::default(),
+        }
+    }
+}
+
+impl StorageConfig {
+    /// Loads batching configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `STORAGE_BATCH_SIZE` - Optional: Events per write (default: 100)
+    /// - `STORAGE_FLUSH_INTERVAL_MS` - Optional: Flush interval in ms (default: 1000)
+    /// - `STORAGE_QUEUE_CAPACITY` - Optional: Max queued events (default: 10000)
+    /// - `STORAGE_MAX_AGE_HOURS`, `STORAGE_MAX_SIZE_MB`, `CANDLE_MAX_AGE_HOURS`,
+    ///   `ROLLUP_AFTER_DAYS` - Optional: Retention limits (see [`RetentionPolicy::from_env`])
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+
+        let batch_size: usize = env::var("STORAGE_BATCH_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(defaults.batch_size);
+
+        let flush_interval_ms: u64 = env::var("STORAGE_FLUSH_INTERVAL_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(defaults.flush_interval.as_millis() as u64);
+
+        let queue_capacity: usize = env::var("STORAGE_QUEUE_CAPACITY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(defaults.queue_capacity);
+
+        Self {
+            batch_size,
+            flush_interval: Duration::from_millis(flush_interval_ms),
+            queue_capacity,
+            retention: RetentionPolicy::from_env("STORAGE"),
+        }
+    }
+}
+
+/// An event queued on a [`BatchSink`].
+enum Queued {
+    /// A new event to store
+    Store(SwapEvent),
+    /// A more complete version of a stored event
+    Update(SwapEvent),
+}
+
+/// Channel-backed sink that batches events and hands them to a [`BatchWriter`].
+pub struct BatchSink {
+    /// Writer name for logging
+    name: &'static str,
+    /// Whether the writer applies updates
+    updates: bool,
+    /// Channel sender for queuing events
+    tx: mpsc::Sender<Queued>,
+    /// Handle to the background flush task
+    _task_handle: tokio::task::JoinHandle<()>,
+}
+
+impl BatchSink {
+    /// Spawns the background flush task for the given writer.
+    ///
+    /// # Arguments
+    ///
+    /// * `writer` - Backing store writer
+    /// * `config` - Batching configuration
+    /// * `candles` - Optional candle builder fed with every stored swap
+    pub fn spawn<W: BatchWriter>(
+        writer: W,
+        config: StorageConfig,
+        candles: Option<Arc<CandleBuilder>>,
+    ) -> Self {
+        let name = writer.name();
+        let updates = writer.supports_updates();
+        let (tx, rx) = mpsc::channel::<Queued>(config.queue_capacity);
+        let task_handle = tokio::spawn(Self::flush_task(rx, writer, config, candles));
+
+        Self {
+            name,
+            updates,
+            tx,
+            _task_handle: task_handle,
+        }
+    }
+
+    /// Background task that collects events and flushes them by size or interval.
+    async fn flush_task<W: BatchWriter>(
+        mut rx: mpsc::Receiver<Queued>,
+        mut writer: W,
+        config: StorageConfig,
+        candles: Option<Arc<CandleBuilder>>,
+    ) {
+        let mut batch: Vec<SwapEvent> = Vec::with_capacity(config.batch_size);
+        let mut updates: Vec<SwapEvent> = Vec::new();
+        let mut ticker = tokio::time::interval(config.flush_interval);
+        let mut retention = config
+            .retention
+            .is_enabled()
+            .then(|| tokio::time::interval(config.retention.interval));
+
+        loop {
+            tokio::select! {
+                received = rx.recv() => match received {
+                    Some(queued) => {
+                        match queued {
+                            Queued::Store(event) => batch.push(event),
+                            Queued::Update(event) => updates.push(event),
+                        }
+                        if batch.len() + updates.len() >= config.batch_size {
+                            Self::flush(&mut writer, &mut batch, candles.as_deref()).await;
+                            Self::update(&mut writer, &mut updates).await;
+                        }
+                    }
+                    None => {
+                        Self::flush(&mut writer, &mut batch, candles.as_deref()).await;
+                        Self::update(&mut writer, &mut updates).await;
+                        break;
+                    }
+                },
+                _ = ticker.tick() => {
+                    Self::flush(&mut writer, &mut batch, candles.as_deref()).await;
+                    Self::update(&mut writer, &mut updates).await;
+                    if let Some(ref builder) = candles {
+                        let expired = builder.close_expired(unix_now());
+                        Self::store_candles(&mut writer, &expired).await;
+                    }
+                }
+                _ = retention_tick(&mut retention) => {
+                    Self::prune(&mut writer, &config.retention).await;
+                }
+            }
+        }
+
+        log::info!("{} storage task shutting down", writer.name());
+    }
+
+    /// Writes the pending batch, feeds it to the candle builder, and clears it.
+    ///
+    /// Candles are only updated from batches that were written, so they never
+    /// include swaps missing from the store.
+    async fn flush<W: BatchWriter>(
+        writer: &mut W,
+        batch: &mut Vec<SwapEvent>,
+        candles: Option<&CandleBuilder>,
+    ) {
+        if batch.is_empty() {
+            return;
+        }
+        match writer.write_batch(batch).await {
+            Ok(()) => {
+                log::debug!("{}: stored {} events", writer.name(), batch.len());
+                if let Some(builder) = candles {
+                    let closed: Vec<Candle> = batch
+                        .iter()
+                        .filter_map(|event| {
+                            PriceTick::from_event(event)
+                                .map(|tick| builder.update(&event.pool, &tick))
+                        })
+                        .flatten()
+                        .collect();
+                    Self::store_candles(writer, &closed).await;
+                }
+            }
+            Err(e) => log::error!(
+                "{}: dropped batch of {} events: {e}",
+                writer.name(),
+                batch.len()
+            ),
+        }
+        batch.clear();
+    }
+
+    /// Writes the pending updates, after the batch holding their events, and
+    /// clears them. Updates never feed the candle builder.
+    async fn update<W: BatchWriter>(writer: &mut W, updates: &mut Vec<SwapEvent>) {
+        if updates.is_empty() {
+            return;
+        }
+        match writer.update_batch(updates).await {
+            Ok(()) => log::debug!("{}: updated {} events", writer.name(), updates.len()),
+            Err(e) => log::error!(
+                "{}: dropped {} event updates: {e}",
+                writer.name(),
+                updates.len()
+            ),
+        }
+        updates.clear();
+    }
+
+    /// Applies the retention policy, logging what was removed.
+    async fn prune<W: BatchWriter>(writer: &mut W, policy: &RetentionPolicy) {
+        match writer.prune(policy, unix_now()).await {
+            Ok(stats) if stats.is_empty() => {}
+            Ok(stats) => log::info!(
+                "{}: pruned {} events, {} candles, {} files, rolled up {} candles ({policy})",
+                writer.name(),
+                stats.events,
+                stats.candles,
+                stats.files,
+                stats.rolled_up
+            ),
+            Err(e) => log::error!("{}: retention failed: {e}", writer.name()),
+        }
+    }
+
+    /// Writes closed candles, logging failures.
+    async fn store_candles<W: BatchWriter>(writer: &mut W, candles: &[Candle]) {
+        if candles.is_empty() {
+            return;
+        }
+        match writer.write_candles(candles).await {
+            Ok(()) => log::debug!("{}: stored {} candles", writer.name(), candles.len()),
+            Err(e) => log::error!("{}: dropped {} candles: {e}", writer.name(), candles.len()),
+        }
+    }
+}
+
+/// Waits for the next retention run, or forever if retention is disabled.
+async fn retention_tick(ticker: &mut Option<tokio::time::Interval>) {
+    match ticker {
+        Some(ticker) => {
+            ticker.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+impl StorageSink for BatchSink {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn try_store(&self, event: SwapEvent) -> Result<(), StorageError> {
+        self.queue(Queued::Store(event))
+    }
+
+    fn try_update(&self, event: SwapEvent) -> Result<(), StorageError> {
+        if !self.updates {
+            return Ok(());
+        }
+        self.queue(Queued::Update(event))
+    }
+}
+
+impl BatchSink {
+    fn queue(&self, queued: Queued) -> Result<(), StorageError> {
+        self.tx.try_send(queued).map_err(|e| match e {
+            mpsc::error::TrySendError::Full(_) => StorageError::QueueFull,
+            mpsc::error::TrySendError::Closed(_) => StorageError::Closed,
+        })
+    }
+}
+
+/// Sink that queues each event on several sinks (e.g., a database and the archive).
+pub struct FanoutSink {
+    /// Combined name for logging (e.g., "postgres+archive")
+    name: String,
+    /// Target sinks
+    sinks: Vec<BatchSink>,
+}
+
+impl FanoutSink {
+    /// Creates a sink sending events to all of `sinks`.
+    pub fn new(sinks: Vec<BatchSink>) -> Self {
+        let name = sinks
+            .iter()
+            .map(|sink| sink.name)
+            .collect::<Vec<_>>()
+            .join("+");
+        Self { name, sinks }
+    }
+}
+
+impl StorageSink for FanoutSink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Queues the event on every sink; a failing sink does not block the others.
+    fn try_store(&self, event: SwapEvent) -> Result<(), StorageError> {
+        let mut result = Ok(());
+        for sink in &self.sinks {
+            if let Err(e) = sink.try_store(event.clone()) {
+                result = Err(e);
+            }
+        }
+        result
+    }
+
+    /// Queues the update on every sink that applies updates.
+    fn try_update(&self, event: SwapEvent) -> Result<(), StorageError> {
+        let mut result = Ok(());
+        for sink in self.sinks.iter().filter(|sink| sink.updates) {
+            if let Err(e) = sink.try_update(event.clone()) {
+                result = Err(e);
+            }
+        }
+        result
+    }
+}
+
+/// Configured storage: the event sink and read access to persisted events and candles.
+pub struct Storage {
+    /// Sink persisting events (and candles, if a database and builder are set)
+    pub sink: Arc<dyn StorageSink>,
+    /// Persisted candles; `None` when only the archive is configured
+    pub candles: Option<Arc<dyn CandleStore>>,
+    /// Persisted events; `None` when only the archive is configured
+    pub events: Option<Arc<dyn EventStore>>,
+    /// Age after which the database keeps only hourly and daily candles
+    pub rollup_after: Option<Duration>,
+}
+
+/// Creates the configured storage from environment variables.
+///
+/// # Arguments
+///
+/// * `candles` - Optional candle builder fed with every swap stored in the database
+/// * `memory_candles` - Closed candles kept in memory per pool and interval when
+///   no database is configured; `None` builds no candles without a database
+/// * `migrations` - Whether pending schema migrations of the database are applied
+///
+/// # Environment Variables
+///
+/// - `POSTGRES_URL` - Optional: PostgreSQL connection URL enabling [`PostgresSink`]
+/// - `SQLITE_PATH` - Optional: Database file path enabling [`SqliteSink`]
+/// - `ARCHIVE_DIR` - Optional: Directory enabling [`ArchiveSink`] (see [`ArchiveConfig`])
+/// - `ARCHIVE_MAX_AGE_HOURS`, `ARCHIVE_MAX_SIZE_MB` - Optional: Archive retention limits
+/// - `EXPORT_DIR` - Optional: Directory enabling [`ExportSink`] (see [`ExportConfig`])
+/// - `NATS_URL` - Optional: Server URL enabling [`NatsSink`] (see [`NatsConfig`])
+/// - `AMQP_URL` - Optional: Broker URL enabling [`AmqpSink`] (see [`AmqpConfig`])
+/// - `MQTT_HOST` - Optional: Broker host enabling [`MqttSink`] (see [`MqttConfig`])
+/// - `INFLUX_URL` - Optional: Server URL enabling [`InfluxSink`] (see [`InfluxConfig`])
+/// - `ROLLUP_AFTER_DAYS` - Optional: Days of full-resolution database history
+///   before swaps are rolled up into hourly and daily candles
+///
+/// If both databases are set, PostgreSQL takes precedence. The archive, export
+/// files, NATS, AMQP, and MQTT publishers, and InfluxDB writer run alongside
+/// the database, each with its own batching. Without a database,
+/// `memory_candles` enables a [`MemoryCandles`] sink that only builds candles.
+///
+/// # Returns
+///
+/// `Ok(None)` if no storage is configured.
+pub async fn from_env(
+    candles: Option<Arc<CandleBuilder>>,
+    memory_candles: Option<usize>,
+    migrations: MigrationMode,
+) -> Result<Option<Storage>, StorageError> {
+    let config = StorageConfig::from_env();
+    let rollup_after = config.retention.rollup_after;
+    let postgres_url = env::var("POSTGRES_URL")
+        .ok()
+        .filter(|v| !v.trim().is_empty());
+    let sqlite_path = env::var("SQLITE_PATH")
+        .ok()
+        .filter(|v| !v.trim().is_empty());
+
+    let mut sinks = Vec::new();
+    let mut candle_store: Option<Arc<dyn CandleStore>> = None;
+    let mut event_store: Option<Arc<dyn EventStore>> = None;
+
+    if let Some(url) = postgres_url {
+        if sqlite_path.is_some() {
+            log::warn!("Both POSTGRES_URL and SQLITE_PATH are set; using PostgreSQL");
+        }
+        let writer = PostgresSink::connect(&url, migrations).await?;
+        candle_store = Some(Arc::new(writer.clone()));
+        event_store = Some(Arc::new(writer.clone()));
+        sinks.push(BatchSink::spawn(writer, config.clone(), candles));
+    } else if let Some(path) = sqlite_path {
+        let writer = SqliteSink::connect(&path, migrations).await?;
+        candle_store = Some(Arc::new(writer.clone()));
+        event_store = Some(Arc::new(writer.clone()));
+        sinks.push(BatchSink::spawn(writer, config.clone(), candles));
+    } else if let (Some(builder), Some(limit)) = (candles, memory_candles) {
+        let writer = MemoryCandles::new(limit);
+        candle_store = Some(Arc::new(writer.clone()));
+        sinks.push(BatchSink::spawn(writer, config.clone(), Some(builder)));
+    }
+
+    if let Some(archive) = ArchiveConfig::from_env() {
+        let archive_config = StorageConfig {
+            batch_size: archive.batch_size,
+            flush_interval: archive.flush_interval,
+            retention: RetentionPolicy::from_env("ARCHIVE"),
+            ..config
+        };
+        let writer = ArchiveSink::open(archive).await?;
+        sinks.push(BatchSink::spawn(writer, archive_config, None));
+    }
+
+    if let Some(export) = ExportConfig::from_env() {
+        let export_config = StorageConfig {
+            batch_size: export.batch_size,
+            flush_interval: export.flush_interval,
+            retention: RetentionPolicy::default(),
+            ..config
+        };
+        let writer = ExportSink::open(export).await?;
+        sinks.push(BatchSink::spawn(writer, export_config, None));
+    }
+
+    if let Some(nats) = NatsConfig::from_env() {
+        let nats_config = StorageConfig {
+            batch_size: nats.batch_size,
+            flush_interval: nats.flush_interval,
+            retention: RetentionPolicy::default(),
+            ..config
+        };
+        let writer = NatsSink::connect(&nats).await?;
+        sinks.push(BatchSink::spawn(writer, nats_config, None));
+    }
+
+    if let Some(amqp) = AmqpConfig::from_env() {
+        let amqp_config = StorageConfig {
+            batch_size: amqp.batch_size,
+            flush_interval: amqp.flush_interval,
+            retention: RetentionPolicy::default(),
+            ..config
+        };
+        let writer = AmqpSink::connect(amqp).await?;
+        sinks.push(BatchSink::spawn(writer, amqp_config, None));
+    }
+
+    if let Some(mqtt) = MqttConfig::from_env() {
+        let mqtt_config = StorageConfig {
+            batch_size: mqtt.batch_size,
+            flush_interval: mqtt.flush_interval,
+            retention: RetentionPolicy::default(),
+            ..config
+        };
+        sinks.push(BatchSink::spawn(MqttSink::connect(mqtt), mqtt_config, None));
+    }
+
+    if let Some(influx) = InfluxConfig::from_env() {
+        let influx_config = StorageConfig {
+            batch_size: influx.batch_size,
+            flush_interval: influx.flush_interval,
+            retention: RetentionPolicy::default(),
+            ..config
+        };
+        let writer = InfluxSink::connect(influx)?;
+        sinks.push(BatchSink::spawn(writer, influx_config, None));
+    }
+
+    let sink: Arc<dyn StorageSink> = match sinks.len() {
+        0 => return Ok(None),
+        1 => Arc::new(sinks.remove(0)),
+        _ => Arc::new(FanoutSink::new(sinks)),
+    };
+    Ok(Some(Storage {
+        sink,
+        candles: candle_store,
+        rollup_after: event_store.as_ref().and(rollup_after),
+        events: event_store,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::Protocol,
+        std::sync::{Arc, Mutex},
+    };
+
+    /// Writer that records batch sizes for assertions.
+    struct RecordingWriter {
+        batches: Arc<Mutex<Vec<usize>>>,
+    }
+
+    #[async_trait]
+    impl BatchWriter for RecordingWriter {
+        fn name(&self) -> &'static str {
+            "recording"
+        }
+
+        async fn write_batch(&mut self, events: &[SwapEvent]) -> Result<(), StorageError> {
+            self.batches.lock().unwrap().push(events.len());
+            Ok(())
+        }
+    }
+
+    /// Writer that records written and updated signatures in order.
+    struct UpdatingWriter {
+        writes: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl BatchWriter for UpdatingWriter {
+        fn name(&self) -> &'static str {
+            "updating"
+        }
+
+        async fn write_batch(&mut self, events: &[SwapEvent]) -> Result<(), StorageError> {
+            let mut writes = self.writes.lock().unwrap();
+            writes.extend(events.iter().map(|e| format!("store {}", e.signature)));
+            Ok(())
+        }
+
+        fn supports_updates(&self) -> bool {
+            true
+        }
+
+        async fn update_batch(&mut self, events: &[SwapEvent]) -> Result<(), StorageError> {
+            let mut writes = self.writes.lock().unwrap();
+            writes.extend(events.iter().map(|e| format!("update {}", e.signature)));
+            Ok(())
+        }
+    }
+
+    fn test_event(sig: &str) -> SwapEvent {
+        SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature(sig)
+            .pool("pool")
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_batch_sink_flushes_by_size() {
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let config = StorageConfig {
+            batch_size: 2,
+            flush_interval: Duration::from_secs(3600),
+            queue_capacity: 10,
+            retention: RetentionPolicy::default(),
+        };
+        let sink = BatchSink::spawn(
+            RecordingWriter {
+                batches: batches.clone(),
+            },
+            config,
+            None,
+        );
+
+        for i in 0..4 {
+            sink.try_store(test_event(&format!("sig{i}"))).unwrap();
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(*batches.lock().unwrap(), vec![2, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_batch_sink_flushes_by_interval() {
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let config = StorageConfig {
+            batch_size: 100,
+            flush_interval: Duration::from_millis(20),
+            queue_capacity: 10,
+            retention: RetentionPolicy::default(),
+        };
+        let sink = BatchSink::spawn(
+            RecordingWriter {
+                batches: batches.clone(),
+            },
+            config,
+            None,
+        );
+
+        sink.try_store(test_event("sig")).unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(*batches.lock().unwrap(), vec![1]);
+    }
+
+    #[tokio::test]
+    async fn test_batch_sink_writes_updates_after_events() {
+        let writes = Arc::new(Mutex::new(Vec::new()));
+        let config = StorageConfig {
+            batch_size: 100,
+            flush_interval: Duration::from_millis(20),
+            queue_capacity: 10,
+            retention: RetentionPolicy::default(),
+        };
+        let sink = BatchSink::spawn(
+            UpdatingWriter {
+                writes: writes.clone(),
+            },
+            config.clone(),
+            None,
+        );
+
+        sink.try_store(test_event("a")).unwrap();
+        sink.try_update(test_event("a")).unwrap();
+        sink.try_store(test_event("b")).unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(*writes.lock().unwrap(), ["store a", "store b", "update a"]);
+
+        // Updates are not queued for writers without update support
+        let sink = BatchSink::spawn(
+            RecordingWriter {
+                batches: Arc::new(Mutex::new(Vec::new())),
+            },
+            config,
+            None,
+        );
+        assert!(!sink.updates);
+        sink.try_update(test_event("a")).unwrap();
+    }
+}
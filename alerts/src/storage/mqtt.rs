@@ -0,0 +1,365 @@
+//! MQTT publisher of swap events for lightweight consumers.
+//!
+//! Each event is published to the topic of its pool and of its traded token
+//! (the non-base token of the pair), so a device subscribes to just the
+//! pool or token it shows:
+//!
+//! ```text
+//! raydium/pool/<POOL>               # Events of one pool
+//! raydium/token/<MINT>              # Events trading one token, across pools
+//! raydium/status                    # `online`, or `offline` (last will)
+//! ```
+//!
+//! Payloads are compact JSON by default ([`CompactEvent`]), small enough for
+//! microcontroller buffers; `MQTT_PAYLOAD=full` sends the complete event. The
+//! status topic is retained: `online` is published on every connection, and
+//! the broker publishes the `offline` last will when the connection drops.
+//! The client reconnects on its own; events published while disconnected
+//! are queued up to a limit, then dropped.
+
+use {
+    super::{BatchWriter, StorageError},
+    crate::output::SwapEvent,
+    async_trait::async_trait,
+    rumqttc::{AsyncClient, Event, LastWill, MqttOptions, Packet, QoS},
+    serde::Serialize,
+    std::{env, time::Duration},
+};
+
+/// Requests queued while the client is disconnected.
+const REQUEST_CAPACITY: usize = 10_000;
+
+/// Delay between reconnection attempts.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// Payload published for each event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MqttPayload {
+    /// Compact summary ([`CompactEvent`])
+    #[default]
+    Compact,
+    /// The complete event, as sent to webhooks
+    Full,
+}
+
+/// Configuration for the MQTT publisher.
+#[derive(Debug, Clone)]
+pub struct MqttConfig {
+    /// Broker host
+    pub host: String,
+    /// Broker port
+    pub port: u16,
+    /// Client identifier
+    pub client_id: String,
+    /// Username and password, if the broker requires them
+    pub credentials: Option<(String, String)>,
+    /// First level of every topic
+    pub topic_prefix: String,
+    /// Quality of service of the events and the status messages
+    pub qos: QoS,
+    /// Whether events are retained, so subscribers get the latest at once
+    pub retain: bool,
+    /// Published payload
+    pub payload: MqttPayload,
+    /// Maximum events per publish batch
+    pub batch_size: usize,
+    /// Maximum time an event waits before it is published
+    pub flush_interval: Duration,
+}
+
+impl MqttConfig {
+    /// Creates MQTT configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `MQTT_HOST` - Optional: Broker host enabling the publisher
+    /// - `MQTT_PORT` - Optional: Broker port (default: 1883)
+    /// - `MQTT_CLIENT_ID` - Optional: Client identifier (default: raydium-alerts)
+    /// - `MQTT_USERNAME`, `MQTT_PASSWORD` - Optional: Broker credentials
+    /// - `MQTT_TOPIC_PREFIX` - Optional: First topic level (default: raydium)
+    /// - `MQTT_QOS` - Optional: `0`, `1`, or `2` (default: 1)
+    /// - `MQTT_RETAIN` - Optional: `true` to retain the latest event per topic
+    /// - `MQTT_PAYLOAD` - Optional: `compact` or `full` (default: compact)
+    /// - `MQTT_BATCH_SIZE` - Optional: Events per batch (default: 100)
+    /// - `MQTT_FLUSH_INTERVAL_MS` - Optional: Publish interval in ms (default: 100)
+    ///
+    /// # Returns
+    ///
+    /// `None` if `MQTT_HOST` is not set.
+    pub fn from_env() -> Option<Self> {
+        let host = non_empty("MQTT_HOST")?;
+
+        let port = match env::var("MQTT_PORT") {
+            Ok(value) => value.trim().parse().unwrap_or_else(|_| {
+                log::warn!("Invalid MQTT_PORT '{value}', using 1883");
+                1883
+            }),
+            Err(_) => 1883,
+        };
+
+        let credentials = non_empty("MQTT_USERNAME")
+            .map(|username| (username, env::var("MQTT_PASSWORD").unwrap_or_default()));
+
+        let topic_prefix = non_empty("MQTT_TOPIC_PREFIX")
+            .map(|v| v.trim_matches('/').to_string())
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| "raydium".to_string());
+
+        let qos = match env::var("MQTT_QOS").as_deref().map(str::trim) {
+            Ok("0") => QoS::AtMostOnce,
+            Ok("1") | Err(_) => QoS::AtLeastOnce,
+            Ok("2") => QoS::ExactlyOnce,
+            Ok(value) => {
+                log::warn!("Invalid MQTT_QOS '{value}', expected 0, 1, or 2");
+                QoS::AtLeastOnce
+            }
+        };
+
+        let retain = env::var("MQTT_RETAIN")
+            .is_ok_and(|v| matches!(v.trim().to_lowercase().as_str(), "true" | "1"));
+
+        let payload = match non_empty("MQTT_PAYLOAD")
+            .map(|v| v.to_lowercase())
+            .as_deref()
+        {
+            None | Some("compact") => MqttPayload::Compact,
+            Some("full") => MqttPayload::Full,
+            Some(value) => {
+                log::warn!("Invalid MQTT_PAYLOAD '{value}', expected compact or full");
+                MqttPayload::Compact
+            }
+        };
+
+        let batch_size: usize = env::var("MQTT_BATCH_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(100);
+
+        let flush_interval_ms: u64 = env::var("MQTT_FLUSH_INTERVAL_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(100);
+
+        Some(Self {
+            host,
+            port,
+            client_id: non_empty("MQTT_CLIENT_ID").unwrap_or_else(|| "raydium-alerts".to_string()),
+            credentials,
+            topic_prefix,
+            qos,
+            retain,
+            payload,
+            batch_size,
+            flush_interval: Duration::from_millis(flush_interval_ms),
+        })
+    }
+
+    /// Returns the topic of the connection status and last will.
+    fn status_topic(&self) -> String {
+        format!("{}/status", self.topic_prefix)
+    }
+}
+
+/// Returns a trimmed, non-empty environment variable.
+fn non_empty(name: &str) -> Option<String> {
+    env::var(name)
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Compact summary of an event for small displays.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CompactEvent<'a> {
+    /// Event type, lowercased (e.g., `swap`)
+    #[serde(rename = "type")]
+    pub event_type: String,
+    /// Protocol, lowercased (e.g., `cpmm`)
+    pub protocol: String,
+    /// Pool address
+    pub pool: &'a str,
+    /// Traded token mint (the non-base token of the pair)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mint: Option<&'a str>,
+    /// Traded token symbol
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<&'a str>,
+    /// `buy` or `sell` of the traded token
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub side: Option<String>,
+    /// UI amount of the traded token
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<f64>,
+    /// USD value
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usd: Option<f64>,
+    /// Base token per unit of the traded token
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub price: Option<f64>,
+    /// Unix timestamp (seconds)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ts: Option<i64>,
+}
+
+impl<'a> CompactEvent<'a> {
+    /// Summarizes an event.
+    pub fn new(event: &'a SwapEvent) -> Self {
+        let (_, token) = event.get_base_quote_tokens();
+        Self {
+            event_type: event.event_type.to_string().to_lowercase(),
+            protocol: event.protocol.to_string().to_lowercase(),
+            pool: &event.pool,
+            mint: token.map(|token| token.mint.as_str()),
+            symbol: token.and_then(|token| token.symbol.as_deref()),
+            side: event.side.map(|side| side.to_string()),
+            amount: token.and_then(|token| token.amount),
+            usd: event.usd_value(),
+            price: event.effective_price,
+            ts: event.timestamp,
+        }
+    }
+}
+
+/// Returns a topic level, with the characters MQTT reserves replaced.
+fn level(value: &str) -> String {
+    if value.is_empty() {
+        return "_".to_string();
+    }
+    value
+        .chars()
+        .map(|c| match c {
+            '/' | '+' | '#' => '_',
+            c => c,
+        })
+        .collect()
+}
+
+/// Returns the topics of an event: its pool's, and its traded token's.
+pub fn topics(prefix: &str, event: &SwapEvent) -> Vec<String> {
+    let mut topics = vec![format!("{prefix}/pool/{}", level(&event.pool))];
+    if let (_, Some(token)) = event.get_base_quote_tokens() {
+        topics.push(format!("{prefix}/token/{}", level(&token.mint)));
+    }
+    topics
+}
+
+/// Publishes events to MQTT topics.
+pub struct MqttSink {
+    client: AsyncClient,
+    config: MqttConfig,
+}
+
+impl MqttSink {
+    /// Creates the client and spawns its event loop, which connects in the
+    /// background and reconnects after failures.
+    pub fn connect(config: MqttConfig) -> Self {
+        let status_topic = config.status_topic();
+        let mut options = MqttOptions::new(&config.client_id, &config.host, config.port);
+        options.set_keep_alive(Duration::from_secs(30));
+        options.set_last_will(LastWill::new(&status_topic, "offline", config.qos, true));
+        if let Some((ref username, ref password)) = config.credentials {
+            options.set_credentials(username, password);
+        }
+
+        let (client, mut eventloop) = AsyncClient::new(options, REQUEST_CAPACITY);
+        let status_client = client.clone();
+        let qos = config.qos;
+        tokio::spawn(async move {
+            loop {
+                match eventloop.poll().await {
+                    Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                        log::info!("MQTT publisher connected");
+                        if let Err(e) =
+                            status_client.try_publish(&status_topic, qos, true, "online")
+                        {
+                            log::warn!("Failed to publish MQTT status: {e}");
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        log::warn!("MQTT connection error, reconnecting: {e}");
+                        tokio::time::sleep(RECONNECT_DELAY).await;
+                    }
+                }
+            }
+        });
+
+        log::info!(
+            "MQTT publisher enabled: {}:{} ({}/pool/#, {}/token/#)",
+            config.host,
+            config.port,
+            config.topic_prefix,
+            config.topic_prefix
+        );
+        Self { client, config }
+    }
+}
+
+#[async_trait]
+impl BatchWriter for MqttSink {
+    fn name(&self) -> &'static str {
+        "mqtt"
+    }
+
+    async fn write_batch(&mut self, events: &[SwapEvent]) -> Result<(), StorageError> {
+        for event in events {
+            let payload = match self.config.payload {
+                MqttPayload::Compact => serde_json::to_vec(&CompactEvent::new(event)),
+                MqttPayload::Full => serde_json::to_vec(event),
+            }
+            .map_err(|e| StorageError::Write(e.to_string()))?;
+            for topic in topics(&self.config.topic_prefix, event) {
+                self.client
+                    .try_publish(topic, self.config.qos, self.config.retain, payload.clone())
+                    .map_err(|e| StorageError::Write(e.to_string()))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{swap_event::WSOL_MINT, Protocol, TokenInfo},
+    };
+
+    #[test]
+    fn test_topics_and_compact_payload() {
+        let event = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool/1")
+            .input_token(TokenInfo::new(WSOL_MINT, 1_500_000_000).with_usd_value(187.5))
+            .output_token(
+                TokenInfo::new("mint", 1_250_000)
+                    .with_decimals(6)
+                    .with_symbol("TICK"),
+            )
+            .timestamp(1_792_069_200)
+            .build();
+        assert_eq!(
+            topics("raydium", &event),
+            vec!["raydium/pool/pool_1", "raydium/token/mint"]
+        );
+
+        let payload = serde_json::to_value(CompactEvent::new(&event)).unwrap();
+        assert_eq!(
+            payload,
+            serde_json::json!({
+                "type": "swap",
+                "protocol": "cpmm",
+                "pool": "pool/1",
+                "mint": "mint",
+                "symbol": "TICK",
+                "side": "buy",
+                "amount": 1.25,
+                "usd": 187.5,
+                "ts": 1_792_069_200,
+            })
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//! NATS publisher of swap events, with optional JetStream persistence.
+//!
+//! Each event is published as JSON to a subject built from its type,
+//! protocol, and pool, so internal consumers subscribe to just the slice they
+//! need with NATS wildcards:
+//!
+//! ```text
+//! raydium.swap.cpmm.<pool>          # Swaps of one CPMM pool
+//! raydium.swap.>                    # All swaps
+//! raydium.*.pumpfun.>               # All Pump.fun events
+//! ```
+//!
+//! With `NATS_JETSTREAM_STREAM` set, events are published through JetStream
+//! to a stream capturing `<prefix>.>` (created if missing), and each batch
+//! waits for the server's acknowledgements, so subscribers that were offline
+//! can replay what they missed.
+
+use {
+    super::{BatchWriter, StorageError},
+    crate::output::SwapEvent,
+    async_nats::jetstream,
+    async_trait::async_trait,
+    std::{env, time::Duration},
+};
+
+/// Configuration for the NATS publisher.
+#[derive(Debug, Clone)]
+pub struct NatsConfig {
+    /// Server URL(s), comma-separated
+    pub url: String,
+    /// First token of every subject
+    pub subject_prefix: String,
+    /// JetStream stream the events are persisted in; `None` publishes core NATS
+    pub stream: Option<String>,
+    /// Maximum events per publish batch
+    pub batch_size: usize,
+    /// Maximum time an event waits before it is published
+    pub flush_interval: Duration,
+}
+
+impl NatsConfig {
+    /// Creates NATS configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `NATS_URL` - Optional: Server URL(s) enabling the publisher
+    /// - `NATS_SUBJECT_PREFIX` - Optional: First subject token (default: raydium)
+    /// - `NATS_JETSTREAM_STREAM` - Optional: JetStream stream persisting the events
+    ///   (default: core NATS, no persistence)
+    /// - `NATS_BATCH_SIZE` - Optional: Events per batch (default: 100)
+    /// - `NATS_FLUSH_INTERVAL_MS` - Optional: Publish interval in ms (default: 100)
+    ///
+    /// # Returns
+    ///
+    /// `None` if `NATS_URL` is not set.
+    pub fn from_env() -> Option<Self> {
+        let url = env::var("NATS_URL").ok().filter(|v| !v.trim().is_empty())?;
+
+        let subject_prefix = env::var("NATS_SUBJECT_PREFIX")
+            .ok()
+            .map(|v| v.trim().trim_matches('.').to_string())
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| "raydium".to_string());
+
+        let stream = env::var("NATS_JETSTREAM_STREAM")
+            .ok()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty());
+
+        let batch_size: usize = env::var("NATS_BATCH_SIZE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(100);
+
+        let flush_interval_ms: u64 = env::var("NATS_FLUSH_INTERVAL_MS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(100);
+
+        Some(Self {
+            url,
+            subject_prefix,
+            stream,
+            batch_size,
+            flush_interval: Duration::from_millis(flush_interval_ms),
+        })
+    }
+}
+
+/// Returns a subject token, with the characters NATS reserves replaced.
+fn token(value: &str) -> String {
+    if value.is_empty() {
+        return "_".to_string();
+    }
+    value
+        .chars()
+        .map(|c| match c {
+            '.' | '*' | '>' => '_',
+            c if c.is_whitespace() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+/// Returns the subject of an event: `<prefix>.<event_type>.<protocol>.<pool>`.
+pub fn subject(prefix: &str, event: &SwapEvent) -> String {
+    format!(
+        "{prefix}.{}.{}.{}",
+        token(&event.event_type.to_string().to_lowercase()),
+        token(&event.protocol.to_string().to_lowercase()),
+        token(&event.pool)
+    )
+}
+
+/// Publishes events to NATS subjects.
+pub struct NatsSink {
+    client: async_nats::Client,
+    jetstream: Option<jetstream::Context>,
+    subject_prefix: String,
+}
+
+impl NatsSink {
+    /// Connects to the server and, with JetStream, creates the stream if missing.
+    pub async fn connect(config: &NatsConfig) -> Result<Self, StorageError> {
+        let client = async_nats::ConnectOptions::new()
+            .name("raydium-alerts")
+            .connect(config.url.as_str())
+            .await
+            .map_err(|e| StorageError::Connection(e.to_string()))?;
+
+        let jetstream = match config.stream {
+            Some(ref name) => {
+                let context = jetstream::new(client.clone());
+                context
+                    .get_or_create_stream(jetstream::stream::Config {
+                        name: name.clone(),
+                        subjects: vec![format!("{}.>", config.subject_prefix)],
+                        ..Default::default()
+                    })
+                    .await
+                    .map_err(|e| StorageError::Connection(e.to_string()))?;
+                log::info!(
+                    "NATS publisher connected, persisting {}.> in JetStream stream {name}",
+                    config.subject_prefix
+                );
+                Some(context)
+            }
+            None => {
+                log::info!(
+                    "NATS publisher connected, publishing {}.>",
+                    config.subject_prefix
+                );
+                None
+            }
+        };
+
+        Ok(Self {
+            client,
+            jetstream,
+            subject_prefix: config.subject_prefix.clone(),
+        })
+    }
+}
+
+#[async_trait]
+impl BatchWriter for NatsSink {
+    fn name(&self) -> &'static str {
+        "nats"
+    }
+
+    async fn write_batch(&mut self, events: &[SwapEvent]) -> Result<(), StorageError> {
+        let mut acks = Vec::new();
+        for event in events {
+            let subject = subject(&self.subject_prefix, event);
+            let payload = serde_json::to_vec(event)
+                .map_err(|e| StorageError::Write(e.to_string()))?
+                .into();
+            match self.jetstream {
+                Some(ref context) => acks.push(
+                    context
+                        .publish(subject, payload)
+                        .await
+                        .map_err(|e| StorageError::Write(e.to_string()))?,
+                ),
+                None => self
+                    .client
+                    .publish(subject, payload)
+                    .await
+                    .map_err(|e| StorageError::Write(e.to_string()))?,
+            }
+        }
+
+        if self.jetstream.is_some() {
+            for ack in acks {
+                ack.await.map_err(|e| StorageError::Write(e.to_string()))?;
+            }
+        } else {
+            self.client
+                .flush()
+                .await
+                .map_err(|e| StorageError::Write(e.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{EventType, Protocol},
+    };
+
+    #[test]
+    fn test_subject_tokens_are_sanitized() {
+        let event = SwapEvent::builder()
+            .protocol(Protocol::AmmV4)
+            .signature("sig")
+            .pool("Pool1")
+            .build();
+        assert_eq!(subject("raydium", &event), "raydium.swap.amm-v4.Pool1");
+
+        let mut event = event;
+        event.event_type = EventType::AddLiquidity;
+        event.pool = "a.b *>".to_string();
+        assert_eq!(subject("alerts", &event), "alerts.add_lp.amm-v4.a_b___");
+
+        event.pool.clear();
+        assert_eq!(subject("raydium", &event), "raydium.add_lp.amm-v4._");
+    }
+}
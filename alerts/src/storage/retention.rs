@@ -0,0 +1,197 @@
+//! Retention policies for storage sinks.
+//!
+//! A [`RetentionPolicy`] bounds how much history a sink keeps, by age and by
+//! size. The sink's flush task applies it periodically through
+//! [`BatchWriter::prune`](super::BatchWriter::prune), so pruning never runs
+//! concurrently with writes to the same store.
+//!
+//! Databases can also keep two tiers: full-resolution swaps and sub-hour
+//! candles for `ROLLUP_AFTER_DAYS`, and hourly and daily candles beyond that.
+//! Swaps past the boundary are rolled up into the hourly and daily candles
+//! missing from the store before they are deleted.
+
+use {
+    crate::candles::rollup_cutoff,
+    std::{env, fmt, time::Duration},
+};
+
+/// Default interval between retention runs.
+const DEFAULT_INTERVAL_SECS: u64 = 3_600;
+
+/// How much history a store keeps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    /// Events stored longer ago than this are deleted
+    pub max_age: Option<Duration>,
+    /// Oldest events are deleted while the store is larger than this (bytes)
+    pub max_size: Option<u64>,
+    /// Candles opened longer ago than this are deleted
+    pub candle_max_age: Option<Duration>,
+    /// Swaps and sub-hour candles older than this are rolled up into hourly
+    /// and daily candles and deleted
+    pub rollup_after: Option<Duration>,
+    /// Time between retention runs
+    pub interval: Duration,
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            max_age: None,
+            max_size: None,
+            candle_max_age: None,
+            rollup_after: None,
+            interval: Duration::from_secs(DEFAULT_INTERVAL_SECS),
+        }
+    }
+}
+
+impl RetentionPolicy {
+    /// Loads a retention policy from environment variables.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - Variable prefix for the store (e.g., `STORAGE`, `ARCHIVE`)
+    ///
+    /// # Environment Variables
+    ///
+    /// - `<PREFIX>_MAX_AGE_HOURS` - Optional: Delete events older than this
+    /// - `<PREFIX>_MAX_SIZE_MB` - Optional: Delete the oldest events above this size
+    /// - `CANDLE_MAX_AGE_HOURS` - Optional: Delete candles older than this
+    /// - `ROLLUP_AFTER_DAYS` - Optional: Roll swaps older than this up into
+    ///   hourly and daily candles, deleting them and sub-hour candles (databases only)
+    /// - `RETENTION_INTERVAL_SECS` - Optional: Time between runs (default: 3600)
+    ///
+    /// Unset or zero limits keep history forever.
+    pub fn from_env(prefix: &str) -> Self {
+        let positive = |name: String| -> Option<u64> {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .filter(|&n| n > 0)
+        };
+        let hours = |name: String| positive(name).map(|h| Duration::from_secs(h * 3_600));
+
+        Self {
+            max_age: hours(format!("{prefix}_MAX_AGE_HOURS")),
+            max_size: positive(format!("{prefix}_MAX_SIZE_MB")).map(|mb| mb * 1024 * 1024),
+            candle_max_age: hours("CANDLE_MAX_AGE_HOURS".to_string()),
+            rollup_after: positive("ROLLUP_AFTER_DAYS".to_string())
+                .map(|d| Duration::from_secs(d * 86_400)),
+            interval: Duration::from_secs(
+                positive("RETENTION_INTERVAL_SECS".to_string()).unwrap_or(DEFAULT_INTERVAL_SECS),
+            ),
+        }
+    }
+
+    /// Returns `true` if any limit is configured.
+    pub fn is_enabled(&self) -> bool {
+        self.max_age.is_some()
+            || self.max_size.is_some()
+            || self.candle_max_age.is_some()
+            || self.rollup_after.is_some()
+    }
+
+    /// Returns the Unix time before which events are expired, if an age limit is set.
+    pub fn event_cutoff(&self, now: i64) -> Option<i64> {
+        self.max_age.map(|age| now - age.as_secs() as i64)
+    }
+
+    /// Returns the Unix time before which candles are expired, if an age limit is set.
+    pub fn candle_cutoff(&self, now: i64) -> Option<i64> {
+        self.candle_max_age.map(|age| now - age.as_secs() as i64)
+    }
+
+    /// Returns the day-aligned Unix time before which swaps are rolled up, if
+    /// rollup is enabled.
+    pub fn rollup_cutoff(&self, now: i64) -> Option<i64> {
+        self.rollup_after.map(|after| rollup_cutoff(after, now))
+    }
+}
+
+impl fmt::Display for RetentionPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.is_enabled() {
+            return write!(f, "keep forever");
+        }
+        let mut limits = Vec::new();
+        if let Some(age) = self.max_age {
+            limits.push(format!("events {}h", age.as_secs() / 3_600));
+        }
+        if let Some(size) = self.max_size {
+            limits.push(format!("{} MB", size / (1024 * 1024)));
+        }
+        if let Some(age) = self.candle_max_age {
+            limits.push(format!("candles {}h", age.as_secs() / 3_600));
+        }
+        if let Some(after) = self.rollup_after {
+            limits.push(format!("rollup after {}d", after.as_secs() / 86_400));
+        }
+        write!(f, "{}", limits.join(", "))
+    }
+}
+
+/// What a retention run removed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PruneStats {
+    /// Events deleted
+    pub events: u64,
+    /// Candles deleted
+    pub candles: u64,
+    /// Files deleted (archive segments)
+    pub files: u64,
+    /// Hourly and daily candles created from rolled-up swaps
+    pub rolled_up: u64,
+}
+
+impl PruneStats {
+    /// Returns `true` if nothing was removed.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Returns the number of oldest rows to delete so `used` bytes drop to `max`,
+/// assuming rows are of similar size.
+pub fn excess_rows(rows: u64, used: u64, max: u64) -> u64 {
+    if used <= max || used == 0 {
+        return 0;
+    }
+    let excess = (rows as u128 * (used - max) as u128).div_ceil(used as u128);
+    excess as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_cutoffs_and_display() {
+        let policy = RetentionPolicy {
+            max_age: Some(Duration::from_secs(2 * 3_600)),
+            max_size: Some(512 * 1024 * 1024),
+            ..RetentionPolicy::default()
+        };
+        assert!(policy.is_enabled());
+        assert_eq!(policy.event_cutoff(10_000), Some(10_000 - 7_200));
+        assert_eq!(policy.candle_cutoff(10_000), None);
+        assert_eq!(policy.to_string(), "events 2h, 512 MB");
+        assert_eq!(RetentionPolicy::default().to_string(), "keep forever");
+
+        let policy = RetentionPolicy {
+            rollup_after: Some(Duration::from_secs(2 * 86_400)),
+            ..RetentionPolicy::default()
+        };
+        assert!(policy.is_enabled());
+        assert_eq!(policy.rollup_cutoff(3 * 86_400 + 10), Some(86_400));
+        assert_eq!(policy.to_string(), "rollup after 2d");
+    }
+
+    #[test]
+    fn test_excess_rows() {
+        assert_eq!(excess_rows(1_000, 100, 200), 0);
+        assert_eq!(excess_rows(1_000, 200, 100), 500);
+        assert_eq!(excess_rows(3, 300, 250), 1);
+        assert_eq!(excess_rows(0, 300, 100), 0);
+    }
+}
@@ -0,0 +1,843 @@
+//! SQLite storage sink.
+//!
+//! Lightweight alternative to [`super::PostgresSink`] for laptops and small VPS
+//! deployments. Events are written to a local database file in WAL mode so
+//! readers can query history while the sink is writing.
+//!
+//! The schema is versioned with `PRAGMA user_version`; a new database is
+//! created at the latest version, and pending migrations of an existing one
+//! are applied on connect with [`MigrationMode::Apply`] (see
+//! [`super::migrations`]).
+//!
+//! Every stored event gets an increasing `seq` (the table's `AUTOINCREMENT`
+//! key), which [`EventStore`] backfill is keyed by.
+
+use {
+    super::{
+        migrations::{check_version, MigrationMode},
+        retention::{excess_rows, PruneStats, RetentionPolicy},
+        BatchWriter, EventStore, StorageError, StoredEvent,
+    },
+    crate::{
+        candles::{Candle, CandleStore, Interval, Rollup},
+        output::{EventType, SwapEvent},
+    },
+    async_trait::async_trait,
+    sqlx::{
+        sqlite::{
+            SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions, SqliteRow,
+            SqliteSynchronous,
+        },
+        QueryBuilder, Row, Sqlite, SqlitePool,
+    },
+    std::str::FromStr,
+};
+
+/// Schema migrations, applied in order. The index + 1 is the schema version.
+const MIGRATIONS: &[&str] = &[
+    "CREATE TABLE IF NOT EXISTS swaps (
+        signature TEXT NOT NULL,
+        instruction_index INTEGER NOT NULL,
+        inner_path TEXT NOT NULL DEFAULT '',
+        slot INTEGER NOT NULL,
+        block_time INTEGER,
+        event_type TEXT NOT NULL,
+        protocol TEXT NOT NULL,
+        pool TEXT NOT NULL,
+        input_mint TEXT,
+        input_amount NUMERIC,
+        output_mint TEXT,
+        output_amount NUMERIC,
+        direction TEXT NOT NULL,
+        fee NUMERIC,
+        maker TEXT,
+        payload TEXT NOT NULL,
+        inserted_at INTEGER NOT NULL DEFAULT (unixepoch()),
+        PRIMARY KEY (signature, instruction_index, inner_path)
+    );
+    CREATE INDEX IF NOT EXISTS swaps_pool_slot_idx ON swaps (pool, slot);
+    CREATE INDEX IF NOT EXISTS swaps_slot_idx ON swaps (slot);
+    CREATE INDEX IF NOT EXISTS swaps_maker_idx ON swaps (maker);",
+    "CREATE TABLE IF NOT EXISTS candles (
+        pool TEXT NOT NULL,
+        timeframe TEXT NOT NULL,
+        open_time INTEGER NOT NULL,
+        open REAL NOT NULL,
+        high REAL NOT NULL,
+        low REAL NOT NULL,
+        close REAL NOT NULL,
+        volume REAL NOT NULL,
+        volume_usd REAL,
+        trades INTEGER NOT NULL,
+        PRIMARY KEY (pool, timeframe, open_time)
+    );",
+    "CREATE INDEX IF NOT EXISTS swaps_inserted_at_idx ON swaps (inserted_at);",
+    // Number events in insertion order for backfill; AUTOINCREMENT never
+    // reuses the sequence of deleted rows
+    "CREATE TABLE swaps_seq (
+        seq INTEGER PRIMARY KEY AUTOINCREMENT,
+        signature TEXT NOT NULL,
+        instruction_index INTEGER NOT NULL,
+        inner_path TEXT NOT NULL DEFAULT '',
+        slot INTEGER NOT NULL,
+        block_time INTEGER,
+        event_type TEXT NOT NULL,
+        protocol TEXT NOT NULL,
+        pool TEXT NOT NULL,
+        input_mint TEXT,
+        input_amount NUMERIC,
+        output_mint TEXT,
+        output_amount NUMERIC,
+        direction TEXT NOT NULL,
+        fee NUMERIC,
+        maker TEXT,
+        payload TEXT NOT NULL,
+        inserted_at INTEGER NOT NULL DEFAULT (unixepoch()),
+        UNIQUE (signature, instruction_index, inner_path)
+    );
+    INSERT INTO swaps_seq (signature, instruction_index, inner_path, slot, block_time,
+        event_type, protocol, pool, input_mint, input_amount, output_mint, output_amount,
+        direction, fee, maker, payload, inserted_at)
+    SELECT signature, instruction_index, inner_path, slot, block_time, event_type, protocol,
+        pool, input_mint, input_amount, output_mint, output_amount, direction, fee, maker,
+        payload, inserted_at
+    FROM swaps ORDER BY rowid;
+    DROP TABLE swaps;
+    ALTER TABLE swaps_seq RENAME TO swaps;
+    CREATE INDEX swaps_pool_slot_idx ON swaps (pool, slot);
+    CREATE INDEX swaps_slot_idx ON swaps (slot);
+    CREATE INDEX swaps_maker_idx ON swaps (maker);
+    CREATE INDEX swaps_inserted_at_idx ON swaps (inserted_at);",
+];
+
+/// Maximum bind parameters per statement in SQLite (since 3.32).
+const MAX_BIND_PARAMS: usize = 32_766;
+
+/// Columns bound per inserted row.
+const PARAMS_PER_ROW: usize = 16;
+
+/// Columns bound per inserted candle.
+const PARAMS_PER_CANDLE: usize = 10;
+
+/// Swaps read per query when rolling up.
+const ROLLUP_PAGE_SIZE: i64 = 5_000;
+
+/// Merges a candle into one stored earlier for the same interval.
+const MERGE_CANDLE: &str = " ON CONFLICT (pool, timeframe, open_time) DO UPDATE SET \
+    high = MAX(high, excluded.high), \
+    low = MIN(low, excluded.low), \
+    close = excluded.close, \
+    volume = volume + excluded.volume, \
+    volume_usd = CASE WHEN volume_usd IS NULL AND excluded.volume_usd IS NULL \
+        THEN NULL ELSE IFNULL(volume_usd, 0) + IFNULL(excluded.volume_usd, 0) END, \
+    trades = trades + excluded.trades";
+
+/// Keeps a candle stored earlier for the same interval.
+const KEEP_CANDLE: &str = " ON CONFLICT (pool, timeframe, open_time) DO NOTHING";
+
+/// Batch writer persisting swap events to a local SQLite database.
+///
+/// Clones share the connection pool, so clones can serve candle and event
+/// reads while the original is owned by the sink's flush task.
+#[derive(Clone)]
+pub struct SqliteSink {
+    pool: SqlitePool,
+}
+
+impl SqliteSink {
+    /// Opens (or creates) the database file and checks its schema version.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Database file path (e.g., `./raydium.db`)
+    /// * `mode` - Whether pending migrations of an existing database are applied
+    pub async fn connect(path: &str, mode: MigrationMode) -> Result<Self, StorageError> {
+        let options = SqliteConnectOptions::from_str(path)
+            .map_err(|e| StorageError::Connection(e.to_string()))?
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .synchronous(SqliteSynchronous::Normal);
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .map_err(|e| StorageError::Connection(e.to_string()))?;
+
+        migrate(&pool, mode).await?;
+
+        log::info!("SQLite storage enabled: {path}");
+        Ok(Self { pool })
+    }
+
+    /// Inserts candles, resolving conflicts with stored candles by `on_conflict`.
+    ///
+    /// Returns the number of rows inserted or updated.
+    async fn insert_candles(
+        &self,
+        candles: &[Candle],
+        on_conflict: &str,
+    ) -> Result<u64, StorageError> {
+        let mut affected = 0;
+        for chunk in candles.chunks(MAX_BIND_PARAMS / PARAMS_PER_CANDLE) {
+            let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new(
+                "INSERT INTO candles (pool, timeframe, open_time, open, high, low, close, \
+                 volume, volume_usd, trades) ",
+            );
+            builder.push_values(chunk, |mut row, candle| {
+                row.push_bind(candle.pool.clone())
+                    .push_bind(candle.interval.to_string())
+                    .push_bind(candle.open_time)
+                    .push_bind(candle.open)
+                    .push_bind(candle.high)
+                    .push_bind(candle.low)
+                    .push_bind(candle.close)
+                    .push_bind(candle.volume)
+                    .push_bind(candle.volume_usd)
+                    .push_bind(candle.trades as i64);
+            });
+            builder.push(on_conflict);
+
+            affected += builder
+                .build()
+                .execute(&self.pool)
+                .await
+                .map_err(|e| StorageError::Write(e.to_string()))?
+                .rows_affected();
+        }
+
+        Ok(affected)
+    }
+
+    /// Rolls swaps before `cutoff` up into the hourly and daily candles
+    /// missing from the store, returning the number of candles created.
+    ///
+    /// Swaps are timed by their block time, or their insertion time without one.
+    async fn roll_up(&self, cutoff: i64) -> Result<u64, StorageError> {
+        let read_err = |e: sqlx::Error| StorageError::Read(e.to_string());
+        let mut rollup = Rollup::default();
+        let mut after_seq = 0_i64;
+        loop {
+            let rows = sqlx::query(
+                "SELECT seq, COALESCE(block_time, inserted_at) AS ts, payload FROM swaps \
+                 WHERE seq > ? AND event_type = ? AND COALESCE(block_time, inserted_at) < ? \
+                 ORDER BY seq LIMIT ?",
+            )
+            .bind(after_seq)
+            .bind(EventType::Swap.to_string())
+            .bind(cutoff)
+            .bind(ROLLUP_PAGE_SIZE)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(read_err)?;
+
+            for row in &rows {
+                after_seq = row.try_get("seq").map_err(read_err)?;
+                let payload: String = row.try_get("payload").map_err(read_err)?;
+                if let Ok(event) = serde_json::from_str::<SwapEvent>(&payload) {
+                    rollup.add(&event, row.try_get("ts").map_err(read_err)?);
+                }
+            }
+            if (rows.len() as i64) < ROLLUP_PAGE_SIZE {
+                break;
+            }
+        }
+
+        self.insert_candles(&rollup.into_candles(), KEEP_CANDLE)
+            .await
+    }
+}
+
+/// Checks the database's `user_version` and applies the migrations newer than it.
+async fn migrate(pool: &SqlitePool, mode: MigrationMode) -> Result<(), StorageError> {
+    let current: i64 = sqlx::query_scalar("PRAGMA user_version")
+        .fetch_one(pool)
+        .await
+        .map_err(|e| StorageError::Connection(e.to_string()))?;
+    let fresh: bool = sqlx::query_scalar(
+        "SELECT NOT EXISTS (SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'swaps')",
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| StorageError::Connection(e.to_string()))?;
+    check_version("SQLite", current, MIGRATIONS.len(), fresh, mode)?;
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(current as usize) {
+        let version = index + 1;
+        let mut tx = pool
+            .begin()
+            .await
+            .map_err(|e| StorageError::Connection(e.to_string()))?;
+        sqlx::raw_sql(migration)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| StorageError::Connection(format!("migration {version}: {e}")))?;
+        sqlx::raw_sql(&format!("PRAGMA user_version = {version}"))
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| StorageError::Connection(e.to_string()))?;
+        tx.commit()
+            .await
+            .map_err(|e| StorageError::Connection(e.to_string()))?;
+        log::info!("SQLite schema migrated to version {version}");
+    }
+
+    Ok(())
+}
+
+#[async_trait]
+impl BatchWriter for SqliteSink {
+    fn name(&self) -> &'static str {
+        "sqlite"
+    }
+
+    async fn write_batch(&mut self, events: &[SwapEvent]) -> Result<(), StorageError> {
+        for chunk in events.chunks(MAX_BIND_PARAMS / PARAMS_PER_ROW) {
+            let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new(
+                "INSERT OR IGNORE INTO swaps (signature, instruction_index, inner_path, slot, \
+                 block_time, event_type, protocol, pool, input_mint, input_amount, output_mint, \
+                 output_amount, direction, fee, maker, payload) ",
+            );
+
+            // Amounts are bound as text; NUMERIC affinity stores them as integers
+            // when they fit in i64, avoiding silent u64 wrap-around.
+            builder.push_values(chunk, |mut row, event| {
+                row.push_bind(event.signature.clone())
+                    .push_bind(event.provenance.instruction_index as i64)
+                    .push_bind(event.provenance.inner_path.clone())
+                    .push_bind(event.slot as i64)
+                    .push_bind(event.timestamp)
+                    .push_bind(event.event_type.to_string())
+                    .push_bind(event.protocol.to_string())
+                    .push_bind(event.pool.clone())
+                    .push_bind(event.input_token.as_ref().map(|t| t.mint.clone()))
+                    .push_bind(event.input_token.as_ref().map(|t| t.amount_raw.to_string()))
+                    .push_bind(event.output_token.as_ref().map(|t| t.mint.clone()))
+                    .push_bind(
+                        event
+                            .output_token
+                            .as_ref()
+                            .map(|t| t.amount_raw.to_string()),
+                    )
+                    .push_bind(event.direction.to_string())
+                    .push_bind(event.fee.map(|f| f.to_string()))
+                    .push_bind(event.maker.clone())
+                    .push_bind(serde_json::to_string(event).unwrap_or_default());
+            });
+
+            builder
+                .build()
+                .execute(&self.pool)
+                .await
+                .map_err(|e| StorageError::Write(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    fn supports_updates(&self) -> bool {
+        true
+    }
+
+    /// Replaces the payload of stored events in one transaction; `seq` is
+    /// kept, so backfill consumers that already read an event do not see its
+    /// update.
+    async fn update_batch(&mut self, events: &[SwapEvent]) -> Result<(), StorageError> {
+        let write_err = |e: sqlx::Error| StorageError::Write(e.to_string());
+        let mut tx = self.pool.begin().await.map_err(write_err)?;
+        for event in events {
+            sqlx::query(
+                "UPDATE swaps SET payload = ? \
+                 WHERE signature = ? AND instruction_index = ? AND inner_path = ?",
+            )
+            .bind(serde_json::to_string(event).unwrap_or_default())
+            .bind(event.signature.clone())
+            .bind(event.provenance.instruction_index as i64)
+            .bind(event.provenance.inner_path.clone())
+            .execute(&mut *tx)
+            .await
+            .map_err(write_err)?;
+        }
+        tx.commit().await.map_err(write_err)
+    }
+
+    async fn write_candles(&mut self, candles: &[Candle]) -> Result<(), StorageError> {
+        self.insert_candles(candles, MERGE_CANDLE).await.map(|_| ())
+    }
+
+    /// Rolls up and deletes swaps and sub-hour candles past the rollup
+    /// boundary, deletes expired swaps and candles, then the oldest swaps
+    /// while the database's used pages exceed the size limit. Freed pages are
+    /// reused by later writes, so the file stops growing; `VACUUM` shrinks it.
+    async fn prune(
+        &mut self,
+        policy: &RetentionPolicy,
+        now: i64,
+    ) -> Result<PruneStats, StorageError> {
+        let write_err = |e: sqlx::Error| StorageError::Write(e.to_string());
+        let mut stats = PruneStats::default();
+
+        if let Some(cutoff) = policy.rollup_cutoff(now) {
+            stats.rolled_up += self.roll_up(cutoff).await?;
+            stats.events +=
+                sqlx::query("DELETE FROM swaps WHERE COALESCE(block_time, inserted_at) < ?")
+                    .bind(cutoff)
+                    .execute(&self.pool)
+                    .await
+                    .map_err(write_err)?
+                    .rows_affected();
+            stats.candles += sqlx::query(&format!(
+                "DELETE FROM candles WHERE timeframe IN ({}) AND open_time < ?",
+                sub_hour_timeframes()
+            ))
+            .bind(cutoff)
+            .execute(&self.pool)
+            .await
+            .map_err(write_err)?
+            .rows_affected();
+        }
+
+        if let Some(cutoff) = policy.event_cutoff(now) {
+            stats.events += sqlx::query("DELETE FROM swaps WHERE inserted_at < ?")
+                .bind(cutoff)
+                .execute(&self.pool)
+                .await
+                .map_err(write_err)?
+                .rows_affected();
+        }
+        if let Some(cutoff) = policy.candle_cutoff(now) {
+            stats.candles += sqlx::query("DELETE FROM candles WHERE open_time < ?")
+                .bind(cutoff)
+                .execute(&self.pool)
+                .await
+                .map_err(write_err)?
+                .rows_affected();
+        }
+
+        if let Some(max_size) = policy.max_size {
+            let pragma =
+                |name: &'static str| sqlx::query_scalar::<_, i64>(name).fetch_one(&self.pool);
+            let pages = pragma("PRAGMA page_count").await.map_err(write_err)?;
+            let free = pragma("PRAGMA freelist_count").await.map_err(write_err)?;
+            let page_size = pragma("PRAGMA page_size").await.map_err(write_err)?;
+            let rows = pragma("SELECT COUNT(*) FROM swaps")
+                .await
+                .map_err(write_err)?;
+
+            let used = ((pages - free) * page_size).max(0) as u64;
+            let excess = excess_rows(rows.max(0) as u64, used, max_size);
+            if excess > 0 {
+                stats.events += sqlx::query(
+                    "DELETE FROM swaps WHERE rowid IN \
+                     (SELECT rowid FROM swaps ORDER BY inserted_at LIMIT ?)",
+                )
+                .bind(excess as i64)
+                .execute(&self.pool)
+                .await
+                .map_err(write_err)?
+                .rows_affected();
+            }
+        }
+
+        if !stats.is_empty() {
+            // Keep the WAL from holding on to the deleted pages
+            sqlx::query("PRAGMA wal_checkpoint(TRUNCATE)")
+                .execute(&self.pool)
+                .await
+                .map_err(write_err)?;
+        }
+        Ok(stats)
+    }
+}
+
+#[async_trait]
+impl CandleStore for SqliteSink {
+    async fn candles(
+        &self,
+        pool: &str,
+        interval: Interval,
+        from: i64,
+        to: i64,
+        limit: usize,
+    ) -> Result<Vec<Candle>, StorageError> {
+        let rows = sqlx::query(
+            "SELECT open_time, open, high, low, close, volume, volume_usd, trades FROM candles \
+             WHERE pool = ? AND timeframe = ? AND open_time BETWEEN ? AND ? \
+             ORDER BY open_time LIMIT ?",
+        )
+        .bind(pool)
+        .bind(interval.to_string())
+        .bind(from)
+        .bind(to)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| StorageError::Read(e.to_string()))?;
+
+        rows.iter()
+            .map(|row| candle_from_row(row, pool, interval))
+            .collect::<Result<_, sqlx::Error>>()
+            .map_err(|e| StorageError::Read(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl EventStore for SqliteSink {
+    async fn events_after(
+        &self,
+        after_seq: u64,
+        from_slot: u64,
+        limit: usize,
+    ) -> Result<Vec<StoredEvent>, StorageError> {
+        let rows = sqlx::query(
+            "SELECT seq, payload FROM swaps WHERE seq > ? AND slot >= ? ORDER BY seq LIMIT ?",
+        )
+        .bind(after_seq as i64)
+        .bind(from_slot as i64)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| StorageError::Read(e.to_string()))?;
+
+        rows.iter()
+            .map(|row| {
+                let seq: i64 = row.try_get("seq")?;
+                let payload: String = row.try_get("payload")?;
+                let event =
+                    serde_json::from_str(&payload).map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+                Ok(StoredEvent {
+                    seq: seq as u64,
+                    event,
+                })
+            })
+            .collect::<Result<_, sqlx::Error>>()
+            .map_err(|e| StorageError::Read(e.to_string()))
+    }
+}
+
+/// Returns the quoted names of sub-hour intervals for an `IN` list.
+fn sub_hour_timeframes() -> String {
+    Interval::ALL
+        .iter()
+        .filter(|interval| interval.is_sub_hour())
+        .map(|interval| format!("'{interval}'"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Converts a `candles` row into a [`Candle`].
+fn candle_from_row(row: &SqliteRow, pool: &str, interval: Interval) -> Result<Candle, sqlx::Error> {
+    Ok(Candle {
+        pool: pool.to_string(),
+        interval,
+        open_time: row.try_get("open_time")?,
+        open: row.try_get("open")?,
+        high: row.try_get("high")?,
+        low: row.try_get("low")?,
+        close: row.try_get("close")?,
+        volume: row.try_get("volume")?,
+        volume_usd: row.try_get("volume_usd")?,
+        trades: row.try_get::<i64, _>("trades")? as u64,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{swap_event::WSOL_MINT, Protocol, TokenInfo},
+    };
+
+    fn test_event(sig: &str, amount: u64) -> SwapEvent {
+        SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature(sig)
+            .pool("pool")
+            .input_token(TokenInfo::new("mint_in", amount))
+            .output_token(TokenInfo::new("mint_out", 1))
+            .slot(42)
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_sink_writes_and_ignores_duplicates() {
+        let mut sink = SqliteSink::connect("sqlite::memory:", MigrationMode::Check)
+            .await
+            .unwrap();
+
+        let events = vec![test_event("sig1", u64::MAX), test_event("sig2", 100)];
+        sink.write_batch(&events).await.unwrap();
+        // Re-writing the same events must not fail or duplicate rows
+        sink.write_batch(&events).await.unwrap();
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM swaps")
+            .fetch_one(&sink.pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 2);
+
+        let amount: i64 =
+            sqlx::query_scalar("SELECT input_amount FROM swaps WHERE signature = 'sig2'")
+                .fetch_one(&sink.pool)
+                .await
+                .unwrap();
+        assert_eq!(amount, 100);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_update_replaces_payload() {
+        let mut sink = SqliteSink::connect("sqlite::memory:", MigrationMode::Check)
+            .await
+            .unwrap();
+        sink.write_batch(&[test_event("sig1", 1)]).await.unwrap();
+
+        let mut enriched = test_event("sig1", 1);
+        enriched.market_cap_usd = Some(1_000.0);
+        // An update of an event that was never stored is ignored
+        sink.update_batch(&[enriched, test_event("sig2", 1)])
+            .await
+            .unwrap();
+
+        let payloads: Vec<String> = sqlx::query_scalar("SELECT payload FROM swaps")
+            .fetch_all(&sink.pool)
+            .await
+            .unwrap();
+        assert_eq!(payloads.len(), 1);
+        assert!(payloads[0].contains("\"market_cap_usd\":1000.0"));
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_migrations_are_idempotent() {
+        let sink = SqliteSink::connect("sqlite::memory:", MigrationMode::Check)
+            .await
+            .unwrap();
+        migrate(&sink.pool, MigrationMode::Check).await.unwrap();
+
+        let version: i64 = sqlx::query_scalar("PRAGMA user_version")
+            .fetch_one(&sink.pool)
+            .await
+            .unwrap();
+        assert_eq!(version, MIGRATIONS.len() as i64);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_schema_check_refuses_other_versions() {
+        let sink = SqliteSink::connect("sqlite::memory:", MigrationMode::Check)
+            .await
+            .unwrap();
+
+        // Pending migrations of an existing database need --migrate
+        sqlx::raw_sql("PRAGMA user_version = 3")
+            .execute(&sink.pool)
+            .await
+            .unwrap();
+        assert!(matches!(
+            migrate(&sink.pool, MigrationMode::Check).await,
+            Err(StorageError::Schema(_))
+        ));
+
+        // A database written by a newer release is refused even with --migrate
+        let newer = format!("PRAGMA user_version = {}", MIGRATIONS.len() + 1);
+        sqlx::raw_sql(&newer).execute(&sink.pool).await.unwrap();
+        assert!(matches!(
+            migrate(&sink.pool, MigrationMode::Apply).await,
+            Err(StorageError::Schema(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_candles_merge_and_query() {
+        let mut sink = SqliteSink::connect("sqlite::memory:", MigrationMode::Check)
+            .await
+            .unwrap();
+        let candle = |open_time, price, volume_usd| Candle {
+            pool: "pool".to_string(),
+            interval: Interval::M1,
+            open_time,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: 1.0,
+            volume_usd,
+            trades: 1,
+        };
+
+        sink.write_candles(&[candle(60, 2.0, None), candle(120, 3.0, None)])
+            .await
+            .unwrap();
+        // A late partial candle for the same minute is merged, not overwritten
+        sink.write_candles(&[candle(60, 5.0, Some(10.0))])
+            .await
+            .unwrap();
+
+        let candles = sink
+            .candles("pool", Interval::M1, 0, 1_000, 10)
+            .await
+            .unwrap();
+        assert_eq!(candles.len(), 2);
+        assert_eq!(
+            (candles[0].open, candles[0].high, candles[0].close),
+            (2.0, 5.0, 5.0)
+        );
+        assert_eq!(candles[0].trades, 2);
+        assert_eq!(candles[0].volume_usd, Some(10.0));
+
+        assert_eq!(
+            sink.candles("pool", Interval::M1, 100, 1_000, 10)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(sink
+            .candles("pool", Interval::M5, 0, 1_000, 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_events_after() {
+        let mut sink = SqliteSink::connect("sqlite::memory:", MigrationMode::Check)
+            .await
+            .unwrap();
+        let mut late = test_event("sig3", 3);
+        late.slot = 40;
+        let events = vec![test_event("sig1", 1), test_event("sig2", 2), late];
+        sink.write_batch(&events).await.unwrap();
+        // Duplicates are ignored and do not get a new seq
+        sink.write_batch(&events[..1]).await.unwrap();
+
+        let stored = sink.events_after(0, 0, 10).await.unwrap();
+        let seqs: Vec<u64> = stored.iter().map(|stored| stored.seq).collect();
+        assert_eq!(seqs, [1, 2, 3]);
+        assert_eq!(stored[0].event["signature"], "sig1");
+
+        let page = sink.events_after(1, 0, 1).await.unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].seq, 2);
+
+        let from_slot = sink.events_after(0, 42, 10).await.unwrap();
+        let seqs: Vec<u64> = from_slot.iter().map(|stored| stored.seq).collect();
+        assert_eq!(seqs, [1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_prune_by_age_and_size() {
+        let mut sink = SqliteSink::connect("sqlite::memory:", MigrationMode::Check)
+            .await
+            .unwrap();
+        let events: Vec<_> = (0..4u64)
+            .map(|i| test_event(&format!("sig{i}"), i))
+            .collect();
+        sink.write_batch(&events).await.unwrap();
+        sqlx::query("UPDATE swaps SET inserted_at = 1000 WHERE signature IN ('sig0', 'sig1')")
+            .execute(&sink.pool)
+            .await
+            .unwrap();
+
+        let now = 1000 + 7_200;
+        let policy = RetentionPolicy {
+            max_age: Some(std::time::Duration::from_secs(3_600)),
+            ..RetentionPolicy::default()
+        };
+        assert_eq!(sink.prune(&policy, now).await.unwrap().events, 2);
+        assert!(sink.prune(&policy, now).await.unwrap().is_empty());
+
+        // A size limit below the database size removes the oldest swaps
+        let policy = RetentionPolicy {
+            max_size: Some(1),
+            ..RetentionPolicy::default()
+        };
+        assert_eq!(sink.prune(&policy, now).await.unwrap().events, 2);
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM swaps")
+            .fetch_one(&sink.pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_prune_rolls_up_old_swaps() {
+        let mut sink = SqliteSink::connect("sqlite::memory:", MigrationMode::Check)
+            .await
+            .unwrap();
+        let priced = |sig: &str, sol: u64| {
+            SwapEvent::builder()
+                .protocol(Protocol::Cpmm)
+                .signature(sig)
+                .pool("pool")
+                .input_token(TokenInfo::new(WSOL_MINT, sol))
+                .output_token(TokenInfo::new("mint", 2).with_decimals(0))
+                .build()
+        };
+        let day = 86_400;
+        let events = vec![
+            priced("old1", 1_000_000_000),
+            priced("old2", 3_000_000_000),
+            priced("recent", 1_000_000_000),
+        ];
+        sink.write_batch(&events).await.unwrap();
+        for (sig, ts) in [("old1", 60), ("old2", 3_700), ("recent", 2 * day)] {
+            sqlx::query("UPDATE swaps SET block_time = ? WHERE signature = ?")
+                .bind(ts)
+                .bind(sig)
+                .execute(&sink.pool)
+                .await
+                .unwrap();
+        }
+        let candle = |interval, open_time, price| Candle {
+            pool: "pool".to_string(),
+            interval,
+            open_time,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: 1.0,
+            volume_usd: None,
+            trades: 1,
+        };
+        // The live builder already wrote the second hour, which must be kept
+        sink.write_candles(&[
+            candle(Interval::M1, 0, 0.5),
+            candle(Interval::H1, 3_600, 9.0),
+        ])
+        .await
+        .unwrap();
+
+        let policy = RetentionPolicy {
+            rollup_after: Some(std::time::Duration::from_secs(day as u64)),
+            ..RetentionPolicy::default()
+        };
+        let stats = sink.prune(&policy, 2 * day + 60).await.unwrap();
+        assert_eq!(stats.rolled_up, 2);
+        assert_eq!(stats.events, 2);
+        assert_eq!(stats.candles, 1);
+
+        let hours = sink
+            .candles("pool", Interval::H1, 0, day, 10)
+            .await
+            .unwrap();
+        let bars: Vec<_> = hours.iter().map(|c| (c.open_time, c.close)).collect();
+        assert_eq!(bars, [(0, 0.5), (3_600, 9.0)]);
+        let days = sink
+            .candles("pool", Interval::D1, 0, day, 10)
+            .await
+            .unwrap();
+        assert_eq!(days.len(), 1);
+        assert_eq!((days[0].open, days[0].close, days[0].trades), (0.5, 1.5, 2));
+        assert!(sink
+            .candles("pool", Interval::M1, 0, day, 10)
+            .await
+            .unwrap()
+            .is_empty());
+
+        let remaining: Vec<String> = sqlx::query_scalar("SELECT signature FROM swaps")
+            .fetch_all(&sink.pool)
+            .await
+            .unwrap();
+        assert_eq!(remaining, ["recent"]);
+    }
+}
@@ -0,0 +1,190 @@
+//! Per-chat routing and delivery of subscribed events.
+
+use {
+    super::{
+        quota::{AlertQuota, OverflowDigest},
+        SubscriptionRegistry, TelegramClient,
+    },
+    crate::{
+        clock,
+        output::{template::SUBSCRIPTIONS_TEMPLATE, Notifier, SwapEvent},
+    },
+    async_trait::async_trait,
+    std::{sync::Arc, time::Duration},
+    tokio::sync::mpsc,
+};
+
+/// Delay between messages, keeping the bot under Telegram's global send limit.
+const SEND_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How often ended quota windows are checked for overflow digests.
+const DIGEST_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A message queued for a chat.
+enum Delivery {
+    /// A subscribed event
+    Alert(Box<SwapEvent>),
+    /// Summary of the alerts withheld by the chat's quota
+    Digest(OverflowDigest),
+}
+
+impl Delivery {
+    fn text(&self) -> String {
+        match self {
+            Self::Alert(event) => event.format_text_with(SUBSCRIPTIONS_TEMPLATE),
+            Self::Digest(digest) => digest.to_string(),
+        }
+    }
+
+    fn describe(&self) -> String {
+        match self {
+            Self::Alert(event) => format!("sig={}", event.signature),
+            Self::Digest(digest) => format!("digest of {} alert(s)", digest.withheld),
+        }
+    }
+}
+
+/// Routes emitted events to every chat with a matching subscription.
+///
+/// Each chat receives an event at most once, even if several of its
+/// subscriptions match. Messages are delivered by a background task; a chat
+/// that blocked the bot loses its subscriptions. With an alert quota, chats
+/// over their hourly limit are not queued and get a digest instead.
+pub struct SubscriptionBus {
+    registry: Arc<SubscriptionRegistry>,
+    /// Optional per-chat hourly alert limit
+    quota: Option<Arc<AlertQuota>>,
+    /// Channel sender for queuing `(chat_id, delivery)` messages
+    tx: mpsc::Sender<(i64, Delivery)>,
+    /// Handle to the background delivery task
+    _task_handle: tokio::task::JoinHandle<()>,
+}
+
+impl SubscriptionBus {
+    /// Creates a bus delivering matches from `registry` through `client`.
+    ///
+    /// # Arguments
+    ///
+    /// * `registry` - Subscriptions to route events by
+    /// * `client` - Telegram client delivering the alerts
+    pub fn new(registry: Arc<SubscriptionRegistry>, client: Arc<TelegramClient>) -> Self {
+        let (tx, rx) = mpsc::channel(1000);
+        let task_handle = tokio::spawn(Self::delivery_task(rx, registry.clone(), client));
+
+        Self {
+            registry,
+            quota: None,
+            tx,
+            _task_handle: task_handle,
+        }
+    }
+
+    /// Limits each chat to `max_per_hour` alerts per hour; `0` is unlimited.
+    ///
+    /// Alerts over the limit are counted per token and sent as one digest
+    /// message when the chat's hour ends.
+    pub fn with_alert_quota(mut self, max_per_hour: u32) -> Self {
+        if max_per_hour == 0 {
+            return self;
+        }
+        let quota = Arc::new(AlertQuota::new(max_per_hour));
+        tokio::spawn(Self::digest_task(quota.clone(), self.tx.clone()));
+        self.quota = Some(quota);
+        self
+    }
+
+    /// Returns the number of subscriptions across all chats.
+    pub fn len(&self) -> usize {
+        self.registry.len()
+    }
+
+    /// Returns the per-chat hourly alert limit, if any.
+    pub fn alert_limit(&self) -> Option<u32> {
+        self.quota.as_ref().map(|quota| quota.limit())
+    }
+
+    /// Queues the event for every chat subscribed to it, without blocking.
+    pub fn dispatch(&self, event: &SwapEvent) {
+        let now = clock::now();
+        for chat_id in self.registry.matching_chats(event) {
+            if let Some(ref quota) = self.quota {
+                if !quota.admit(chat_id, event, now) {
+                    continue;
+                }
+            }
+            if let Err(e) = self
+                .tx
+                .try_send((chat_id, Delivery::Alert(Box::new(event.clone()))))
+            {
+                log::warn!("Failed to queue subscription alert for chat {chat_id}: {e}");
+            }
+        }
+    }
+
+    /// Background task queuing the digests of ended quota windows.
+    async fn digest_task(quota: Arc<AlertQuota>, tx: mpsc::Sender<(i64, Delivery)>) {
+        let mut interval = tokio::time::interval(DIGEST_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            for (chat_id, digest) in quota.take_digests(clock::now()) {
+                log::debug!(
+                    "Chat {chat_id} exceeded its alert quota by {} alert(s)",
+                    digest.withheld
+                );
+                if tx.send((chat_id, Delivery::Digest(digest))).await.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Background task sending queued alerts.
+    async fn delivery_task(
+        mut rx: mpsc::Receiver<(i64, Delivery)>,
+        registry: Arc<SubscriptionRegistry>,
+        client: Arc<TelegramClient>,
+    ) {
+        while let Some((chat_id, delivery)) = rx.recv().await {
+            let text = delivery.text();
+            let mut result = client.send_message(chat_id, &text).await;
+
+            // Honour flood control once before giving up on the message
+            if let Some(delay) = result.as_ref().err().and_then(|e| e.retry_after()) {
+                tokio::time::sleep(delay).await;
+                result = client.send_message(chat_id, &text).await;
+            }
+
+            match result {
+                Ok(()) => log::debug!(
+                    "Subscription alert delivered: chat={chat_id}, {}",
+                    delivery.describe()
+                ),
+                Err(e) if e.is_forbidden() => match registry.unsubscribe_all(chat_id) {
+                    Ok(removed) => log::info!(
+                        "Chat {chat_id} blocked the bot, removed {removed} subscription(s)"
+                    ),
+                    Err(e) => log::warn!("Failed to remove subscriptions of chat {chat_id}: {e}"),
+                },
+                Err(e) => log::warn!(
+                    "Subscription alert failed: chat={chat_id}, {}, err={e}",
+                    delivery.describe()
+                ),
+            }
+
+            tokio::time::sleep(SEND_INTERVAL).await;
+        }
+
+        log::info!("Subscription delivery task shutting down");
+    }
+}
+
+#[async_trait]
+impl Notifier for SubscriptionBus {
+    fn name(&self) -> &str {
+        "telegram"
+    }
+
+    async fn notify(&self, event: &SwapEvent) {
+        self.dispatch(event);
+    }
+}
@@ -0,0 +1,288 @@
+//! Self-service alert subscriptions via a Telegram bot.
+//!
+//! End users subscribe their chat to a token mint or a wallet by opening a bot
+//! deep link such as `https://t.me/<bot>?start=m_<MINT>_1000`, which sends
+//! `/start m_<MINT>_1000` to the bot. Subscriptions are kept in the
+//! [`SubscriptionRegistry`] (persisted to `SUBSCRIPTIONS_FILE`), and the
+//! [`SubscriptionBus`] routes every emitted event to each subscribed chat
+//! individually.
+//!
+//! Start payloads have the form `<kind>_<address>[_<min_usd>]`:
+//!
+//! - `m_<MINT>` - swaps where either token is the mint
+//! - `w_<WALLET>` - swaps made by the wallet
+//! - `_<min_usd>` - optional minimum USD value (events without a USD value are skipped)
+//!
+//! Subscriptions see the events that pass the global `FILTER_*` filters.
+//!
+//! To keep delivery fair across chats, each chat may hold a limited number of
+//! subscriptions and receive a limited number of alerts per hour; alerts over
+//! the hourly limit are summarized in an [`OverflowDigest`](quota::OverflowDigest).
+
+mod bus;
+mod quota;
+mod registry;
+mod telegram;
+
+pub use {
+    bus::SubscriptionBus,
+    registry::SubscriptionRegistry,
+    telegram::{TelegramBot, TelegramClient},
+};
+
+use {
+    crate::output::SwapEvent,
+    serde::{Deserialize, Serialize},
+    solana_pubkey::Pubkey,
+    std::{env, fmt, path::PathBuf, str::FromStr},
+};
+
+/// Default Telegram Bot API endpoint.
+pub(crate) const DEFAULT_API_URL: &str = "https://api.telegram.org";
+
+/// Default file the subscription registry is persisted to.
+const DEFAULT_SUBSCRIPTIONS_FILE: &str = "subscriptions.json";
+
+/// Default maximum number of subscriptions per chat.
+const DEFAULT_MAX_PER_CHAT: usize = 20;
+
+/// Default maximum number of alerts per chat per hour.
+const DEFAULT_MAX_ALERTS_PER_HOUR: u32 = 60;
+
+/// Errors that can occur while managing subscriptions.
+#[derive(Debug)]
+pub enum SubscriptionError {
+    /// The start payload or command arguments are malformed
+    InvalidPayload(String),
+    /// The registry file could not be read or written
+    Io(String),
+    /// The registry file is not valid JSON
+    Parse(String),
+    /// The chat already holds the maximum number of subscriptions
+    QuotaExceeded(usize),
+}
+
+impl fmt::Display for SubscriptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPayload(e) => write!(f, "invalid subscription: {e}"),
+            Self::Io(e) => write!(f, "subscription registry I/O error: {e}"),
+            Self::Parse(e) => write!(f, "failed to parse subscription registry: {e}"),
+            Self::QuotaExceeded(max) => write!(
+                f,
+                "limit of {max} subscriptions per chat reached, /unsubscribe one first"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SubscriptionError {}
+
+/// Configuration for the Telegram subscription bot.
+#[derive(Debug, Clone)]
+pub struct SubscriptionsConfig {
+    /// Telegram bot token
+    pub bot_token: String,
+    /// Telegram Bot API endpoint
+    pub api_url: String,
+    /// File the registry is persisted to
+    pub file: PathBuf,
+    /// Maximum number of subscriptions per chat (0 is unlimited)
+    pub max_per_chat: usize,
+    /// Maximum number of alerts per chat per hour (0 is unlimited)
+    pub max_alerts_per_hour: u32,
+}
+
+impl SubscriptionsConfig {
+    /// Loads the subscription bot configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `TELEGRAM_BOT_TOKEN` - Required: Bot token from @BotFather
+    /// - `TELEGRAM_API_URL` - Optional: Bot API endpoint (default: https://api.telegram.org)
+    /// - `SUBSCRIPTIONS_FILE` - Optional: Registry file (default: subscriptions.json)
+    /// - `SUBSCRIPTION_MAX_PER_CHAT` - Optional: Subscriptions per chat, 0 is unlimited (default: 20)
+    /// - `SUBSCRIPTION_MAX_ALERTS_PER_HOUR` - Optional: Alerts per chat per hour before
+    ///   the rest are summarized in a digest, 0 is unlimited (default: 60)
+    ///
+    /// # Returns
+    ///
+    /// `Some(SubscriptionsConfig)` if `TELEGRAM_BOT_TOKEN` is set, `None` otherwise.
+    pub fn from_env() -> Option<Self> {
+        let bot_token = env::var("TELEGRAM_BOT_TOKEN").ok()?;
+        if bot_token.trim().is_empty() {
+            return None;
+        }
+        let non_empty = |name: &str| {
+            env::var(name)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        Some(Self {
+            bot_token: bot_token.trim().to_string(),
+            api_url: non_empty("TELEGRAM_API_URL").unwrap_or_else(|| DEFAULT_API_URL.to_string()),
+            file: PathBuf::from(
+                non_empty("SUBSCRIPTIONS_FILE")
+                    .unwrap_or_else(|| DEFAULT_SUBSCRIPTIONS_FILE.to_string()),
+            ),
+            max_per_chat: parse_limit("SUBSCRIPTION_MAX_PER_CHAT", DEFAULT_MAX_PER_CHAT),
+            max_alerts_per_hour: parse_limit(
+                "SUBSCRIPTION_MAX_ALERTS_PER_HOUR",
+                DEFAULT_MAX_ALERTS_PER_HOUR,
+            ),
+        })
+    }
+}
+
+/// Parses a numeric limit from an environment variable, falling back to `default`.
+fn parse_limit<T: FromStr + fmt::Display>(name: &str, default: T) -> T {
+    match env::var(name) {
+        Ok(value) if !value.trim().is_empty() => value.trim().parse().unwrap_or_else(|_| {
+            log::warn!("Invalid {name} '{value}', using default {default}");
+            default
+        }),
+        _ => default,
+    }
+}
+
+/// What a subscription watches.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "address", rename_all = "snake_case")]
+pub enum Target {
+    /// Swaps where either token is this mint
+    Mint(String),
+    /// Swaps made by this wallet
+    Wallet(String),
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mint(mint) => write!(f, "mint {mint}"),
+            Self::Wallet(wallet) => write!(f, "wallet {wallet}"),
+        }
+    }
+}
+
+/// A chat's subscription to a mint or wallet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Subscription {
+    /// Telegram chat receiving the alerts
+    pub chat_id: i64,
+    /// Watched mint or wallet
+    pub target: Target,
+    /// Minimum USD value of alerted events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_usd: Option<f64>,
+}
+
+impl Subscription {
+    /// Parses a deep-link start payload (`<kind>_<address>[_<min_usd>]`) for a chat.
+    pub fn from_payload(chat_id: i64, payload: &str) -> Result<Self, SubscriptionError> {
+        let invalid =
+            |reason: &str| SubscriptionError::InvalidPayload(format!("'{payload}': {reason}"));
+        let mut parts = payload.trim().split('_');
+
+        let kind = parts.next().unwrap_or_default();
+        let address = parts.next().ok_or_else(|| invalid("missing address"))?;
+        Pubkey::from_str(address).map_err(|_| invalid("address is not a valid pubkey"))?;
+        let target = match kind.to_lowercase().as_str() {
+            "m" | "mint" => Target::Mint(address.to_string()),
+            "w" | "wallet" => Target::Wallet(address.to_string()),
+            _ => return Err(invalid("kind must be 'm' (mint) or 'w' (wallet)")),
+        };
+
+        let min_usd = match parts.next() {
+            Some(value) => Some(
+                value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|usd| usd.is_finite() && *usd >= 0.0)
+                    .ok_or_else(|| invalid("minimum USD value must be a positive number"))?,
+            ),
+            None => None,
+        };
+        if parts.next().is_some() {
+            return Err(invalid("unexpected trailing data"));
+        }
+
+        Ok(Self {
+            chat_id,
+            target,
+            min_usd,
+        })
+    }
+
+    /// Returns `true` if the event concerns the target and meets the USD threshold.
+    pub fn matches(&self, event: &SwapEvent) -> bool {
+        let targeted = match &self.target {
+            Target::Mint(mint) => [&event.input_token, &event.output_token]
+                .into_iter()
+                .flatten()
+                .any(|token| token.mint == *mint),
+            Target::Wallet(wallet) => event.maker.as_deref() == Some(wallet.as_str()),
+        };
+        targeted
+            && self
+                .min_usd
+                .is_none_or(|min| event.usd_value().is_some_and(|usd| usd >= min))
+    }
+}
+
+impl fmt::Display for Subscription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.target)?;
+        if let Some(min) = self.min_usd {
+            write!(f, " (min ${min})")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{Protocol, TokenInfo},
+    };
+
+    const MINT: &str = "So11111111111111111111111111111111111111112";
+
+    #[test]
+    fn test_parse_payload() {
+        let sub = Subscription::from_payload(7, &format!("m_{MINT}_1000")).unwrap();
+        assert_eq!(sub.chat_id, 7);
+        assert_eq!(sub.target, Target::Mint(MINT.to_string()));
+        assert_eq!(sub.min_usd, Some(1000.0));
+
+        let sub = Subscription::from_payload(7, &format!("wallet_{MINT}")).unwrap();
+        assert_eq!(sub.target, Target::Wallet(MINT.to_string()));
+        assert_eq!(sub.min_usd, None);
+
+        assert!(Subscription::from_payload(7, "m_notapubkey").is_err());
+        assert!(Subscription::from_payload(7, &format!("x_{MINT}")).is_err());
+        assert!(Subscription::from_payload(7, &format!("m_{MINT}_abc")).is_err());
+        assert!(Subscription::from_payload(7, "").is_err());
+    }
+
+    #[test]
+    fn test_matches_target_and_threshold() {
+        let event = SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .input_token(TokenInfo::new(MINT, 1).with_usd_value(500.0))
+            .output_token(TokenInfo::new("other", 1))
+            .maker("maker")
+            .build();
+
+        let mint = Subscription::from_payload(1, &format!("m_{MINT}")).unwrap();
+        assert!(mint.matches(&event));
+        let whale = Subscription::from_payload(1, &format!("m_{MINT}_1000")).unwrap();
+        assert!(!whale.matches(&event));
+        let wallet = Subscription::from_payload(1, &format!("w_{MINT}")).unwrap();
+        assert!(!wallet.matches(&event));
+    }
+}
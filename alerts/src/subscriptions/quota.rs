@@ -0,0 +1,248 @@
+//! Per-chat alert quotas with overflow digests.
+//!
+//! Each chat may receive a limited number of alerts per hour. Matches beyond
+//! the limit are not queued for delivery; they are counted per token and
+//! summarized in a single digest message once the chat's hour is over, so a
+//! chat watching many busy tokens cannot starve delivery to other chats.
+//! High-priority events (liquidity drains) are always delivered, though they
+//! count toward the quota.
+
+use {
+    crate::output::SwapEvent,
+    std::{
+        collections::{BTreeMap, HashMap},
+        fmt,
+        sync::{Mutex, PoisonError},
+        time::{Duration, Instant},
+    },
+};
+
+/// Length of a quota window.
+pub const QUOTA_WINDOW: Duration = Duration::from_secs(3600);
+
+/// Number of tokens listed individually in a digest.
+const DIGEST_TOP_TOKENS: usize = 5;
+
+/// Alerts withheld from a chat during one quota window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverflowDigest {
+    /// Alert limit of the window
+    pub limit: u32,
+    /// Number of alerts withheld
+    pub withheld: u32,
+    /// Withheld alerts per token (symbol, or mint if unknown)
+    pub by_token: BTreeMap<String, u32>,
+}
+
+impl fmt::Display for OverflowDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "⏳ Alert limit reached ({}/hour)", self.limit)?;
+        write!(f, "{} more alert(s) in the last hour", self.withheld)?;
+
+        let mut tokens: Vec<(&String, &u32)> = self.by_token.iter().collect();
+        tokens.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        for (token, count) in tokens.iter().take(DIGEST_TOP_TOKENS) {
+            write!(f, "\n• {token}: {count}")?;
+        }
+        if tokens.len() > DIGEST_TOP_TOKENS {
+            write!(
+                f,
+                "\n…and {} more token(s)",
+                tokens.len() - DIGEST_TOP_TOKENS
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Alerts counted for a chat in its current window.
+struct ChatWindow {
+    started: Instant,
+    sent: u32,
+    withheld: u32,
+    by_token: BTreeMap<String, u32>,
+}
+
+impl ChatWindow {
+    fn new(started: Instant) -> Self {
+        Self {
+            started,
+            sent: 0,
+            withheld: 0,
+            by_token: BTreeMap::new(),
+        }
+    }
+
+    fn expired(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.started) >= QUOTA_WINDOW
+    }
+
+    /// Returns the digest of withheld alerts, if any.
+    fn digest(self, limit: u32) -> Option<OverflowDigest> {
+        (self.withheld > 0).then_some(OverflowDigest {
+            limit,
+            withheld: self.withheld,
+            by_token: self.by_token,
+        })
+    }
+}
+
+/// Hourly alert limit applied to every chat.
+pub struct AlertQuota {
+    max_per_window: u32,
+    windows: Mutex<HashMap<i64, ChatWindow>>,
+    /// Digests of windows that ended while the chat kept receiving alerts
+    pending: Mutex<Vec<(i64, OverflowDigest)>>,
+}
+
+impl AlertQuota {
+    /// Creates a quota allowing `max_per_window` alerts per chat per hour.
+    pub fn new(max_per_window: u32) -> Self {
+        Self {
+            max_per_window,
+            windows: Mutex::new(HashMap::new()),
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns the number of alerts allowed per chat per hour.
+    pub fn limit(&self) -> u32 {
+        self.max_per_window
+    }
+
+    /// Counts an alert for a chat.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the alert should be delivered, `false` if the chat exceeded
+    /// its quota and the alert was added to the chat's overflow digest.
+    /// High-priority alerts are always delivered.
+    pub fn admit(&self, chat_id: i64, event: &SwapEvent, now: Instant) -> bool {
+        let mut windows = self.windows.lock().unwrap_or_else(PoisonError::into_inner);
+        let window = windows
+            .entry(chat_id)
+            .or_insert_with(|| ChatWindow::new(now));
+
+        if window.expired(now) {
+            let ended = std::mem::replace(window, ChatWindow::new(now));
+            if let Some(digest) = ended.digest(self.max_per_window) {
+                self.pending
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .push((chat_id, digest));
+            }
+        }
+
+        if window.sent < self.max_per_window || event.event_type.is_high_priority() {
+            window.sent = window.sent.saturating_add(1);
+            return true;
+        }
+        window.withheld += 1;
+        *window.by_token.entry(token_label(event)).or_default() += 1;
+        false
+    }
+
+    /// Takes the digests of windows that ended by `now` and forgets idle chats.
+    pub fn take_digests(&self, now: Instant) -> Vec<(i64, OverflowDigest)> {
+        let mut digests =
+            std::mem::take(&mut *self.pending.lock().unwrap_or_else(PoisonError::into_inner));
+
+        let mut windows = self.windows.lock().unwrap_or_else(PoisonError::into_inner);
+        let expired: Vec<i64> = windows
+            .iter()
+            .filter(|(_, window)| window.expired(now))
+            .map(|(chat_id, _)| *chat_id)
+            .collect();
+        for chat_id in expired {
+            if let Some(digest) = windows
+                .remove(&chat_id)
+                .and_then(|window| window.digest(self.max_per_window))
+            {
+                digests.push((chat_id, digest));
+            }
+        }
+        digests
+    }
+}
+
+/// Names the token an alert is about: the non-base token of the pair.
+fn token_label(event: &SwapEvent) -> String {
+    let (base, quote) = event.get_base_quote_tokens();
+    match quote.or(base) {
+        Some(token) => token.symbol.clone().unwrap_or_else(|| token.mint.clone()),
+        None => event.pool.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{swap_event::WSOL_MINT, EventType, Protocol, TokenInfo},
+    };
+
+    fn swap(symbol: &str) -> SwapEvent {
+        SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .input_token(TokenInfo::new(WSOL_MINT, 1))
+            .output_token(TokenInfo::new("mint", 1).with_symbol(symbol))
+            .build()
+    }
+
+    #[test]
+    fn test_limits_chat_and_digests_overflow() {
+        let quota = AlertQuota::new(2);
+        let start = Instant::now();
+
+        assert!(quota.admit(1, &swap("BONK"), start));
+        assert!(quota.admit(1, &swap("BONK"), start));
+        assert!(!quota.admit(1, &swap("BONK"), start));
+        assert!(!quota.admit(1, &swap("WIF"), start));
+        // Other chats keep their own quota
+        assert!(quota.admit(2, &swap("BONK"), start));
+
+        assert!(quota.take_digests(start).is_empty());
+
+        let digests = quota.take_digests(start + QUOTA_WINDOW);
+        assert_eq!(digests.len(), 1);
+        let (chat_id, digest) = &digests[0];
+        assert_eq!(*chat_id, 1);
+        assert_eq!(digest.withheld, 2);
+        assert_eq!(digest.by_token["WIF"], 1);
+        assert_eq!(
+            digest.to_string(),
+            "⏳ Alert limit reached (2/hour)\n2 more alert(s) in the last hour\n• BONK: 1\n• WIF: 1"
+        );
+
+        // A new window starts with a fresh quota
+        assert!(quota.admit(1, &swap("BONK"), start + QUOTA_WINDOW));
+    }
+
+    #[test]
+    fn test_high_priority_alerts_bypass_quota() {
+        let quota = AlertQuota::new(1);
+        let start = Instant::now();
+        let mut drain = swap("BONK");
+        drain.event_type = EventType::LiquidityDrain;
+
+        assert!(quota.admit(1, &swap("BONK"), start));
+        assert!(quota.admit(1, &drain, start));
+        assert!(!quota.admit(1, &swap("BONK"), start));
+    }
+
+    #[test]
+    fn test_digest_of_window_ended_by_new_alert() {
+        let quota = AlertQuota::new(1);
+        let start = Instant::now();
+
+        assert!(quota.admit(1, &swap("BONK"), start));
+        assert!(!quota.admit(1, &swap("BONK"), start));
+        assert!(quota.admit(1, &swap("BONK"), start + QUOTA_WINDOW));
+
+        let digests = quota.take_digests(start + QUOTA_WINDOW);
+        assert_eq!(digests.len(), 1);
+        assert_eq!(digests[0].1.withheld, 1);
+    }
+}
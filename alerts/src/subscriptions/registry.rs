@@ -0,0 +1,256 @@
+//! Persistent registry of chat subscriptions.
+
+use {
+    super::{Subscription, SubscriptionError},
+    crate::output::SwapEvent,
+    std::{
+        collections::BTreeSet,
+        fs,
+        io::ErrorKind,
+        path::{Path, PathBuf},
+        sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    },
+};
+
+/// Subscriptions of all chats, saved to a JSON file on every change.
+pub struct SubscriptionRegistry {
+    /// File the registry is persisted to (`None` keeps it in memory only)
+    path: Option<PathBuf>,
+    /// Maximum number of subscriptions per chat (`None` is unlimited)
+    max_per_chat: Option<usize>,
+    subscriptions: RwLock<Vec<Subscription>>,
+}
+
+impl SubscriptionRegistry {
+    /// Creates an empty registry that is not persisted.
+    #[cfg(test)]
+    pub fn in_memory() -> Self {
+        Self {
+            path: None,
+            max_per_chat: None,
+            subscriptions: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Loads the registry from a JSON file; a missing file starts an empty registry.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, SubscriptionError> {
+        let path = path.as_ref();
+        let subscriptions = match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|e| SubscriptionError::Parse(format!("{}: {e}", path.display())))?,
+            Err(e) if e.kind() == ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(SubscriptionError::Io(format!("{}: {e}", path.display()))),
+        };
+
+        Ok(Self {
+            path: Some(path.to_path_buf()),
+            max_per_chat: None,
+            subscriptions: RwLock::new(subscriptions),
+        })
+    }
+
+    /// Limits the number of subscriptions a chat can hold; `0` is unlimited.
+    ///
+    /// Existing subscriptions above the limit are kept, but the chat cannot add
+    /// new ones until it is below the limit.
+    pub fn with_max_per_chat(mut self, max_per_chat: usize) -> Self {
+        self.max_per_chat = (max_per_chat > 0).then_some(max_per_chat);
+        self
+    }
+
+    /// Returns the number of subscriptions across all chats.
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Adds a subscription, replacing the chat's existing threshold for the same target.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the subscription is new, `false` if an existing one was updated.
+    /// Fails with [`SubscriptionError::QuotaExceeded`] if a new subscription
+    /// would exceed the per-chat limit.
+    pub fn subscribe(&self, subscription: Subscription) -> Result<bool, SubscriptionError> {
+        let mut subscriptions = self.write();
+        if let Some(max) = self.max_per_chat {
+            let chat = subscriptions
+                .iter()
+                .filter(|s| s.chat_id == subscription.chat_id);
+            let (count, exists) = chat.fold((0, false), |(count, exists), s| {
+                (count + 1, exists || s.target == subscription.target)
+            });
+            if !exists && count >= max {
+                return Err(SubscriptionError::QuotaExceeded(max));
+            }
+        }
+        let existing = subscriptions
+            .iter_mut()
+            .find(|s| s.chat_id == subscription.chat_id && s.target == subscription.target);
+        let added = match existing {
+            Some(existing) => {
+                existing.min_usd = subscription.min_usd;
+                false
+            }
+            None => {
+                subscriptions.push(subscription);
+                true
+            }
+        };
+        self.save(&subscriptions)?;
+        Ok(added)
+    }
+
+    /// Removes a chat's subscription by its 1-based position in [`Self::for_chat`].
+    pub fn unsubscribe(
+        &self,
+        chat_id: i64,
+        position: usize,
+    ) -> Result<Option<Subscription>, SubscriptionError> {
+        let mut subscriptions = self.write();
+        let Some(index) = subscriptions
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.chat_id == chat_id)
+            .nth(position.wrapping_sub(1))
+            .map(|(index, _)| index)
+        else {
+            return Ok(None);
+        };
+        let removed = subscriptions.remove(index);
+        self.save(&subscriptions)?;
+        Ok(Some(removed))
+    }
+
+    /// Removes all subscriptions of a chat, returning how many were removed.
+    pub fn unsubscribe_all(&self, chat_id: i64) -> Result<usize, SubscriptionError> {
+        let mut subscriptions = self.write();
+        let before = subscriptions.len();
+        subscriptions.retain(|s| s.chat_id != chat_id);
+        let removed = before - subscriptions.len();
+        if removed > 0 {
+            self.save(&subscriptions)?;
+        }
+        Ok(removed)
+    }
+
+    /// Returns a chat's subscriptions in the order they were added.
+    pub fn for_chat(&self, chat_id: i64) -> Vec<Subscription> {
+        self.read()
+            .iter()
+            .filter(|s| s.chat_id == chat_id)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the chats with at least one subscription matching the event.
+    pub fn matching_chats(&self, event: &SwapEvent) -> BTreeSet<i64> {
+        self.read()
+            .iter()
+            .filter(|s| s.matches(event))
+            .map(|s| s.chat_id)
+            .collect()
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, Vec<Subscription>> {
+        self.subscriptions
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, Vec<Subscription>> {
+        self.subscriptions
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Writes the registry atomically (temporary file, then rename).
+    fn save(&self, subscriptions: &[Subscription]) -> Result<(), SubscriptionError> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        let json = serde_json::to_string_pretty(subscriptions)
+            .map_err(|e| SubscriptionError::Io(e.to_string()))?;
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, json)
+            .and_then(|_| fs::rename(&tmp, path))
+            .map_err(|e| SubscriptionError::Io(format!("{}: {e}", path.display())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crate::subscriptions::Target};
+
+    const MINT: &str = "So11111111111111111111111111111111111111112";
+
+    fn subscription(chat_id: i64, payload: &str) -> Subscription {
+        Subscription::from_payload(chat_id, payload).unwrap()
+    }
+
+    #[test]
+    fn test_subscribe_updates_and_unsubscribes() {
+        let registry = SubscriptionRegistry::in_memory();
+        assert!(registry
+            .subscribe(subscription(1, &format!("m_{MINT}")))
+            .unwrap());
+        assert!(!registry
+            .subscribe(subscription(1, &format!("m_{MINT}_50")))
+            .unwrap());
+        assert!(registry
+            .subscribe(subscription(1, &format!("w_{MINT}")))
+            .unwrap());
+        assert!(registry
+            .subscribe(subscription(2, &format!("m_{MINT}")))
+            .unwrap());
+
+        let chat = registry.for_chat(1);
+        assert_eq!(chat.len(), 2);
+        assert_eq!(chat[0].min_usd, Some(50.0));
+
+        let removed = registry.unsubscribe(1, 2).unwrap().unwrap();
+        assert_eq!(removed.target, Target::Wallet(MINT.to_string()));
+        assert!(registry.unsubscribe(1, 0).unwrap().is_none());
+        assert_eq!(registry.unsubscribe_all(1).unwrap(), 1);
+        assert_eq!(registry.len(), 1);
+    }
+
+    #[test]
+    fn test_max_per_chat() {
+        let registry = SubscriptionRegistry::in_memory().with_max_per_chat(1);
+        registry
+            .subscribe(subscription(1, &format!("m_{MINT}")))
+            .unwrap();
+        // Updating the existing subscription is not limited
+        assert!(!registry
+            .subscribe(subscription(1, &format!("m_{MINT}_10")))
+            .unwrap());
+        assert!(matches!(
+            registry.subscribe(subscription(1, &format!("w_{MINT}"))),
+            Err(SubscriptionError::QuotaExceeded(1))
+        ));
+        // Other chats have their own limit
+        assert!(registry
+            .subscribe(subscription(2, &format!("w_{MINT}")))
+            .unwrap());
+    }
+
+    #[test]
+    fn test_persists_across_loads() {
+        let dir = std::env::temp_dir().join(format!(
+            "raydium-alerts-subscriptions-{}",
+            std::process::id()
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("subscriptions.json");
+        let _ = fs::remove_file(&path);
+
+        let registry = SubscriptionRegistry::load(&path).unwrap();
+        registry
+            .subscribe(subscription(42, &format!("m_{MINT}_1000")))
+            .unwrap();
+
+        let reloaded = SubscriptionRegistry::load(&path).unwrap();
+        assert_eq!(reloaded.for_chat(42), registry.for_chat(42));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,465 @@
+//! Telegram Bot API client and subscription commands.
+//!
+//! The bot long-polls `getUpdates` and understands:
+//!
+//! - `/start <payload>` - subscribe (sent by deep links `https://t.me/<bot>?start=<payload>`)
+//! - `/list` - list the chat's subscriptions
+//! - `/unsubscribe <n>` - remove the n-th subscription
+//! - `/stop` - remove all of the chat's subscriptions
+//!
+//! With a [`StatsStore`], the bot also answers inline queries (`@bot <mint or
+//! symbol>`) with the matching tokens' price, 24h volume, and latest alerts.
+//! Inline mode must be enabled for the bot with @BotFather (`/setinline`).
+
+use {
+    super::{Subscription, SubscriptionRegistry},
+    crate::{server::unix_now, stats::StatsStore},
+    serde::{de::DeserializeOwned, Deserialize},
+    serde_json::{json, Value},
+    std::{fmt, sync::Arc, time::Duration},
+};
+
+/// Long-polling timeout for `getUpdates`, in seconds.
+const POLL_TIMEOUT_SECS: u64 = 30;
+
+/// Delay before polling again after a failed `getUpdates` call.
+const POLL_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Maximum number of tokens returned for an inline query.
+const INLINE_RESULTS: usize = 10;
+
+/// Seconds Telegram may cache inline query results.
+const INLINE_CACHE_SECS: u64 = 10;
+
+/// Bot API error code returned when the user blocked the bot or left the chat.
+const FORBIDDEN: u16 = 403;
+
+/// Errors returned by the Telegram Bot API client.
+#[derive(Debug)]
+pub enum TelegramError {
+    /// The request failed or the response could not be read
+    Http(String),
+    /// The Bot API rejected the request
+    Api {
+        /// Bot API error code (e.g., 403, 429)
+        code: Option<u16>,
+        /// Error description
+        description: String,
+        /// Seconds to wait before retrying (flood control)
+        retry_after: Option<u64>,
+    },
+}
+
+impl TelegramError {
+    /// Returns `true` if the chat can no longer be messaged (bot blocked, chat gone).
+    pub fn is_forbidden(&self) -> bool {
+        matches!(
+            self,
+            Self::Api {
+                code: Some(FORBIDDEN),
+                ..
+            }
+        )
+    }
+
+    /// Returns the flood-control delay requested by the Bot API, if any.
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Self::Api {
+                retry_after: Some(secs),
+                ..
+            } => Some(Duration::from_secs(*secs)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TelegramError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Http(e) => write!(f, "Telegram request failed: {e}"),
+            Self::Api {
+                code, description, ..
+            } => match code {
+                Some(code) => write!(f, "Telegram API error {code}: {description}"),
+                None => write!(f, "Telegram API error: {description}"),
+            },
+        }
+    }
+}
+
+impl std::error::Error for TelegramError {}
+
+/// Bot API response envelope.
+#[derive(Deserialize)]
+struct ApiResponse<T> {
+    ok: bool,
+    result: Option<T>,
+    description: Option<String>,
+    error_code: Option<u16>,
+    parameters: Option<ResponseParameters>,
+}
+
+#[derive(Deserialize)]
+struct ResponseParameters {
+    retry_after: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct Update {
+    update_id: i64,
+    message: Option<Message>,
+    inline_query: Option<InlineQuery>,
+}
+
+#[derive(Deserialize)]
+struct Message {
+    chat: Chat,
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct InlineQuery {
+    id: String,
+    query: String,
+}
+
+#[derive(Deserialize)]
+struct Chat {
+    id: i64,
+}
+
+#[derive(Deserialize)]
+struct User {
+    username: Option<String>,
+}
+
+/// Minimal Telegram Bot API client.
+pub struct TelegramClient {
+    http: reqwest::Client,
+    /// `<api_url>/bot<token>`
+    base_url: String,
+}
+
+impl TelegramClient {
+    /// Creates a client for a bot.
+    ///
+    /// # Arguments
+    ///
+    /// * `api_url` - Bot API endpoint (e.g., `https://api.telegram.org`)
+    /// * `bot_token` - Bot token from @BotFather
+    pub fn new(api_url: &str, bot_token: &str) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(POLL_TIMEOUT_SECS + 10))
+            .build()
+            .unwrap_or_default();
+        Self {
+            http,
+            base_url: format!("{}/bot{bot_token}", api_url.trim_end_matches('/')),
+        }
+    }
+
+    /// Calls a Bot API method with a JSON body.
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        body: serde_json::Value,
+    ) -> Result<T, TelegramError> {
+        // Errors are stripped of the URL, which contains the bot token
+        let response: ApiResponse<T> = self
+            .http
+            .post(format!("{}/{method}", self.base_url))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| TelegramError::Http(e.without_url().to_string()))?
+            .json()
+            .await
+            .map_err(|e| TelegramError::Http(e.without_url().to_string()))?;
+
+        match response {
+            ApiResponse {
+                ok: true,
+                result: Some(result),
+                ..
+            } => Ok(result),
+            response => Err(TelegramError::Api {
+                code: response.error_code,
+                description: response.description.unwrap_or_default(),
+                retry_after: response.parameters.and_then(|p| p.retry_after),
+            }),
+        }
+    }
+
+    /// Returns the bot's username, used to build deep links.
+    pub async fn username(&self) -> Result<Option<String>, TelegramError> {
+        let me: User = self.call("getMe", json!({})).await?;
+        Ok(me.username)
+    }
+
+    /// Long-polls for updates after `offset`.
+    async fn get_updates(&self, offset: i64) -> Result<Vec<Update>, TelegramError> {
+        self.call(
+            "getUpdates",
+            json!({
+                "offset": offset,
+                "timeout": POLL_TIMEOUT_SECS,
+                "allowed_updates": ["message", "inline_query"],
+            }),
+        )
+        .await
+    }
+
+    /// Sends a plain-text message to a chat.
+    pub async fn send_message(&self, chat_id: i64, text: &str) -> Result<(), TelegramError> {
+        self.call::<Value>(
+            "sendMessage",
+            json!({
+                "chat_id": chat_id,
+                "text": text,
+                "disable_web_page_preview": true,
+            }),
+        )
+        .await
+        .map(|_| ())
+    }
+
+    /// Answers an inline query with a list of results.
+    async fn answer_inline_query(
+        &self,
+        query_id: &str,
+        results: Vec<Value>,
+    ) -> Result<(), TelegramError> {
+        self.call::<Value>(
+            "answerInlineQuery",
+            json!({
+                "inline_query_id": query_id,
+                "results": results,
+                "cache_time": INLINE_CACHE_SECS,
+            }),
+        )
+        .await
+        .map(|_| ())
+    }
+}
+
+/// Bot answering subscription commands.
+pub struct TelegramBot {
+    client: Arc<TelegramClient>,
+    registry: Arc<SubscriptionRegistry>,
+    /// Optional token statistics answering inline queries
+    stats: Option<Arc<StatsStore>>,
+}
+
+impl TelegramBot {
+    /// Creates a bot managing subscriptions in `registry`.
+    pub fn new(client: Arc<TelegramClient>, registry: Arc<SubscriptionRegistry>) -> Self {
+        Self {
+            client,
+            registry,
+            stats: None,
+        }
+    }
+
+    /// Answers inline queries with token statistics from `stats`.
+    pub fn with_stats(mut self, stats: Arc<StatsStore>) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    /// Spawns the task polling for commands.
+    pub fn spawn(self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            match self.client.username().await {
+                Ok(Some(username)) => log::info!(
+                    "Telegram bot @{username}: subscribe with https://t.me/{username}?start=m_<MINT>_<MIN_USD>"
+                ),
+                Ok(None) => {}
+                Err(e) => log::warn!("Failed to fetch Telegram bot info: {e}"),
+            }
+
+            let mut offset = 0;
+            loop {
+                let updates = match self.client.get_updates(offset).await {
+                    Ok(updates) => updates,
+                    Err(e) => {
+                        log::warn!("Failed to poll Telegram updates: {e}");
+                        tokio::time::sleep(e.retry_after().unwrap_or(POLL_RETRY_DELAY)).await;
+                        continue;
+                    }
+                };
+
+                for update in updates {
+                    offset = offset.max(update.update_id + 1);
+                    if let Some(query) = update.inline_query {
+                        let results = self
+                            .stats
+                            .as_deref()
+                            .map(|stats| inline_results(stats, &query.query))
+                            .unwrap_or_default();
+                        if let Err(e) = self.client.answer_inline_query(&query.id, results).await {
+                            log::warn!("Failed to answer Telegram inline query: {e}");
+                        }
+                        continue;
+                    }
+                    let Some(Message {
+                        chat,
+                        text: Some(text),
+                    }) = update.message
+                    else {
+                        continue;
+                    };
+                    let reply = handle_command(&self.registry, chat.id, &text);
+                    if let Err(e) = self.client.send_message(chat.id, &reply).await {
+                        log::warn!("Failed to reply to Telegram chat {}: {e}", chat.id);
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Builds inline query results (articles) for the tokens matching `query`.
+fn inline_results(stats: &StatsStore, query: &str) -> Vec<Value> {
+    let now = unix_now();
+    stats
+        .search(query, INLINE_RESULTS)
+        .into_iter()
+        .map(|token| {
+            json!({
+                "type": "article",
+                "id": token.mint,
+                "title": token.name(),
+                "description": token.summary(),
+                "input_message_content": { "message_text": token.format_text(now) },
+            })
+        })
+        .collect()
+}
+
+/// Usage text sent for unknown commands and bare `/start`.
+const HELP: &str = "Subscribe to swap alerts:\n\
+                    /start m_<MINT>[_<MIN_USD>] - swaps of a token\n\
+                    /start w_<WALLET>[_<MIN_USD>] - swaps made by a wallet\n\
+                    /list - your subscriptions\n\
+                    /unsubscribe <n> - remove a subscription\n\
+                    /stop - remove all subscriptions";
+
+/// Applies a chat command to the registry and returns the reply.
+fn handle_command(registry: &SubscriptionRegistry, chat_id: i64, text: &str) -> String {
+    let mut words = text.split_whitespace();
+    // Commands in groups may be addressed as `/command@bot`
+    let command = words
+        .next()
+        .unwrap_or_default()
+        .split('@')
+        .next()
+        .unwrap_or_default();
+    let argument = words.next();
+
+    let result = match (command, argument) {
+        ("/start", Some(payload)) => Subscription::from_payload(chat_id, payload).and_then(|sub| {
+            let reply = format!("✅ Subscribed to {sub}");
+            registry.subscribe(sub).map(|added| match added {
+                true => reply,
+                false => format!("{reply} (updated)"),
+            })
+        }),
+        ("/list", _) => {
+            let subscriptions = registry.for_chat(chat_id);
+            Ok(match subscriptions.is_empty() {
+                true => "No subscriptions.".to_string(),
+                false => subscriptions
+                    .iter()
+                    .enumerate()
+                    .map(|(i, sub)| format!("{}. {sub}", i + 1))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            })
+        }
+        ("/unsubscribe", Some(position)) => match position.parse() {
+            Ok(position) => registry
+                .unsubscribe(chat_id, position)
+                .map(|removed| match removed {
+                    Some(sub) => format!("Unsubscribed from {sub}"),
+                    None => format!("No subscription #{position}, see /list"),
+                }),
+            Err(_) => Ok(HELP.to_string()),
+        },
+        ("/stop", _) => registry
+            .unsubscribe_all(chat_id)
+            .map(|removed| format!("Removed {removed} subscription(s)")),
+        _ => Ok(HELP.to_string()),
+    };
+
+    result.unwrap_or_else(|e| {
+        log::warn!("Subscription command from chat {chat_id} failed: {e}");
+        format!("❌ {e}")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINT: &str = "So11111111111111111111111111111111111111112";
+
+    #[test]
+    fn test_handle_commands() {
+        let registry = SubscriptionRegistry::in_memory();
+
+        let reply = handle_command(&registry, 1, &format!("/start m_{MINT}_250"));
+        assert_eq!(reply, format!("✅ Subscribed to mint {MINT} (min $250)"));
+        let reply = handle_command(&registry, 1, &format!("/start@alert_bot w_{MINT}"));
+        assert!(reply.starts_with("✅"));
+        assert!(handle_command(&registry, 1, "/start m_bad").starts_with("❌"));
+        assert_eq!(handle_command(&registry, 1, "/start"), HELP);
+
+        let list = handle_command(&registry, 1, "/list");
+        assert_eq!(list.lines().count(), 2);
+        assert!(list.starts_with(&format!("1. mint {MINT}")));
+
+        assert!(handle_command(&registry, 1, "/unsubscribe 2").starts_with("Unsubscribed"));
+        assert!(handle_command(&registry, 1, "/unsubscribe 5").starts_with("No subscription"));
+        assert_eq!(
+            handle_command(&registry, 1, "/stop"),
+            "Removed 1 subscription(s)"
+        );
+        assert_eq!(handle_command(&registry, 1, "/list"), "No subscriptions.");
+    }
+
+    #[test]
+    fn test_inline_results() {
+        let stats = StatsStore::default();
+        let event = crate::output::SwapEvent::builder()
+            .protocol(crate::output::Protocol::Cpmm)
+            .signature("sig")
+            .pool("pool")
+            .input_token(crate::output::TokenInfo::new(MINT, 1).with_symbol("SOL"))
+            .build();
+        stats.record(&event);
+
+        let results = inline_results(&stats, "sol");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["id"], MINT);
+        assert_eq!(results[0]["title"], "SOL");
+        assert!(inline_results(&stats, "bonk").is_empty());
+    }
+
+    #[test]
+    fn test_error_classification() {
+        let forbidden = TelegramError::Api {
+            code: Some(403),
+            description: "Forbidden: bot was blocked by the user".into(),
+            retry_after: None,
+        };
+        assert!(forbidden.is_forbidden());
+        let flood = TelegramError::Api {
+            code: Some(429),
+            description: "Too Many Requests".into(),
+            retry_after: Some(3),
+        };
+        assert!(!flood.is_forbidden());
+        assert_eq!(flood.retry_after(), Some(Duration::from_secs(3)));
+    }
+}
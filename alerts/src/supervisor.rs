@@ -0,0 +1,429 @@
+//! Supervision of the block subscription.
+//!
+//! [`SupervisedDatasource`] runs a fresh inner datasource (the block
+//! subscription) per connection and forwards its updates to the pipeline.
+//! The connection is replaced, after an exponential backoff, when it fails,
+//! when its updates stop, or when no block arrived for `SUBSCRIBE_STALL_SECS`.
+//!
+//! The slot of every forwarded transaction is tracked. When the first block
+//! after a reconnect skips slots, the skipped range is logged as a gap and
+//! counted in the `block_subscribe_gap_slots` metric; with
+//! `GAP_BACKFILL_MAX_SLOTS` and an HTTP RPC endpoint, gaps up to that size are
+//! fetched block by block and fed into the pipeline. On a stall, the last
+//! processed slot is compared against the chain tip, which tells a quiet
+//! filter from a dead connection.
+//!
+//! The time of the first transaction of each live block is compared with the
+//! host clock ([`clock::drift`]) and reported as the `clock_drift_secs` gauge.
+
+use {
+    crate::{chain, clock},
+    async_trait::async_trait,
+    carbon_core::{
+        datasource::{Datasource, DatasourceId, Update, UpdateType},
+        error::CarbonResult,
+        metrics::MetricsCollection,
+    },
+    carbon_rpc_block_crawler_datasource::RpcBlockCrawler,
+    solana_client::{nonblocking::rpc_client::RpcClient, rpc_config::RpcBlockConfig},
+    solana_transaction_status_client_types::UiTransactionEncoding,
+    std::{env, sync::Arc, time::Duration},
+    tokio::sync::mpsc,
+    tokio_util::sync::CancellationToken,
+};
+
+/// Default time without blocks after which the subscription is replaced.
+const DEFAULT_STALL_SECS: u64 = 60;
+
+/// Default maximum delay between reconnection attempts.
+const DEFAULT_BACKOFF_MAX_SECS: u64 = 60;
+
+/// Delay before the first reconnection attempt.
+const BACKOFF_MIN: Duration = Duration::from_secs(1);
+
+/// Updates buffered between the inner datasource and the pipeline.
+const FORWARD_CAPACITY: usize = 1_000;
+
+/// Configuration for the block subscription supervisor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SupervisorConfig {
+    /// Time without blocks after which the subscription is replaced
+    pub stall: Duration,
+    /// Maximum delay between reconnection attempts
+    pub backoff_max: Duration,
+    /// Largest gap fetched block by block (`None` only logs gaps)
+    pub backfill_max_slots: Option<u64>,
+    /// HTTP RPC endpoint for the chain tip and backfill
+    pub rpc_http_url: Option<String>,
+}
+
+impl SupervisorConfig {
+    /// Creates supervisor configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `SUBSCRIBE_STALL_SECS` - Optional: Seconds without blocks after which the
+    ///   subscription is replaced (default: 60)
+    /// - `SUBSCRIBE_BACKOFF_MAX_SECS` - Optional: Maximum seconds between reconnection
+    ///   attempts, doubling from 1s (default: 60)
+    /// - `GAP_BACKFILL_MAX_SLOTS` - Optional: Largest slot gap fetched block by block
+    ///   after a reconnect (requires `RPC_HTTP_URL`; default: gaps are only logged)
+    pub fn from_env() -> Self {
+        let secs = |name: &str, default: u64| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .filter(|&n| n > 0)
+                .unwrap_or(default)
+        };
+        let rpc_http_url = chain::profile().rpc_http_url.clone();
+        let backfill_max_slots = env::var("GAP_BACKFILL_MAX_SLOTS")
+            .ok()
+            .and_then(|v| v.trim().parse().ok())
+            .filter(|&n: &u64| n > 0);
+        if backfill_max_slots.is_some() && rpc_http_url.is_none() {
+            log::warn!("GAP_BACKFILL_MAX_SLOTS requires RPC_HTTP_URL, gaps are only logged");
+        }
+
+        Self {
+            stall: Duration::from_secs(secs("SUBSCRIBE_STALL_SECS", DEFAULT_STALL_SECS)),
+            backoff_max: Duration::from_secs(secs(
+                "SUBSCRIBE_BACKOFF_MAX_SECS",
+                DEFAULT_BACKOFF_MAX_SECS,
+            )),
+            backfill_max_slots: backfill_max_slots.filter(|_| rpc_http_url.is_some()),
+            rpc_http_url,
+        }
+    }
+}
+
+/// Slots possibly missed while the subscription was down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotGap {
+    /// First missed slot
+    pub from: u64,
+    /// Last missed slot
+    pub to: u64,
+}
+
+impl SlotGap {
+    /// Returns the number of slots in the gap.
+    pub fn slots(&self) -> u64 {
+        self.to - self.from + 1
+    }
+}
+
+/// Last processed slot across reconnections.
+#[derive(Debug, Default)]
+struct SlotTracker {
+    last: Option<u64>,
+    /// Whether a reconnect happened since the last update
+    resumed: bool,
+}
+
+impl SlotTracker {
+    /// Marks the start of a new connection.
+    fn reconnected(&mut self) {
+        self.resumed = true;
+    }
+
+    /// Records an update's slot; returns the gap since the last processed
+    /// slot if this is the first update after a reconnect.
+    fn observe(&mut self, slot: u64) -> Option<SlotGap> {
+        let gap = match self.last {
+            Some(last) if std::mem::take(&mut self.resumed) && slot > last + 1 => Some(SlotGap {
+                from: last + 1,
+                to: slot - 1,
+            }),
+            _ => None,
+        };
+        self.last = Some(self.last.map_or(slot, |last| last.max(slot)));
+        gap
+    }
+}
+
+/// Exponential reconnection delay, reset after a healthy connection.
+#[derive(Debug)]
+struct Backoff {
+    next: Duration,
+    max: Duration,
+}
+
+impl Backoff {
+    fn new(max: Duration) -> Self {
+        Self {
+            next: BACKOFF_MIN.min(max),
+            max,
+        }
+    }
+
+    /// Returns the delay before the next attempt and doubles it.
+    fn delay(&mut self) -> Duration {
+        let delay = self.next;
+        self.next = (self.next * 2).min(self.max);
+        delay
+    }
+
+    fn reset(&mut self) {
+        self.next = BACKOFF_MIN.min(self.max);
+    }
+}
+
+/// Why a connection was replaced.
+enum Outcome {
+    Cancelled,
+    Failed(String),
+    Closed,
+    Stalled,
+}
+
+/// Datasource that reconnects its inner datasource and detects slot gaps.
+pub struct SupervisedDatasource<D> {
+    connect: Box<dyn Fn() -> D + Send + Sync>,
+    config: SupervisorConfig,
+}
+
+impl<D: Datasource + 'static> SupervisedDatasource<D> {
+    /// Creates a supervisor connecting with `connect` for each connection.
+    pub fn new(config: SupervisorConfig, connect: impl Fn() -> D + Send + Sync + 'static) -> Self {
+        Self {
+            connect: Box::new(connect),
+            config,
+        }
+    }
+
+    /// Runs one connection until it is cancelled, fails, closes, or stalls.
+    async fn run_connection(
+        &self,
+        id: &DatasourceId,
+        sender: &mpsc::Sender<(Update, DatasourceId)>,
+        cancellation_token: &CancellationToken,
+        metrics: &Arc<MetricsCollection>,
+        tracker: &mut SlotTracker,
+        backoff: &mut Backoff,
+    ) -> Outcome {
+        let (tx, mut rx) = mpsc::channel(FORWARD_CAPACITY);
+        let connection = cancellation_token.child_token();
+        let mut task = {
+            let (datasource, id, token, metrics) = (
+                (self.connect)(),
+                id.clone(),
+                connection.clone(),
+                metrics.clone(),
+            );
+            tokio::spawn(async move { datasource.consume(id, tx, token, metrics).await })
+        };
+        let mut task_done = false;
+
+        let outcome = loop {
+            tokio::select! {
+                _ = cancellation_token.cancelled() => break Outcome::Cancelled,
+                joined = &mut task, if !task_done => {
+                    task_done = true;
+                    match joined {
+                        Ok(Ok(())) => {}
+                        Ok(Err(e)) => break Outcome::Failed(e.to_string()),
+                        Err(e) => break Outcome::Failed(e.to_string()),
+                    }
+                }
+                update = tokio::time::timeout(self.config.stall, rx.recv()) => match update {
+                    Ok(Some((update, _))) => {
+                        backoff.reset();
+                        if let Update::Transaction(ref transaction) = update {
+                            if tracker.last != Some(transaction.slot) {
+                                if let Some(block_time) = transaction.block_time {
+                                    let drift = clock::drift().observe(block_time);
+                                    let _ = metrics
+                                        .update_gauge("clock_drift_secs", drift as f64)
+                                        .await;
+                                }
+                            }
+                            if let Some(gap) = tracker.observe(transaction.slot) {
+                                self.on_gap(gap, id, sender, cancellation_token, metrics).await;
+                            }
+                        }
+                        if sender.send((update, id.clone())).await.is_err() {
+                            break Outcome::Cancelled;
+                        }
+                    }
+                    Ok(None) => break Outcome::Closed,
+                    Err(_) => break Outcome::Stalled,
+                },
+            }
+        };
+
+        connection.cancel();
+        task.abort();
+        outcome
+    }
+
+    /// Logs and counts a gap, and backfills it if configured.
+    async fn on_gap(
+        &self,
+        gap: SlotGap,
+        id: &DatasourceId,
+        sender: &mpsc::Sender<(Update, DatasourceId)>,
+        cancellation_token: &CancellationToken,
+        metrics: &Arc<MetricsCollection>,
+    ) {
+        let _ = metrics
+            .increment_counter("block_subscribe_gap_slots", gap.slots())
+            .await;
+        let (Some(max), Some(url)) = (self.config.backfill_max_slots, &self.config.rpc_http_url)
+        else {
+            log::warn!(
+                "Block subscription gap: slots {}..={} ({} slots) possibly missed",
+                gap.from,
+                gap.to,
+                gap.slots()
+            );
+            return;
+        };
+        if gap.slots() > max {
+            log::warn!(
+                "Block subscription gap: slots {}..={} ({} slots) possibly missed, \
+                 larger than GAP_BACKFILL_MAX_SLOTS={max}",
+                gap.from,
+                gap.to,
+                gap.slots()
+            );
+            return;
+        }
+
+        log::warn!(
+            "Block subscription gap: backfilling slots {}..={} ({} slots)",
+            gap.from,
+            gap.to,
+            gap.slots()
+        );
+        let crawler = RpcBlockCrawler::new(
+            url.clone(),
+            gap.from,
+            Some(gap.to),
+            None,
+            RpcBlockConfig {
+                encoding: Some(UiTransactionEncoding::Base64),
+                max_supported_transaction_version: Some(0),
+                ..RpcBlockConfig::default()
+            },
+            None,
+            None,
+        );
+        let (id, sender, token, metrics) = (
+            id.clone(),
+            sender.clone(),
+            cancellation_token.child_token(),
+            metrics.clone(),
+        );
+        tokio::spawn(async move {
+            if let Err(e) = crawler.consume(id, sender, token, metrics).await {
+                log::error!(
+                    "Gap backfill of slots {}..={} failed: {e}",
+                    gap.from,
+                    gap.to
+                );
+            }
+        });
+    }
+
+    /// Logs how far the last processed slot is behind the chain tip.
+    async fn log_tip_lag(&self, tracker: &SlotTracker) {
+        let (Some(url), Some(last)) = (&self.config.rpc_http_url, tracker.last) else {
+            return;
+        };
+        match RpcClient::new(url.clone()).get_slot().await {
+            Ok(tip) => log::warn!(
+                "Last processed slot {last} is {} slots behind the chain tip {tip}",
+                tip.saturating_sub(last)
+            ),
+            Err(e) => log::debug!("Failed to fetch the chain tip: {e}"),
+        }
+    }
+}
+
+#[async_trait]
+impl<D: Datasource + 'static> Datasource for SupervisedDatasource<D> {
+    async fn consume(
+        &self,
+        id: DatasourceId,
+        sender: mpsc::Sender<(Update, DatasourceId)>,
+        cancellation_token: CancellationToken,
+        metrics: Arc<MetricsCollection>,
+    ) -> CarbonResult<()> {
+        let mut tracker = SlotTracker::default();
+        let mut backoff = Backoff::new(self.config.backoff_max);
+        loop {
+            let outcome = self
+                .run_connection(
+                    &id,
+                    &sender,
+                    &cancellation_token,
+                    &metrics,
+                    &mut tracker,
+                    &mut backoff,
+                )
+                .await;
+            match outcome {
+                Outcome::Cancelled => return Ok(()),
+                Outcome::Failed(e) => log::error!("Block subscription failed: {e}"),
+                Outcome::Closed => log::warn!("Block subscription closed"),
+                Outcome::Stalled => {
+                    log::warn!(
+                        "No blocks for {:?}, replacing the block subscription",
+                        self.config.stall
+                    );
+                    self.log_tip_lag(&tracker).await;
+                }
+            }
+
+            let delay = backoff.delay();
+            log::info!("Reconnecting the block subscription in {delay:?}");
+            let _ = metrics
+                .increment_counter("block_subscribe_reconnects", 1)
+                .await;
+            tokio::select! {
+                _ = cancellation_token.cancelled() => return Ok(()),
+                _ = tokio::time::sleep(delay) => {}
+            }
+            tracker.reconnected();
+        }
+    }
+
+    fn update_types(&self) -> Vec<UpdateType> {
+        (self.connect)().update_types()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gap_after_reconnect() {
+        let mut tracker = SlotTracker::default();
+        assert_eq!(tracker.observe(100), None);
+        // Skipped slots on a live connection are not gaps
+        assert_eq!(tracker.observe(105), None);
+
+        tracker.reconnected();
+        assert_eq!(tracker.observe(120), Some(SlotGap { from: 106, to: 119 }));
+        assert_eq!(SlotGap { from: 106, to: 119 }.slots(), 14);
+        assert_eq!(tracker.observe(125), None);
+
+        // Resuming at the next slot, or with an older block, is no gap
+        tracker.reconnected();
+        assert_eq!(tracker.observe(126), None);
+        tracker.reconnected();
+        assert_eq!(tracker.observe(90), None);
+        assert_eq!(tracker.last, Some(126));
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let mut backoff = Backoff::new(Duration::from_secs(5));
+        let delays: Vec<_> = (0..5).map(|_| backoff.delay().as_secs()).collect();
+        assert_eq!(delays, [1, 2, 4, 5, 5]);
+        backoff.reset();
+        assert_eq!(backoff.delay(), Duration::from_secs(1));
+    }
+}
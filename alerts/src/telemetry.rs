@@ -0,0 +1,217 @@
+//! Log output and trace export.
+//!
+//! This module provides:
+//! - [`TelemetryConfig`] - Log format, filter, and OTLP export settings
+//! - [`Telemetry`] - Installs the `tracing` subscriber and flushes exported
+//!   spans on shutdown
+//!
+//! Records are collected by `tracing`. The `log` records of the application
+//! and its dependencies are forwarded to it, so every record carries the
+//! fields of the spans it is emitted in: a `transaction` span (protocol,
+//! slot, signature) around the processing of each instruction, and a
+//! `webhook` span (webhook, slot, signature, or the slot range of a batch)
+//! around each delivery. A webhook failure can thus be traced back to the
+//! block and transaction it originates from.
+//!
+//! Records are written to stderr as text or as JSON lines. With the `otlp`
+//! feature, spans are also exported to an OpenTelemetry collector over
+//! OTLP/gRPC.
+
+use {
+    std::env,
+    tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer},
+};
+
+/// Filter applied when `RUST_LOG` is not set.
+const DEFAULT_FILTER: &str = "info";
+
+/// Service name reported to the OTLP collector when `OTEL_SERVICE_NAME` is not set.
+const DEFAULT_SERVICE_NAME: &str = "raydium-alerts";
+
+/// How log records are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    /// Human-readable lines, prefixed with the fields of the enclosing spans
+    #[default]
+    Text,
+    /// One JSON object per record, with the enclosing spans' fields
+    Json,
+}
+
+impl LogFormat {
+    /// Parses a log format name (case-insensitive).
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "text" => Some(Self::Text),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Configuration for log output and trace export.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TelemetryConfig {
+    /// Log record format
+    pub format: LogFormat,
+    /// OTLP/gRPC collector endpoint spans are exported to
+    pub otlp_endpoint: Option<String>,
+    /// Service name reported with exported spans
+    pub service_name: String,
+}
+
+impl TelemetryConfig {
+    /// Creates telemetry configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `LOG_FORMAT` - `text` or `json` (default: text)
+    /// - `RUST_LOG` - Log filter directives, read when the subscriber is
+    ///   installed (default: info)
+    /// - `OTEL_EXPORTER_OTLP_ENDPOINT` - Optional: OTLP/gRPC collector spans
+    ///   are exported to (requires the `otlp` feature)
+    /// - `OTEL_SERVICE_NAME` - Service name of exported spans (default: raydium-alerts)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `LOG_FORMAT` is not a known format. Logging is not
+    /// set up yet at this point, so the error is not logged.
+    pub fn from_env() -> Result<Self, String> {
+        let format = match env::var("LOG_FORMAT") {
+            Ok(value) if !value.trim().is_empty() => LogFormat::parse(&value).ok_or_else(|| {
+                format!("Invalid LOG_FORMAT '{value}', expected 'text' or 'json'")
+            })?,
+            _ => LogFormat::default(),
+        };
+        let otlp_endpoint = env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+            .ok()
+            .map(|endpoint| endpoint.trim().to_string())
+            .filter(|endpoint| !endpoint.is_empty());
+        let service_name = env::var("OTEL_SERVICE_NAME")
+            .ok()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| DEFAULT_SERVICE_NAME.to_string());
+
+        Ok(Self {
+            format,
+            otlp_endpoint,
+            service_name,
+        })
+    }
+}
+
+/// Installed `tracing` subscriber.
+pub struct Telemetry {
+    #[cfg(feature = "otlp")]
+    provider: Option<opentelemetry_sdk::trace::TracerProvider>,
+}
+
+impl Telemetry {
+    /// Installs the global `tracing` subscriber and forwards `log` records to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a subscriber is already installed or the OTLP
+    /// exporter cannot be built.
+    pub fn init(config: &TelemetryConfig) -> Result<Self, String> {
+        let filter =
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+        let output = match config.format {
+            LogFormat::Text => tracing_subscriber::fmt::layer()
+                .with_writer(std::io::stderr)
+                .boxed(),
+            LogFormat::Json => tracing_subscriber::fmt::layer()
+                .json()
+                .with_current_span(true)
+                .with_span_list(true)
+                .with_writer(std::io::stderr)
+                .boxed(),
+        };
+        let subscriber = tracing_subscriber::registry().with(filter).with(output);
+
+        #[cfg(feature = "otlp")]
+        {
+            let provider = config
+                .otlp_endpoint
+                .as_deref()
+                .map(|endpoint| otlp::provider(endpoint, &config.service_name))
+                .transpose()?;
+            let export = provider.as_ref().map(|provider| {
+                use opentelemetry::trace::TracerProvider as _;
+                tracing_opentelemetry::layer().with_tracer(provider.tracer(DEFAULT_SERVICE_NAME))
+            });
+            subscriber
+                .with(export)
+                .try_init()
+                .map_err(|e| format!("Failed to install log subscriber: {e}"))?;
+            if let Some(ref endpoint) = config.otlp_endpoint {
+                log::info!("Exporting spans to {endpoint} as '{}'", config.service_name);
+            }
+            Ok(Self { provider })
+        }
+
+        #[cfg(not(feature = "otlp"))]
+        {
+            subscriber
+                .try_init()
+                .map_err(|e| format!("Failed to install log subscriber: {e}"))?;
+            if config.otlp_endpoint.is_some() {
+                log::warn!(
+                    "OTEL_EXPORTER_OTLP_ENDPOINT is set, but span export requires building with \
+                     `--features otlp`; spans of '{}' are not exported",
+                    config.service_name
+                );
+            }
+            Ok(Self {})
+        }
+    }
+
+    /// Flushes the spans not yet exported.
+    pub fn shutdown(self) {
+        #[cfg(feature = "otlp")]
+        if let Some(provider) = self.provider {
+            if let Err(e) = provider.shutdown() {
+                log::warn!("Failed to flush exported spans: {e}");
+            }
+        }
+    }
+}
+
+/// OTLP span export.
+#[cfg(feature = "otlp")]
+mod otlp {
+    use {
+        opentelemetry::KeyValue,
+        opentelemetry_otlp::{SpanExporter, WithExportConfig},
+        opentelemetry_sdk::{runtime, trace::TracerProvider, Resource},
+    };
+
+    /// Builds a tracer provider exporting spans in batches to `endpoint`.
+    pub fn provider(endpoint: &str, service_name: &str) -> Result<TracerProvider, String> {
+        let exporter = SpanExporter::builder()
+            .with_tonic()
+            .with_endpoint(endpoint)
+            .build()
+            .map_err(|e| format!("Failed to build OTLP exporter for {endpoint}: {e}"))?;
+        Ok(TracerProvider::builder()
+            .with_batch_exporter(exporter, runtime::Tokio)
+            .with_resource(Resource::new([KeyValue::new(
+                "service.name",
+                service_name.to_string(),
+            )]))
+            .build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_format() {
+        assert_eq!(LogFormat::parse("text"), Some(LogFormat::Text));
+        assert_eq!(LogFormat::parse(" JSON "), Some(LogFormat::Json));
+        assert_eq!(LogFormat::parse("yaml"), None);
+    }
+}
@@ -0,0 +1,372 @@
+//! Per-token and per-pool swap alert throttling.
+//!
+//! This module provides:
+//! - [`ThrottleConfig`] - Swap alert limits per token and per pool
+//! - [`AlertThrottle`] - Counts swap alerts per token and pool, withholding
+//!   those over a limit
+//!
+//! A hot token can trade thousands of times a minute and drown every other
+//! alert. Processors ask the throttle before alerting on a swap (log, rules,
+//! subscriptions, webhooks). A swap over the limit of its token (the non-base
+//! token of the pair) or its pool is still stored, streamed, and counted in
+//! statistics and volume summaries, but it is only reported in a
+//! `ThrottleDigest` event, emitted for the token or pool once its window is
+//! over. Events other than swaps are never throttled.
+
+use {
+    crate::{
+        clock,
+        output::{
+            EventType, OutputFormat, Protocol, SwapEvent, ThrottleDigest, ThrottleScope, TradeSide,
+            WebhookNotifier,
+        },
+        server::unix_now,
+    },
+    std::{
+        collections::HashMap,
+        env,
+        sync::{Arc, Mutex, PoisonError},
+        time::{Duration, Instant},
+    },
+};
+
+/// Length of a throttle window.
+pub const THROTTLE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Configuration for swap alert throttling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThrottleConfig {
+    /// Most swap alerts per token per window
+    pub per_token: Option<u32>,
+    /// Most swap alerts per pool per window
+    pub per_pool: Option<u32>,
+}
+
+impl ThrottleConfig {
+    /// Creates throttle configuration from environment variables.
+    ///
+    /// # Environment Variables
+    ///
+    /// - `THROTTLE_TOKEN_PER_MIN` - Optional: Most swap alerts per token per minute
+    /// - `THROTTLE_POOL_PER_MIN` - Optional: Most swap alerts per pool per minute
+    ///
+    /// # Returns
+    ///
+    /// `Some(ThrottleConfig)` if either variable is set to a positive number,
+    /// `None` otherwise.
+    pub fn from_env() -> Option<Self> {
+        let config = Self {
+            per_token: parse_limit("THROTTLE_TOKEN_PER_MIN"),
+            per_pool: parse_limit("THROTTLE_POOL_PER_MIN"),
+        };
+        (config.per_token.is_some() || config.per_pool.is_some()).then_some(config)
+    }
+}
+
+/// Parses a positive alert limit from an environment variable.
+fn parse_limit(name: &str) -> Option<u32> {
+    let value = env::var(name).ok()?;
+    match value.trim().parse::<u32>() {
+        Ok(limit) if limit > 0 => Some(limit),
+        _ => {
+            log::warn!("Invalid {name} '{value}', expected a positive number");
+            None
+        }
+    }
+}
+
+/// Swap alerts of a token or pool in its current window.
+struct Window {
+    started: Instant,
+    sent: u32,
+    withheld: u64,
+    buys: u64,
+    sells: u64,
+    volume_usd: f64,
+    symbol: Option<String>,
+    /// Protocol, pool, and slot of the last withheld swap
+    last: Option<(Protocol, String, u64)>,
+}
+
+impl Window {
+    fn new(started: Instant) -> Self {
+        Self {
+            started,
+            sent: 0,
+            withheld: 0,
+            buys: 0,
+            sells: 0,
+            volume_usd: 0.0,
+            symbol: None,
+            last: None,
+        }
+    }
+
+    fn expired(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.started) >= THROTTLE_WINDOW
+    }
+
+    fn withhold(&mut self, event: &SwapEvent, symbol: Option<&String>) {
+        self.withheld += 1;
+        match event.side {
+            Some(TradeSide::Buy) => self.buys += 1,
+            Some(TradeSide::Sell) => self.sells += 1,
+            None => {}
+        }
+        self.volume_usd += event.usd_value().unwrap_or_default();
+        if self.symbol.is_none() {
+            self.symbol = symbol.cloned();
+        }
+        self.last = Some((event.protocol, event.pool.clone(), event.slot));
+    }
+
+    /// Returns the digest event of withheld swaps, if any.
+    fn digest(self, scope: ThrottleScope, key: String, limit: u32) -> Option<SwapEvent> {
+        let (protocol, pool, slot) = self.last?;
+        let digest = ThrottleDigest {
+            scope,
+            key,
+            symbol: self.symbol,
+            limit,
+            window_secs: THROTTLE_WINDOW.as_secs(),
+            suppressed: self.withheld,
+            buys: self.buys,
+            sells: self.sells,
+            volume_usd: self.volume_usd,
+        };
+        Some(SwapEvent::from_throttle_digest(
+            protocol,
+            pool,
+            slot,
+            unix_now(),
+            digest,
+        ))
+    }
+}
+
+/// Per-token and per-pool swap alert limits.
+pub struct AlertThrottle {
+    config: ThrottleConfig,
+    windows: Mutex<HashMap<(ThrottleScope, String), Window>>,
+    /// Digests of windows that ended while their token or pool kept trading
+    pending: Mutex<Vec<SwapEvent>>,
+}
+
+impl AlertThrottle {
+    /// Creates a throttle with the given limits.
+    pub fn new(config: ThrottleConfig) -> Self {
+        Self {
+            config,
+            windows: Mutex::new(HashMap::new()),
+            pending: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Returns the limit of a scope.
+    fn limit(&self, scope: ThrottleScope) -> Option<u32> {
+        match scope {
+            ThrottleScope::Token => self.config.per_token,
+            ThrottleScope::Pool => self.config.per_pool,
+        }
+    }
+
+    /// Counts an alert for the token and pool of an event.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the event should be alerted on, `false` if its token or pool
+    /// exceeded its limit and the swap was added to that token's or pool's
+    /// digest. Only swaps are throttled.
+    pub fn admit(&self, event: &SwapEvent, now: Instant) -> bool {
+        if event.event_type != EventType::Swap {
+            return true;
+        }
+        let (base, quote) = event.get_base_quote_tokens();
+        let token = quote.or(base);
+        let keys: Vec<(ThrottleScope, String, u32)> = [
+            (ThrottleScope::Token, token.map(|token| token.mint.clone())),
+            (ThrottleScope::Pool, Some(event.pool.clone())),
+        ]
+        .into_iter()
+        .filter_map(|(scope, key)| Some((scope, key?, self.limit(scope)?)))
+        .collect();
+
+        let mut windows = self.windows.lock().unwrap_or_else(PoisonError::into_inner);
+        for (scope, key, limit) in &keys {
+            let window = windows
+                .entry((*scope, key.clone()))
+                .or_insert_with(|| Window::new(now));
+            if window.expired(now) {
+                let ended = std::mem::replace(window, Window::new(now));
+                if let Some(digest) = ended.digest(*scope, key.clone(), *limit) {
+                    self.pending
+                        .lock()
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .push(digest);
+                }
+            }
+        }
+
+        let over = keys
+            .iter()
+            .find(|(scope, key, limit)| windows[&(*scope, key.clone())].sent >= *limit);
+        match over {
+            Some((scope, key, _)) => {
+                let symbol = token.and_then(|token| token.symbol.as_ref());
+                let symbol = (*scope == ThrottleScope::Token).then_some(symbol).flatten();
+                if let Some(window) = windows.get_mut(&(*scope, key.clone())) {
+                    window.withhold(event, symbol);
+                }
+                false
+            }
+            None => {
+                for (scope, key, _) in &keys {
+                    if let Some(window) = windows.get_mut(&(*scope, key.clone())) {
+                        window.sent = window.sent.saturating_add(1);
+                    }
+                }
+                true
+            }
+        }
+    }
+
+    /// Takes the digests of windows that ended by `now`, most withheld swaps
+    /// first, and forgets idle tokens and pools.
+    pub fn take_digests(&self, now: Instant) -> Vec<SwapEvent> {
+        let mut digests =
+            std::mem::take(&mut *self.pending.lock().unwrap_or_else(PoisonError::into_inner));
+
+        let mut windows = self.windows.lock().unwrap_or_else(PoisonError::into_inner);
+        let expired: Vec<(ThrottleScope, String)> = windows
+            .iter()
+            .filter(|(_, window)| window.expired(now))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for (scope, key) in expired {
+            let Some(limit) = self.limit(scope) else {
+                continue;
+            };
+            if let Some(digest) = windows
+                .remove(&(scope, key.clone()))
+                .and_then(|window| window.digest(scope, key, limit))
+            {
+                digests.push(digest);
+            }
+        }
+        digests.sort_by_key(|event| {
+            std::cmp::Reverse(event.throttled.as_ref().map_or(0, |t| t.suppressed))
+        });
+        digests
+    }
+
+    /// Spawns the task emitting the digests of ended windows.
+    ///
+    /// Digests are logged in `output_format` and queued for the webhooks.
+    pub fn spawn_digests(
+        self: Arc<Self>,
+        output_format: OutputFormat,
+        webhook_notifier: Option<Arc<WebhookNotifier>>,
+    ) {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(THROTTLE_WINDOW);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            // The first tick completes immediately
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                for event in self.take_digests(clock::now()) {
+                    log::info!("{}", event.format(output_format));
+                    if let Some(ref notifier) = webhook_notifier {
+                        if let Err(e) = notifier.try_send(event) {
+                            log::warn!("Failed to queue throttle digest: {e}");
+                        }
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::output::{swap_event::WSOL_MINT, TokenInfo},
+    };
+
+    fn swap(pool: &str, mint: &str, usd: f64) -> SwapEvent {
+        SwapEvent::builder()
+            .protocol(Protocol::Cpmm)
+            .signature("sig")
+            .pool(pool)
+            .input_token(TokenInfo::new(WSOL_MINT, 1).with_usd_value(usd))
+            .output_token(TokenInfo::new(mint, 1).with_symbol("BONK"))
+            .slot(7)
+            .build()
+    }
+
+    fn digest(event: &SwapEvent) -> &ThrottleDigest {
+        event.throttled.as_ref().unwrap()
+    }
+
+    #[test]
+    fn test_limits_token_across_pools_and_digests_overflow() {
+        let throttle = AlertThrottle::new(ThrottleConfig {
+            per_token: Some(2),
+            per_pool: None,
+        });
+        let start = Instant::now();
+
+        assert!(throttle.admit(&swap("P1", "bonk", 10.0), start));
+        assert!(throttle.admit(&swap("P2", "bonk", 10.0), start));
+        assert!(!throttle.admit(&swap("P1", "bonk", 10.0), start));
+        assert!(!throttle.admit(&swap("P2", "bonk", 5.0), start));
+        // Other tokens keep their own limit
+        assert!(throttle.admit(&swap("P1", "wif", 10.0), start));
+
+        assert!(throttle.take_digests(start).is_empty());
+
+        let digests = throttle.take_digests(start + THROTTLE_WINDOW);
+        assert_eq!(digests.len(), 1);
+        let event = &digests[0];
+        assert_eq!(event.event_type, EventType::ThrottleDigest);
+        assert_eq!(event.pool, "P2");
+        let digest = digest(event);
+        assert_eq!(
+            (digest.scope, digest.key.as_str(), digest.symbol.as_deref()),
+            (ThrottleScope::Token, "bonk", Some("BONK"))
+        );
+        assert_eq!((digest.suppressed, digest.buys), (2, 2));
+        assert_eq!(digest.volume_usd, 15.0);
+
+        // A new window starts with a fresh limit
+        assert!(throttle.admit(&swap("P1", "bonk", 10.0), start + THROTTLE_WINDOW));
+    }
+
+    #[test]
+    fn test_pool_limit_and_unthrottled_events() {
+        let throttle = AlertThrottle::new(ThrottleConfig {
+            per_token: Some(10),
+            per_pool: Some(1),
+        });
+        let start = Instant::now();
+        let mut create = swap("P1", "bonk", 0.0);
+        create.event_type = EventType::CreatePool;
+
+        assert!(throttle.admit(&swap("P1", "bonk", 10.0), start));
+        assert!(!throttle.admit(&swap("P1", "bonk", 10.0), start));
+        assert!(throttle.admit(&create, start));
+        assert!(throttle.admit(&swap("P2", "bonk", 10.0), start));
+        // The window ended by a new swap is digested on the next take
+        assert!(throttle.admit(&swap("P1", "bonk", 10.0), start + THROTTLE_WINDOW));
+
+        let digests = throttle.take_digests(start + THROTTLE_WINDOW);
+        assert_eq!(digests.len(), 1);
+        let digest = digest(&digests[0]);
+        assert_eq!(
+            (digest.scope, digest.key.as_str(), digest.symbol.as_deref()),
+            (ThrottleScope::Pool, "P1", None)
+        );
+        assert_eq!(digest.suppressed, 1);
+    }
+}
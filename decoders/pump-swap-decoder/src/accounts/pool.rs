@@ -0,0 +1,19 @@
+use carbon_core::{borsh, CarbonDeserialize};
+
+#[derive(
+    CarbonDeserialize, Debug, serde::Deserialize, serde::Serialize, PartialEq, Eq, Clone, Hash,
+)]
+#[carbon(discriminator = "0xf19a6d0411b16dbc")]
+pub struct Pool {
+    pub pool_bump: u8,
+    pub index: u16,
+    pub creator: solana_pubkey::Pubkey,
+    pub base_mint: solana_pubkey::Pubkey,
+    pub quote_mint: solana_pubkey::Pubkey,
+    pub lp_mint: solana_pubkey::Pubkey,
+    pub pool_base_token_account: solana_pubkey::Pubkey,
+    pub pool_quote_token_account: solana_pubkey::Pubkey,
+    pub lp_supply: u64,
+    pub coin_creator: solana_pubkey::Pubkey,
+    pub is_mayhem_mode: bool,
+}
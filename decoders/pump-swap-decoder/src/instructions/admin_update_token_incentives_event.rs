@@ -0,0 +1,15 @@
+use carbon_core::{borsh, CarbonDeserialize};
+
+#[derive(
+    CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash,
+)]
+#[carbon(discriminator = "0xe445a52e51cb9a1d93fa6c78f71d43de")]
+pub struct AdminUpdateTokenIncentivesEvent {
+    pub start_time: i64,
+    pub end_time: i64,
+    pub day_number: u64,
+    pub token_supply_per_day: u64,
+    pub mint: solana_pubkey::Pubkey,
+    pub seconds_in_a_day: i64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,11 @@
+use carbon_core::{borsh, CarbonDeserialize};
+
+#[derive(
+    CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash,
+)]
+#[carbon(discriminator = "0xe445a52e51cb9a1de198ab57f63f42ea")]
+pub struct UpdateAdminEvent {
+    pub timestamp: i64,
+    pub admin: solana_pubkey::Pubkey,
+    pub new_admin: solana_pubkey::Pubkey,
+}
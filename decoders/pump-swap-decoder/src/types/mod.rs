@@ -0,0 +1,56 @@
+pub mod admin_set_coin_creator_event;
+pub use admin_set_coin_creator_event::*;
+pub mod admin_update_token_incentives_event;
+pub use admin_update_token_incentives_event::*;
+pub mod bonding_curve;
+pub use bonding_curve::*;
+pub mod buy_event;
+pub use buy_event::*;
+pub mod claim_token_incentives_event;
+pub use claim_token_incentives_event::*;
+pub mod close_user_volume_accumulator_event;
+pub use close_user_volume_accumulator_event::*;
+pub mod collect_coin_creator_fee_event;
+pub use collect_coin_creator_fee_event::*;
+pub mod create_config_event;
+pub use create_config_event::*;
+pub mod create_pool_event;
+pub use create_pool_event::*;
+pub mod deposit_event;
+pub use deposit_event::*;
+pub mod disable_event;
+pub use disable_event::*;
+pub mod extend_account_event;
+pub use extend_account_event::*;
+pub mod fee_config;
+pub use fee_config::*;
+pub mod fee_tier;
+pub use fee_tier::*;
+pub mod fees;
+pub use fees::*;
+pub mod global_config;
+pub use global_config::*;
+pub mod global_volume_accumulator;
+pub use global_volume_accumulator::*;
+pub mod init_user_volume_accumulator_event;
+pub use init_user_volume_accumulator_event::*;
+pub mod option_bool;
+pub use option_bool::*;
+pub mod pool;
+pub use pool::*;
+pub mod sell_event;
+pub use sell_event::*;
+pub mod set_bonding_curve_coin_creator_event;
+pub use set_bonding_curve_coin_creator_event::*;
+pub mod set_metaplex_coin_creator_event;
+pub use set_metaplex_coin_creator_event::*;
+pub mod sync_user_volume_accumulator_event;
+pub use sync_user_volume_accumulator_event::*;
+pub mod update_admin_event;
+pub use update_admin_event::*;
+pub mod update_fee_config_event;
+pub use update_fee_config_event::*;
+pub mod user_volume_accumulator;
+pub use user_volume_accumulator::*;
+pub mod withdraw_event;
+pub use withdraw_event::*;
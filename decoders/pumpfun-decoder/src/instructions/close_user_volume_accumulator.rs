@@ -0,0 +1,36 @@
+use carbon_core::{account_utils::next_account, borsh, CarbonDeserialize};
+
+#[derive(
+    CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash,
+)]
+#[carbon(discriminator = "0xf945a4da9667548a")]
+pub struct CloseUserVolumeAccumulator {}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, serde::Serialize, serde::Deserialize)]
+pub struct CloseUserVolumeAccumulatorInstructionAccounts {
+    pub user: solana_pubkey::Pubkey,
+    pub user_volume_accumulator: solana_pubkey::Pubkey,
+    pub event_authority: solana_pubkey::Pubkey,
+    pub program: solana_pubkey::Pubkey,
+}
+
+impl carbon_core::deserialize::ArrangeAccounts for CloseUserVolumeAccumulator {
+    type ArrangedAccounts = CloseUserVolumeAccumulatorInstructionAccounts;
+
+    fn arrange_accounts(
+        accounts: &[solana_instruction::AccountMeta],
+    ) -> Option<Self::ArrangedAccounts> {
+        let mut iter = accounts.iter();
+        let user = next_account(&mut iter)?;
+        let user_volume_accumulator = next_account(&mut iter)?;
+        let event_authority = next_account(&mut iter)?;
+        let program = next_account(&mut iter)?;
+
+        Some(CloseUserVolumeAccumulatorInstructionAccounts {
+            user,
+            user_volume_accumulator,
+            event_authority,
+            program,
+        })
+    }
+}
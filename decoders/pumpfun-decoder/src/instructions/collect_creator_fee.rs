@@ -0,0 +1,39 @@
+use carbon_core::{account_utils::next_account, borsh, CarbonDeserialize};
+
+#[derive(
+    CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash,
+)]
+#[carbon(discriminator = "0x1416567bc61cdb84")]
+pub struct CollectCreatorFee {}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, serde::Serialize, serde::Deserialize)]
+pub struct CollectCreatorFeeInstructionAccounts {
+    pub creator: solana_pubkey::Pubkey,
+    pub creator_vault: solana_pubkey::Pubkey,
+    pub system_program: solana_pubkey::Pubkey,
+    pub event_authority: solana_pubkey::Pubkey,
+    pub program: solana_pubkey::Pubkey,
+}
+
+impl carbon_core::deserialize::ArrangeAccounts for CollectCreatorFee {
+    type ArrangedAccounts = CollectCreatorFeeInstructionAccounts;
+
+    fn arrange_accounts(
+        accounts: &[solana_instruction::AccountMeta],
+    ) -> Option<Self::ArrangedAccounts> {
+        let mut iter = accounts.iter();
+        let creator = next_account(&mut iter)?;
+        let creator_vault = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let event_authority = next_account(&mut iter)?;
+        let program = next_account(&mut iter)?;
+
+        Some(CollectCreatorFeeInstructionAccounts {
+            creator,
+            creator_vault,
+            system_program,
+            event_authority,
+            program,
+        })
+    }
+}
@@ -0,0 +1,71 @@
+use carbon_core::{account_utils::next_account, borsh, CarbonDeserialize};
+
+#[derive(
+    CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash,
+)]
+#[carbon(discriminator = "0x181ec828051c0777")]
+pub struct Create {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub creator: solana_pubkey::Pubkey,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, serde::Serialize, serde::Deserialize)]
+pub struct CreateInstructionAccounts {
+    pub mint: solana_pubkey::Pubkey,
+    pub mint_authority: solana_pubkey::Pubkey,
+    pub bonding_curve: solana_pubkey::Pubkey,
+    pub associated_bonding_curve: solana_pubkey::Pubkey,
+    pub global: solana_pubkey::Pubkey,
+    pub mpl_token_metadata: solana_pubkey::Pubkey,
+    pub metadata: solana_pubkey::Pubkey,
+    pub user: solana_pubkey::Pubkey,
+    pub system_program: solana_pubkey::Pubkey,
+    pub token_program: solana_pubkey::Pubkey,
+    pub associated_token_program: solana_pubkey::Pubkey,
+    pub rent: solana_pubkey::Pubkey,
+    pub event_authority: solana_pubkey::Pubkey,
+    pub program: solana_pubkey::Pubkey,
+}
+
+impl carbon_core::deserialize::ArrangeAccounts for Create {
+    type ArrangedAccounts = CreateInstructionAccounts;
+
+    fn arrange_accounts(
+        accounts: &[solana_instruction::AccountMeta],
+    ) -> Option<Self::ArrangedAccounts> {
+        let mut iter = accounts.iter();
+        let mint = next_account(&mut iter)?;
+        let mint_authority = next_account(&mut iter)?;
+        let bonding_curve = next_account(&mut iter)?;
+        let associated_bonding_curve = next_account(&mut iter)?;
+        let global = next_account(&mut iter)?;
+        let mpl_token_metadata = next_account(&mut iter)?;
+        let metadata = next_account(&mut iter)?;
+        let user = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+        let associated_token_program = next_account(&mut iter)?;
+        let rent = next_account(&mut iter)?;
+        let event_authority = next_account(&mut iter)?;
+        let program = next_account(&mut iter)?;
+
+        Some(CreateInstructionAccounts {
+            mint,
+            mint_authority,
+            bonding_curve,
+            associated_bonding_curve,
+            global,
+            mpl_token_metadata,
+            metadata,
+            user,
+            system_program,
+            token_program,
+            associated_token_program,
+            rent,
+            event_authority,
+            program,
+        })
+    }
+}
@@ -0,0 +1,39 @@
+use carbon_core::{account_utils::next_account, borsh, CarbonDeserialize};
+
+#[derive(
+    CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash,
+)]
+#[carbon(discriminator = "0xe3b54ac4d01561d5")]
+pub struct UpdateGlobalAuthority {}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, serde::Serialize, serde::Deserialize)]
+pub struct UpdateGlobalAuthorityInstructionAccounts {
+    pub global: solana_pubkey::Pubkey,
+    pub authority: solana_pubkey::Pubkey,
+    pub new_authority: solana_pubkey::Pubkey,
+    pub event_authority: solana_pubkey::Pubkey,
+    pub program: solana_pubkey::Pubkey,
+}
+
+impl carbon_core::deserialize::ArrangeAccounts for UpdateGlobalAuthority {
+    type ArrangedAccounts = UpdateGlobalAuthorityInstructionAccounts;
+
+    fn arrange_accounts(
+        accounts: &[solana_instruction::AccountMeta],
+    ) -> Option<Self::ArrangedAccounts> {
+        let mut iter = accounts.iter();
+        let global = next_account(&mut iter)?;
+        let authority = next_account(&mut iter)?;
+        let new_authority = next_account(&mut iter)?;
+        let event_authority = next_account(&mut iter)?;
+        let program = next_account(&mut iter)?;
+
+        Some(UpdateGlobalAuthorityInstructionAccounts {
+            global,
+            authority,
+            new_authority,
+            event_authority,
+            program,
+        })
+    }
+}
@@ -0,0 +1,12 @@
+use carbon_core::{borsh, CarbonDeserialize};
+
+#[derive(
+    CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash,
+)]
+#[carbon(discriminator = "0xe445a52e51cb9a1db6c3892a23cecff7")]
+pub struct UpdateGlobalAuthorityEvent {
+    pub global: solana_pubkey::Pubkey,
+    pub authority: solana_pubkey::Pubkey,
+    pub new_authority: solana_pubkey::Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,11 @@
+extern crate alloc;
+
+use solana_pubkey::Pubkey;
+
+pub struct PumpfunDecoder;
+pub mod accounts;
+pub mod instructions;
+pub mod types;
+
+pub const PROGRAM_ID: Pubkey =
+    Pubkey::from_str_const("6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P");
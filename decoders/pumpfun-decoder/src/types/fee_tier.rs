@@ -0,0 +1,11 @@
+use super::*;
+
+use carbon_core::{borsh, CarbonDeserialize};
+
+#[derive(
+    CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash,
+)]
+pub struct FeeTier {
+    pub market_cap_lamports_threshold: u128,
+    pub fees: Fees,
+}
@@ -0,0 +1,50 @@
+pub mod admin_set_creator_event;
+pub use admin_set_creator_event::*;
+pub mod admin_set_idl_authority_event;
+pub use admin_set_idl_authority_event::*;
+pub mod admin_update_token_incentives_event;
+pub use admin_update_token_incentives_event::*;
+pub mod bonding_curve;
+pub use bonding_curve::*;
+pub mod claim_token_incentives_event;
+pub use claim_token_incentives_event::*;
+pub mod close_user_volume_accumulator_event;
+pub use close_user_volume_accumulator_event::*;
+pub mod collect_creator_fee_event;
+pub use collect_creator_fee_event::*;
+pub mod complete_event;
+pub use complete_event::*;
+pub mod complete_pump_amm_migration_event;
+pub use complete_pump_amm_migration_event::*;
+pub mod create_event;
+pub use create_event::*;
+pub mod extend_account_event;
+pub use extend_account_event::*;
+pub mod fee_config;
+pub use fee_config::*;
+pub mod fee_tier;
+pub use fee_tier::*;
+pub mod fees;
+pub use fees::*;
+pub mod global;
+pub use global::*;
+pub mod global_volume_accumulator;
+pub use global_volume_accumulator::*;
+pub mod init_user_volume_accumulator_event;
+pub use init_user_volume_accumulator_event::*;
+pub mod option_bool;
+pub use option_bool::*;
+pub mod set_creator_event;
+pub use set_creator_event::*;
+pub mod set_metaplex_creator_event;
+pub use set_metaplex_creator_event::*;
+pub mod set_params_event;
+pub use set_params_event::*;
+pub mod sync_user_volume_accumulator_event;
+pub use sync_user_volume_accumulator_event::*;
+pub mod trade_event;
+pub use trade_event::*;
+pub mod update_global_authority_event;
+pub use update_global_authority_event::*;
+pub mod user_volume_accumulator;
+pub use user_volume_accumulator::*;
@@ -0,0 +1,11 @@
+use carbon_core::{borsh, CarbonDeserialize};
+
+#[derive(
+    CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash,
+)]
+pub struct UpdateGlobalAuthorityEvent {
+    pub global: solana_pubkey::Pubkey,
+    pub authority: solana_pubkey::Pubkey,
+    pub new_authority: solana_pubkey::Pubkey,
+    pub timestamp: i64,
+}
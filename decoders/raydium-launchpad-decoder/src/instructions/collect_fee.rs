@@ -0,0 +1,48 @@
+use carbon_core::{account_utils::next_account, borsh, CarbonDeserialize};
+
+#[derive(
+    CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash,
+)]
+#[carbon(discriminator = "0x3cadf767045d8230")]
+pub struct CollectFee {}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, serde::Serialize, serde::Deserialize)]
+pub struct CollectFeeInstructionAccounts {
+    pub owner: solana_pubkey::Pubkey,
+    pub authority: solana_pubkey::Pubkey,
+    pub pool_state: solana_pubkey::Pubkey,
+    pub global_config: solana_pubkey::Pubkey,
+    pub quote_vault: solana_pubkey::Pubkey,
+    pub quote_mint: solana_pubkey::Pubkey,
+    pub recipient_token_account: solana_pubkey::Pubkey,
+    pub token_program: solana_pubkey::Pubkey,
+}
+
+impl carbon_core::deserialize::ArrangeAccounts for CollectFee {
+    type ArrangedAccounts = CollectFeeInstructionAccounts;
+
+    fn arrange_accounts(
+        accounts: &[solana_instruction::AccountMeta],
+    ) -> Option<Self::ArrangedAccounts> {
+        let mut iter = accounts.iter();
+        let owner = next_account(&mut iter)?;
+        let authority = next_account(&mut iter)?;
+        let pool_state = next_account(&mut iter)?;
+        let global_config = next_account(&mut iter)?;
+        let quote_vault = next_account(&mut iter)?;
+        let quote_mint = next_account(&mut iter)?;
+        let recipient_token_account = next_account(&mut iter)?;
+        let token_program = next_account(&mut iter)?;
+
+        Some(CollectFeeInstructionAccounts {
+            owner,
+            authority,
+            pool_state,
+            global_config,
+            quote_vault,
+            quote_mint,
+            recipient_token_account,
+            token_program,
+        })
+    }
+}
@@ -0,0 +1,85 @@
+use super::super::types::*;
+
+use carbon_core::{account_utils::next_account, borsh, CarbonDeserialize};
+
+#[derive(
+    CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash,
+)]
+#[carbon(discriminator = "0x4399af27da102620")]
+pub struct InitializeV2 {
+    pub base_mint_param: MintParams,
+    pub curve_param: CurveParams,
+    pub vesting_param: VestingParams,
+    pub amm_fee_on: AmmCreatorFeeOn,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash, serde::Serialize, serde::Deserialize)]
+pub struct InitializeV2InstructionAccounts {
+    pub payer: solana_pubkey::Pubkey,
+    pub creator: solana_pubkey::Pubkey,
+    pub global_config: solana_pubkey::Pubkey,
+    pub platform_config: solana_pubkey::Pubkey,
+    pub authority: solana_pubkey::Pubkey,
+    pub pool_state: solana_pubkey::Pubkey,
+    pub base_mint: solana_pubkey::Pubkey,
+    pub quote_mint: solana_pubkey::Pubkey,
+    pub base_vault: solana_pubkey::Pubkey,
+    pub quote_vault: solana_pubkey::Pubkey,
+    pub metadata_account: solana_pubkey::Pubkey,
+    pub base_token_program: solana_pubkey::Pubkey,
+    pub quote_token_program: solana_pubkey::Pubkey,
+    pub metadata_program: solana_pubkey::Pubkey,
+    pub system_program: solana_pubkey::Pubkey,
+    pub rent_program: solana_pubkey::Pubkey,
+    pub event_authority: solana_pubkey::Pubkey,
+    pub program: solana_pubkey::Pubkey,
+}
+
+impl carbon_core::deserialize::ArrangeAccounts for InitializeV2 {
+    type ArrangedAccounts = InitializeV2InstructionAccounts;
+
+    fn arrange_accounts(
+        accounts: &[solana_instruction::AccountMeta],
+    ) -> Option<Self::ArrangedAccounts> {
+        let mut iter = accounts.iter();
+        let payer = next_account(&mut iter)?;
+        let creator = next_account(&mut iter)?;
+        let global_config = next_account(&mut iter)?;
+        let platform_config = next_account(&mut iter)?;
+        let authority = next_account(&mut iter)?;
+        let pool_state = next_account(&mut iter)?;
+        let base_mint = next_account(&mut iter)?;
+        let quote_mint = next_account(&mut iter)?;
+        let base_vault = next_account(&mut iter)?;
+        let quote_vault = next_account(&mut iter)?;
+        let metadata_account = next_account(&mut iter)?;
+        let base_token_program = next_account(&mut iter)?;
+        let quote_token_program = next_account(&mut iter)?;
+        let metadata_program = next_account(&mut iter)?;
+        let system_program = next_account(&mut iter)?;
+        let rent_program = next_account(&mut iter)?;
+        let event_authority = next_account(&mut iter)?;
+        let program = next_account(&mut iter)?;
+
+        Some(InitializeV2InstructionAccounts {
+            payer,
+            creator,
+            global_config,
+            platform_config,
+            authority,
+            pool_state,
+            base_mint,
+            quote_mint,
+            base_vault,
+            quote_vault,
+            metadata_account,
+            base_token_program,
+            quote_token_program,
+            metadata_program,
+            system_program,
+            rent_program,
+            event_authority,
+            program,
+        })
+    }
+}
@@ -0,0 +1,10 @@
+use alloc::format;
+use carbon_core::{borsh, CarbonDeserialize};
+
+#[derive(
+    CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash,
+)]
+pub enum AmmCreatorFeeOn {
+    QuoteToken,
+    BothToken,
+}
@@ -0,0 +1,10 @@
+use carbon_core::{borsh, CarbonDeserialize};
+
+#[derive(
+    CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash,
+)]
+pub struct ClaimVestedEvent {
+    pub pool_state: solana_pubkey::Pubkey,
+    pub beneficiary: solana_pubkey::Pubkey,
+    pub claim_amount: u64,
+}
@@ -0,0 +1,10 @@
+use carbon_core::{borsh, CarbonDeserialize};
+
+#[derive(
+    CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash,
+)]
+pub struct MigrateNftInfo {
+    pub platform_scale: u64,
+    pub creator_scale: u64,
+    pub burn_scale: u64,
+}
@@ -0,0 +1,50 @@
+pub mod amm_creator_fee_on;
+pub use amm_creator_fee_on::*;
+pub mod bonding_curve_param;
+pub use bonding_curve_param::*;
+pub mod claim_vested_event;
+pub use claim_vested_event::*;
+pub mod constant_curve;
+pub use constant_curve::*;
+pub mod create_vesting_event;
+pub use create_vesting_event::*;
+pub mod curve_params;
+pub use curve_params::*;
+pub mod fixed_curve;
+pub use fixed_curve::*;
+pub mod global_config;
+pub use global_config::*;
+pub mod linear_curve;
+pub use linear_curve::*;
+pub mod migrate_nft_info;
+pub use migrate_nft_info::*;
+pub mod mint_params;
+pub use mint_params::*;
+pub mod platform_config;
+pub use platform_config::*;
+pub mod platform_config_info;
+pub use platform_config_info::*;
+pub mod platform_config_param;
+pub use platform_config_param::*;
+pub mod platform_curve_param;
+pub use platform_curve_param::*;
+pub mod platform_params;
+pub use platform_params::*;
+pub mod pool_create_event;
+pub use pool_create_event::*;
+pub mod pool_state;
+pub use pool_state::*;
+pub mod pool_status;
+pub use pool_status::*;
+pub mod trade_direction;
+pub use trade_direction::*;
+pub mod trade_event;
+pub use trade_event::*;
+pub mod transfer_fee_extension_params;
+pub use transfer_fee_extension_params::*;
+pub mod vesting_params;
+pub use vesting_params::*;
+pub mod vesting_record;
+pub use vesting_record::*;
+pub mod vesting_schedule;
+pub use vesting_schedule::*;
@@ -0,0 +1,16 @@
+use super::*;
+
+use alloc::string::String;
+use carbon_core::{borsh, CarbonDeserialize};
+
+#[derive(
+    CarbonDeserialize, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Hash,
+)]
+pub struct PlatformParams {
+    pub migrate_nft_info: MigrateNftInfo,
+    pub fee_rate: u64,
+    pub name: String,
+    pub web: String,
+    pub img: String,
+    pub creator_fee_rate: u64,
+}